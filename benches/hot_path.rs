@@ -0,0 +1,79 @@
+//! Micro-benchmarks for the per-request work in `execute_transaction`.
+//!
+//! Run with `cargo bench --bench hot_path [filter]`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use std::time::Duration;
+
+use ecash_sdk_core::agent::{select_best_route, RouteQuote};
+use ecash_sdk_core::cache::Cache;
+use ecash_sdk_core::monitoring::Metrics;
+use ecash_sdk_core::{receipt, validator, ChainId, IntentType, TransactionRequest};
+
+fn request() -> TransactionRequest {
+    TransactionRequest::new("ref_bench_001", IntentType::Transfer, "5000.00", "USDC", ChainId::Base)
+        .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+        .with_target_chain(ChainId::Ethereum)
+        .with_shielded(true)
+}
+
+fn quotes() -> Vec<RouteQuote> {
+    (0..8)
+        .map(|i| RouteQuote {
+            agent_id: format!("agent-{:03}", i),
+            estimated_fee: format!("0.0{} USDC", i + 1),
+            estimated_time: Duration::from_secs(10 + 5 * i),
+            route: vec!["base".to_string(), "ethereum".to_string()],
+            security_score: 0.90 + i as f64 / 100.0,
+            estimated_fee_usd: None,
+            signature: None,
+            agent_pubkey: None,
+            region: None,
+        })
+        .collect()
+}
+
+fn validation(c: &mut Criterion) {
+    let req = request();
+    c.bench_function("validation", |b| {
+        b.iter(|| validator::validate_transaction_request(black_box(&req)).unwrap())
+    });
+}
+
+fn canonical_hash(c: &mut Criterion) {
+    let req = request();
+    c.bench_function("canonical_hash", |b| b.iter(|| receipt::intent_hash(black_box(&req))));
+}
+
+fn route_scoring(c: &mut Criterion) {
+    let quotes = quotes();
+    let mut group = c.benchmark_group("route_scoring");
+    for preference in ["speed", "cost", "security", "balanced"] {
+        group.bench_function(preference, |b| {
+            b.iter(|| select_best_route(black_box(&quotes), preference).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn metrics_record(c: &mut Criterion) {
+    let metrics = Metrics::new();
+    c.bench_function("metrics_record", |b| {
+        b.iter(|| metrics.record_transaction(true, black_box(0.05), Duration::from_millis(120)))
+    });
+}
+
+fn cache_lookup(c: &mut Criterion) {
+    // `Cache::new` spawns its cleanup task, so it needs a runtime
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let cache = runtime.block_on(async { Cache::new(Duration::from_secs(60)) });
+    cache.set("base:ethereum:USDC".to_string(), quotes());
+    let mut group = c.benchmark_group("cache_lookup");
+    group.bench_function("hit", |b| b.iter(|| cache.get(black_box("base:ethereum:USDC"))));
+    group.bench_function("miss", |b| b.iter(|| cache.get(black_box("base:solana:USDC"))));
+    group.finish();
+}
+
+criterion_group!(benches, validation, canonical_hash, route_scoring, metrics_record, cache_lookup);
+criterion_main!(benches);
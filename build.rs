@@ -0,0 +1,13 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // Server and client for the gRPC API, using a bundled protoc
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("no bundled protoc for this platform");
+        std::env::set_var("PROTOC", protoc);
+        tonic_prost_build::configure()
+            .compile_protos(&["proto/ecash/v1/transaction.proto"], &["proto"])
+            .expect("failed to compile proto/ecash/v1/transaction.proto");
+    }
+}
@@ -0,0 +1,9 @@
+//! Prints the OpenAPI 3.1 document describing the SDK's wire types.
+//!
+//! cargo run --example openapi --features schema > ecash-sdk.openapi.json
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let doc = ecash_sdk_core::schema::openapi();
+    println!("{}", serde_json::to_string_pretty(&doc)?);
+    Ok(())
+}
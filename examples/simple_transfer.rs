@@ -14,16 +14,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 EasyCash SDK Initialized (Advanced Mode)");
 
     // 2. Define a Shielded Transfer Request
-    let req = TransactionRequest {
-        reference_id: "ref_pay_salary_001".to_string(),
-        intent_type: IntentType::Transfer,
-        amount: "5000.00".to_string(),
-        asset: "USDC".to_string(),
-        recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-        source_chain: ChainId::Base,
-        target_chain: None,
-        is_shielded: true, // Enable ZK Privacy
-    };
+    let req = TransactionRequest::new("ref_pay_salary_001", IntentType::Transfer, "5000.00", "USDC", ChainId::Base)
+        .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+        .with_shielded(true);
 
     // 3. Execute
     println!(
@@ -0,0 +1,16 @@
+//! Amount and address validation must reject, never panic on, arbitrary input.
+#![no_main]
+
+use ecash_sdk_core::validator;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        if validator::validate_amount(s).is_ok() {
+            // Anything accepted must be a plain positive ASCII decimal
+            assert!(s.bytes().all(|b| b.is_ascii_digit() || b == b'.'));
+            assert!(s.parse::<f64>().unwrap() > 0.0);
+        }
+        let _ = validator::validate_address(s);
+    }
+});
@@ -0,0 +1,20 @@
+//! Ingest path: JSON body -> TransactionRequest -> validation -> intent hash.
+#![no_main]
+
+use ecash_sdk_core::receipt::intent_hash;
+use ecash_sdk_core::types::TransactionRequest;
+use ecash_sdk_core::validator;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(req) = serde_json::from_slice::<TransactionRequest>(data) else {
+        return;
+    };
+    let _ = validator::validate_transaction_request(&req);
+
+    // Canonical serialization: re-encoding must not change the intent hash
+    let hash = intent_hash(&req);
+    let json = serde_json::to_vec(&req).expect("request serializes");
+    let decoded: TransactionRequest = serde_json::from_slice(&json).expect("request round-trips");
+    assert_eq!(intent_hash(&decoded), hash);
+});
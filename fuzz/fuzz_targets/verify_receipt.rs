@@ -0,0 +1,14 @@
+//! Receipts arrive from third parties; parsing and verifying must not panic.
+#![no_main]
+
+use ecash_sdk_core::crypto::TransactionSigner;
+use ecash_sdk_core::receipt::{verify_receipt, SignedReceipt};
+use k256::SecretKey;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let signer = TransactionSigner::new(SecretKey::from_bytes(&[7u8; 32].into()).unwrap());
+    if let Ok(receipt) = serde_json::from_slice::<SignedReceipt>(data) {
+        let _ = verify_receipt(&receipt, &signer.verifying_key());
+    }
+});
@@ -0,0 +1,22 @@
+//! Signature verification must reject malformed signatures without panicking.
+#![no_main]
+
+use ecash_sdk_core::crypto::{self, TransactionSigner};
+use k256::SecretKey;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let signer = TransactionSigner::new(SecretKey::from_bytes(&[7u8; 32].into()).unwrap());
+    let key = signer.verifying_key();
+
+    // Split the input into a message and a (possibly malformed) hex signature
+    let split = data.first().map_or(0, |b| *b as usize).min(data.len());
+    let (message, signature) = data.split_at(split);
+    if let Ok(signature) = std::str::from_utf8(signature) {
+        let _ = crypto::verify_signature(&key, message, signature);
+    }
+    let _ = crypto::verify_signature(&key, message, &hex::encode(signature));
+
+    // Public key parsing is also fed untrusted hex
+    let _ = crypto::public_key_from_hex(&hex::encode(data));
+});
@@ -0,0 +1,77 @@
+/*
+ * C bindings for the EasyCash SDK.
+ *
+ * Generated from src/ffi.rs with cbindgen (`make header`); do not edit by hand.
+ */
+
+#ifndef ECASH_SDK_H
+#define ECASH_SDK_H
+
+#include <stdint.h>
+
+/* Call succeeded */
+#define ECASH_OK 0
+
+/* SDK error codes (see ErrorCode) */
+#define ECASH_ERR_INVALID_REQUEST 1
+#define ECASH_ERR_INSUFFICIENT_FUNDS 2
+#define ECASH_ERR_NETWORK_FAILURE 3
+#define ECASH_ERR_PROOF_GENERATION_FAILED 4
+#define ECASH_ERR_AGENT_UNAVAILABLE 5
+#define ECASH_ERR_TIMEOUT 6
+#define ECASH_ERR_RATE_LIMITED 7
+#define ECASH_ERR_FEE_TOO_HIGH 8
+#define ECASH_ERR_UNSUPPORTED_CHAIN 9
+#define ECASH_ERR_UNSUPPORTED_ASSET 10
+#define ECASH_ERR_DUPLICATE_REFERENCE 11
+#define ECASH_ERR_COMPLIANCE_REJECTED 12
+#define ECASH_ERR_POLICY_VIOLATION 13
+#define ECASH_ERR_EXPIRED 14
+#define ECASH_ERR_SIGNER_UNAVAILABLE 15
+#define ECASH_ERR_BUDGET_EXCEEDED 16
+#define ECASH_ERR_UNSUPPORTED_PROTOCOL 17
+#define ECASH_ERR_CANCELLED 18
+#define ECASH_ERR_EXECUTION_REVERTED 19
+
+/* A pointer argument was null or a string was not valid UTF-8 */
+#define ECASH_ERR_INVALID_ARGUMENT 100
+
+/* The SDK panicked; the client should be discarded */
+#define ECASH_ERR_INTERNAL 101
+
+/* Opaque client handle */
+typedef struct EcashClient EcashClient;
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Creates a client. `config_json` may be NULL for the default configuration,
+ * or a JSON object whose top-level keys override the defaults.
+ */
+int32_t ecash_client_new(const char *config_json, EcashClient **out_client);
+
+/* Releases a client created by ecash_client_new. */
+void ecash_client_free(EcashClient *client);
+
+/*
+ * Executes a TransactionRequest given as JSON, blocking until it completes.
+ * On success *out_json receives the TransactionResponse; on SDK errors it
+ * receives an SdkErrorResponse.
+ */
+int32_t ecash_execute_transaction_json(const EcashClient *client,
+                                       const char *request_json,
+                                       char **out_json);
+
+/* Writes the client's metrics snapshot as JSON to *out_json. */
+int32_t ecash_get_metrics_json(const EcashClient *client, char **out_json);
+
+/* Releases a string returned by the SDK. */
+void ecash_string_free(char *s);
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* ECASH_SDK_H */
@@ -0,0 +1,303 @@
+//! Approved recipient addresses.
+//!
+//! Operators register the addresses they pay out to, with a label and
+//! optional per-transaction limits per asset. Once the book is attached with
+//! `EasyCashClient::with_address_book`, every transfer to a registered address
+//! is held to that address's chain and limits. With
+//! `SdkConfig::require_allowlisted_recipients` set, transfers to any other
+//! address are refused, as custody deployments usually require.
+//!
+//! ```
+//! use ecash_sdk_core::address_book::{AddressBook, AddressEntry, InMemoryAddressStore};
+//! use ecash_sdk_core::ChainId;
+//! use std::sync::Arc;
+//!
+//! let book = AddressBook::new(Arc::new(InMemoryAddressStore::new()));
+//! book.add(
+//!     AddressEntry::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "Treasury cold wallet")
+//!         .on_chain(ChainId::Base)
+//!        .with_limit("USDC", "50000"),
+//! )
+//! .unwrap();
+//! assert_eq!(book.get("0x742d35cc6634c0532925a3b844bc9e7595f0beb0").unwrap().unwrap().label, "Treasury cold wallet");
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::types::{ChainId, TransactionRequest};
+
+/// Approved recipient address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressEntry {
+    pub address: String,
+    pub label: String,
+    /// Chain the address may receive on (any chain if `None`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainId>,
+    /// Largest amount per transaction, by upper-case asset symbol. Assets
+    /// without an entry are not limited.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_per_transaction: BTreeMap<String, String>,
+}
+
+impl AddressEntry {
+    pub fn new(address: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            label: label.into(),
+            chain: None,
+            max_per_transaction: BTreeMap::new(),
+        }
+    }
+
+    /// Restricts the address to one chain
+    pub fn on_chain(mut self, chain: ChainId) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Caps each transaction of `asset` to this address at `max_amount`
+    pub fn with_limit(mut self, asset: &str, max_amount: impl Into<String>) -> Self {
+        self.max_per_transaction.insert(asset.to_uppercase(), max_amount.into());
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.address.is_empty() {
+            return Err("address is required".to_string());
+        }
+        for (asset, max) in &self.max_per_transaction {
+            max.parse::<Amount>()
+                .map_err(|e| format!("invalid {} limit for {}: {}", asset, self.address, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Key addresses are stored under: EVM addresses are case-insensitive,
+/// other chains' addresses are compared exactly
+pub fn address_key(address: &str) -> String {
+    let address = address.trim();
+    if address.starts_with("0x") || address.starts_with("0X") {
+        address.to_ascii_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+/// Persistent storage of the address book, keyed by `address_key`
+pub trait AddressStore: Send + Sync {
+    fn get(&self, key: &str) -> Result<Option<AddressEntry>, String>;
+
+    /// Inserts or replaces an entry
+    fn put(&self, key: &str, entry: &AddressEntry) -> Result<(), String>;
+
+    /// Returns whether an entry was removed
+    fn remove(&self, key: &str) -> Result<bool, String>;
+
+    fn list(&self) -> Result<Vec<AddressEntry>, String>;
+}
+
+/// In-memory address store (per process)
+#[derive(Default)]
+pub struct InMemoryAddressStore {
+    entries: Mutex<HashMap<String, AddressEntry>>,
+}
+
+impl InMemoryAddressStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, AddressEntry>>, String> {
+        self.entries.lock().map_err(|_| "address store poisoned".to_string())
+    }
+}
+
+impl AddressStore for InMemoryAddressStore {
+    fn get(&self, key: &str) -> Result<Option<AddressEntry>, String> {
+        Ok(self.lock()?.get(key).cloned())
+    }
+
+    fn put(&self, key: &str, entry: &AddressEntry) -> Result<(), String> {
+        self.lock()?.insert(key.to_string(), entry.clone());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<bool, String> {
+        Ok(self.lock()?.remove(key).is_some())
+    }
+
+    fn list(&self) -> Result<Vec<AddressEntry>, String> {
+        let mut entries: Vec<_> = self.lock()?.values().cloned().collect();
+        entries.sort_by(|a, b| a.label.cmp(&b.label));
+        Ok(entries)
+    }
+}
+
+/// Why a transfer was refused by the address book
+#[derive(Debug, Clone, PartialEq)]
+pub enum AddressRejection {
+    /// The recipient is not registered and the allowlist is enforced
+    NotAllowlisted { address: String },
+    /// The recipient is registered for another chain
+    WrongChain { label: String, chain: ChainId },
+    /// The amount exceeds the recipient's per-transaction limit
+    LimitExceeded { label: String, max_amount: String },
+    /// The store failed; the transfer is refused to be safe
+    Unavailable(String),
+}
+
+impl AddressRejection {
+    /// Machine-readable reason, for error details
+    pub fn reason(&self) -> &'static str {
+        match self {
+            AddressRejection::NotAllowlisted { .. } => "recipient_not_allowlisted",
+            AddressRejection::WrongChain { .. } => "recipient_wrong_chain",
+            AddressRejection::LimitExceeded { .. } => "recipient_limit_exceeded",
+            AddressRejection::Unavailable(_) => "address_book_unavailable",
+        }
+    }
+}
+
+impl std::fmt::Display for AddressRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AddressRejection::NotAllowlisted { address } => write!(f, "recipient {} is not in the address book", address),
+            AddressRejection::WrongChain { label, chain } => {
+                write!(f, "recipient '{}' may only receive on {}", label, chain)
+            }
+            AddressRejection::LimitExceeded { label, max_amount } => {
+                write!(f, "amount exceeds the {} per-transaction limit of recipient '{}'", max_amount, label)
+            }
+            AddressRejection::Unavailable(e) => write!(f, "address book unavailable: {}", e),
+        }
+    }
+}
+
+/// Labelled allowlist of recipient addresses
+pub struct AddressBook {
+    store: Arc<dyn AddressStore>,
+}
+
+impl AddressBook {
+    pub fn new(store: Arc<dyn AddressStore>) -> Self {
+        Self { store }
+    }
+
+    /// Registers an address, replacing any entry for the same address
+    pub fn add(&self, entry: AddressEntry) -> Result<(), String> {
+        entry.validate()?;
+        self.store.put(&address_key(&entry.address), &entry)
+    }
+
+    /// Returns whether the address was registered
+    pub fn remove(&self, address: &str) -> Result<bool, String> {
+        self.store.remove(&address_key(address))
+    }
+
+    pub fn get(&self, address: &str) -> Result<Option<AddressEntry>, String> {
+        self.store.get(&address_key(address))
+    }
+
+    /// Every entry, sorted by label
+    pub fn list(&self) -> Result<Vec<AddressEntry>, String> {
+        self.store.list()
+    }
+
+    /// Checks a request's recipient against the book. Returns the matching
+    /// entry, or `None` for an unregistered recipient when `require_listed`
+    /// is off. Requests without a recipient pay the sender and always pass.
+    pub fn check(&self, req: &TransactionRequest, require_listed: bool) -> Result<Option<AddressEntry>, AddressRejection> {
+        let Some(ref recipient) = req.recipient else {
+            return Ok(None);
+        };
+        let entry = match self.get(recipient).map_err(AddressRejection::Unavailable)? {
+            Some(entry) => entry,
+            None if require_listed => {
+                return Err(AddressRejection::NotAllowlisted {
+                    address: recipient.clone(),
+                })
+            }
+            None => return Ok(None),
+        };
+
+        let destination = req.target_chain.unwrap_or(req.source_chain);
+        if let Some(chain) = entry.chain.filter(|c| *c != destination) {
+            return Err(AddressRejection::WrongChain {
+                label: entry.label,
+                chain,
+            });
+        }
+        if let Some(max) = entry.max_per_transaction.get(&req.asset.to_uppercase()) {
+            let max_amount: Amount = max.parse().map_err(AddressRejection::Unavailable)?;
+            // Unparseable amounts are rejected by validation before this check
+            if req.amount.parse::<Amount>().is_ok_and(|amount| amount > max_amount) {
+                return Err(AddressRejection::LimitExceeded {
+                    label: entry.label,
+                    max_amount: format!("{} {}", max, req.asset.to_uppercase()),
+                });
+            }
+        }
+        Ok(Some(entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::IntentType;
+
+    const TREASURY: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+
+    fn request(recipient: Option<&str>, amount: &str, target_chain: Option<ChainId>) -> TransactionRequest {
+        TransactionRequest {
+            recipient: recipient.map(str::to_string),
+            target_chain,
+            ..TransactionRequest::new("ref_001", IntentType::Transfer, amount, "usdc", ChainId::Base)
+        }
+    }
+
+    fn book() -> AddressBook {
+        let book = AddressBook::new(Arc::new(InMemoryAddressStore::new()));
+        book.add(AddressEntry::new(TREASURY, "Treasury").on_chain(ChainId::Base).with_limit("USDC", "1000"))
+            .unwrap();
+        book
+    }
+
+    #[test]
+    fn test_check_listed_recipient() {
+        let book = book();
+        let listed = request(Some(&TREASURY.to_lowercase()), "1000.00", None);
+        assert_eq!(book.check(&listed, true).unwrap().unwrap().label, "Treasury");
+
+        let over = request(Some(TREASURY), "1000.01", None);
+        assert_eq!(
+            book.check(&over, false).unwrap_err(),
+            AddressRejection::LimitExceeded {
+                label: "Treasury".to_string(),
+                max_amount: "1000 USDC".to_string(),
+            }
+        );
+        let bridged = request(Some(TREASURY), "10", Some(ChainId::Ethereum));
+        assert_eq!(book.check(&bridged, false).unwrap_err().reason(), "recipient_wrong_chain");
+    }
+
+    #[test]
+    fn test_unlisted_recipient_requires_allowlist_flag() {
+        let book = book();
+        let unlisted = request(Some("0x0000000000000000000000000000000000000001"), "10", None);
+        assert_eq!(book.check(&unlisted, false).unwrap(), None);
+        assert_eq!(book.check(&unlisted, true).unwrap_err().reason(), "recipient_not_allowlisted");
+        assert_eq!(book.check(&request(None, "10", None), true).unwrap(), None);
+
+        assert!(book.remove(TREASURY).unwrap());
+        assert!(book.check(&request(Some(TREASURY), "10", None), true).is_err());
+        assert!(book.list().unwrap().is_empty());
+        assert!(book.add(AddressEntry::new(TREASURY, "Treasury").with_limit("USDC", "lots")).is_err());
+    }
+}
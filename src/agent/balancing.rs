@@ -0,0 +1,213 @@
+//! Load balancing across equally ranked routes.
+//!
+//! When several quotes score identically, always taking the first
+//! concentrates load on one agent. `LoadBalancer` spreads such ties
+//! according to the configured `TieBreak` strategy.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use super::RouteQuote;
+
+/// How a route is chosen among equally ranked quotes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// The first quote returned by the negotiator
+    #[default]
+    First,
+    /// Agents in turn, ordered by agent ID
+    RoundRobin,
+    /// Randomly, weighted by each agent's configured capacity
+    WeightedRandom,
+    /// The agent selected least recently
+    LeastRecentlyUsed,
+}
+
+impl TieBreak {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TieBreak::First => "first",
+            TieBreak::RoundRobin => "round_robin",
+            TieBreak::WeightedRandom => "weighted_random",
+            TieBreak::LeastRecentlyUsed => "least_recently_used",
+        }
+    }
+}
+
+/// Configuration of tie-breaking between equally ranked routes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadBalancingConfig {
+    #[serde(default)]
+    pub strategy: TieBreak,
+    /// Relative capacity of each agent for `WeightedRandom`; unlisted agents
+    /// have a capacity of 1
+    #[serde(default)]
+    pub capacities: HashMap<String, u32>,
+}
+
+#[derive(Default)]
+struct BalancerState {
+    selections: u64,
+    last_selected: HashMap<String, u64>,
+}
+
+/// Chooses among equally ranked routes.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::agent::balancing::{LoadBalancer, LoadBalancingConfig, TieBreak};
+/// use ecash_sdk_core::agent::RouteQuote;
+/// use std::time::Duration;
+///
+/// let quote = |agent_id: &str| RouteQuote {
+///     agent_id: agent_id.to_string(),
+///     estimated_fee: "0.05 USDC".to_string(),
+///     estimated_time: Duration::from_secs(15),
+///     route: vec!["base".to_string()],
+///     security_score: 0.9,
+///     estimated_fee_usd: None,
+///     signature: None,
+///     agent_pubkey: None,
+///     region: None,
+/// };
+/// let (a, b) = (quote("agent-001"), quote("agent-002"));
+/// let balancer = LoadBalancer::new(LoadBalancingConfig { strategy: TieBreak::RoundRobin, ..Default::default() });
+///
+/// assert_eq!(balancer.pick(&[&a, &b]).agent_id, "agent-001");
+/// balancer.record_selection("agent-001");
+/// assert_eq!(balancer.pick(&[&a, &b]).agent_id, "agent-002");
+/// ```
+pub struct LoadBalancer {
+    config: LoadBalancingConfig,
+    state: Mutex<BalancerState>,
+}
+
+impl LoadBalancer {
+    pub fn new(config: LoadBalancingConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BalancerState::default()),
+        }
+    }
+
+    pub fn strategy(&self) -> TieBreak {
+        self.config.strategy
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BalancerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Picks one of `tied`, which must not be empty
+    pub fn pick<'a>(&self, tied: &[&'a RouteQuote]) -> &'a RouteQuote {
+        let first = tied[0];
+        if tied.len() == 1 {
+            return first;
+        }
+        match self.config.strategy {
+            TieBreak::First => first,
+            TieBreak::RoundRobin => {
+                let mut ordered = tied.to_vec();
+                ordered.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+                ordered[(self.lock().selections % ordered.len() as u64) as usize]
+            }
+            TieBreak::WeightedRandom => {
+                let weights: Vec<u64> = tied.iter().map(|q| self.capacity(&q.agent_id)).collect();
+                let total: u64 = weights.iter().sum();
+                if total == 0 {
+                    return first;
+                }
+                let mut target = rand::thread_rng().gen_range(0..total);
+                for (quote, weight) in tied.iter().zip(weights) {
+                    if target < weight {
+                        return quote;
+                    }
+                    target -= weight;
+                }
+                first
+            }
+            TieBreak::LeastRecentlyUsed => {
+                let state = self.lock();
+                tied.iter()
+                    .min_by_key(|q| state.last_selected.get(&q.agent_id).copied().unwrap_or(0))
+                    .copied()
+                    .unwrap_or(first)
+            }
+        }
+    }
+
+    /// Records that `agent_id` was selected for execution
+    pub fn record_selection(&self, agent_id: &str) {
+        let mut state = self.lock();
+        state.selections += 1;
+        let sequence = state.selections;
+        state.last_selected.insert(agent_id.to_string(), sequence);
+    }
+
+    fn capacity(&self, agent_id: &str) -> u64 {
+        self.config.capacities.get(agent_id).copied().unwrap_or(1) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn quote(agent_id: &str) -> RouteQuote {
+        RouteQuote {
+            agent_id: agent_id.to_string(),
+            estimated_fee: "0.05 USDC".to_string(),
+            estimated_time: Duration::from_secs(15),
+            route: vec!["base".to_string()],
+            security_score: 0.9,
+            estimated_fee_usd: None,
+            signature: None,
+            agent_pubkey: None,
+            region: None,
+        }
+    }
+
+    fn balancer(strategy: TieBreak) -> LoadBalancer {
+        LoadBalancer::new(LoadBalancingConfig {
+            strategy,
+            capacities: HashMap::from([("agent-001".to_string(), 0), ("agent-003".to_string(), 3)]),
+        })
+    }
+
+    #[test]
+    fn test_strategies() {
+        let (a, b, c) = (quote("agent-001"), quote("agent-002"), quote("agent-003"));
+        let tied = [&c, &a, &b];
+        assert_eq!(balancer(TieBreak::First).pick(&tied).agent_id, "agent-003");
+
+        let round_robin = balancer(TieBreak::RoundRobin);
+        let picked: Vec<String> = (0..4)
+            .map(|_| {
+                let agent_id = round_robin.pick(&tied).agent_id.clone();
+                round_robin.record_selection(&agent_id);
+                agent_id
+            })
+            .collect();
+        assert_eq!(picked, ["agent-001", "agent-002", "agent-003", "agent-001"]);
+
+        let lru = balancer(TieBreak::LeastRecentlyUsed);
+        lru.record_selection("agent-003");
+        lru.record_selection("agent-001");
+        assert_eq!(lru.pick(&tied).agent_id, "agent-002");
+        lru.record_selection("agent-002");
+        assert_eq!(lru.pick(&tied).agent_id, "agent-003");
+
+        // agent-001 has no capacity; agent-003 takes about three times agent-002's share
+        let weighted = balancer(TieBreak::WeightedRandom);
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for _ in 0..2000 {
+            *counts.entry(weighted.pick(&tied).agent_id.clone()).or_default() += 1;
+        }
+        assert!(!counts.contains_key("agent-001"));
+        assert!(counts["agent-003"] > counts["agent-002"] * 2);
+    }
+}
@@ -0,0 +1,83 @@
+//! Explanations of routing decisions.
+//!
+//! `EasyCashClient::explain_route` reports every quote an agent returned,
+//! its score broken down per factor and why the winner was chosen, so
+//! operators can audit and debug route selection.
+
+use serde::{Deserialize, Serialize};
+
+use super::{RouteQuote, ScoreFactors};
+
+/// What happened to a quote during route selection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum QuoteStatus {
+    /// The route that would be executed
+    Selected,
+    /// Considered but ranked below the selected route
+    Eligible,
+    /// Skipped because the agent is serving an SLA penalty
+    Penalized,
+    /// Removed before selection
+    Discarded { reason: String },
+}
+
+/// One quote and how it scored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteExplanation {
+    #[serde(rename = "agent_id")]
+    pub agent_id: String,
+    pub estimated_fee: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_fee_usd: Option<f64>,
+    pub estimated_time_ms: u64,
+    pub route: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Weighted fee, time and security contributions to `score`
+    pub factors: ScoreFactors,
+    /// Share of the agent's past executions delivered within the quoted time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reputation: Option<f64>,
+    pub score: f64,
+    #[serde(flatten)]
+    pub status: QuoteStatus,
+}
+
+impl QuoteExplanation {
+    pub fn new(quote: &RouteQuote, reputation: Option<f64>, status: QuoteStatus) -> Self {
+        let factors = ScoreFactors::of(quote);
+        Self {
+            agent_id: quote.agent_id.clone(),
+            estimated_fee: quote.estimated_fee.clone(),
+            estimated_fee_usd: quote.estimated_fee_usd,
+            estimated_time_ms: quote.estimated_time.as_millis() as u64,
+            route: quote.route.clone(),
+            region: quote.region.clone(),
+            factors,
+            reputation,
+            score: factors.total(),
+            status,
+        }
+    }
+}
+
+/// All quotes for a request, ranked, and the reason for the selection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteExplanation {
+    #[serde(rename = "reference_id")]
+    pub reference_id: String,
+    /// Quotes ordered by descending score, discarded quotes last
+    pub quotes: Vec<QuoteExplanation>,
+    /// Agent of the selected route, if any quote was usable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected: Option<String>,
+    pub reason: String,
+}
+
+impl RouteExplanation {
+    /// Explanation of the selected quote
+    pub fn winner(&self) -> Option<&QuoteExplanation> {
+        self.quotes.iter().find(|q| q.status == QuoteStatus::Selected)
+    }
+}
@@ -1,11 +1,27 @@
-use crate::types::TransactionRequest;
+pub mod balancing;
+pub mod explain;
+pub mod sla;
+
+use crate::amount::{self, Amount};
+use crate::crypto::hash::HashFunction;
+use crate::crypto::{self, SigningDomain, TransactionSigner};
+use crate::escrow::{Escrow, EscrowTerms};
+use crate::protocol;
+use crate::sponsorship::Sponsorship;
+use crate::streaming::ProgressReporter;
+use crate::transport::SealedIntent;
+use crate::types::{ChainId, Disbursement, DisbursementResult, IntentType, TransactionRequest, TransactionResponse};
+use k256::ecdsa::VerifyingKey;
+use k256::PublicKey;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Route quote from an agent for executing a transaction.
 ///
 /// Contains all information needed to evaluate and execute a transaction route
 /// through the EasyCash agent network.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RouteQuote {
     /// Unique identifier for the agent providing this quote
     pub agent_id: String,
@@ -17,6 +33,115 @@ pub struct RouteQuote {
     pub route: Vec<String>,
     /// Security score from 0.0 (lowest) to 1.0 (highest)
     pub security_score: f64,
+    /// Fee converted to USD by a price oracle, when one is configured
+    pub estimated_fee_usd: Option<f64>,
+    /// Hex-encoded ECDSA signature of the agent over the quote (see `RouteQuote::sign`)
+    pub signature: Option<String>,
+    /// Compressed hex public key the quote was signed with
+    pub agent_pubkey: Option<String>,
+    /// ISO 3166-1 alpha-2 country code of the jurisdiction the agent
+    /// executes from; covered by the signature when present
+    pub region: Option<String>,
+}
+
+impl RouteQuote {
+    /// Returns the fee used to compare quotes: the USD-normalized fee when
+    /// available, otherwise the numeric part of `estimated_fee`
+    pub fn fee_value(&self) -> Option<f64> {
+        self.estimated_fee_usd.or_else(|| {
+            self.estimated_fee
+                .split_whitespace()
+                .next()
+                .and_then(|s| s.parse().ok())
+        })
+    }
+
+    /// Signs the quote as `signer`, setting `signature` and `agent_pubkey`
+    pub fn sign(mut self, signer: &TransactionSigner) -> Result<Self, String> {
+        self.agent_pubkey = Some(crypto::public_key_to_hex(&PublicKey::from(&signer.verifying_key())));
+        self.signature = Some(signer.sign_in_domain(SigningDomain::Quote, &self.signing_payload()?)?);
+        Ok(self)
+    }
+
+    /// True if the quote carries a signature
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some() || self.agent_pubkey.is_some()
+    }
+
+    /// Checks the signature against `agent_pubkey`, returning the key it was
+    /// signed with
+    pub fn verify_signature(&self) -> Result<PublicKey, String> {
+        let (Some(signature), Some(pubkey)) = (&self.signature, &self.agent_pubkey) else {
+            return Err(format!("quote from {} is unsigned", self.agent_id));
+        };
+        let key = crypto::public_key_from_hex(pubkey)?;
+        let verifying_key = VerifyingKey::from(&key);
+        let payload = self.signing_payload()?;
+        if !crypto::verify_signature_in_domain(HashFunction::Sha256, SigningDomain::Quote, &verifying_key, &payload, signature)? {
+            return Err(format!("quote from {} has an invalid signature", self.agent_id));
+        }
+        Ok(key)
+    }
+
+    /// Hex SHA-256 commitment to the quoted terms, bound into the settlement
+    /// receipt of the execution that accepted the quote
+    pub fn commitment(&self) -> Result<String, String> {
+        let payload = SigningDomain::Quote.separate(&self.signing_payload()?);
+        Ok(format!("0x{}", hex::encode(HashFunction::Sha256.hash(&payload))))
+    }
+
+    /// True if `fee_used` is more than the quoted fee, or in another asset
+    pub fn is_exceeded_by(&self, fee_used: &str) -> bool {
+        let parse = |fee: &str| {
+            let mut parts = fee.split_whitespace();
+            let amount: Amount = parts.next()?.parse().ok()?;
+            Some((amount, parts.next().unwrap_or("").to_ascii_uppercase()))
+        };
+        match (parse(&self.estimated_fee), parse(fee_used)) {
+            (Some((quoted, quoted_asset)), Some((used, used_asset))) => used > quoted || used_asset != quoted_asset,
+            // Fees that can't be compared are only acceptable verbatim
+            _ => fee_used.trim() != self.estimated_fee.trim(),
+        }
+    }
+
+    /// Fields the agent signs; the USD fee is added locally and not covered
+    fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let mut payload = serde_json::json!({
+            "agent_id": self.agent_id,
+            "estimated_fee": self.estimated_fee,
+            "estimated_time_ms": self.estimated_time.as_millis() as u64,
+            "route": self.route,
+            "security_score": self.security_score,
+        });
+        if let Some(ref region) = self.region {
+            payload["region"] = serde_json::json!(region);
+        }
+        serde_json::to_vec(&payload).map_err(|e| format!("failed to encode quote: {}", e))
+    }
+}
+
+/// Request to replace a stuck EVM transaction with one paying a higher fee
+/// (same nonce)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedUpRequest {
+    pub reference_id: String,
+    /// Agent that executed the original transaction
+    pub agent_id: String,
+    pub chain: ChainId,
+    /// Hash of the transaction to replace
+    pub tx_hash: String,
+    /// New max fee per gas, in wei
+    pub max_fee_per_gas: String,
+}
+
+/// How an agent replaced a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum Replacement {
+    /// The agent broadcast the replacement itself
+    Broadcast { tx_hash: String },
+    /// Signed, hex-encoded replacement for the SDK to broadcast through the
+    /// chain adapter
+    Signed { raw_tx: String },
 }
 
 /// Trait for agent negotiation (allows for future real implementation).
@@ -35,6 +160,131 @@ pub trait AgentNegotiatorTrait: Send + Sync {
     /// * `Err(String)` - Error message if quote fetching fails
     async fn request_quotes(&self, req: &TransactionRequest) -> Result<Vec<RouteQuote>, String>;
 
+    /// Submits the transaction to the agent behind `route` and returns the
+    /// agent's report of it; its `fee_used` is checked against the quote.
+    ///
+    /// The default implementation reports that the agent executes nothing.
+    async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> Result<TransactionResponse, String> {
+        Err(format!("agent {} does not execute intents", route.agent_id))
+    }
+
+    /// Like `execute`, reporting per-hop progress to `progress` for callers
+    /// of `execute_transaction_streaming`.
+    ///
+    /// The default implementation calls `execute` and reports nothing.
+    async fn execute_with_progress(
+        &self,
+        req: &TransactionRequest,
+        route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        self.execute(req, route).await
+    }
+
+    /// Submits a shielded intent whose sensitive fields are sealed to the
+    /// agent's transport key (see `transport`); `req` has those fields cleared.
+    ///
+    /// The default implementation reports that sealed intents are unsupported.
+    async fn execute_sealed(
+        &self,
+        _req: &TransactionRequest,
+        _sealed: &SealedIntent,
+        route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        Err(format!("agent {} does not accept sealed intents", route.agent_id))
+    }
+
+    /// Executes a `Disburse` request, funding every recipient from the one
+    /// debit, and reports the outcome per recipient in order in
+    /// `disbursements`.
+    ///
+    /// The default implementation submits one transfer per recipient along
+    /// `route` and reports the fees of the legs together; agents that settle
+    /// splits in a single operation override it.
+    async fn execute_disbursement(
+        &self,
+        req: &TransactionRequest,
+        disbursements: &[Disbursement],
+        route: &RouteQuote,
+        progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        let mut results = Vec::with_capacity(disbursements.len());
+        let mut fees = Vec::new();
+        for (i, d) in disbursements.iter().enumerate() {
+            let mut leg = req.clone();
+            leg.reference_id = format!("{}-{}", req.reference_id, i);
+            leg.intent_type = IntentType::Transfer;
+            leg.recipient = Some(d.recipient.clone());
+            leg.amount = d.amount.clone();
+            leg.amount_base_units = None;
+            leg.disbursements.clear();
+            leg.fee_splits.clear();
+            leg.escrow = None;
+            let outcome = self.execute_with_progress(&leg, route, progress).await;
+            results.push(DisbursementResult::new(d, outcome.as_ref().err().cloned()));
+            fees.extend(outcome.map(|executed| executed.fee_used));
+        }
+        Ok(TransactionResponse {
+            disbursements: results,
+            ..TransactionResponse::executed(total_fee(&fees))
+        })
+    }
+
+    /// Locks the amount of an `Escrow` request with the agent behind
+    /// `route` under `terms`, instead of paying the recipient.
+    ///
+    /// The default implementation reports that escrow is unsupported.
+    async fn lock_escrow(
+        &self,
+        _req: &TransactionRequest,
+        _terms: &EscrowTerms,
+        route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        Err(format!("agent {} does not support escrow", route.agent_id))
+    }
+
+    /// Pays the funds of `escrow` to its recipient once the SDK has checked
+    /// the release condition. Hash-time-locked escrows carry the revealed
+    /// `secret`.
+    ///
+    /// The default implementation reports that escrow is unsupported.
+    async fn release_escrow(&self, escrow: &Escrow) -> Result<(), String> {
+        Err(format!("agent {} does not support escrow", escrow.agent_id))
+    }
+
+    /// Returns the funds of `escrow` to the payer after its refund deadline.
+    ///
+    /// The default implementation reports that escrow is unsupported.
+    async fn refund_escrow(&self, escrow: &Escrow) -> Result<(), String> {
+        Err(format!("agent {} does not support escrow", escrow.agent_id))
+    }
+
+    /// Attaches `sponsorship` to the intent it was arranged for, before that
+    /// intent is executed along `route`, so the network fee is charged to
+    /// `sponsorship.fee_account` instead of the payer.
+    ///
+    /// The default implementation reports that sponsored fees are unsupported.
+    async fn sponsor_fee(&self, _sponsorship: &Sponsorship, route: &RouteQuote) -> Result<(), String> {
+        Err(format!("agent {} does not support sponsored fees", route.agent_id))
+    }
+
+    /// Replaces a stuck transaction previously executed by
+    /// `req.agent_id` with one paying `req.max_fee_per_gas`.
+    ///
+    /// The default implementation reports that speed-up is unsupported.
+    async fn speed_up(&self, req: &SpeedUpRequest) -> Result<Replacement, String> {
+        Err(format!("agent {} does not support speed-up", req.agent_id))
+    }
+
+    /// Protocol versions the agents accept (see `protocol`).
+    ///
+    /// The default implementation reports the versions of this SDK.
+    async fn supported_versions(&self) -> Result<Vec<u32>, String> {
+        Ok(protocol::supported_versions().to_vec())
+    }
+
     /// Applies multi-factor optimization to choose the best agent.
     ///
     /// # Arguments
@@ -51,6 +301,57 @@ pub trait AgentNegotiatorTrait: Send + Sync {
     ) -> Result<RouteQuote, String>;
 }
 
+/// Directory of the keys agents publish for encrypted transport.
+#[async_trait::async_trait]
+pub trait AgentDirectory: Send + Sync {
+    /// Transport public key published by `agent_id`, if any
+    async fn transport_key(&self, agent_id: &str) -> Result<Option<PublicKey>, String>;
+
+    /// Key `agent_id` signs its quotes with, if published. Quotes signed
+    /// with any other key are rejected.
+    ///
+    /// The default implementation publishes none.
+    async fn quote_key(&self, _agent_id: &str) -> Result<Option<PublicKey>, String> {
+        Ok(None)
+    }
+}
+
+/// Agent directory with a fixed set of keys
+#[derive(Debug, Clone, Default)]
+pub struct StaticAgentDirectory {
+    keys: HashMap<String, PublicKey>,
+    quote_keys: HashMap<String, PublicKey>,
+}
+
+impl StaticAgentDirectory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publishes `key` as the transport key of `agent_id`
+    pub fn with_agent(mut self, agent_id: impl Into<String>, key: PublicKey) -> Self {
+        self.keys.insert(agent_id.into(), key);
+        self
+    }
+
+    /// Publishes `key` as the quote signing key of `agent_id`
+    pub fn with_quote_key(mut self, agent_id: impl Into<String>, key: PublicKey) -> Self {
+        self.quote_keys.insert(agent_id.into(), key);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl AgentDirectory for StaticAgentDirectory {
+    async fn transport_key(&self, agent_id: &str) -> Result<Option<PublicKey>, String> {
+        Ok(self.keys.get(agent_id).copied())
+    }
+
+    async fn quote_key(&self, agent_id: &str) -> Result<Option<PublicKey>, String> {
+        Ok(self.quote_keys.get(agent_id).copied())
+    }
+}
+
 /// Mock agent negotiator for development/testing.
 ///
 /// **NOTE: This is a simulation/mock implementation.**
@@ -114,6 +415,10 @@ impl AgentNegotiatorTrait for MockAgentNegotiator {
                         .unwrap_or_else(|| req.source_chain.as_str().to_string()),
                 ],
                 security_score: 0.98,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: Some("DE".to_string()),
             },
             RouteQuote {
                 agent_id: "agent-002".to_string(),
@@ -127,88 +432,178 @@ impl AgentNegotiatorTrait for MockAgentNegotiator {
                         .unwrap_or_else(|| req.source_chain.as_str().to_string()),
                 ],
                 security_score: 0.85,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: Some("US".to_string()),
             },
         ];
 
-        Ok(quotes)
+        quotes
+            .into_iter()
+            .map(|quote| {
+                let signer = mock_quote_signer(&quote.agent_id);
+                quote.sign(&signer)
+            })
+            .collect()
     }
 
-    /// Applies multi-factor optimization to choose the best agent.
-    ///
-    /// Supports multiple preference modes:
-    /// - "speed": Prioritize fastest execution time
-    /// - "cost": Prioritize lowest fees
-    /// - "security": Prioritize highest security score
-    /// - "balanced" (default): Weighted combination of all factors
+    /// **MOCK IMPLEMENTATION**: Simulates agent processing time and charges
+    /// the quoted fee.
+    async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> Result<TransactionResponse, String> {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+    }
+
+    /// **MOCK IMPLEMENTATION**: Accepts the sealed intent without decrypting it.
+    async fn execute_sealed(
+        &self,
+        req: &TransactionRequest,
+        _sealed: &SealedIntent,
+        route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        self.execute(req, route).await
+    }
+
+    /// **MOCK IMPLEMENTATION**: Settles every recipient in one operation.
+    async fn execute_disbursement(
+        &self,
+        req: &TransactionRequest,
+        disbursements: &[Disbursement],
+        route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        let executed = self.execute(req, route).await?;
+        Ok(TransactionResponse {
+            disbursements: disbursements.iter().map(|d| DisbursementResult::new(d, None)).collect(),
+            ..executed
+        })
+    }
+
+    /// **MOCK IMPLEMENTATION**: Simulates locking the funds.
+    async fn lock_escrow(
+        &self,
+        req: &TransactionRequest,
+        _terms: &EscrowTerms,
+        route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> Result<TransactionResponse, String> {
+        self.execute(req, route).await
+    }
+
+    /// **MOCK IMPLEMENTATION**: Accepts immediately.
+    async fn release_escrow(&self, _escrow: &Escrow) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// **MOCK IMPLEMENTATION**: Accepts immediately.
+    async fn refund_escrow(&self, _escrow: &Escrow) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// **MOCK IMPLEMENTATION**: Accepts immediately.
+    async fn sponsor_fee(&self, _sponsorship: &Sponsorship, _route: &RouteQuote) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Applies multi-factor optimization to choose the best agent
+    /// (see `select_best_route`).
     fn select_best_route(
         &self,
         quotes: &[RouteQuote],
         preference: &str,
     ) -> Result<RouteQuote, String> {
-        if quotes.is_empty() {
-            return Err("no quotes available".to_string());
-        }
+        select_best_route(quotes, preference)
+    }
+}
 
-        let best = match preference {
-            "speed" => quotes
-                .iter()
-                .min_by(|a, b| a.estimated_time.cmp(&b.estimated_time)),
-            "cost" => {
-                // Parse fee and find minimum (assumes format "X.XX ASSET")
-                quotes.iter().min_by(|a, b| {
-                    let fee_a: f64 = a
-                        .estimated_fee
-                        .split_whitespace()
-                        .next()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or(f64::MAX);
-                    let fee_b: f64 = b
-                        .estimated_fee
-                        .split_whitespace()
-                        .next()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or(f64::MAX);
-                    fee_a.partial_cmp(&fee_b).unwrap_or(std::cmp::Ordering::Equal)
-                })
-            }
-            "security" => quotes.iter().max_by(|a, b| {
-                a.security_score
-                    .partial_cmp(&b.security_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            }),
-            _ => {
-                // "balanced" - weighted score (security has higher weight)
-                quotes.iter().max_by(|a, b| {
-                    let score_a = a.security_score * 0.5
-                        + (1.0 / (a.estimated_time.as_secs_f64() + 1.0)) * 0.3
-                        + (1.0
-                            / (a.estimated_fee
-                                .split_whitespace()
-                                .next()
-                                .and_then(|s| s.parse::<f64>().ok())
-                                .unwrap_or(1.0)
-                                + 1.0))
-                            * 0.2;
-                    let score_b = b.security_score * 0.5
-                        + (1.0 / (b.estimated_time.as_secs_f64() + 1.0)) * 0.3
-                        + (1.0
-                            / (b.estimated_fee
-                                .split_whitespace()
-                                .next()
-                                .and_then(|s| s.parse::<f64>().ok())
-                                .unwrap_or(1.0)
-                                + 1.0))
-                            * 0.2;
-                    score_a
-                        .partial_cmp(&score_b)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                })
+/// Sum of the fees of the legs of an execution. Fees in different assets,
+/// or that can't be read, are listed as reported and match no quote
+fn total_fee(fees: &[String]) -> String {
+    let total = fees.iter().try_fold(None::<(Amount, &str)>, |total, fee| {
+        let (fee, asset) = amount::parse_fee(fee)?;
+        match total {
+            None => Some(Some((fee, asset))),
+            Some((sum, total_asset)) if total_asset.eq_ignore_ascii_case(asset) => {
+                Some(Some((sum.checked_add(&fee)?, total_asset)))
             }
-        };
+            Some(_) => None,
+        }
+    });
+    match total {
+        Some(Some((sum, ""))) => sum.to_string(),
+        Some(Some((sum, asset))) => format!("{} {}", sum, asset),
+        Some(None) => String::new(),
+        None => fees.join(" + "),
+    }
+}
+
+/// Quote signing key of a simulated agent, derived from its ID
+fn mock_quote_signer(agent_id: &str) -> TransactionSigner {
+    let seed = HashFunction::Sha256.hash(agent_id.as_bytes());
+    // A SHA-256 digest is a valid secp256k1 scalar with overwhelming probability
+    TransactionSigner::new(k256::SecretKey::from_slice(&seed).expect("valid mock agent key"))
+}
+
+/// Score of `quote` under a preference; higher ranks better.
+///
+/// Supports multiple preference modes:
+/// - "speed": Prioritize fastest execution time
+/// - "cost": Prioritize lowest fees
+/// - "security": Prioritize highest security score
+/// - "balanced" (default): Weighted combination of all factors
+pub fn route_score(quote: &RouteQuote, preference: &str) -> f64 {
+    match preference {
+        "speed" => -quote.estimated_time.as_secs_f64(),
+        "cost" => -quote.fee_value().unwrap_or(f64::MAX),
+        "security" => quote.security_score,
+        // "balanced" - weighted score (security has higher weight)
+        _ => ScoreFactors::of(quote).total(),
+    }
+}
+
+/// Weighted contributions of each factor to a quote's "balanced" score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreFactors {
+    pub fee: f64,
+    pub time: f64,
+    pub security: f64,
+}
 
-        best.cloned()
-            .ok_or_else(|| "no quotes available".to_string())
+impl ScoreFactors {
+    pub fn of(quote: &RouteQuote) -> Self {
+        Self {
+            fee: (1.0 / (quote.fee_value().unwrap_or(1.0) + 1.0)) * 0.2,
+            time: (1.0 / (quote.estimated_time.as_secs_f64() + 1.0)) * 0.3,
+            security: quote.security_score * 0.5,
+        }
     }
+
+    pub fn total(&self) -> f64 {
+        self.security + self.time + self.fee
+    }
+}
+
+/// Picks the best quote for a preference (see `route_score`); of equally
+/// ranked quotes the first wins
+pub fn select_best_route(quotes: &[RouteQuote], preference: &str) -> Result<RouteQuote, String> {
+    // Each quote's score is computed once (fees are parsed from strings)
+    quotes
+        .iter()
+        .map(|q| (route_score(q, preference), q))
+        .fold(None, |best: Option<(f64, &RouteQuote)>, (score, q)| match best {
+            Some((top, _)) if score.partial_cmp(&top) != Some(std::cmp::Ordering::Greater) => best,
+            _ => Some((score, q)),
+        })
+        .map(|(_, q)| q.clone())
+        .ok_or_else(|| "no quotes available".to_string())
+}
+
+/// Quotes ranked the same as `best` under a preference, in quote order
+pub fn equally_ranked<'a>(quotes: &'a [RouteQuote], best: &RouteQuote, preference: &str) -> Vec<&'a RouteQuote> {
+    let top = route_score(best, preference);
+    quotes.iter().filter(|q| route_score(q, preference) == top).collect()
 }
 
 /// Type alias for current agent negotiator (can be swapped for real implementation)
@@ -222,21 +617,108 @@ mod tests {
     #[tokio::test]
     async fn test_request_quotes() {
         let negotiator = MockAgentNegotiator::new(Duration::from_secs(30));
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: None,
-            source_chain: ChainId::Base,
-            target_chain: Some(ChainId::Ethereum),
-            is_shielded: false,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_target_chain(ChainId::Ethereum);
 
         let quotes = negotiator.request_quotes(&req).await.unwrap();
         assert_eq!(quotes.len(), 2);
         assert_eq!(quotes[0].agent_id, "agent-001");
         assert_eq!(quotes[1].agent_id, "agent-002");
+        assert!(quotes.iter().all(|q| q.verify_signature().is_ok()));
+    }
+
+    #[tokio::test]
+    async fn test_leg_by_leg_disbursement_reports_total_fee() {
+        /// Charges 0.02 USDC per transfer
+        struct PerLeg;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for PerLeg {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> Result<Vec<RouteQuote>, String> {
+                Ok(Vec::new())
+            }
+
+            async fn execute(&self, _req: &TransactionRequest, _route: &RouteQuote) -> Result<TransactionResponse, String> {
+                Ok(TransactionResponse::executed("0.02 USDC"))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> Result<RouteQuote, String> {
+                select_best_route(quotes, preference)
+            }
+        }
+
+        let req = TransactionRequest::new("ref_legs", IntentType::Disburse, "30", "USDC", ChainId::Base);
+        let disbursements = ["0xaaa", "0xbbb"].map(|recipient| Disbursement {
+            recipient: recipient.to_string(),
+            amount: "15".to_string(),
+        });
+        let route = MockAgentNegotiator::new(Duration::ZERO).request_quotes(&req).await.unwrap().remove(0);
+        let executed = PerLeg
+            .execute_disbursement(&req, &disbursements, &route, &ProgressReporter::default())
+            .await
+            .unwrap();
+        assert_eq!(executed.fee_used, "0.04 USDC");
+        assert!(executed.disbursements.iter().all(DisbursementResult::is_confirmed));
+
+        assert_eq!(total_fee(&["0.02 USDC".to_string(), "0.01 ETH".to_string()]), "0.02 USDC + 0.01 ETH");
+    }
+
+    #[test]
+    fn test_quote_signature_covers_quoted_terms() {
+        let signer = mock_quote_signer("agent-001");
+        let quote = RouteQuote {
+            agent_id: "agent-001".to_string(),
+            estimated_fee: "0.05 USDC".to_string(),
+            estimated_time: Duration::from_secs(15),
+            route: vec!["base".to_string()],
+            security_score: 0.9,
+            estimated_fee_usd: None,
+            signature: None,
+            agent_pubkey: None,
+            region: None,
+        };
+        assert!(!quote.is_signed());
+        assert!(quote.verify_signature().unwrap_err().contains("unsigned"));
+
+        let mut signed = quote.sign(&signer).unwrap();
+        assert_eq!(signed.verify_signature().unwrap(), PublicKey::from(&signer.verifying_key()));
+        // The USD fee is derived locally and not signed
+        signed.estimated_fee_usd = Some(0.05);
+        assert!(signed.verify_signature().is_ok());
+
+        let mut cheaper = signed.clone();
+        cheaper.estimated_fee = "0.01 USDC".to_string();
+        assert!(cheaper.verify_signature().unwrap_err().contains("invalid signature"));
+        let mut spoofed = signed.clone();
+        spoofed.agent_id = "agent-002".to_string();
+        assert!(spoofed.verify_signature().is_err());
+        let mut relocated = signed.clone();
+        relocated.region = Some("US".to_string());
+        assert!(relocated.verify_signature().is_err());
+
+        assert_ne!(signed.commitment().unwrap(), cheaper.commitment().unwrap());
+        let unconverted = RouteQuote { estimated_fee_usd: None, ..signed.clone() };
+        assert_eq!(signed.commitment().unwrap(), unconverted.commitment().unwrap());
+    }
+
+    #[test]
+    fn test_fee_used_against_quote() {
+        let quote = RouteQuote {
+            agent_id: "agent-001".to_string(),
+            estimated_fee: "0.05 USDC".to_string(),
+            estimated_time: Duration::from_secs(15),
+            route: vec!["base".to_string()],
+            security_score: 0.9,
+            estimated_fee_usd: None,
+            signature: None,
+            agent_pubkey: None,
+            region: None,
+        };
+        assert!(!quote.is_exceeded_by("0.05 USDC"));
+        assert!(!quote.is_exceeded_by("0.050 usdc"));
+        assert!(!quote.is_exceeded_by("0.04 USDC"));
+        assert!(quote.is_exceeded_by("0.051 USDC"));
+        assert!(quote.is_exceeded_by("0.01 ETH"));
+        assert!(quote.is_exceeded_by("unknown"));
     }
 
     #[test]
@@ -249,6 +731,10 @@ mod tests {
                 estimated_time: Duration::from_secs(15),
                 route: vec!["base".to_string(), "ethereum".to_string()],
                 security_score: 0.98,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
             RouteQuote {
                 agent_id: "agent-002".to_string(),
@@ -256,6 +742,10 @@ mod tests {
                 estimated_time: Duration::from_secs(30),
                 route: vec!["base".to_string(), "polygon".to_string(), "ethereum".to_string()],
                 security_score: 0.85,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
         ];
 
@@ -274,6 +764,10 @@ mod tests {
                 estimated_time: Duration::from_secs(15),
                 route: vec!["base".to_string()],
                 security_score: 0.98,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
             RouteQuote {
                 agent_id: "agent-002".to_string(),
@@ -281,6 +775,10 @@ mod tests {
                 estimated_time: Duration::from_secs(30),
                 route: vec!["base".to_string()],
                 security_score: 0.85,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
         ];
 
@@ -299,6 +797,10 @@ mod tests {
                 estimated_time: Duration::from_secs(60),
                 route: vec!["base".to_string()],
                 security_score: 0.98,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
             RouteQuote {
                 agent_id: "agent-002".to_string(),
@@ -306,6 +808,10 @@ mod tests {
                 estimated_time: Duration::from_secs(10),
                 route: vec!["base".to_string()],
                 security_score: 0.85,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
         ];
 
@@ -324,6 +830,10 @@ mod tests {
                 estimated_time: Duration::from_secs(5),
                 route: vec!["base".to_string()],
                 security_score: 0.70,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
             RouteQuote {
                 agent_id: "agent-002".to_string(),
@@ -331,6 +841,10 @@ mod tests {
                 estimated_time: Duration::from_secs(60),
                 route: vec!["base".to_string()],
                 security_score: 0.99,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
             },
         ];
 
@@ -339,6 +853,39 @@ mod tests {
         assert_eq!(best.security_score, 0.99);
     }
 
+    #[test]
+    fn test_select_best_route_cost_uses_normalized_fee() {
+        let negotiator = MockAgentNegotiator::new(Duration::from_secs(30));
+        let quotes = vec![
+            RouteQuote {
+                agent_id: "agent-001".to_string(),
+                estimated_fee: "0.03 USDC".to_string(),
+                estimated_time: Duration::from_secs(15),
+                route: vec!["base".to_string()],
+                security_score: 0.9,
+                estimated_fee_usd: Some(0.03),
+                signature: None,
+                agent_pubkey: None,
+                region: None,
+            },
+            RouteQuote {
+                agent_id: "agent-002".to_string(),
+                estimated_fee: "0.0004 ETH".to_string(),
+                estimated_time: Duration::from_secs(15),
+                route: vec!["base".to_string()],
+                security_score: 0.9,
+                estimated_fee_usd: Some(1.2),
+                signature: None,
+                agent_pubkey: None,
+                region: None,
+            },
+        ];
+
+        // Comparing raw amounts would wrongly pick the 0.0004 ETH quote
+        let best = negotiator.select_best_route(&quotes, "cost").unwrap();
+        assert_eq!(best.agent_id, "agent-001");
+    }
+
     #[test]
     fn test_select_best_route_empty() {
         let negotiator = MockAgentNegotiator::new(Duration::from_secs(30));
@@ -0,0 +1,289 @@
+//! Per-agent SLA tracking.
+//!
+//! Every execution is compared against the quote it was accepted on. Agents
+//! that repeatedly deliver more than `time_tolerance` slower than their
+//! quoted `estimated_time` are down-ranked in route selection until their
+//! cooldown expires.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use super::RouteQuote;
+
+/// Configuration of SLA tracking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlaConfig {
+    /// Fraction by which delivery may exceed the quoted time before it
+    /// counts as a miss (0.5 allows 50% over the quote)
+    pub time_tolerance: f64,
+    /// Consecutive misses after which the agent is penalized
+    pub max_misses: u32,
+    /// How long a penalized agent is down-ranked
+    pub cooldown: Duration,
+    /// Whether misses penalize agents; deltas are tracked either way
+    pub enabled: bool,
+}
+
+impl Default for SlaConfig {
+    fn default() -> Self {
+        Self {
+            time_tolerance: 0.5,
+            max_misses: 3,
+            cooldown: Duration::from_secs(300),
+            enabled: true,
+        }
+    }
+}
+
+/// SLA record of one agent
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentSla {
+    pub executions: u64,
+    /// Executions delivered later than the quoted time allows
+    pub time_misses: u64,
+    pub consecutive_misses: u32,
+    /// Mean of (delivered - quoted) / quoted time
+    pub average_time_delta: f64,
+    /// Mean of the charged fee minus the quoted fee, over comparable fees
+    pub average_fee_delta: f64,
+    /// Times the agent has been penalized
+    pub penalties: u64,
+    /// Remaining cooldown, when the agent is currently penalized
+    pub penalized_for: Option<Duration>,
+}
+
+impl AgentSla {
+    /// Share of executions delivered within the quoted time, once the agent
+    /// has executed at least once
+    pub fn on_time_rate(&self) -> Option<f64> {
+        (self.executions > 0).then(|| 1.0 - self.time_misses as f64 / self.executions as f64)
+    }
+}
+
+/// SLA records keyed by agent ID
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlaReport {
+    pub agents: HashMap<String, AgentSla>,
+}
+
+impl SlaReport {
+    /// Agents currently down-ranked
+    pub fn penalized(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .agents
+            .iter()
+            .filter(|(_, sla)| sla.penalized_for.is_some())
+            .map(|(id, _)| id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+#[derive(Default)]
+struct AgentRecord {
+    executions: u64,
+    time_misses: u64,
+    consecutive_misses: u32,
+    time_delta_sum: f64,
+    fee_delta_sum: f64,
+    fee_samples: u64,
+    penalties: u64,
+    penalized_until: Option<Instant>,
+}
+
+impl AgentRecord {
+    fn remaining_cooldown(&self, now: Instant) -> Option<Duration> {
+        self.penalized_until.filter(|until| *until > now).map(|until| until - now)
+    }
+}
+
+/// Tracks delivered-vs-quoted time and fee of each agent.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::agent::sla::{SlaConfig, SlaTracker};
+/// use std::time::Duration;
+///
+/// let tracker = SlaTracker::new(SlaConfig { max_misses: 1, ..Default::default() });
+/// tracker.record("agent-001", Duration::from_secs(10), Duration::from_secs(30), "0.05 USDC", "0.05 USDC");
+/// assert!(tracker.is_penalized("agent-001"));
+/// assert_eq!(tracker.report().agents["agent-001"].time_misses, 1);
+/// ```
+pub struct SlaTracker {
+    config: SlaConfig,
+    agents: Mutex<HashMap<String, AgentRecord>>,
+}
+
+impl SlaTracker {
+    pub fn new(config: SlaConfig) -> Self {
+        Self {
+            config,
+            agents: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, AgentRecord>> {
+        self.agents.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Records an execution of `agent_id` against its quoted time and fee.
+    /// Returns true if the agent was penalized as a result
+    pub fn record(&self, agent_id: &str, quoted_time: Duration, delivered: Duration, quoted_fee: &str, fee_used: &str) -> bool {
+        let quoted_secs = quoted_time.as_secs_f64();
+        let time_delta = if quoted_secs > 0.0 {
+            (delivered.as_secs_f64() - quoted_secs) / quoted_secs
+        } else if delivered.is_zero() {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+        let missed = time_delta > self.config.time_tolerance;
+
+        let mut agents = self.lock();
+        let record = agents.entry(agent_id.to_string()).or_default();
+        record.executions += 1;
+        if time_delta.is_finite() {
+            record.time_delta_sum += time_delta;
+        }
+        if let Some(delta) = fee_delta(quoted_fee, fee_used) {
+            record.fee_delta_sum += delta;
+            record.fee_samples += 1;
+        }
+        if !missed {
+            record.consecutive_misses = 0;
+            return false;
+        }
+        record.time_misses += 1;
+        record.consecutive_misses += 1;
+        if !self.config.enabled || record.consecutive_misses < self.config.max_misses {
+            return false;
+        }
+        record.consecutive_misses = 0;
+        record.penalties += 1;
+        record.penalized_until = Some(Instant::now() + self.config.cooldown);
+        tracing::warn!(
+            "[SDK] Agent {} missed its quoted time {} times in a row, down-ranked for {:?}",
+            agent_id, self.config.max_misses, self.config.cooldown
+        );
+        true
+    }
+
+    /// True if `agent_id` is within a penalty cooldown
+    pub fn is_penalized(&self, agent_id: &str) -> bool {
+        let now = Instant::now();
+        self.lock().get(agent_id).is_some_and(|r| r.remaining_cooldown(now).is_some())
+    }
+
+    /// Drops quotes of penalized agents, unless every quote is penalized
+    pub fn down_rank(&self, quotes: Vec<RouteQuote>) -> Vec<RouteQuote> {
+        let now = Instant::now();
+        let agents = self.lock();
+        let penalized = |q: &RouteQuote| agents.get(&q.agent_id).is_some_and(|r| r.remaining_cooldown(now).is_some());
+        if quotes.iter().all(penalized) {
+            return quotes;
+        }
+        quotes.into_iter().filter(|q| !penalized(q)).collect()
+    }
+
+    pub fn report(&self) -> SlaReport {
+        let now = Instant::now();
+        let agents = self
+            .lock()
+            .iter()
+            .map(|(id, r)| {
+                let sla = AgentSla {
+                    executions: r.executions,
+                    time_misses: r.time_misses,
+                    consecutive_misses: r.consecutive_misses,
+                    average_time_delta: r.time_delta_sum / r.executions.max(1) as f64,
+                    average_fee_delta: r.fee_delta_sum / r.fee_samples.max(1) as f64,
+                    penalties: r.penalties,
+                    penalized_for: r.remaining_cooldown(now),
+                };
+                (id.clone(), sla)
+            })
+            .collect();
+        SlaReport { agents }
+    }
+}
+
+/// Charged minus quoted fee, when both are numeric and in the same asset
+fn fee_delta(quoted: &str, used: &str) -> Option<f64> {
+    let parse = |fee: &str| {
+        let mut parts = fee.split_whitespace();
+        let amount: f64 = parts.next()?.parse().ok()?;
+        Some((amount, parts.next().unwrap_or("").to_ascii_uppercase()))
+    };
+    let ((quoted, quoted_asset), (used, used_asset)) = (parse(quoted)?, parse(used)?);
+    (quoted_asset == used_asset).then_some(used - quoted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(agent_id: &str) -> RouteQuote {
+        RouteQuote {
+            agent_id: agent_id.to_string(),
+            estimated_fee: "0.05 USDC".to_string(),
+            estimated_time: Duration::from_secs(10),
+            route: vec!["base".to_string()],
+            security_score: 0.9,
+            estimated_fee_usd: None,
+            signature: None,
+            agent_pubkey: None,
+            region: None,
+        }
+    }
+
+    #[test]
+    fn test_consecutive_misses_penalize() {
+        let tracker = SlaTracker::new(SlaConfig::default());
+        let (quoted, slow, on_time) = (Duration::from_secs(10), Duration::from_secs(16), Duration::from_secs(14));
+
+        assert!(!tracker.record("agent-001", quoted, slow, "0.05 USDC", "0.07 USDC"));
+        assert!(!tracker.record("agent-001", quoted, slow, "0.05 USDC", "0.05 USDC"));
+        // An on-time delivery resets the streak
+        assert!(!tracker.record("agent-001", quoted, on_time, "0.05 USDC", "0.05 USDC"));
+        assert!(!tracker.record("agent-001", quoted, slow, "0.05 USDC", "0.05 USDC"));
+        assert!(!tracker.record("agent-001", quoted, slow, "0.05 USDC", "0.05 USDC"));
+        assert!(tracker.record("agent-001", quoted, slow, "0.05 USDC", "0.05 USDC"));
+
+        let sla = &tracker.report().agents["agent-001"];
+        assert_eq!((sla.executions, sla.time_misses, sla.penalties), (6, 5, 1));
+        assert!(sla.penalized_for.is_some());
+        assert!((sla.average_fee_delta - 0.02 / 6.0).abs() < 1e-9);
+        assert!((sla.average_time_delta - 3.4 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_down_rank_and_cooldown() {
+        let tracker = SlaTracker::new(SlaConfig {
+            max_misses: 1,
+            cooldown: Duration::from_millis(50),
+            ..Default::default()
+        });
+        tracker.record("agent-001", Duration::from_secs(1), Duration::from_secs(5), "0.05 USDC", "0.05 USDC");
+
+        let ranked = tracker.down_rank(vec![quote("agent-001"), quote("agent-002")]);
+        assert_eq!(ranked.iter().map(|q| q.agent_id.as_str()).collect::<Vec<_>>(), ["agent-002"]);
+        // With no alternative the penalized agent is still usable
+        assert_eq!(tracker.down_rank(vec![quote("agent-001")]).len(), 1);
+        assert_eq!(tracker.report().penalized(), ["agent-001"]);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!tracker.is_penalized("agent-001"));
+        assert_eq!(tracker.down_rank(vec![quote("agent-001"), quote("agent-002")]).len(), 2);
+    }
+
+    #[test]
+    fn test_disabled_tracks_without_penalizing() {
+        let tracker = SlaTracker::new(SlaConfig { enabled: false, max_misses: 1, ..Default::default() });
+        assert!(!tracker.record("agent-001", Duration::ZERO, Duration::from_secs(1), "n/a", "n/a"));
+        let sla = &tracker.report().agents["agent-001"];
+        assert_eq!((sla.time_misses, sla.penalties, sla.average_fee_delta), (1, 0, 0.0));
+    }
+}
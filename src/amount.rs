@@ -0,0 +1,324 @@
+//! Exact decimal amounts and conversion to an asset's smallest unit.
+//!
+//! Amounts are kept as an integer and a decimal scale, so "1000.00" and
+//! "1000" compare equal and converting to base units never goes through
+//! floating point.
+//!
+//! ```
+//! use ecash_sdk_core::amount::{Amount, Rounding};
+//!
+//! let amount: Amount = "1000.50".parse().unwrap();
+//! assert_eq!(amount.to_base_units(6, Rounding::Down).unwrap(), 1_000_500_000);
+//! assert_eq!(Amount::from_base_units(1_000_500_000, 6).to_string(), "1000.5");
+//!
+//! let precise: Amount = "0.1234567".parse().unwrap();
+//! assert_eq!(precise.to_base_units(6, Rounding::HalfEven).unwrap(), 123_457);
+//! assert!(precise.to_base_units_exact(6).is_err());
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Most fractional digits an amount may have (10^38 still fits in a u128)
+const MAX_SCALE: u32 = 38;
+
+/// How to round when an amount has more fractional digits than the asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Toward zero (truncate)
+    Down,
+    /// Away from zero
+    Up,
+    /// To nearest; halves away from zero
+    HalfUp,
+    /// To nearest; halves to the even neighbour (banker's rounding)
+    HalfEven,
+}
+
+/// Non-negative decimal amount
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Amount {
+    /// Value times 10^scale, with trailing fractional zeros removed
+    units: u128,
+    scale: u32,
+}
+
+impl Amount {
+    /// Amount of `units` in an asset with `decimals` decimals
+    pub fn from_base_units(units: u128, decimals: u32) -> Self {
+        Self { units, scale: decimals }.normalized()
+    }
+
+    /// Converts to the asset's smallest unit, rounding as requested
+    pub fn to_base_units(&self, decimals: u32, rounding: Rounding) -> Result<u128, String> {
+        if self.scale <= decimals {
+            return pow10(decimals - self.scale)
+                .and_then(|factor| self.units.checked_mul(factor))
+                .ok_or_else(|| format!("{} is too large for {} decimals", self, decimals));
+        }
+        // scale <= MAX_SCALE, so the divisor always fits
+        let divisor = pow10(self.scale - decimals).unwrap_or(u128::MAX);
+        let (quotient, remainder) = (self.units / divisor, self.units % divisor);
+        let round_up = match rounding {
+            Rounding::Down => false,
+            Rounding::Up => remainder > 0,
+            Rounding::HalfUp => remainder >= divisor - remainder,
+            Rounding::HalfEven => {
+                remainder > divisor - remainder || (remainder == divisor - remainder && quotient % 2 == 1)
+            }
+        };
+        Ok(quotient + u128::from(round_up))
+    }
+
+    /// Converts to the asset's smallest unit, failing if that would round
+    pub fn to_base_units_exact(&self, decimals: u32) -> Result<u128, String> {
+        if self.scale > decimals {
+            return Err(format!("{} has more than {} decimal places", self, decimals));
+        }
+        self.to_base_units(decimals, Rounding::Down)
+    }
+
+    /// Digits after the decimal point (without trailing zeros)
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.units == 0
+    }
+
+    /// Nearest `f64`, for display and for code that still works in floating point
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::MAX)
+    }
+
+    /// `self + other`, or `None` on overflow
+    pub fn checked_add(&self, other: &Amount) -> Option<Amount> {
+        let (a, b, scale) = self.aligned(other)?;
+        Some(Self { units: a.checked_add(b)?, scale }.normalized())
+    }
+
+    /// `self - other`, or `None` if `other` is larger
+    pub fn checked_sub(&self, other: &Amount) -> Option<Amount> {
+        let (a, b, scale) = self.aligned(other)?;
+        Some(Self { units: a.checked_sub(b)?, scale }.normalized())
+    }
+
+    /// Both units at the larger of the two scales
+    fn aligned(&self, other: &Amount) -> Option<(u128, u128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let a = self.units.checked_mul(pow10(scale - self.scale)?)?;
+        let b = other.units.checked_mul(pow10(scale - other.scale)?)?;
+        Some((a, b, scale))
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.scale > 0 && self.units.is_multiple_of(10) {
+            self.units /= 10;
+            self.scale -= 1;
+        }
+        if self.units == 0 {
+            self.scale = 0;
+        }
+        self
+    }
+}
+
+/// Splits a fee such as `"0.05 USDC"` into its amount and asset (empty if
+/// the fee has no asset)
+pub fn parse_fee(fee: &str) -> Option<(Amount, &str)> {
+    let mut parts = fee.split_whitespace();
+    let amount = parts.next()?.parse().ok()?;
+    Some((amount, parts.next().unwrap_or("")))
+}
+
+fn pow10(exp: u32) -> Option<u128> {
+    10u128.checked_pow(exp)
+}
+
+impl Ord for Amount {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Integer parts, then fractions scaled to the common scale (both
+        // below 10^38, so neither overflows)
+        let scale = self.scale.max(other.scale);
+        let split = |a: &Amount| {
+            let one = pow10(a.scale).unwrap_or(u128::MAX);
+            (a.units / one, (a.units % one) * pow10(scale - a.scale).unwrap_or(0))
+        };
+        split(self).cmp(&split(other))
+    }
+}
+
+impl PartialOrd for Amount {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Amount {
+    type Err = String;
+
+    /// Parses digits, optionally followed by `.` and more digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() || !digits(int) || !digits(frac) || (s.contains('.') && frac.is_empty()) {
+            return Err(format!("invalid amount: {}", s));
+        }
+        let frac = frac.trim_end_matches('0');
+        if frac.len() as u32 > MAX_SCALE {
+            return Err(format!("amount has more than {} decimal places: {}", MAX_SCALE, s));
+        }
+        let mut units: u128 = 0;
+        for b in int.bytes().chain(frac.bytes()) {
+            units = units
+                .checked_mul(10)
+                .and_then(|u| u.checked_add(u128::from(b - b'0')))
+                .ok_or_else(|| format!("amount too large: {}", s))?;
+        }
+        Ok(Self {
+            units,
+            scale: frac.len() as u32,
+        }
+        .normalized())
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Canonical form: no leading or trailing zeros beyond the units digit
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:0>width$}", self.units, width = self.scale as usize + 1);
+        let (int, frac) = digits.split_at(digits.len() - self.scale as usize);
+        if frac.is_empty() {
+            write!(f, "{}", int)
+        } else {
+            write!(f, "{}.{}", int, frac)
+        }
+    }
+}
+
+/// Serialized as a decimal string, so no precision is lost
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accepts decimal strings and, for configuration written by hand,
+/// non-negative JSON numbers
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Amount;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a non-negative decimal amount")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Amount, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Amount, E> {
+                Ok(Amount::from_base_units(u128::from(v), 0))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Amount, E> {
+                u64::try_from(v).map_err(E::custom).and_then(|v| self.visit_u64(v))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Amount, E> {
+                // f64's Display is the shortest exact decimal, never exponential
+                self.visit_str(&v.to_string())
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn amount(s: &str) -> Amount {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_canonical_form() {
+        assert_eq!(amount("1000.00"), amount("1000"));
+        assert_eq!(amount("0001000.0").to_string(), "1000");
+        assert_eq!(amount("0.050").to_string(), "0.05");
+        assert_eq!(amount("0.000").to_string(), "0");
+        assert!(amount("0").is_zero());
+        for invalid in ["", ".5", "5.", "1.2.3", "-1", "1e3", " 1", "\u{661}"] {
+            assert!(invalid.parse::<Amount>().is_err(), "{:?}", invalid);
+        }
+        assert!("340282366920938463463374607431768211456".parse::<Amount>().is_err());
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let cases = [
+            // amount, Down, Up, HalfUp, HalfEven at 2 decimals
+            ("1.234", 123, 124, 123, 123),
+            ("1.235", 123, 124, 124, 124),
+            ("1.245", 124, 125, 125, 124),
+            ("1.2451", 124, 125, 125, 125),
+            ("1.23", 123, 123, 123, 123),
+        ];
+        for (value, down, up, half_up, half_even) in cases {
+            let a = amount(value);
+            assert_eq!(a.to_base_units(2, Rounding::Down).unwrap(), down, "{}", value);
+            assert_eq!(a.to_base_units(2, Rounding::Up).unwrap(), up, "{}", value);
+            assert_eq!(a.to_base_units(2, Rounding::HalfUp).unwrap(), half_up, "{}", value);
+            assert_eq!(a.to_base_units(2, Rounding::HalfEven).unwrap(), half_even, "{}", value);
+        }
+        assert!(amount("1.235").to_base_units_exact(2).is_err());
+        assert_eq!(amount("1.20").to_base_units_exact(2).unwrap(), 120);
+        assert!(amount("340282366920938463463374607431768211455").to_base_units(1, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic_and_ordering() {
+        assert_eq!(amount("1000.50").checked_sub(&amount("0.5")).unwrap().to_string(), "1000");
+        assert_eq!(amount("0.1").checked_add(&amount("0.25")).unwrap(), amount("0.35"));
+        assert!(amount("1").checked_sub(&amount("1.01")).is_none());
+        assert!(amount("340282366920938463463374607431768211455").checked_add(&amount("1")).is_none());
+
+        assert!(amount("1.5") > amount("1.25"));
+        assert!(amount("2") > amount("1.99999999999999999999999999999999999999"));
+        assert_eq!(amount("1.50").cmp(&amount("1.5")), std::cmp::Ordering::Equal);
+        // Ordering works where aligning both values would overflow
+        assert!(amount("340282366920938463463374607431768211455") > amount("0.00000000000000000000000000000000000001"));
+    }
+
+    #[test]
+    fn test_base_units_round_trip() {
+        let mut rng = StdRng::seed_from_u64(2379);
+        for _ in 0..5_000 {
+            let decimals = rng.gen_range(0..=18);
+            let units: u128 = rng.gen_range(0..u64::MAX as u128);
+            let a = Amount::from_base_units(units, decimals);
+            assert_eq!(a.to_base_units_exact(decimals).unwrap(), units);
+            assert_eq!(amount(&a.to_string()), a);
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(serde_json::to_string(&amount("1000.50")).unwrap(), "\"1000.5\"");
+        assert_eq!(serde_json::from_str::<Amount>("\"0.1\"").unwrap(), amount("0.1"));
+        assert_eq!(serde_json::from_str::<Amount>("50000").unwrap(), amount("50000"));
+        assert_eq!(serde_json::from_str::<Amount>("0.1").unwrap(), amount("0.1"));
+        assert!(serde_json::from_str::<Amount>("-1").is_err());
+        assert!(serde_json::from_str::<Amount>("\"1e3\"").is_err());
+    }
+}
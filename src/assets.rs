@@ -0,0 +1,178 @@
+//! Asset registry.
+//!
+//! Per-asset settings from `SdkConfig::assets`, keyed by symbol
+//! (case-insensitive), with optional per-chain overrides:
+//!
+//! ```
+//! use ecash_sdk_core::assets::AssetRegistry;
+//! use ecash_sdk_core::ChainId;
+//!
+//! let registry: AssetRegistry = serde_json::from_str(r#"{
+//!     "USDC": {
+//!         "decimals": 6,
+//!         "min_amount": 1.0,
+//!         "dust_threshold": 0.01,
+//!         "chains": { "ethereum": { "min_amount": 25.0 } }
+//!     }
+//! }"#).unwrap();
+//! assert_eq!(registry.min_amount("usdc", ChainId::Base), Some(1.0));
+//! assert_eq!(registry.min_amount("usdc", ChainId::Ethereum), Some(25.0));
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::{Amount, Rounding};
+use crate::types::ChainId;
+
+/// Decimals of common assets, used when the registry doesn't set them
+const WELL_KNOWN_DECIMALS: &[(&str, u32)] = &[
+    ("USDC", 6),
+    ("USDT", 6),
+    ("DAI", 18),
+    ("ETH", 18),
+    ("WETH", 18),
+    ("SOL", 9),
+];
+
+/// Limits overriding the asset-wide ones on one chain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChainLimits {
+    pub min_amount: Option<f64>,
+    pub dust_threshold: Option<f64>,
+}
+
+/// Settings for one asset
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AssetConfig {
+    /// Digits after the decimal point in the asset's smallest unit
+    pub decimals: Option<u32>,
+    /// Smallest amount worth transferring (fees would exceed anything less)
+    pub min_amount: Option<f64>,
+    /// Shielded change below this is not worth a note of its own
+    pub dust_threshold: Option<f64>,
+    pub chains: HashMap<ChainId, ChainLimits>,
+}
+
+/// Asset settings keyed by symbol
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AssetRegistry {
+    assets: HashMap<String, AssetConfig>,
+}
+
+impl AssetRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the settings of `asset`
+    pub fn with_asset(mut self, asset: &str, config: AssetConfig) -> Self {
+        self.assets.insert(asset.to_uppercase(), config);
+        self
+    }
+
+    pub fn get(&self, asset: &str) -> Option<&AssetConfig> {
+        self.assets
+            .get(asset)
+            .or_else(|| self.assets.iter().find(|(a, _)| a.eq_ignore_ascii_case(asset)).map(|(_, c)| c))
+    }
+
+    /// Decimals of `asset`, falling back to well-known values for common assets
+    pub fn decimals(&self, asset: &str) -> Option<u32> {
+        self.get(asset).and_then(|c| c.decimals).or_else(|| {
+            WELL_KNOWN_DECIMALS
+                .iter()
+                .find(|(symbol, _)| symbol.eq_ignore_ascii_case(asset))
+                .map(|&(_, decimals)| decimals)
+        })
+    }
+
+    /// `amount` in the smallest unit of `asset`; `None` if its decimals are unknown
+    pub fn to_base_units(&self, asset: &str, amount: &Amount, rounding: Rounding) -> Option<Result<u128, String>> {
+        let decimals = self.decimals(asset)?;
+        Some(amount.to_base_units(decimals, rounding))
+    }
+
+    /// Minimum transfer amount of `asset` on `chain`
+    pub fn min_amount(&self, asset: &str, chain: ChainId) -> Option<f64> {
+        let config = self.get(asset)?;
+        config.chains.get(&chain).and_then(|c| c.min_amount).or(config.min_amount)
+    }
+
+    /// Dust threshold of `asset` on `chain`
+    pub fn dust_threshold(&self, asset: &str, chain: ChainId) -> Option<f64> {
+        let config = self.get(asset)?;
+        config.chains.get(&chain).and_then(|c| c.dust_threshold).or(config.dust_threshold)
+    }
+
+    /// Dust threshold in the asset's smallest unit (needs `decimals`)
+    pub fn dust_threshold_units(&self, asset: &str, chain: ChainId) -> Option<u128> {
+        // f64's Display never uses exponents, so it always parses as an Amount
+        let threshold: Amount = self.dust_threshold(asset, chain)?.to_string().parse().ok()?;
+        self.to_base_units(asset, &threshold, Rounding::Up)?.ok()
+    }
+
+    /// Returns true if any asset has a minimum or dust threshold
+    pub fn has_limits(&self) -> bool {
+        self.assets.values().any(|a| {
+            a.min_amount.is_some()
+                || a.dust_threshold.is_some()
+                || a.chains.values().any(|c| c.min_amount.is_some() || c.dust_threshold.is_some())
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (asset, config) in &self.assets {
+            let limits = [config.min_amount, config.dust_threshold]
+                .into_iter()
+                .chain(config.chains.values().flat_map(|c| [c.min_amount, c.dust_threshold]));
+            if limits.flatten().any(|v| !v.is_finite() || v < 0.0) {
+                return Err(format!("assets.{}: limits must be non-negative numbers", asset));
+            }
+            if config.decimals.is_some_and(|d| d > 36) {
+                return Err(format!("assets.{}: decimals must be at most 36", asset));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_and_overrides() {
+        let registry = AssetRegistry::new().with_asset(
+            "usdc",
+            AssetConfig {
+                decimals: Some(6),
+                min_amount: Some(1.0),
+                dust_threshold: Some(0.01),
+                chains: HashMap::from([(
+                    ChainId::Ethereum,
+                    ChainLimits {
+                        min_amount: Some(25.0),
+                        dust_threshold: None,
+                    },
+                )]),
+            },
+        );
+        assert_eq!(registry.min_amount("USDC", ChainId::Ethereum), Some(25.0));
+        assert_eq!(registry.min_amount("USDC", ChainId::Solana), Some(1.0));
+        assert_eq!(registry.dust_threshold("Usdc", ChainId::Ethereum), Some(0.01));
+        assert_eq!(registry.dust_threshold_units("USDC", ChainId::Base), Some(10_000));
+        assert_eq!(registry.min_amount("DAI", ChainId::Base), None);
+        assert_eq!(registry.decimals("dai"), Some(18));
+        assert_eq!(registry.decimals("BONK"), None);
+        assert!(registry.has_limits());
+        assert!(!AssetRegistry::new().has_limits());
+
+        let invalid: AssetRegistry = serde_json::from_str(r#"{"USDC": {"min_amount": -1}}"#).unwrap();
+        assert!(invalid.validate().unwrap_err().contains("non-negative"));
+    }
+}
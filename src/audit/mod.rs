@@ -0,0 +1,469 @@
+//! Append-only audit log with tamper-evident hash chaining.
+//!
+//! Every entry commits to the hash of the previous one, so modifying, removing,
+//! or reordering any recorded entry breaks verification of the chain.
+//! Payloads are redacted (see `redaction`) before they are hashed and stored.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::redaction::RedactionConfig;
+
+#[cfg(feature = "audit-s3")]
+pub mod s3;
+
+/// Hash used as `prev_hash` for the first entry of a chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Category of an audit entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditKind {
+    /// Incoming transaction request
+    Request,
+    /// Routing or policy decision taken by the SDK
+    Decision,
+    /// Successful response returned to the caller
+    Response,
+    /// Error returned to the caller
+    Error,
+}
+
+/// Single hash-chained audit record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    #[serde(rename = "timestamp_ms")]
+    pub timestamp_ms: u64,
+    pub kind: AuditKind,
+    #[serde(rename = "reference_id")]
+    pub reference_id: String,
+    pub payload: serde_json::Value,
+    #[serde(rename = "prev_hash")]
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Computes the hash this entry should carry given its contents
+    pub fn compute_hash(&self) -> String {
+        let body = serde_json::json!({
+            "sequence": self.sequence,
+            "timestamp_ms": self.timestamp_ms,
+            "kind": self.kind,
+            "reference_id": self.reference_id,
+            "payload": self.payload,
+        });
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(body.to_string().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Destination for audit entries (file, object storage, SIEM, ...)
+pub trait AuditSink: Send + Sync {
+    /// Persists a single entry; called in chain order
+    fn write(&self, entry: &AuditEntry) -> Result<(), String>;
+
+    /// Identifies the sink in `AuditWriteError`s
+    fn name(&self) -> String {
+        "custom".to_string()
+    }
+}
+
+/// A sink that failed to persist an entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkFailure {
+    pub sink: String,
+    pub error: String,
+}
+
+/// An entry that was appended to the chain but not accepted by every sink.
+///
+/// The chain has still advanced, so the failed sinks now have a gap at
+/// `sequence`; the other sinks hold the entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditWriteError {
+    pub sequence: u64,
+    pub hash: String,
+    pub failures: Vec<SinkFailure>,
+}
+
+impl std::fmt::Display for AuditWriteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "audit entry {} not written to", self.sequence)?;
+        for (i, failure) in self.failures.iter().enumerate() {
+            let sep = if i == 0 { " " } else { "; " };
+            write!(f, "{}{}: {}", sep, failure.sink, failure.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AuditWriteError {}
+
+/// Keeps audit entries in memory (useful for testing and short-lived exports)
+#[derive(Default)]
+pub struct MemoryAuditSink {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl MemoryAuditSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of all recorded entries
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries.lock().map(|e| e.clone()).unwrap_or_default()
+    }
+}
+
+impl AuditSink for MemoryAuditSink {
+    fn write(&self, entry: &AuditEntry) -> Result<(), String> {
+        self.entries
+            .lock()
+            .map_err(|_| "audit sink poisoned".to_string())?
+            .push(entry.clone());
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        "memory".to_string()
+    }
+}
+
+/// Appends audit entries to a file as JSON lines
+pub struct FileAuditSink {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileAuditSink {
+    /// Opens (or creates) the audit file in append mode
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("failed to open audit file {}: {}", path.display(), e))?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Returns the path of the audit file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AuditSink for FileAuditSink {
+    fn write(&self, entry: &AuditEntry) -> Result<(), String> {
+        let line = serde_json::to_string(entry).map_err(|e| format!("failed to encode audit entry: {}", e))?;
+        let mut file = self.file.lock().map_err(|_| "audit sink poisoned".to_string())?;
+        writeln!(file, "{}", line).map_err(|e| format!("failed to write audit entry: {}", e))?;
+        file.flush().map_err(|e| format!("failed to flush audit file: {}", e))
+    }
+
+    fn name(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+}
+
+/// Reads entries previously written by a `FileAuditSink`
+pub fn read_audit_file(path: impl AsRef<Path>) -> Result<Vec<AuditEntry>, String> {
+    let file = File::open(path.as_ref()).map_err(|e| format!("failed to open audit file: {}", e))?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line.map_err(|e| format!("failed to read line {}: {}", i + 1, e))?;
+            serde_json::from_str(&line).map_err(|e| format!("invalid entry on line {}: {}", i + 1, e))
+        })
+        .collect()
+}
+
+/// Verifies that entries form an unbroken hash chain starting from genesis.
+///
+/// Returns the sequence number of the first invalid entry in the error message.
+pub fn verify_chain(entries: &[AuditEntry]) -> Result<(), String> {
+    let mut prev_hash = GENESIS_HASH.to_string();
+    for (i, entry) in entries.iter().enumerate() {
+        if entry.sequence != i as u64 {
+            return Err(format!(
+                "sequence gap at entry {}: expected {}, found {}",
+                i, i, entry.sequence
+            ));
+        }
+        if entry.prev_hash != prev_hash {
+            return Err(format!("broken chain link at sequence {}", entry.sequence));
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(format!("hash mismatch at sequence {}", entry.sequence));
+        }
+        prev_hash = entry.hash.clone();
+    }
+    Ok(())
+}
+
+struct ChainState {
+    next_sequence: u64,
+    last_hash: String,
+}
+
+/// Hash-chaining audit logger fanning entries out to one or more sinks.
+///
+/// Payloads are redacted with `RedactionConfig::default()` unless another
+/// configuration is set with `with_redaction`.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use ecash_sdk_core::audit::{verify_chain, AuditKind, AuditLogger, MemoryAuditSink};
+///
+/// let sink = Arc::new(MemoryAuditSink::new());
+/// let logger = AuditLogger::new().with_sink(sink.clone());
+/// logger.record(AuditKind::Request, "ref_001", serde_json::json!({"amount": "10"})).unwrap();
+/// assert!(verify_chain(&sink.entries()).is_ok());
+/// ```
+pub struct AuditLogger {
+    sinks: Vec<Arc<dyn AuditSink>>,
+    redaction: RedactionConfig,
+    state: Mutex<ChainState>,
+}
+
+impl Default for AuditLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuditLogger {
+    /// Creates a logger with no sinks starting a new chain
+    pub fn new() -> Self {
+        Self {
+            sinks: Vec::new(),
+            redaction: RedactionConfig::default(),
+            state: Mutex::new(ChainState {
+                next_sequence: 0,
+                last_hash: GENESIS_HASH.to_string(),
+            }),
+        }
+    }
+
+    /// Continues an existing chain (e.g. after a restart) from its last entry
+    pub fn resume_from(last: &AuditEntry) -> Self {
+        Self {
+            sinks: Vec::new(),
+            redaction: RedactionConfig::default(),
+            state: Mutex::new(ChainState {
+                next_sequence: last.sequence + 1,
+                last_hash: last.hash.clone(),
+            }),
+        }
+    }
+
+    /// Adds a sink that receives every subsequent entry
+    pub fn with_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Sets how sensitive payload values are redacted before being recorded
+    pub fn with_redaction(mut self, redaction: RedactionConfig) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Appends an entry to the chain, then writes it to every sink.
+    ///
+    /// The chain advances even if some sinks fail, so all sinks agree on the
+    /// sequence and hash of every entry; the failures are returned.
+    pub fn record(
+        &self,
+        kind: AuditKind,
+        reference_id: &str,
+        mut payload: serde_json::Value,
+    ) -> Result<AuditEntry, AuditWriteError> {
+        self.redaction.redact_json(&mut payload);
+
+        // Held while writing so sinks see entries in chain order. The state
+        // is updated before any sink runs, so it stays valid if one panics.
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut entry = AuditEntry {
+            sequence: state.next_sequence,
+            timestamp_ms,
+            kind,
+            reference_id: reference_id.to_string(),
+            payload,
+            prev_hash: state.last_hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        state.next_sequence += 1;
+        state.last_hash = entry.hash.clone();
+
+        let failures: Vec<SinkFailure> = self
+            .sinks
+            .iter()
+            .filter_map(|sink| {
+                sink.write(&entry).err().map(|error| SinkFailure {
+                    sink: sink.name(),
+                    error,
+                })
+            })
+            .collect();
+        if failures.is_empty() {
+            Ok(entry)
+        } else {
+            Err(AuditWriteError {
+                sequence: entry.sequence,
+                hash: entry.hash,
+                failures,
+            })
+        }
+    }
+
+    /// Returns the hash of the most recent entry (the chain head)
+    pub fn head(&self) -> String {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .last_hash
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logger_with_memory() -> (AuditLogger, Arc<MemoryAuditSink>) {
+        let sink = Arc::new(MemoryAuditSink::new());
+        (AuditLogger::new().with_sink(sink.clone()), sink)
+    }
+
+    #[test]
+    fn test_record_chains_entries() {
+        let (logger, sink) = logger_with_memory();
+        let first = logger.record(AuditKind::Request, "ref_001", serde_json::json!({"a": 1})).unwrap();
+        let second = logger.record(AuditKind::Response, "ref_001", serde_json::json!({"b": 2})).unwrap();
+
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(logger.head(), second.hash);
+        assert!(verify_chain(&sink.entries()).is_ok());
+    }
+
+    #[test]
+    fn test_verify_detects_tampered_payload() {
+        let (logger, sink) = logger_with_memory();
+        logger.record(AuditKind::Request, "ref_001", serde_json::json!({"amount": "10"})).unwrap();
+        logger.record(AuditKind::Response, "ref_001", serde_json::json!({})).unwrap();
+
+        let mut entries = sink.entries();
+        entries[0].payload = serde_json::json!({"amount": "1000"});
+        assert!(verify_chain(&entries).unwrap_err().contains("hash mismatch at sequence 0"));
+    }
+
+    #[test]
+    fn test_verify_detects_removed_entry() {
+        let (logger, sink) = logger_with_memory();
+        for i in 0..3 {
+            logger.record(AuditKind::Decision, "ref_001", serde_json::json!({ "i": i })).unwrap();
+        }
+
+        let mut entries = sink.entries();
+        entries.remove(1);
+        assert!(verify_chain(&entries).is_err());
+    }
+
+    #[test]
+    fn test_resume_from_continues_chain() {
+        let (logger, sink) = logger_with_memory();
+        let last = logger.record(AuditKind::Request, "ref_001", serde_json::json!({})).unwrap();
+
+        let resumed = AuditLogger::resume_from(&last).with_sink(sink.clone());
+        resumed.record(AuditKind::Response, "ref_001", serde_json::json!({})).unwrap();
+        assert!(verify_chain(&sink.entries()).is_ok());
+    }
+
+    struct FailingSink;
+
+    impl AuditSink for FailingSink {
+        fn write(&self, _entry: &AuditEntry) -> Result<(), String> {
+            Err("bucket unavailable".to_string())
+        }
+    }
+
+    #[test]
+    fn test_failed_sink_does_not_fork_chain() {
+        let healthy = Arc::new(MemoryAuditSink::new());
+        let logger = AuditLogger::new().with_sink(Arc::new(FailingSink)).with_sink(healthy.clone());
+
+        let err = logger.record(AuditKind::Request, "ref_001", serde_json::json!({})).unwrap_err();
+        assert_eq!(
+            err.failures,
+            vec![SinkFailure {
+                sink: "custom".to_string(),
+                error: "bucket unavailable".to_string()
+            }]
+        );
+        assert_eq!(err.to_string(), "audit entry 0 not written to custom: bucket unavailable");
+        assert_eq!(logger.head(), err.hash);
+
+        // The next entry links to the one the healthy sink accepted
+        let next = logger.record(AuditKind::Response, "ref_001", serde_json::json!({})).unwrap_err();
+        assert_eq!(next.sequence, 1);
+        assert_eq!(healthy.entries()[1].prev_hash, err.hash);
+        assert!(verify_chain(&healthy.entries()).is_ok());
+    }
+
+    #[test]
+    fn test_payloads_are_redacted() {
+        let (logger, sink) = logger_with_memory();
+        let payload = serde_json::json!({ "amount": "10", "recipient": "0xabc", "asset": "USDC" });
+        logger.record(AuditKind::Request, "ref_001", payload.clone()).unwrap();
+
+        let stored = &sink.entries()[0];
+        assert_eq!(stored.payload["amount"], "[redacted]");
+        assert_ne!(stored.payload["recipient"], "0xabc");
+        assert_eq!(stored.payload["asset"], "USDC");
+        assert!(verify_chain(&sink.entries()).is_ok());
+
+        let (logger, sink) = logger_with_memory();
+        let logger = logger.with_redaction(RedactionConfig::disabled());
+        logger.record(AuditKind::Request, "ref_001", payload.clone()).unwrap();
+        assert_eq!(sink.entries()[0].payload, payload);
+    }
+
+    #[test]
+    fn test_file_sink_round_trip() {
+        let path = std::env::temp_dir().join(format!("ecash-audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let sink = Arc::new(FileAuditSink::open(&path).unwrap());
+        let logger = AuditLogger::new().with_sink(sink);
+        logger.record(AuditKind::Request, "ref_001", serde_json::json!({"x": 1})).unwrap();
+        logger.record(AuditKind::Error, "ref_001", serde_json::json!({"y": 2})).unwrap();
+
+        let entries = read_audit_file(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(verify_chain(&entries).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,213 @@
+//! S3 audit sink: one immutable object per entry.
+
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client;
+use tokio::runtime::Runtime;
+
+use super::{AuditEntry, AuditSink};
+
+/// `AuditSink` storing each entry as `<prefix><sequence>.json` in an S3 bucket.
+///
+/// Sequence numbers are zero-padded so objects list in chain order, and
+/// objects are written with `If-None-Match: *` so an entry that already exists
+/// is never overwritten. Uploads run on a runtime owned by the sink, so
+/// `write` can be called from sync code and from within another runtime; it
+/// blocks the calling thread until S3 responds.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use ecash_sdk_core::audit::{s3::S3AuditSink, AuditLogger};
+///
+/// fn audit_logger(s3: aws_sdk_s3::Client) -> Result<AuditLogger, String> {
+///     let sink = S3AuditSink::new(s3, "acme-audit")?.with_prefix("payments/");
+///     Ok(AuditLogger::new().with_sink(Arc::new(sink)))
+/// }
+/// ```
+pub struct S3AuditSink {
+    client: Client,
+    bucket: String,
+    prefix: String,
+    runtime: Option<Runtime>,
+}
+
+impl S3AuditSink {
+    /// Writes to `bucket` with a client configured by the caller (region,
+    /// credentials, endpoint)
+    pub fn new(client: Client, bucket: impl Into<String>) -> Result<Self, String> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("ecash-audit-s3")
+            .enable_all()
+            .build()
+            .map_err(|e| format!("failed to start S3 audit runtime: {}", e))?;
+        Ok(Self {
+            client,
+            bucket: bucket.into(),
+            prefix: String::new(),
+            runtime: Some(runtime),
+        })
+    }
+
+    /// Key prefix for the objects, e.g. `"payments/"`
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Key of the object holding the entry with the given sequence number
+    pub fn key(&self, sequence: u64) -> String {
+        format!("{}{:020}.json", self.prefix, sequence)
+    }
+
+    /// Reads back every entry under the prefix, in chain order, for export
+    /// or `verify_chain`
+    pub fn read_entries(&self) -> Result<Vec<AuditEntry>, String> {
+        let client = self.client.clone();
+        let bucket = self.bucket.clone();
+        let prefix = self.prefix.clone();
+        self.run(async move {
+            let mut keys = Vec::new();
+            let mut pages = client.list_objects_v2().bucket(&bucket).prefix(&prefix).into_paginator().send();
+            while let Some(page) = pages.next().await {
+                let page = page.map_err(|e| format!("failed to list audit objects: {}", e))?;
+                keys.extend(page.contents().iter().filter_map(|o| o.key().map(str::to_string)));
+            }
+            keys.sort();
+
+            let mut entries = Vec::with_capacity(keys.len());
+            for key in keys {
+                let object = client
+                    .get_object()
+                    .bucket(&bucket)
+                    .key(&key)
+                    .send()
+                    .await
+                    .map_err(|e| format!("failed to read audit object {}: {}", key, e))?;
+                let body = object
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| format!("failed to read audit object {}: {}", key, e))?;
+                entries.push(
+                    serde_json::from_slice(&body.into_bytes())
+                        .map_err(|e| format!("invalid audit object {}: {}", key, e))?,
+                );
+            }
+            Ok(entries)
+        })
+    }
+
+    /// Runs `fut` on the sink's runtime and waits for its result
+    fn run<T: Send + 'static>(
+        &self,
+        fut: impl std::future::Future<Output = Result<T, String>> + Send + 'static,
+    ) -> Result<T, String> {
+        let runtime = self.runtime.as_ref().ok_or("S3 audit sink is shut down")?;
+        let (tx, rx) = std::sync::mpsc::channel();
+        runtime.spawn(async move {
+            let _ = tx.send(fut.await);
+        });
+        rx.recv().map_err(|_| "S3 audit upload was aborted".to_string())?
+    }
+}
+
+impl AuditSink for S3AuditSink {
+    fn write(&self, entry: &AuditEntry) -> Result<(), String> {
+        let body = serde_json::to_vec(entry).map_err(|e| format!("failed to encode audit entry: {}", e))?;
+        let request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.key(entry.sequence))
+            .content_type("application/json")
+            .if_none_match("*")
+            .body(ByteStream::from(body));
+        let key = self.key(entry.sequence);
+        self.run(async move {
+            request
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("failed to upload audit object {}: {}", key, e))
+        })
+    }
+
+    fn name(&self) -> String {
+        format!("s3://{}/{}", self.bucket, self.prefix)
+    }
+}
+
+impl Drop for S3AuditSink {
+    fn drop(&mut self) {
+        // Dropping a runtime blocks, which panics inside another runtime
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditKind, AuditLogger};
+    use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answers every request with `status` and records the request heads
+    async fn fake_s3(status: &'static str) -> (u16, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = vec![0u8; 64 * 1024];
+                let n = socket.read(&mut buf).await.unwrap_or(0);
+                let head = String::from_utf8_lossy(&buf[..n]).split("\r\n\r\n").next().unwrap_or("").to_string();
+                seen.lock().unwrap().push(head);
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (port, requests)
+    }
+
+    fn sink(port: u16) -> S3AuditSink {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .endpoint_url(format!("http://127.0.0.1:{}", port))
+            .credentials_provider(Credentials::new("AKIDEXAMPLE", "secret", None, None, "test"))
+            .force_path_style(true)
+            .retry_config(aws_sdk_s3::config::retry::RetryConfig::disabled())
+            .build();
+        S3AuditSink::new(Client::from_conf(config), "audit").unwrap().with_prefix("payments/")
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_write_puts_one_object_per_entry() {
+        let (port, requests) = fake_s3("200 OK").await;
+        let sink = Arc::new(sink(port));
+        let logger = AuditLogger::new().with_sink(sink.clone());
+        logger.record(AuditKind::Request, "ref_001", serde_json::json!({})).unwrap();
+
+        let head = requests.lock().unwrap()[0].to_lowercase();
+        assert!(head.starts_with("put /audit/payments/00000000000000000000.json"));
+        assert!(head.contains("if-none-match: *"));
+        assert!(head.contains("authorization: aws4-hmac-sha256"));
+        assert_eq!(sink.name(), "s3://audit/payments/");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rejected_upload_is_reported() {
+        // S3 answers 412 when the object already exists
+        let (port, _) = fake_s3("412 Precondition Failed").await;
+        let logger = AuditLogger::new().with_sink(Arc::new(sink(port)));
+
+        let err = logger.record(AuditKind::Request, "ref_001", serde_json::json!({})).unwrap_err();
+        assert_eq!(err.failures[0].sink, "s3://audit/payments/");
+        assert!(err.failures[0].error.contains("payments/00000000000000000000.json"));
+    }
+}
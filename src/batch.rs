@@ -0,0 +1,311 @@
+//! Batch import of transaction requests (e.g. payroll exports).
+//!
+//! Rows are parsed and validated independently: valid rows become
+//! `TransactionRequest`s ready for `EasyCashClient::execute_batch`, invalid
+//! rows are reported with their line number and offending field.
+
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{IntentType, TransactionRequest};
+use crate::validator;
+
+/// CSV columns that must be present in the header
+pub const REQUIRED_COLUMNS: [&str; 4] = ["reference_id", "amount", "asset", "source_chain"];
+
+/// Why a row was rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowError {
+    /// 1-based line number in the input
+    pub line: usize,
+    /// Column the error refers to, when it can be attributed to one
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.field {
+            Some(ref field) => write!(f, "line {}: {}: {}", self.line, field, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// Parsed batch
+#[derive(Debug, Clone, Default)]
+pub struct BatchImport {
+    /// Valid requests, in input order
+    pub requests: Vec<TransactionRequest>,
+    /// Rejected rows, in input order
+    pub errors: Vec<RowError>,
+}
+
+impl BatchImport {
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn push(&mut self, line: usize, req: Result<TransactionRequest, RowError>, seen: &mut HashSet<String>) {
+        let req = req.and_then(|req| {
+            validate_row(&req).map_err(|(field, message)| RowError {
+                line,
+                field,
+                message,
+            })?;
+            if !seen.insert(req.reference_id.clone()) {
+                return Err(RowError {
+                    line,
+                    field: Some("reference_id".to_string()),
+                    message: format!("duplicate reference_id {}", req.reference_id),
+                });
+            }
+            Ok(req)
+        });
+        match req {
+            Ok(req) => self.requests.push(req),
+            Err(e) => self.errors.push(e),
+        }
+    }
+}
+
+/// Parses CSV with a header row naming the columns. Besides
+/// `REQUIRED_COLUMNS`, `recipient`, `target_chain`, `is_shielded`, `type` and
+/// `correlation_id` are recognised; empty cells are treated as absent.
+///
+/// Returns `Err` only when the input cannot be read or the header is unusable.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::batch;
+///
+/// let csv = "reference_id,amount,asset,source_chain\n\
+///            salary_001,2500.00,USDC,base\n\
+///            salary_002,-1,USDC,base\n";
+/// let import = batch::from_csv(csv.as_bytes()).unwrap();
+/// assert_eq!(import.requests.len(), 1);
+/// assert_eq!(import.errors[0].line, 3);
+/// assert_eq!(import.errors[0].field.as_deref(), Some("amount"));
+/// ```
+pub fn from_csv(reader: impl Read) -> Result<BatchImport, String> {
+    let mut lines = BufReader::new(reader).lines().enumerate();
+    let header = loop {
+        match lines.next() {
+            Some((_, line)) => {
+                let line = line.map_err(|e| format!("failed to read CSV: {}", e))?;
+                if !line.trim().is_empty() {
+                    break split_csv_line(&line).map_err(|e| format!("invalid CSV header: {}", e))?;
+                }
+            }
+            None => return Err("CSV input is empty".to_string()),
+        }
+    };
+    let header: Vec<String> = header.into_iter().map(|h| h.trim().to_lowercase()).collect();
+    for required in REQUIRED_COLUMNS {
+        if !header.iter().any(|h| h == required) {
+            return Err(format!("CSV header is missing column {}", required));
+        }
+    }
+
+    let mut import = BatchImport::default();
+    let mut seen = HashSet::new();
+    for (index, line) in lines {
+        let line = line.map_err(|e| format!("failed to read CSV: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_no = index + 1;
+        let req = split_csv_line(&line)
+            .map_err(|message| RowError {
+                line: line_no,
+                field: None,
+                message,
+            })
+            .and_then(|cells| csv_row(&header, &cells, line_no));
+        import.push(line_no, req, &mut seen);
+    }
+    Ok(import)
+}
+
+/// Parses one JSON `TransactionRequest` per line (the SDK's wire format).
+///
+/// Returns `Err` only when the input cannot be read.
+pub fn from_jsonl(reader: impl Read) -> Result<BatchImport, String> {
+    let mut import = BatchImport::default();
+    let mut seen = HashSet::new();
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line.map_err(|e| format!("failed to read JSONL: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_no = index + 1;
+        let req = serde_json::from_str::<TransactionRequest>(&line).map_err(|e| RowError {
+            line: line_no,
+            field: None,
+            message: format!("invalid JSON: {}", e),
+        });
+        import.push(line_no, req, &mut seen);
+    }
+    Ok(import)
+}
+
+fn csv_row(header: &[String], cells: &[String], line: usize) -> Result<TransactionRequest, RowError> {
+    let field = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .and_then(|i| cells.get(i))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    };
+    let err = |name: &str, message: String| RowError {
+        line,
+        field: Some(name.to_string()),
+        message,
+    };
+    let required = |name: &str| field(name).ok_or_else(|| err(name, "is required".to_string()));
+
+    let reference_id = required("reference_id")?;
+    let intent_type = match field("type") {
+        Some(v) => v.parse().map_err(|e| err("type", e))?,
+        None => IntentType::Transfer,
+    };
+    let amount = required("amount")?;
+    let asset = required("asset")?;
+    let source_chain = required("source_chain")?
+        .parse()
+        .map_err(|e| err("source_chain", e))?;
+
+    Ok(TransactionRequest {
+        recipient: field("recipient").map(str::to_string),
+        target_chain: field("target_chain")
+            .map(str::parse)
+            .transpose()
+            .map_err(|e| err("target_chain", e))?,
+        is_shielded: match field("is_shielded").map(str::to_lowercase).as_deref() {
+            None | Some("false") | Some("0") | Some("no") => false,
+            Some("true") | Some("1") | Some("yes") => true,
+            Some(other) => return Err(err("is_shielded", format!("expected true or false, got {}", other))),
+        },
+        correlation_id: field("correlation_id").map(str::to_string),
+        ..TransactionRequest::new(reference_id, intent_type, amount, asset, source_chain)
+    })
+}
+
+/// Validates a row, attributing the failure to a field where possible
+fn validate_row(req: &TransactionRequest) -> Result<(), (Option<String>, String)> {
+    let field = |name: &str| Some(name.to_string());
+    if req.reference_id.trim().is_empty() {
+        return Err((field("reference_id"), "is required".to_string()));
+    }
+    if req.asset.trim().is_empty() {
+        return Err((field("asset"), "is required".to_string()));
+    }
+    validator::validate_amount(&req.amount).map_err(|e| (field("amount"), e))?;
+    if let Some(ref recipient) = req.recipient {
+        validator::validate_address(recipient).map_err(|e| (field("recipient"), e))?;
+    }
+    validator::validate_transaction_request(req).map_err(|e| (None, e))
+}
+
+/// Splits a CSV line, honouring double-quoted cells (`""` escapes a quote)
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                in_quotes = true;
+            }
+            ',' if !in_quotes => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("unterminated quoted field".to_string());
+    }
+    cells.push(cell);
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ChainId;
+
+    const RECIPIENT: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+
+    #[test]
+    fn test_from_csv_reports_row_errors() {
+        let csv = format!(
+            "Reference_ID,amount,asset,source_chain,recipient,is_shielded\n\
+             pay_1,10.00,USDC,base,{r},true\n\
+             \n\
+             pay_2,abc,USDC,base,,\n\
+             pay_3,1,USDC,bitcoin,,\n\
+             pay_1,5,USDC,base,,\n\
+             pay_4,1,USDC,base,0x123,\n\
+             \"pay_5\",\"1,000\",USDC,ethereum,,no\n",
+            r = RECIPIENT
+        );
+        let import = from_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(import.requests.len(), 1);
+        assert_eq!(import.requests[0].recipient.as_deref(), Some(RECIPIENT));
+        assert!(import.requests[0].is_shielded);
+
+        let errors: Vec<(usize, Option<&str>)> =
+            import.errors.iter().map(|e| (e.line, e.field.as_deref())).collect();
+        assert_eq!(
+            errors,
+            vec![
+                (4, Some("amount")),
+                (5, Some("source_chain")),
+                (6, Some("reference_id")),
+                (7, Some("recipient")),
+                // Quoted "1,000" stays one cell and fails amount validation
+                (8, Some("amount")),
+            ]
+        );
+        assert_eq!(import.errors[1].to_string(), "line 5: source_chain: unknown chain: bitcoin");
+    }
+
+    #[test]
+    fn test_from_csv_header_errors() {
+        assert!(from_csv("".as_bytes()).is_err());
+        let err = from_csv("reference_id,amount,asset\n".as_bytes()).unwrap_err();
+        assert_eq!(err, "CSV header is missing column source_chain");
+    }
+
+    #[test]
+    fn test_from_jsonl() {
+        let jsonl = format!(
+            "{{\"reference_id\":\"j_1\",\"type\":\"transfer\",\"amount\":\"1.5\",\"asset\":\"USDC\",\"source_chain\":\"solana\",\"is_shielded\":false}}\n\
+             not json\n\
+             {{\"reference_id\":\"j_2\",\"type\":\"transfer\",\"amount\":\"0\",\"asset\":\"USDC\",\"source_chain\":\"base\",\"is_shielded\":false,\"recipient\":\"{}\"}}\n",
+            RECIPIENT
+        );
+        let import = from_jsonl(jsonl.as_bytes()).unwrap();
+        assert_eq!(import.requests.len(), 1);
+        assert_eq!(import.requests[0].source_chain, ChainId::Solana);
+        assert_eq!(import.errors[0].line, 2);
+        assert!(import.errors[0].field.is_none());
+        assert_eq!(import.errors[1].field.as_deref(), Some("amount"));
+    }
+
+    #[test]
+    fn test_split_csv_line() {
+        assert_eq!(split_csv_line(r#"a,"b ""c"", d",,e"#).unwrap(), vec!["a", "b \"c\", d", "", "e"]);
+        assert!(split_csv_line(r#"a,"b"#).is_err());
+    }
+}
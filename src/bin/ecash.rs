@@ -0,0 +1,249 @@
+//! `ecash` command-line tool for manual treasury operations.
+//!
+//! Configuration is read from the JSON file given by `--config` or
+//! `ECASH_CONFIG`, falling back to the defaults (which honour
+//! `ECASH_API_KEY`, `ECASH_API_ENDPOINT` and `ECASH_ENV`).
+
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ecash_sdk_core::batch;
+use ecash_sdk_core::chain::evm::EvmRpcAdapter;
+use ecash_sdk_core::chain::solana::SolanaRpcAdapter;
+use ecash_sdk_core::chain::ChainAdapter;
+use ecash_sdk_core::crypto::public_key_to_hex;
+use ecash_sdk_core::{ChainId, EasyCashClient, IntentType, SdkConfig, SdkErrorResponse, TransactionRequest};
+
+const USAGE: &str = "\
+Usage: ecash [--config FILE] <command> [options]
+
+Commands:
+  quote    --amount AMOUNT --asset ASSET --chain CHAIN [--to ADDRESS] [--target-chain CHAIN] [--shielded]
+  send     --amount AMOUNT --asset ASSET --chain CHAIN [--to ADDRESS] [--target-chain CHAIN] [--shielded] [--reference ID]
+  status   --chain CHAIN --tx-hash HASH [--rpc-url URL]   (or ECASH_RPC_URL)
+  batch    --csv FILE   (columns: reference_id,amount,asset,source_chain[,recipient,target_chain,is_shielded,type])
+           Invalid rows are reported and nothing is sent.
+  metrics
+  keygen";
+
+/// Parsed command line
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: String,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(argv: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut command = None;
+        let mut options = HashMap::new();
+        let mut argv = argv.into_iter();
+        while let Some(arg) = argv.next() {
+            match arg.strip_prefix("--") {
+                // Boolean flag
+                Some("shielded") => {
+                    options.insert("shielded".to_string(), "true".to_string());
+                }
+                Some(name) => {
+                    let value = argv.next().ok_or_else(|| format!("missing value for --{}", name))?;
+                    options.insert(name.to_string(), value);
+                }
+                None if command.is_none() => command = Some(arg),
+                None => return Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+        Ok(Self {
+            command: command.ok_or("missing command")?,
+            options,
+        })
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    fn require(&self, name: &str) -> Result<&str, String> {
+        self.get(name).ok_or_else(|| format!("missing --{}", name))
+    }
+
+    fn chain(&self, name: &str) -> Result<Option<ChainId>, String> {
+        self.get(name).map(str::parse).transpose()
+    }
+
+    fn transaction_request(&self) -> Result<TransactionRequest, String> {
+        let reference_id = self
+            .get("reference")
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("cli_{}", uuid::Uuid::new_v4().simple()));
+        let intent_type = self.get("type").map(str::parse).transpose()?.unwrap_or(IntentType::Transfer);
+        let amount = self.require("amount")?;
+        let asset = self.require("asset")?;
+        let source_chain = self.chain("chain")?.ok_or("missing --chain")?;
+
+        Ok(TransactionRequest {
+            recipient: self.get("to").map(str::to_string),
+            target_chain: self.chain("target-chain")?,
+            is_shielded: self.get("shielded") == Some("true"),
+            ..TransactionRequest::new(reference_id, intent_type, amount, asset, source_chain)
+        })
+    }
+}
+
+fn load_config(args: &Args) -> Result<SdkConfig, String> {
+    let path = args.get("config").map(str::to_string).or_else(|| std::env::var("ECASH_CONFIG").ok());
+    let config = match path {
+        Some(path) => SdkConfig::from_file(path)?,
+        None => SdkConfig::default(),
+    };
+    config.validate()?;
+    Ok(config)
+}
+
+fn print_json<T: serde::Serialize>(value: &T) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_else(|_| "null".to_string()));
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    if args.command == "keygen" {
+        let secret = k256::SecretKey::random(&mut rand::rngs::OsRng);
+        print_json(&serde_json::json!({
+            "secret_key": format!("0x{}", hex::encode(secret.to_bytes())),
+            "public_key": public_key_to_hex(&secret.public_key()),
+        }));
+        return Ok(());
+    }
+
+    let config = load_config(&args)?;
+    let timeout = config.timeout;
+    let mut client = EasyCashClient::new(Some(config)).map_err(|e| e.to_string())?;
+
+    match args.command.as_str() {
+        "quote" => {
+            let quote = client.get_quote(&args.transaction_request()?).await.map_err(error_json)?;
+            print_json(&serde_json::json!({
+                "agent_id": quote.agent_id,
+                "estimated_fee": quote.estimated_fee,
+                "estimated_fee_usd": quote.estimated_fee_usd,
+                "estimated_time_ms": quote.estimated_time.as_millis() as u64,
+                "route": quote.route,
+                "security_score": quote.security_score,
+            }));
+        }
+        "send" => {
+            let resp = client.execute_transaction(&args.transaction_request()?).await.map_err(error_json)?;
+            print_json(&resp);
+        }
+        "status" => {
+            let chain = args.chain("chain")?.ok_or("missing --chain")?;
+            let tx_hash = args.require("tx-hash")?;
+            let url = match args.get("rpc-url") {
+                Some(url) => url.to_string(),
+                None => std::env::var("ECASH_RPC_URL").map_err(|_| "missing --rpc-url or ECASH_RPC_URL")?,
+            };
+            let adapter: Arc<dyn ChainAdapter> = match chain {
+                ChainId::Solana => Arc::new(SolanaRpcAdapter::new(url, timeout)),
+                evm => Arc::new(EvmRpcAdapter::new(evm, url, timeout)),
+            };
+            client = client.with_chain_adapter(adapter);
+            match client.get_transaction_status(chain, tx_hash).await.map_err(error_json)? {
+                Some(receipt) => print_json(&receipt),
+                None => print_json(&serde_json::json!({ "tx_hash": tx_hash, "status": "pending" })),
+            }
+        }
+        "batch" => {
+            let path = args.require("csv")?;
+            let file = std::fs::File::open(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+            let import = batch::from_csv(file)?;
+            if !import.is_clean() {
+                let errors: Vec<String> = import.errors.iter().map(|e| e.to_string()).collect();
+                return Err(format!("{} invalid row(s), nothing sent:\n{}", errors.len(), errors.join("\n")));
+            }
+            let mut failed = 0;
+            let results = client.execute_batch(&import.requests).await;
+            for (req, result) in import.requests.iter().zip(results) {
+                // One JSON line per row so results can be piped into other tools
+                let line = match result {
+                    Ok(resp) => serde_json::json!({ "reference_id": req.reference_id, "ok": true, "response": resp }),
+                    Err(e) => {
+                        failed += 1;
+                        serde_json::json!({ "reference_id": req.reference_id, "ok": false, "error": SdkErrorResponse::from(&e) })
+                    }
+                };
+                println!("{}", line);
+            }
+            if failed > 0 {
+                return Err(format!("{} transaction(s) failed", failed));
+            }
+        }
+        "metrics" => print_json(&client.metrics_snapshot()),
+        other => return Err(format!("unknown command: {}\n\n{}", other, USAGE)),
+    }
+    Ok(())
+}
+
+fn error_json(err: ecash_sdk_core::SdkError) -> String {
+    serde_json::to_string_pretty(&SdkErrorResponse::from(&err)).unwrap_or_else(|_| err.to_string())
+}
+
+fn main() -> ExitCode {
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+    if argv.is_empty() || argv.iter().any(|a| a == "--help" || a == "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let args = match Args::parse(argv) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let runtime = match tokio::runtime::Builder::new_multi_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: failed to start runtime: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    // The runtime is only used for this one command; don't wait on idle timers
+    let result = runtime.block_on(run(args));
+    runtime.shutdown_timeout(Duration::from_secs(1));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        Args::parse(s.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse_send() {
+        let args = args("send --amount 10.00 --asset USDC --chain base --shielded --reference ref_1").unwrap();
+        assert_eq!(args.command, "send");
+        let req = args.transaction_request().unwrap();
+        assert_eq!(req.reference_id, "ref_1");
+        assert_eq!(req.source_chain, ChainId::Base);
+        assert!(req.is_shielded);
+        assert!(req.target_chain.is_none());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(args("--amount").unwrap_err(), "missing value for --amount");
+        assert_eq!(args("send extra").unwrap_err(), "unexpected argument: extra");
+        let req = args("quote --amount 1 --asset USDC").unwrap().transaction_request();
+        assert_eq!(req.unwrap_err(), "missing --chain");
+    }
+}
@@ -0,0 +1,118 @@
+//! Synchronous facade over `EasyCashClient` for non-async backends.
+//!
+//! The facade owns a small Tokio runtime and blocks the calling thread on each
+//! call. It must not be used from inside an async context (calling it from a
+//! Tokio worker thread panics); async code should use `EasyCashClient` directly.
+
+use std::collections::HashMap;
+
+use crate::agent::RouteQuote;
+use crate::client::EasyCashClient;
+use crate::config::SdkConfig;
+use crate::errors::{ErrorCode, Result, SdkError};
+use crate::monitoring::MetricsSnapshot;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+/// Blocking EasyCash client
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::blocking::EasyCashClientBlocking;
+/// use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+///
+/// let client = EasyCashClientBlocking::new(None).unwrap();
+/// let req = TransactionRequest::new("order_123", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+///     .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+/// let resp = client.execute_transaction(&req).unwrap();
+/// assert_eq!(resp.status, "confirmed");
+/// ```
+pub struct EasyCashClientBlocking {
+    // Dropped before the runtime so background tasks shut down cleanly
+    inner: EasyCashClient,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl EasyCashClientBlocking {
+    /// Creates a blocking client with the given configuration
+    pub fn new(config: Option<SdkConfig>) -> Result<Self> {
+        Self::build(|| EasyCashClient::new(config))
+    }
+
+    /// Creates a blocking client from an async client built by `build`.
+    ///
+    /// `build` runs inside the facade's runtime, so it can use the async
+    /// client's `with_*` builder methods:
+    /// ```
+    /// use ecash_sdk_core::blocking::EasyCashClientBlocking;
+    /// use ecash_sdk_core::client::EasyCashClient;
+    /// use ecash_sdk_core::policy::PolicyEngine;
+    ///
+    /// let client = EasyCashClientBlocking::build(|| {
+    ///     Ok(EasyCashClient::new(None)?.with_policy_engine(PolicyEngine::new()))
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn build(build: impl FnOnce() -> Result<EasyCashClient>) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("ecash-sdk-blocking")
+            .enable_all()
+            .build()
+            .map_err(|e| SdkError::wrap(ErrorCode::InvalidRequest, "failed to start runtime", e))?;
+        let inner = {
+            let _guard = runtime.enter();
+            build()?
+        };
+        Ok(Self { inner, runtime })
+    }
+
+    /// Executes a transaction, blocking until it completes
+    pub fn execute_transaction(&self, req: &TransactionRequest) -> Result<TransactionResponse> {
+        self.runtime.block_on(self.inner.execute_transaction(req))
+    }
+
+    /// Returns the route the client would select for a request, without executing it
+    pub fn get_quote(&self, req: &TransactionRequest) -> Result<RouteQuote> {
+        self.runtime.block_on(self.inner.get_quote(req))
+    }
+
+    /// Returns current SDK performance metrics
+    pub fn get_metrics(&self) -> HashMap<String, f64> {
+        self.inner.get_metrics()
+    }
+
+    /// Returns a typed, serializable snapshot of the current SDK metrics
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.inner.metrics_snapshot()
+    }
+
+    /// Returns the wrapped async client
+    pub fn inner(&self) -> &EasyCashClient {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+
+    fn request(amount: &str) -> TransactionRequest {
+        TransactionRequest::new("ref_blocking", IntentType::Transfer, amount, "USDC", ChainId::Base)
+    }
+
+    #[test]
+    fn test_blocking_execute_and_metrics() {
+        let client = EasyCashClientBlocking::new(None).unwrap();
+
+        let quote = client.get_quote(&request("10.00")).unwrap();
+        assert!(quote.agent_id.starts_with("agent-"));
+
+        let resp = client.execute_transaction(&request("10.00")).unwrap();
+        assert_eq!(resp.status, "confirmed");
+        assert_eq!(client.get_metrics()["total_transactions"], 1.0);
+
+        let err = client.execute_transaction(&request("-1")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+    }
+}
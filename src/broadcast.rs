@@ -0,0 +1,131 @@
+//! Self-broadcast execution.
+//!
+//! Custodians that must broadcast through their own infrastructure run
+//! `EasyCashClient::execute_self_broadcast` instead of `execute_transaction`:
+//! the request goes through validation, policies and route selection as
+//! usual, then the client signs the settlement contract call (see
+//! `contracts`) with its broadcast signer and returns the raw transactions
+//! instead of handing the intent to an agent. Once broadcast,
+//! `confirm_external_broadcast` reads the receipt and books the outcome.
+
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::ContractCall;
+use crate::crypto::TransactionSigner;
+use crate::evm::permit::{self, APPROVAL_GAS_LIMIT};
+use crate::evm::tx::SignedTransaction;
+use crate::types::ChainId;
+
+/// Nonce and gas of self-broadcast transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfBroadcast {
+    /// Next nonce of the signer's account
+    pub nonce: u64,
+    /// Gas limit of the settlement transaction
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    /// Allowance to approve for the contract first, in the token's base
+    /// units or `permit::UNLIMITED`
+    pub approval: Option<String>,
+}
+
+impl SelfBroadcast {
+    pub fn new(nonce: u64, gas_limit: u64, max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Self {
+        Self {
+            nonce,
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            approval: None,
+        }
+    }
+
+    /// Signs an `approve` of `amount` before the settlement, for signers
+    /// that haven't approved the contract yet
+    pub fn with_approval(mut self, amount: impl Into<String>) -> Self {
+        self.approval = Some(amount.into());
+        self
+    }
+
+    /// Transactions making `call` on chain `chain_id`, preceded by the
+    /// approval of `token` if one is requested
+    pub fn sign(
+        &self,
+        chain_id: u64,
+        call: &ContractCall,
+        token: &str,
+        signer: &TransactionSigner,
+    ) -> Result<Vec<SignedTransaction>, String> {
+        let mut transactions = Vec::with_capacity(2);
+        let mut nonce = self.nonce;
+        if let Some(ref amount) = self.approval {
+            let approval = permit::approval(chain_id, token, &call.to, amount)?
+                .with_nonce(nonce)
+                .with_gas(APPROVAL_GAS_LIMIT, self.max_fee_per_gas, self.max_priority_fee_per_gas);
+            transactions.push(approval.sign(signer)?);
+            nonce += 1;
+        }
+        let settlement = call
+            .transaction(chain_id)
+            .with_nonce(nonce)
+            .with_gas(self.gas_limit, self.max_fee_per_gas, self.max_priority_fee_per_gas);
+        transactions.push(settlement.sign(signer)?);
+        Ok(transactions)
+    }
+}
+
+/// Signed transactions of a self-broadcast execution, with what the client
+/// decided for them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedExecution {
+    pub reference_id: String,
+    pub correlation_id: String,
+    pub chain: ChainId,
+    /// EIP-155 chain ID the transactions are signed for
+    pub chain_id: u64,
+    /// Agent whose route was selected
+    pub agent_id: String,
+    /// Contract call made by the last transaction
+    pub call: ContractCall,
+    /// Transactions to broadcast in order: the approval, if requested, then
+    /// the settlement
+    pub transactions: Vec<SignedTransaction>,
+}
+
+impl SignedExecution {
+    /// Hash of the settlement transaction, as passed to
+    /// `confirm_external_broadcast`
+    pub fn tx_hash(&self) -> &str {
+        self.transactions.last().map_or("", |tx| tx.hash.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm;
+    use k256::SecretKey;
+
+    #[test]
+    fn test_sign_with_approval() {
+        let signer = TransactionSigner::new(SecretKey::from_bytes(&[2u8; 32].into()).unwrap());
+        let usdc = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+        let call = ContractCall {
+            to: "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string(),
+            function: "refund(bytes32)".to_string(),
+            data: format!("0x{}{}", hex::encode(evm::selector("refund(bytes32)")), "00".repeat(32)),
+        };
+        let broadcast = SelfBroadcast::new(4, 120_000, 2_000_000_000, 1_000_000_000);
+        let txs = broadcast.sign(8453, &call, usdc, &signer).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!((txs[0].transaction.nonce, txs[0].transaction.to.as_str()), (4, call.to.as_str()));
+
+        let txs = broadcast.with_approval(permit::UNLIMITED).sign(8453, &call, usdc, &signer).unwrap();
+        let nonces: Vec<u64> = txs.iter().map(|tx| tx.transaction.nonce).collect();
+        assert_eq!(nonces, [4, 5]);
+        assert_eq!(txs[0].transaction.to, usdc);
+        assert!(txs[0].transaction.data.contains(&call.to[2..].to_lowercase()));
+        assert!(txs.iter().all(|tx| tx.from == evm::address(&signer.verifying_key())));
+    }
+}
@@ -0,0 +1,334 @@
+//! Routing fee budgets.
+//!
+//! Operators cap how much they spend on routing fees per asset and calendar
+//! period (UTC day or month). The client checks the selected route's fee
+//! against the budget before execution and records `fee_used` afterwards.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::amount;
+use crate::policy::{InMemoryUsageStore, UsageStore};
+use crate::solvency::parse_fee;
+
+const DAY_MS: u64 = 86_400_000;
+
+/// Calendar period a budget resets on (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Daily,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetPeriod::Daily => "daily",
+            BudgetPeriod::Monthly => "monthly",
+        }
+    }
+
+    /// Returns the start of the period containing `timestamp_ms`
+    pub fn start_ms(&self, timestamp_ms: u64) -> u64 {
+        let day = timestamp_ms / DAY_MS;
+        match self {
+            BudgetPeriod::Daily => day * DAY_MS,
+            BudgetPeriod::Monthly => {
+                let (_, _, day_of_month) = civil_from_days(day as i64);
+                (day - (day_of_month as u64 - 1)) * DAY_MS
+            }
+        }
+    }
+}
+
+/// Fee budget for one asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeBudget {
+    /// Asset the fees are charged in (e.g. "USDC")
+    pub asset: String,
+    pub period: BudgetPeriod,
+    /// Maximum fees per period, in units of `asset`
+    pub limit: f64,
+    /// Fractions of the limit (0.0-1.0) at which a warning is logged
+    #[serde(default)]
+    pub warn_at: Vec<f64>,
+    /// Rejects executions that would exceed the limit; otherwise only warns
+    #[serde(default = "default_hard_stop")]
+    pub hard_stop: bool,
+}
+
+fn default_hard_stop() -> bool {
+    true
+}
+
+impl FeeBudget {
+    /// Creates a hard-stop budget that warns at 80% of the limit
+    pub fn new(asset: impl Into<String>, period: BudgetPeriod, limit: f64) -> Self {
+        Self {
+            asset: asset.into(),
+            period,
+            limit,
+            warn_at: vec![0.8],
+            hard_stop: true,
+        }
+    }
+
+    pub fn daily(asset: impl Into<String>, limit: f64) -> Self {
+        Self::new(asset, BudgetPeriod::Daily, limit)
+    }
+
+    pub fn monthly(asset: impl Into<String>, limit: f64) -> Self {
+        Self::new(asset, BudgetPeriod::Monthly, limit)
+    }
+
+    /// Replaces the warning thresholds
+    pub fn with_warnings(mut self, warn_at: Vec<f64>) -> Self {
+        self.warn_at = warn_at;
+        self
+    }
+
+    /// Only warns when the budget is exhausted instead of rejecting executions
+    pub fn warn_only(mut self) -> Self {
+        self.hard_stop = false;
+        self
+    }
+
+    fn usage_key(&self) -> String {
+        format!("fee-budget:{}:{}", self.period.as_str(), self.asset.to_uppercase())
+    }
+}
+
+/// Budget that the next execution would exceed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetExceeded {
+    pub asset: String,
+    pub period: BudgetPeriod,
+    pub limit: f64,
+    pub spent: f64,
+    /// Fee of the rejected execution
+    pub fee: f64,
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} fee budget of {} exhausted ({} spent, fee {})",
+            self.period.as_str(),
+            self.asset,
+            self.limit,
+            self.spent,
+            self.fee
+        )
+    }
+}
+
+/// Current spend against a budget
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetStatus {
+    pub asset: String,
+    pub period: BudgetPeriod,
+    pub limit: f64,
+    pub spent: f64,
+    pub remaining: f64,
+}
+
+/// Tracks fee spend against budgets
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::budget::{FeeBudget, FeeBudgetTracker};
+///
+/// let tracker = FeeBudgetTracker::new()
+///     .with_budget(FeeBudget::daily("USDC", 50.0))
+///     .with_budget(FeeBudget::monthly("USDC", 1_000.0));
+/// assert!(tracker.check("0.05 USDC").is_ok());
+/// ```
+pub struct FeeBudgetTracker {
+    budgets: Vec<FeeBudget>,
+    store: Arc<dyn UsageStore>,
+}
+
+impl Default for FeeBudgetTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeeBudgetTracker {
+    /// Creates a tracker with no budgets and an in-memory usage store
+    pub fn new() -> Self {
+        Self {
+            budgets: Vec::new(),
+            store: Arc::new(InMemoryUsageStore::with_retention(std::time::Duration::from_secs(32 * 24 * 3600))),
+        }
+    }
+
+    /// Uses a custom usage store (e.g. shared across instances)
+    pub fn with_store(mut self, store: Arc<dyn UsageStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Adds a budget
+    pub fn with_budget(mut self, budget: FeeBudget) -> Self {
+        self.budgets.push(budget);
+        self
+    }
+
+    /// Checks whether a fee (e.g. "0.05 USDC") fits in every hard-stop budget
+    pub fn check(&self, fee: &str) -> Result<(), BudgetExceeded> {
+        self.check_at(fee, now_ms())
+    }
+
+    /// Records a paid fee and returns a message for every warning threshold
+    /// (or limit) the spend crossed
+    pub fn record(&self, fee: &str) -> Result<Vec<String>, String> {
+        self.record_at(fee, now_ms())
+    }
+
+    /// Returns the spend in the current period of every budget
+    pub fn status(&self) -> Result<Vec<BudgetStatus>, String> {
+        let now = now_ms();
+        self.budgets
+            .iter()
+            .map(|b| {
+                let spent = self.spent(b, now)?;
+                Ok(BudgetStatus {
+                    asset: b.asset.clone(),
+                    period: b.period,
+                    limit: b.limit,
+                    spent,
+                    remaining: (b.limit - spent).max(0.0),
+                })
+            })
+            .collect()
+    }
+
+    fn check_at(&self, fee: &str, now: u64) -> Result<(), BudgetExceeded> {
+        let Some((amount, asset)) = parse_fee(fee) else { return Ok(()) };
+        for budget in self.matching(asset).filter(|b| b.hard_stop) {
+            // An unreadable store counts as nothing spent rather than blocking payments
+            let spent = self.spent(budget, now).unwrap_or_else(|e| {
+                tracing::warn!("[SDK] Fee budget store unavailable: {}", e);
+                0.0
+            });
+            if spent + amount > budget.limit {
+                return Err(BudgetExceeded {
+                    asset: budget.asset.clone(),
+                    period: budget.period,
+                    limit: budget.limit,
+                    spent,
+                    fee: amount,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn record_at(&self, fee: &str, now: u64) -> Result<Vec<String>, String> {
+        let Some((exact, asset)) = amount::parse_fee(fee) else { return Ok(Vec::new()) };
+        let mut warnings = Vec::new();
+        for budget in self.matching(asset) {
+            let before = self.spent(budget, now)?;
+            self.store.record(&budget.usage_key(), exact, now)?;
+            let after = before + exact.to_f64();
+
+            let crossed = budget
+                .warn_at
+                .iter()
+                .chain(std::iter::once(&1.0))
+                .filter(|t| before < *t * budget.limit && after >= *t * budget.limit)
+                .fold(None, |max: Option<f64>, t| Some(max.map_or(*t, |m| m.max(*t))));
+            if let Some(threshold) = crossed {
+                warnings.push(format!(
+                    "{} {} fee budget at {:.0}% ({} of {})",
+                    budget.period.as_str(),
+                    budget.asset,
+                    threshold * 100.0,
+                    after,
+                    budget.limit
+                ));
+            }
+        }
+        Ok(warnings)
+    }
+
+    fn matching<'a>(&'a self, asset: &'a str) -> impl Iterator<Item = &'a FeeBudget> {
+        self.budgets.iter().filter(move |b| b.asset.eq_ignore_ascii_case(asset))
+    }
+
+    fn spent(&self, budget: &FeeBudget, now: u64) -> Result<f64, String> {
+        self.store
+            .usage_since(&budget.usage_key(), budget.period.start_ms(now))
+            .map(|spent| spent.to_f64())
+    }
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) civil date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-03-15T12:00:00Z
+    const NOW: u64 = 1_710_504_000_000;
+
+    #[test]
+    fn test_period_start() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_797), (2024, 3, 15));
+        // 2024-03-15T00:00:00Z
+        assert_eq!(BudgetPeriod::Daily.start_ms(NOW), 1_710_460_800_000);
+        // 2024-03-01T00:00:00Z
+        assert_eq!(BudgetPeriod::Monthly.start_ms(NOW), 1_709_251_200_000);
+    }
+
+    #[test]
+    fn test_hard_stop_and_warnings() {
+        let tracker = FeeBudgetTracker::new().with_budget(FeeBudget::daily("USDC", 1.0).with_warnings(vec![0.5, 0.75]));
+
+        assert!(tracker.record_at("0.25 USDC", NOW).unwrap().is_empty());
+        // Crossing several thresholds at once reports the highest
+        let warnings = tracker.record_at("0.5 usdc", NOW).unwrap();
+        assert_eq!(warnings, vec!["daily USDC fee budget at 75% (0.75 of 1)"]);
+
+        let exceeded = tracker.check_at("0.5 USDC", NOW).unwrap_err();
+        assert_eq!(exceeded.spent, 0.75);
+        assert!(tracker.check_at("0.25 USDC", NOW).is_ok());
+        // Other assets and the next day are unaffected
+        assert!(tracker.check_at("0.2 ETH", NOW).is_ok());
+        assert!(tracker.check_at("0.2 USDC", NOW + DAY_MS).is_ok());
+    }
+
+    #[test]
+    fn test_warn_only_budget_never_rejects() {
+        let tracker = FeeBudgetTracker::new().with_budget(FeeBudget::monthly("USDC", 1.0).warn_only());
+        let warnings = tracker.record_at("1.5 USDC", NOW).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("100%"));
+        assert!(tracker.check_at("1.0 USDC", NOW).is_ok());
+    }
+}
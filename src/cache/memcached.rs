@@ -0,0 +1,259 @@
+//! memcached cache backend (text protocol over a single connection).
+
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use super::CacheBackend;
+use crate::http::HttpClient;
+
+/// Longest key memcached accepts
+const MAX_KEY_LEN: usize = 250;
+
+/// `CacheBackend` storing entries in memcached.
+///
+/// Commands are sent over one connection, opened on first use through the
+/// proxy settings of its `HttpClient` and reopened after an error. Keys
+/// memcached can't store as-is (too long, or containing whitespace or
+/// control characters) are replaced by their SHA-256.
+pub struct MemcachedCacheBackend {
+    host: String,
+    port: u16,
+    timeout: Duration,
+    http: HttpClient,
+    conn: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl MemcachedCacheBackend {
+    /// Parses `memcached://host[:port]`
+    pub fn new(url: &str) -> Result<Self, String> {
+        let authority = url
+            .strip_prefix("memcached://")
+            .ok_or_else(|| format!("unsupported memcached URL (expected memcached://): {}", url))?
+            .trim_end_matches('/');
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| format!("invalid port in memcached URL: {}", url))?),
+            None => (authority, 11211),
+        };
+        if host.is_empty() {
+            return Err(format!("missing host in memcached URL: {}", url));
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            timeout: Duration::from_secs(2),
+            http: HttpClient::default(),
+            conn: Mutex::new(None),
+        })
+    }
+
+    /// Sets how long a command may take, including connecting
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Connects through the proxy settings of `http` (e.g.
+    /// `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Sends `request` and reads the reply, returning the status line and the
+    /// value of a `get`
+    async fn command(&self, request: &[u8]) -> Result<(String, Option<Vec<u8>>), String> {
+        let mut conn = self.conn.lock().await;
+        let result = tokio::time::timeout(self.timeout, async {
+            if conn.is_none() {
+                *conn = Some(BufReader::new(self.http.connect_tcp(&self.host, self.port).await?));
+            }
+            let Some(stream) = conn.as_mut() else {
+                return Err("memcached connection unavailable".to_string());
+            };
+            stream
+                .get_mut()
+                .write_all(request)
+                .await
+                .map_err(|e| format!("memcached write failed: {}", e))?;
+            read_reply(stream).await
+        })
+        .await
+        .unwrap_or_else(|_| Err(format!("memcached command timed out after {:?}", self.timeout)));
+        if result.is_err() {
+            *conn = None;
+        }
+        result
+    }
+
+    async fn store(&self, verb: &str, key: &str, value: &[u8], ttl: Duration) -> Result<bool, String> {
+        let mut request = format!("{} {} 0 {} {}\r\n", verb, wire_key(key), exptime(ttl), value.len()).into_bytes();
+        request.extend_from_slice(value);
+        request.extend_from_slice(b"\r\n");
+        match self.command(&request).await?.0.as_str() {
+            "STORED" => Ok(true),
+            "NOT_STORED" => Ok(false),
+            other => Err(format!("unexpected memcached reply to {}: {}", verb, other)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for MemcachedCacheBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.command(format!("get {}\r\n", wire_key(key)).as_bytes()).await?.1)
+    }
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), String> {
+        self.store("set", key, value, ttl).await.map(|_| ())
+    }
+
+    async fn set_if_absent(&self, key: &str, value: &[u8], ttl: Duration) -> Result<Option<Vec<u8>>, String> {
+        // `add` only stores missing keys; the existing value can expire
+        // before it is read, so try again then
+        for _ in 0..3 {
+            if self.store("add", key, value, ttl).await? {
+                return Ok(None);
+            }
+            if let Some(existing) = self.get(key).await? {
+                return Ok(Some(existing));
+            }
+        }
+        Err(format!("memcached key {} kept expiring during add", key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        match self.command(format!("delete {}\r\n", wire_key(key)).as_bytes()).await?.0.as_str() {
+            "DELETED" | "NOT_FOUND" => Ok(()),
+            other => Err(format!("unexpected memcached reply to delete: {}", other)),
+        }
+    }
+}
+
+fn wire_key(key: &str) -> String {
+    if key.len() <= MAX_KEY_LEN && !key.bytes().any(|b| b.is_ascii_whitespace() || b.is_ascii_control()) {
+        key.to_string()
+    } else {
+        hex::encode(Sha256::digest(key.as_bytes()))
+    }
+}
+
+/// Expiration in whole seconds (memcached reads values over 30 days as Unix
+/// timestamps, which cache TTLs never reach)
+fn exptime(ttl: Duration) -> u64 {
+    ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0)
+}
+
+async fn read_line(stream: &mut BufReader<TcpStream>) -> Result<String, String> {
+    let mut line = String::new();
+    stream
+        .read_line(&mut line)
+        .await
+        .map_err(|e| format!("memcached read failed: {}", e))?;
+    if !line.ends_with("\r\n") {
+        return Err("memcached connection closed".to_string());
+    }
+    line.truncate(line.len() - 2);
+    Ok(line)
+}
+
+async fn read_reply(stream: &mut BufReader<TcpStream>) -> Result<(String, Option<Vec<u8>>), String> {
+    let line = read_line(stream).await?;
+    let Some(header) = line.strip_prefix("VALUE ") else {
+        if line == "ERROR" || line.starts_with("CLIENT_ERROR") || line.starts_with("SERVER_ERROR") {
+            return Err(format!("memcached error: {}", line));
+        }
+        return Ok((line, None));
+    };
+    // VALUE <key> <flags> <bytes>
+    let len: usize = header
+        .rsplit(' ')
+        .next()
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("invalid memcached reply: {}", line))?;
+    let mut data = vec![0u8; len + 2];
+    stream
+        .read_exact(&mut data)
+        .await
+        .map_err(|e| format!("memcached read failed: {}", e))?;
+    data.truncate(len);
+    let end = read_line(stream).await?;
+    Ok((end, Some(data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+
+    /// Minimal memcached speaking the commands the backend sends (TTLs ignored)
+    async fn fake_memcached() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(socket);
+            let mut data: HashMap<String, Vec<u8>> = HashMap::new();
+            while let Ok(line) = read_line(&mut stream).await {
+                let parts: Vec<&str> = line.split(' ').collect();
+                let reply = match parts[0] {
+                    "set" | "add" => {
+                        let mut value = vec![0u8; parts[4].parse::<usize>().unwrap() + 2];
+                        stream.read_exact(&mut value).await.unwrap();
+                        value.truncate(value.len() - 2);
+                        if parts[0] == "add" && data.contains_key(parts[1]) {
+                            b"NOT_STORED\r\n".to_vec()
+                        } else {
+                            data.insert(parts[1].to_string(), value);
+                            b"STORED\r\n".to_vec()
+                        }
+                    }
+                    "get" => match data.get(parts[1]) {
+                        Some(v) => [format!("VALUE {} 0 {}\r\n", parts[1], v.len()).as_bytes(), v, b"\r\nEND\r\n"].concat(),
+                        None => b"END\r\n".to_vec(),
+                    },
+                    "delete" => match data.remove(parts[1]) {
+                        Some(_) => b"DELETED\r\n".to_vec(),
+                        None => b"NOT_FOUND\r\n".to_vec(),
+                    },
+                    _ => b"ERROR\r\n".to_vec(),
+                };
+                stream.get_mut().write_all(&reply).await.unwrap();
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_keys_and_expiry() {
+        assert_eq!(wire_key("quotes:transfer-10.00-USDC"), "quotes:transfer-10.00-USDC");
+        assert_eq!(wire_key("with space").len(), 64);
+        assert_eq!(wire_key(&"k".repeat(300)).len(), 64);
+        assert_eq!(exptime(Duration::from_millis(1500)), 2);
+        assert_eq!(exptime(Duration::from_secs(60)), 60);
+        assert!(MemcachedCacheBackend::new("redis://localhost").is_err());
+        assert_eq!(MemcachedCacheBackend::new("memcached://cache.internal").unwrap().port, 11211);
+    }
+
+    #[tokio::test]
+    async fn test_commands_against_server() {
+        let port = fake_memcached().await;
+        let backend = MemcachedCacheBackend::new(&format!("memcached://127.0.0.1:{}", port)).unwrap();
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(backend.get("quotes:a").await.unwrap(), None);
+        backend.set("quotes:a", b"one\r\nEND\r\n", ttl).await.unwrap();
+        assert_eq!(backend.get("quotes:a").await.unwrap().unwrap(), b"one\r\nEND\r\n");
+
+        assert_eq!(backend.set_if_absent("ref 1", b"first", ttl).await.unwrap(), None);
+        assert_eq!(backend.set_if_absent("ref 1", b"second", ttl).await.unwrap().unwrap(), b"first");
+
+        backend.delete("quotes:a").await.unwrap();
+        backend.delete("quotes:a").await.unwrap();
+        assert_eq!(backend.get("quotes:a").await.unwrap(), None);
+    }
+}
@@ -0,0 +1,533 @@
+//! Response and quote caching.
+//!
+//! `Cache` is the process-local store the client uses by default. Deployments
+//! running several instances can share a cache through a `CacheBackend`
+//! (Redis and memcached are available behind the `cache-redis` and
+//! `cache-memcached` features); `SharedCache` layers typed values over any
+//! backend with a pluggable `CacheCodec`.
+
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::time;
+
+#[cfg(feature = "cache-memcached")]
+pub mod memcached;
+#[cfg(feature = "cache-redis")]
+pub mod redis;
+
+/// Number of slots in the timer wheel; entries expiring further out than
+/// `WHEEL_SLOTS * resolution` wait for more than one rotation
+const WHEEL_SLOTS: u64 = 512;
+
+/// How expired entries are removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheExpiryConfig {
+    /// Tick of the expiry sweeper; entries are removed at most about this long
+    /// after they expire
+    pub resolution: Duration,
+    /// Maximum number of keys the sweeper examines per tick. Keys over the
+    /// budget are carried over to the next tick, so a burst of expirations is
+    /// spread out instead of stalling the cache.
+    pub scan_budget: usize,
+}
+
+impl Default for CacheExpiryConfig {
+    fn default() -> Self {
+        Self {
+            resolution: Duration::from_secs(1),
+            scan_budget: 10_000,
+        }
+    }
+}
+
+impl CacheExpiryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.resolution.is_zero() {
+            return Err("cache_expiry.resolution must be greater than 0".to_string());
+        }
+        if self.scan_budget == 0 {
+            return Err("cache_expiry.scan_budget must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Cache entry with expiration
+struct CacheEntry<T> {
+    value: T,
+    expiration: Instant,
+}
+
+/// Keys filed under one wheel slot, with the tick at which each is due
+type Slot = Vec<(String, u64)>;
+
+/// Hashed timer wheel: each key is filed under the tick at which it expires,
+/// so a sweep only looks at the keys that are due instead of the whole cache
+struct TimerWheel {
+    start: Instant,
+    resolution: Duration,
+    slots: Box<[Mutex<Slot>]>,
+}
+
+impl TimerWheel {
+    fn new(resolution: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            resolution,
+            slots: (0..WHEEL_SLOTS).map(|_| Mutex::new(Vec::new())).collect(),
+        }
+    }
+
+    /// Tick at or before `at`
+    fn tick(&self, at: Instant) -> u64 {
+        (at.saturating_duration_since(self.start).as_nanos() / self.resolution.as_nanos()) as u64
+    }
+
+    fn slot(&self, tick: u64) -> std::sync::MutexGuard<'_, Slot> {
+        self.slots[(tick % WHEEL_SLOTS) as usize]
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn schedule(&self, key: String, expiration: Instant) {
+        // Round up so keys are never examined before they expire
+        let tick = self.tick(expiration) + 1;
+        self.slot(tick).push((key, tick));
+    }
+}
+
+/// Sweeper position: the next tick whose slot has not been fully processed
+#[derive(Default)]
+struct Sweep {
+    next_tick: u64,
+    /// Keys taken from that slot that are due in a later rotation; put back
+    /// once the slot is done
+    deferred: Slot,
+}
+
+/// In-memory cache for agent quotes and route data
+pub struct Cache<T> {
+    items: Arc<DashMap<String, CacheEntry<T>>>,
+    wheel: Arc<TimerWheel>,
+    ttl: Duration,
+}
+
+impl<T: Clone + Send + Sync + 'static> Cache<T> {
+    /// Creates a new cache with specified TTL
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_expiry(ttl, CacheExpiryConfig::default())
+    }
+
+    /// Creates a cache whose expired entries are swept as configured by `expiry`
+    pub fn with_expiry(ttl: Duration, expiry: CacheExpiryConfig) -> Self {
+        let cache = Self {
+            items: Arc::new(DashMap::new()),
+            wheel: Arc::new(TimerWheel::new(expiry.resolution)),
+            ttl,
+        };
+
+        // Start the sweeper; it stops once the cache is dropped
+        let items = Arc::downgrade(&cache.items);
+        let wheel = Arc::downgrade(&cache.wheel);
+        tokio::spawn(async move {
+            let mut interval = time::interval(expiry.resolution);
+            let mut sweep = Sweep::default();
+            loop {
+                interval.tick().await;
+                let (Some(items), Some(wheel)) = (Weak::upgrade(&items), Weak::upgrade(&wheel)) else {
+                    break;
+                };
+                sweep_expired(&items, &wheel, &mut sweep, Instant::now(), expiry.scan_budget);
+            }
+        });
+
+        cache
+    }
+
+    /// Stores a value in the cache
+    pub fn set(&self, key: String, value: T) {
+        self.set_with_ttl(key, value, self.ttl);
+    }
+
+    /// Stores a value that expires after `ttl` instead of the cache's TTL
+    pub fn set_with_ttl(&self, key: String, value: T, ttl: Duration) {
+        let expiration = Instant::now() + ttl;
+        self.items.insert(key.clone(), CacheEntry { value, expiration });
+        self.wheel.schedule(key, expiration);
+    }
+
+    /// Stores a value unless the key holds an unexpired one, which is
+    /// returned instead
+    pub fn insert_if_absent(&self, key: String, value: T, ttl: Duration) -> Option<T> {
+        let now = Instant::now();
+        let expiration = now + ttl;
+        match self.items.entry(key.clone()) {
+            Entry::Occupied(entry) if now < entry.get().expiration => return Some(entry.get().value.clone()),
+            Entry::Occupied(mut entry) => {
+                entry.insert(CacheEntry { value, expiration });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(CacheEntry { value, expiration });
+            }
+        }
+        self.wheel.schedule(key, expiration);
+        None
+    }
+
+    /// Retrieves a value from the cache; expired entries are left for the sweeper
+    pub fn get(&self, key: &str) -> Option<T> {
+        let entry = self.items.get(key)?;
+        (Instant::now() < entry.expiration).then(|| entry.value.clone())
+    }
+
+    /// Removes a key from the cache
+    pub fn delete(&self, key: &str) {
+        self.items.remove(key);
+    }
+
+    /// Number of stored entries, including expired ones not yet swept
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Byte-oriented store shared by every SDK instance pointing at it
+#[async_trait::async_trait]
+pub trait CacheBackend: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), String>;
+
+    /// Stores `value` unless the key holds an unexpired value, which is
+    /// returned instead. Must be atomic across instances, since idempotency
+    /// checks rely on it.
+    async fn set_if_absent(&self, key: &str, value: &[u8], ttl: Duration) -> Result<Option<Vec<u8>>, String>;
+
+    async fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+/// The in-memory cache as a backend (per process)
+#[async_trait::async_trait]
+impl CacheBackend for Cache<Vec<u8>> {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(Cache::get(self, key))
+    }
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), String> {
+        self.set_with_ttl(key.to_string(), value.to_vec(), ttl);
+        Ok(())
+    }
+
+    async fn set_if_absent(&self, key: &str, value: &[u8], ttl: Duration) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.insert_if_absent(key.to_string(), value.to_vec(), ttl))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        Cache::delete(self, key);
+        Ok(())
+    }
+}
+
+/// Converts cached values to and from the bytes stored in a backend
+pub trait CacheCodec<T>: Send + Sync {
+    fn encode(&self, value: &T) -> Result<Vec<u8>, String>;
+    fn decode(&self, bytes: &[u8]) -> Result<T, String>;
+}
+
+/// Stores values as JSON
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl<T: Serialize + DeserializeOwned> CacheCodec<T> for JsonCodec {
+    fn encode(&self, value: &T) -> Result<Vec<u8>, String> {
+        serde_json::to_vec(value).map_err(|e| e.to_string())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, String> {
+        serde_json::from_slice(bytes).map_err(|e| e.to_string())
+    }
+}
+
+/// Typed cache over a `CacheBackend`.
+///
+/// Keys are prefixed with `namespace` so several caches can share a backend.
+/// Backend failures are logged and treated as misses, so an unreachable
+/// store degrades to uncached operation instead of failing requests.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use ecash_sdk_core::cache::{Cache, SharedCache};
+///
+/// # tokio_test::block_on(async {
+/// let backend = Arc::new(Cache::<Vec<u8>>::new(Duration::from_secs(60)));
+/// let quotes: SharedCache<Vec<String>> = SharedCache::new(backend, "quotes", Duration::from_secs(30));
+/// quotes.set("base-USDC", &vec!["agent-001".to_string()]).await;
+/// assert_eq!(quotes.get("base-USDC").await.unwrap(), ["agent-001"]);
+/// # });
+/// ```
+pub struct SharedCache<T, C = JsonCodec> {
+    backend: Arc<dyn CacheBackend>,
+    codec: C,
+    namespace: String,
+    ttl: Duration,
+    _values: PhantomData<fn() -> T>,
+}
+
+impl<T> SharedCache<T, JsonCodec>
+where
+    JsonCodec: CacheCodec<T>,
+{
+    pub fn new(backend: Arc<dyn CacheBackend>, namespace: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            backend,
+            codec: JsonCodec,
+            namespace: namespace.into(),
+            ttl,
+            _values: PhantomData,
+        }
+    }
+}
+
+impl<T, C: CacheCodec<T>> SharedCache<T, C> {
+    /// Replaces the codec values are stored with
+    pub fn with_codec<D: CacheCodec<T>>(self, codec: D) -> SharedCache<T, D> {
+        SharedCache {
+            backend: self.backend,
+            codec,
+            namespace: self.namespace,
+            ttl: self.ttl,
+            _values: PhantomData,
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}:{}", self.namespace, key)
+    }
+
+    pub async fn get(&self, key: &str) -> Option<T> {
+        let bytes = match self.backend.get(&self.key(key)).await {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                tracing::warn!("[SDK] Cache backend read failed: {}", e);
+                return None;
+            }
+        };
+        self.codec
+            .decode(&bytes)
+            .map_err(|e| tracing::warn!("[SDK] Ignoring undecodable cache entry {}: {}", key, e))
+            .ok()
+    }
+
+    pub async fn set(&self, key: &str, value: &T) {
+        let result = match self.codec.encode(value) {
+            Ok(bytes) => self.backend.set(&self.key(key), &bytes, self.ttl).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::warn!("[SDK] Cache backend write failed: {}", e);
+        }
+    }
+
+    /// Stores `value` unless another instance already stored one, which is
+    /// returned instead. Unlike `get` and `set`, backend failures are
+    /// returned, since callers rely on the outcome.
+    pub async fn set_if_absent(&self, key: &str, value: &T) -> Result<Option<T>, String> {
+        let bytes = self.codec.encode(value)?;
+        match self.backend.set_if_absent(&self.key(key), &bytes, self.ttl).await? {
+            Some(existing) => self.codec.decode(&existing).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn delete(&self, key: &str) {
+        if let Err(e) = self.backend.delete(&self.key(key)).await {
+            tracing::warn!("[SDK] Cache backend delete failed: {}", e);
+        }
+    }
+}
+
+/// Removes the entries due by `now`, examining at most `budget` keys; returns
+/// how many were examined
+fn sweep_expired<T>(
+    items: &DashMap<String, CacheEntry<T>>,
+    wheel: &TimerWheel,
+    sweep: &mut Sweep,
+    now: Instant,
+    budget: usize,
+) -> usize {
+    let now_tick = wheel.tick(now);
+    // After a long stall every slot is due; visiting each once is enough
+    sweep.next_tick = sweep.next_tick.max(now_tick.saturating_sub(WHEEL_SLOTS - 1));
+
+    let mut examined = 0;
+    while sweep.next_tick <= now_tick && examined < budget {
+        let (batch, done) = {
+            let mut slot = wheel.slot(sweep.next_tick);
+            let keep = slot.len() - slot.len().min(budget - examined);
+            let batch = slot.split_off(keep);
+            (batch, slot.is_empty())
+        };
+        examined += batch.len();
+
+        for (key, tick) in batch {
+            if tick > now_tick {
+                // Due in a later rotation of the wheel
+                sweep.deferred.push((key, tick));
+            } else {
+                // The key may have been set again since; only remove it if expired
+                items.remove_if(&key, |_, entry| now >= entry.expiration);
+            }
+        }
+        if done {
+            if !sweep.deferred.is_empty() {
+                wheel.slot(sweep.next_tick).append(&mut sweep.deferred);
+            }
+            sweep.next_tick += 1;
+        }
+    }
+    examined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cache_set_get() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.set("key1".to_string(), "value1".to_string());
+        assert_eq!(cache.get("key1"), Some("value1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_cache_expiration() {
+        let cache = Cache::new(Duration::from_millis(100));
+        cache.set("key1".to_string(), "value1".to_string());
+        assert_eq!(cache.get("key1"), Some("value1".to_string()));
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(cache.get("key1"), None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_swept() {
+        let expiry = CacheExpiryConfig {
+            resolution: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let cache = Cache::with_expiry(Duration::from_millis(30), expiry);
+        cache.set("key1".to_string(), "value1".to_string());
+        cache.set("key2".to_string(), "value2".to_string());
+        assert_eq!(cache.len(), 2);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_sweep_respects_budget_and_rewrites() {
+        let items = DashMap::new();
+        let wheel = TimerWheel::new(Duration::from_millis(10));
+        let start = wheel.start;
+        for i in 0..25 {
+            let expiration = start + Duration::from_millis(50);
+            items.insert(format!("key{}", i), CacheEntry { value: i, expiration });
+            wheel.schedule(format!("key{}", i), expiration);
+        }
+        // key0 was set again with a later expiry before the sweep
+        let renewed = start + Duration::from_secs(60);
+        items.insert("key0".to_string(), CacheEntry { value: 0, expiration: renewed });
+        wheel.schedule("key0".to_string(), renewed);
+
+        let mut sweep = Sweep::default();
+        let now = start + Duration::from_millis(100);
+        assert_eq!(sweep_expired(&items, &wheel, &mut sweep, now, 10), 10);
+        assert_eq!(items.len(), 15);
+        assert_eq!(sweep_expired(&items, &wheel, &mut sweep, now, 10), 10);
+        assert_eq!(sweep_expired(&items, &wheel, &mut sweep, now, 10), 5);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items.get("key0").unwrap().value, 0);
+        assert_eq!(sweep_expired(&items, &wheel, &mut sweep, now, 10), 0);
+
+        // The renewed key is swept once its own tick comes round
+        let later = renewed + Duration::from_millis(20);
+        sweep_expired(&items, &wheel, &mut sweep, later, 10);
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn test_sweep_defers_later_rotations() {
+        let items = DashMap::new();
+        let wheel = TimerWheel::new(Duration::from_millis(10));
+        // Same slot as tick 6, but one rotation later; more keys than the budget
+        let expiration = wheel.start + Duration::from_millis(10 * (WHEEL_SLOTS + 5));
+        for i in 0..15 {
+            items.insert(format!("key{}", i), CacheEntry { value: i, expiration });
+            wheel.schedule(format!("key{}", i), expiration);
+        }
+
+        let mut sweep = Sweep::default();
+        let now = wheel.start + Duration::from_millis(100);
+        assert_eq!(sweep_expired(&items, &wheel, &mut sweep, now, 10), 10);
+        assert_eq!(sweep_expired(&items, &wheel, &mut sweep, now, 10), 5);
+        assert_eq!(sweep.next_tick, 11);
+        assert_eq!(items.len(), 15);
+
+        let due = expiration + Duration::from_millis(20);
+        sweep_expired(&items, &wheel, &mut sweep, due, 10);
+        sweep_expired(&items, &wheel, &mut sweep, due, 10);
+        assert!(items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shared_cache_over_memory_backend() {
+        let backend = Arc::new(Cache::<Vec<u8>>::new(Duration::from_secs(60)));
+        let first: SharedCache<String> = SharedCache::new(backend.clone(), "idempotency", Duration::from_secs(60));
+        let second: SharedCache<String> = SharedCache::new(backend.clone(), "idempotency", Duration::from_secs(60));
+
+        assert_eq!(first.set_if_absent("ref_1", &"instance-a".to_string()).await.unwrap(), None);
+        assert_eq!(
+            second.set_if_absent("ref_1", &"instance-b".to_string()).await.unwrap().as_deref(),
+            Some("instance-a")
+        );
+        assert_eq!(second.get("ref_1").await.as_deref(), Some("instance-a"));
+        assert_eq!(backend.get("idempotency:ref_1"), Some(b"\"instance-a\"".to_vec()));
+
+        // Undecodable entries read as misses
+        backend.set("idempotency:ref_2".to_string(), b"not json".to_vec());
+        assert_eq!(first.get("ref_2").await, None);
+
+        first.delete("ref_1").await;
+        assert_eq!(second.get("ref_1").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_cache_delete() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.set("key1".to_string(), "value1".to_string());
+        cache.delete("key1");
+        assert_eq!(cache.get("key1"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cache_multiple_keys() {
+        let cache = Cache::new(Duration::from_secs(60));
+        cache.set("key1".to_string(), "value1".to_string());
+        cache.set("key2".to_string(), "value2".to_string());
+        assert_eq!(cache.get("key1"), Some("value1".to_string()));
+        assert_eq!(cache.get("key2"), Some("value2".to_string()));
+    }
+}
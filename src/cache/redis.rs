@@ -0,0 +1,326 @@
+//! Redis cache backend (RESP2 over a single connection).
+
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use super::CacheBackend;
+use crate::http::HttpClient;
+use crate::secrets::SecretString;
+
+/// Reply to a Redis command
+#[derive(Debug, PartialEq)]
+enum Reply {
+    Status(String),
+    Integer(i64),
+    Bulk(Option<Vec<u8>>),
+    Array(Option<Vec<Reply>>),
+}
+
+/// `CacheBackend` storing entries in Redis.
+///
+/// Commands are sent over one connection, opened on first use through the
+/// proxy settings of its `HttpClient` and reopened after an error.
+///
+/// # Example
+/// ```no_run
+/// use std::sync::Arc;
+/// use ecash_sdk_core::cache::redis::RedisCacheBackend;
+/// use ecash_sdk_core::EasyCashClient;
+///
+/// let client = EasyCashClient::new(None).unwrap();
+/// let backend = RedisCacheBackend::new("redis://:s3cret@cache.internal:6379/2")
+///     .unwrap()
+///     .with_http_client(client.http_client().clone());
+/// let client = client.with_cache_backend(Arc::new(backend));
+/// ```
+pub struct RedisCacheBackend {
+    host: String,
+    port: u16,
+    password: Option<SecretString>,
+    db: u32,
+    timeout: Duration,
+    http: HttpClient,
+    conn: Mutex<Option<BufReader<TcpStream>>>,
+}
+
+impl RedisCacheBackend {
+    /// Parses `redis://[[user]:password@]host[:port][/db]`
+    pub fn new(url: &str) -> Result<Self, String> {
+        let rest = url
+            .strip_prefix("redis://")
+            .ok_or_else(|| format!("unsupported Redis URL (expected redis://): {}", url))?;
+        let (authority, db) = match rest.split_once('/') {
+            Some((authority, "")) => (authority, 0),
+            Some((authority, db)) => (authority, db.parse().map_err(|_| format!("invalid database in Redis URL: {}", url))?),
+            None => (rest, 0),
+        };
+        let (password, authority) = match authority.rsplit_once('@') {
+            Some((credentials, authority)) => {
+                let password = credentials.split_once(':').map_or(credentials, |(_, password)| password);
+                (Some(SecretString::from(password)), authority)
+            }
+            None => (None, authority),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| format!("invalid port in Redis URL: {}", url))?),
+            None => (authority, 6379),
+        };
+        if host.is_empty() {
+            return Err(format!("missing host in Redis URL: {}", url));
+        }
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            password: password.filter(|p| !p.is_empty()),
+            db,
+            timeout: Duration::from_secs(2),
+            http: HttpClient::default(),
+            conn: Mutex::new(None),
+        })
+    }
+
+    /// Sets how long a command may take, including connecting
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Connects through the proxy settings of `http` (e.g.
+    /// `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
+    async fn command(&self, args: &[&[u8]]) -> Result<Reply, String> {
+        let mut conn = self.conn.lock().await;
+        let result = tokio::time::timeout(self.timeout, async {
+            if conn.is_none() {
+                *conn = Some(self.open().await?);
+            }
+            match conn.as_mut() {
+                Some(stream) => call(stream, args).await,
+                None => Err("Redis connection unavailable".to_string()),
+            }
+        })
+        .await
+        .unwrap_or_else(|_| Err(format!("Redis command timed out after {:?}", self.timeout)));
+        if result.is_err() {
+            // The connection may be mid-reply; start over next time
+            *conn = None;
+        }
+        result
+    }
+
+    async fn open(&self) -> Result<BufReader<TcpStream>, String> {
+        let mut stream = BufReader::new(self.http.connect_tcp(&self.host, self.port).await?);
+        if let Some(password) = &self.password {
+            call(&mut stream, &[b"AUTH", password.expose().as_bytes()]).await?;
+        }
+        if self.db != 0 {
+            call(&mut stream, &[b"SELECT", self.db.to_string().as_bytes()]).await?;
+        }
+        Ok(stream)
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for RedisCacheBackend {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        match self.command(&[b"GET", key.as_bytes()]).await? {
+            Reply::Bulk(value) => Ok(value),
+            other => Err(format!("unexpected Redis reply to GET: {:?}", other)),
+        }
+    }
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Duration) -> Result<(), String> {
+        let ttl_ms = ttl.as_millis().max(1).to_string();
+        self.command(&[b"SET", key.as_bytes(), value, b"PX", ttl_ms.as_bytes()]).await?;
+        Ok(())
+    }
+
+    async fn set_if_absent(&self, key: &str, value: &[u8], ttl: Duration) -> Result<Option<Vec<u8>>, String> {
+        let ttl_ms = ttl.as_millis().max(1).to_string();
+        // The existing value can expire between SET NX and GET; try again then
+        for _ in 0..3 {
+            match self.command(&[b"SET", key.as_bytes(), value, b"PX", ttl_ms.as_bytes(), b"NX"]).await? {
+                Reply::Status(_) => return Ok(None),
+                Reply::Bulk(None) => {}
+                other => return Err(format!("unexpected Redis reply to SET NX: {:?}", other)),
+            }
+            if let Some(existing) = self.get(key).await? {
+                return Ok(Some(existing));
+            }
+        }
+        Err(format!("Redis key {} kept expiring during SET NX", key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        self.command(&[b"DEL", key.as_bytes()]).await?;
+        Ok(())
+    }
+}
+
+async fn call(stream: &mut BufReader<TcpStream>, args: &[&[u8]]) -> Result<Reply, String> {
+    let mut request = format!("*{}\r\n", args.len()).into_bytes();
+    for arg in args {
+        request.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        request.extend_from_slice(arg);
+        request.extend_from_slice(b"\r\n");
+    }
+    stream
+        .get_mut()
+        .write_all(&request)
+        .await
+        .map_err(|e| format!("Redis write failed: {}", e))?;
+    read_reply(stream).await
+}
+
+async fn read_line(stream: &mut BufReader<TcpStream>) -> Result<String, String> {
+    let mut line = Vec::new();
+    stream
+        .read_until(b'\n', &mut line)
+        .await
+        .map_err(|e| format!("Redis read failed: {}", e))?;
+    if !line.ends_with(b"\r\n") {
+        return Err("Redis connection closed".to_string());
+    }
+    line.truncate(line.len() - 2);
+    String::from_utf8(line).map_err(|_| "invalid Redis reply".to_string())
+}
+
+fn parse_len(value: &str) -> Result<i64, String> {
+    value.parse().map_err(|_| format!("invalid Redis length: {}", value))
+}
+
+async fn read_reply(stream: &mut BufReader<TcpStream>) -> Result<Reply, String> {
+    // Nested arrays are read iteratively: (items read so far, expected length)
+    let mut stack: Vec<(Vec<Reply>, usize)> = Vec::new();
+    loop {
+        let line = read_line(stream).await?;
+        let (kind, value) = line.split_at(line.len().min(1));
+        let mut reply = match kind {
+            "+" => Reply::Status(value.to_string()),
+            "-" => return Err(format!("Redis error: {}", value)),
+            ":" => Reply::Integer(parse_len(value)?),
+            "$" => match parse_len(value)? {
+                len if len < 0 => Reply::Bulk(None),
+                len => {
+                    let mut data = vec![0u8; len as usize + 2];
+                    stream
+                        .read_exact(&mut data)
+                        .await
+                        .map_err(|e| format!("Redis read failed: {}", e))?;
+                    data.truncate(len as usize);
+                    Reply::Bulk(Some(data))
+                }
+            },
+            "*" => match parse_len(value)? {
+                len if len < 0 => Reply::Array(None),
+                0 => Reply::Array(Some(Vec::new())),
+                len => {
+                    stack.push((Vec::with_capacity(len as usize), len as usize));
+                    continue;
+                }
+            },
+            _ => return Err(format!("invalid Redis reply: {}", line)),
+        };
+        loop {
+            let Some((items, len)) = stack.last_mut() else {
+                return Ok(reply);
+            };
+            items.push(reply);
+            if items.len() < *len {
+                break;
+            }
+            let (items, _) = stack.pop().unwrap_or_default();
+            reply = Reply::Array(Some(items));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::net::TcpListener;
+
+    /// Minimal Redis speaking the commands the backend sends (TTLs ignored)
+    async fn fake_redis(password: &'static str) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(socket);
+            let mut data: HashMap<Vec<u8>, Vec<u8>> = HashMap::new();
+            let mut authenticated = false;
+            while let Ok(Reply::Array(Some(args))) = read_reply(&mut stream).await {
+                let args: Vec<Vec<u8>> = args
+                    .into_iter()
+                    .map(|a| match a {
+                        Reply::Bulk(Some(a)) => a,
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                let reply: Vec<u8> = match (args[0].as_slice(), authenticated) {
+                    (b"AUTH", _) if args[1] == password.as_bytes() => {
+                        authenticated = true;
+                        b"+OK\r\n".to_vec()
+                    }
+                    (_, false) => b"-NOAUTH Authentication required.\r\n".to_vec(),
+                    (b"SELECT", _) => b"+OK\r\n".to_vec(),
+                    (b"GET", _) => match data.get(&args[1]) {
+                        Some(v) => [format!("${}\r\n", v.len()).as_bytes(), v, b"\r\n"].concat(),
+                        None => b"$-1\r\n".to_vec(),
+                    },
+                    (b"SET", _) if args.last().unwrap() == b"NX" && data.contains_key(&args[1]) => b"$-1\r\n".to_vec(),
+                    (b"SET", _) => {
+                        data.insert(args[1].clone(), args[2].clone());
+                        b"+OK\r\n".to_vec()
+                    }
+                    (b"DEL", _) => format!(":{}\r\n", data.remove(&args[1]).is_some() as u8).into_bytes(),
+                    _ => b"-ERR unknown command\r\n".to_vec(),
+                };
+                stream.get_mut().write_all(&reply).await.unwrap();
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_parse_url() {
+        let backend = RedisCacheBackend::new("redis://:s3cret@cache.internal/2").unwrap();
+        assert_eq!((backend.host.as_str(), backend.port, backend.db), ("cache.internal", 6379, 2));
+        assert_eq!(backend.password.unwrap().expose(), "s3cret");
+        assert!(RedisCacheBackend::new("redis://localhost:6380").unwrap().password.is_none());
+        assert!(RedisCacheBackend::new("memcached://localhost").is_err());
+        assert!(RedisCacheBackend::new("redis://localhost/x").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_commands_against_server() {
+        let port = fake_redis("s3cret").await;
+        let backend = RedisCacheBackend::new(&format!("redis://:s3cret@127.0.0.1:{}/1", port)).unwrap();
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(backend.get("quotes:a").await.unwrap(), None);
+        backend.set("quotes:a", b"one\r\ntwo", ttl).await.unwrap();
+        assert_eq!(backend.get("quotes:a").await.unwrap().unwrap(), b"one\r\ntwo");
+
+        assert_eq!(backend.set_if_absent("ref:1", b"first", ttl).await.unwrap(), None);
+        assert_eq!(backend.set_if_absent("ref:1", b"second", ttl).await.unwrap().unwrap(), b"first");
+
+        backend.delete("quotes:a").await.unwrap();
+        assert_eq!(backend.get("quotes:a").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_password_fails() {
+        let port = fake_redis("s3cret").await;
+        let backend = RedisCacheBackend::new(&format!("redis://:wrong@127.0.0.1:{}", port)).unwrap();
+        assert!(backend.get("a").await.unwrap_err().contains("NOAUTH"));
+    }
+}
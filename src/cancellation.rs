@@ -0,0 +1,215 @@
+//! Cancellation of in-flight transactions.
+//!
+//! An execution can be cancelled until it reaches the point of no return:
+//! the hand-off to the selected agent. Before that, cancelling aborts
+//! whatever the execution is waiting on (admission, screening, quoting) and
+//! the call fails with `CANCELLED`. After it, the transaction may already be
+//! broadcast, so cancellation is refused and the execution runs to completion.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::errors::{ErrorCode, SdkError};
+
+/// Signal shared between an execution and whoever may cancel it. Clones
+/// share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<TokenState>,
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        if !self.inner.cancelled.swap(true, Ordering::SeqCst) {
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the token is cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Result of `EasyCashClient::cancel`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CancelOutcome {
+    /// Stopped before broadcast; the execution fails with `CANCELLED`
+    Cancelled,
+    /// Already handed to an agent; the execution runs to completion
+    AlreadySubmitted,
+    /// No execution with this reference ID is in flight
+    NotInFlight,
+}
+
+/// One execution as seen by `cancel`
+#[derive(Debug)]
+pub(crate) struct InFlight {
+    token: CancellationToken,
+    /// Set at the point of no return, under the lock so that `cancel` and
+    /// `submit` agree on which happened first
+    submitted: Mutex<bool>,
+}
+
+impl InFlight {
+    fn new(token: CancellationToken) -> Self {
+        Self {
+            token,
+            submitted: Mutex::new(false),
+        }
+    }
+
+    /// Passes the point of no return, unless cancelled first
+    pub(crate) fn submit(&self) -> Result<(), SdkError> {
+        let mut submitted = self.submitted.lock().unwrap_or_else(|e| e.into_inner());
+        if self.token.is_cancelled() {
+            return Err(cancelled_error());
+        }
+        *submitted = true;
+        Ok(())
+    }
+
+    fn cancel(&self) -> CancelOutcome {
+        let submitted = self.submitted.lock().unwrap_or_else(|e| e.into_inner());
+        if *submitted {
+            return CancelOutcome::AlreadySubmitted;
+        }
+        self.token.cancel();
+        CancelOutcome::Cancelled
+    }
+
+    /// Resolves when the execution is cancelled before its point of no
+    /// return; never resolves afterwards
+    pub(crate) async fn cancelled_before_submission(&self) {
+        self.token.cancelled().await;
+        if *self.submitted.lock().unwrap_or_else(|e| e.into_inner()) {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+pub(crate) fn cancelled_error() -> SdkError {
+    SdkError::new(ErrorCode::Cancelled, "transaction cancelled before submission")
+}
+
+/// In-flight executions by reference ID
+#[derive(Debug, Default)]
+pub(crate) struct InFlightRegistry {
+    executions: DashMap<String, Arc<InFlight>>,
+}
+
+impl InFlightRegistry {
+    /// Registers an execution until the returned guard is dropped. A later
+    /// execution with the same reference ID replaces it for `cancel`.
+    pub(crate) fn register(&self, reference_id: &str, token: CancellationToken) -> InFlightGuard<'_> {
+        let in_flight = Arc::new(InFlight::new(token));
+        self.executions.insert(reference_id.to_string(), in_flight.clone());
+        InFlightGuard {
+            registry: self,
+            reference_id: reference_id.to_string(),
+            in_flight,
+        }
+    }
+
+    pub(crate) fn cancel(&self, reference_id: &str) -> CancelOutcome {
+        // Cloned out so the map shard isn't locked while cancelling
+        let in_flight = self.executions.get(reference_id).map(|entry| entry.clone());
+        match in_flight {
+            Some(in_flight) => in_flight.cancel(),
+            None => CancelOutcome::NotInFlight,
+        }
+    }
+}
+
+pub(crate) struct InFlightGuard<'a> {
+    registry: &'a InFlightRegistry,
+    reference_id: String,
+    in_flight: Arc<InFlight>,
+}
+
+impl std::ops::Deref for InFlightGuard<'_> {
+    type Target = InFlight;
+
+    fn deref(&self) -> &InFlight {
+        &self.in_flight
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.registry
+            .executions
+            .remove_if(&self.reference_id, |_, current| Arc::ptr_eq(current, &self.in_flight));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_token_wakes_waiters() {
+        let token = CancellationToken::new();
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!token.is_cancelled());
+        token.cancel();
+        waiter.await.unwrap();
+        // Already cancelled tokens resolve immediately
+        token.cancelled().await;
+    }
+
+    #[test]
+    fn test_cancel_before_and_after_submission() {
+        let registry = InFlightRegistry::default();
+        assert_eq!(registry.cancel("ref_1"), CancelOutcome::NotInFlight);
+
+        let first = registry.register("ref_1", CancellationToken::new());
+        assert_eq!(registry.cancel("ref_1"), CancelOutcome::Cancelled);
+        assert_eq!(first.submit().unwrap_err().code, ErrorCode::Cancelled);
+        drop(first);
+        assert_eq!(registry.cancel("ref_1"), CancelOutcome::NotInFlight);
+
+        let second = registry.register("ref_1", CancellationToken::new());
+        second.submit().unwrap();
+        assert_eq!(registry.cancel("ref_1"), CancelOutcome::AlreadySubmitted);
+    }
+
+    #[test]
+    fn test_replaced_execution_keeps_latest() {
+        let registry = InFlightRegistry::default();
+        let first = registry.register("ref_1", CancellationToken::new());
+        let second = registry.register("ref_1", CancellationToken::new());
+        drop(first);
+        assert_eq!(registry.cancel("ref_1"), CancelOutcome::Cancelled);
+        assert!(second.token.is_cancelled());
+    }
+}
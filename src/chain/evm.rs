@@ -0,0 +1,295 @@
+//! EVM chain adapter on an `alloy` provider.
+
+use alloy::network::{AnyNetwork, ReceiptResponse, TransactionBuilder};
+use alloy::primitives::{hex, Address, TxHash};
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::client::RpcClient;
+use alloy::rpc::json_rpc::{RequestPacket, ResponsePacket};
+use alloy::sol_types::SolCall;
+use alloy::transports::{TransportError, TransportErrorKind, TransportFut};
+use std::collections::HashMap;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use super::{ChainAdapter, NativeAsset, TxReceipt};
+use crate::http::HttpClient;
+use crate::types::ChainId;
+
+alloy::sol! {
+    function balanceOf(address owner) external view returns (uint256);
+}
+
+/// Chain adapter for Ethereum-compatible chains (Ethereum, Base).
+///
+/// The native asset is queried with `eth_getBalance`; ERC-20 assets must be
+/// registered with `with_token` and are queried with `balanceOf`.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use ecash_sdk_core::chain::evm::EvmRpcAdapter;
+/// use ecash_sdk_core::types::ChainId;
+///
+/// let adapter = EvmRpcAdapter::new(ChainId::Base, "http://localhost:8545", Duration::from_secs(10))
+///     .with_token("USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+/// ```
+pub struct EvmRpcAdapter {
+    chain: ChainId,
+    transport: HttpTransport,
+    provider: RootProvider<AnyNetwork>,
+    native_asset: String,
+    tokens: HashMap<String, String>,
+}
+
+impl EvmRpcAdapter {
+    /// Creates an adapter for the JSON-RPC endpoint at `url`
+    pub fn new(chain: ChainId, url: impl Into<String>, timeout: Duration) -> Self {
+        let transport = HttpTransport {
+            http: HttpClient::default(),
+            url: url.into(),
+            timeout,
+        };
+        Self {
+            chain,
+            provider: transport.provider(),
+            transport,
+            native_asset: "ETH".to_string(),
+            tokens: HashMap::new(),
+        }
+    }
+
+    /// Sets the symbol of the chain's native asset (default "ETH")
+    pub fn with_native_asset(mut self, symbol: impl Into<String>) -> Self {
+        self.native_asset = symbol.into().to_uppercase();
+        self
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.transport.http = http;
+        self.provider = self.transport.provider();
+        self
+    }
+
+    /// Registers the contract address of an ERC-20 asset
+    pub fn with_token(mut self, asset: &str, contract: impl Into<String>) -> Self {
+        self.tokens.insert(asset.to_uppercase(), contract.into());
+        self
+    }
+
+    /// The `alloy` provider behind the adapter, for calls the adapter
+    /// doesn't wrap
+    pub fn provider(&self) -> &RootProvider<AnyNetwork> {
+        &self.provider
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainAdapter for EvmRpcAdapter {
+    fn chain(&self) -> ChainId {
+        self.chain
+    }
+
+    fn native_asset(&self) -> Option<NativeAsset> {
+        // Every EVM chain's gas token has 18 decimals
+        Some(NativeAsset::new(self.native_asset.clone(), 18))
+    }
+
+    async fn get_balance(&self, address: &str, asset: &str) -> Result<String, String> {
+        let owner = parse_address(address)?;
+        let asset = asset.to_uppercase();
+        if asset == self.native_asset {
+            let balance = self
+                .provider
+                .get_balance(owner)
+                .await
+                .map_err(|e| format!("eth_getBalance failed: {}", e))?;
+            return Ok(balance.to_string());
+        }
+        let contract = self
+            .tokens
+            .get(&asset)
+            .ok_or_else(|| format!("no contract registered for {} on {}", asset, self.chain))?;
+        let call = alloy::rpc::types::TransactionRequest::default()
+            .with_to(parse_address(contract)?)
+            .with_input(balanceOfCall { owner }.abi_encode());
+        let output = self
+            .provider
+            .call(call.into())
+            .await
+            .map_err(|e| format!("eth_call failed: {}", e))?;
+        let balance = balanceOfCall::abi_decode_returns(&output)
+            .map_err(|e| format!("invalid balanceOf result from {}: {}", contract, e))?;
+        Ok(balance.to_string())
+    }
+
+    async fn broadcast_raw_transaction(&self, raw_tx: &str) -> Result<String, String> {
+        let raw_tx = hex::decode(raw_tx).map_err(|e| format!("raw transaction is not hex: {}", e))?;
+        let pending = self
+            .provider
+            .send_raw_transaction(&raw_tx)
+            .await
+            .map_err(|e| format!("eth_sendRawTransaction failed: {}", e))?;
+        Ok(pending.tx_hash().to_string())
+    }
+
+    async fn get_receipt(&self, tx_hash: &str) -> Result<Option<TxReceipt>, String> {
+        let hash: TxHash = tx_hash
+            .parse()
+            .map_err(|e| format!("invalid transaction hash {}: {}", tx_hash, e))?;
+        let receipt = self
+            .provider
+            .get_transaction_receipt(hash)
+            .await
+            .map_err(|e| format!("eth_getTransactionReceipt failed: {}", e))?;
+        Ok(receipt.map(|receipt| TxReceipt {
+            tx_hash: tx_hash.to_string(),
+            block_height: receipt.block_number().unwrap_or(0),
+            success: receipt.status(),
+            fee_paid: receipt.cost().to_string(),
+        }))
+    }
+
+    async fn get_block_height(&self) -> Result<u64, String> {
+        self.provider
+            .get_block_number()
+            .await
+            .map_err(|e| format!("eth_blockNumber failed: {}", e))
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address, String> {
+    address.parse().map_err(|_| format!("invalid EVM address: {}", address))
+}
+
+/// `alloy` transport sending JSON-RPC over the SDK's `HttpClient`, so the
+/// provider uses the configured egress proxy, TLS and connection pool
+#[derive(Clone)]
+struct HttpTransport {
+    http: HttpClient,
+    url: String,
+    timeout: Duration,
+}
+
+impl HttpTransport {
+    fn provider(&self) -> RootProvider<AnyNetwork> {
+        RootProvider::new(RpcClient::new(self.clone(), false))
+    }
+}
+
+impl tower::Service<RequestPacket> for HttpTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            let body = serde_json::to_value(&request).map_err(TransportError::ser_err)?;
+            let resp = this
+                .http
+                .post_json(&this.url, &[], &body, this.timeout)
+                .await
+                .map_err(|e| TransportErrorKind::custom_str(&e))?;
+            if !resp.is_success() {
+                return Err(TransportErrorKind::http_error(resp.status, resp.body));
+            }
+            serde_json::from_str(&resp.body).map_err(|e| TransportError::deser_err(e, &resp.body))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::serve_json_rpc as serve;
+    use serde_json::{json, Value};
+
+    const TX_HASH: &str = "0xc9b7b5f0b1a0f7a2e3d4c5b6a79881726354433221100ffeeddccbbaa9988776";
+
+    #[tokio::test]
+    async fn test_native_balance_is_uint256() {
+        let (url, server) = serve(vec![json!("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")]).await;
+        let adapter = EvmRpcAdapter::new(ChainId::Ethereum, url, Duration::from_secs(2));
+
+        let balance = adapter.get_balance("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "eth").await.unwrap();
+        assert_eq!(
+            balance,
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0]["method"], "eth_getBalance");
+        assert!(adapter.get_balance("0x742d", "ETH").await.unwrap_err().contains("invalid EVM address"));
+    }
+
+    #[tokio::test]
+    async fn test_token_balance_uses_balance_of() {
+        let (url, server) = serve(vec![json!("0x00000000000000000000000000000000000000000000000000000000000f4240")]).await;
+        let adapter = EvmRpcAdapter::new(ChainId::Base, url, Duration::from_secs(2))
+            .with_token("USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+
+        let balance = adapter
+            .get_balance("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "usdc")
+            .await
+            .unwrap();
+        assert_eq!(balance, "1000000");
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0]["method"], "eth_call");
+        let data = requests[0]["params"][0]["input"].as_str().unwrap();
+        assert!(data.starts_with("0x70a08231000000000000000000000000742d35cc"));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_and_block_height() {
+        let (url, _server) = serve(vec![
+            json!({
+                "type": "0x2",
+                "status": "0x1",
+                "transactionHash": TX_HASH,
+                "transactionIndex": "0x0",
+                "blockHash": "0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+                "blockNumber": "0x10",
+                "from": "0x742d35cc6634c0532925a3b844bc9e7595f0beb0",
+                "to": "0x833589fcd6edb6e08f4c7c32d4f71b54bda02913",
+                "contractAddress": null,
+                "gasUsed": "0x5208",
+                "cumulativeGasUsed": "0x5208",
+                "effectiveGasPrice": "0x3b9aca00",
+                "logs": [],
+                "logsBloom": format!("0x{}", "0".repeat(512)),
+            }),
+            Value::Null,
+            json!("0x1dbd9"),
+        ])
+        .await;
+        let adapter = EvmRpcAdapter::new(ChainId::Ethereum, url, Duration::from_secs(2));
+
+        let receipt = adapter.get_receipt(TX_HASH).await.unwrap().unwrap();
+        assert_eq!(receipt.block_height, 16);
+        assert!(receipt.success);
+        assert_eq!(receipt.fee_paid, "21000000000000");
+
+        assert!(adapter.get_receipt(TX_HASH).await.unwrap().is_none());
+        assert_eq!(adapter.get_block_height().await.unwrap(), 121_817);
+    }
+
+    #[test]
+    fn test_native_asset() {
+        let adapter = EvmRpcAdapter::new(ChainId::Ethereum, "http://127.0.0.1:1/", Duration::from_secs(1));
+        assert_eq!(adapter.native_asset(), Some(NativeAsset::new("ETH", 18)));
+        let adapter = adapter.with_native_asset("pol");
+        assert_eq!(adapter.native_asset().unwrap().format_fee(21_000_000_000_000), "0.000021 POL");
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_token_fails() {
+        let adapter = EvmRpcAdapter::new(ChainId::Base, "http://127.0.0.1:1/", Duration::from_secs(1));
+        let err = adapter.get_balance("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "DAI").await.unwrap_err();
+        assert!(err.contains("no contract registered"));
+    }
+}
@@ -0,0 +1,345 @@
+//! Chain adapters: the SDK's interface to on-chain state.
+//!
+//! The execution pipeline and status polling talk to chains only through the
+//! `ChainAdapter` trait. Real adapters live behind feature flags; the
+//! `MockChainAdapter` is always available for tests and local development.
+
+#[cfg(feature = "evm-rpc")]
+pub mod evm;
+#[cfg(feature = "solana-rpc")]
+pub mod solana;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::amount::Amount;
+use crate::types::ChainId;
+
+/// On-chain outcome of a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxReceipt {
+    #[serde(rename = "tx_hash")]
+    pub tx_hash: String,
+    #[serde(rename = "block_height")]
+    pub block_height: u64,
+    /// False if the transaction was included but reverted
+    pub success: bool,
+    /// Fee paid in the chain's smallest native unit (wei, lamports)
+    #[serde(rename = "fee_paid")]
+    pub fee_paid: String,
+}
+
+/// Asset a chain's fees are paid in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeAsset {
+    pub symbol: String,
+    /// Decimals of `TxReceipt::fee_paid` (18 for wei, 9 for lamports)
+    pub decimals: u32,
+}
+
+impl NativeAsset {
+    pub fn new(symbol: impl Into<String>, decimals: u32) -> Self {
+        Self {
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+
+    /// Native asset of a known chain
+    pub fn of(chain: ChainId) -> Option<Self> {
+        match chain {
+            ChainId::Ethereum | ChainId::Base => Some(Self::new("ETH", 18)),
+            ChainId::Solana => Some(Self::new("SOL", 9)),
+            ChainId::Unknown => None,
+        }
+    }
+
+    /// Formats a fee in the smallest unit, e.g. `"0.000021 ETH"`
+    pub fn format_fee(&self, fee_paid: u128) -> String {
+        format!("{} {}", Amount::from_base_units(fee_paid, self.decimals), self.symbol)
+    }
+}
+
+/// Trait for reading from and submitting to a single chain.
+///
+/// Balances and fees are decimal strings in the asset's smallest unit, so
+/// values larger than `u64` (e.g. 18-decimal tokens) are not truncated.
+#[async_trait::async_trait]
+pub trait ChainAdapter: Send + Sync {
+    /// Chain this adapter is connected to
+    fn chain(&self) -> ChainId;
+
+    /// Asset fees are paid in; `None` if unknown
+    fn native_asset(&self) -> Option<NativeAsset> {
+        NativeAsset::of(self.chain())
+    }
+
+    /// Returns the balance of `asset` held by `address`
+    async fn get_balance(&self, address: &str, asset: &str) -> Result<String, String>;
+
+    /// Broadcasts a signed, hex-encoded transaction and returns its hash
+    async fn broadcast_raw_transaction(&self, raw_tx: &str) -> Result<String, String>;
+
+    /// Returns the receipt, or `None` while the transaction is pending
+    async fn get_receipt(&self, tx_hash: &str) -> Result<Option<TxReceipt>, String>;
+
+    /// Returns the latest block height (slot on Solana)
+    async fn get_block_height(&self) -> Result<u64, String>;
+}
+
+/// Polls `adapter` until the transaction has a receipt or `timeout` elapses
+pub async fn wait_for_receipt(
+    adapter: &dyn ChainAdapter,
+    tx_hash: &str,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<TxReceipt, String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(receipt) = adapter.get_receipt(tx_hash).await? {
+            return Ok(receipt);
+        }
+        if Instant::now() + poll_interval > deadline {
+            return Err(format!(
+                "transaction {} not confirmed on {} within {:?}",
+                tx_hash,
+                adapter.chain(),
+                timeout
+            ));
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Sends a JSON-RPC 2.0 request and returns its `result`
+#[cfg(feature = "solana-rpc")]
+pub(crate) async fn json_rpc_call(
+    http: &crate::http::HttpClient,
+    url: &str,
+    timeout: Duration,
+    id: u64,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": params,
+    });
+    let resp = http.post_json(url, &[], &body, timeout).await?;
+    if !resp.is_success() {
+        return Err(format!("{} returned HTTP status {}", method, resp.status));
+    }
+    let mut parsed: serde_json::Value =
+        serde_json::from_str(&resp.body).map_err(|e| format!("invalid JSON-RPC response: {}", e))?;
+    if let Some(error) = parsed.get("error") {
+        return Err(format!(
+            "{} failed: {}",
+            method,
+            error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error")
+        ));
+    }
+    Ok(parsed
+        .get_mut("result")
+        .map(serde_json::Value::take)
+        .unwrap_or(serde_json::Value::Null))
+}
+
+/// Serves one JSON-RPC result per connection; resolves to the requests received
+#[cfg(all(test, any(feature = "evm-rpc", feature = "solana-rpc")))]
+pub(crate) async fn serve_json_rpc(
+    results: Vec<serde_json::Value>,
+) -> (String, tokio::task::JoinHandle<Vec<serde_json::Value>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut seen = Vec::new();
+        for result in results {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 8192];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let body = request.split("\r\n\r\n").nth(1).unwrap_or("");
+            let request: serde_json::Value = serde_json::from_str(body).unwrap();
+            let body = serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+            seen.push(request);
+            let resp = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+            socket.write_all(resp.as_bytes()).await.unwrap();
+        }
+        seen
+    });
+    (url, handle)
+}
+
+#[derive(Default)]
+struct MockChainState {
+    balances: HashMap<(String, String), String>,
+    receipts: HashMap<String, TxReceipt>,
+    broadcasts: Vec<String>,
+    block_height: u64,
+}
+
+/// In-memory chain adapter for development/testing.
+///
+/// Broadcast transactions are confirmed immediately in the next block unless
+/// `set_auto_confirm(false)` is called.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::chain::{ChainAdapter, MockChainAdapter};
+/// use ecash_sdk_core::types::ChainId;
+///
+/// # tokio_test::block_on(async {
+/// let adapter = MockChainAdapter::new(ChainId::Base);
+/// adapter.set_balance("0xabc", "USDC", "1000000");
+/// assert_eq!(adapter.get_balance("0xabc", "USDC").await.unwrap(), "1000000");
+/// # });
+/// ```
+pub struct MockChainAdapter {
+    chain: ChainId,
+    state: Mutex<MockChainState>,
+    auto_confirm: AtomicBool,
+}
+
+impl MockChainAdapter {
+    pub fn new(chain: ChainId) -> Self {
+        Self {
+            chain,
+            state: Mutex::new(MockChainState {
+                block_height: 1,
+                ..Default::default()
+            }),
+            auto_confirm: AtomicBool::new(true),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockChainState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sets the balance (in smallest units) of an address
+    pub fn set_balance(&self, address: &str, asset: &str, amount: &str) {
+        self.state()
+            .balances
+            .insert((address.to_lowercase(), asset.to_uppercase()), amount.to_string());
+    }
+
+    /// Sets the latest block height
+    pub fn set_block_height(&self, height: u64) {
+        self.state().block_height = height;
+    }
+
+    /// Controls whether broadcast transactions are confirmed immediately
+    pub fn set_auto_confirm(&self, auto_confirm: bool) {
+        self.auto_confirm.store(auto_confirm, Ordering::SeqCst);
+    }
+
+    /// Records a receipt for a transaction (e.g. to confirm it later)
+    pub fn insert_receipt(&self, receipt: TxReceipt) {
+        self.state().receipts.insert(receipt.tx_hash.clone(), receipt);
+    }
+
+    /// Returns all raw transactions broadcast so far
+    pub fn broadcasts(&self) -> Vec<String> {
+        self.state().broadcasts.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainAdapter for MockChainAdapter {
+    fn chain(&self) -> ChainId {
+        self.chain
+    }
+
+    async fn get_balance(&self, address: &str, asset: &str) -> Result<String, String> {
+        Ok(self
+            .state()
+            .balances
+            .get(&(address.to_lowercase(), asset.to_uppercase()))
+            .cloned()
+            .unwrap_or_else(|| "0".to_string()))
+    }
+
+    async fn broadcast_raw_transaction(&self, raw_tx: &str) -> Result<String, String> {
+        let raw = hex::decode(raw_tx.strip_prefix("0x").unwrap_or(raw_tx))
+            .map_err(|e| format!("invalid raw transaction hex: {}", e))?;
+        let tx_hash = format!("0x{}", hex::encode(crate::crypto::keccak256(&raw)));
+
+        let auto_confirm = self.auto_confirm.load(Ordering::SeqCst);
+        let mut state = self.state();
+        state.broadcasts.push(raw_tx.to_string());
+        if auto_confirm {
+            state.block_height += 1;
+            let receipt = TxReceipt {
+                tx_hash: tx_hash.clone(),
+                block_height: state.block_height,
+                success: true,
+                fee_paid: "0".to_string(),
+            };
+            state.receipts.insert(tx_hash.clone(), receipt);
+        }
+        Ok(tx_hash)
+    }
+
+    async fn get_receipt(&self, tx_hash: &str) -> Result<Option<TxReceipt>, String> {
+        Ok(self.state().receipts.get(tx_hash).cloned())
+    }
+
+    async fn get_block_height(&self) -> Result<u64, String> {
+        Ok(self.state().block_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_balance_defaults_to_zero() {
+        let adapter = MockChainAdapter::new(ChainId::Ethereum);
+        adapter.set_balance("0xABC", "usdc", "42");
+        assert_eq!(adapter.get_balance("0xabc", "USDC").await.unwrap(), "42");
+        assert_eq!(adapter.get_balance("0xdef", "USDC").await.unwrap(), "0");
+    }
+
+    #[test]
+    fn test_native_asset() {
+        assert_eq!(MockChainAdapter::new(ChainId::Base).native_asset(), Some(NativeAsset::new("ETH", 18)));
+        let sol = MockChainAdapter::new(ChainId::Solana).native_asset().unwrap();
+        assert_eq!(sol.format_fee(5_000), "0.000005 SOL");
+        assert_eq!(MockChainAdapter::new(ChainId::Unknown).native_asset(), None);
+    }
+
+    #[tokio::test]
+    async fn test_mock_broadcast_confirms() {
+        let adapter = MockChainAdapter::new(ChainId::Base);
+        adapter.set_block_height(100);
+        let tx_hash = adapter.broadcast_raw_transaction("0x02f870").await.unwrap();
+
+        let receipt = wait_for_receipt(&adapter, &tx_hash, Duration::from_millis(1), Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert_eq!(receipt.block_height, 101);
+        assert!(receipt.success);
+        assert_eq!(adapter.broadcasts(), vec!["0x02f870".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_receipt_times_out() {
+        let adapter = MockChainAdapter::new(ChainId::Base);
+        adapter.set_auto_confirm(false);
+        let tx_hash = adapter.broadcast_raw_transaction("0x01").await.unwrap();
+        assert!(adapter.get_receipt(&tx_hash).await.unwrap().is_none());
+
+        let err = wait_for_receipt(&adapter, &tx_hash, Duration::from_millis(5), Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(err.contains("not confirmed"));
+    }
+}
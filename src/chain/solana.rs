@@ -0,0 +1,301 @@
+//! Solana chain adapter over the Solana JSON-RPC API.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use super::{ChainAdapter, TxReceipt};
+use crate::http::HttpClient;
+use crate::types::ChainId;
+
+/// Commitment level used for reads and confirmation tracking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Commitment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "processed" => Some(Commitment::Processed),
+            "confirmed" => Some(Commitment::Confirmed),
+            "finalized" => Some(Commitment::Finalized),
+            _ => None,
+        }
+    }
+}
+
+/// Blockhash to use as the recent blockhash of a new transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentBlockhash {
+    pub blockhash: String,
+    /// Last block height at which a transaction using this blockhash is valid
+    pub last_valid_block_height: u64,
+}
+
+/// Chain adapter for Solana.
+///
+/// SOL balances are returned in lamports; SPL token assets must be registered
+/// with `with_token` and are summed across all of the owner's token accounts.
+/// A transaction counts as confirmed once it reaches the configured commitment.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use ecash_sdk_core::chain::solana::{Commitment, SolanaRpcAdapter};
+///
+/// let adapter = SolanaRpcAdapter::new("http://localhost:8899", Duration::from_secs(10))
+///     .with_token("USDC", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v")
+///     .with_commitment(Commitment::Finalized);
+/// ```
+pub struct SolanaRpcAdapter {
+    url: String,
+    timeout: Duration,
+    http: HttpClient,
+    commitment: Commitment,
+    tokens: HashMap<String, String>,
+    next_id: AtomicU64,
+}
+
+impl SolanaRpcAdapter {
+    /// Creates an adapter for the RPC endpoint at `url` using `confirmed` commitment
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            url: url.into(),
+            timeout,
+            http: HttpClient::default(),
+            commitment: Commitment::Confirmed,
+            tokens: HashMap::new(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Sets the commitment level required for reads and confirmations
+    pub fn with_commitment(mut self, commitment: Commitment) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Registers the mint address of an SPL token asset
+    pub fn with_token(mut self, asset: &str, mint: impl Into<String>) -> Self {
+        self.tokens.insert(asset.to_uppercase(), mint.into());
+        self
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Fetches a recent blockhash for building a new transaction
+    pub async fn get_latest_blockhash(&self) -> Result<RecentBlockhash, String> {
+        let result = self
+            .rpc("getLatestBlockhash", json!([{ "commitment": self.commitment.as_str() }]))
+            .await?;
+        let value = &result["value"];
+        Ok(RecentBlockhash {
+            blockhash: value["blockhash"]
+                .as_str()
+                .ok_or("getLatestBlockhash returned no blockhash")?
+                .to_string(),
+            last_valid_block_height: value["lastValidBlockHeight"].as_u64().unwrap_or(0),
+        })
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        super::json_rpc_call(&self.http, &self.url, self.timeout, id, method, params).await
+    }
+
+    async fn transaction_fee(&self, signature: &str) -> Result<u64, String> {
+        // getTransaction does not accept "processed"
+        let commitment = self.commitment.max(Commitment::Confirmed);
+        let result = self
+            .rpc(
+                "getTransaction",
+                json!([signature, {
+                    "encoding": "json",
+                    "commitment": commitment.as_str(),
+                    "maxSupportedTransactionVersion": 0,
+                }]),
+            )
+            .await?;
+        Ok(result["meta"]["fee"].as_u64().unwrap_or(0))
+    }
+}
+
+#[async_trait::async_trait]
+impl ChainAdapter for SolanaRpcAdapter {
+    fn chain(&self) -> ChainId {
+        ChainId::Solana
+    }
+
+    async fn get_balance(&self, address: &str, asset: &str) -> Result<String, String> {
+        let asset = asset.to_uppercase();
+        let commitment = self.commitment.as_str();
+        if asset == "SOL" {
+            let result = self
+                .rpc("getBalance", json!([address, { "commitment": commitment }]))
+                .await?;
+            let lamports = result["value"].as_u64().ok_or("getBalance returned no value")?;
+            return Ok(lamports.to_string());
+        }
+
+        let mint = self
+            .tokens
+            .get(&asset)
+            .ok_or_else(|| format!("no mint registered for {} on solana", asset))?;
+        let result = self
+            .rpc(
+                "getTokenAccountsByOwner",
+                json!([address, { "mint": mint }, { "encoding": "jsonParsed", "commitment": commitment }]),
+            )
+            .await?;
+        let accounts = result["value"]
+            .as_array()
+            .ok_or("getTokenAccountsByOwner returned no accounts")?;
+        let mut total: u128 = 0;
+        for account in accounts {
+            let amount = account["account"]["data"]["parsed"]["info"]["tokenAmount"]["amount"]
+                .as_str()
+                .ok_or("token account has no amount")?
+                .parse::<u128>()
+                .map_err(|e| format!("invalid token amount: {}", e))?;
+            total = total.saturating_add(amount);
+        }
+        Ok(total.to_string())
+    }
+
+    async fn broadcast_raw_transaction(&self, raw_tx: &str) -> Result<String, String> {
+        let bytes = hex::decode(raw_tx.strip_prefix("0x").unwrap_or(raw_tx))
+            .map_err(|e| format!("invalid raw transaction hex: {}", e))?;
+        let result = self
+            .rpc(
+                "sendTransaction",
+                json!([crate::crypto::base64_encode(&bytes), {
+                    "encoding": "base64",
+                    "preflightCommitment": self.commitment.as_str(),
+                }]),
+            )
+            .await?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "sendTransaction returned no signature".to_string())
+    }
+
+    async fn get_receipt(&self, tx_hash: &str) -> Result<Option<TxReceipt>, String> {
+        let result = self
+            .rpc(
+                "getSignatureStatuses",
+                json!([[tx_hash], { "searchTransactionHistory": true }]),
+            )
+            .await?;
+        let status = &result["value"][0];
+        if status.is_null() {
+            return Ok(None);
+        }
+        let reached = status["confirmationStatus"]
+            .as_str()
+            .and_then(Commitment::parse)
+            .unwrap_or(Commitment::Processed);
+        if reached < self.commitment {
+            return Ok(None);
+        }
+
+        Ok(Some(TxReceipt {
+            tx_hash: tx_hash.to_string(),
+            block_height: status["slot"].as_u64().unwrap_or(0),
+            success: status["err"].is_null(),
+            fee_paid: self.transaction_fee(tx_hash).await?.to_string(),
+        }))
+    }
+
+    async fn get_block_height(&self) -> Result<u64, String> {
+        let result = self
+            .rpc("getSlot", json!([{ "commitment": self.commitment.as_str() }]))
+            .await?;
+        result.as_u64().ok_or_else(|| "getSlot returned no slot".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::serve_json_rpc as serve;
+
+    const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    #[tokio::test]
+    async fn test_spl_balance_sums_token_accounts() {
+        let account = |amount: &str| {
+            json!({ "account": { "data": { "parsed": { "info": { "tokenAmount": { "amount": amount } } } } } })
+        };
+        let (url, server) = serve(vec![json!({ "value": [account("1500000"), account("250000")] })]).await;
+        let adapter = SolanaRpcAdapter::new(url, Duration::from_secs(2))
+            .with_token("USDC", "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+        assert_eq!(adapter.get_balance(OWNER, "usdc").await.unwrap(), "1750000");
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0]["method"], "getTokenAccountsByOwner");
+        assert_eq!(requests[0]["params"][1]["mint"], "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_sends_base64() {
+        let (url, server) = serve(vec![json!("5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnb")]).await;
+        let adapter = SolanaRpcAdapter::new(url, Duration::from_secs(2));
+
+        let signature = adapter.broadcast_raw_transaction("0x666f6f").await.unwrap();
+        assert_eq!(signature, "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnb");
+        let requests = server.await.unwrap();
+        assert_eq!(requests[0]["params"][0], "Zm9v");
+        assert_eq!(requests[0]["params"][1]["encoding"], "base64");
+    }
+
+    #[tokio::test]
+    async fn test_receipt_respects_commitment() {
+        let (url, _server) = serve(vec![
+            json!({ "value": [{ "slot": 250, "confirmationStatus": "confirmed", "err": null }] }),
+            json!({ "value": [{ "slot": 250, "confirmationStatus": "finalized", "err": null }] }),
+            json!({ "meta": { "fee": 5000 } }),
+            json!({ "value": [null] }),
+        ])
+        .await;
+        let adapter = SolanaRpcAdapter::new(url, Duration::from_secs(2)).with_commitment(Commitment::Finalized);
+
+        assert!(adapter.get_receipt("sig").await.unwrap().is_none());
+        let receipt = adapter.get_receipt("sig").await.unwrap().unwrap();
+        assert_eq!(receipt.block_height, 250);
+        assert!(receipt.success);
+        assert_eq!(receipt.fee_paid, "5000");
+        assert!(adapter.get_receipt("unknown").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_latest_blockhash_and_slot() {
+        let (url, _server) = serve(vec![
+            json!({ "value": { "blockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N", "lastValidBlockHeight": 3090 } }),
+            json!(123456),
+        ])
+        .await;
+        let adapter = SolanaRpcAdapter::new(url, Duration::from_secs(2));
+
+        let recent = adapter.get_latest_blockhash().await.unwrap();
+        assert_eq!(recent.blockhash, "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N");
+        assert_eq!(recent.last_valid_block_height, 3090);
+        assert_eq!(adapter.get_block_height().await.unwrap(), 123456);
+    }
+}
@@ -0,0 +1,439 @@
+//! Circuit breaker for calls to the agent network.
+//!
+//! Tracks the outcome of recent calls and short-circuits new requests while the
+//! downstream service is failing, instead of letting every request wait for the
+//! full timeout.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+// Tokio's clock follows paused time, so simulations can step through open periods
+#[cfg(feature = "client")]
+use tokio::time::Instant;
+#[cfg(not(feature = "client"))]
+use std::time::Instant;
+
+/// Configuration for the circuit breaker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Fraction of failed calls (0.0 - 1.0) at which the breaker opens
+    pub failure_rate_threshold: f64,
+    /// Minimum number of recorded calls before the failure rate is evaluated
+    pub minimum_calls: u32,
+    /// Number of most recent calls used to compute the failure rate
+    pub window_size: u32,
+    /// How long the breaker stays open before allowing trial calls
+    pub open_duration: Duration,
+    /// Number of trial calls allowed (and required to succeed) while half-open
+    pub half_open_max_calls: u32,
+    /// Whether to enable the circuit breaker
+    pub enabled: bool,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_calls: 10,
+            window_size: 20,
+            open_duration: Duration::from_secs(30),
+            half_open_max_calls: 3,
+            enabled: true,
+        }
+    }
+}
+
+/// State of the circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls flow normally
+    Closed,
+    /// Calls are rejected immediately
+    Open,
+    /// A limited number of trial calls are let through
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+
+    /// Numeric representation used in the metrics map (0 = closed, 1 = open, 2 = half-open)
+    pub fn as_metric(&self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::Open => 1.0,
+            CircuitState::HalfOpen => 2.0,
+        }
+    }
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+struct BreakerInner {
+    state: CircuitState,
+    outcomes: VecDeque<bool>,
+    opened_at: Option<Instant>,
+    half_open_in_flight: u32,
+    half_open_successes: u32,
+}
+
+/// Trial slot of a half-open call, given back on drop unless the call
+/// recorded an outcome
+struct TrialSlot<'a>(Option<&'a CircuitBreaker>);
+
+impl Drop for TrialSlot<'_> {
+    fn drop(&mut self) {
+        if let Some(breaker) = self.0 {
+            breaker.release_trial();
+        }
+    }
+}
+
+/// Closed/open/half-open circuit breaker driven by failure rate.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+///
+/// let breaker = CircuitBreaker::new(CircuitBreakerConfig::default());
+/// assert!(breaker.acquire().is_ok());
+/// breaker.record_success();
+/// assert_eq!(breaker.state(), CircuitState::Closed);
+/// ```
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    /// Creates a new circuit breaker with the given configuration.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                half_open_in_flight: 0,
+                half_open_successes: 0,
+            }),
+        }
+    }
+
+    /// Creates a disabled circuit breaker (always allows calls).
+    pub fn disabled() -> Self {
+        Self::new(CircuitBreakerConfig {
+            enabled: false,
+            ..Default::default()
+        })
+    }
+
+    /// Checks whether a call may proceed.
+    ///
+    /// Every successful `acquire` must be followed by `record_success` or
+    /// `record_failure` once the call completes.
+    pub fn acquire(&self) -> Result<(), String> {
+        self.admit().map(|_| ())
+    }
+
+    /// `acquire`, returning whether the call took a half-open trial slot
+    fn admit(&self) -> Result<bool, String> {
+        if !self.config.enabled {
+            return Ok(false);
+        }
+
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        if inner.state == CircuitState::Open {
+            let elapsed = inner.opened_at.map(|t| t.elapsed()).unwrap_or_default();
+            if elapsed < self.config.open_duration {
+                return Err(format!(
+                    "circuit breaker open: retry in {:?}",
+                    self.config.open_duration - elapsed
+                ));
+            }
+            inner.state = CircuitState::HalfOpen;
+            inner.half_open_in_flight = 0;
+            inner.half_open_successes = 0;
+        }
+
+        if inner.state == CircuitState::HalfOpen {
+            if inner.half_open_in_flight >= self.config.half_open_max_calls {
+                return Err("circuit breaker half-open: trial calls in progress".to_string());
+            }
+            inner.half_open_in_flight += 1;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Gives back the trial slot of a call that ended without an outcome
+    fn release_trial(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.state == CircuitState::HalfOpen {
+            inner.half_open_in_flight = inner.half_open_in_flight.saturating_sub(1);
+        }
+    }
+
+    /// Records a successful call.
+    pub fn record_success(&self) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match inner.state {
+            CircuitState::Closed => self.push_outcome(&mut inner, true),
+            CircuitState::HalfOpen => {
+                inner.half_open_in_flight = inner.half_open_in_flight.saturating_sub(1);
+                inner.half_open_successes += 1;
+                if inner.half_open_successes >= self.config.half_open_max_calls {
+                    tracing::info!("[SDK] Circuit breaker closed");
+                    inner.state = CircuitState::Closed;
+                    inner.outcomes.clear();
+                    inner.opened_at = None;
+                }
+            }
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Records a failed call.
+    pub fn record_failure(&self) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match inner.state {
+            CircuitState::Closed => {
+                self.push_outcome(&mut inner, false);
+                let calls = inner.outcomes.len() as u32;
+                if calls >= self.config.minimum_calls
+                    && Self::rate(&inner.outcomes) >= self.config.failure_rate_threshold
+                {
+                    Self::trip(&mut inner);
+                }
+            }
+            CircuitState::HalfOpen => Self::trip(&mut inner),
+            CircuitState::Open => {}
+        }
+    }
+
+    /// Runs `call` through the breaker, recording its outcome.
+    pub async fn call<F, T>(&self, call: F) -> Result<T, String>
+    where
+        F: Future<Output = Result<T, String>>,
+    {
+        let trial = self.admit()?;
+        // Dropping the future mid-call must not hold the trial slot for good
+        let mut abandoned = TrialSlot(trial.then_some(self));
+        let result = call.await;
+        abandoned.0 = None;
+        match result {
+            Ok(_) => self.record_success(),
+            Err(_) => self.record_failure(),
+        }
+        result
+    }
+
+    /// Returns the current breaker state.
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).state
+    }
+
+    /// Returns the failure rate over the current window.
+    pub fn failure_rate(&self) -> f64 {
+        Self::rate(&self.inner.lock().unwrap_or_else(|e| e.into_inner()).outcomes)
+    }
+
+    /// Forces the breaker back to the closed state.
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.state = CircuitState::Closed;
+        inner.outcomes.clear();
+        inner.opened_at = None;
+        inner.half_open_in_flight = 0;
+        inner.half_open_successes = 0;
+    }
+
+    fn push_outcome(&self, inner: &mut BreakerInner, success: bool) {
+        inner.outcomes.push_back(success);
+        while inner.outcomes.len() > self.config.window_size.max(1) as usize {
+            inner.outcomes.pop_front();
+        }
+    }
+
+    fn trip(inner: &mut BreakerInner) {
+        tracing::warn!("[SDK] Circuit breaker opened");
+        inner.state = CircuitState::Open;
+        inner.opened_at = Some(Instant::now());
+        inner.half_open_in_flight = 0;
+        inner.half_open_successes = 0;
+    }
+
+    fn rate(outcomes: &VecDeque<bool>) -> f64 {
+        if outcomes.is_empty() {
+            return 0.0;
+        }
+        let failures = outcomes.iter().filter(|ok| !**ok).count();
+        failures as f64 / outcomes.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            minimum_calls: 4,
+            window_size: 10,
+            open_duration: Duration::from_millis(50),
+            half_open_max_calls: 2,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_breaker_stays_closed_below_threshold() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..3 {
+            breaker.acquire().unwrap();
+            breaker.record_success();
+        }
+        breaker.acquire().unwrap();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.failure_rate(), 0.25);
+    }
+
+    #[test]
+    fn test_breaker_opens_on_failure_rate() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..4 {
+            breaker.acquire().unwrap();
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let result = breaker.acquire();
+        assert!(result.unwrap_err().contains("circuit breaker open"));
+    }
+
+    #[test]
+    fn test_breaker_requires_minimum_calls() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..3 {
+            breaker.acquire().unwrap();
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_half_open_recovers() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        breaker.acquire().unwrap();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.acquire().unwrap();
+        // Trial slots exhausted
+        assert!(breaker.acquire().is_err());
+
+        breaker.record_success();
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_half_open_failure_reopens() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        breaker.acquire().unwrap();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_call_records_outcome() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..4 {
+            let result: Result<(), String> = breaker.call(async { Err("boom".to_string()) }).await;
+            assert!(result.is_err());
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let result = breaker.call(async { Ok(1) }).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_breaker_dropped_trial_call_releases_slot() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            half_open_max_calls: 1,
+            ..test_config()
+        });
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        // The trial call is cancelled before it completes
+        let pending = breaker.call(std::future::pending::<Result<(), String>>());
+        let timed_out = tokio::time::timeout(Duration::from_millis(10), pending).await;
+        assert!(timed_out.is_err());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // Its slot is free for the next trial, which closes the breaker
+        assert_eq!(breaker.call(async { Ok(1) }).await, Ok(1));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_breaker_disabled() {
+        let breaker = CircuitBreaker::disabled();
+        for _ in 0..100 {
+            breaker.record_failure();
+        }
+        assert!(breaker.acquire().is_ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_breaker_reset() {
+        let breaker = CircuitBreaker::new(test_config());
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        breaker.reset();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.acquire().is_ok());
+    }
+}
@@ -1,21 +1,170 @@
-use crate::agent::{AgentNegotiator, AgentNegotiatorTrait};
-use crate::cache::Cache;
+use crate::address_book::AddressBook;
+use crate::agent::{self, AgentDirectory, AgentNegotiator, AgentNegotiatorTrait, Replacement, RouteQuote, SpeedUpRequest};
+use crate::agent::balancing::LoadBalancer;
+use crate::agent::explain::{QuoteExplanation, QuoteStatus, RouteExplanation};
+use crate::agent::sla::{SlaReport, SlaTracker};
+use crate::amount::Amount;
+use crate::audit::{AuditKind, AuditLogger};
+use crate::broadcast::{SelfBroadcast, SignedExecution};
+use crate::budget::FeeBudgetTracker;
+use crate::cache::{Cache, CacheBackend, SharedCache};
+use crate::cancellation::{self, CancelOutcome, CancellationToken, InFlight, InFlightRegistry};
+use crate::chain::{ChainAdapter, TxReceipt};
+use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::compliance::{ScreeningDecision, ScreeningProvider, ScreeningRequest};
+use crate::concurrency::ConcurrencyLimiter;
 use crate::config::SdkConfig;
+use crate::contracts::{self, ContractCall, Deployments};
+use crate::conversion::{self, ConversionProvider, ConversionQuote};
+use crate::credentials::{ApiKeyRing, KeyRotation, SecretsProvider};
+use crate::crypto::TransactionSigner;
 use crate::errors::{ErrorCode, Result, SdkError};
-use crate::monitoring::Metrics;
-use crate::types::{TransactionRequest, TransactionResponse};
-use crate::validator;
-use crate::zk::{ProofGenerator, ZkProofGenerator};
+use crate::events::{EventBus, SdkEvent};
+use crate::evm::tx::SignedTransaction;
+use crate::exactly_once::{ExactlyOnceGuard, GuardRejection};
+#[cfg(feature = "test-utils")]
+use crate::faults::{self, Fault, FaultInjector, InjectionPoint};
+use crate::fees::history::{FeeHistory, FeeRoute};
+use crate::hooks::{ExecutionHook, ExecutionHooks};
+use crate::intents::{self, IntentContext, IntentHandler};
+use crate::ledger::Ledger;
+use crate::monitoring::alerts::AlertMonitor;
+use crate::offline::{self, PreparedIntent, PreparedProof, QuoteBundle};
+use crate::monitoring::{AgentStats, HealthStatus, Metrics, MetricsSink, MetricsSnapshot};
+use crate::policy::PolicyEngine;
+use crate::pricing::{self, PriceOracle};
+use crate::protocol;
+use crate::receipt::{self, SignedReceipt};
+use crate::redaction::SensitiveField;
+use crate::http::HttpClient;
+use crate::sandbox::{self, Faucet, FaucetGrant, FaucetRequest, HttpFaucet, TestAccount};
+use crate::escrow::{Escrow, EscrowManager, EscrowRejection, EscrowState, EscrowTerms};
+use crate::refunds::{RefundManager, RefundRejection};
+use crate::solvency::{self, BalanceProvider};
+use crate::sponsorship::{self, FeePayer, SponsorRegistry, SponsorRejection};
+use crate::streaming::{ExecutionStream, ExecutionUpdate, ProgressReporter};
+use crate::tls::TlsConnector;
+use crate::transport::{self, SealedIntent};
+use crate::travel_rule::{self, SealedTravelRule};
+use crate::types::{
+    Balance, ChainId, Disbursement, DisbursementResult, FeeSplitAmount, IntentType, TransactionRequest, TransactionResponse,
+};
+use crate::validator::{self, ValidationPipeline, Validator};
+use crate::zk::notes::{self, NoteScanner, ShieldedNote, ViewingKey};
+use crate::zk::nullifiers::{NullifierTracker, SpendRejection};
+use crate::zk::artifacts::{ArtifactKind, ArtifactManager};
+use crate::zk::pool::{ProofJob, ProofPool};
+use crate::zk::proof_cache::{ProofCache, ProofCacheStore};
+use crate::zk::witness::{KeyEncryptionKeys, WitnessVault};
+use crate::zk::{self, ProofGenerator, SolvencyInput};
+use k256::PublicKey;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, OnceCell};
+use tracing::Instrument;
 use uuid::Uuid;
 
+/// Per-call options for `execute_transaction_with_options`
+#[derive(Debug, Clone, Default)]
+pub struct ExecuteOptions {
+    cancellation: Option<CancellationToken>,
+    batch_proof: Option<Arc<BatchProof>>,
+    notes: Vec<ShieldedNote>,
+    /// Set by `execute_self_broadcast`
+    self_broadcast: Option<SelfBroadcast>,
+    /// Set by `submit`
+    prepared: Option<Arc<PreparedIntent>>,
+}
+
+impl ExecuteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the execution when `token` is cancelled before the
+    /// transaction is handed to an agent
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Shielded notes the transaction spends. With a nullifier tracker
+    /// configured, notes already spent by another transaction are refused.
+    pub fn with_notes(mut self, notes: Vec<ShieldedNote>) -> Self {
+        self.notes = notes;
+        self
+    }
+}
+
+/// Aggregated solvency proof generated up front for a batch's shielded requests
+#[derive(Debug)]
+struct BatchProof {
+    proof: String,
+    inputs: HashMap<String, SolvencyInput>,
+}
+
+impl BatchProof {
+    /// The proof, if it was generated for exactly this request's statement
+    fn covering(&self, reference_id: &str, input: &SolvencyInput) -> Option<&str> {
+        (self.inputs.get(reference_id) == Some(input)).then_some(self.proof.as_str())
+    }
+}
+
 /// Main entry point for the SDK
 pub struct EasyCashClient {
     config: SdkConfig,
-    zk: ProofGenerator,
-    negotiator: AgentNegotiator,
-    cache: Option<Cache<TransactionResponse>>,
+    http: HttpClient,
+    credentials: Arc<ApiKeyRing>,
+    proofs: ProofPool,
+    proof_cache: Option<ProofCache>,
+    artifacts: Option<Arc<ArtifactManager>>,
+    negotiator: Arc<dyn AgentNegotiatorTrait>,
+    agent_directory: Option<Arc<dyn AgentDirectory>>,
+    cache: Option<ResponseCache>,
     metrics: Metrics,
+    breaker: CircuitBreaker,
+    sla: SlaTracker,
+    balancer: LoadBalancer,
+    limiter: ConcurrencyLimiter,
+    events: EventBus,
+    audit: Option<AuditLogger>,
+    screening: Option<Arc<dyn ScreeningProvider>>,
+    policy: Option<PolicyEngine>,
+    address_book: Option<AddressBook>,
+    receipt_signer: Option<TransactionSigner>,
+    broadcast_signer: Option<TransactionSigner>,
+    prepare_signer: Option<TransactionSigner>,
+    preparer_keys: Vec<PublicKey>,
+    chains: HashMap<ChainId, Arc<dyn ChainAdapter>>,
+    note_scanner: Option<Arc<dyn NoteScanner>>,
+    nullifiers: Option<Arc<NullifierTracker>>,
+    balance_provider: Option<Arc<dyn BalanceProvider>>,
+    price_oracle: Option<Arc<dyn PriceOracle>>,
+    conversion: Option<Arc<dyn ConversionProvider>>,
+    alerts: Option<AlertMonitor>,
+    fee_budget: Option<FeeBudgetTracker>,
+    fee_history: Option<Arc<FeeHistory>>,
+    exactly_once: Option<ExactlyOnceGuard>,
+    refunds: Option<RefundManager>,
+    escrows: Option<EscrowManager>,
+    sponsors: Option<SponsorRegistry>,
+    contracts: Option<Deployments>,
+    faucet: Option<Arc<dyn Faucet>>,
+    ledger: Option<Ledger>,
+    validators: ValidationPipeline,
+    hooks: ExecutionHooks,
+    intents: HashMap<IntentType, Arc<dyn IntentHandler>>,
+    /// Negotiated with the agent network on first use
+    protocol_version: OnceCell<u32>,
+    in_flight: InFlightRegistry,
+    submissions: SubmittedTransactions,
+    /// Self-broadcast executions awaiting `confirm_external_broadcast`, by
+    /// settlement transaction hash
+    broadcasts: Mutex<HashMap<String, PendingBroadcast>>,
+    #[cfg(feature = "test-utils")]
+    faults: Option<Arc<dyn FaultInjector>>,
 }
 
 impl EasyCashClient {
@@ -26,237 +175,4767 @@ impl EasyCashClient {
         cfg.validate()
             .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid configuration: {}", e)))?;
 
+        // Managed artifacts are verified as they are loaded
+        let artifacts = &cfg.zk_artifacts;
+        if cfg.enable_zk_proofs && !artifacts.trusted_transcripts.is_empty() && !artifacts.is_managed() {
+            zk::verify_parameter_files(
+                &artifacts.default_proving_key_path(),
+                &artifacts.default_transcript_path(),
+                &artifacts.trusted_transcripts,
+            )
+            .map_err(|e| {
+                SdkError::new(ErrorCode::ProofGeneration, format!("circuit parameters failed trusted-setup verification: {}", e))
+            })?;
+        }
+        let http = HttpClient::from_config(&cfg)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid network configuration: {}", e)))?;
+        let zk = Arc::new(ProofGenerator::new(artifacts.default_circuit_path().to_string_lossy()));
         let mut client = Self {
             config: cfg.clone(),
-            zk: ProofGenerator::new("./circuits/spend.wasm"),
-            negotiator: AgentNegotiator::new(cfg.timeout),
+            http: http.clone(),
+            credentials: Arc::new(ApiKeyRing::new(cfg.api_key.clone())),
+            proofs: ProofPool::new(zk, cfg.proof_pool.clone()),
+            proof_cache: None,
+            artifacts: cfg
+                .zk_artifacts
+                .is_managed()
+                .then(|| Arc::new(ArtifactManager::new(cfg.zk_artifacts.clone()).with_http_client(http.clone()))),
+            negotiator: Arc::new(AgentNegotiator::new(cfg.timeout)),
+            agent_directory: None,
             cache: None,
             metrics: Metrics::new(),
+            breaker: CircuitBreaker::new(cfg.circuit_breaker.clone()),
+            sla: SlaTracker::new(cfg.sla.clone()),
+            balancer: LoadBalancer::new(cfg.load_balancing.clone()),
+            limiter: ConcurrencyLimiter::new(cfg.concurrency.clone()),
+            events: EventBus::default(),
+            audit: None,
+            screening: None,
+            policy: None,
+            address_book: None,
+            receipt_signer: None,
+            broadcast_signer: None,
+            prepare_signer: None,
+            preparer_keys: Vec::new(),
+            chains: HashMap::new(),
+            note_scanner: None,
+            nullifiers: None,
+            balance_provider: None,
+            price_oracle: None,
+            conversion: None,
+            alerts: None,
+            fee_budget: None,
+            fee_history: None,
+            exactly_once: None,
+            refunds: None,
+            escrows: None,
+            sponsors: None,
+            contracts: None,
+            faucet: None,
+            ledger: None,
+            validators: ValidationPipeline::from_config(&cfg.validation, &cfg.assets),
+            hooks: ExecutionHooks::default(),
+            intents: intents::default_handlers().into_iter().map(|h| (h.intent(), h)).collect(),
+            protocol_version: OnceCell::new(),
+            in_flight: InFlightRegistry::default(),
+            submissions: SubmittedTransactions::default(),
+            broadcasts: Mutex::new(HashMap::new()),
+            #[cfg(feature = "test-utils")]
+            faults: None,
         };
 
         if cfg.enable_caching {
-            client.cache = Some(Cache::new(cfg.cache_ttl));
+            client.cache = Some(ResponseCache::Local(Cache::with_expiry(
+                cfg.cache_ttl,
+                cfg.cache_expiry.clone(),
+            )));
+        }
+        if let Some(endpoint) = cfg.env().faucet_endpoint() {
+            let faucet = HttpFaucet::new(endpoint, cfg.timeout).with_http_client(http);
+            let faucet = match cfg.api_key.expose() {
+                "" => faucet,
+                key => faucet.with_api_key(key),
+            };
+            client.faucet = Some(Arc::new(faucet));
         }
 
         Ok(client)
     }
 
-    /// Constructs a transfer intent and executes it with full validation
-    pub async fn execute_transaction(
-        &self,
-        req: &TransactionRequest,
-    ) -> Result<TransactionResponse> {
-        let start_time = Instant::now();
-        
-        // Execute transaction and capture result
-        let result = self.execute_transaction_internal(req).await;
-        
-        // Record metrics based on actual result
-        if self.config.enable_metrics {
-            let success = result.is_ok();
-            let fee = result.as_ref().map(|r| {
-                // Try to parse fee from response, default to 0.0
-                r.fee_used.split_whitespace().next()
-                    .and_then(|s| s.parse::<f64>().ok())
-                    .unwrap_or(0.0)
-            }).unwrap_or(0.0);
-            let latency = start_time.elapsed();
-            
-            self.metrics.record_transaction(success, fee, latency);
-        }
-        
-        result
+    /// Performs the TLS handshake of the client's `https://` and `wss://`
+    /// connections with `connector`
+    pub fn with_tls_connector(self, connector: Arc<dyn TlsConnector>) -> Self {
+        self.http.set_tls_connector(connector);
+        self
     }
 
-    async fn execute_transaction_internal(
-        &self,
-        req: &TransactionRequest,
-    ) -> Result<TransactionResponse> {
+    /// HTTP client carrying this client's proxy, TLS and request signing
+    /// settings, for providers built separately (their `with_http_client`)
+    pub fn http_client(&self) -> &HttpClient {
+        &self.http
+    }
 
-        // 1. Validate Request
-        validator::validate_transaction_request(req)
-            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("validation failed: {}", e)))?;
+    /// Replaces the built-in agent negotiator (e.g. with a test double)
+    pub fn with_negotiator(mut self, negotiator: Arc<dyn AgentNegotiatorTrait>) -> Self {
+        self.negotiator = negotiator;
+        self.protocol_version = OnceCell::new();
+        self
+    }
 
-        // 2. Check Cache for similar recent transactions
-        if let Some(ref cache) = self.cache {
-            let cache_key = format!("{}-{}-{}", req.intent_type.as_str(), req.amount, req.asset);
-            if let Some(cached) = cache.get(&cache_key) {
-                tracing::info!("[SDK] Cache hit for transaction pattern");
-                return Ok(cached);
-            }
-        }
+    /// Injects faults into `execute_transaction` for chaos testing
+    #[cfg(feature = "test-utils")]
+    pub fn with_fault_injector(mut self, injector: Arc<dyn FaultInjector>) -> Self {
+        self.faults = Some(injector);
+        self
+    }
 
-        // 3. Generate ZK Proof if shielded
-        if self.config.enable_zk_proofs && req.is_shielded {
-            let proof = self
-                .zk
-                .generate_solvency_proof(&req.amount, "0")
-                .map_err(|e| SdkError::new(ErrorCode::ProofGeneration, format!("failed to generate privacy proof: {}", e)))?;
-            tracing::info!("[SDK] Generated ZK Proof: {}...", &proof[..10.min(proof.len())]);
+    /// Records every request, routing decision, and outcome to the given audit
+    /// logger, redacted per the logger's `RedactionConfig`
+    pub fn with_audit_logger(mut self, logger: AuditLogger) -> Self {
+        self.audit = Some(logger);
+        self
+    }
+
+    /// Screens every transaction with the given provider before execution
+    pub fn with_screening_provider(mut self, provider: Arc<dyn ScreeningProvider>) -> Self {
+        self.screening = Some(provider);
+        self
+    }
+
+    /// Enforces the engine's spending policies before every execution
+    pub fn with_policy_engine(mut self, engine: PolicyEngine) -> Self {
+        self.policy = Some(engine);
+        self
+    }
+
+    /// Holds transfers to registered addresses to their chain and limits;
+    /// with `require_allowlisted_recipients`, refuses all other recipients
+    pub fn with_address_book(mut self, book: AddressBook) -> Self {
+        self.address_book = Some(book);
+        self
+    }
+
+    /// Forwards every recorded transaction to the sink (e.g. a StatsD agent)
+    pub fn with_metrics_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.metrics = self.metrics.with_sink(sink);
+        self
+    }
+
+    /// Evaluates the monitor's alert rules after every recorded transaction
+    pub fn with_alert_monitor(mut self, monitor: AlertMonitor) -> Self {
+        self.alerts = Some(monitor);
+        self
+    }
+
+    /// Tracks routing fees against the tracker's budgets, rejecting executions
+    /// that would exceed a hard-stop budget
+    pub fn with_fee_budget(mut self, tracker: FeeBudgetTracker) -> Self {
+        self.fee_budget = Some(tracker);
+        self
+    }
+
+    /// Records the fee of every execution in `history`, for percentile and
+    /// forecast queries
+    pub fn with_fee_history(mut self, history: Arc<FeeHistory>) -> Self {
+        self.fee_history = Some(history);
+        self
+    }
+
+    /// Runs `validator` on every request after the built-in validators
+    pub fn with_validator(mut self, validator: Arc<dyn Validator>) -> Self {
+        self.validators = self.validators.with_validator(validator);
+        self
+    }
+
+    /// Runs `hook` around every execution, after the hooks registered before it
+    pub fn with_execution_hook(mut self, hook: Arc<dyn ExecutionHook>) -> Self {
+        self.hooks = self.hooks.with_hook(hook);
+        self
+    }
+
+    /// Handles requests of the handler's intent type with it, replacing the
+    /// built-in handler
+    pub fn with_intent_handler(mut self, handler: Arc<dyn IntentHandler>) -> Self {
+        self.intents.insert(handler.intent(), handler);
+        self
+    }
+
+    /// Caches responses in `backend` (e.g. Redis) so every instance sharing it
+    /// benefits from the others' results; enables caching even when
+    /// `enable_caching` is off
+    pub fn with_cache_backend(mut self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.cache = Some(ResponseCache::Shared(SharedCache::new(
+            backend,
+            "ecash:responses",
+            self.config.cache_ttl,
+        )));
+        self
+    }
+
+    /// Guards every execution against running twice for the same reference ID,
+    /// including after a crash mid-execution
+    pub fn with_exactly_once(mut self, guard: ExactlyOnceGuard) -> Self {
+        self.exactly_once = Some(guard);
+        self
+    }
+
+    /// Enables `refund` against the settlements recorded in the manager
+    pub fn with_refunds(mut self, refunds: RefundManager) -> Self {
+        self.refunds = Some(refunds);
+        self
+    }
+
+    /// Enables `Escrow` requests, tracking their escrows in the manager for
+    /// `release_escrow` and `refund_escrow`
+    pub fn with_escrows(mut self, escrows: EscrowManager) -> Self {
+        self.escrows = Some(escrows);
+        self
+    }
+
+    /// Lets requests have their fees paid by the registry's sponsors (see
+    /// `sponsorship`); recipient-paid fees need no registry
+    pub fn with_sponsors(mut self, sponsors: SponsorRegistry) -> Self {
+        self.sponsors = Some(sponsors);
+        self
+    }
+
+    /// Builds the settlement contract call of every unshielded transfer,
+    /// disbursement and escrow on a chain with a deployment (see
+    /// `contracts`), rejecting requests it can't be built for
+    pub fn with_contracts(mut self, deployments: Deployments) -> Self {
+        self.contracts = Some(deployments);
+        self
+    }
+
+    /// Signs the transactions of `execute_self_broadcast` with `signer`
+    pub fn with_broadcast_signer(mut self, signer: TransactionSigner) -> Self {
+        self.broadcast_signer = Some(signer);
+        self
+    }
+
+    /// Configuration the client was built with
+    pub fn config(&self) -> &SdkConfig {
+        &self.config
+    }
+
+    /// Signs the intents of `prepare` with `signer`. Its key is trusted by
+    /// `submit` on the same client.
+    pub fn with_prepare_signer(mut self, signer: TransactionSigner) -> Self {
+        self.prepare_signer = Some(signer);
+        self
+    }
+
+    /// Trusts intents prepared with `key` in `submit`
+    pub fn with_preparer_key(mut self, key: PublicKey) -> Self {
+        self.preparer_keys.push(key);
+        self
+    }
+
+    /// Books every execution in the ledger: pending on submission, settled
+    /// (with its fee) on confirmation, released on failure
+    pub fn with_ledger(mut self, ledger: Ledger) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Caches generated proofs in `store` for `SdkConfig::proof_cache_ttl`, so
+    /// a retried execution reuses its proof. Witnesses (the balances proven)
+    /// are stored encrypted under data keys wrapped by `keys`.
+    pub fn with_proof_cache(mut self, store: Arc<dyn ProofCacheStore>, keys: Arc<dyn KeyEncryptionKeys>) -> Self {
+        self.proof_cache = Some(ProofCache::new(store, WitnessVault::new(keys), self.config.proof_cache_ttl));
+        self
+    }
+
+    /// Manages circuit artifacts with `manager` instead of the one built from
+    /// `SdkConfig::zk_artifacts`
+    pub fn with_artifact_manager(mut self, manager: ArtifactManager) -> Self {
+        self.artifacts = Some(Arc::new(manager));
+        self
+    }
+
+    /// Loads circuit artifacts (`version`, or the configured one) and proves
+    /// with them from now on. Runs before the first shielded execution
+    /// unless called earlier. Returns the loaded version.
+    pub async fn reload_circuit_artifacts(&self, version: Option<&str>) -> Result<String> {
+        let manager = self
+            .artifacts
+            .as_ref()
+            .ok_or_else(|| SdkError::new(ErrorCode::InvalidRequest, "no circuit artifact registry or version configured"))?;
+        let loaded = match version {
+            Some(version) => manager.activate(version).await,
+            None => manager.load().await,
         }
+        .map_err(|e| SdkError::new(ErrorCode::ProofGeneration, format!("failed to load circuit artifacts: {}", e)))?;
+        let wasm = loaded
+            .path(ArtifactKind::CircuitWasm)
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.proofs.set_generator(Arc::new(ProofGenerator::new(wasm)));
+        Ok(loaded.version().to_string())
+    }
 
-        // 4. Request quotes from agents
-        let quotes = self
-            .negotiator
-            .request_quotes(req)
+    /// Uses the adapter for on-chain reads on its chain (replaces any previous adapter)
+    pub fn with_chain_adapter(mut self, adapter: Arc<dyn ChainAdapter>) -> Self {
+        self.chains.insert(adapter.chain(), adapter);
+        self
+    }
+
+    /// Returns the on-chain receipt of a transaction, or `None` while it is pending
+    pub async fn get_transaction_status(&self, chain: ChainId, tx_hash: &str) -> Result<Option<TxReceipt>> {
+        self.chain_adapter(chain)?
+            .get_receipt(tx_hash)
             .await
-            .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("failed to get agent quotes: {}", e)))?;
+            .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch receipt: {}", e)))
+    }
 
-        // 5. Select best route
-        let best_route = self
-            .negotiator
-            .select_best_route(&quotes, "balanced")
-            .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("no suitable route found: {}", e)))?;
+    /// Replaces the sandbox's faucet (see `sandbox`)
+    pub fn with_faucet(mut self, faucet: Arc<dyn Faucet>) -> Self {
+        self.faucet = Some(faucet);
+        self
+    }
 
-        tracing::info!(
-            "[SDK] Selected Agent: {} (Fee: {}, Security: {:.2})",
-            best_route.agent_id,
-            best_route.estimated_fee,
-            best_route.security_score
-        );
+    /// Sends sandbox test funds of `asset` to `address`, on Base for EVM
+    /// addresses and on Solana otherwise. Fails on mainnet.
+    pub async fn request_test_funds(&self, address: &str, asset: &str) -> Result<FaucetGrant> {
+        self.request_funds_on(sandbox::chain_of(address), address, asset).await
+    }
 
-        // 6. Execute via selected agent
-        // NOTE: This is a mock execution. Real implementation would:
-        // - Submit transaction to selected agent
-        // - Wait for on-chain confirmation
-        // - Handle retries and error cases
-        tokio::time::sleep(Duration::from_millis(100)).await;
+    /// Generates a throwaway EVM account on `chain` in the sandbox
+    pub fn create_test_account(&self, chain: ChainId) -> Result<TestAccount> {
+        self.require_sandbox("test accounts")?;
+        TestAccount::generate(chain).map_err(|e| SdkError::new(ErrorCode::UnsupportedChain, e))
+    }
 
-        // 7. Construct Response
-        // NOTE: In production, tx_hash and block_height come from blockchain
-        let tx_hash = format!("0x{}", Uuid::new_v4().to_string().replace("-", ""));
+    /// Sends sandbox test funds of `asset` to `account`
+    pub async fn fund_test_account(&self, account: &TestAccount, asset: &str) -> Result<FaucetGrant> {
+        self.request_funds_on(account.chain, &account.address, asset).await
+    }
 
-        let resp = TransactionResponse {
-            tx_hash,
-            status: "confirmed".to_string(),
-            block_height: 1948201,
-            fee_used: best_route.estimated_fee.clone(),
+    async fn request_funds_on(&self, chain: ChainId, address: &str, asset: &str) -> Result<FaucetGrant> {
+        self.require_sandbox("test funds")?;
+        let faucet = self.faucet.as_ref().ok_or_else(|| {
+            SdkError::new(ErrorCode::InvalidRequest, "no faucet configured; set one with with_faucet")
+        })?;
+        let req = FaucetRequest {
+            chain,
+            address: address.to_string(),
+            asset: asset.to_uppercase(),
         };
+        faucet
+            .request_funds(&req)
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to get test funds: {}", e)))
+    }
 
-        // 8. Cache successful result
-        if let Some(ref cache) = self.cache {
-            let cache_key = format!("{}-{}-{}", req.intent_type.as_str(), req.amount, req.asset);
-            cache.set(cache_key, resp.clone());
+    fn require_sandbox(&self, what: &str) -> Result<()> {
+        let env = self.config.env();
+        if env.is_sandbox() {
+            return Ok(());
         }
+        Err(SdkError::new(
+            ErrorCode::InvalidRequest,
+            format!("{} are only available in testnet and devnet, not {}", what, env),
+        )
+        .with_details(serde_json::json!({ "reason": "not_sandbox" })))
+    }
 
-        Ok(resp)
+    /// Rejects transactions the payer cannot cover before generating a proof
+    pub fn with_balance_provider(mut self, provider: Arc<dyn BalanceProvider>) -> Self {
+        self.balance_provider = Some(provider);
+        self
     }
 
-    /// Returns current SDK performance metrics
-    pub fn get_metrics(&self) -> std::collections::HashMap<String, f64> {
-        if !self.config.enable_metrics {
-            let mut map = std::collections::HashMap::new();
-            map.insert("metrics_disabled".to_string(), 1.0);
-            return map;
+    /// Normalizes quoted fees and fee metrics to USD using the oracle
+    pub fn with_price_oracle(mut self, oracle: Arc<dyn PriceOracle>) -> Self {
+        self.price_oracle = Some(oracle);
+        self
+    }
+
+    /// Supplies the exchange rates of `get_conversion_quote`
+    pub fn with_conversion_provider(mut self, provider: Arc<dyn ConversionProvider>) -> Self {
+        self.conversion = Some(provider);
+        self
+    }
+
+    /// Uses the scanner to discover shielded notes for viewing-key balance queries
+    pub fn with_note_scanner(mut self, scanner: Arc<dyn NoteScanner>) -> Self {
+        self.note_scanner = Some(scanner);
+        self
+    }
+
+    /// Records the nullifiers of shielded spends (see `ExecuteOptions::with_notes`)
+    /// and refuses a second spend of the same note before proving it
+    pub fn with_nullifier_tracker(mut self, tracker: Arc<NullifierTracker>) -> Self {
+        self.nullifiers = Some(tracker);
+        self
+    }
+
+    /// Seals the amount, recipient and travel-rule data of shielded intents
+    /// to the executing agent's transport key from `directory` (see
+    /// `transport`). Shielded intents to agents without a key are refused.
+    pub fn with_agent_directory(mut self, directory: Arc<dyn AgentDirectory>) -> Self {
+        self.agent_directory = Some(directory);
+        self
+    }
+
+    /// Returns the balance of `asset` on `chain`.
+    ///
+    /// An address yields its transparent on-chain balance (via the chain
+    /// adapter); a viewing key (`evk_...`) yields its unspent shielded balance.
+    pub async fn get_balance(&self, address_or_viewing_key: &str, asset: &str, chain: ChainId) -> Result<Balance> {
+        let mut balance = Balance {
+            asset: asset.to_uppercase(),
+            chain,
+            transparent: None,
+            shielded: None,
+        };
+
+        if ViewingKey::is_viewing_key(address_or_viewing_key) {
+            let viewing_key = ViewingKey::parse(address_or_viewing_key)
+                .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
+            let scanner = self
+                .note_scanner
+                .as_ref()
+                .ok_or_else(|| SdkError::new(ErrorCode::InvalidRequest, "no note scanner configured"))?;
+            let notes = scanner
+                .notes(&viewing_key, chain)
+                .await
+                .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to scan notes: {}", e)))?;
+            balance.shielded = Some(notes::unspent_balance(&notes, asset).to_string());
+            return Ok(balance);
+        }
+
+        if chain != ChainId::Solana {
+            validator::validate_address(address_or_viewing_key)
+                .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
         }
-        self.metrics.get_stats()
+        let transparent = self
+            .chain_adapter(chain)?
+            .get_balance(address_or_viewing_key, asset)
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch balance: {}", e)))?;
+        balance.transparent = Some(transparent);
+        Ok(balance)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::{ChainId, IntentType, TransactionRequest};
+    fn chain_adapter(&self, chain: ChainId) -> Result<&Arc<dyn ChainAdapter>> {
+        self.chains
+            .get(&chain)
+            .ok_or_else(|| SdkError::new(ErrorCode::UnsupportedChain, format!("no chain adapter configured for {}", chain)))
+    }
 
-    #[tokio::test]
-    async fn test_client_new() {
-        let config = SdkConfig::default_config();
-        let client = EasyCashClient::new(Some(config));
-        assert!(client.is_ok());
+    /// Signs settlement receipts with the given key (see `sign_receipt`),
+    /// digesting them with `SdkConfig::hashes.signing`
+    pub fn with_receipt_signer(mut self, signer: TransactionSigner) -> Self {
+        self.receipt_signer = Some(signer.with_hash(self.config.hashes.signing));
+        self
     }
 
-    #[tokio::test]
-    async fn test_client_new_default() {
-        let client = EasyCashClient::new(None);
-        assert!(client.is_ok());
+    /// Produces a signed receipt binding `resp` to the intent of `req`
+    pub fn sign_receipt(&self, req: &TransactionRequest, resp: TransactionResponse) -> Result<SignedReceipt> {
+        let signer = self
+            .receipt_signer
+            .as_ref()
+            .ok_or_else(|| SdkError::new(ErrorCode::SignerUnavailable, "no receipt signer configured"))?;
+        let (quote_hash, sealed) = self
+            .submissions
+            .get(&resp.tx_hash)
+            .map_or((None, None), |tx| (tx.quote_hash, tx.sealed_intent));
+        SignedReceipt::sign_settlement(req, resp, quote_hash, sealed, signer)
+            .map_err(|e| SdkError::new(ErrorCode::SignerUnavailable, format!("failed to sign receipt: {}", e)))
     }
 
-    #[tokio::test]
-    async fn test_execute_transaction() {
-        let client = EasyCashClient::new(None).unwrap();
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
+    /// Constructs a transfer intent and executes it with full validation.
+    ///
+    /// The request's `correlation_id` (or a generated one) is attached to the
+    /// tracing span, events, errors, and response of this call.
+    pub async fn execute_transaction(
+        &self,
+        req: &TransactionRequest,
+    ) -> Result<TransactionResponse> {
+        self.execute_with_progress(req, &ExecuteOptions::default(), &ProgressReporter::default())
+            .await
+    }
+
+    /// Executes `req` like `execute_transaction`, with per-call `options`
+    pub async fn execute_transaction_with_options(
+        &self,
+        req: &TransactionRequest,
+        options: &ExecuteOptions,
+    ) -> Result<TransactionResponse> {
+        self.execute_with_progress(req, options, &ProgressReporter::default()).await
+    }
+
+    /// Runs `req` through validation, policies and route selection, then
+    /// signs its settlement contract call with the broadcast signer instead
+    /// of handing it to an agent (see `broadcast`). Broadcast the returned
+    /// transactions in order and pass the last one's hash to
+    /// `confirm_external_broadcast`. Needs a deployment for the request's
+    /// chain (see `with_contracts`); shielded and sponsored requests can't be
+    /// self-broadcast.
+    pub async fn execute_self_broadcast(&self, req: &TransactionRequest, broadcast: SelfBroadcast) -> Result<SignedExecution> {
+        let options = ExecuteOptions {
+            self_broadcast: Some(broadcast),
+            ..Default::default()
         };
+        let resp = self.execute_with_progress(req, &options, &ProgressReporter::default()).await?;
+        // A replayed reference returns its recorded response; its signed
+        // transactions are only kept until confirmed
+        let broadcasts = self.broadcasts.lock().unwrap_or_else(|e| e.into_inner());
+        broadcasts.get(&resp.tx_hash).map(|pending| pending.execution.clone()).ok_or_else(|| {
+            SdkError::new(
+                ErrorCode::DuplicateReference,
+                format!("{} was already executed ({} in {})", req.reference_id, resp.status, resp.tx_hash),
+            )
+        })
+    }
 
-        let resp = client.execute_transaction(&req).await;
-        assert!(resp.is_ok());
-        let resp = resp.unwrap();
-        assert!(resp.tx_hash.starts_with("0x"));
-        assert_eq!(resp.status, "confirmed");
+    /// Books the outcome of a self-broadcast transaction from its receipt:
+    /// the exactly-once record, ledger, escrows, audit trail and events are
+    /// updated as if an agent had executed it. Returns a "pending" response,
+    /// changing nothing, while the transaction isn't included yet. A reverted
+    /// transaction fails with `EXECUTION_REVERTED` and releases the reference
+    /// ID so the request can be signed again.
+    pub async fn confirm_external_broadcast(&self, tx_hash: &str) -> Result<TransactionResponse> {
+        let (req, correlation_id) = {
+            let broadcasts = self.broadcasts.lock().unwrap_or_else(|e| e.into_inner());
+            let pending = broadcasts.get(tx_hash).ok_or_else(|| {
+                SdkError::new(
+                    ErrorCode::InvalidRequest,
+                    format!("{} is not a self-broadcast transaction awaiting confirmation", tx_hash),
+                )
+            })?;
+            (pending.req.clone(), pending.execution.correlation_id.clone())
+        };
+        let adapter = self.chain_adapter(req.source_chain)?;
+        let native = adapter.native_asset().ok_or_else(|| {
+            SdkError::new(
+                ErrorCode::UnsupportedChain,
+                format!("the native asset of {} is unknown", req.source_chain),
+            )
+        })?;
+        let receipt = adapter
+            .get_receipt(tx_hash)
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch receipt: {}", e)))?;
+        let Some(receipt) = receipt else {
+            return Ok(TransactionResponse {
+                tx_hash: tx_hash.to_string(),
+                status: "pending".to_string(),
+                block_height: 0,
+                fee_used: String::new(),
+                correlation_id,
+                disbursements: Vec::new(),
+                fee_splits: Vec::new(),
+            });
+        };
+        let Some(pending) = self.broadcasts.lock().unwrap_or_else(|e| e.into_inner()).remove(tx_hash) else {
+            return Err(SdkError::new(ErrorCode::DuplicateReference, format!("{} was already confirmed", tx_hash)));
+        };
+
+        if !receipt.success {
+            if let Some(ref guard) = self.exactly_once {
+                if let Err(e) = guard.release(&req.reference_id) {
+                    tracing::warn!("[SDK] Failed to release {}: {}", req.reference_id, e);
+                }
+            }
+            if let Some(ref ledger) = self.ledger {
+                if let Err(e) = ledger.release(&req.reference_id) {
+                    tracing::warn!("[SDK] Failed to book {} in the ledger: {}", req.reference_id, e);
+                }
+            }
+            let err = SdkError::new(
+                ErrorCode::ExecutionReverted,
+                format!("transaction {} reverted on {}", tx_hash, req.source_chain),
+            )
+            .with_details(serde_json::json!({ "tx_hash": tx_hash, "block_height": receipt.block_height }))
+            .with_correlation_id(&correlation_id);
+            self.publish_failure(&req, &correlation_id, &err);
+            return Err(err);
+        }
+
+        let fee_paid = receipt
+            .fee_paid
+            .parse::<u128>()
+            .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("invalid fee in receipt: {}", e)))?;
+        let resp = TransactionResponse {
+            tx_hash: tx_hash.to_string(),
+            // Escrowed funds are only paid on release
+            status: if pending.escrow_terms.is_some() { "escrowed" } else { "confirmed" }.to_string(),
+            block_height: receipt.block_height,
+            fee_used: native.format_fee(fee_paid),
+            correlation_id: correlation_id.clone(),
+            disbursements: pending.disbursements.iter().map(|d| DisbursementResult::new(d, None)).collect(),
+            fee_splits: pending.fee_splits,
+        };
+        if let Some(ref guard) = self.exactly_once {
+            if let Err(e) = guard.complete(&req, &resp) {
+                tracing::warn!("[SDK] Failed to record submission of {}: {}", req.reference_id, e);
+            }
+        }
+        if let Some(ref ledger) = self.ledger {
+            let booked = match pending.escrow_terms {
+                Some(_) => Ok(()),
+                None => ledger
+                    .settle(&req.reference_id, tx_hash, Some(&resp.fee_used))
+                    .and_then(|_| ledger.record_fee_splits(&req.reference_id, &req.asset, &resp.fee_splits, tx_hash)),
+            };
+            if let Err(e) = booked {
+                tracing::warn!("[SDK] Failed to book {} in the ledger: {}", req.reference_id, e);
+            }
+        }
+        self.submissions.record(
+            tx_hash,
+            SubmittedTx {
+                reference_id: req.reference_id.clone(),
+                agent_id: pending.execution.agent_id.clone(),
+                chain: req.source_chain,
+                replaced_by: None,
+                sealed_intent: None,
+                quote_hash: pending.quote_hash,
+            },
+        );
+        if let (Some(terms), Some(ref escrows)) = (pending.escrow_terms, &self.escrows) {
+            let recorded = Escrow::new(&req, terms, &pending.execution.agent_id, tx_hash)
+                .and_then(|escrow| escrows.record_lock(escrow));
+            if let Err(e) = recorded {
+                tracing::warn!("[SDK] Failed to record escrow {}: {}", req.reference_id, e);
+            }
+        }
+        self.record_audit(AuditKind::Response, &req.reference_id, || serde_json::json!(resp));
+        self.events.publish_with(|| SdkEvent::Confirmed {
+            reference_id: req.reference_id.clone(),
+            correlation_id: correlation_id.clone(),
+            tx_hash: resp.tx_hash.clone(),
+            fee_used: resp.fee_used.clone(),
+            account_id: req.account_id.clone(),
+            metadata: req.metadata.clone(),
+        });
+        Ok(resp)
     }
 
-    #[tokio::test]
-    async fn test_execute_transaction_with_shield() {
-        let client = EasyCashClient::new(None).unwrap();
-        let req = TransactionRequest {
-            reference_id: "ref_002".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: true,
+    /// Fetches the agent quotes for `req` into a bundle that `prepare` can
+    /// select a route from offline until `valid_for` has passed (see
+    /// `offline`)
+    pub async fn fetch_quote_bundle(&self, req: &TransactionRequest, valid_for: Duration) -> Result<QuoteBundle> {
+        let req = &*self.with_normalized_amount(req);
+        let (quotes, _) = self.request_quotes(&*self.with_base_units(req)?).await?;
+        let fetched_at_ms = offline::now_ms();
+        Ok(QuoteBundle {
+            intent_hash: receipt::intent_hash(req),
+            quotes,
+            fetched_at_ms,
+            expires_at_ms: fetched_at_ms + valid_for.as_millis() as u64,
+        })
+    }
+
+    /// Validates `req`, selects its route from `bundle` and generates its
+    /// solvency proof without network access, then signs the result with
+    /// the prepare signer. Policies and the fee budget are checked against
+    /// local state; `submit` checks them again before executing.
+    pub async fn prepare(&self, req: &TransactionRequest, bundle: &QuoteBundle) -> Result<PreparedIntent> {
+        let signer = self.prepare_signer.as_ref().ok_or_else(|| {
+            SdkError::new(ErrorCode::SignerUnavailable, "no prepare signer configured; set one with with_prepare_signer")
+        })?;
+        let req = &*self.with_normalized_amount(req);
+        self.validators
+            .validate(req)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("validation failed: {}", e)))?;
+        travel_rule::validate_request(req, &self.config.travel_rule)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("travel rule validation failed: {}", e)))?;
+        if !bundle.covers(req) {
+            return Err(SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!("the quote bundle was not fetched for {}", req.reference_id),
+            ));
+        }
+        if bundle.is_expired() {
+            return Err(SdkError::new(ErrorCode::Expired, "the quote bundle has expired; fetch a new one"));
+        }
+        let req = &*self.with_base_units(req)?;
+        if let Some(ref policy) = self.policy {
+            policy.evaluate(req).map_err(|v| {
+                SdkError::new(ErrorCode::PolicyViolation, v.to_string()).with_details(serde_json::json!(v))
+            })?;
+        }
+        let handler = self.intents.get(&req.intent_type).ok_or_else(|| {
+            SdkError::new(ErrorCode::InvalidRequest, format!("no handler for {} intents", req.intent_type))
+        })?;
+        let intent_ctx = IntentContext {
+            correlation_id: req.correlation_id.as_deref().unwrap_or_default(),
+            address_book: self.address_book.as_ref(),
+            require_allowlisted_recipients: self.config.require_allowlisted_recipients,
+            commitment_hash: self.config.hashes.commitments,
         };
+        handler.check(&intent_ctx, req).await?;
 
-        let resp = client.execute_transaction(&req).await;
-        assert!(resp.is_ok());
+        // The bundle crossed the gap, so its quotes are checked again
+        let residency = &self.config.residency;
+        let mut quotes = Vec::with_capacity(bundle.quotes.len());
+        for quote in &bundle.quotes {
+            if self.check_quote_signature(quote).await.is_ok() && residency.permits(quote.region.as_deref()) {
+                quotes.push(quote.clone());
+            }
+        }
+        if quotes.is_empty() {
+            return Err(SdkError::new(ErrorCode::AgentUnavailable, "the quote bundle has no usable quote")
+                .with_details(serde_json::json!({ "reason": "unverified_quotes" })));
+        }
+        let route = self.select_route(&quotes)?;
+        if let Some(ref budget) = self.fee_budget {
+            budget.check(&route.estimated_fee).map_err(|b| {
+                SdkError::new(ErrorCode::BudgetExceeded, b.to_string()).with_details(serde_json::json!(b))
+            })?;
+        }
+
+        let proof = match self.config.enable_zk_proofs && req.is_shielded {
+            true => {
+                let sponsored = FeePayer::of(req).is_some_and(|payer| payer != FeePayer::Sender);
+                let payer_fee = if sponsored { "" } else { route.estimated_fee.as_str() };
+                let required =
+                    solvency::required_amount(req, payer_fee).map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
+                // No balance provider offline: the balance is assumed to equal the requirement
+                let input = SolvencyInput::new(required.to_string(), required.to_string());
+                let proof = self.proofs.prove(ProofJob { inputs: vec![input.clone()], size: 1 }).await.map_err(|e| {
+                    SdkError::new(ErrorCode::ProofGeneration, format!("failed to generate privacy proof: {}", e))
+                })?;
+                if !self.proofs.verify(&proof) {
+                    return Err(SdkError::new(ErrorCode::ProofGeneration, "generated privacy proof failed verification"));
+                }
+                Some(PreparedProof { input, proof })
+            }
+            false => None,
+        };
+
+        PreparedIntent {
+            request: req.clone(),
+            route,
+            proof,
+            prepared_at_ms: offline::now_ms(),
+            expires_at_ms: bundle.expires_at_ms,
+            public_key: String::new(),
+            signature: String::new(),
+        }
+        .sign(signer)
+        .map_err(|e| SdkError::new(ErrorCode::SignerUnavailable, format!("failed to sign prepared intent: {}", e)))
     }
 
-    #[tokio::test]
-    async fn test_execute_transaction_invalid() {
-        let client = EasyCashClient::new(None).unwrap();
-        let req = TransactionRequest {
-            reference_id: "ref_003".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "".to_string(), // Invalid: empty amount
-            asset: "USDC".to_string(),
-            recipient: None,
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
+    /// Executes an intent from `prepare` on its prepared route, reusing its
+    /// proof when the balance it was proven against still holds. Only
+    /// intents signed by a trusted preparer (see `with_preparer_key`) and
+    /// not yet expired are accepted.
+    pub async fn submit(&self, prepared: &PreparedIntent) -> Result<TransactionResponse> {
+        let key = prepared
+            .verify()
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
+        let own_key = self.prepare_signer.as_ref().map(|s| PublicKey::from(&s.verifying_key()));
+        if !self.preparer_keys.contains(&key) && own_key != Some(key) {
+            return Err(SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!("{} was prepared by an untrusted key", prepared.request.reference_id),
+            )
+            .with_details(serde_json::json!({ "reason": "untrusted_preparer", "public_key": prepared.public_key })));
+        }
+        if prepared.is_expired() {
+            return Err(SdkError::new(
+                ErrorCode::Expired,
+                format!("the prepared intent {} has expired; prepare it again", prepared.request.reference_id),
+            ));
+        }
+        let options = ExecuteOptions {
+            prepared: Some(Arc::new(prepared.clone())),
+            ..Default::default()
         };
+        self.execute_with_progress(&prepared.request, &options, &ProgressReporter::default()).await
+    }
 
-        let resp = client.execute_transaction(&req).await;
-        assert!(resp.is_err());
+    /// Cancels the in-flight execution of `reference_id` if it hasn't been
+    /// handed to an agent yet (see `cancellation`). A cancelled execution
+    /// stops waiting on quotes, screening or admission and fails with
+    /// `CANCELLED`; one already submitted runs to completion.
+    pub fn cancel(&self, reference_id: &str) -> CancelOutcome {
+        let outcome = self.in_flight.cancel(reference_id);
+        tracing::info!("[SDK] Cancellation of {}: {:?}", reference_id, outcome);
+        outcome
     }
 
-    #[tokio::test]
-    async fn test_execute_transaction_caching() {
-        let mut config = SdkConfig::default_config();
-        config.enable_caching = true;
-        let client = EasyCashClient::new(Some(config)).unwrap();
-        
-        let req = TransactionRequest {
-            reference_id: "ref_004".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
+    /// Executes `req` like `execute_transaction`, streaming progress for
+    /// long-running (e.g. multi-hop cross-chain) executions: the selected
+    /// route, per-hop updates and bridge confirmations reported by the agent,
+    /// then the final result.
+    pub fn execute_transaction_streaming<'a>(&'a self, req: &'a TransactionRequest) -> ExecutionStream<'a> {
+        let (progress, rx) = ProgressReporter::channel();
+        ExecutionStream::new(
+            rx,
+            Box::pin(async move { self.execute_with_progress(req, &ExecuteOptions::default(), &progress).await }),
+        )
+    }
+
+    async fn execute_with_progress(
+        &self,
+        req: &TransactionRequest,
+        options: &ExecuteOptions,
+        progress: &ProgressReporter,
+    ) -> Result<TransactionResponse> {
+        let req = &*self.with_normalized_amount(req);
+        let req = &*match self.hooks.before_validate(req).await {
+            Ok(req) => req,
+            Err(err) => {
+                let correlation_id = req.correlation_id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
+                let err = err.with_correlation_id(&correlation_id);
+                self.publish_failure(req, &correlation_id, &err);
+                return Err(err);
+            }
         };
+        let correlation_id = req
+            .correlation_id
+            .clone()
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let redaction = &self.config.redaction;
+        let span = tracing::info_span!(
+            "execute_transaction",
+            correlation_id = %correlation_id,
+            reference_id = %req.reference_id,
+            recipient = %redaction.redact_opt(SensitiveField::Recipient, req.recipient.as_deref()),
+            amount = %redaction.redact(SensitiveField::Amount, &req.amount)
+        );
 
-        // First call
-        let resp1 = client.execute_transaction(&req).await.unwrap();
-        
-        // Second call should hit cache
-        let resp2 = client.execute_transaction(&req).await.unwrap();
-        assert_eq!(resp1.tx_hash, resp2.tx_hash);
+        let in_flight = self
+            .in_flight
+            .register(&req.reference_id, options.cancellation.clone().unwrap_or_default());
+        self.execute_transaction_traced(req, &correlation_id, &in_flight, options, progress)
+            .instrument(span)
+            .await
     }
 
-    #[tokio::test]
-    async fn test_get_metrics() {
-        let client = EasyCashClient::new(None).unwrap();
-        let metrics = client.get_metrics();
-        assert!(metrics.contains_key("total_transactions"));
+    async fn execute_transaction_traced(
+        &self,
+        req: &TransactionRequest,
+        correlation_id: &str,
+        in_flight: &InFlight,
+        options: &ExecuteOptions,
+        progress: &ProgressReporter,
+    ) -> Result<TransactionResponse> {
+        // Wait for an execution slot (backpressure under high load)
+        let admission = tokio::select! {
+            biased;
+            _ = in_flight.cancelled_before_submission() => Err(cancellation::cancelled_error()),
+            permit = self.limiter.acquire() => {
+                permit.map_err(|e| SdkError::new(ErrorCode::Timeout, format!("admission rejected: {}", e)))
+            }
+        };
+        let _permit = match admission {
+            Ok(permit) => permit,
+            Err(err) => {
+                let err = err.with_correlation_id(correlation_id);
+                self.publish_failure(req, correlation_id, &err);
+                return Err(err);
+            }
+        };
+
+        // Return the recorded outcome instead of executing a reference twice
+        if let Some(ref guard) = self.exactly_once {
+            match guard.begin(req).await {
+                Ok(None) => {}
+                Ok(Some(previous)) => {
+                    tracing::info!("[SDK] Reference {} already executed, returning recorded response", req.reference_id);
+                    return Ok(previous);
+                }
+                Err(rejection) => {
+                    let err = guard_error(rejection).with_correlation_id(correlation_id);
+                    self.publish_failure(req, correlation_id, &err);
+                    return Err(err);
+                }
+            }
+        }
+
+        let start_time = Instant::now();
+        self.record_audit(AuditKind::Request, &req.reference_id, || serde_json::json!(req));
+        
+        // Execute transaction and capture result; cancellation aborts whatever
+        // the execution is waiting on until it passes the point of no return
+        let result = tokio::select! {
+            biased;
+            _ = in_flight.cancelled_before_submission() => Err(cancellation::cancelled_error()),
+            result = self.execute_transaction_internal(req, correlation_id, in_flight, options, progress) => result,
+        };
+        let result = result
+            .map(|mut resp| {
+                resp.correlation_id = correlation_id.to_string();
+                resp
+            })
+            .map_err(|e| e.with_correlation_id(correlation_id));
+
+        if let Some(ref guard) = self.exactly_once {
+            let recorded = match result {
+                Ok(ref resp) => guard.complete(req, resp),
+                Err(_) => guard.abort(req),
+            };
+            if let Err(e) = recorded {
+                tracing::warn!("[SDK] Failed to record submission of {}: {}", req.reference_id, e);
+            }
+        }
+
+        // Unconfirmed transactions stay pending until settled after reconciliation
+        if let Some(ref ledger) = self.ledger {
+            let booked = match result {
+                Ok(ref resp) if resp.status == "confirmed" => ledger
+                    .settle(&req.reference_id, &resp.tx_hash, Some(&resp.fee_used))
+                    .and_then(|_| ledger.record_fee_splits(&req.reference_id, &req.asset, &resp.fee_splits, &resp.tx_hash)),
+                Ok(_) => Ok(()),
+                Err(_) => ledger.release(&req.reference_id),
+            };
+            if let Err(e) = booked {
+                tracing::warn!("[SDK] Failed to book {} in the ledger: {}", req.reference_id, e);
+            }
+        }
+        self.hooks.after_execute(req, &result).await;
+        
+        // Record metrics based on actual result
+        if self.config.enable_metrics {
+            let success = result.is_ok();
+            let fee = match (&result, &self.price_oracle) {
+                (Ok(r), Some(oracle)) => pricing::normalize_fee(oracle.as_ref(), &r.fee_used)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("[SDK] Failed to normalize fee for metrics: {}", e);
+                        0.0
+                    }),
+                // Try to parse fee from response, default to 0.0
+                (Ok(r), None) => r.fee_used.split_whitespace().next()
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .unwrap_or(0.0),
+                (Err(_), _) => 0.0,
+            };
+            let latency = start_time.elapsed();
+            
+            self.metrics.record_transaction_for(req.account_id.as_deref(), success, fee, latency);
+
+            // Evaluated in the background so slow alert handlers never delay the caller
+            if let Some(ref alerts) = self.alerts {
+                let alerts = alerts.clone();
+                let metrics = self.metrics.clone();
+                tokio::spawn(async move {
+                    alerts.evaluate(&metrics).await;
+                });
+            }
+        }
+
+        match &result {
+            // Signed transactions are confirmed by confirm_external_broadcast
+            Ok(resp) if resp.status == "signed" => {
+                self.record_audit(AuditKind::Response, &req.reference_id, || serde_json::json!(resp));
+            }
+            Ok(resp) => {
+                self.record_audit(AuditKind::Response, &req.reference_id, || serde_json::json!(resp));
+                self.events.publish_with(|| SdkEvent::Confirmed {
+                    reference_id: req.reference_id.clone(),
+                    correlation_id: correlation_id.to_string(),
+                    tx_hash: resp.tx_hash.clone(),
+                    fee_used: resp.fee_used.clone(),
+                    account_id: req.account_id.clone(),
+                    metadata: req.metadata.clone(),
+                });
+            }
+            Err(err) => self.publish_failure(req, correlation_id, err),
+        }
+        
+        result
+    }
+
+    fn publish_failure(&self, req: &TransactionRequest, correlation_id: &str, err: &SdkError) {
+        self.record_audit(
+            AuditKind::Error,
+            &req.reference_id,
+            || serde_json::json!({
+                "code": err.code.to_string(),
+                "message": err.message,
+                "correlation_id": correlation_id,
+            }),
+        );
+        self.events.publish_with(|| SdkEvent::Failed {
+            reference_id: req.reference_id.clone(),
+            correlation_id: correlation_id.to_string(),
+            code: err.code,
+            message: err.message.clone(),
+            account_id: req.account_id.clone(),
+            metadata: req.metadata.clone(),
+        });
+    }
+
+    /// `payload` is only built when an audit logger is configured
+    fn record_audit(&self, kind: AuditKind, reference_id: &str, payload: impl FnOnce() -> serde_json::Value) {
+        if let Some(ref audit) = self.audit {
+            if let Err(e) = audit.record(kind, reference_id, payload()) {
+                tracing::warn!("[SDK] Failed to write audit entry: {}", e);
+            }
+        }
+    }
+
+    async fn execute_transaction_internal(
+        &self,
+        req: &TransactionRequest,
+        correlation_id: &str,
+        in_flight: &InFlight,
+        options: &ExecuteOptions,
+        progress: &ProgressReporter,
+    ) -> Result<TransactionResponse> {
+
+        // 1. Validate Request
+        if let Err(e) = self.validators.validate(req) {
+            self.events.publish_with(|| SdkEvent::ValidationFailed {
+                reference_id: req.reference_id.clone(),
+                correlation_id: correlation_id.to_string(),
+                reason: e.clone(),
+            });
+            return Err(SdkError::new(ErrorCode::InvalidRequest, format!("validation failed: {}", e)));
+        }
+
+        if let Err(e) = travel_rule::validate_request(req, &self.config.travel_rule) {
+            self.events.publish_with(|| SdkEvent::ValidationFailed {
+                reference_id: req.reference_id.clone(),
+                correlation_id: correlation_id.to_string(),
+                reason: e.clone(),
+            });
+            return Err(SdkError::new(ErrorCode::InvalidRequest, format!("travel rule validation failed: {}", e)));
+        }
+        let req = &*self.with_base_units(req)?;
+
+        // 1a. Compliance screening of the recipient, every disbursement leg
+        // and every fee split recipient; any rejection rejects the whole request
+        if let Some(ref screening) = self.screening {
+            for screened in Self::screening_requests(req)? {
+                let decision = screening
+                    .screen(&screened)
+                    .await
+                    .map_err(|e| SdkError::new(ErrorCode::ComplianceRejected, format!("screening unavailable: {}", e)))?;
+                if let ScreeningDecision::Rejected { reason } = decision {
+                    return Err(SdkError::new(ErrorCode::ComplianceRejected, format!("screening rejected transaction: {}", reason))
+                        .with_details(serde_json::json!({ "recipient": screened.recipient })));
+                }
+            }
+        }
+
+        // 1b. Spending policies
+        if let Some(ref policy) = self.policy {
+            policy.evaluate(req).map_err(|v| {
+                SdkError::new(ErrorCode::PolicyViolation, v.to_string()).with_details(serde_json::json!(v))
+            })?;
+        }
+
+        // 1c. Intent-specific checks (e.g. the recipient allowlist)
+        let handler = self.intents.get(&req.intent_type).ok_or_else(|| {
+            SdkError::new(ErrorCode::InvalidRequest, format!("no handler for {} intents", req.intent_type))
+        })?;
+        let intent_ctx = IntentContext {
+            correlation_id,
+            address_book: self.address_book.as_ref(),
+            require_allowlisted_recipients: self.config.require_allowlisted_recipients,
+            commitment_hash: self.config.hashes.commitments,
+        };
+        handler.check(&intent_ctx, req).await?;
+        let fee_splits = intents::fee_splits(req)?;
+        let escrow_terms = match req.intent_type {
+            IntentType::Escrow if self.escrows.is_none() => {
+                return Err(SdkError::new(
+                    ErrorCode::InvalidRequest,
+                    "escrows are not enabled; configure them with with_escrows",
+                ));
+            }
+            IntentType::Escrow => Some(EscrowTerms::of(req).map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?),
+            _ => None,
+        };
+        let fee_payer = FeePayer::of(req).filter(|payer| *payer != FeePayer::Sender);
+        if matches!(fee_payer, Some(FeePayer::Sponsor { .. })) && self.sponsors.is_none() {
+            return Err(SdkError::new(
+                ErrorCode::InvalidRequest,
+                "sponsored fees are not enabled; configure them with with_sponsors",
+            ));
+        }
+
+        // Fee splits are paid out alongside the recipient, as a disbursement
+        let legs = intents::disbursements(req)?;
+        let (disbursements, platform_fees) = match req.intent_type {
+            IntentType::Disburse => (Some(legs.to_vec()), Vec::new()),
+            _ if !fee_splits.is_empty() => {
+                let (net, platform_fees) = intents::apply_fee_splits(req, fee_splits)?;
+                let recipient = Disbursement {
+                    recipient: req.recipient.clone().unwrap_or_default(),
+                    amount: net.to_string(),
+                };
+                let legs = std::iter::once(recipient)
+                    .chain(platform_fees.iter().map(|fee| Disbursement {
+                        recipient: fee.recipient.clone(),
+                        amount: fee.amount.clone(),
+                    }))
+                    .filter(|leg| leg.amount != "0")
+                    .collect();
+                (Some(legs), platform_fees)
+            }
+            _ => (None, Vec::new()),
+        };
+
+        // 1d. Settlement contract call, signed here for self-broadcast executions
+        let settlement_call = self.settlement_call(req, disbursements.as_deref())?;
+        if let Some(ref call) = settlement_call {
+            self.record_audit(AuditKind::Decision, &req.reference_id, || {
+                serde_json::json!({
+                    "settlement_call": {
+                        "contract": call.to,
+                        "function": call.function,
+                        "calldata_hash": call.calldata_hash(),
+                    },
+                })
+            });
+        }
+        let broadcast = match options.self_broadcast {
+            Some(ref broadcast) => Some(self.sign_broadcast(req, broadcast, settlement_call.as_ref(), fee_payer.is_some())?),
+            None => None,
+        };
+
+        // 2. Check Cache for similar recent transactions
+        // Self-broadcast executions always sign their own transactions
+        let cache_key = self.cache.as_ref().filter(|_| options.self_broadcast.is_none()).map(|_| cache_key(req));
+        if let (Some(cache), Some(key)) = (&self.cache, &cache_key) {
+            if let Some(cached) = cache.get(key).await {
+                tracing::info!("[SDK] Cache hit for transaction pattern");
+                return Ok(cached);
+            }
+        }
+
+        // 3. Solvency pre-check (the fee is checked once a route is selected)
+        let available = match self.balance_provider {
+            Some(ref provider) => {
+                let available = provider
+                    .available_balance(req)
+                    .await
+                    .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch payer balance: {}", e)))?;
+                let amount = req
+                    .amount
+                    .parse::<f64>()
+                    .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid amount: {}", e)))?;
+                check_solvency(available, amount, &req.asset)?;
+                Some(available)
+            }
+            None => None,
+        };
+
+        // 4. Request quotes from agents, or take the prepared route
+        let (quotes, quote_latency) = match options.prepared {
+            Some(ref prepared) => {
+                self.check_quote_signature(&prepared.route).await.map_err(|e| {
+                    SdkError::new(ErrorCode::AgentUnavailable, format!("prepared route rejected: {}", e))
+                })?;
+                (vec![prepared.route.clone()], Duration::ZERO)
+            }
+            None => self.request_quotes(req).await?,
+        };
+
+        for quote in &quotes {
+            if self.config.enable_metrics && options.prepared.is_none() {
+                self.metrics.record_quote(&quote.agent_id, quote_latency);
+            }
+            self.events.publish_with(|| SdkEvent::QuoteReceived {
+                reference_id: req.reference_id.clone(),
+                correlation_id: correlation_id.to_string(),
+                agent_id: quote.agent_id.clone(),
+                estimated_fee: quote.estimated_fee.clone(),
+            });
+        }
+
+        // 5. Select best route
+        let best_route = self.select_route(&quotes)?;
+        self.balancer.record_selection(&best_route.agent_id);
+
+        tracing::info!(
+            "[SDK] Selected Agent: {} (Fee: {}, Security: {:.2})",
+            best_route.agent_id,
+            best_route.estimated_fee,
+            best_route.security_score
+        );
+        if self.config.enable_metrics {
+            let best_fee = quotes.iter().filter_map(|q| q.fee_value()).reduce(f64::min);
+            let fee_delta = best_route.fee_value().zip(best_fee).map(|(fee, best)| fee - best);
+            self.metrics.record_selection(&best_route.agent_id, fee_delta);
+        }
+        self.events.publish_with(|| SdkEvent::RouteSelected {
+            reference_id: req.reference_id.clone(),
+            correlation_id: correlation_id.to_string(),
+            agent_id: best_route.agent_id.clone(),
+            estimated_fee: best_route.estimated_fee.clone(),
+            security_score: best_route.security_score,
+        });
+        progress.send(ExecutionUpdate::RouteSelected {
+            agent_id: best_route.agent_id.clone(),
+            hops: best_route.route.clone(),
+        });
+        self.record_audit(
+            AuditKind::Decision,
+            &req.reference_id,
+            || serde_json::json!({
+                "agent_id": best_route.agent_id,
+                "estimated_fee": best_route.estimated_fee,
+                "security_score": best_route.security_score,
+                "route": best_route.route,
+                "quotes_considered": quotes.len(),
+                "quote_hash": best_route.commitment().ok(),
+            }),
+        );
+
+        // 5a. Fee budget
+        if let Some(ref budget) = self.fee_budget {
+            budget.check(&best_route.estimated_fee).map_err(|b| {
+                SdkError::new(ErrorCode::BudgetExceeded, b.to_string()).with_details(serde_json::json!(b))
+            })?;
+        }
+
+        // 5b. Fees paid by the recipient or a sponsor
+        let sponsorship = match fee_payer {
+            Some(ref payer) => {
+                let sponsorship = match self.sponsors {
+                    Some(ref sponsors) => sponsors.authorize(req, payer, &best_route.estimated_fee),
+                    None => sponsorship::recipient_pays(req, &best_route.estimated_fee),
+                }
+                .map_err(sponsor_error)?;
+                self.record_audit(AuditKind::Decision, &req.reference_id, || {
+                    serde_json::json!({ "fee_payer": sponsorship.fee_payer, "fee_account": sponsorship.fee_account })
+                });
+                Some(sponsorship)
+            }
+            None => None,
+        };
+
+        // 5c. Intent-specific preparation (e.g. minting a shield's note)
+        let prepared = handler.prepare(&intent_ctx, req, &best_route).await?;
+        if !prepared.is_null() {
+            self.record_audit(
+                AuditKind::Decision,
+                &req.reference_id,
+                || serde_json::json!({ "intent": req.intent_type, "prepared": prepared }),
+            );
+        }
+
+        // 6. Generate ZK Proof if shielded, proving the balance covers amount + fee
+        // Fees paid by someone else don't come out of the payer's balance
+        let payer_fee = if sponsorship.is_some() { "" } else { best_route.estimated_fee.as_str() };
+        let required = solvency::required_amount(req, payer_fee).map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
+        if let Some(available) = available {
+            check_solvency(available, required, &req.asset)?;
+        }
+        let spends = match self.nullifiers {
+            Some(ref tracker) if req.is_shielded && !options.notes.is_empty() => {
+                tracker.check(&req.reference_id, &options.notes).map_err(spend_error)?;
+                Some(tracker)
+            }
+            _ => None,
+        };
+        if self.config.enable_zk_proofs && req.is_shielded {
+            // Without a balance provider the balance is unverified and assumed to equal the requirement
+            let balance = available.unwrap_or(required);
+            #[cfg(feature = "test-utils")]
+            let corrupt = self.inject_fault(InjectionPoint::BeforeProofGeneration, req, ErrorCode::ProofGeneration).await?;
+            let input = SolvencyInput::new(balance.to_string(), required.to_string());
+            let prepared = options
+                .prepared
+                .as_ref()
+                .and_then(|p| p.proof.as_ref())
+                .filter(|p| p.input == input)
+                .map(|p| p.proof.as_str());
+            let cached = match prepared.or_else(|| options.batch_proof.as_deref().and_then(|b| b.covering(&req.reference_id, &input))) {
+                Some(proof) => Some(proof.to_string()),
+                None => self.cached_proof(&req.reference_id, &input),
+            };
+            #[allow(unused_mut)]
+            let mut proof = match cached {
+                Some(proof) => proof,
+                None => {
+                    if self.artifacts.as_ref().is_some_and(|a| a.current().is_none()) {
+                        self.reload_circuit_artifacts(None).await?;
+                    }
+                    let proof = self.proofs.prove(ProofJob { inputs: vec![input.clone()], size: 1 }).await.map_err(|e| {
+                        SdkError::new(ErrorCode::ProofGeneration, format!("failed to generate privacy proof: {}", e))
+                    })?;
+                    if let Some(ref cache) = self.proof_cache {
+                        if let Err(e) = cache.put(&req.reference_id, &input, &proof) {
+                            tracing::warn!("[SDK] Failed to cache proof of {}: {}", req.reference_id, e);
+                        }
+                    }
+                    proof
+                }
+            };
+            #[cfg(feature = "test-utils")]
+            if corrupt {
+                faults::corrupt_proof(&mut proof);
+            }
+            if !self.proofs.verify(&proof) {
+                return Err(SdkError::new(ErrorCode::ProofGeneration, "generated privacy proof failed verification"));
+            }
+            tracing::info!(
+                "[SDK] Generated ZK Proof: {}",
+                self.config.redaction.redact(SensitiveField::Proof, &proof)
+            );
+            self.events.publish_with(|| SdkEvent::ProofGenerated {
+                reference_id: req.reference_id.clone(),
+                correlation_id: correlation_id.to_string(),
+                proof,
+            });
+        }
+
+        // Travel-rule data only leaves the SDK sealed to the beneficiary VASP
+        let sealed_travel_rule = match req.travel_rule {
+            Some(ref info) => {
+                let sealed = info
+                    .seal()
+                    .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("failed to seal travel-rule data: {}", e)))?;
+                tracing::debug!("[SDK] Sealed travel-rule data for {}", sealed.beneficiary_vasp);
+                Some(sealed)
+            }
+            None => None,
+        };
+
+        // Shielded intents are sealed to the agent, so its key is needed before submitting
+        let transport_key = match self.agent_directory {
+            Some(ref directory) if req.is_shielded => Some(self.transport_key(directory.as_ref(), &best_route).await?),
+            _ => None,
+        };
+
+        // 7. Execute via selected agent
+        // NOTE: This is a mock execution. Real implementation would:
+        // - Submit transaction to selected agent
+        // - Wait for on-chain confirmation
+        // - Handle retries and error cases
+        self.hooks.before_execute(req, &best_route).await?;
+        if let Some(ref sponsorship) = sponsorship {
+            self.breaker
+                .call(self.negotiator.sponsor_fee(sponsorship, &best_route))
+                .await
+                .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("agent rejected fee sponsorship: {}", e)))?;
+        }
+        // Point of no return: from here on the agent may broadcast
+        in_flight.submit()?;
+        if let Some(tracker) = spends {
+            tracker.record(&req.reference_id, &options.notes).map_err(spend_error)?;
+        }
+        if let Some(ref guard) = self.exactly_once {
+            guard.mark_submitted(req).map_err(|e| {
+                SdkError::new(ErrorCode::NetworkFailure, format!("failed to record submission: {}", e))
+            })?;
+        }
+        if let Some(ref ledger) = self.ledger {
+            ledger.record_submission(req).map_err(|e| {
+                SdkError::new(ErrorCode::NetworkFailure, format!("failed to record ledger entry: {}", e))
+            })?;
+        }
+        // Self-broadcast executions stop here; the caller broadcasts the
+        // transactions and confirms them with confirm_external_broadcast
+        if let (Some(transactions), Some(call)) = (broadcast, settlement_call) {
+            let execution = SignedExecution {
+                reference_id: req.reference_id.clone(),
+                correlation_id: correlation_id.to_string(),
+                chain: req.source_chain,
+                chain_id: transactions[0].transaction.chain_id,
+                agent_id: best_route.agent_id.clone(),
+                call,
+                transactions,
+            };
+            return Ok(self.hold_broadcast(PendingBroadcast {
+                req: req.clone(),
+                execution,
+                quote_hash: best_route.commitment().ok(),
+                disbursements: disbursements.unwrap_or_default(),
+                fee_splits: platform_fees,
+                escrow_terms,
+            }));
+        }
+        self.events.publish_with(|| SdkEvent::ExecutionStarted {
+            reference_id: req.reference_id.clone(),
+            correlation_id: correlation_id.to_string(),
+            agent_id: best_route.agent_id.clone(),
+        });
+        #[cfg(feature = "test-utils")]
+        let corrupt = self.inject_fault(InjectionPoint::BeforeExecution, req, ErrorCode::AgentUnavailable).await?;
+        let wire_req = self.wire_request(req, sealed_travel_rule, best_route.commitment().ok()).await?;
+        let sealed = match transport_key {
+            Some(key) => Some(transport::seal(&wire_req, &best_route.agent_id, &key).map_err(|e| {
+                SdkError::new(ErrorCode::InvalidRequest, format!("failed to seal intent: {}", e))
+            })?),
+            None => None,
+        };
+        let started = Instant::now();
+        let mut disbursed = Vec::new();
+        let execution = match (sealed.as_ref(), disbursements.as_deref(), escrow_terms.as_ref()) {
+            (Some((redacted, sealed)), _, _) => {
+                self.breaker
+                    .call(self.negotiator.execute_sealed(redacted, sealed, &best_route, progress))
+                    .await
+            }
+            (None, Some(disbursements), _) => self
+                .breaker
+                .call(self.negotiator.execute_disbursement(&wire_req, disbursements, &best_route, progress))
+                .await
+                .and_then(|mut executed| {
+                    let results = std::mem::take(&mut executed.disbursements);
+                    if results.len() != disbursements.len() {
+                        return Err(format!(
+                            "agent reported {} results for {} recipients",
+                            results.len(),
+                            disbursements.len()
+                        ));
+                    }
+                    if !results.iter().any(DisbursementResult::is_confirmed) {
+                        return Err("every disbursement failed".to_string());
+                    }
+                    disbursed = results;
+                    Ok(executed)
+                }),
+            (None, None, Some(terms)) => {
+                self.breaker
+                    .call(self.negotiator.lock_escrow(&wire_req, terms, &best_route, progress))
+                    .await
+            }
+            (None, None, None) => {
+                self.breaker
+                    .call(self.negotiator.execute_with_progress(&wire_req, &best_route, progress))
+                    .await
+            }
+        };
+        if self.config.enable_metrics {
+            self.metrics.record_execution(&best_route.agent_id, execution.is_ok());
+        }
+        let executed = execution
+            .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("agent execution failed: {}", e)))?;
+        let fee_used = self.check_fee_charged(req, correlation_id, &best_route, executed.fee_used);
+        self.sla.record(
+            &best_route.agent_id,
+            best_route.estimated_time,
+            started.elapsed(),
+            &best_route.estimated_fee,
+            &fee_used,
+        );
+
+        // 8. Construct Response
+        // NOTE: In production, tx_hash and block_height come from blockchain
+        #[allow(unused_mut)]
+        let mut tx_hash = format!("0x{}", Uuid::new_v4().simple());
+        #[cfg(feature = "test-utils")]
+        if corrupt {
+            faults::corrupt_tx_hash(&mut tx_hash);
+        }
+        let block_height = match self.chains.get(&req.source_chain) {
+            Some(adapter) => adapter
+                .get_block_height()
+                .await
+                .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch block height: {}", e)))?,
+            None => 1948201,
+        };
+
+        // Disbursements where only some recipients were paid are partial;
+        // escrowed funds are only paid on release
+        let status = match disbursed.iter().all(DisbursementResult::is_confirmed) {
+            _ if escrow_terms.is_some() => "escrowed",
+            true => "confirmed",
+            false => "partial",
+        };
+        let resp = TransactionResponse {
+            tx_hash,
+            status: status.to_string(),
+            block_height,
+            fee_used,
+            correlation_id: correlation_id.to_string(),
+            disbursements: disbursed,
+            fee_splits: platform_fees,
+        };
+        self.submissions.record(
+            &resp.tx_hash,
+            SubmittedTx {
+                reference_id: req.reference_id.clone(),
+                agent_id: best_route.agent_id.clone(),
+                chain: req.source_chain,
+                replaced_by: None,
+                sealed_intent: sealed.map(|(_, sealed)| sealed),
+                quote_hash: best_route.commitment().ok(),
+            },
+        );
+
+        if let (Some(terms), Some(ref escrows)) = (escrow_terms, &self.escrows) {
+            let recorded = Escrow::new(req, terms, &best_route.agent_id, &resp.tx_hash)
+                .and_then(|escrow| escrows.record_lock(escrow));
+            if let Err(e) = recorded {
+                tracing::warn!("[SDK] Failed to record escrow {}: {}", req.reference_id, e);
+            }
+        }
+        if let Some(ref policy) = self.policy {
+            if let Err(e) = policy.record_usage(req) {
+                tracing::warn!("[SDK] Failed to record policy usage: {}", e);
+            }
+        }
+        if let (Some(sponsorship), Some(ref sponsors)) = (sponsorship, &self.sponsors) {
+            if let Err(e) = sponsors.record(&sponsorship, &resp.fee_used) {
+                tracing::warn!("[SDK] Failed to record sponsored fee of {}: {}", req.reference_id, e);
+            }
+        }
+        if let Some(ref budget) = self.fee_budget {
+            match budget.record(&resp.fee_used) {
+                Ok(warnings) => {
+                    for warning in warnings {
+                        tracing::warn!("[SDK] {}", warning);
+                    }
+                }
+                Err(e) => tracing::warn!("[SDK] Failed to record fee budget usage: {}", e),
+            }
+        }
+        if let Some(ref history) = self.fee_history {
+            let mut fee = resp.fee_used.split_whitespace();
+            let value = fee.next().and_then(|v| v.parse::<f64>().ok());
+            let same_asset = fee.next().is_none_or(|asset| asset.eq_ignore_ascii_case(&req.asset));
+            match (value, req.amount.parse::<f64>()) {
+                (Some(value), Ok(amount)) if same_asset => history.record(&FeeRoute::of(req), amount, value),
+                _ => tracing::debug!("[SDK] Fee {} of {} not recorded in fee history", resp.fee_used, req.reference_id),
+            }
+        }
+
+        // 9. Cache successful result
+        if let (Some(cache), Some(key)) = (&self.cache, cache_key) {
+            cache.set(key, &resp).await;
+        }
+
+        Ok(resp)
+    }
+
+    /// Replaces a stuck EVM transaction executed by this client with one
+    /// paying `new_max_fee` (max fee per gas, in wei) under the same nonce.
+    /// The original agent signs the replacement and either broadcasts it or
+    /// returns it for broadcast through the chain adapter. Returns the
+    /// replacement's hash, which replays of the reference ID now return.
+    pub async fn speed_up(&self, tx_hash: &str, new_max_fee: &str) -> Result<String> {
+        let tx = self.submissions.get(tx_hash).ok_or_else(|| {
+            SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!("unknown transaction {}: only recent transactions executed by this client can be sped up", tx_hash),
+            )
+        })?;
+        if let Some(replacement) = tx.replaced_by {
+            return Err(SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!("transaction {} was already replaced by {}", tx_hash, replacement),
+            ));
+        }
+        if !matches!(tx.chain, ChainId::Ethereum | ChainId::Base) {
+            return Err(SdkError::new(
+                ErrorCode::UnsupportedChain,
+                format!("speed-up is only supported on EVM chains, not {}", tx.chain),
+            ));
+        }
+        if !matches!(new_max_fee.parse::<u128>(), Ok(fee) if fee > 0) {
+            return Err(SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!("invalid max fee per gas: {} (expected a positive integer amount of wei)", new_max_fee),
+            ));
+        }
+
+        let adapter = self.chains.get(&tx.chain);
+        if let Some(adapter) = adapter {
+            let receipt = adapter
+                .get_receipt(tx_hash)
+                .await
+                .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch receipt: {}", e)))?;
+            if receipt.is_some() {
+                return Err(SdkError::new(
+                    ErrorCode::InvalidRequest,
+                    format!("transaction {} is already confirmed", tx_hash),
+                ));
+            }
+        }
+
+        let request = SpeedUpRequest {
+            reference_id: tx.reference_id.clone(),
+            agent_id: tx.agent_id.clone(),
+            chain: tx.chain,
+            tx_hash: tx_hash.to_string(),
+            max_fee_per_gas: new_max_fee.to_string(),
+        };
+        let replacement = self
+            .negotiator
+            .speed_up(&request)
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("speed-up failed: {}", e)))?;
+        let new_hash = match replacement {
+            Replacement::Broadcast { tx_hash } => tx_hash,
+            Replacement::Signed { raw_tx } => {
+                let adapter = adapter.ok_or_else(|| {
+                    SdkError::new(
+                        ErrorCode::UnsupportedChain,
+                        format!("no chain adapter configured for {} to broadcast the replacement", tx.chain),
+                    )
+                })?;
+                adapter.broadcast_raw_transaction(&raw_tx).await.map_err(|e| {
+                    SdkError::new(ErrorCode::NetworkFailure, format!("failed to broadcast replacement: {}", e))
+                })?
+            }
+        };
+
+        self.submissions.replace(tx_hash, &new_hash);
+        if let Some(ref guard) = self.exactly_once {
+            if let Err(e) = guard.record_replacement(&tx.reference_id, &new_hash) {
+                tracing::warn!("[SDK] Failed to record replacement of {}: {}", tx_hash, e);
+            }
+        }
+        self.record_audit(AuditKind::Decision, &tx.reference_id, || {
+            serde_json::json!({
+                "speed_up": {
+                    "replaced_tx_hash": tx_hash,
+                    "tx_hash": new_hash,
+                    "max_fee_per_gas": new_max_fee,
+                },
+            })
+        });
+        tracing::info!("[SDK] Replaced {} with {} (max fee per gas {} wei)", tx_hash, new_hash, new_max_fee);
+        Ok(new_hash)
+    }
+
+    /// Refunds `amount` of the settled payment `original_reference_id` to its
+    /// payer. The refund executes as a transfer with reference ID
+    /// `<original>-refund-<n>` and is recorded against the settlement; a
+    /// failed refund releases its amount so it can be retried.
+    pub async fn refund(&self, original_reference_id: &str, amount: &str) -> Result<TransactionResponse> {
+        let refunds = self.refunds.as_ref().ok_or_else(|| {
+            SdkError::new(ErrorCode::InvalidRequest, "refunds are not enabled; configure them with with_refunds")
+        })?;
+        let req = refunds.begin(original_reference_id, amount).map_err(refund_error)?;
+        self.record_audit(AuditKind::Decision, &req.reference_id, || {
+            serde_json::json!({
+                "refund_of": original_reference_id,
+                "amount": req.amount,
+                "asset": req.asset,
+            })
+        });
+
+        match self.execute_transaction(&req).await {
+            Ok(resp) => {
+                if let Err(e) = refunds.complete(original_reference_id, &req.reference_id, &resp.tx_hash) {
+                    tracing::warn!("[SDK] Failed to record refund {}: {}", req.reference_id, e);
+                }
+                tracing::info!("[SDK] Refunded {} {} of {}", req.amount, req.asset, original_reference_id);
+                Ok(resp)
+            }
+            Err(e) => {
+                if let Err(abort_err) = refunds.abort(original_reference_id, &req.reference_id) {
+                    tracing::warn!("[SDK] Failed to release refund {}: {}", req.reference_id, abort_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Pays the funds of escrow `escrow_id` to its recipient. `signature` is
+    /// the counterparty's signature of the release message or the oracle's
+    /// attestation, as the escrow's release condition requires (`None` for
+    /// timed escrows). A failed release leaves the funds locked.
+    pub async fn release_escrow(&self, escrow_id: &str, signature: Option<&str>) -> Result<Escrow> {
+        let escrows = self.escrow_manager()?;
+        let escrow = escrows.begin_release(escrow_id, signature).map_err(escrow_error)?;
+        self.settle_escrow(escrows, escrow).await
+    }
+
+    /// Returns the funds of escrow `escrow_id` to the payer once its refund
+    /// deadline has passed. A failed refund leaves the funds locked.
+    pub async fn refund_escrow(&self, escrow_id: &str) -> Result<Escrow> {
+        let escrows = self.escrow_manager()?;
+        let escrow = escrows.begin_refund(escrow_id).map_err(escrow_error)?;
+        self.settle_escrow(escrows, escrow).await
+    }
+
+    /// Releases hash-time-locked escrow `escrow_id` by revealing the secret
+    /// behind its hashlock. The secret is recorded on the returned escrow
+    /// for the counterparty.
+    pub async fn reveal_secret(&self, escrow_id: &str, secret: &[u8]) -> Result<Escrow> {
+        let escrows = self.escrow_manager()?;
+        let escrow = escrows.begin_reveal(escrow_id, secret).map_err(escrow_error)?;
+        self.settle_escrow(escrows, escrow).await
+    }
+
+    /// Refunds every locked escrow whose refund deadline has passed and
+    /// returns the refunded escrows. Escrows that fail to refund stay locked
+    /// and are retried on the next call.
+    pub async fn refund_expired_escrows(&self) -> Result<Vec<Escrow>> {
+        let escrows = self.escrow_manager()?;
+        let expired = escrows.expired().map_err(|e| {
+            SdkError::new(ErrorCode::NetworkFailure, format!("failed to list escrows: {}", e))
+                .with_details(serde_json::json!({ "reason": "escrow_store_unavailable" }))
+        })?;
+        let mut refunded = Vec::with_capacity(expired.len());
+        for escrow in expired {
+            match self.refund_escrow(&escrow.escrow_id).await {
+                Ok(escrow) => refunded.push(escrow),
+                Err(e) => tracing::warn!("[SDK] Failed to refund expired escrow {}: {}", escrow.escrow_id, e),
+            }
+        }
+        Ok(refunded)
+    }
+
+    fn escrow_manager(&self) -> Result<&EscrowManager> {
+        self.escrows.as_ref().ok_or_else(|| {
+            SdkError::new(ErrorCode::InvalidRequest, "escrows are not enabled; configure them with with_escrows")
+        })
+    }
+
+    /// Executes a reserved release or refund through the agent holding the
+    /// funds and books it in the ledger
+    async fn settle_escrow(&self, escrows: &EscrowManager, escrow: Escrow) -> Result<Escrow> {
+        let releasing = escrow.state == EscrowState::Releasing;
+        self.record_audit(AuditKind::Decision, &escrow.escrow_id, || {
+            serde_json::json!({
+                "escrow": if releasing { "release" } else { "refund" },
+                "agent_id": escrow.agent_id,
+            })
+        });
+        let execution = match releasing {
+            true => self.breaker.call(self.negotiator.release_escrow(&escrow)).await,
+            false => self.breaker.call(self.negotiator.refund_escrow(&escrow)).await,
+        };
+        if let Err(e) = execution {
+            if let Err(abort_err) = escrows.abort(&escrow.escrow_id) {
+                tracing::warn!("[SDK] Failed to unlock escrow {}: {}", escrow.escrow_id, abort_err);
+            }
+            return Err(SdkError::new(ErrorCode::AgentUnavailable, format!("agent execution failed: {}", e)));
+        }
+
+        let tx_hash = format!("0x{}", Uuid::new_v4().simple());
+        if let Some(ref ledger) = self.ledger {
+            let booked = match releasing {
+                true => ledger.settle(&escrow.escrow_id, &tx_hash, None),
+                false => ledger.release(&escrow.escrow_id),
+            };
+            if let Err(e) = booked {
+                tracing::warn!("[SDK] Failed to book escrow {} in the ledger: {}", escrow.escrow_id, e);
+            }
+        }
+        escrows.complete(&escrow.escrow_id, &tx_hash).map_err(|e| {
+            SdkError::new(ErrorCode::NetworkFailure, format!("failed to record escrow {}: {}", escrow.escrow_id, e))
+        })
+    }
+
+    /// Proof cached for this statement; cache errors only cost a re-proof
+    fn cached_proof(&self, reference_id: &str, input: &SolvencyInput) -> Option<String> {
+        self.proof_cache.as_ref()?.get(reference_id, input).unwrap_or_else(|e| {
+            tracing::warn!("[SDK] Failed to read cached proof of {}: {}", reference_id, e);
+            None
+        })
+    }
+
+    /// Executes requests one after another (e.g. from `batch::from_csv`),
+    /// returning one result per request in input order. A failed request does
+    /// not stop the batch.
+    ///
+    /// With ZK proofs enabled, the solvency of all shielded requests is proven
+    /// up front in one aggregated proof. A request whose route or balance
+    /// changed by the time it executes is proven on its own.
+    pub async fn execute_batch(&self, reqs: &[TransactionRequest]) -> Vec<Result<TransactionResponse>> {
+        let options = ExecuteOptions {
+            batch_proof: self.batch_proof(reqs).await.map(Arc::new),
+            ..ExecuteOptions::default()
+        };
+        let mut results = Vec::with_capacity(reqs.len());
+        for req in reqs {
+            results.push(self.execute_with_progress(req, &options, &ProgressReporter::default()).await);
+        }
+        results
+    }
+
+    /// Proves the solvency of a batch's shielded requests at their current
+    /// quotes. `None` when fewer than two can be proven together or proving
+    /// fails, leaving each request to its own proof.
+    async fn batch_proof(&self, reqs: &[TransactionRequest]) -> Option<BatchProof> {
+        if !self.config.enable_zk_proofs || reqs.iter().filter(|r| r.is_shielded).count() < 2 {
+            return None;
+        }
+        let mut inputs = HashMap::new();
+        for req in reqs.iter().filter(|r| r.is_shielded) {
+            match self.solvency_input(req).await {
+                Ok(Some(input)) => {
+                    inputs.entry(req.reference_id.clone()).or_insert(input);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!("[SDK] Proving {} outside the batch proof: {}", req.reference_id, e),
+            }
+        }
+        if inputs.len() < 2 {
+            return None;
+        }
+        if self.artifacts.as_ref().is_some_and(|a| a.current().is_none()) {
+            if let Err(e) = self.reload_circuit_artifacts(None).await {
+                tracing::warn!("[SDK] Failed to load circuit artifacts for batch proof: {}", e);
+                return None;
+            }
+        }
+        let proof = self.proofs.prove(ProofJob::batch(inputs.values().cloned().collect())).await;
+        match proof {
+            Ok(proof) if self.proofs.verify(&proof) => {
+                tracing::info!("[SDK] Generated batch proof over {} shielded requests", inputs.len());
+                Some(BatchProof { proof, inputs })
+            }
+            Ok(_) => {
+                tracing::warn!("[SDK] Batch proof failed verification, proving requests individually");
+                None
+            }
+            Err(e) => {
+                tracing::warn!("[SDK] Failed to generate batch proof, proving requests individually: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Solvency statement `req` would be proven with at its current quote
+    /// (`None` when the payer cannot cover it)
+    async fn solvency_input(&self, req: &TransactionRequest) -> Result<Option<SolvencyInput>> {
+        let route = self.get_quote(req).await?;
+        let req = &*self.with_normalized_amount(req);
+        let required = solvency::required_amount(req, &route.estimated_fee)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
+        let balance = match self.balance_provider {
+            Some(ref provider) => {
+                let req = &*self.with_base_units(req)?;
+                provider
+                    .available_balance(req)
+                    .await
+                    .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch payer balance: {}", e)))?
+            }
+            None => required,
+        };
+        Ok((balance >= required).then(|| SolvencyInput::new(balance.to_string(), required.to_string())))
+    }
+
+    /// Returns the route the client would select for a request, without executing it
+    pub async fn get_quote(&self, req: &TransactionRequest) -> Result<RouteQuote> {
+        let req = &*self.with_normalized_amount(req);
+        self.validators
+            .validate(req)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("validation failed: {}", e)))?;
+        let req = &*self.with_base_units(req)?;
+        let (quotes, _) = self.request_quotes(req).await?;
+        self.select_route(&quotes)
+    }
+
+    /// Explains the route the client would select for a request: every quote
+    /// an agent returned with its per-factor score and status, and why the
+    /// winner was chosen. Nothing is executed.
+    pub async fn explain_route(&self, req: &TransactionRequest) -> Result<RouteExplanation> {
+        let req = &*self.with_normalized_amount(req);
+        self.validators
+            .validate(req)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("validation failed: {}", e)))?;
+        let req = &*self.with_base_units(req)?;
+        let collected = self.collect_quotes(req).await?;
+        let sla = self.sla.report();
+        let reputation = |agent_id: &str| sla.agents.get(agent_id).and_then(|a| a.on_time_rate());
+
+        let (selected, reason) = match collected.exhausted {
+            Some(ref e) => (None, e.message.clone()),
+            None => {
+                let best = self.select_route(&collected.usable)?;
+                let reason = self.selection_reason(&collected.usable, &best);
+                (Some(best), reason)
+            }
+        };
+        let mut quotes: Vec<QuoteExplanation> = collected
+            .usable
+            .iter()
+            .map(|quote| {
+                let status = if selected.as_ref() == Some(quote) {
+                    QuoteStatus::Selected
+                } else if self.sla.is_penalized(&quote.agent_id) {
+                    QuoteStatus::Penalized
+                } else {
+                    QuoteStatus::Eligible
+                };
+                QuoteExplanation::new(quote, reputation(&quote.agent_id), status)
+            })
+            .collect();
+        quotes.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        quotes.extend(collected.discarded.iter().map(|(quote, reason)| {
+            QuoteExplanation::new(quote, reputation(&quote.agent_id), QuoteStatus::Discarded { reason: reason.clone() })
+        }));
+        Ok(RouteExplanation {
+            reference_id: req.reference_id.clone(),
+            quotes,
+            selected: selected.map(|q| q.agent_id),
+            reason,
+        })
+    }
+
+    /// Why `best` was selected from `quotes`
+    fn selection_reason(&self, quotes: &[RouteQuote], best: &RouteQuote) -> String {
+        let eligible = self.sla.down_rank(quotes.to_vec());
+        let score = agent::route_score(best, "balanced");
+        let tied = agent::equally_ranked(&eligible, best, "balanced");
+        let mut reason = if !tied.contains(&best) {
+            "chosen by the agent negotiator's own ranking".to_string()
+        } else if tied.len() > 1 {
+            let others: Vec<&str> = tied.iter().filter(|q| **q != best).map(|q| q.agent_id.as_str()).collect();
+            format!(
+                "tied with {} at a balanced score of {:.4}; chosen by {} tie-breaking",
+                others.join(", "),
+                score,
+                self.balancer.strategy().as_str()
+            )
+        } else {
+            format!("highest balanced score ({:.4}) of {} eligible quote(s)", score, eligible.len())
+        };
+        let penalized = quotes.len() - eligible.len();
+        if penalized > 0 {
+            reason.push_str(&format!("; {} quote(s) skipped for missing quoted times", penalized));
+        }
+        reason
+    }
+
+    /// Quotes the transfer in `to` (an asset or fiat currency such as "EUR"):
+    /// the request's amount and the selected route's fee, converted at the
+    /// conversion provider's rates
+    pub async fn get_conversion_quote(&self, req: &TransactionRequest, to: &str) -> Result<ConversionQuote> {
+        let provider = self
+            .conversion
+            .as_ref()
+            .ok_or_else(|| SdkError::new(ErrorCode::InvalidRequest, "no conversion provider configured"))?;
+        let route = self.get_quote(req).await?;
+        conversion::quote(provider.as_ref(), &req.asset, to, &req.amount, &route.estimated_fee, &route.agent_id)
+            .await
+            .map_err(|e| {
+                SdkError::new(ErrorCode::NetworkFailure, format!("failed to quote conversion: {}", e))
+                    .with_details(serde_json::json!({ "from_asset": req.asset, "to": to }))
+            })
+    }
+
+    /// Protocol version used with the agent network: the highest version both
+    /// sides support, negotiated on first use
+    pub async fn protocol_version(&self) -> Result<u32> {
+        self.protocol_version
+            .get_or_try_init(|| async {
+                let remote = self.negotiator.supported_versions().await.map_err(|e| {
+                    SdkError::new(ErrorCode::AgentUnavailable, format!("failed to negotiate protocol version: {}", e))
+                })?;
+                protocol::negotiate(&remote).map_err(|e| SdkError::new(ErrorCode::UnsupportedProtocol, e))
+            })
+            .await
+            .copied()
+    }
+
+    /// Verifies the agent's signature on a quote, if it has one, against the
+    /// key the agent directory publishes for it. With `require_signed_quotes`
+    /// the quote must be signed and the key published: a quote carrying only
+    /// its own key proves nothing about who made it
+    async fn check_quote_signature(&self, quote: &RouteQuote) -> std::result::Result<(), String> {
+        if !quote.is_signed() {
+            return match self.config.require_signed_quotes {
+                true => Err(format!("quote from {} is unsigned", quote.agent_id)),
+                false => Ok(()),
+            };
+        }
+        let key = quote.verify_signature()?;
+        let published = match self.agent_directory {
+            Some(ref directory) => directory.quote_key(&quote.agent_id).await?,
+            None => None,
+        };
+        match published {
+            Some(published) if published != key => {
+                Err(format!("quote from {} is not signed with its published key", quote.agent_id))
+            }
+            None if self.config.require_signed_quotes => {
+                Err(format!("{} has no published quote key", quote.agent_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the fee the agent reports charging for the execution against
+    /// its quote. Charges above the quote are recorded as violations.
+    fn check_fee_charged(&self, req: &TransactionRequest, correlation_id: &str, route: &RouteQuote, fee_used: String) -> String {
+        if route.is_exceeded_by(&fee_used) {
+            tracing::warn!(
+                "[SDK] Agent {} charged {} for {}, above its quote of {}",
+                route.agent_id, fee_used, req.reference_id, route.estimated_fee
+            );
+            if self.config.enable_metrics {
+                self.metrics.record_quote_violation(&route.agent_id);
+            }
+            self.events.publish_with(|| SdkEvent::QuoteViolated {
+                reference_id: req.reference_id.clone(),
+                correlation_id: correlation_id.to_string(),
+                agent_id: route.agent_id.clone(),
+                quote_hash: route.commitment().unwrap_or_default(),
+                quoted_fee: route.estimated_fee.clone(),
+                fee_used: fee_used.clone(),
+            });
+        }
+        fee_used
+    }
+
+    /// Selects the route to execute, down-ranking agents penalized for
+    /// missing their quoted times and spreading ties between equally ranked
+    /// routes
+    fn select_route(&self, quotes: &[RouteQuote]) -> Result<RouteQuote> {
+        let quotes = self.sla.down_rank(quotes.to_vec());
+        let best = self
+            .negotiator
+            .select_best_route(&quotes, "balanced")
+            .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("no suitable route found: {}", e)))?;
+        let tied = agent::equally_ranked(&quotes, &best, "balanced");
+        // Negotiators ranking by other criteria keep their choice
+        if tied.len() < 2 || !tied.iter().any(|q| **q == best) {
+            return Ok(best);
+        }
+        Ok(self.balancer.pick(&tied).clone())
+    }
+
+    /// Transport key published by the agent behind `route`
+    async fn transport_key(&self, directory: &dyn AgentDirectory, route: &RouteQuote) -> Result<PublicKey> {
+        directory
+            .transport_key(&route.agent_id)
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::NetworkFailure, format!("failed to fetch agent transport key: {}", e)))?
+            .ok_or_else(|| {
+                SdkError::new(
+                    ErrorCode::AgentUnavailable,
+                    format!("agent {} publishes no transport key for shielded intents", route.agent_id),
+                )
+                .with_details(serde_json::json!({ "reason": "no_transport_key", "agent_id": route.agent_id }))
+            })
+    }
+
+    /// What is screened for `req`: the request itself, then one screening per
+    /// disbursement leg and per fee split recipient
+    fn screening_requests(req: &TransactionRequest) -> Result<Vec<ScreeningRequest>> {
+        let mut screened = vec![ScreeningRequest::from(req)];
+        screened.extend(
+            intents::disbursements(req)?
+                .iter()
+                .map(|leg| ScreeningRequest::for_payee(req, &leg.recipient, &leg.amount)),
+        );
+        let fee_splits = intents::fee_splits(req)?;
+        if !fee_splits.is_empty() {
+            let (_, applied) = intents::apply_fee_splits(req, fee_splits)?;
+            screened.extend(
+                applied
+                    .iter()
+                    .map(|fee| ScreeningRequest::for_payee(req, &fee.recipient, &fee.amount)),
+            );
+        }
+        Ok(screened)
+    }
+
+    /// Settlement contract call of `req` when its chain has a deployment
+    fn settlement_call(&self, req: &TransactionRequest, disbursements: Option<&[Disbursement]>) -> Result<Option<ContractCall>> {
+        let Some(deployment) = self.contracts.as_ref().and_then(|d| d.get(req.source_chain)) else {
+            return Ok(None);
+        };
+        contracts::v1::call_for(deployment, req, disbursements, &self.config.assets).map_err(|e| {
+            SdkError::new(ErrorCode::InvalidRequest, format!("failed to build settlement call: {}", e))
+        })
+    }
+
+    /// Transactions of a self-broadcast execution, signed before any quote
+    /// is requested so a request that can't be signed fails early
+    fn sign_broadcast(
+        &self,
+        req: &TransactionRequest,
+        broadcast: &SelfBroadcast,
+        call: Option<&ContractCall>,
+        sponsored: bool,
+    ) -> Result<Vec<SignedTransaction>> {
+        let signer = self.broadcast_signer.as_ref().ok_or_else(|| {
+            SdkError::new(ErrorCode::SignerUnavailable, "no broadcast signer configured; set one with with_broadcast_signer")
+        })?;
+        if sponsored {
+            return Err(SdkError::new(ErrorCode::InvalidRequest, "self-broadcast transactions pay their own network fee"));
+        }
+        let (Some(call), Some(chain_id), Some(deployment)) = (
+            call,
+            self.config.env().evm_chain_id(req.source_chain),
+            self.contracts.as_ref().and_then(|d| d.get(req.source_chain)),
+        ) else {
+            return Err(SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!(
+                    "{} can't be self-broadcast: no settlement contract call for it on {}",
+                    req.reference_id, req.source_chain
+                ),
+            ));
+        };
+        let token = deployment.token(&req.asset).map_err(|e| SdkError::new(ErrorCode::UnsupportedAsset, e))?;
+        broadcast
+            .sign(chain_id, call, token, signer)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("failed to sign settlement: {}", e)))
+    }
+
+    /// Keeps a signed self-broadcast execution until it is confirmed and
+    /// returns its "signed" response
+    fn hold_broadcast(&self, pending: PendingBroadcast) -> TransactionResponse {
+        let resp = TransactionResponse {
+            tx_hash: pending.execution.tx_hash().to_string(),
+            status: "signed".to_string(),
+            block_height: 0,
+            fee_used: String::new(),
+            correlation_id: pending.execution.correlation_id.clone(),
+            disbursements: Vec::new(),
+            fee_splits: pending.fee_splits.clone(),
+        };
+        // The transactions can be broadcast from now on
+        if let Some(ref policy) = self.policy {
+            if let Err(e) = policy.record_usage(&pending.req) {
+                tracing::warn!("[SDK] Failed to record policy usage: {}", e);
+            }
+        }
+        self.record_audit(AuditKind::Decision, &pending.req.reference_id, || {
+            serde_json::json!({
+                "self_broadcast": {
+                    "from": pending.execution.transactions.last().map(|tx| tx.from.clone()),
+                    "tx_hashes": pending.execution.transactions.iter().map(|tx| tx.hash.clone()).collect::<Vec<_>>(),
+                },
+            })
+        });
+        self.broadcasts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(resp.tx_hash.clone(), pending);
+        resp
+    }
+
+    /// `req` in the negotiated protocol version, with its travel-rule data
+    /// replaced by `sealed_travel_rule` and bound to `quote_hash`
+    async fn wire_request<'a>(
+        &self,
+        req: &'a TransactionRequest,
+        sealed_travel_rule: Option<SealedTravelRule>,
+        quote_hash: Option<String>,
+    ) -> Result<Cow<'a, TransactionRequest>> {
+        let req = protocol::downconvert(req, self.protocol_version().await?);
+        if req.travel_rule.is_none()
+            && req.sealed_travel_rule.is_none()
+            && sealed_travel_rule.is_none()
+            && req.quote_hash.is_none()
+            && quote_hash.is_none()
+        {
+            return Ok(req);
+        }
+        // Agents never see travel-rule data in plaintext
+        let mut req = req.into_owned();
+        req.travel_rule = None;
+        req.sealed_travel_rule = sealed_travel_rule;
+        req.quote_hash = quote_hash;
+        Ok(Cow::Owned(req))
+    }
+
+    /// Rewrites a localized amount ("1,000.50") as a plain decimal when
+    /// `validation.normalize_locale_amounts` is set
+    fn with_normalized_amount<'a>(&self, req: &'a TransactionRequest) -> Cow<'a, TransactionRequest> {
+        if !self.config.validation.normalize_locale_amounts {
+            return Cow::Borrowed(req);
+        }
+        match validator::normalize_amount(&req.amount) {
+            Ok(amount) if amount != req.amount => {
+                let mut req = req.clone();
+                req.amount = amount;
+                Cow::Owned(req)
+            }
+            // Unrecognized formats are left to validation to explain
+            _ => Cow::Borrowed(req),
+        }
+    }
+
+    /// Sets `amount_base_units` when the asset's decimals are known, so agents
+    /// get an exact integer amount. Amounts more precise than the asset are rejected.
+    fn with_base_units<'a>(&self, req: &'a TransactionRequest) -> Result<Cow<'a, TransactionRequest>> {
+        let Some(decimals) = self.config.assets.decimals(&req.asset) else {
+            return Ok(Cow::Borrowed(req));
+        };
+        let units = req
+            .amount
+            .parse::<Amount>()
+            .and_then(|amount| amount.to_base_units_exact(decimals))
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid {} amount: {}", req.asset, e)))?;
+        let mut req = req.clone();
+        req.amount_base_units = Some(units.to_string());
+        Ok(Cow::Owned(req))
+    }
+
+    /// Applies the injected fault for `point`, if any; returns true if the
+    /// step's result should be corrupted
+    #[cfg(feature = "test-utils")]
+    async fn inject_fault(&self, point: InjectionPoint, req: &TransactionRequest, code: ErrorCode) -> Result<bool> {
+        match self.faults.as_ref().and_then(|f| f.inject(point, req)) {
+            Some(Fault::Error(message)) => Err(SdkError::new(code, format!("injected fault: {}", message))),
+            Some(Fault::Delay(delay)) => {
+                tokio::time::sleep(delay).await;
+                Ok(false)
+            }
+            Some(Fault::Corrupt) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    /// Fetches agent quotes, normalizing fees when a price oracle is configured.
+    /// Also returns the latency of the agent round-trip.
+    async fn request_quotes(&self, req: &TransactionRequest) -> Result<(Vec<RouteQuote>, Duration)> {
+        let quotes = self.collect_quotes(req).await?;
+        match quotes.exhausted {
+            Some(e) => Err(e),
+            None => Ok((quotes.usable, quotes.latency)),
+        }
+    }
+
+    /// Fetches agent quotes and sorts out those unusable for selection
+    async fn collect_quotes(&self, req: &TransactionRequest) -> Result<CollectedQuotes> {
+        #[cfg(feature = "test-utils")]
+        let corrupt = self.inject_fault(InjectionPoint::BeforeQuote, req, ErrorCode::AgentUnavailable).await?;
+        let wire_req = self.wire_request(req, None, None).await?;
+        let started = Instant::now();
+        let usable = self
+            .breaker
+            .call(self.negotiator.request_quotes(&wire_req))
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::AgentUnavailable, format!("failed to get agent quotes: {}", e)))?;
+        let mut collected = CollectedQuotes {
+            usable,
+            discarded: Vec::new(),
+            exhausted: None,
+            latency: started.elapsed(),
+        };
+        #[cfg(feature = "test-utils")]
+        if corrupt {
+            faults::corrupt_quotes(&mut collected.usable);
+        }
+
+        // A fee that can't be compared or budgeted makes the quote unusable
+        collected.discard(
+            |quote| match quote.fee_value().is_some_and(|fee| fee.is_finite() && fee >= 0.0) {
+                true => None,
+                false => Some(format!("malformed fee {:?}", quote.estimated_fee)),
+            },
+            || SdkError::new(ErrorCode::AgentUnavailable, "no agent returned a well-formed quote"),
+        );
+        if collected.exhausted.is_some() {
+            return Ok(collected);
+        }
+
+        // Spoofed or tampered quotes never reach selection
+        let mut rejections = Vec::with_capacity(collected.usable.len());
+        for quote in &collected.usable {
+            rejections.push(self.check_quote_signature(quote).await.err());
+        }
+        let mut rejections = rejections.into_iter();
+        collected.discard(
+            |_| rejections.next().flatten(),
+            || {
+                SdkError::new(ErrorCode::AgentUnavailable, "no agent returned a validly signed quote")
+                    .with_details(serde_json::json!({ "reason": "unverified_quotes" }))
+            },
+        );
+        if collected.exhausted.is_some() {
+            return Ok(collected);
+        }
+
+        let residency = &self.config.residency;
+        if residency.is_restricted() {
+            collected.discard(
+                |quote| {
+                    (!residency.permits(quote.region.as_deref()))
+                        .then(|| format!("region {:?} is not permitted", quote.region.as_deref().unwrap_or("unknown")))
+                },
+                || {
+                    SdkError::new(ErrorCode::AgentUnavailable, "no agent in a permitted region returned a quote")
+                        .with_details(serde_json::json!({
+                            "reason": "data_residency",
+                            "allowed_regions": residency.allowed_regions,
+                            "excluded_countries": residency.excluded_countries,
+                        }))
+                },
+            );
+            if collected.exhausted.is_some() {
+                return Ok(collected);
+            }
+        }
+
+        if let Some(ref oracle) = self.price_oracle {
+            for quote in collected.usable.iter_mut() {
+                match pricing::normalize_fee(oracle.as_ref(), &quote.estimated_fee).await {
+                    Ok(usd) => quote.estimated_fee_usd = Some(usd),
+                    Err(e) => tracing::warn!("[SDK] Failed to normalize fee from {}: {}", quote.agent_id, e),
+                }
+            }
+        }
+        Ok(collected)
+    }
+
+    /// Returns current SDK performance metrics
+    ///
+    /// Kept for backward compatibility; prefer `metrics_snapshot`.
+    pub fn get_metrics(&self) -> std::collections::HashMap<String, f64> {
+        self.metrics_snapshot().to_map()
+    }
+
+    /// Returns a typed, serializable snapshot of the current SDK metrics
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        let mut snapshot = self.metrics.snapshot();
+        snapshot.metrics_enabled = self.config.enable_metrics;
+        snapshot.circuit_breaker = self.breaker.state();
+        snapshot.circuit_breaker_failure_rate = self.breaker.failure_rate();
+        snapshot.in_flight_transactions = self.limiter.in_flight();
+        snapshot.queue_depth = self.limiter.queue_depth();
+        snapshot.average_queue_wait_ms = self.limiter.average_wait_ms();
+        snapshot.rejected_admissions = self.limiter.rejected_count();
+        snapshot.connection_pool = self.http.pool_stats();
+        snapshot.proof_pool = self.proofs.stats();
+        snapshot.near_miss_double_spends = self.nullifiers.as_ref().map_or(0, |t| t.near_misses());
+        snapshot
+    }
+
+    /// Returns per-agent quote, selection and execution statistics keyed by
+    /// agent ID, so degrading agents can be spotted and denylisted
+    pub fn get_agent_stats(&self) -> HashMap<String, AgentStats> {
+        self.metrics.get_agent_stats()
+    }
+
+    /// Returns delivered-vs-quoted time and fee records per agent, including
+    /// agents currently down-ranked for missing their quoted times
+    pub fn sla_report(&self) -> SlaReport {
+        self.sla.report()
+    }
+
+    /// API key holder shared with network components (e.g. via
+    /// `SubscriptionConfig::with_credentials`) so rotations reach them
+    pub fn credentials(&self) -> Arc<ApiKeyRing> {
+        self.credentials.clone()
+    }
+
+    /// Switches to `new_key` for subsequent requests; the previous key stays
+    /// usable during the grace period. Returns false if the key is unchanged
+    pub fn rotate_api_key(&self, new_key: impl Into<String>) -> bool {
+        self.credentials.rotate(new_key.into())
+    }
+
+    /// Rotates automatically whenever `provider` returns a new key, polling
+    /// every `interval` until the returned handle is dropped
+    pub fn start_key_rotation(&self, provider: Arc<dyn SecretsProvider>, interval: Duration) -> KeyRotation {
+        KeyRotation::spawn(self.credentials.clone(), provider, interval)
+    }
+
+    /// Subscribes to lifecycle events for all transactions executed by this client
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SdkEvent> {
+        self.events.subscribe()
+    }
+
+    /// Reports whether the client can currently reach the agent network
+    pub fn health_check(&self) -> HealthStatus {
+        let breaker_state = self.breaker.state();
+        HealthStatus {
+            healthy: breaker_state != CircuitState::Open,
+            circuit_breaker: breaker_state,
+        }
+    }
+}
+
+/// Quotes usable for route selection, and those discarded with the reason
+struct CollectedQuotes {
+    usable: Vec<RouteQuote>,
+    discarded: Vec<(RouteQuote, String)>,
+    /// Error to fail with once no quote is usable
+    exhausted: Option<SdkError>,
+    latency: Duration,
+}
+
+impl CollectedQuotes {
+    /// Discards the usable quotes `check` returns a reason for, recording
+    /// `exhausted` if none are left
+    fn discard(&mut self, mut check: impl FnMut(&RouteQuote) -> Option<String>, exhausted: impl FnOnce() -> SdkError) {
+        let mut usable = Vec::with_capacity(self.usable.len());
+        for quote in self.usable.drain(..) {
+            match check(&quote) {
+                Some(reason) => {
+                    tracing::warn!("[SDK] Discarding quote from {}: {}", quote.agent_id, reason);
+                    self.discarded.push((quote, reason));
+                }
+                None => usable.push(quote),
+            }
+        }
+        self.usable = usable;
+        if self.usable.is_empty() {
+            self.exhausted = Some(exhausted());
+        }
+    }
+}
+
+/// Process-local response cache, or one shared through a backend
+enum ResponseCache {
+    Local(Cache<TransactionResponse>),
+    Shared(SharedCache<TransactionResponse>),
+}
+
+impl ResponseCache {
+    async fn get(&self, key: &str) -> Option<TransactionResponse> {
+        match self {
+            ResponseCache::Local(cache) => cache.get(key),
+            ResponseCache::Shared(cache) => cache.get(key).await,
+        }
+    }
+
+    async fn set(&self, key: String, resp: &TransactionResponse) {
+        match self {
+            ResponseCache::Local(cache) => cache.set(key, resp.clone()),
+            ResponseCache::Shared(cache) => cache.set(&key, resp).await,
+        }
+    }
+}
+
+/// Key under which responses for the same intent pattern are cached
+/// (base units when known, so "1000.00" and "1000" share an entry)
+/// Number of recent transactions remembered for `speed_up`
+const TRACKED_SUBMISSIONS: usize = 4096;
+
+/// Transaction executed by this client
+#[derive(Debug, Clone)]
+struct SubmittedTx {
+    reference_id: String,
+    agent_id: String,
+    chain: ChainId,
+    /// Hash of the transaction that replaced this one
+    replaced_by: Option<String>,
+    /// Intent fields as sealed to the agent, if they were
+    sealed_intent: Option<SealedIntent>,
+    /// Commitment to the accepted quote
+    quote_hash: Option<String>,
+}
+
+/// Self-broadcast execution awaiting `confirm_external_broadcast`
+struct PendingBroadcast {
+    req: TransactionRequest,
+    execution: SignedExecution,
+    quote_hash: Option<String>,
+    disbursements: Vec<Disbursement>,
+    fee_splits: Vec<FeeSplitAmount>,
+    escrow_terms: Option<EscrowTerms>,
+}
+
+/// Recently executed transactions by hash, oldest evicted first
+#[derive(Default)]
+struct SubmittedTransactions {
+    inner: Mutex<(HashMap<String, SubmittedTx>, VecDeque<String>)>,
+}
+
+impl SubmittedTransactions {
+    fn record(&self, tx_hash: &str, tx: SubmittedTx) {
+        let mut guard = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let (txs, order) = &mut *guard;
+        if txs.len() >= TRACKED_SUBMISSIONS {
+            if let Some(oldest) = order.pop_front() {
+                txs.remove(&oldest);
+            }
+        }
+        if txs.insert(tx_hash.to_string(), tx).is_none() {
+            order.push_back(tx_hash.to_string());
+        }
+    }
+
+    fn get(&self, tx_hash: &str) -> Option<SubmittedTx> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).0.get(tx_hash).cloned()
+    }
+
+    fn replace(&self, tx_hash: &str, new_tx_hash: &str) {
+        let Some(mut tx) = self.get(tx_hash) else {
+            return;
+        };
+        if let Some(old) = self.inner.lock().unwrap_or_else(|e| e.into_inner()).0.get_mut(tx_hash) {
+            old.replaced_by = Some(new_tx_hash.to_string());
+        }
+        tx.replaced_by = None;
+        self.record(new_tx_hash, tx);
+    }
+}
+
+fn cache_key(req: &TransactionRequest) -> String {
+    let intent = req.intent_type.as_str();
+    let amount = req.amount_base_units.as_deref().unwrap_or(&req.amount);
+    let mut key = String::with_capacity(intent.len() + amount.len() + req.asset.len() + 2);
+    key.push_str(intent);
+    key.push('-');
+    key.push_str(amount);
+    key.push('-');
+    key.push_str(&req.asset);
+    key
+}
+
+fn check_solvency(available: f64, required: f64, asset: &str) -> Result<()> {
+    if available < required {
+        return Err(SdkError::new(
+            ErrorCode::InsufficientFunds,
+            format!("insufficient {} balance: {} available, {} required", asset, available, required),
+        )
+        .with_details(serde_json::json!({ "available": available, "required": required })));
+    }
+    Ok(())
+}
+
+fn refund_error(rejection: RefundRejection) -> SdkError {
+    let code = match rejection {
+        RefundRejection::NotFound { .. } | RefundRejection::ExceedsRefundable { .. } | RefundRejection::InvalidAmount(_) => {
+            ErrorCode::InvalidRequest
+        }
+        RefundRejection::WindowExpired { .. } => ErrorCode::Expired,
+        RefundRejection::PartialNotAllowed { .. } | RefundRejection::TooManyRefunds { .. } => ErrorCode::PolicyViolation,
+        RefundRejection::Unavailable(_) => ErrorCode::NetworkFailure,
+    };
+    SdkError::new(code, rejection.to_string())
+}
+
+fn sponsor_error(rejection: SponsorRejection) -> SdkError {
+    let code = match rejection {
+        SponsorRejection::NotFound { .. } | SponsorRejection::InvalidAuthorization { .. } => ErrorCode::InvalidRequest,
+        SponsorRejection::NotCovered { .. } | SponsorRejection::DailyLimitExceeded { .. } => ErrorCode::PolicyViolation,
+        SponsorRejection::InsufficientBalance { .. } => ErrorCode::InsufficientFunds,
+        SponsorRejection::RecipientCannotPay { .. } => ErrorCode::FeeTooHigh,
+        SponsorRejection::Unavailable(_) => ErrorCode::NetworkFailure,
+    };
+    SdkError::new(code, rejection.to_string()).with_details(serde_json::json!({ "reason": rejection.reason() }))
+}
+
+fn escrow_error(rejection: EscrowRejection) -> SdkError {
+    let code = match rejection {
+        EscrowRejection::NotFound { .. } | EscrowRejection::NotLocked { .. } => ErrorCode::InvalidRequest,
+        EscrowRejection::ConditionUnmet { .. } | EscrowRejection::RefundNotDue { .. } => ErrorCode::PolicyViolation,
+        EscrowRejection::Unavailable(_) => ErrorCode::NetworkFailure,
+    };
+    SdkError::new(code, rejection.to_string()).with_details(serde_json::json!({ "reason": rejection.reason() }))
+}
+
+fn guard_error(rejection: GuardRejection) -> SdkError {
+    let reason = match rejection {
+        GuardRejection::IntentMismatch { .. } => "intent_mismatch",
+        GuardRejection::InProgress { .. } => "in_progress",
+        GuardRejection::OutcomeUnknown { .. } => "outcome_unknown",
+        GuardRejection::Unavailable(_) => {
+            return SdkError::new(ErrorCode::NetworkFailure, rejection.to_string());
+        }
+    };
+    SdkError::new(ErrorCode::DuplicateReference, rejection.to_string())
+        .with_details(serde_json::json!({ "reason": reason }))
+}
+
+fn spend_error(rejection: SpendRejection) -> SdkError {
+    let code = match rejection {
+        SpendRejection::Unavailable(_) => ErrorCode::NetworkFailure,
+        _ => ErrorCode::InvalidRequest,
+    };
+    SdkError::new(code, rejection.to_string()).with_details(serde_json::json!({ "reason": rejection.reason() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType, TransactionRequest};
+
+    #[tokio::test]
+    async fn test_client_new() {
+        let config = SdkConfig::default_config();
+        let client = EasyCashClient::new(Some(config));
+        assert!(client.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_client_new_default() {
+        let client = EasyCashClient::new(None);
+        assert!(client.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction() {
+        let client = EasyCashClient::new(None).unwrap();
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+
+        let resp = client.execute_transaction(&req).await;
+        assert!(resp.is_ok());
+        let resp = resp.unwrap();
+        assert!(resp.tx_hash.starts_with("0x"));
+        assert_eq!(resp.status, "confirmed");
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_with_shield() {
+        let client = EasyCashClient::new(None).unwrap();
+        let req = TransactionRequest::new("ref_002", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_shielded(true);
+
+        let resp = client.execute_transaction(&req).await;
+        assert!(resp.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_invalid() {
+        let client = EasyCashClient::new(None).unwrap();
+        let req = TransactionRequest::new("ref_003", IntentType::Transfer, "", "USDC", ChainId::Base);
+
+        let resp = client.execute_transaction(&req).await;
+        assert!(resp.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_caching() {
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = true;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        
+        let req = TransactionRequest::new("ref_004", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+
+        // First call
+        let resp1 = client.execute_transaction(&req).await.unwrap();
+        
+        // Second call should hit cache
+        let resp2 = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp1.tx_hash, resp2.tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_custom_validator_runs_in_execute() {
+        struct NoSwaps;
+        impl Validator for NoSwaps {
+            fn name(&self) -> &str {
+                "no_swaps"
+            }
+            fn validate(&self, req: &TransactionRequest) -> std::result::Result<(), String> {
+                match req.intent_type {
+                    IntentType::Swap => Err("swaps are disabled for this account".to_string()),
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config)).unwrap().with_validator(Arc::new(NoSwaps));
+        let mut req = TransactionRequest::new("ref_validator", IntentType::Swap, "5.00", "USDC", ChainId::Base);
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        assert!(err.message.contains("no_swaps validation failed"), "{}", err.message);
+
+        req.intent_type = IntentType::Transfer;
+        assert!(client.execute_transaction(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cache_backend_shared_between_clients() {
+        let backend = Arc::new(Cache::<Vec<u8>>::new(Duration::from_secs(60)));
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let first = EasyCashClient::new(Some(config.clone())).unwrap().with_cache_backend(backend.clone());
+        let second = EasyCashClient::new(Some(config)).unwrap().with_cache_backend(backend.clone());
+
+        let req = TransactionRequest::new("ref_shared_cache", IntentType::Transfer, "250.00", "USDC", ChainId::Base);
+        let resp1 = first.execute_transaction(&req).await.unwrap();
+        let resp2 = second.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp1.tx_hash, resp2.tx_hash);
+        assert!(backend.get("ecash:responses:transfer-250000000-USDC").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_attribution_reaches_events_and_store() {
+        use crate::exactly_once::{InMemorySubmissionStore, SubmissionStore};
+
+        let store = Arc::new(InMemorySubmissionStore::new());
+        let client = EasyCashClient::new(None)
+            .unwrap()
+            .with_exactly_once(ExactlyOnceGuard::new(store.clone()));
+        let mut rx = client.subscribe_events();
+
+        let req = TransactionRequest::new("ref_attributed", IntentType::Transfer, "10.00", "USDC", ChainId::Base)
+            .with_account_id("acct-7")
+            .with_metadata("desk", "otc");
+        client.execute_transaction(&req).await.unwrap();
+
+        let record = store.get("ref_attributed").unwrap().unwrap();
+        assert_eq!(record.account_id.as_deref(), Some("acct-7"));
+        assert_eq!(record.metadata["desk"], "otc");
+        let mut confirmed = None;
+        while let Ok(event) = rx.try_recv() {
+            if let SdkEvent::Confirmed { .. } = event {
+                confirmed = Some(event);
+            }
+        }
+        let confirmed = confirmed.unwrap();
+        assert_eq!(confirmed.account_id(), Some("acct-7"));
+        assert!(matches!(confirmed, SdkEvent::Confirmed { ref metadata, .. } if metadata["desk"] == "otc"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_publishes_events() {
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        let mut rx = client.subscribe_events();
+
+        let req = TransactionRequest::new("ref_events", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_shielded(true);
+        client.execute_transaction(&req).await.unwrap();
+
+        let mut names = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            assert_eq!(event.reference_id(), "ref_events");
+            names.push(event.name());
+        }
+        assert_eq!(
+            names,
+            vec![
+                "quote_received",
+                "quote_received",
+                "route_selected",
+                "proof_generated",
+                "execution_started",
+                "confirmed"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_propagates_correlation_id() {
+        let client = EasyCashClient::new(None).unwrap();
+        let mut rx = client.subscribe_events();
+
+        let mut req = TransactionRequest::new("ref_corr", IntentType::Transfer, "25.00", "USDC", ChainId::Base)
+            .with_correlation_id("corr-abc");
+        let resp = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.correlation_id, "corr-abc");
+        while let Ok(event) = rx.try_recv() {
+            assert_eq!(event.correlation_id(), "corr-abc");
+        }
+
+        // A correlation ID is generated when the caller does not supply one
+        req.correlation_id = None;
+        req.amount = "-1".to_string();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        let generated = err.correlation_id.expect("correlation id on error");
+        assert!(!generated.is_empty());
+        while let Ok(event) = rx.try_recv() {
+            assert_eq!(event.correlation_id(), generated);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sign_receipt() {
+        use crate::receipt::verify_receipt;
+        use k256::SecretKey;
+
+        let req = TransactionRequest::new("ref_receipt", IntentType::Transfer, "25.00", "USDC", ChainId::Base);
+
+        let client = EasyCashClient::new(None).unwrap();
+        let resp = client.execute_transaction(&req).await.unwrap();
+        let err = client.sign_receipt(&req, resp.clone()).unwrap_err();
+        assert_eq!(err.code, ErrorCode::SignerUnavailable);
+
+        let key = SecretKey::from_bytes(&[11u8; 32].into()).unwrap();
+        let verifying_key = TransactionSigner::new(key.clone()).verifying_key();
+        let client = client.with_receipt_signer(TransactionSigner::new(key));
+        let receipt = client.sign_receipt(&req, resp).unwrap();
+        assert!(verify_receipt(&receipt, &verifying_key).unwrap());
+        assert!(receipt.matches_request(&req));
+    }
+
+    #[tokio::test]
+    async fn test_chain_adapter_integration() {
+        use crate::chain::MockChainAdapter;
+
+        let adapter = Arc::new(MockChainAdapter::new(ChainId::Base));
+        adapter.set_block_height(5_000_000);
+        let client = EasyCashClient::new(None).unwrap().with_chain_adapter(adapter.clone());
+
+        let req = TransactionRequest::new("ref_chain", IntentType::Transfer, "25.00", "USDC", ChainId::Base);
+        let resp = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.block_height, 5_000_000);
+
+        let tx_hash = adapter.broadcast_raw_transaction("0xf86c").await.unwrap();
+        let receipt = client.get_transaction_status(ChainId::Base, &tx_hash).await.unwrap();
+        assert!(receipt.unwrap().success);
+
+        let err = client.get_transaction_status(ChainId::Solana, &tx_hash).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::UnsupportedChain);
+    }
+
+    #[tokio::test]
+    async fn test_get_balance() {
+        use crate::chain::MockChainAdapter;
+        use crate::zk::notes::{InMemoryNoteScanner, ShieldedNote};
+
+        let address = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+        let adapter = Arc::new(MockChainAdapter::new(ChainId::Base));
+        adapter.set_balance(address, "USDC", "5000000");
+
+        let vk_str = format!("evk_{}", "cd".repeat(32));
+        let scanner = Arc::new(InMemoryNoteScanner::new());
+        scanner.add_note(
+            &ViewingKey::parse(&vk_str).unwrap(),
+            ChainId::Base,
+            ShieldedNote {
+                commitment: "0x01".to_string(),
+                asset: "USDC".to_string(),
+                amount: 1_250_000,
+                spent: false,
+            },
+        );
+
+        let client = EasyCashClient::new(None)
+            .unwrap()
+            .with_chain_adapter(adapter)
+            .with_note_scanner(scanner);
+
+        let balance = client.get_balance(address, "usdc", ChainId::Base).await.unwrap();
+        assert_eq!(balance.transparent.as_deref(), Some("5000000"));
+        assert!(balance.shielded.is_none());
+
+        let balance = client.get_balance(&vk_str, "USDC", ChainId::Base).await.unwrap();
+        assert_eq!(balance.shielded.as_deref(), Some("1250000"));
+        assert!(balance.transparent.is_none());
+
+        let err = client.get_balance("not-an-address", "USDC", ChainId::Base).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        let err = client.get_balance(address, "USDC", ChainId::Ethereum).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::UnsupportedChain);
+    }
+
+    #[tokio::test]
+    async fn test_solvency_precheck() {
+        use crate::solvency::StaticBalanceProvider;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let provider = Arc::new(StaticBalanceProvider::new());
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_balance_provider(provider.clone());
+
+        let req = TransactionRequest::new("ref_solvency", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_shielded(true);
+
+        // Not even the amount is covered: rejected before quoting
+        provider.set_balance("USDC", 50.0);
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InsufficientFunds);
+        assert_eq!(err.details["required"], 100.0);
+
+        // The amount is covered but not the fee
+        provider.set_balance("USDC", 100.0);
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InsufficientFunds);
+
+        provider.set_balance("USDC", 1000.0);
+        assert!(client.execute_transaction(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_price_oracle_normalizes_fee_metrics() {
+        use crate::pricing::StaticPriceOracle;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let oracle = Arc::new(StaticPriceOracle::new().with_price("USDC", 0.5));
+        let client = EasyCashClient::new(Some(config)).unwrap().with_price_oracle(oracle);
+
+        let req = TransactionRequest::new("ref_price", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+        let resp = client.execute_transaction(&req).await.unwrap();
+        let raw_fee: f64 = resp.fee_used.split_whitespace().next().unwrap().parse().unwrap();
+
+        let stats = client.get_metrics();
+        assert!((stats["total_fee_paid"] - raw_fee * 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_publishes_validation_failure() {
+        let client = EasyCashClient::new(None).unwrap();
+        let mut rx = client.subscribe_events();
+
+        let req = TransactionRequest::new("ref_invalid", IntentType::Transfer, "abc", "USDC", ChainId::Base);
+        assert!(client.execute_transaction(&req).await.is_err());
+
+        assert_eq!(rx.try_recv().unwrap().name(), "validation_failed");
+        assert_eq!(rx.try_recv().unwrap().name(), "failed");
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_writes_audit_chain() {
+        use crate::audit::{verify_chain, MemoryAuditSink};
+        use std::sync::Arc;
+
+        let sink = Arc::new(MemoryAuditSink::new());
+        let client = EasyCashClient::new(None)
+            .unwrap()
+            .with_audit_logger(AuditLogger::new().with_sink(sink.clone()));
+
+        let req = TransactionRequest::new("ref_audit", IntentType::Transfer, "1000.00", "USDC", ChainId::Base);
+        client.execute_transaction(&req).await.unwrap();
+
+        let entries = sink.entries();
+        let kinds: Vec<AuditKind> = entries.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![AuditKind::Request, AuditKind::Decision, AuditKind::Response]);
+        assert!(verify_chain(&entries).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_compliance_rejected() {
+        use crate::compliance::DenylistScreeningProvider;
+
+        let denylist = Arc::new(DenylistScreeningProvider::new());
+        denylist.deny_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        let client = EasyCashClient::new(None).unwrap().with_screening_provider(denylist);
+
+        let req = TransactionRequest::new("ref_sanctioned", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ComplianceRejected);
+    }
+
+    #[tokio::test]
+    async fn test_screening_covers_every_payee() {
+        use crate::compliance::DenylistScreeningProvider;
+        use crate::types::FeeSplit;
+
+        let denied = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1";
+        let denylist = Arc::new(DenylistScreeningProvider::new());
+        denylist.deny_address(denied);
+        let client = EasyCashClient::new(None).unwrap().with_screening_provider(denylist);
+
+        // One denylisted leg rejects the whole disbursement
+        let mut req = TransactionRequest::new("ref_payout_denied", IntentType::Disburse, "0", "USDC", ChainId::Base);
+        let legs = [
+            Disbursement {
+                recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string(),
+                amount: "60".to_string(),
+            },
+            Disbursement {
+                recipient: denied.to_string(),
+                amount: "40".to_string(),
+            },
+        ];
+        intents::set_disbursements(&mut req, &legs).unwrap();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ComplianceRejected);
+        assert_eq!(err.details["recipient"], denied);
+
+        // So does a denylisted fee split recipient
+        let mut req = TransactionRequest::new("ref_split_denied", IntentType::Transfer, "200", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        req.fee_splits = vec![FeeSplit { recipient: denied.to_string(), bps: 250 }];
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ComplianceRejected);
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_requires_travel_rule_above_threshold() {
+        let mut config = SdkConfig::default_config();
+        config.travel_rule.enabled = true;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        let req = TransactionRequest::new("ref_vasp", IntentType::Transfer, "25000.00", "USDC", ChainId::Base);
+
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        assert!(err.message.contains("travel-rule data is required"));
+    }
+
+    #[tokio::test]
+    async fn test_travel_rule_data_is_sealed_on_the_wire() {
+        use crate::travel_rule::{TravelRuleInfo, TravelRuleParty, Vasp};
+
+        /// Records the JSON of executed intents
+        #[derive(Default)]
+        struct Recording(std::sync::Mutex<Vec<serde_json::Value>>);
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Recording {
+            async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                assert!(req.travel_rule.is_none() && req.sealed_travel_rule.is_none());
+                crate::agent::MockAgentNegotiator::new(Duration::from_secs(1)).request_quotes(req).await
+            }
+
+            async fn execute(&self, req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                self.0.lock().unwrap().push(serde_json::to_value(req).unwrap());
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let vasp_key = k256::SecretKey::from_bytes(&[9u8; 32].into()).unwrap();
+        let info = TravelRuleInfo {
+            originator: TravelRuleParty {
+                name: "Alice Example".to_string(),
+                account_number: Some("0x1111111111111111111111111111111111111111".to_string()),
+                date_of_birth: Some("1990-01-01".to_string()),
+                ..Default::default()
+            },
+            beneficiary: TravelRuleParty {
+                name: "Bob Example".to_string(),
+                account_number: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
+                ..Default::default()
+            },
+            originating_vasp: None,
+            beneficiary_vasp: Some(Vasp {
+                name: "Beneficiary Exchange".to_string(),
+                lei: None,
+                public_key: Some(crate::crypto::public_key_to_hex(&vasp_key.public_key())),
+            }),
+        };
+        let negotiator = Arc::new(Recording::default());
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config)).unwrap().with_negotiator(negotiator.clone());
+        let req = TransactionRequest::new("ref_vasp_sealed", IntentType::Transfer, "25000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_travel_rule(info.clone());
+        client.execute_transaction(&req).await.unwrap();
+
+        let wire = negotiator.0.lock().unwrap().pop().unwrap();
+        assert!(wire.get("travel_rule").is_none());
+        assert!(!wire.to_string().contains("Alice Example"));
+        let sealed: SealedTravelRule = serde_json::from_value(wire["sealed_travel_rule"].clone()).unwrap();
+        assert_eq!(sealed.open(&vasp_key).unwrap(), info);
+
+        // Data that can't be sealed is not sent at all
+        let mut unsealable = req.clone();
+        unsealable.reference_id = "ref_vasp_unsealable".to_string();
+        unsealable.travel_rule.as_mut().unwrap().beneficiary_vasp = None;
+        let err = client.execute_transaction(&unsealable).await.unwrap_err();
+        assert!(err.message.contains("failed to seal travel-rule data"));
+        assert!(negotiator.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_policy_violation() {
+        use crate::policy::{Policy, PolicyRule};
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let engine = PolicyEngine::new().with_policy(Policy::new(
+            "usdc-daily",
+            PolicyRule::RollingLimit {
+                asset: Some("USDC".to_string()),
+                chain: None,
+                max_amount: "1500".parse().unwrap(),
+                window: Duration::from_secs(3600),
+            },
+        ));
+        let client = EasyCashClient::new(Some(config)).unwrap().with_policy_engine(engine);
+
+        let req = TransactionRequest::new("ref_policy", IntentType::Transfer, "1000.00", "USDC", ChainId::Base);
+
+        assert!(client.execute_transaction(&req).await.is_ok());
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::PolicyViolation);
+        assert!(err.message.contains("usdc-daily"));
+        assert_eq!(err.details["policy"], "usdc-daily");
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_enforces_allowlist() {
+        use crate::address_book::{AddressEntry, InMemoryAddressStore};
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        config.require_allowlisted_recipients = true;
+        let book = AddressBook::new(Arc::new(InMemoryAddressStore::new()));
+        book.add(AddressEntry::new("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "Treasury").with_limit("USDC", "500"))
+            .unwrap();
+        let client = EasyCashClient::new(Some(config.clone())).unwrap().with_address_book(book);
+
+        let mut req = TransactionRequest::new("ref_allowlist", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        assert!(client.execute_transaction(&req).await.is_ok());
+
+        req.amount = "500.01".to_string();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::PolicyViolation);
+        assert_eq!(err.details["reason"], "recipient_limit_exceeded");
+
+        req.amount = "100.00".to_string();
+        req.recipient = Some("0x0000000000000000000000000000000000000001".to_string());
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "recipient_not_allowlisted");
+
+        // Enforcement without a book refuses every recipient
+        let unconfigured = EasyCashClient::new(Some(config)).unwrap();
+        assert_eq!(unconfigured.execute_transaction(&req).await.unwrap_err().code, ErrorCode::PolicyViolation);
+    }
+
+    #[tokio::test]
+    async fn test_execution_hooks_run_in_order() {
+        use crate::hooks::ExecutionHook;
+
+        struct Tagging(&'static str, Arc<Mutex<Vec<String>>>);
+
+        #[async_trait::async_trait]
+        impl ExecutionHook for Tagging {
+            async fn before_validate(&self, req: &mut TransactionRequest) -> Result<()> {
+                let seen = req.metadata.get("hooks").cloned().unwrap_or_default();
+                req.metadata.insert("hooks".to_string(), seen + self.0);
+                Ok(())
+            }
+
+            async fn before_execute(&self, req: &TransactionRequest, route: &RouteQuote) -> Result<()> {
+                if req.amount == "999" {
+                    return Err(SdkError::new(ErrorCode::PolicyViolation, format!("{} refused {}", self.0, route.agent_id)));
+                }
+                Ok(())
+            }
+
+            async fn after_execute(&self, req: &TransactionRequest, result: &Result<TransactionResponse>) {
+                let outcome = if result.is_ok() { "ok" } else { "err" };
+                self.1.lock().unwrap().push(format!("{}:{}:{}", self.0, req.metadata["hooks"], outcome));
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_execution_hook(Arc::new(Tagging("a", seen.clone())))
+            .with_execution_hook(Arc::new(Tagging("b", seen.clone())));
+        let mut req = TransactionRequest::new("ref_hooks", IntentType::Transfer, "10", "USDC", ChainId::Base);
+        assert!(client.execute_transaction(&req).await.is_ok());
+        assert_eq!(*seen.lock().unwrap(), vec!["a:ab:ok", "b:ab:ok"]);
+
+        req.reference_id = "ref_hooks_refused".to_string();
+        req.amount = "999".to_string();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::PolicyViolation);
+        assert!(err.message.starts_with("a refused"));
+        assert_eq!(seen.lock().unwrap()[2..], ["a:ab:err", "b:ab:err"]);
+    }
+
+    #[tokio::test]
+    async fn test_intent_handler_replaces_builtin() {
+        use crate::intents::{IntentContext, IntentHandler};
+
+        struct DomesticTransfers;
+
+        #[async_trait::async_trait]
+        impl IntentHandler for DomesticTransfers {
+            fn intent(&self) -> IntentType {
+                IntentType::Transfer
+            }
+
+            async fn check(&self, _ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+                match req.target_chain {
+                    Some(chain) if chain != req.source_chain => {
+                        Err(SdkError::new(ErrorCode::PolicyViolation, "cross-chain transfers are disabled"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config)).unwrap().with_intent_handler(Arc::new(DomesticTransfers));
+        let mut req = TransactionRequest::new("ref_intent_handler", IntentType::Transfer, "10", "USDC", ChainId::Base);
+        assert!(client.execute_transaction(&req).await.is_ok());
+
+        req.reference_id = "ref_intent_handler_bridge".to_string();
+        req.target_chain = Some(ChainId::Ethereum);
+        assert_eq!(client.execute_transaction(&req).await.unwrap_err().code, ErrorCode::PolicyViolation);
+
+        // Other intents keep their built-in handling
+        req.intent_type = IntentType::Shield;
+        req.target_chain = None;
+        assert!(client.execute_transaction(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_conversion_quote() {
+        use crate::conversion::StaticConversionProvider;
+
+        let req = TransactionRequest::new("ref_conversion", IntentType::Transfer, "200", "USDC", ChainId::Base);
+        let client = EasyCashClient::new(None).unwrap();
+        assert_eq!(client.get_conversion_quote(&req, "EUR").await.unwrap_err().code, ErrorCode::InvalidRequest);
+
+        let rates = StaticConversionProvider::new().with_rate("USDC", "EUR", 0.5).with_rate("ETH", "EUR", 2000.0);
+        let client = client.with_conversion_provider(Arc::new(rates));
+        let quote = client.get_conversion_quote(&req, "eur").await.unwrap();
+        let route = client.get_quote(&req).await.unwrap();
+        assert_eq!(quote.transfer_fee, route.estimated_fee);
+        assert_eq!(quote.converted_amount, 100.0);
+        assert!(quote.total > quote.converted_amount);
+        assert_eq!(client.get_conversion_quote(&req, "GBP").await.unwrap_err().code, ErrorCode::NetworkFailure);
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_books_ledger() {
+        use crate::ledger::{customer_account, InMemoryLedgerStore, EntryKind, PENDING, SETTLED};
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_ledger(Ledger::new(Arc::new(InMemoryLedgerStore::new())));
+        let req = TransactionRequest::new("ref_ledger", IntentType::Transfer, "25.50", "USDC", ChainId::Base)
+            .with_account_id("acct-42");
+        assert!(client.execute_transaction(&req).await.is_ok());
+
+        let ledger = client.ledger.as_ref().unwrap();
+        let kinds: Vec<EntryKind> = ledger.entries().unwrap().iter().map(|e| e.kind).collect();
+        assert_eq!(&kinds[..2], &[EntryKind::Submitted, EntryKind::Settled]);
+        assert_eq!(ledger.balance(PENDING, "USDC").unwrap().net(), "0");
+        assert_eq!(ledger.balance(SETTLED, "USDC").unwrap().net(), "25.5");
+        // The routing fee is charged on top when quoted in USDC
+        let debits: f64 = ledger.balance(&customer_account(Some("acct-42")), "USDC").unwrap().debits.parse().unwrap();
+        assert!(debits >= 25.5);
+    }
+
+    #[tokio::test]
+    async fn test_fee_splits_are_paid_and_itemized() {
+        use crate::ledger::{platform_account, InMemoryLedgerStore, EntryKind, SETTLED};
+        use crate::types::FeeSplit;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_ledger(Ledger::new(Arc::new(InMemoryLedgerStore::new())));
+        let platform = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1";
+        let mut req = TransactionRequest::new("ref_marketplace", IntentType::Transfer, "200", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        req.fee_splits = vec![FeeSplit { recipient: platform.to_string(), bps: 250 }];
+
+        let resp = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.fee_splits.len(), 1);
+        assert_eq!((resp.fee_splits[0].bps, resp.fee_splits[0].amount.as_str()), (250, "5"));
+        let paid: Vec<(&str, &str)> = resp
+            .disbursements
+            .iter()
+            .map(|d| (d.recipient.as_str(), d.amount.as_str()))
+            .collect();
+        assert_eq!(paid, [(req.recipient.as_deref().unwrap(), "195"), (platform, "5")]);
+
+        let ledger = client.ledger.as_ref().unwrap();
+        let fee = ledger.entries().unwrap().into_iter().find(|e| e.kind == EntryKind::PlatformFee).unwrap();
+        assert_eq!((fee.debit_account.as_str(), fee.amount.as_str()), (SETTLED, "5"));
+        assert_eq!(ledger.balance(&platform_account(platform), "USDC").unwrap().net(), "5");
+        assert_eq!(ledger.balance(SETTLED, "USDC").unwrap().net(), "195");
+    }
+
+    #[tokio::test]
+    async fn test_get_metrics() {
+        let client = EasyCashClient::new(None).unwrap();
+        let metrics = client.get_metrics();
+        assert!(metrics.contains_key("total_transactions"));
+        assert_eq!(metrics["circuit_breaker_state"], 0.0);
+        assert_eq!(metrics["queue_depth"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_shielded_proofs_run_on_pool() {
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        config.proof_pool.threads = 1;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        let req = TransactionRequest::new("ref_proof_pool", IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_shielded(true);
+        assert!(client.execute_transaction(&req).await.is_ok());
+        let pool = client.metrics_snapshot().proof_pool;
+        assert_eq!((pool.completed, pool.queued, pool.in_progress), (1, 0, 0));
+        assert!(client.get_metrics().contains_key("average_prove_time_ms"));
+    }
+
+    #[tokio::test]
+    async fn test_retried_execution_reuses_cached_proof() {
+        use crate::zk::proof_cache::InMemoryProofCacheStore;
+        use crate::zk::witness::LocalKeyStore;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let store = Arc::new(InMemoryProofCacheStore::new());
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_proof_cache(store.clone(), Arc::new(LocalKeyStore::new("kek-1", [9u8; 32])));
+        let req = TransactionRequest::new("ref_proof_cache", IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_shielded(true);
+        assert!(client.execute_transaction(&req).await.is_ok());
+        assert!(client.execute_transaction(&req).await.is_ok());
+        assert_eq!(client.metrics_snapshot().proof_pool.completed, 1);
+        assert_eq!(store.get("ref_proof_cache").unwrap().unwrap().witness.key_id, "kek-1");
+    }
+
+    #[tokio::test]
+    async fn test_second_spend_of_note_is_refused() {
+        use crate::zk::notes::create_note;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_nullifier_tracker(Arc::new(NullifierTracker::default()));
+        let mut req = TransactionRequest::new("ref_spend_1", IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_shielded(true);
+        let options = ExecuteOptions::new().with_notes(vec![create_note("USDC", 10_000_000, "0xowner")]);
+        assert!(client.execute_transaction_with_options(&req, &options).await.is_ok());
+
+        req.reference_id = "ref_spend_2".to_string();
+        let err = client.execute_transaction_with_options(&req, &options).await.unwrap_err();
+        assert_eq!((err.code, err.details["reason"].as_str()), (ErrorCode::InvalidRequest, Some("double_spend")));
+        assert_eq!(client.get_metrics()["near_miss_double_spends"], 1.0);
+        assert_eq!(client.metrics_snapshot().proof_pool.completed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_shielded_intents_are_sealed_to_agent() {
+        use crate::agent::StaticAgentDirectory;
+
+        /// Opens sealed intents with the transport key of agent-001
+        struct Sealing(std::sync::Mutex<Vec<TransactionRequest>>);
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Sealing {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                Ok(vec![RouteQuote {
+                    agent_id: "agent-001".to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time: Duration::from_secs(1),
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                }])
+            }
+
+            async fn execute_sealed(
+                &self,
+                req: &TransactionRequest,
+                sealed: &SealedIntent,
+                route: &RouteQuote,
+                _progress: &ProgressReporter,
+            ) -> std::result::Result<TransactionResponse, String> {
+                assert!(req.amount.is_empty() && req.recipient.is_none());
+                let mut req = req.clone();
+                sealed.open(&agent_key())?.restore(&mut req)?;
+                self.0.lock().unwrap().push(req);
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        fn agent_key() -> k256::SecretKey {
+            k256::SecretKey::from_bytes(&[9u8; 32].into()).unwrap()
+        }
+
+        let negotiator = Arc::new(Sealing(Default::default()));
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_negotiator(negotiator.clone())
+            .with_receipt_signer(TransactionSigner::new(k256::SecretKey::from_bytes(&[3u8; 32].into()).unwrap()));
+        let req = TransactionRequest::new("ref_sealed", IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_shielded(true);
+
+        // Without a published key the intent is not sent in plaintext
+        let unpublished = client.with_agent_directory(Arc::new(StaticAgentDirectory::new()));
+        let err = unpublished.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "no_transport_key");
+
+        let directory = StaticAgentDirectory::new().with_agent("agent-001", agent_key().public_key());
+        let client = unpublished.with_agent_directory(Arc::new(directory));
+        let resp = client.execute_transaction(&req).await.unwrap();
+        let received = negotiator.0.lock().unwrap().pop().unwrap();
+        assert_eq!((received.amount.as_str(), received.recipient), ("10", req.recipient.clone()));
+
+        let receipt = client.sign_receipt(&req, resp).unwrap();
+        let secrets = receipt.open_intent(&agent_key()).unwrap().unwrap();
+        assert_eq!(secrets.reference_id, "ref_sealed");
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_rejects_unsigned_and_spoofed_quotes() {
+        use crate::agent::StaticAgentDirectory;
+
+        struct Unsigned;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Unsigned {
+            async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                let quotes = AgentNegotiator::new(Duration::ZERO).request_quotes(req).await?;
+                Ok(quotes
+                    .into_iter()
+                    .map(|q| RouteQuote { signature: None, agent_pubkey: None, ..q })
+                    .collect())
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        // Signs every quote as a new agent with a key of its own
+        struct SelfSigned;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for SelfSigned {
+            async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                let signer = TransactionSigner::new(k256::SecretKey::from_bytes(&[5u8; 32].into()).unwrap());
+                let quotes = AgentNegotiator::new(Duration::ZERO).request_quotes(req).await?;
+                quotes
+                    .into_iter()
+                    .map(|q| RouteQuote { agent_id: format!("rogue-{}", q.agent_id), ..q }.sign(&signer))
+                    .collect()
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let req = TransactionRequest::new("ref_quote_sig", IntentType::Transfer, "10", "USDC", ChainId::Base);
+        let mut config = SdkConfig::default_config();
+        config.require_signed_quotes = true;
+
+        // Signed quotes are only trusted against a published key
+        let client = EasyCashClient::new(Some(config.clone())).unwrap();
+        let err = client.get_quote(&req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "unverified_quotes");
+        let mut published = StaticAgentDirectory::new();
+        for quote in AgentNegotiator::new(Duration::ZERO).request_quotes(&req).await.unwrap() {
+            let key = crate::crypto::public_key_from_hex(quote.agent_pubkey.as_deref().unwrap()).unwrap();
+            published = published.with_quote_key(quote.agent_id, key);
+        }
+        let published = Arc::new(published);
+        let client = client.with_agent_directory(published.clone());
+        assert!(client.get_quote(&req).await.is_ok());
+        let rogue = client.with_negotiator(Arc::new(SelfSigned));
+        let err = rogue.get_quote(&req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "unverified_quotes");
+
+        let unsigned = EasyCashClient::new(Some(config.clone()))
+            .unwrap()
+            .with_agent_directory(published)
+            .with_negotiator(Arc::new(Unsigned));
+        let err = unsigned.get_quote(&req).await.unwrap_err();
+        assert_eq!((err.code, err.details["reason"].as_str()), (ErrorCode::AgentUnavailable, Some("unverified_quotes")));
+        config.require_signed_quotes = false;
+        let lenient = EasyCashClient::new(Some(config.clone())).unwrap().with_negotiator(Arc::new(Unsigned));
+        assert!(lenient.get_quote(&req).await.is_ok());
+
+        // Quotes signed with a key other than the one the agent published
+        let other = k256::SecretKey::from_bytes(&[4u8; 32].into()).unwrap().public_key();
+        let directory = StaticAgentDirectory::new()
+            .with_quote_key("agent-001", other)
+            .with_quote_key("agent-002", other);
+        let spoofed = EasyCashClient::new(Some(config)).unwrap().with_agent_directory(Arc::new(directory));
+        let err = spoofed.get_quote(&req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "unverified_quotes");
+    }
+
+    #[tokio::test]
+    async fn test_routing_respects_data_residency() {
+        use crate::residency::ResidencyConfig;
+
+        let req = TransactionRequest::new("ref_residency", IntentType::Transfer, "10", "USDC", ChainId::Base);
+        let quote_with = |residency: ResidencyConfig| {
+            let mut config = SdkConfig::default_config();
+            config.residency = residency;
+            let client = EasyCashClient::new(Some(config)).unwrap();
+            let req = req.clone();
+            async move { client.get_quote(&req).await }
+        };
+
+        // The mock agents execute from DE (agent-001) and US (agent-002)
+        let eu_only = quote_with(ResidencyConfig::default().allow("EU")).await.unwrap();
+        assert_eq!(eu_only.region.as_deref(), Some("DE"));
+        let not_de = quote_with(ResidencyConfig::default().exclude("DE")).await.unwrap();
+        assert_eq!(not_de.agent_id, "agent-002");
+
+        let err = quote_with(ResidencyConfig::default().allow("CH")).await.unwrap_err();
+        assert_eq!((err.code, err.details["reason"].as_str()), (ErrorCode::AgentUnavailable, Some("data_residency")));
+    }
+
+    #[tokio::test]
+    async fn test_explain_route() {
+        use crate::agent::explain::QuoteStatus;
+        use crate::residency::ResidencyConfig;
+
+        let req = TransactionRequest::new("ref_explain", IntentType::Transfer, "10", "USDC", ChainId::Base);
+        let client = EasyCashClient::new(None).unwrap();
+        let explanation = client.explain_route(&req).await.unwrap();
+        assert_eq!(explanation.selected.as_deref(), Some("agent-001"));
+        assert_eq!(explanation.winner().unwrap().agent_id, client.get_quote(&req).await.unwrap().agent_id);
+        assert!(explanation.reason.starts_with("highest balanced score"));
+        let runner_up = &explanation.quotes[1];
+        assert_eq!((runner_up.agent_id.as_str(), &runner_up.status), ("agent-002", &QuoteStatus::Eligible));
+        assert!(runner_up.score < explanation.quotes[0].score);
+        assert_eq!(runner_up.score, runner_up.factors.total());
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["quotes"][0]["status"], "selected");
+        assert!(json["quotes"][0]["factors"]["security"].as_f64().unwrap() > 0.0);
+
+        // Discarded quotes are listed last, with the reason
+        let mut config = SdkConfig::default_config();
+        config.residency = ResidencyConfig::default().exclude("DE");
+        let explanation = EasyCashClient::new(Some(config)).unwrap().explain_route(&req).await.unwrap();
+        assert_eq!(explanation.selected.as_deref(), Some("agent-002"));
+        assert!(matches!(
+            explanation.quotes[1].status,
+            QuoteStatus::Discarded { ref reason } if reason.contains("\"DE\"")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fee_above_quote_is_flagged() {
+        /// Charges `self.0` regardless of the quote it executes under
+        struct Overcharging(&'static str);
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Overcharging {
+            async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                AgentNegotiator::new(Duration::ZERO).request_quotes(req).await
+            }
+
+            async fn execute(&self, req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                assert_eq!(req.quote_hash, Some(route.commitment()?));
+                Ok(TransactionResponse::executed(self.0))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let mut req = TransactionRequest::new("ref_overcharged", IntentType::Transfer, "10", "USDC", ChainId::Base);
+        let signer = TransactionSigner::new(k256::SecretKey::from_bytes(&[3u8; 32].into()).unwrap());
+        let client = EasyCashClient::new(Some(config.clone()))
+            .unwrap()
+            .with_negotiator(Arc::new(Overcharging("0.50 USDC")))
+            .with_receipt_signer(signer);
+        let quote = client.get_quote(&req).await.unwrap();
+        let mut events = client.subscribe_events();
+        let resp = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.fee_used, "0.50 USDC");
+
+        let violation = std::iter::from_fn(|| events.try_recv().ok())
+            .find(|e| e.name() == "quote_violated")
+            .unwrap();
+        let quote_hash = quote.commitment().unwrap();
+        assert!(matches!(violation, SdkEvent::QuoteViolated { quote_hash: ref h, .. } if *h == quote_hash));
+        assert_eq!(client.get_metrics()["quote_violations"], 1.0);
+        let receipt = client.sign_receipt(&req, resp).unwrap();
+        assert_eq!(receipt.quote_hash, Some(quote_hash));
+
+        // Charging less than quoted is fine
+        let client = EasyCashClient::new(Some(config)).unwrap().with_negotiator(Arc::new(Overcharging("0.01 USDC")));
+        req.reference_id = "ref_undercharged".to_string();
+        client.execute_transaction(&req).await.unwrap();
+        assert_eq!(client.metrics_snapshot().quote_violations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_agents_missing_quoted_times_are_down_ranked() {
+        /// Quotes "agent-fast" at 1ms, which takes 20ms to execute
+        struct Slow;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Slow {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                let quote = |agent_id: &str, estimated_time| RouteQuote {
+                    agent_id: agent_id.to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time,
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                };
+                Ok(vec![quote("agent-fast", Duration::from_millis(1)), quote("agent-steady", Duration::from_secs(30))])
+            }
+
+            async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                if route.agent_id == "agent-fast" {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                }
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        config.sla.max_misses = 2;
+        let client = EasyCashClient::new(Some(config)).unwrap().with_negotiator(Arc::new(Slow));
+        let req = |reference_id: &str| TransactionRequest::new(reference_id, IntentType::Transfer, "10", "USDC", ChainId::Base);
+
+        assert_eq!(client.get_quote(&req("ref_sla_0")).await.unwrap().agent_id, "agent-fast");
+        for reference_id in ["ref_sla_1", "ref_sla_2"] {
+            client.execute_transaction(&req(reference_id)).await.unwrap();
+        }
+        let report = client.sla_report();
+        assert_eq!(report.penalized(), ["agent-fast"]);
+        assert_eq!((report.agents["agent-fast"].executions, report.agents["agent-fast"].time_misses), (2, 2));
+        assert!(report.agents["agent-fast"].average_time_delta > 0.5);
+
+        assert_eq!(client.get_quote(&req("ref_sla_3")).await.unwrap().agent_id, "agent-steady");
+        client.execute_transaction(&req("ref_sla_3")).await.unwrap();
+        assert_eq!(client.sla_report().agents["agent-steady"].executions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_equally_ranked_routes_are_balanced() {
+        use crate::agent::balancing::TieBreak;
+
+        struct Identical;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Identical {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                let quote = |agent_id: &str| RouteQuote {
+                    agent_id: agent_id.to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time: Duration::from_secs(15),
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                };
+                Ok(vec![quote("agent-001"), quote("agent-002")])
+            }
+
+            async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        config.load_balancing.strategy = TieBreak::RoundRobin;
+        let client = EasyCashClient::new(Some(config)).unwrap().with_negotiator(Arc::new(Identical));
+        for i in 0..4 {
+            let req = TransactionRequest::new(format!("ref_balanced_{}", i), IntentType::Transfer, "10", "USDC", ChainId::Base);
+            client.execute_transaction(&req).await.unwrap();
+        }
+        let report = client.sla_report();
+        assert_eq!((report.agents["agent-001"].executions, report.agents["agent-002"].executions), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_startup_verifies_trusted_setup() {
+        use crate::zk::artifacts::sha256_hex;
+        use crate::zk::setup::{CeremonyTranscript, Contribution};
+
+        let cache_dir = std::env::temp_dir().join(format!("ecash-client-setup-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        let transcript = serde_json::to_vec(&CeremonyTranscript {
+            circuit: "spend".to_string(),
+            contributions: vec![Contribution {
+                participant: "alice".to_string(),
+                input_hash: sha256_hex(b"powers of tau"),
+                output_hash: sha256_hex(b"proving key"),
+            }],
+        })
+        .unwrap();
+        std::fs::write(cache_dir.join("spend.zkey"), b"proving key").unwrap();
+        std::fs::write(cache_dir.join("spend.transcript.json"), &transcript).unwrap();
+
+        let mut config = SdkConfig::default_config();
+        config.zk_artifacts.cache_dir = cache_dir.clone();
+        config.zk_artifacts.trusted_transcripts = vec![sha256_hex(&transcript)];
+        assert!(EasyCashClient::new(Some(config.clone())).is_ok());
+
+        std::fs::write(cache_dir.join("spend.zkey"), b"swapped key").unwrap();
+        let err = EasyCashClient::new(Some(config.clone())).err().unwrap();
+        assert_eq!(err.code, ErrorCode::ProofGeneration);
+        assert!(err.message.contains("does not match the ceremony output"), "{}", err.message);
+
+        // Nothing is proven without ZK proofs, so nothing is verified
+        config.enable_zk_proofs = false;
+        assert!(EasyCashClient::new(Some(config)).is_ok());
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_circuit_artifacts_load_before_first_proof() {
+        use crate::zk::artifacts::{sha256_hex, ArtifactEntry, ArtifactManifest, ArtifactSource};
+        use std::collections::BTreeMap;
+
+        struct OneVersion;
+
+        #[async_trait::async_trait]
+        impl ArtifactSource for OneVersion {
+            async fn fetch(&self, path: &str) -> std::result::Result<Vec<u8>, String> {
+                let manifest = ArtifactManifest {
+                    circuit: "spend".to_string(),
+                    version: "2.0.0".to_string(),
+                    artifacts: BTreeMap::from([(
+                        ArtifactKind::CircuitWasm,
+                        ArtifactEntry {
+                            path: "spend.wasm".to_string(),
+                            sha256: sha256_hex(b"wasm"),
+                        },
+                    )]),
+                    signature: None,
+                };
+                match path {
+                    "spend/2.0.0/manifest.json" => Ok(serde_json::to_vec(&manifest).unwrap()),
+                    "spend/2.0.0/spend.wasm" => Ok(b"wasm".to_vec()),
+                    _ => Err(format!("{} not found", path)),
+                }
+            }
+        }
+
+        let cache_dir = std::env::temp_dir().join(format!("ecash-client-artifacts-{}", Uuid::new_v4()));
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        config.zk_artifacts.version = Some("2.0.0".to_string());
+        config.zk_artifacts.cache_dir = cache_dir.clone();
+        let mut req = TransactionRequest::new("ref_artifacts", IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_shielded(true);
+
+        // Pinned but neither cached nor downloadable
+        let offline = EasyCashClient::new(Some(config.clone())).unwrap();
+        assert_eq!(offline.execute_transaction(&req).await.unwrap_err().code, ErrorCode::ProofGeneration);
+
+        let manager = ArtifactManager::new(config.zk_artifacts.clone()).with_source(Arc::new(OneVersion));
+        let client = EasyCashClient::new(Some(config)).unwrap().with_artifact_manager(manager);
+        req.reference_id = "ref_artifacts_2".to_string();
+        assert!(client.execute_transaction(&req).await.is_ok());
+        assert_eq!(client.reload_circuit_artifacts(None).await.unwrap(), "2.0.0");
+        assert!(client.reload_circuit_artifacts(Some("3.0.0")).await.is_err());
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_agent_stats() {
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        assert!(client.get_agent_stats().is_empty());
+
+        let req = TransactionRequest::new("ref_agent_stats", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+        client.execute_transaction(&req).await.unwrap();
+
+        let stats = client.get_agent_stats();
+        assert_eq!(stats.len(), 2);
+        assert!(stats.values().all(|s| s.quotes_received == 1));
+        let winner = stats.values().find(|s| s.selections_won == 1).unwrap();
+        assert_eq!(winner.executions, 1);
+        assert_eq!(winner.execution_success_rate, 1.0);
+        assert!(winner.average_fee_delta >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_snapshot() {
+        let client = EasyCashClient::new(None).unwrap();
+        let snapshot = client.metrics_snapshot();
+        assert!(snapshot.metrics_enabled);
+        assert_eq!(snapshot.circuit_breaker, CircuitState::Closed);
+        assert_eq!(snapshot.to_map(), client.get_metrics());
+
+        let mut config = SdkConfig::default_config();
+        config.enable_metrics = false;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        assert!(!client.metrics_snapshot().metrics_enabled);
+        assert_eq!(client.get_metrics()["metrics_disabled"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_network_settings_are_per_client() {
+        let mut config = SdkConfig::default_config();
+        config.api_endpoint = "https://api.internal.example".to_string();
+        config.request_signing = serde_json::from_value(serde_json::json!({
+            "key": {"algorithm": "hmac_sha256", "key_id": "key_1", "secret": "s3cret"}
+        }))
+        .unwrap();
+        let signed = EasyCashClient::new(Some(config.clone())).unwrap();
+        let unsigned = EasyCashClient::new(None).unwrap();
+        let headers = |client: &EasyCashClient| {
+            client
+                .http_client()
+                .signature_headers("api.internal.example", "POST", "/v1/intents", b"{}")
+        };
+        assert!(!headers(&signed).is_empty());
+        assert!(headers(&unsigned).is_empty());
+
+        config.tls.client_cert = Some("/nonexistent/client.pem".into());
+        config.tls.client_key = Some("/nonexistent/client.key".into());
+        let err = EasyCashClient::new(Some(config)).err().unwrap();
+        assert!(err.message.contains("invalid network configuration"));
+    }
+
+    #[tokio::test]
+    async fn test_alert_monitor_fires_after_transaction() {
+        use crate::monitoring::alerts::{Alert, AlertCondition, AlertRule};
+        use crate::monitoring::MetricsWindow;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let monitor = AlertMonitor::new(Arc::new(move |alert: Alert| {
+            let tx = tx.clone();
+            async move {
+                let _ = tx.send(alert);
+            }
+        }))
+        .with_rule(AlertRule::new(
+            "latency",
+            AlertCondition::AverageLatencyAbove {
+                threshold_ms: 1.0,
+                window: MetricsWindow::OneMinute,
+            },
+        ));
+        let client = EasyCashClient::new(None).unwrap().with_alert_monitor(monitor);
+
+        let req = TransactionRequest::new("ref_alert", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+        client.execute_transaction(&req).await.unwrap();
+
+        let alert = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(alert.rule, "latency");
+    }
+
+    #[tokio::test]
+    async fn test_fee_budget_hard_stop() {
+        use crate::budget::FeeBudget;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        // The mock agents quote 0.03-0.05 USDC, so the budget covers exactly one execution
+        let tracker = FeeBudgetTracker::new().with_budget(FeeBudget::daily("USDC", 0.05));
+        let client = EasyCashClient::new(Some(config)).unwrap().with_fee_budget(tracker);
+
+        let mut req = TransactionRequest::new("ref_budget_1", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+        client.execute_transaction(&req).await.unwrap();
+
+        req.reference_id = "ref_budget_2".to_string();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::BudgetExceeded);
+        assert_eq!(err.details["asset"], "USDC");
+    }
+
+    #[tokio::test]
+    async fn test_executed_fees_are_recorded_in_history() {
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let history = Arc::new(FeeHistory::default());
+        let client = EasyCashClient::new(Some(config)).unwrap().with_fee_history(history.clone());
+
+        let mut req = TransactionRequest::new("ref_history_1", IntentType::Transfer, "1000", "USDC", ChainId::Base)
+            .with_target_chain(ChainId::Ethereum);
+        client.execute_transaction(&req).await.unwrap();
+        req.reference_id = "ref_history_2".to_string();
+        client.execute_transaction(&req).await.unwrap();
+
+        let route = FeeRoute::of(&req);
+        assert_eq!(history.hourly(&route).iter().map(|h| h.count).sum::<usize>(), 2);
+        let forecast = history.forecast(&route, 1000.0, std::time::SystemTime::now()).unwrap();
+        assert_eq!(forecast.expected, 0.05);
+    }
+
+    #[tokio::test]
+    async fn test_disbursement_reports_each_recipient() {
+        let mut req = TransactionRequest::new("ref_payout", IntentType::Disburse, "0", "USDC", ChainId::Base);
+        let split = [
+            Disbursement {
+                recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string(),
+                amount: "60".to_string(),
+            },
+            Disbursement {
+                recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1".to_string(),
+                amount: "40".to_string(),
+            },
+        ];
+        intents::set_disbursements(&mut req, &split).unwrap();
+
+        // The mock agent settles the split in one operation
+        let resp = EasyCashClient::new(None).unwrap().execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.status, "confirmed");
+        assert_eq!(resp.disbursements.len(), 2);
+        assert!(resp.disbursements.iter().all(DisbursementResult::is_confirmed));
+
+        /// Executes one transfer per recipient, rejecting the second
+        struct Legs;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Legs {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                Ok(vec![RouteQuote {
+                    agent_id: "legs-001".to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time: Duration::from_secs(30),
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                }])
+            }
+
+            async fn execute(&self, req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                match req.recipient.as_deref() {
+                    Some(recipient) if recipient.ends_with("bEb1") => Err("recipient rejected".to_string()),
+                    _ => Ok(TransactionResponse::executed(route.estimated_fee.clone())),
+                }
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(Arc::new(Legs));
+        req.reference_id = "ref_payout_legs".to_string();
+        let resp = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.status, "partial");
+        let statuses: Vec<&str> = resp.disbursements.iter().map(|d| d.status.as_str()).collect();
+        assert_eq!(statuses, ["confirmed", "failed"]);
+        assert_eq!(resp.disbursements[1].error.as_deref(), Some("recipient rejected"));
+
+        // Amounts that don't add up are rejected before quoting
+        req.reference_id = "ref_payout_mismatch".to_string();
+        req.amount = "90".to_string();
+        assert_eq!(client.execute_transaction(&req).await.unwrap_err().code, ErrorCode::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn test_get_quote_does_not_execute() {
+        let client = EasyCashClient::new(None).unwrap();
+        let mut events = client.subscribe_events();
+        let req = TransactionRequest::new("ref_quote", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+
+        let quote = client.get_quote(&req).await.unwrap();
+        assert!(quote.agent_id.starts_with("agent-"));
+        assert!(events.try_recv().is_err());
+        assert_eq!(client.get_metrics()["total_transactions"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_from_csv() {
+        let client = EasyCashClient::new(None).unwrap();
+        let csv = "reference_id,amount,asset,source_chain\n\
+                   payroll_1,2500.00,USDC,base\n\
+                   payroll_2,1800.00,USDC,ethereum\n";
+        let import = crate::batch::from_csv(csv.as_bytes()).unwrap();
+        assert!(import.is_clean());
+
+        let results = client.execute_batch(&import.requests).await;
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.as_ref().unwrap().status == "confirmed"));
+        assert_eq!(client.get_metrics()["total_transactions"], 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_batch_aggregates_shielded_proofs() {
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        let mut rx = client.subscribe_events();
+        let reqs: Vec<TransactionRequest> = (0..3)
+            .map(|i| TransactionRequest::new(format!("ref_batch_proof_{}", i), IntentType::Transfer, format!("{}0", i + 1), "USDC", ChainId::Base)
+                .with_shielded(i < 2))
+            .collect();
+
+        let results = client.execute_batch(&reqs).await;
+        assert!(results.iter().all(|r| r.is_ok()));
+        // One aggregated proof covers both shielded requests
+        let pool = client.metrics_snapshot().proof_pool;
+        assert_eq!(pool.completed, 1);
+        let mut proofs = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let SdkEvent::ProofGenerated { proof, .. } = event {
+                proofs.push(proof);
+            }
+        }
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0], proofs[1]);
+    }
+
+    #[tokio::test]
+    async fn test_exactly_once_replays_and_rejects_reused_reference() {
+        use crate::exactly_once::InMemorySubmissionStore;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_exactly_once(ExactlyOnceGuard::new(Arc::new(InMemorySubmissionStore::new())));
+        let mut req = TransactionRequest::new("ref_once", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+
+        let first = client.execute_transaction(&req).await.unwrap();
+        let replay = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(replay.tx_hash, first.tx_hash);
+        assert_eq!(client.get_metrics()["total_transactions"], 1.0);
+
+        req.amount = "20.00".to_string();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::DuplicateReference);
+        assert_eq!(err.details["reason"], "intent_mismatch");
+
+        // Requests rejected before submission can be retried
+        req.reference_id = "ref_once_invalid".to_string();
+        req.amount = "-1".to_string();
+        assert!(client.execute_transaction(&req).await.is_err());
+        req.amount = "5.00".to_string();
+        assert!(client.execute_transaction(&req).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_admission_timeout() {
+        let mut config = SdkConfig::default_config();
+        config.concurrency.max_in_flight = 1;
+        config.concurrency.queue_timeout = Duration::from_millis(10);
+        let client = EasyCashClient::new(Some(config)).unwrap();
+        let _held = client.limiter.acquire().await.unwrap();
+
+        let req = TransactionRequest::new("ref_005", IntentType::Transfer, "1000.00", "USDC", ChainId::Base);
+
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::Timeout);
+        assert_eq!(client.get_metrics()["rejected_admissions"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_agents_receive_base_units() {
+        /// Records the amounts agents are asked to quote
+        #[derive(Default)]
+        struct Recording(std::sync::Mutex<Vec<Option<String>>>);
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Recording {
+            async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                self.0.lock().unwrap().push(req.amount_base_units.clone());
+                Ok(vec![RouteQuote {
+                    agent_id: "agent-001".to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time: Duration::from_secs(1),
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                }])
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let negotiator = Arc::new(Recording::default());
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(negotiator.clone());
+        let mut req = TransactionRequest::new("ref_base_units", IntentType::Transfer, "1000.00", "USDC", ChainId::Base);
+        client.get_quote(&req).await.unwrap();
+        req.asset = "XYZ".to_string();
+        client.get_quote(&req).await.unwrap();
+        assert_eq!(*negotiator.0.lock().unwrap(), [Some("1000000000".to_string()), None]);
+
+        req.asset = "USDC".to_string();
+        req.amount = "0.0000001".to_string();
+        let err = client.get_quote(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        assert!(err.message.contains("more than 6 decimal places"));
+
+        req.amount = "1,000.50".to_string();
+        assert!(client.get_quote(&req).await.unwrap_err().message.contains("did you mean 1000.50?"));
+        let mut config = SdkConfig::default_config();
+        config.validation.normalize_locale_amounts = true;
+        let client = EasyCashClient::new(Some(config)).unwrap().with_negotiator(negotiator.clone());
+        client.get_quote(&req).await.unwrap();
+        assert_eq!(negotiator.0.lock().unwrap().last().unwrap().as_deref(), Some("1000500000"));
+    }
+
+    #[tokio::test]
+    async fn test_protocol_negotiation() {
+        /// Agents speaking the given protocol versions, recording quoted amounts
+        struct Versioned(Vec<u32>, std::sync::Mutex<Vec<Option<String>>>);
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Versioned {
+            async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                self.1.lock().unwrap().push(req.amount_base_units.clone());
+                AgentNegotiator::new(Duration::ZERO).request_quotes(req).await
+            }
+
+            async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+
+            async fn supported_versions(&self) -> std::result::Result<Vec<u32>, String> {
+                Ok(self.0.clone())
+            }
+        }
+
+        let req = TransactionRequest::new("ref_protocol", IntentType::Transfer, "10", "USDC", ChainId::Base);
+
+        // Older agents get the version 1 format, without base units
+        let older = Arc::new(Versioned(vec![1], Default::default()));
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(older.clone());
+        assert_eq!(client.protocol_version().await.unwrap(), 1);
+        client.execute_transaction(&req).await.unwrap();
+        assert_eq!(*older.1.lock().unwrap(), [None]);
+
+        // Newer agents only: refuse rather than send a format they may misread
+        let newer = Arc::new(Versioned(vec![4], Default::default()));
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(newer.clone());
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::UnsupportedProtocol);
+        assert!(newer.1.lock().unwrap().is_empty());
+
+        let current = EasyCashClient::new(None).unwrap();
+        assert_eq!(current.protocol_version().await.unwrap(), protocol::CURRENT_VERSION);
+    }
+
+    #[tokio::test]
+    async fn test_execute_transaction_streaming() {
+        use crate::streaming::{ExecutionUpdate, HopProgress, HopStatus};
+
+        /// Bridges base -> ethereum, reporting each hop
+        struct Bridge;
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Bridge {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                Ok(vec![RouteQuote {
+                    agent_id: "bridge-001".to_string(),
+                    estimated_fee: "0.50 USDC".to_string(),
+                    estimated_time: Duration::from_secs(600),
+                    route: vec!["base".to_string(), "ethereum".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                }])
+            }
+
+            async fn execute_with_progress(
+                &self,
+                _req: &TransactionRequest,
+                route: &RouteQuote,
+                progress: &ProgressReporter,
+            ) -> std::result::Result<TransactionResponse, String> {
+                for (hop, chain) in route.route.iter().enumerate() {
+                    for confirmations in [6, 12] {
+                        progress.report(HopProgress {
+                            hop,
+                            chain: chain.clone(),
+                            status: if confirmations < 12 { HopStatus::Confirming } else { HopStatus::Completed },
+                            tx_hash: Some(format!("0x{}", hop)),
+                            confirmations,
+                            required_confirmations: 12,
+                        });
+                        tokio::task::yield_now().await;
+                    }
+                }
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(Arc::new(Bridge));
+        let req = TransactionRequest::new("ref_bridge", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_target_chain(ChainId::Ethereum)
+            .with_correlation_id("corr-bridge");
+
+        let mut stream = client.execute_transaction_streaming(&req);
+        match stream.next().await {
+            Some(ExecutionUpdate::RouteSelected { agent_id, hops }) => {
+                assert_eq!(agent_id, "bridge-001");
+                assert_eq!(hops, ["base", "ethereum"]);
+            }
+            other => panic!("expected route selection, got {:?}", other),
+        }
+        let mut hops = Vec::new();
+        let resp = loop {
+            match stream.next().await {
+                Some(ExecutionUpdate::Hop(progress)) => hops.push((progress.hop, progress.confirmations, progress.status)),
+                Some(ExecutionUpdate::Finished(result)) => break result.unwrap(),
+                other => panic!("unexpected update {:?}", other),
+            }
+        };
+        assert_eq!(
+            hops,
+            [
+                (0, 6, HopStatus::Confirming),
+                (0, 12, HopStatus::Completed),
+                (1, 6, HopStatus::Confirming),
+                (1, 12, HopStatus::Completed),
+            ]
+        );
+        assert_eq!(resp.correlation_id, "corr-bridge");
+        assert!(stream.next().await.is_none());
+        drop(stream);
+
+        // Failures arrive as the final update
+        let invalid = TransactionRequest { amount: "-1".to_string(), ..req };
+        let err = client.execute_transaction_streaming(&invalid).finish().await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_in_flight_transaction() {
+        /// Signals when quoting or execution starts; hangs in quoting when asked
+        struct Slow {
+            entered: tokio::sync::Notify,
+            hang_in_quotes: bool,
+        }
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Slow {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                if self.hang_in_quotes {
+                    self.entered.notify_one();
+                    std::future::pending::<()>().await;
+                }
+                Ok(vec![RouteQuote {
+                    agent_id: "agent-001".to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time: Duration::from_secs(1),
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                }])
+            }
+
+            async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                self.entered.notify_one();
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let req = TransactionRequest::new("ref_cancel", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        assert_eq!(EasyCashClient::new(None).unwrap().cancel("ref_cancel"), CancelOutcome::NotInFlight);
+
+        // Quoting is aborted
+        let negotiator = Arc::new(Slow { entered: tokio::sync::Notify::new(), hang_in_quotes: true });
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(negotiator.clone());
+        let mut events = client.subscribe_events();
+        let (result, outcome) = tokio::join!(client.execute_transaction(&req), async {
+            negotiator.entered.notified().await;
+            client.cancel("ref_cancel")
+        });
+        assert_eq!(outcome, CancelOutcome::Cancelled);
+        assert_eq!(result.unwrap_err().code, ErrorCode::Cancelled);
+        assert_eq!(client.cancel("ref_cancel"), CancelOutcome::NotInFlight);
+        let mut last = None;
+        while let Ok(event) = events.try_recv() {
+            last = Some(event);
+        }
+        assert!(matches!(last, Some(SdkEvent::Failed { code: ErrorCode::Cancelled, .. })));
+
+        // A token cancelled up front stops the execution before any agent is asked
+        let token = CancellationToken::new();
+        token.cancel();
+        let options = ExecuteOptions::new().with_cancellation_token(token);
+        let err = client.execute_transaction_with_options(&req, &options).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::Cancelled);
+
+        // Past the point of no return the execution completes
+        let negotiator = Arc::new(Slow { entered: tokio::sync::Notify::new(), hang_in_quotes: false });
+        let client = EasyCashClient::new(None).unwrap().with_negotiator(negotiator.clone());
+        let token = CancellationToken::new();
+        let options = ExecuteOptions::new().with_cancellation_token(token.clone());
+        let (result, outcome) = tokio::join!(client.execute_transaction_with_options(&req, &options), async {
+            negotiator.entered.notified().await;
+            token.cancel();
+            client.cancel("ref_cancel")
+        });
+        assert_eq!(outcome, CancelOutcome::AlreadySubmitted);
+        assert_eq!(result.unwrap().status, "confirmed");
+    }
+
+    #[tokio::test]
+    async fn test_escrow_release() {
+        use crate::crypto::{SigningDomain, TransactionSigner};
+        use crate::escrow::{InMemoryEscrowStore, ReleaseCondition};
+        use crate::ledger::{InMemoryLedgerStore, PENDING, SETTLED};
+        use k256::{PublicKey, SecretKey};
+
+        let buyer = TransactionSigner::new(SecretKey::from_bytes(&[3u8; 32].into()).unwrap());
+        let mut req = TransactionRequest::new("ref_escrow", IntentType::Escrow, "250", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        let in_a_day = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() + 86_400;
+        EscrowTerms::new(ReleaseCondition::CounterpartySignature {
+            public_key: crate::crypto::public_key_to_hex(&PublicKey::from(&buyer.verifying_key())),
+        })
+        .with_refund_after(in_a_day)
+        .attach(&mut req);
+
+        let err = EasyCashClient::new(None).unwrap().execute_transaction(&req).await.unwrap_err();
+        assert!(err.message.contains("with_escrows"));
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_escrows(EscrowManager::new(Arc::new(InMemoryEscrowStore::new())))
+            .with_ledger(Ledger::new(Arc::new(InMemoryLedgerStore::new())));
+        let resp = client.execute_transaction(&req).await.unwrap();
+        assert_eq!(resp.status, "escrowed");
+        let ledger = client.ledger.as_ref().unwrap();
+        assert_eq!(ledger.balance(PENDING, "USDC").unwrap().net(), "250");
+
+        // The funds stay locked until the buyer signs, and can't be refunded yet
+        let err = client.release_escrow("ref_escrow", None).await.unwrap_err();
+        assert_eq!(err.details["reason"], "escrow_condition_unmet");
+        let err = client.refund_escrow("ref_escrow").await.unwrap_err();
+        assert_eq!(err.details["reason"], "escrow_refund_not_due");
+
+        let escrow = client.escrows.as_ref().unwrap().escrow("ref_escrow").unwrap().unwrap();
+        let signature = buyer.sign_in_domain(SigningDomain::Escrow, &escrow.release_message()).unwrap();
+        let released = client.release_escrow("ref_escrow", Some(&signature)).await.unwrap();
+        assert_eq!(released.state, EscrowState::Released);
+        assert_eq!(ledger.balance(SETTLED, "USDC").unwrap().net(), "250");
+        let err = client.release_escrow("ref_escrow", Some(&signature)).await.unwrap_err();
+        assert_eq!(err.details["reason"], "escrow_not_locked");
+
+        // Terms that could lock the funds for good are rejected up front
+        let mut unbounded = req.clone();
+        unbounded.reference_id = "ref_escrow_unbounded".to_string();
+        EscrowTerms::new(escrow.terms.condition).attach(&mut unbounded);
+        let err = client.execute_transaction(&unbounded).await.unwrap_err();
+        assert_eq!(err.details["reason"], "invalid_escrow_terms");
+    }
+
+    #[tokio::test]
+    async fn test_sponsored_fees() {
+        use crate::sponsorship::Sponsor;
+        use k256::{PublicKey, SecretKey};
+
+        let platform = TransactionSigner::new(SecretKey::from_bytes(&[4u8; 32].into()).unwrap());
+        let public_key = crate::crypto::public_key_to_hex(&PublicKey::from(&platform.verifying_key()));
+        let mut req = TransactionRequest::new("ref_sponsored", IntentType::Transfer, "20", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        FeePayer::sponsor(&req, "platform", &platform).unwrap().attach(&mut req);
+
+        let err = EasyCashClient::new(None).unwrap().execute_transaction(&req).await.unwrap_err();
+        assert!(err.message.contains("with_sponsors"));
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let sponsor = Sponsor::new("platform", "0xplatform", public_key).with_balance("USDC", 0.06);
+        let client = EasyCashClient::new(Some(config)).unwrap().with_sponsors(SponsorRegistry::new().with_sponsor(sponsor));
+        let resp = client.execute_transaction(&req).await.unwrap();
+        let sponsors = client.sponsors.as_ref().unwrap();
+        let (fee, _) = solvency::parse_fee(&resp.fee_used).unwrap();
+        assert!((sponsors.remaining("platform", "USDC").unwrap() - (0.06 - fee)).abs() < 1e-9);
+
+        // The authorization covers only the request it was signed for
+        let mut replayed = req.clone();
+        replayed.reference_id = "ref_sponsored_2".to_string();
+        let err = client.execute_transaction(&replayed).await.unwrap_err();
+        assert_eq!(err.details["reason"], "invalid_sponsor_authorization");
+
+        // The mock agents quote at least 0.03 USDC, more than the sponsor has left
+        FeePayer::sponsor(&replayed, "platform", &platform).unwrap().attach(&mut replayed);
+        let err = client.execute_transaction(&replayed).await.unwrap_err();
+        assert_eq!((err.code, err.details["reason"].as_str()), (ErrorCode::InsufficientFunds, Some("sponsor_balance_insufficient")));
+    }
+
+    #[tokio::test]
+    async fn test_settlement_contract_call() {
+        use crate::assets::{AssetConfig, AssetRegistry};
+        use crate::audit::MemoryAuditSink;
+        use crate::contracts::Deployment;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        config.assets = AssetRegistry::new().with_asset(
+            "USDC",
+            AssetConfig {
+                decimals: Some(6),
+                ..Default::default()
+            },
+        );
+        let sink = Arc::new(MemoryAuditSink::new());
+        let deployment = Deployment::new(ChainId::Base, "0x5FbDB2315678afecb367f032d93F642f64180aa3")
+            .with_token("USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_audit_logger(AuditLogger::new().with_sink(sink.clone()))
+            .with_contracts(Deployments::new().with_deployment(deployment));
+
+        let mut req = TransactionRequest::new("ref_contract", IntentType::Transfer, "12.5", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        client.execute_transaction(&req).await.unwrap();
+        let call = sink
+            .entries()
+            .into_iter()
+            .find_map(|e| e.payload.get("settlement_call").cloned())
+            .unwrap();
+        assert_eq!(call["function"], "settle(bytes32,bytes32,address,address,uint256)");
+        assert_eq!(call["contract"], "0x5FbDB2315678afecb367f032d93F642f64180aa3");
+
+        // An asset the deployment has no token contract for is rejected before submission
+        req.reference_id = "ref_contract_2".to_string();
+        req.asset = "USDT".to_string();
+        let err = client.execute_transaction(&req).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        assert!(err.message.contains("settlement call"));
+
+        // Chains without a deployment are left to the agent
+        req.source_chain = ChainId::Ethereum;
+        client.execute_transaction(&req).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sandbox_test_funds() {
+        struct Faucet;
+
+        #[async_trait::async_trait]
+        impl crate::sandbox::Faucet for Faucet {
+            async fn request_funds(&self, req: &FaucetRequest) -> std::result::Result<FaucetGrant, String> {
+                Ok(FaucetGrant {
+                    chain: req.chain,
+                    address: req.address.clone(),
+                    asset: req.asset.clone(),
+                    amount: "100".to_string(),
+                    tx_hash: "0xfaucet".to_string(),
+                })
+            }
+        }
+
+        let mut config = SdkConfig::default_config();
+        config.environment = "mainnet".to_string();
+        let mainnet = EasyCashClient::new(Some(config.clone())).unwrap().with_faucet(Arc::new(Faucet));
+        let err = mainnet.request_test_funds("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "USDC").await.unwrap_err();
+        assert_eq!(err.details["reason"], "not_sandbox");
+        assert!(mainnet.create_test_account(ChainId::Base).is_err());
+
+        config.environment = "testnet".to_string();
+        let testnet = EasyCashClient::new(Some(config)).unwrap();
+        assert!(testnet.faucet.is_some());
+        let testnet = testnet.with_faucet(Arc::new(Faucet));
+        let account = testnet.create_test_account(ChainId::Base).unwrap();
+        let grant = testnet.fund_test_account(&account, "usdc").await.unwrap();
+        assert_eq!((grant.chain, grant.address.as_str(), grant.asset.as_str()), (ChainId::Base, account.address.as_str(), "USDC"));
+        let grant = testnet.request_test_funds("7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV", "SOL").await.unwrap();
+        assert_eq!(grant.chain, ChainId::Solana);
+        assert_eq!(testnet.config().endpoint(), "https://api.testnet.useeasy.cash");
+    }
+
+    #[tokio::test]
+    async fn test_offline_prepare_and_submit() {
+        use k256::SecretKey;
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let preparer = TransactionSigner::new(SecretKey::from_bytes(&[6u8; 32].into()).unwrap());
+        let preparer_key = PublicKey::from(&preparer.verifying_key());
+        let offline = EasyCashClient::new(Some(config.clone())).unwrap().with_prepare_signer(preparer);
+        let online = EasyCashClient::new(Some(config)).unwrap();
+        let mut req = TransactionRequest::new("ref_offline", IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_shielded(true);
+
+        let bundle = online.fetch_quote_bundle(&req, Duration::from_secs(60)).await.unwrap();
+        let bundle: QuoteBundle = serde_json::from_str(&serde_json::to_string(&bundle).unwrap()).unwrap();
+        let prepared = offline.prepare(&req, &bundle).await.unwrap();
+        assert!(prepared.proof.is_some());
+        let prepared: PreparedIntent = serde_json::from_str(&serde_json::to_string(&prepared).unwrap()).unwrap();
+
+        let err = online.submit(&prepared).await.unwrap_err();
+        assert_eq!(err.details["reason"], "untrusted_preparer");
+        let online = online.with_preparer_key(preparer_key);
+        let resp = online.submit(&prepared).await.unwrap();
+        assert_eq!(resp.status, "confirmed");
+        // The proof generated offline was reused
+        assert_eq!(online.metrics_snapshot().proof_pool.completed, 0);
+
+        let mut tampered = prepared.clone();
+        tampered.request.amount = "1000".to_string();
+        assert_eq!(online.submit(&tampered).await.unwrap_err().code, ErrorCode::InvalidRequest);
+
+        req.reference_id = "ref_offline_other".to_string();
+        assert_eq!(offline.prepare(&req, &bundle).await.unwrap_err().code, ErrorCode::InvalidRequest);
+        let expired = QuoteBundle { expires_at_ms: 0, ..online.fetch_quote_bundle(&req, Duration::from_secs(60)).await.unwrap() };
+        assert_eq!(offline.prepare(&req, &expired).await.unwrap_err().code, ErrorCode::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_self_broadcast() {
+        use crate::assets::{AssetConfig, AssetRegistry};
+        use crate::chain::MockChainAdapter;
+        use crate::contracts::Deployment;
+        use crate::evm::permit::UNLIMITED;
+        use crate::exactly_once::InMemorySubmissionStore;
+        use k256::SecretKey;
+
+        let mut config = SdkConfig::default_config();
+        config.assets = AssetRegistry::new().with_asset(
+            "USDC",
+            AssetConfig {
+                decimals: Some(6),
+                ..Default::default()
+            },
+        );
+        let deployment = Deployment::new(ChainId::Base, "0x5FbDB2315678afecb367f032d93F642f64180aa3")
+            .with_token("USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+        let adapter = Arc::new(MockChainAdapter::new(ChainId::Base));
+        adapter.set_auto_confirm(false);
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_contracts(Deployments::new().with_deployment(deployment))
+            .with_chain_adapter(adapter.clone())
+            .with_exactly_once(ExactlyOnceGuard::new(Arc::new(InMemorySubmissionStore::new())));
+        let mut req = TransactionRequest::new("ref_self_broadcast", IntentType::Transfer, "40", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        let broadcast = SelfBroadcast::new(7, 150_000, 2_000_000_000, 1_000_000_000).with_approval(UNLIMITED);
+
+        let err = client.execute_self_broadcast(&req, broadcast.clone()).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::SignerUnavailable);
+
+        let signer = TransactionSigner::new(SecretKey::from_bytes(&[6u8; 32].into()).unwrap());
+        let from = crate::evm::address(&signer.verifying_key());
+        let client = client.with_broadcast_signer(signer);
+        let signed = client.execute_self_broadcast(&req, broadcast.clone()).await.unwrap();
+        assert_eq!((signed.chain_id, signed.transactions.len()), (8453, 2));
+        assert!(signed.transactions.iter().all(|tx| tx.from == from));
+        assert_eq!(signed.call.function, "settle(bytes32,bytes32,address,address,uint256)");
+        // Replays return the same transactions instead of signing new ones
+        assert_eq!(client.execute_self_broadcast(&req, broadcast.clone()).await.unwrap(), signed);
+
+        for tx in &signed.transactions {
+            adapter.broadcast_raw_transaction(&tx.raw).await.unwrap();
+        }
+        let resp = client.confirm_external_broadcast(signed.tx_hash()).await.unwrap();
+        assert_eq!(resp.status, "pending");
+        adapter.insert_receipt(TxReceipt {
+            tx_hash: signed.tx_hash().to_string(),
+            block_height: 42,
+            success: true,
+            fee_paid: "21000000000000".to_string(),
+        });
+        let resp = client.confirm_external_broadcast(signed.tx_hash()).await.unwrap();
+        assert_eq!((resp.status.as_str(), resp.block_height), ("confirmed", 42));
+        assert_eq!(resp.fee_used, "0.000021 ETH");
+        assert_eq!(resp.correlation_id, signed.correlation_id);
+        assert!(client.confirm_external_broadcast(signed.tx_hash()).await.is_err());
+
+        // The reference now replays the confirmed response
+        let err = client.execute_self_broadcast(&req, broadcast.clone()).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::DuplicateReference);
+        assert_eq!(client.execute_transaction(&req).await.unwrap(), resp);
+
+        req.reference_id = "ref_self_broadcast_shielded".to_string();
+        req.is_shielded = true;
+        let err = client.execute_self_broadcast(&req, broadcast.clone()).await.unwrap_err();
+        assert!(err.message.contains("can't be self-broadcast"));
+
+        // A revert has its own error code and frees the reference to be signed again
+        req.reference_id = "ref_self_broadcast_reverted".to_string();
+        req.is_shielded = false;
+        let signed = client.execute_self_broadcast(&req, broadcast.clone()).await.unwrap();
+        adapter.insert_receipt(TxReceipt {
+            tx_hash: signed.tx_hash().to_string(),
+            block_height: 43,
+            success: false,
+            fee_paid: "21000000000000".to_string(),
+        });
+        let err = client.confirm_external_broadcast(signed.tx_hash()).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExecutionReverted);
+        assert!(!err.is_retryable());
+        assert_eq!(err.details["tx_hash"], signed.tx_hash());
+        assert!(client.execute_self_broadcast(&req, broadcast).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_htlc_reveal_and_expiry() {
+        use crate::crypto::hash::HashFunction;
+        use crate::escrow::{self, InMemoryEscrowStore};
+
+        let mut req = TransactionRequest::new("ref_htlc", IntentType::Escrow, "75", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        let secret = escrow::generate_secret();
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        let terms = EscrowTerms::htlc(escrow::hashlock(&secret, HashFunction::Sha256), now + 3600);
+        terms.attach(&mut req);
+
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_escrows(EscrowManager::new(Arc::new(InMemoryEscrowStore::new())));
+        assert_eq!(client.execute_transaction(&req).await.unwrap().status, "escrowed");
+        let err = client.reveal_secret("ref_htlc", b"wrong").await.unwrap_err();
+        assert_eq!(err.details["reason"], "escrow_condition_unmet");
+        let released = client.reveal_secret("ref_htlc", &secret).await.unwrap();
+        assert_eq!(released.state, EscrowState::Released);
+        assert_eq!(released.secret, Some(format!("0x{}", hex::encode(secret))));
+
+        // Past the timelock, the funds go back to the payer
+        req.reference_id = "ref_htlc_expired".to_string();
+        let expired = EscrowTerms { refund_after: Some(now - 1), ..terms };
+        let escrow = Escrow::new(&req, expired, "agent-001", "0xlock").unwrap();
+        client.escrows.as_ref().unwrap().record_lock(escrow).unwrap();
+        let refunded = client.refund_expired_escrows().await.unwrap();
+        assert_eq!(refunded.len(), 1);
+        assert_eq!((refunded[0].escrow_id.as_str(), refunded[0].state), ("ref_htlc_expired", EscrowState::Refunded));
+        assert!(client.refund_expired_escrows().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refund_settled_payment() {
+        use crate::refunds::{InMemorySettlementStore, RefundState, Settlement};
+
+        let refunds = RefundManager::new(Arc::new(InMemorySettlementStore::new()));
+        refunds
+            .record_settlement(Settlement::new(
+                "order_7",
+                "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0",
+                "USDC",
+                "100.00",
+                ChainId::Base,
+                "0xpaid",
+            ))
+            .unwrap();
+        let client = EasyCashClient::new(None).unwrap().with_refunds(refunds);
+
+        let resp = client.refund("order_7", "30").await.unwrap();
+        assert_eq!(resp.status, "confirmed");
+        let err = client.refund("order_7", "70.01").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        client.refund("order_7", "70").await.unwrap();
+
+        let settlement = client.refunds.as_ref().unwrap().settlement("order_7").unwrap().unwrap();
+        let refs: Vec<_> = settlement.refunds.iter().map(|r| r.reference_id.as_str()).collect();
+        assert_eq!(refs, ["order_7-refund-1", "order_7-refund-2"]);
+        assert!(settlement.refunds.iter().all(|r| r.state == RefundState::Completed));
+        assert_eq!(settlement.refunds[0].tx_hash.as_deref(), Some(resp.tx_hash.as_str()));
+
+        let unconfigured = EasyCashClient::new(None).unwrap();
+        assert_eq!(unconfigured.refund("order_7", "1").await.unwrap_err().code, ErrorCode::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_stuck_transaction() {
+        use crate::chain::{MockChainAdapter, TxReceipt};
+        use crate::exactly_once::{ExactlyOnceGuard, InMemorySubmissionStore};
+
+        /// Replaces transactions itself, or returns them signed when `signed`
+        struct Replacing {
+            signed: bool,
+            requests: std::sync::Mutex<Vec<SpeedUpRequest>>,
+        }
+        #[async_trait::async_trait]
+        impl AgentNegotiatorTrait for Replacing {
+            async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+                Ok(vec![RouteQuote {
+                    agent_id: "agent-001".to_string(),
+                    estimated_fee: "0.05 USDC".to_string(),
+                    estimated_time: Duration::from_secs(1),
+                    route: vec!["base".to_string()],
+                    security_score: 0.9,
+                    estimated_fee_usd: None,
+                    signature: None,
+                    agent_pubkey: None,
+                    region: None,
+                }])
+            }
+
+            async fn execute(&self, _req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+                Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+            }
+
+            async fn speed_up(&self, req: &SpeedUpRequest) -> std::result::Result<Replacement, String> {
+                self.requests.lock().unwrap().push(req.clone());
+                Ok(match self.signed {
+                    true => Replacement::Signed { raw_tx: "0x02f8".to_string() },
+                    false => Replacement::Broadcast { tx_hash: format!("{}-replaced", req.tx_hash) },
+                })
+            }
+
+            fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+                crate::agent::select_best_route(quotes, preference)
+            }
+        }
+
+        let mut req = TransactionRequest::new("ref_stuck", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        let store = Arc::new(InMemorySubmissionStore::new());
+        let negotiator = Arc::new(Replacing { signed: false, requests: Default::default() });
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config.clone()))
+            .unwrap()
+            .with_negotiator(negotiator.clone())
+            .with_exactly_once(ExactlyOnceGuard::new(store.clone()));
+        let resp = client.execute_transaction(&req).await.unwrap();
+
+        let err = client.speed_up(&resp.tx_hash, "1.5").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidRequest);
+        assert!(client.speed_up("0xunknown", "30000000000").await.unwrap_err().message.contains("unknown transaction"));
+
+        let replacement = client.speed_up(&resp.tx_hash, "30000000000").await.unwrap();
+        assert_eq!(replacement, format!("{}-replaced", resp.tx_hash));
+        let sent = negotiator.requests.lock().unwrap()[0].clone();
+        assert_eq!((sent.agent_id.as_str(), sent.max_fee_per_gas.as_str()), ("agent-001", "30000000000"));
+        // Replays return the replacement, and the original can't be replaced twice
+        assert_eq!(client.execute_transaction(&req).await.unwrap().tx_hash, replacement);
+        assert!(client.speed_up(&resp.tx_hash, "40000000000").await.unwrap_err().message.contains("already replaced"));
+
+        // Signed replacements are broadcast through the chain adapter, once the
+        // original is known to be unconfirmed
+        let adapter = Arc::new(MockChainAdapter::new(ChainId::Base));
+        adapter.set_auto_confirm(false);
+        let negotiator = Arc::new(Replacing { signed: true, requests: Default::default() });
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_negotiator(negotiator.clone())
+            .with_chain_adapter(adapter.clone());
+        let resp = client.execute_transaction(&req).await.unwrap();
+        let replacement = client.speed_up(&resp.tx_hash, "30000000000").await.unwrap();
+        assert_eq!(adapter.broadcasts(), ["0x02f8"]);
+        adapter.insert_receipt(TxReceipt {
+            tx_hash: replacement.clone(),
+            block_height: 2,
+            success: true,
+            fee_paid: "0".to_string(),
+        });
+        let err = client.speed_up(&replacement, "40000000000").await.unwrap_err();
+        assert!(err.message.contains("already confirmed"));
+
+        req.reference_id = "ref_solana".to_string();
+        req.source_chain = ChainId::Solana;
+        req.recipient = Some("7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV".to_string());
+        let resp = client.execute_transaction(&req).await.unwrap();
+        let err = client.speed_up(&resp.tx_hash, "30000000000").await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::UnsupportedChain);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_open_breaker() {
+        let client = EasyCashClient::new(None).unwrap();
+        assert!(client.health_check().healthy);
+
+        for _ in 0..client.config.circuit_breaker.minimum_calls {
+            client.breaker.record_failure();
+        }
+        let health = client.health_check();
+        assert!(!health.healthy);
+        assert_eq!(health.circuit_breaker, CircuitState::Open);
     }
 }
@@ -0,0 +1,315 @@
+//! Compliance screening (sanctions lists, denylists) run before execution.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use crate::types::{ChainId, TransactionRequest};
+
+/// Data submitted to a screening provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScreeningRequest {
+    #[serde(rename = "reference_id")]
+    pub reference_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    pub amount: String,
+    pub asset: String,
+    pub chain: ChainId,
+    #[serde(rename = "target_chain", skip_serializing_if = "Option::is_none")]
+    pub target_chain: Option<ChainId>,
+}
+
+impl From<&TransactionRequest> for ScreeningRequest {
+    fn from(req: &TransactionRequest) -> Self {
+        Self {
+            reference_id: req.reference_id.clone(),
+            recipient: req.recipient.clone(),
+            amount: req.amount.clone(),
+            asset: req.asset.clone(),
+            chain: req.source_chain,
+            target_chain: req.target_chain,
+        }
+    }
+}
+
+impl ScreeningRequest {
+    /// Screening of one payee of `req` (a disbursement leg or a fee split
+    /// recipient), paid `amount`
+    pub fn for_payee(req: &TransactionRequest, recipient: &str, amount: &str) -> Self {
+        Self {
+            recipient: Some(recipient.to_string()),
+            amount: amount.to_string(),
+            ..Self::from(req)
+        }
+    }
+}
+
+/// Outcome of a screening check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "lowercase")]
+pub enum ScreeningDecision {
+    Approved,
+    Rejected { reason: String },
+}
+
+impl ScreeningDecision {
+    pub fn is_approved(&self) -> bool {
+        matches!(self, ScreeningDecision::Approved)
+    }
+}
+
+/// Trait for compliance screening providers.
+///
+/// Providers return `Err` when screening could not be performed (e.g. the
+/// screening service is down); the client treats that as a rejection.
+#[async_trait::async_trait]
+pub trait ScreeningProvider: Send + Sync {
+    /// Screens a transaction before it is executed
+    async fn screen(&self, req: &ScreeningRequest) -> Result<ScreeningDecision, String>;
+}
+
+/// In-memory denylist of recipient addresses, assets, and chains.
+///
+/// Address matching is case-insensitive.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::compliance::DenylistScreeningProvider;
+///
+/// let provider = DenylistScreeningProvider::new();
+/// provider.deny_address("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+/// assert!(provider.is_address_denied("0x742d35cc6634c0532925a3b844bc9e7595f0beb0"));
+/// ```
+#[derive(Default)]
+pub struct DenylistScreeningProvider {
+    addresses: RwLock<HashSet<String>>,
+    assets: RwLock<HashSet<String>>,
+    chains: RwLock<HashSet<ChainId>>,
+}
+
+impl DenylistScreeningProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Blocks transfers to the given address
+    pub fn deny_address(&self, address: &str) {
+        if let Ok(mut set) = self.addresses.write() {
+            set.insert(address.to_lowercase());
+        }
+    }
+
+    /// Removes an address from the denylist
+    pub fn allow_address(&self, address: &str) {
+        if let Ok(mut set) = self.addresses.write() {
+            set.remove(&address.to_lowercase());
+        }
+    }
+
+    /// Blocks all transfers of the given asset
+    pub fn deny_asset(&self, asset: &str) {
+        if let Ok(mut set) = self.assets.write() {
+            set.insert(asset.to_uppercase());
+        }
+    }
+
+    /// Blocks all transfers touching the given chain
+    pub fn deny_chain(&self, chain: ChainId) {
+        if let Ok(mut set) = self.chains.write() {
+            set.insert(chain);
+        }
+    }
+
+    /// Returns true if the address is on the denylist
+    pub fn is_address_denied(&self, address: &str) -> bool {
+        self.addresses
+            .read()
+            .map(|set| set.contains(&address.to_lowercase()))
+            .unwrap_or(false)
+    }
+
+    fn check(&self, req: &ScreeningRequest) -> ScreeningDecision {
+        if let Some(ref recipient) = req.recipient {
+            if self.is_address_denied(recipient) {
+                return ScreeningDecision::Rejected {
+                    reason: format!("recipient {} is on the denylist", recipient),
+                };
+            }
+        }
+
+        let asset_denied = self
+            .assets
+            .read()
+            .map(|set| set.contains(&req.asset.to_uppercase()))
+            .unwrap_or(false);
+        if asset_denied {
+            return ScreeningDecision::Rejected {
+                reason: format!("asset {} is on the denylist", req.asset),
+            };
+        }
+
+        if let Ok(chains) = self.chains.read() {
+            for chain in std::iter::once(req.chain).chain(req.target_chain) {
+                if chains.contains(&chain) {
+                    return ScreeningDecision::Rejected {
+                        reason: format!("chain {} is on the denylist", chain),
+                    };
+                }
+            }
+        }
+
+        ScreeningDecision::Approved
+    }
+}
+
+#[async_trait::async_trait]
+impl ScreeningProvider for DenylistScreeningProvider {
+    async fn screen(&self, req: &ScreeningRequest) -> Result<ScreeningDecision, String> {
+        Ok(self.check(req))
+    }
+}
+
+/// Screening provider backed by an external HTTP screening service.
+///
+/// POSTs the `ScreeningRequest` as JSON and expects a `ScreeningDecision`
+/// (`{"decision": "approved"}` or `{"decision": "rejected", "reason": "..."}`).
+#[cfg(feature = "compliance-http")]
+pub struct HttpScreeningProvider {
+    endpoint: String,
+    api_key: Option<crate::secrets::SecretString>,
+    timeout: std::time::Duration,
+    http: crate::http::HttpClient,
+}
+
+#[cfg(feature = "compliance-http")]
+impl HttpScreeningProvider {
+    /// Creates a provider posting to `endpoint`
+    pub fn new(endpoint: impl Into<String>, timeout: std::time::Duration) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            api_key: None,
+            timeout,
+            http: crate::http::HttpClient::default(),
+        }
+    }
+
+    /// Sends the key as a bearer token with every request
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into().into());
+        self
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: crate::http::HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[cfg(feature = "compliance-http")]
+#[async_trait::async_trait]
+impl ScreeningProvider for HttpScreeningProvider {
+    async fn screen(&self, req: &ScreeningRequest) -> Result<ScreeningDecision, String> {
+        let body = serde_json::to_value(req).map_err(|e| format!("failed to encode request: {}", e))?;
+        let auth = self.api_key.as_ref().map(|k| format!("Bearer {}", k.expose()));
+        let headers: Vec<(&str, &str)> = auth.iter().map(|a| ("Authorization", a.as_str())).collect();
+
+        let resp = self.http.post_json(&self.endpoint, &headers, &body, self.timeout).await?;
+        if !resp.is_success() {
+            return Err(format!("screening service returned status {}", resp.status));
+        }
+        serde_json::from_str(&resp.body).map_err(|e| format!("invalid screening response: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::IntentType;
+
+    fn screening_request(recipient: Option<&str>) -> ScreeningRequest {
+        ScreeningRequest::from(&TransactionRequest {
+            recipient: recipient.map(|r| r.to_string()),
+            ..TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+                .with_target_chain(ChainId::Ethereum)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_denylist_rejects_address_case_insensitive() {
+        let provider = DenylistScreeningProvider::new();
+        provider.deny_address("0x742D35CC6634C0532925A3B844BC9E7595F0BEB0");
+
+        let req = screening_request(Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0"));
+        let decision = provider.screen(&req).await.unwrap();
+        assert!(!decision.is_approved());
+    }
+
+    #[tokio::test]
+    async fn test_denylist_approves_clean_request() {
+        let provider = DenylistScreeningProvider::new();
+        provider.deny_address("0x0000000000000000000000000000000000000001");
+
+        let req = screening_request(Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0"));
+        assert_eq!(provider.screen(&req).await.unwrap(), ScreeningDecision::Approved);
+    }
+
+    #[tokio::test]
+    async fn test_denylist_rejects_asset_and_target_chain() {
+        let provider = DenylistScreeningProvider::new();
+        provider.deny_chain(ChainId::Ethereum);
+        let decision = provider.screen(&screening_request(None)).await.unwrap();
+        assert!(matches!(decision, ScreeningDecision::Rejected { reason } if reason.contains("ethereum")));
+
+        let provider = DenylistScreeningProvider::new();
+        provider.deny_asset("usdc");
+        assert!(!provider.screen(&screening_request(None)).await.unwrap().is_approved());
+    }
+
+    #[test]
+    fn test_allow_address_removes_entry() {
+        let provider = DenylistScreeningProvider::new();
+        provider.deny_address("0xabc");
+        provider.allow_address("0xABC");
+        assert!(!provider.is_address_denied("0xabc"));
+    }
+
+    #[cfg(feature = "compliance-http")]
+    #[tokio::test]
+    async fn test_http_provider_parses_rejection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            let body = r#"{"decision":"rejected","reason":"sanctioned entity"}"#;
+            let resp = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+            socket.write_all(resp.as_bytes()).await.unwrap();
+        });
+
+        let provider = HttpScreeningProvider::new(
+            format!("http://{}/screen", addr),
+            std::time::Duration::from_secs(2),
+        );
+        let decision = provider.screen(&screening_request(None)).await.unwrap();
+        assert_eq!(
+            decision,
+            ScreeningDecision::Rejected {
+                reason: "sanctioned entity".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_decision_serialization() {
+        let json = serde_json::to_string(&ScreeningDecision::Rejected {
+            reason: "sanctioned".to_string(),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"decision":"rejected","reason":"sanctioned"}"#);
+    }
+}
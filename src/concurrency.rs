@@ -0,0 +1,291 @@
+//! Admission control for in-flight transactions.
+//!
+//! Caps the number of concurrently executing transactions and queues the rest,
+//! applying backpressure instead of letting load pile up on the agent network.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Configuration for the concurrency limiter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencyLimiterConfig {
+    /// Maximum number of transactions executing at the same time
+    pub max_in_flight: u32,
+    /// Maximum number of callers allowed to wait for a slot
+    pub max_queue_depth: u32,
+    /// Maximum time a caller waits for a slot before giving up
+    pub queue_timeout: Duration,
+    /// Whether to enable admission control
+    pub enabled: bool,
+}
+
+impl Default for ConcurrencyLimiterConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 64,
+            max_queue_depth: 1024,
+            queue_timeout: Duration::from_secs(10),
+            enabled: true,
+        }
+    }
+}
+
+/// Slot held for the duration of a transaction; released on drop.
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// Place in the wait queue, given up on drop
+struct QueueSlot<'a>(&'a AtomicU64);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Semaphore-based concurrency limiter with a bounded wait queue.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::concurrency::{ConcurrencyLimiter, ConcurrencyLimiterConfig};
+///
+/// # tokio_test::block_on(async {
+/// let limiter = ConcurrencyLimiter::new(ConcurrencyLimiterConfig::default());
+/// let permit = limiter.acquire().await.expect("admission rejected");
+/// assert_eq!(limiter.in_flight(), 1);
+/// drop(permit);
+/// # });
+/// ```
+pub struct ConcurrencyLimiter {
+    config: ConcurrencyLimiterConfig,
+    semaphore: Arc<Semaphore>,
+    queue_depth: AtomicU64,
+    total_wait_us: AtomicU64,
+    admitted: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl ConcurrencyLimiter {
+    /// Creates a new concurrency limiter with the given configuration.
+    pub fn new(config: ConcurrencyLimiterConfig) -> Self {
+        let permits = config.max_in_flight.max(1) as usize;
+        Self {
+            config,
+            semaphore: Arc::new(Semaphore::new(permits)),
+            queue_depth: AtomicU64::new(0),
+            total_wait_us: AtomicU64::new(0),
+            admitted: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a disabled limiter (admits every call immediately).
+    pub fn disabled() -> Self {
+        Self::new(ConcurrencyLimiterConfig {
+            enabled: false,
+            ..Default::default()
+        })
+    }
+
+    /// Waits for an execution slot.
+    ///
+    /// Fails immediately if the wait queue is full, or after `queue_timeout`
+    /// if no slot became available.
+    pub async fn acquire(&self) -> Result<ConcurrencyPermit, String> {
+        if !self.config.enabled {
+            return Ok(ConcurrencyPermit { _permit: None });
+        }
+
+        // Fast path: a slot is free
+        if let Ok(permit) = self.semaphore.clone().try_acquire_owned() {
+            self.admitted.fetch_add(1, Ordering::Relaxed);
+            return Ok(ConcurrencyPermit {
+                _permit: Some(permit),
+            });
+        }
+
+        let depth = self.queue_depth.fetch_add(1, Ordering::SeqCst);
+        // Leaves the queue however the wait ends, including the caller
+        // dropping this future
+        let queued = QueueSlot(&self.queue_depth);
+        if depth >= self.config.max_queue_depth as u64 {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(format!(
+                "admission queue full: {} callers waiting",
+                self.config.max_queue_depth
+            ));
+        }
+
+        let start = Instant::now();
+        let result =
+            tokio::time::timeout(self.config.queue_timeout, self.semaphore.clone().acquire_owned())
+                .await;
+        drop(queued);
+        self.total_wait_us
+            .fetch_add(start.elapsed().as_micros() as u64, Ordering::Relaxed);
+
+        match result {
+            Ok(Ok(permit)) => {
+                self.admitted.fetch_add(1, Ordering::Relaxed);
+                Ok(ConcurrencyPermit {
+                    _permit: Some(permit),
+                })
+            }
+            Ok(Err(_)) => {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                Err("concurrency limiter closed".to_string())
+            }
+            Err(_) => {
+                self.rejected.fetch_add(1, Ordering::Relaxed);
+                Err(format!(
+                    "timed out after {:?} waiting for an execution slot",
+                    self.config.queue_timeout
+                ))
+            }
+        }
+    }
+
+    /// Returns the number of transactions currently holding a slot.
+    pub fn in_flight(&self) -> u64 {
+        if !self.config.enabled {
+            return 0;
+        }
+        let max = self.config.max_in_flight.max(1) as u64;
+        max.saturating_sub(self.semaphore.available_permits() as u64)
+    }
+
+    /// Returns the number of callers currently waiting for a slot.
+    pub fn queue_depth(&self) -> u64 {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of calls rejected because of a full queue or timeout.
+    pub fn rejected_count(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    /// Returns the average time admitted callers spent waiting, in milliseconds.
+    pub fn average_wait_ms(&self) -> f64 {
+        let admitted = self.admitted.load(Ordering::Relaxed);
+        if admitted == 0 {
+            return 0.0;
+        }
+        self.total_wait_us.load(Ordering::Relaxed) as f64 / 1000.0 / admitted as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(max_in_flight: u32, max_queue_depth: u32) -> ConcurrencyLimiterConfig {
+        ConcurrencyLimiterConfig {
+            max_in_flight,
+            max_queue_depth,
+            queue_timeout: Duration::from_millis(50),
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limiter_admits_within_capacity() {
+        let limiter = ConcurrencyLimiter::new(test_config(2, 10));
+        let _a = limiter.acquire().await.unwrap();
+        let _b = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.in_flight(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_releases_on_drop() {
+        let limiter = ConcurrencyLimiter::new(test_config(1, 10));
+        let permit = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.in_flight(), 1);
+        drop(permit);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_queue_timeout() {
+        let limiter = ConcurrencyLimiter::new(test_config(1, 10));
+        let _held = limiter.acquire().await.unwrap();
+
+        let result = limiter.acquire().await;
+        assert!(result.unwrap_err().contains("timed out"));
+        assert_eq!(limiter.rejected_count(), 1);
+        assert_eq!(limiter.queue_depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_queue_full() {
+        let limiter = ConcurrencyLimiter::new(test_config(1, 0));
+        let _held = limiter.acquire().await.unwrap();
+
+        let result = limiter.acquire().await;
+        assert!(result.unwrap_err().contains("queue full"));
+    }
+
+    #[tokio::test]
+    async fn test_limiter_waiter_admitted_after_release() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(ConcurrencyLimiterConfig {
+            queue_timeout: Duration::from_secs(1),
+            ..test_config(1, 10)
+        }));
+        let held = limiter.acquire().await.unwrap();
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await.map(|_| ()) })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(limiter.queue_depth(), 1);
+        drop(held);
+
+        assert!(waiter.await.unwrap().is_ok());
+        assert!(limiter.average_wait_ms() > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_limiter_cancelled_waiter_leaves_queue() {
+        let limiter = Arc::new(ConcurrencyLimiter::new(ConcurrencyLimiterConfig {
+            queue_timeout: Duration::from_secs(10),
+            ..test_config(1, 1)
+        }));
+        let held = limiter.acquire().await.unwrap();
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await.map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(limiter.queue_depth(), 1);
+
+        waiter.abort();
+        assert!(waiter.await.unwrap_err().is_cancelled());
+        assert_eq!(limiter.queue_depth(), 0);
+
+        // The freed place in the queue can be taken again
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await.map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(held);
+        assert!(waiter.await.unwrap().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_limiter_disabled() {
+        let limiter = ConcurrencyLimiter::disabled();
+        let mut permits = Vec::new();
+        for _ in 0..200 {
+            permits.push(limiter.acquire().await.unwrap());
+        }
+        assert_eq!(limiter.in_flight(), 0);
+    }
+}
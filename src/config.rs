@@ -1,15 +1,35 @@
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use crate::assets::AssetRegistry;
+use crate::agent::balancing::LoadBalancingConfig;
+use crate::agent::sla::SlaConfig;
+use crate::cache::CacheExpiryConfig;
+use crate::circuit_breaker::CircuitBreakerConfig;
+use crate::concurrency::ConcurrencyLimiterConfig;
+use crate::crypto::hash::HashConfig;
+use crate::environment::Environment;
+use crate::network::NetworkConfig;
+use crate::redaction::RedactionConfig;
+use crate::request_signing::RequestSigningConfig;
+use crate::residency::ResidencyConfig;
+use crate::secrets::SecretString;
+use crate::tls::TlsConfig;
+use crate::travel_rule::TravelRuleConfig;
+use crate::validator::ValidationConfig;
+use crate::zk::artifacts::ArtifactConfig;
+use crate::zk::pool::ProofPoolConfig;
+
 /// Global configuration for the SDK
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SdkConfig {
     /// API Configuration
     #[serde(rename = "api_endpoint")]
     pub api_endpoint: String,
-    #[serde(rename = "api_key")]
-    pub api_key: String,
-    pub environment: String, // "mainnet" | "testnet" | "devnet"
+    /// Never serialized; read from `ECASH_API_KEY` unless given explicitly
+    #[serde(rename = "api_key", skip_serializing, default = "default_api_key")]
+    pub api_key: SecretString,
+    pub environment: String, // "mainnet" | "testnet" | "devnet" (see `env`)
 
     /// Network Configuration
     pub timeout: Duration,
@@ -23,6 +43,15 @@ pub struct SdkConfig {
     pub enable_zk_proofs: bool,
     #[serde(rename = "proof_cache_ttl")]
     pub proof_cache_ttl: Duration,
+    /// Threads and queue depth of the proof worker pool
+    #[serde(default)]
+    pub proof_pool: ProofPoolConfig,
+    /// Registry, pinned version and cache of proving keys and circuit WASM
+    #[serde(default)]
+    pub zk_artifacts: ArtifactConfig,
+    /// Hash functions of note commitments and receipt signing
+    #[serde(default)]
+    pub hashes: HashConfig,
 
     /// Performance Configuration
     #[serde(rename = "enable_metrics")]
@@ -31,14 +60,65 @@ pub struct SdkConfig {
     pub enable_caching: bool,
     #[serde(rename = "cache_ttl")]
     pub cache_ttl: Duration,
+    #[serde(default)]
+    pub cache_expiry: CacheExpiryConfig,
+
+    /// Resilience Configuration
+    #[serde(rename = "circuit_breaker")]
+    pub circuit_breaker: CircuitBreakerConfig,
+    pub concurrency: ConcurrencyLimiterConfig,
+    /// Agent SLA tracking and penalization
+    #[serde(default)]
+    pub sla: SlaConfig,
+    /// Tie-breaking between equally ranked routes
+    #[serde(default)]
+    pub load_balancing: LoadBalancingConfig,
+
+    /// Compliance Configuration
+    #[serde(rename = "travel_rule")]
+    pub travel_rule: TravelRuleConfig,
+    /// Refuse transfers to recipients missing from the client's address book
+    #[serde(default)]
+    pub require_allowlisted_recipients: bool,
+    /// Strict mode: discard quotes not signed with the key the agent
+    /// directory publishes for their agent. Quotes with an invalid signature
+    /// are discarded either way
+    #[serde(default)]
+    pub require_signed_quotes: bool,
+    /// Jurisdictions agents must execute from
+    #[serde(default)]
+    pub residency: ResidencyConfig,
+
+    /// Request Validation Configuration
+    #[serde(default)]
+    pub validation: ValidationConfig,
+    /// Per-asset decimals, minimums and dust thresholds
+    #[serde(default)]
+    pub assets: AssetRegistry,
+
+    /// Logging Configuration
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+
+    /// TLS Configuration (applied to the client's `HttpClient`)
+    #[serde(default)]
+    pub tls: TlsConfig,
+
+    /// Proxy and DNS Configuration (applied to the client's `HttpClient`)
+    #[serde(default)]
+    pub network: NetworkConfig,
+
+    /// Request Signing Configuration (applied to the client's `HttpClient`)
+    #[serde(skip_serializing, default)]
+    pub request_signing: RequestSigningConfig,
 }
 
 impl Default for SdkConfig {
     fn default() -> Self {
         Self {
             api_endpoint: std::env::var("ECASH_API_ENDPOINT")
-                .unwrap_or_else(|_| "https://api.useeasy.cash".to_string()),
-            api_key: std::env::var("ECASH_API_KEY").unwrap_or_default(),
+                .unwrap_or_else(|_| Environment::Mainnet.api_endpoint().to_string()),
+            api_key: default_api_key(),
             environment: std::env::var("ECASH_ENV")
                 .unwrap_or_else(|_| "mainnet".to_string()),
             timeout: Duration::from_secs(30),
@@ -46,22 +126,80 @@ impl Default for SdkConfig {
             retry_backoff: Duration::from_secs(2),
             enable_zk_proofs: true,
             proof_cache_ttl: Duration::from_secs(300), // 5 minutes
+            proof_pool: ProofPoolConfig::default(),
+            zk_artifacts: ArtifactConfig::default(),
+            hashes: HashConfig::default(),
             enable_metrics: true,
             enable_caching: true,
             cache_ttl: Duration::from_secs(60), // 1 minute
+            cache_expiry: CacheExpiryConfig::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            concurrency: ConcurrencyLimiterConfig::default(),
+            sla: SlaConfig::default(),
+            load_balancing: LoadBalancingConfig::default(),
+            travel_rule: TravelRuleConfig::default(),
+            require_allowlisted_recipients: false,
+            require_signed_quotes: false,
+            residency: ResidencyConfig::default(),
+            validation: ValidationConfig::default(),
+            assets: AssetRegistry::default(),
+            redaction: RedactionConfig::default(),
+            tls: TlsConfig::default(),
+            network: NetworkConfig::default(),
+            request_signing: RequestSigningConfig::default(),
         }
     }
 }
 
+fn default_api_key() -> SecretString {
+    std::env::var("ECASH_API_KEY").unwrap_or_default().into()
+}
+
 impl SdkConfig {
     /// Returns sensible defaults
     pub fn default_config() -> Self {
         Self::default()
     }
     
+    /// Parses a JSON object whose top-level keys override the defaults
+    /// (e.g. `{"api_key": "...", "environment": "testnet"}`)
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let overrides: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let overrides = overrides.as_object().ok_or("config must be a JSON object")?;
+        let mut config = serde_json::to_value(Self::default()).map_err(|e| e.to_string())?;
+        if let Some(fields) = config.as_object_mut() {
+            for (key, value) in overrides {
+                fields.insert(key.clone(), value.clone());
+            }
+        }
+        serde_json::from_value(config).map_err(|e| e.to_string())
+    }
+
+    /// Reads a JSON config file (see `from_json`)
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    /// Parsed `environment`; mainnet if it is invalid (which `validate`
+    /// rejects)
+    pub fn env(&self) -> Environment {
+        self.environment.parse().unwrap_or(Environment::Mainnet)
+    }
+
+    /// API endpoint in effect: `api_endpoint`, or the environment's own
+    /// endpoint while `api_endpoint` is left at the mainnet default
+    pub fn endpoint(&self) -> &str {
+        match self.api_endpoint == Environment::Mainnet.api_endpoint() {
+            true => self.env().api_endpoint(),
+            false => &self.api_endpoint,
+        }
+    }
+
     /// Sets the API key
     pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = api_key.into();
+        self.api_key = SecretString::from(api_key.into());
         self
     }
 
@@ -73,15 +211,39 @@ impl SdkConfig {
         if self.cache_ttl.as_secs() == 0 {
             return Err("cache_ttl must be greater than 0".to_string());
         }
+        self.cache_expiry.validate()?;
+        self.validation.validate()?;
+        self.assets.validate()?;
         if self.proof_cache_ttl.as_secs() == 0 {
             return Err("proof_cache_ttl must be greater than 0".to_string());
         }
-        if !matches!(self.environment.as_str(), "mainnet" | "testnet" | "devnet") {
-            return Err(format!("invalid environment: {} (must be mainnet, testnet, or devnet)", self.environment));
-        }
+        self.environment.parse::<Environment>()?;
         if self.max_retries == 0 {
             return Err("max_retries must be greater than 0".to_string());
         }
+        self.tls.validate()?;
+        self.network.validate()?;
+        self.request_signing.validate()?;
+        self.residency.validate()?;
+        let threshold = self.circuit_breaker.failure_rate_threshold;
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err("circuit_breaker.failure_rate_threshold must be in (0, 1]".to_string());
+        }
+        if self.circuit_breaker.half_open_max_calls == 0 {
+            return Err("circuit_breaker.half_open_max_calls must be greater than 0".to_string());
+        }
+        if self.concurrency.max_in_flight == 0 {
+            return Err("concurrency.max_in_flight must be greater than 0".to_string());
+        }
+        if self.sla.time_tolerance.is_nan() || self.sla.time_tolerance < 0.0 {
+            return Err("sla.time_tolerance must not be negative".to_string());
+        }
+        if self.sla.max_misses == 0 {
+            return Err("sla.max_misses must be greater than 0".to_string());
+        }
+        if self.proof_pool.queue_depth == 0 {
+            return Err("proof_pool.queue_depth must be greater than 0".to_string());
+        }
         Ok(())
     }
 }
@@ -102,7 +264,25 @@ mod tests {
     #[test]
     fn test_config_with_api_key() {
         let config = SdkConfig::default_config().with_api_key("test_key");
-        assert_eq!(config.api_key, "test_key");
+        assert_eq!(config.api_key.expose(), "test_key");
+    }
+
+    #[test]
+    fn test_api_key_is_not_leaked() {
+        let config = SdkConfig::from_json(r#"{"api_key": "sk_live_123"}"#).unwrap();
+        assert_eq!(config.api_key.expose(), "sk_live_123");
+        assert!(!format!("{:?}", config).contains("sk_live_123"));
+        let dumped = serde_json::to_value(&config).unwrap();
+        assert!(dumped.get("api_key").is_none());
+    }
+
+    #[test]
+    fn test_config_from_json_overlays_defaults() {
+        let config = SdkConfig::from_json(r#"{"environment": "testnet", "max_retries": 5}"#).unwrap();
+        assert_eq!(config.environment, "testnet");
+        assert_eq!(config.max_retries, 5);
+        assert!(config.enable_caching);
+        assert!(SdkConfig::from_json("[]").is_err());
     }
 
     #[test]
@@ -127,10 +307,48 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_endpoint_follows_environment() {
+        let mut config = SdkConfig::default_config();
+        config.api_endpoint = Environment::Mainnet.api_endpoint().to_string();
+        config.environment = "devnet".to_string();
+        assert_eq!(config.endpoint(), "https://api.devnet.useeasy.cash");
+        // An explicit endpoint wins
+        config.api_endpoint = "https://easycash.internal".to_string();
+        assert_eq!(config.endpoint(), "https://easycash.internal");
+    }
+
     #[test]
     fn test_config_validate_max_retries() {
         let mut config = SdkConfig::default_config();
         config.max_retries = 0;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_config_validate_circuit_breaker() {
+        let mut config = SdkConfig::default_config();
+        config.circuit_breaker.failure_rate_threshold = 1.5;
+        assert!(config.validate().is_err());
+        config.circuit_breaker.failure_rate_threshold = 0.5;
+        config.circuit_breaker.half_open_max_calls = 0;
+        assert!(config.validate().unwrap_err().contains("half_open_max_calls"));
+    }
+
+    #[test]
+    fn test_config_validate_sla() {
+        let mut config = SdkConfig::from_json(r#"{"sla": {"time_tolerance": 0.2, "max_misses": 0, "cooldown": {"secs": 60, "nanos": 0}, "enabled": true}}"#).unwrap();
+        assert_eq!(config.sla.cooldown, Duration::from_secs(60));
+        assert!(config.validate().unwrap_err().contains("sla.max_misses"));
+        config.sla.max_misses = 1;
+        config.sla.time_tolerance = -0.1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_validate_concurrency() {
+        let mut config = SdkConfig::default_config();
+        config.concurrency.max_in_flight = 0;
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,248 @@
+//! Bindings for the EasyCash settlement and escrow contracts (EVM).
+//!
+//! Each ABI version under `contracts/abi` has a module here (`v1`) with one
+//! struct per contract function; a test keeps the structs in step with the
+//! ABI files. A `Deployment` says where a version is deployed on a chain and
+//! which token contracts it settles. The client builds the call of every
+//! unshielded transfer, disbursement and escrow on a chain with a deployment,
+//! and self-broadcasting integrators sign the same call with `evm::tx`.
+//!
+//! ```
+//! use ecash_sdk_core::contracts::{v1, Deployment};
+//! use ecash_sdk_core::types::ChainId;
+//!
+//! let base = Deployment::new(ChainId::Base, "0x5FbDB2315678afecb367f032d93F642f64180aa3")
+//!     .with_token("USDC", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+//! let call = base
+//!     .call(&v1::Settle {
+//!         reference_id: "order_1001".to_string(),
+//!         intent_hash: [0u8; 32],
+//!         token: base.token("USDC").unwrap().to_string(),
+//!         recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string(),
+//!         amount: "25000000".to_string(),
+//!     })
+//!     .unwrap();
+//! assert_eq!(call.to, base.settlement);
+//! assert!(call.data.starts_with("0x"));
+//! ```
+
+pub mod v1;
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::keccak256;
+use crate::evm::{self, tx::Eip1559Transaction};
+use crate::types::ChainId;
+
+/// Contract of a deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contract {
+    Settlement,
+    Escrow,
+}
+
+/// Call of one contract function
+pub trait ContractFunction {
+    /// Contract the function belongs to
+    const CONTRACT: Contract;
+    /// Canonical signature, e.g. "refund(bytes32)"
+    const SIGNATURE: &'static str;
+
+    /// "0x" hex calldata
+    fn encode(&self) -> Result<String, String>;
+}
+
+/// Encoded call of a deployed contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractCall {
+    /// Contract address
+    pub to: String,
+    /// Signature of the called function
+    pub function: String,
+    /// "0x" hex calldata
+    pub data: String,
+}
+
+impl ContractCall {
+    /// Hex Keccak-256 hash of the calldata
+    pub fn calldata_hash(&self) -> String {
+        let data = hex::decode(self.data.trim_start_matches("0x")).unwrap_or_default();
+        format!("0x{}", hex::encode(keccak256(&data)))
+    }
+
+    /// Transaction making the call on chain `chain_id`; set the nonce and
+    /// gas before signing it
+    pub fn transaction(&self, chain_id: u64) -> Eip1559Transaction {
+        Eip1559Transaction::call(chain_id, &self.to, &self.data)
+    }
+}
+
+/// Contracts of one ABI version deployed on a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deployment {
+    /// ABI version of the contracts (`v1::VERSION`)
+    pub version: u32,
+    pub chain: ChainId,
+    pub settlement: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escrow: Option<String>,
+    /// Token contract addresses keyed by asset symbol
+    #[serde(default)]
+    pub tokens: HashMap<String, String>,
+}
+
+impl Deployment {
+    /// Current-version deployment with its settlement contract at `settlement`
+    pub fn new(chain: ChainId, settlement: impl Into<String>) -> Self {
+        Self {
+            version: v1::VERSION,
+            chain,
+            settlement: settlement.into(),
+            escrow: None,
+            tokens: HashMap::new(),
+        }
+    }
+
+    pub fn with_escrow(mut self, escrow: impl Into<String>) -> Self {
+        self.escrow = Some(escrow.into());
+        self
+    }
+
+    /// Registers the contract address of `asset`
+    pub fn with_token(mut self, asset: &str, contract: impl Into<String>) -> Self {
+        self.tokens.insert(asset.to_uppercase(), contract.into());
+        self
+    }
+
+    /// Contract address of `asset`
+    pub fn token(&self, asset: &str) -> Result<&str, String> {
+        self.tokens
+            .get(&asset.to_uppercase())
+            .map(String::as_str)
+            .ok_or_else(|| format!("no {} token contract registered on {}", asset, self.chain))
+    }
+
+    /// Address of `contract`
+    pub fn address(&self, contract: Contract) -> Result<&str, String> {
+        match contract {
+            Contract::Settlement => Ok(&self.settlement),
+            Contract::Escrow => self
+                .escrow
+                .as_deref()
+                .ok_or_else(|| format!("no escrow contract deployed on {}", self.chain)),
+        }
+    }
+
+    /// Call of `function` on this deployment
+    pub fn call<F: ContractFunction>(&self, function: &F) -> Result<ContractCall, String> {
+        if self.version != v1::VERSION {
+            return Err(format!(
+                "the deployment on {} is v{}, these bindings are v{}",
+                self.chain,
+                self.version,
+                v1::VERSION
+            ));
+        }
+        Ok(ContractCall {
+            to: self.address(F::CONTRACT)?.to_string(),
+            function: F::SIGNATURE.to_string(),
+            data: function.encode()?,
+        })
+    }
+}
+
+/// Deployments keyed by chain
+#[derive(Debug, Clone, Default)]
+pub struct Deployments {
+    deployments: HashMap<ChainId, Deployment>,
+}
+
+impl Deployments {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_deployment(mut self, deployment: Deployment) -> Self {
+        self.deployments.insert(deployment.chain, deployment);
+        self
+    }
+
+    pub fn get(&self, chain: ChainId) -> Option<&Deployment> {
+        self.deployments.get(&chain)
+    }
+}
+
+/// On-chain `bytes32` ID of a reference ID (its Keccak-256 hash)
+pub fn reference_key(reference_id: &str) -> [u8; 32] {
+    keccak256(reference_id.as_bytes())
+}
+
+/// ABI-encoded argument
+enum Param {
+    Word([u8; 32]),
+    /// Dynamic array of static values
+    Array(Vec<[u8; 32]>),
+}
+
+/// Selector of `signature` followed by the head and tail of `params`
+fn encode_call(signature: &str, params: &[Param]) -> String {
+    let head_len = params.len() * 32;
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    for param in params {
+        match param {
+            Param::Word(word) => head.extend_from_slice(word),
+            Param::Array(items) => {
+                head.extend_from_slice(&uint_word((head_len + tail.len()) as u128));
+                tail.extend_from_slice(&uint_word(items.len() as u128));
+                tail.extend(items.iter().flatten());
+            }
+        }
+    }
+    format!("0x{}", hex::encode([&evm::selector(signature)[..], &head, &tail].concat()))
+}
+
+fn uint_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_dynamic_arrays() {
+        // f(uint256,uint256[]) with 1 and [2, 3]
+        let data = encode_call(
+            "f(uint256,uint256[])",
+            &[Param::Word(uint_word(1)), Param::Array(vec![uint_word(2), uint_word(3)])],
+        );
+        let words: Vec<u128> = hex::decode(&data[10..])
+            .unwrap()
+            .chunks(32)
+            .map(|word| u128::from_be_bytes(word[16..].try_into().unwrap()))
+            .collect();
+        assert_eq!(words, [1, 0x40, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_deployment_addresses() {
+        let deployment = Deployment::new(ChainId::Base, "0x5FbDB2315678afecb367f032d93F642f64180aa3")
+            .with_token("usdc", "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+        assert!(deployment.token("USDC").is_ok());
+        assert!(deployment.token("DAI").is_err());
+        assert!(deployment.address(Contract::Escrow).is_err());
+
+        let refund = v1::Refund { escrow_id: "escrow_1".to_string() };
+        assert!(deployment.call(&refund).is_err());
+        let deployment = deployment.with_escrow("0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+        assert_eq!(deployment.call(&refund).unwrap().to, "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+
+        let newer = Deployment { version: 2, ..deployment };
+        assert!(newer.call(&refund).unwrap_err().contains("v2"));
+    }
+}
@@ -0,0 +1,372 @@
+//! Version 1 of the contracts (`contracts/abi/v1`).
+//!
+//! `EasyCashSettlement` pays unshielded transfers and disbursements out of
+//! the payer's allowance (or a permit) and records the reference and intent
+//! hash of each payment. `EasyCashEscrow` locks funds until a SHA-256
+//! hashlock is opened, the release time passes or the agent releases them
+//! as arbiter, and refunds them after the refund time.
+
+use crate::amount::Amount;
+use crate::assets::AssetRegistry;
+use crate::crypto::hash::HashFunction;
+use crate::escrow::{EscrowTerms, ReleaseCondition};
+use crate::evm::permit::SignedPermit;
+use crate::evm::{address_word, uint256};
+use crate::receipt;
+use crate::types::{Disbursement, IntentType, TransactionRequest};
+
+use super::{encode_call, reference_key, uint_word, Contract, ContractCall, ContractFunction, Deployment, Param};
+
+/// ABI version of these bindings
+pub const VERSION: u32 = 1;
+
+/// `settle`: pays `amount` (base units) of `token` to `recipient`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settle {
+    pub reference_id: String,
+    pub intent_hash: [u8; 32],
+    pub token: String,
+    pub recipient: String,
+    pub amount: String,
+}
+
+impl ContractFunction for Settle {
+    const CONTRACT: Contract = Contract::Settlement;
+    const SIGNATURE: &'static str = "settle(bytes32,bytes32,address,address,uint256)";
+
+    fn encode(&self) -> Result<String, String> {
+        Ok(encode_call(
+            Self::SIGNATURE,
+            &[
+                Param::Word(reference_key(&self.reference_id)),
+                Param::Word(self.intent_hash),
+                Param::Word(address_word(&self.token)?),
+                Param::Word(address_word(&self.recipient)?),
+                Param::Word(uint256(&self.amount)?),
+            ],
+        ))
+    }
+}
+
+/// `settleWithPermit`: redeems the payer's permit, then pays its value
+/// to `recipient`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettleWithPermit {
+    pub reference_id: String,
+    pub intent_hash: [u8; 32],
+    pub token: String,
+    pub recipient: String,
+    /// Permit for the settlement contract, signed for `token`
+    pub permit: SignedPermit,
+}
+
+impl ContractFunction for SettleWithPermit {
+    const CONTRACT: Contract = Contract::Settlement;
+    const SIGNATURE: &'static str =
+        "settleWithPermit(bytes32,bytes32,address,address,uint256,uint256,uint8,bytes32,bytes32)";
+
+    fn encode(&self) -> Result<String, String> {
+        Ok(encode_call(
+            Self::SIGNATURE,
+            &[
+                Param::Word(reference_key(&self.reference_id)),
+                Param::Word(self.intent_hash),
+                Param::Word(address_word(&self.token)?),
+                Param::Word(address_word(&self.recipient)?),
+                Param::Word(uint256(&self.permit.permit.value)?),
+                Param::Word(uint_word(self.permit.permit.deadline as u128)),
+                Param::Word(uint_word(self.permit.v as u128)),
+                Param::Word(uint256(&self.permit.r)?),
+                Param::Word(uint256(&self.permit.s)?),
+            ],
+        ))
+    }
+}
+
+/// `disburse`: pays `amounts[i]` (base units) of `token` to `recipients[i]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disburse {
+    pub reference_id: String,
+    pub intent_hash: [u8; 32],
+    pub token: String,
+    pub recipients: Vec<String>,
+    pub amounts: Vec<String>,
+}
+
+impl ContractFunction for Disburse {
+    const CONTRACT: Contract = Contract::Settlement;
+    const SIGNATURE: &'static str = "disburse(bytes32,bytes32,address,address[],uint256[])";
+
+    fn encode(&self) -> Result<String, String> {
+        if self.recipients.len() != self.amounts.len() {
+            return Err(format!(
+                "{} recipients but {} amounts",
+                self.recipients.len(),
+                self.amounts.len()
+            ));
+        }
+        Ok(encode_call(
+            Self::SIGNATURE,
+            &[
+                Param::Word(reference_key(&self.reference_id)),
+                Param::Word(self.intent_hash),
+                Param::Word(address_word(&self.token)?),
+                Param::Array(self.recipients.iter().map(|r| address_word(r)).collect::<Result<_, _>>()?),
+                Param::Array(self.amounts.iter().map(|a| uint256(a)).collect::<Result<_, _>>()?),
+            ],
+        ))
+    }
+}
+
+/// `lock`: locks `amount` (base units) of `token` for `recipient`. A zero
+/// hashlock leaves the release to the agent; zero times are unset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock {
+    pub escrow_id: String,
+    pub intent_hash: [u8; 32],
+    pub token: String,
+    pub recipient: String,
+    pub amount: String,
+    /// SHA-256 hash of the release secret
+    pub hashlock: [u8; 32],
+    /// Unix time (seconds) before which the funds can't be released
+    pub release_after: u64,
+    /// Unix time (seconds) after which the payer may take the funds back
+    pub refund_after: u64,
+}
+
+impl Lock {
+    /// Sets the hashlock and times of `terms`
+    pub fn with_terms(mut self, terms: &EscrowTerms) -> Result<Self, String> {
+        match terms.condition {
+            ReleaseCondition::Timeout { release_at } => self.release_after = release_at,
+            ReleaseCondition::CounterpartySignature { .. } | ReleaseCondition::OracleAttestation { .. } => {}
+            ReleaseCondition::Hashlock {
+                ref hash,
+                hash_function: HashFunction::Sha256,
+            } => self.hashlock = uint256(hash)?,
+            ReleaseCondition::Hashlock { hash_function, .. } => {
+                return Err(format!("v{} escrows take SHA-256 hashlocks, not {:?}", VERSION, hash_function));
+            }
+        }
+        self.refund_after = terms.refund_after.unwrap_or(0);
+        Ok(self)
+    }
+}
+
+impl ContractFunction for Lock {
+    const CONTRACT: Contract = Contract::Escrow;
+    const SIGNATURE: &'static str = "lock(bytes32,bytes32,address,address,uint256,bytes32,uint64,uint64)";
+
+    fn encode(&self) -> Result<String, String> {
+        Ok(encode_call(
+            Self::SIGNATURE,
+            &[
+                Param::Word(reference_key(&self.escrow_id)),
+                Param::Word(self.intent_hash),
+                Param::Word(address_word(&self.token)?),
+                Param::Word(address_word(&self.recipient)?),
+                Param::Word(uint256(&self.amount)?),
+                Param::Word(self.hashlock),
+                Param::Word(uint_word(self.release_after as u128)),
+                Param::Word(uint_word(self.refund_after as u128)),
+            ],
+        ))
+    }
+}
+
+/// `release`: pays a locked escrow to its recipient. `secret` is the
+/// hashlock's preimage, or zero for escrows without one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub escrow_id: String,
+    pub secret: [u8; 32],
+}
+
+impl ContractFunction for Release {
+    const CONTRACT: Contract = Contract::Escrow;
+    const SIGNATURE: &'static str = "release(bytes32,bytes32)";
+
+    fn encode(&self) -> Result<String, String> {
+        Ok(encode_call(
+            Self::SIGNATURE,
+            &[Param::Word(reference_key(&self.escrow_id)), Param::Word(self.secret)],
+        ))
+    }
+}
+
+/// `refund`: returns an expired escrow to the payer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Refund {
+    pub escrow_id: String,
+}
+
+impl ContractFunction for Refund {
+    const CONTRACT: Contract = Contract::Escrow;
+    const SIGNATURE: &'static str = "refund(bytes32)";
+
+    fn encode(&self) -> Result<String, String> {
+        Ok(encode_call(Self::SIGNATURE, &[Param::Word(reference_key(&self.escrow_id))]))
+    }
+}
+
+/// Call that settles `req` on `deployment`: `settle` for transfers, `lock`
+/// for escrows and `disburse` when the payment has several `disbursements`
+/// (a disbursement, or a transfer with fee splits). Amounts are converted
+/// to base units with the asset's decimals in `assets`. `None` for shielded
+/// requests and intents settled elsewhere (swaps, shields).
+pub fn call_for(
+    deployment: &Deployment,
+    req: &TransactionRequest,
+    disbursements: Option<&[Disbursement]>,
+    assets: &AssetRegistry,
+) -> Result<Option<ContractCall>, String> {
+    let settled_here = matches!(req.intent_type, IntentType::Transfer | IntentType::Disburse | IntentType::Escrow);
+    if req.is_shielded || !settled_here {
+        return Ok(None);
+    }
+    let decimals = assets
+        .decimals(&req.asset)
+        .ok_or_else(|| format!("{} has no decimals configured", req.asset))?;
+    let base_units = |amount: &str| -> Result<String, String> {
+        Ok(amount.parse::<Amount>()?.to_base_units_exact(decimals)?.to_string())
+    };
+    let intent_hash = uint256(&receipt::intent_hash(req))?;
+    let token = deployment.token(&req.asset)?.to_string();
+    let recipient = || req.recipient.clone().ok_or_else(|| format!("{} has no recipient", req.reference_id));
+    let call = match (req.intent_type, disbursements) {
+        (IntentType::Transfer | IntentType::Disburse, Some(legs)) => deployment.call(&Disburse {
+            reference_id: req.reference_id.clone(),
+            intent_hash,
+            token,
+            recipients: legs.iter().map(|leg| leg.recipient.clone()).collect(),
+            amounts: legs.iter().map(|leg| base_units(&leg.amount)).collect::<Result<_, _>>()?,
+        })?,
+        (IntentType::Transfer, None) => deployment.call(&Settle {
+            reference_id: req.reference_id.clone(),
+            intent_hash,
+            token,
+            recipient: recipient()?,
+            amount: base_units(&req.amount)?,
+        })?,
+        (IntentType::Escrow, _) => {
+            let lock = Lock {
+                escrow_id: req.reference_id.clone(),
+                intent_hash,
+                token,
+                recipient: recipient()?,
+                amount: base_units(&req.amount)?,
+                hashlock: [0u8; 32],
+                release_after: 0,
+                refund_after: 0,
+            };
+            deployment.call(&lock.with_terms(&EscrowTerms::of(req)?)?)?
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(call))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::AssetConfig;
+    use crate::escrow::hashlock;
+    use crate::types::ChainId;
+    use serde_json::Value;
+    use std::collections::BTreeSet;
+
+    const USDC: &str = "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913";
+    const RECIPIENT: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+
+    fn abi_signatures(abi: &str) -> BTreeSet<String> {
+        let abi: Vec<Value> = serde_json::from_str(abi).unwrap();
+        abi.iter()
+            .filter(|item| item["type"] == "function")
+            .map(|item| {
+                let inputs: Vec<&str> = item["inputs"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|input| input["type"].as_str().unwrap())
+                    .collect();
+                format!("{}({})", item["name"].as_str().unwrap(), inputs.join(","))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bindings_match_abi() {
+        let settlement = abi_signatures(include_str!("../../contracts/abi/v1/EasyCashSettlement.json"));
+        let bound: BTreeSet<String> = [Settle::SIGNATURE, SettleWithPermit::SIGNATURE, Disburse::SIGNATURE]
+            .map(String::from)
+            .into();
+        assert_eq!(settlement, bound);
+
+        let escrow = abi_signatures(include_str!("../../contracts/abi/v1/EasyCashEscrow.json"));
+        let bound: BTreeSet<String> = [Lock::SIGNATURE, Release::SIGNATURE, Refund::SIGNATURE].map(String::from).into();
+        assert_eq!(escrow, bound);
+    }
+
+    fn request(intent_type: IntentType) -> TransactionRequest {
+        TransactionRequest::new("order_1001", intent_type, "25.5", "USDC", ChainId::Base)
+            .with_recipient(RECIPIENT)
+    }
+
+    fn assets(decimals: u32) -> AssetRegistry {
+        AssetRegistry::new().with_asset(
+            "USDC",
+            AssetConfig {
+                decimals: Some(decimals),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_call_for_request() {
+        let deployment = Deployment::new(ChainId::Base, "0x5FbDB2315678afecb367f032d93F642f64180aa3")
+            .with_escrow("0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512")
+            .with_token("USDC", USDC);
+
+        let req = request(IntentType::Transfer);
+        let call = call_for(&deployment, &req, None, &assets(6)).unwrap().unwrap();
+        assert_eq!(call.function, Settle::SIGNATURE);
+        let words = hex::decode(&call.data[10..]).unwrap();
+        assert_eq!(call.data.len(), 2 + 8 + 5 * 64);
+        assert_eq!(words[..32], reference_key("order_1001"));
+        assert_eq!(format!("0x{}", hex::encode(&words[32..64])), receipt::intent_hash(&req));
+        assert_eq!(words[128..160], uint256("25500000").unwrap());
+        // More precise than the token
+        assert!(call_for(&deployment, &request(IntentType::Transfer), None, &assets(0)).is_err());
+
+        let legs = [
+            Disbursement { recipient: RECIPIENT.to_string(), amount: "25".to_string() },
+            Disbursement { recipient: USDC.to_string(), amount: "0.5".to_string() },
+        ];
+        let call = call_for(&deployment, &req, Some(&legs), &assets(6)).unwrap().unwrap();
+        assert_eq!(call.function, Disburse::SIGNATURE);
+
+        let mut escrow = request(IntentType::Escrow);
+        EscrowTerms::htlc(hashlock(b"secret", HashFunction::Sha256), 4_102_444_800)
+            .attach(&mut escrow);
+        let call = call_for(&deployment, &escrow, None, &assets(6)).unwrap().unwrap();
+        assert_eq!(call.to, "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+        let words = hex::decode(&call.data[10..]).unwrap();
+        assert_eq!(format!("0x{}", hex::encode(&words[160..192])), hashlock(b"secret", HashFunction::Sha256));
+        let keccak = EscrowTerms::new(ReleaseCondition::Hashlock {
+            hash: hashlock(b"secret", HashFunction::Keccak256),
+            hash_function: HashFunction::Keccak256,
+        });
+        keccak.attach(&mut escrow);
+        assert!(call_for(&deployment, &escrow, None, &assets(6)).is_err());
+
+        let mut shielded = request(IntentType::Transfer);
+        shielded.is_shielded = true;
+        assert_eq!(call_for(&deployment, &shielded, None, &assets(6)).unwrap(), None);
+        assert_eq!(call_for(&deployment, &request(IntentType::Swap), None, &assets(6)).unwrap(), None);
+        let mut dai = request(IntentType::Transfer);
+        dai.asset = "DAI".to_string();
+        assert!(call_for(&deployment, &dai, None, &assets(6)).is_err());
+    }
+}
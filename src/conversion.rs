@@ -0,0 +1,196 @@
+//! Conversion quotes for off-ramps and fiat-denominated settlements.
+//!
+//! A `ConversionProvider` supplies exchange rates between assets and fiat
+//! currencies. `EasyCashClient::get_conversion_quote` combines its rate with
+//! the routing fee of the transfer, so a gateway can state what a settlement
+//! costs in EUR or USD in one call.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::pricing::PriceOracle;
+
+/// Source of exchange rates
+#[async_trait::async_trait]
+pub trait ConversionProvider: Send + Sync {
+    /// Returns how many units of `to` one unit of `from` converts to
+    async fn rate(&self, from: &str, to: &str) -> Result<f64, String>;
+}
+
+/// Fixed rates, useful for tests and for rates locked with a counterparty
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::conversion::{ConversionProvider, StaticConversionProvider};
+///
+/// # tokio_test::block_on(async {
+/// let rates = StaticConversionProvider::new().with_rate("USDC", "EUR", 0.92);
+/// assert_eq!(rates.rate("usdc", "eur").await.unwrap(), 0.92);
+/// assert_eq!(rates.rate("EUR", "USDC").await.unwrap(), 1.0 / 0.92);
+/// # });
+/// ```
+#[derive(Default)]
+pub struct StaticConversionProvider {
+    rates: RwLock<HashMap<(String, String), f64>>,
+}
+
+impl StaticConversionProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rate (builder style)
+    pub fn with_rate(self, from: &str, to: &str, rate: f64) -> Self {
+        self.set_rate(from, to, rate);
+        self
+    }
+
+    /// Sets or updates the rate from `from` to `to`; the inverse rate is
+    /// implied unless set separately
+    pub fn set_rate(&self, from: &str, to: &str, rate: f64) {
+        if let Ok(mut rates) = self.rates.write() {
+            rates.insert((from.to_uppercase(), to.to_uppercase()), rate);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ConversionProvider for StaticConversionProvider {
+    async fn rate(&self, from: &str, to: &str) -> Result<f64, String> {
+        let (from, to) = (from.to_uppercase(), to.to_uppercase());
+        if from == to {
+            return Ok(1.0);
+        }
+        let rates = self.rates.read().map_err(|_| "conversion provider poisoned".to_string())?;
+        if let Some(rate) = rates.get(&(from.clone(), to.clone())) {
+            return Ok(*rate);
+        }
+        match rates.get(&(to.clone(), from.clone())) {
+            Some(rate) if *rate > 0.0 => Ok(1.0 / rate),
+            _ => Err(format!("no rate from {} to {}", from, to)),
+        }
+    }
+}
+
+/// Derives rates from a price oracle's USD prices, for conversions between
+/// assets and into USD
+pub struct OracleConversionProvider {
+    oracle: Arc<dyn PriceOracle>,
+}
+
+impl OracleConversionProvider {
+    pub fn new(oracle: Arc<dyn PriceOracle>) -> Self {
+        Self { oracle }
+    }
+
+    async fn usd_price(&self, asset: &str) -> Result<f64, String> {
+        if asset.eq_ignore_ascii_case("USD") {
+            return Ok(1.0);
+        }
+        self.oracle.usd_price(asset).await
+    }
+}
+
+#[async_trait::async_trait]
+impl ConversionProvider for OracleConversionProvider {
+    async fn rate(&self, from: &str, to: &str) -> Result<f64, String> {
+        let to_price = self.usd_price(to).await?;
+        if to_price <= 0.0 {
+            return Err(format!("invalid USD price for {}: {}", to, to_price));
+        }
+        Ok(self.usd_price(from).await? / to_price)
+    }
+}
+
+/// Cost of a transfer stated in another asset or fiat currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversionQuote {
+    pub from_asset: String,
+    /// Asset or fiat currency the quote is stated in (e.g. "EUR")
+    pub to: String,
+    /// Transfer amount, in `from_asset`
+    pub amount: String,
+    /// Units of `to` per unit of `from_asset`
+    pub rate: f64,
+    /// Transfer amount, in `to`
+    pub converted_amount: f64,
+    /// Routing fee as quoted by the agent (e.g. "0.05 USDC")
+    pub transfer_fee: String,
+    /// Routing fee, in `to`
+    pub transfer_fee_converted: f64,
+    /// What the payer spends in `to`: converted amount plus routing fee
+    pub total: f64,
+    /// Agent whose route the fee was taken from
+    pub agent_id: String,
+}
+
+/// Quotes `amount` of `from_asset` in `to`, adding `transfer_fee` converted
+/// from whatever asset it is charged in
+pub async fn quote(
+    provider: &dyn ConversionProvider,
+    from_asset: &str,
+    to: &str,
+    amount: &str,
+    transfer_fee: &str,
+    agent_id: &str,
+) -> Result<ConversionQuote, String> {
+    let value: f64 = amount.parse().map_err(|e| format!("invalid amount {}: {}", amount, e))?;
+    let mut fee_parts = transfer_fee.split_whitespace();
+    let fee = fee_parts
+        .next()
+        .and_then(|s| s.parse::<f64>().ok())
+        .ok_or_else(|| format!("invalid fee amount: {}", transfer_fee))?;
+    let fee_asset = fee_parts.next().unwrap_or(from_asset);
+
+    let rate = provider.rate(from_asset, to).await?;
+    let fee_rate = if fee_asset.eq_ignore_ascii_case(from_asset) {
+        rate
+    } else {
+        provider.rate(fee_asset, to).await?
+    };
+    let converted_amount = value * rate;
+    let transfer_fee_converted = fee * fee_rate;
+    Ok(ConversionQuote {
+        from_asset: from_asset.to_uppercase(),
+        to: to.to_uppercase(),
+        amount: amount.to_string(),
+        rate,
+        converted_amount,
+        transfer_fee: transfer_fee.to_string(),
+        transfer_fee_converted,
+        total: converted_amount + transfer_fee_converted,
+        agent_id: agent_id.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing::StaticPriceOracle;
+
+    #[tokio::test]
+    async fn test_quote_converts_fee_in_another_asset() {
+        let rates = StaticConversionProvider::new()
+            .with_rate("USDC", "EUR", 0.9)
+            .with_rate("ETH", "EUR", 3000.0);
+        let quote = quote(&rates, "usdc", "eur", "100", "0.001 ETH", "agent-1").await.unwrap();
+        assert_eq!(quote.to, "EUR");
+        assert!((quote.converted_amount - 90.0).abs() < 1e-9);
+        assert!((quote.transfer_fee_converted - 3.0).abs() < 1e-9);
+        assert!((quote.total - 93.0).abs() < 1e-9);
+
+        assert!(super::quote(&rates, "USDC", "GBP", "100", "0.05 USDC", "agent-1").await.is_err());
+        assert!(super::quote(&rates, "USDC", "EUR", "lots", "0.05 USDC", "agent-1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_oracle_provider() {
+        let oracle = StaticPriceOracle::new().with_price("ETH", 3000.0).with_price("USDC", 1.0);
+        let rates = OracleConversionProvider::new(Arc::new(oracle));
+        assert_eq!(rates.rate("ETH", "USD").await.unwrap(), 3000.0);
+        assert_eq!(rates.rate("USDC", "ETH").await.unwrap(), 1.0 / 3000.0);
+        assert!(rates.rate("DOGE", "USD").await.is_err());
+    }
+}
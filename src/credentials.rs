@@ -0,0 +1,189 @@
+//! API key rotation.
+//!
+//! `ApiKeyRing` holds the key network components authenticate with. Rotating
+//! it takes effect for the next request without restarting the client; the
+//! previous key stays usable for a grace period so requests that race with
+//! the rotation, or hit a server that hasn't seen the new key yet, still
+//! succeed. `KeyRotation` polls a `SecretsProvider` (Vault, AWS Secrets
+//! Manager, ...) and rotates automatically when the stored key changes.
+
+use std::fmt;
+#[cfg(feature = "client")]
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::secrets::SecretString;
+
+/// How long the previous key stays usable after a rotation by default
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(300);
+
+struct Keys {
+    current: SecretString,
+    previous: Option<(SecretString, Instant)>,
+}
+
+/// Current API key plus the previous one during its grace period
+pub struct ApiKeyRing {
+    keys: RwLock<Keys>,
+    grace_period: Duration,
+}
+
+impl ApiKeyRing {
+    pub fn new(api_key: impl Into<SecretString>) -> Self {
+        Self {
+            keys: RwLock::new(Keys {
+                current: api_key.into(),
+                previous: None,
+            }),
+            grace_period: DEFAULT_GRACE_PERIOD,
+        }
+    }
+
+    /// Sets how long the previous key stays usable after a rotation
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Key to authenticate new requests with
+    pub fn current(&self) -> SecretString {
+        self.keys.read().unwrap_or_else(|e| e.into_inner()).current.clone()
+    }
+
+    /// Previous key, while its grace period lasts
+    pub fn previous(&self) -> Option<SecretString> {
+        let keys = self.keys.read().unwrap_or_else(|e| e.into_inner());
+        keys.previous
+            .as_ref()
+            .filter(|(_, rotated_at)| rotated_at.elapsed() < self.grace_period)
+            .map(|(key, _)| key.clone())
+    }
+
+    /// Keys to try in order: the current one, then the previous one if it is
+    /// still in its grace period
+    pub fn candidates(&self) -> Vec<SecretString> {
+        let mut keys = vec![self.current()];
+        keys.extend(self.previous());
+        keys
+    }
+
+    /// Replaces the current key; returns false if `new_key` is already current
+    pub fn rotate(&self, new_key: impl Into<SecretString>) -> bool {
+        let new_key = new_key.into();
+        let mut keys = self.keys.write().unwrap_or_else(|e| e.into_inner());
+        if keys.current == new_key {
+            return false;
+        }
+        let old = std::mem::replace(&mut keys.current, new_key);
+        keys.previous = Some((old, Instant::now()));
+        tracing::info!("[SDK] API key rotated; previous key valid for {:?}", self.grace_period);
+        true
+    }
+}
+
+impl fmt::Debug for ApiKeyRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyRing")
+            .field("current", &self.current())
+            .field("previous", &self.previous())
+            .field("grace_period", &self.grace_period)
+            .finish()
+    }
+}
+
+/// Source of the current API key (e.g. a Vault or AWS Secrets Manager client)
+#[async_trait::async_trait]
+pub trait SecretsProvider: Send + Sync {
+    async fn fetch_api_key(&self) -> Result<SecretString, String>;
+}
+
+/// Background task that keeps an `ApiKeyRing` in sync with a
+/// `SecretsProvider`; stops when dropped
+#[cfg(feature = "client")]
+pub struct KeyRotation {
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "client")]
+impl KeyRotation {
+    /// Polls `provider` every `interval` (must be called within a Tokio runtime)
+    pub fn spawn(ring: Arc<ApiKeyRing>, provider: Arc<dyn SecretsProvider>, interval: Duration) -> Self {
+        let task = tokio::spawn(async move {
+            loop {
+                if let Err(e) = refresh(&ring, provider.as_ref()).await {
+                    tracing::warn!("[SDK] Failed to fetch API key from secrets provider: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+        Self { task }
+    }
+}
+
+#[cfg(feature = "client")]
+impl Drop for KeyRotation {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Fetches the key once and rotates if it changed; returns whether it did
+pub async fn refresh(ring: &ApiKeyRing, provider: &dyn SecretsProvider) -> Result<bool, String> {
+    let key = provider.fetch_api_key().await?;
+    if key.is_empty() {
+        return Err("secrets provider returned an empty API key".to_string());
+    }
+    Ok(ring.rotate(key))
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct StaticProvider(Mutex<Result<String, String>>);
+
+    #[async_trait::async_trait]
+    impl SecretsProvider for StaticProvider {
+        async fn fetch_api_key(&self) -> Result<SecretString, String> {
+            self.0.lock().unwrap().clone().map(SecretString::from)
+        }
+    }
+
+    #[test]
+    fn test_rotation_and_grace_period() {
+        let ring = ApiKeyRing::new("key_1").with_grace_period(Duration::from_millis(50));
+        assert_eq!(ring.candidates().len(), 1);
+        assert!(!ring.rotate("key_1"));
+
+        assert!(ring.rotate("key_2"));
+        assert_eq!(ring.current().expose(), "key_2");
+        let candidates: Vec<String> = ring.candidates().iter().map(|k| k.expose().to_string()).collect();
+        assert_eq!(candidates, ["key_2", "key_1"]);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(ring.previous().is_none());
+        assert_eq!(ring.candidates().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_auto_rotation_follows_provider() {
+        let ring = Arc::new(ApiKeyRing::new("key_1"));
+        let provider = Arc::new(StaticProvider(Mutex::new(Ok("key_1".to_string()))));
+        let _rotation = KeyRotation::spawn(ring.clone(), provider.clone(), Duration::from_secs(60));
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert!(ring.previous().is_none());
+
+        *provider.0.lock().unwrap() = Ok("key_2".to_string());
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(ring.current().expose(), "key_2");
+
+        // Provider outages keep the current key
+        *provider.0.lock().unwrap() = Err("vault sealed".to_string());
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(ring.current().expose(), "key_2");
+        assert!(refresh(&ring, provider.as_ref()).await.is_err());
+    }
+}
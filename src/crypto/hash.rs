@@ -0,0 +1,192 @@
+//! Hash functions selectable per use.
+//!
+//! SHA-256 remains the default everywhere. Commitments can switch to a
+//! circuit-friendly hash (Poseidon, behind the `poseidon` feature) to match
+//! the proving circuits, and message signing to Keccak-256 or BLAKE2b
+//! independently, via `HashConfig`.
+
+use blake2::{digest::consts::U32, Blake2b};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 256-bit hash function
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HashFunction {
+    #[default]
+    Sha256,
+    /// Keccak-256 as used by Ethereum
+    Keccak256,
+    /// BLAKE2b with a 256-bit output
+    Blake2b256,
+    /// Poseidon over the BN254 scalar field (circom parameters). Outputs are
+    /// big-endian field elements.
+    #[cfg(feature = "poseidon")]
+    Poseidon,
+}
+
+impl HashFunction {
+    /// Hashes arbitrary bytes
+    pub fn hash(&self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashFunction::Sha256 => Sha256::digest(data).into(),
+            HashFunction::Keccak256 => super::keccak256(data),
+            HashFunction::Blake2b256 => Blake2b::<U32>::digest(data).into(),
+            #[cfg(feature = "poseidon")]
+            HashFunction::Poseidon => poseidon::hash_bytes(data),
+        }
+    }
+
+    /// Hashes two 32-byte nodes, e.g. the children of a Merkle tree node.
+    /// Byte hashes hash the 64-byte concatenation; Poseidon hashes the two
+    /// values (reduced into the field) as two inputs.
+    pub fn hash_pair(&self, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        match self {
+            #[cfg(feature = "poseidon")]
+            HashFunction::Poseidon => poseidon::hash_pair(left, right),
+            _ => self.hash(&[left.as_slice(), right.as_slice()].concat()),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashFunction::Sha256 => "sha256",
+            HashFunction::Keccak256 => "keccak256",
+            HashFunction::Blake2b256 => "blake2b256",
+            #[cfg(feature = "poseidon")]
+            HashFunction::Poseidon => "poseidon",
+        }
+    }
+}
+
+impl std::fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for HashFunction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "sha256" => Ok(HashFunction::Sha256),
+            "keccak256" => Ok(HashFunction::Keccak256),
+            "blake2b256" | "blake2b" => Ok(HashFunction::Blake2b256),
+            #[cfg(feature = "poseidon")]
+            "poseidon" => Ok(HashFunction::Poseidon),
+            _ => Err(format!("unknown hash function: {}", s)),
+        }
+    }
+}
+
+/// Hash function of each use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashConfig {
+    /// Note commitments and the commitment tree
+    #[serde(default)]
+    pub commitments: HashFunction,
+    /// Digest of messages before ECDSA signing
+    #[serde(default)]
+    pub signing: HashFunction,
+}
+
+#[cfg(feature = "poseidon")]
+mod poseidon {
+    use ark_bn254::Fr;
+    use ark_ff::{BigInteger, PrimeField};
+    use light_poseidon::{Poseidon, PoseidonHasher};
+
+    /// Most inputs the circom parameters support per permutation
+    const MAX_INPUTS: usize = 12;
+    /// Bytes per input, so every chunk is below the field modulus
+    const CHUNK_LEN: usize = 31;
+
+    fn permute(inputs: &[Fr]) -> Fr {
+        // Input counts are kept within 1..=MAX_INPUTS, which always have parameters
+        Poseidon::<Fr>::new_circom(inputs.len())
+            .and_then(|mut poseidon| poseidon.hash(inputs))
+            .expect("poseidon parameters exist for 1..=12 inputs")
+    }
+
+    fn to_bytes(value: Fr) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&value.into_bigint().to_bytes_be());
+        out
+    }
+
+    /// Absorbs the length and 31-byte chunks of `data`, chaining full
+    /// permutations through the first input of the next
+    pub(super) fn hash_bytes(data: &[u8]) -> [u8; 32] {
+        let mut inputs = vec![Fr::from(data.len() as u64)];
+        inputs.extend(data.chunks(CHUNK_LEN).map(Fr::from_be_bytes_mod_order));
+        let mut state = permute(&inputs[..inputs.len().min(MAX_INPUTS)]);
+        for chunk in inputs[inputs.len().min(MAX_INPUTS)..].chunks(MAX_INPUTS - 1) {
+            let mut next = vec![state];
+            next.extend_from_slice(chunk);
+            state = permute(&next);
+        }
+        to_bytes(state)
+    }
+
+    pub(super) fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        to_bytes(permute(&[Fr::from_be_bytes_mod_order(left), Fr::from_be_bytes_mod_order(right)]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digests() {
+        assert_eq!(
+            hex::encode(HashFunction::Sha256.hash(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex::encode(HashFunction::Keccak256.hash(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(HashFunction::Blake2b256.hash(b"abc")),
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319"
+        );
+        let (left, right) = ([1u8; 32], [2u8; 32]);
+        assert_eq!(
+            HashFunction::Blake2b256.hash_pair(&left, &right),
+            HashFunction::Blake2b256.hash(&[left, right].concat())
+        );
+    }
+
+    #[test]
+    fn test_parse_and_config_defaults() {
+        assert_eq!("BLAKE2b-256".parse::<HashFunction>().unwrap(), HashFunction::Blake2b256);
+        assert_eq!(HashFunction::Keccak256.to_string().parse::<HashFunction>().unwrap(), HashFunction::Keccak256);
+        assert!("md5".parse::<HashFunction>().is_err());
+
+        let config: HashConfig = serde_json::from_str(r#"{"signing":"keccak256"}"#).unwrap();
+        assert_eq!((config.commitments, config.signing), (HashFunction::Sha256, HashFunction::Keccak256));
+    }
+
+    #[cfg(feature = "poseidon")]
+    #[test]
+    fn test_poseidon() {
+        use ark_ff::{BigInteger, PrimeField};
+
+        // circom's poseidon([1, 2])
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut two = [0u8; 32];
+        two[31] = 2;
+        assert_eq!(
+            hex::encode(HashFunction::Poseidon.hash_pair(&one, &two)),
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+        );
+        let long = vec![7u8; 31 * 20];
+        assert_ne!(HashFunction::Poseidon.hash(&long), HashFunction::Poseidon.hash(&long[..31 * 19]));
+        let digest = HashFunction::Poseidon.hash(b"note");
+        assert_eq!(ark_bn254::Fr::from_be_bytes_mod_order(&digest).into_bigint().to_bytes_be(), digest.to_vec());
+    }
+}
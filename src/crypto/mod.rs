@@ -0,0 +1,647 @@
+pub mod hash;
+
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD};
+use base64::engine::{DecodePaddingMode, Engine};
+use hmac::{Hmac, Mac};
+#[cfg(feature = "crypto")]
+use k256::{
+    ecdsa::{
+        signature::{
+            hazmat::{PrehashSigner, PrehashVerifier},
+            Signer as SignerTrait, Verifier,
+        },
+        Signature, SigningKey, VerifyingKey,
+    },
+    elliptic_curve::sec1::ToEncodedPoint,
+    PublicKey, SecretKey,
+};
+#[cfg(feature = "crypto")]
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    ChaCha20Poly1305, XChaCha20Poly1305, XNonce,
+};
+#[cfg(feature = "crypto")]
+use hkdf::Hkdf;
+#[cfg(feature = "crypto")]
+use k256::ecdh::{self, EphemeralSecret, SharedSecret};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "crypto")]
+use zeroize::Zeroizing;
+
+#[cfg(feature = "crypto")]
+use self::hash::HashFunction;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Length of a compressed SEC1 secp256k1 public key
+#[cfg(feature = "crypto")]
+const COMPRESSED_KEY_LEN: usize = 33;
+/// Length of the Poly1305 authentication tag
+#[cfg(feature = "crypto")]
+const AEAD_TAG_LEN: usize = 16;
+
+/// Length of the random nonce of `seal_symmetric`
+#[cfg(feature = "crypto")]
+const NONCE_LEN: usize = 24;
+
+/// What a signature is for. Each domain's tag is prefixed to the payload
+/// before signing, so a signature made for one purpose never verifies as
+/// another (e.g. a receipt payload replayed as an invoice).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SigningDomain {
+    /// Intents and other requests sent to the EasyCash API (`request_signing`)
+    Intent,
+    /// Settlement receipts (`receipt`)
+    Receipt,
+    /// Webhook deliveries (`webhooks`)
+    Webhook,
+    /// Payment requests (`invoices`)
+    Invoice,
+    /// Circuit artifact manifests (`zk::artifacts`)
+    ArtifactManifest,
+    /// Route quotes issued by agents (`agent`)
+    Quote,
+    /// Escrow releases and oracle attestations (`escrow`)
+    Escrow,
+    /// Fee sponsorship authorizations (`sponsorship`)
+    Sponsorship,
+    /// Intents prepared offline for later submission (`offline`)
+    PreparedIntent,
+}
+
+impl SigningDomain {
+    /// Versioned tag identifying the domain
+    pub fn tag(&self) -> &'static str {
+        match self {
+            SigningDomain::Intent => "ecash-sdk/intent/v1",
+            SigningDomain::Receipt => "ecash-sdk/receipt/v1",
+            SigningDomain::Webhook => "ecash-sdk/webhook/v1",
+            SigningDomain::Invoice => "ecash-sdk/invoice/v1",
+            SigningDomain::ArtifactManifest => "ecash-sdk/artifact-manifest/v1",
+            SigningDomain::Quote => "ecash-sdk/quote/v1",
+            SigningDomain::Escrow => "ecash-sdk/escrow/v1",
+            SigningDomain::Sponsorship => "ecash-sdk/sponsorship/v1",
+            SigningDomain::PreparedIntent => "ecash-sdk/prepared-intent/v1",
+        }
+    }
+
+    /// Bytes actually signed: `tag || 0x00 || payload`
+    pub fn separate(&self, payload: &[u8]) -> Vec<u8> {
+        [self.tag().as_bytes(), &[0u8], payload].concat()
+    }
+}
+
+/// TransactionSigner handles cryptographic signing operations for transactions.
+///
+/// This struct wraps an ECDSA signing key and provides methods for signing
+/// transaction data with SHA-256 hashing (see `with_hash` for others). The
+/// key is zeroed when the signer is dropped.
+///
+/// Signatures are deterministic (RFC 6979 nonces, low-S normalized): the
+/// same key, hash function and message always give the same signature.
+///
+/// # Example
+/// ```
+/// use k256::SecretKey;
+/// use ecash_sdk_core::crypto::TransactionSigner;
+///
+/// let secret_key_bytes = [1u8; 32];
+/// let secret_key = SecretKey::from_bytes(&secret_key_bytes.into()).unwrap();
+/// let signer = TransactionSigner::new(secret_key);
+/// let signature = signer.sign_message(b"transaction data").unwrap();
+/// ```
+#[cfg(feature = "crypto")]
+pub struct TransactionSigner {
+    signing_key: SigningKey,
+    hash: HashFunction,
+}
+
+#[cfg(feature = "crypto")]
+impl TransactionSigner {
+    /// Creates a new signer with a given private key.
+    ///
+    /// # Arguments
+    /// * `secret_key` - The secp256k1 secret key for signing
+    pub fn new(secret_key: SecretKey) -> Self {
+        Self {
+            signing_key: SigningKey::from(&secret_key),
+            hash: HashFunction::Sha256,
+        }
+    }
+
+    /// Digests messages with `hash` instead of SHA-256 before signing.
+    /// Signatures must then be checked with `verify_signature_with`.
+    pub fn with_hash(mut self, hash: HashFunction) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    /// Hash function messages are digested with
+    pub fn hash_function(&self) -> HashFunction {
+        self.hash
+    }
+
+    /// Signs arbitrary data and returns hex-encoded signature.
+    ///
+    /// The data is first hashed (SHA-256 unless set with `with_hash`), then
+    /// signed using ECDSA.
+    /// Returns a hex-encoded signature prefixed with "0x".
+    ///
+    /// # Arguments
+    /// * `data` - The raw bytes to sign
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Hex-encoded signature (e.g., "0x1234...")
+    /// * `Err(String)` - Error message if signing fails
+    pub fn sign_message(&self, data: &[u8]) -> Result<String, String> {
+        let signature: Signature = match self.hash {
+            HashFunction::Sha256 => SignerTrait::sign(&self.signing_key, &Sha256::digest(data)),
+            hash => PrehashSigner::sign_prehash(&self.signing_key, &hash.hash(data))
+                .map_err(|e| format!("signing failed: {}", e))?,
+        };
+        Ok(format!("0x{}", hex::encode(signature.to_bytes())))
+    }
+
+    /// Signs `data` prefixed with the tag of `domain` (see `SigningDomain`)
+    pub fn sign_in_domain(&self, domain: SigningDomain, data: &[u8]) -> Result<String, String> {
+        self.sign_message(&domain.separate(data))
+    }
+
+    /// Signs a 32-byte digest as is, returning the `r || s` signature and
+    /// its recovery ID (0 or 1), as EVM transactions and EIP-712 need
+    pub fn sign_digest_recoverable(&self, digest: &[u8; 32]) -> Result<([u8; 64], u8), String> {
+        let (signature, recovery_id) = self
+            .signing_key
+            .sign_prehash_recoverable(digest)
+            .map_err(|e| format!("signing failed: {}", e))?;
+        Ok((signature.to_bytes().into(), recovery_id.to_byte()))
+    }
+
+    /// Returns the public verifying key corresponding to this signer.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        *self.signing_key.verifying_key()
+    }
+}
+
+/// Verifies a signature against a public key.
+///
+/// # Arguments
+/// * `verifying_key` - The public key to verify against
+/// * `data` - The original data that was signed
+/// * `signature_hex` - Hex-encoded signature (with or without "0x" prefix)
+///
+/// # Returns
+/// * `Ok(true)` - Signature is valid
+/// * `Ok(false)` - Signature verification failed
+/// * `Err(String)` - Error parsing hex or signature format
+#[cfg(feature = "crypto")]
+pub fn verify_signature(
+    verifying_key: &VerifyingKey,
+    data: &[u8],
+    signature_hex: &str,
+) -> Result<bool, String> {
+    verify_signature_with(HashFunction::Sha256, verifying_key, data, signature_hex)
+}
+
+/// Verifies a signature made by a `TransactionSigner` using `hash`
+#[cfg(feature = "crypto")]
+pub fn verify_signature_with(
+    hash: HashFunction,
+    verifying_key: &VerifyingKey,
+    data: &[u8],
+    signature_hex: &str,
+) -> Result<bool, String> {
+    // Decode hex signature (strip 0x prefix if present)
+    let sig_hex = signature_hex.strip_prefix("0x").unwrap_or(signature_hex);
+    let sig_bytes = hex::decode(sig_hex).map_err(|e| format!("invalid hex: {}", e))?;
+
+    // ECDSA signatures are 64 bytes (r: 32, s: 32)
+    if sig_bytes.len() != 64 {
+        return Err(format!(
+            "invalid signature length: expected 64 bytes, got {}",
+            sig_bytes.len()
+        ));
+    }
+
+    // Convert to fixed-size array for Signature::from_bytes
+    let sig_array: [u8; 64] = sig_bytes
+        .try_into()
+        .map_err(|_| "failed to convert signature bytes")?;
+
+    let signature =
+        Signature::from_bytes(&sig_array.into()).map_err(|e| format!("invalid signature: {}", e))?;
+
+    Ok(match hash {
+        HashFunction::Sha256 => verifying_key.verify(&Sha256::digest(data), &signature).is_ok(),
+        hash => verifying_key.verify_prehash(&hash.hash(data), &signature).is_ok(),
+    })
+}
+
+/// Verifies a signature made by `TransactionSigner::sign_in_domain`
+#[cfg(feature = "crypto")]
+pub fn verify_signature_in_domain(
+    hash: HashFunction,
+    domain: SigningDomain,
+    verifying_key: &VerifyingKey,
+    data: &[u8],
+    signature_hex: &str,
+) -> Result<bool, String> {
+    verify_signature_with(hash, verifying_key, &domain.separate(data), signature_hex)
+}
+
+/// Encrypts `plaintext` to a secp256k1 public key (ECIES).
+///
+/// An ephemeral key is generated per message; the ECDH shared secret is run
+/// through HKDF-SHA256, salted with the ephemeral public key, to derive a
+/// one-time ChaCha20-Poly1305 key and nonce.
+///
+/// Output layout: `ephemeral_pubkey (33) || ciphertext || tag (16)`.
+#[cfg(feature = "crypto")]
+pub fn ecies_encrypt(recipient: &PublicKey, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let ephemeral = EphemeralSecret::random(&mut rand::rngs::OsRng);
+    let ephemeral_pub = ephemeral.public_key().to_encoded_point(true);
+    let (cipher, nonce) = ecies_cipher(&ephemeral.diffie_hellman(recipient), ephemeral_pub.as_bytes())?;
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "encryption failed".to_string())?;
+
+    let mut out = Vec::with_capacity(COMPRESSED_KEY_LEN + ciphertext.len());
+    out.extend_from_slice(ephemeral_pub.as_bytes());
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypts a payload produced by `ecies_encrypt`.
+#[cfg(feature = "crypto")]
+pub fn ecies_decrypt(secret_key: &SecretKey, payload: &[u8]) -> Result<Vec<u8>, String> {
+    if payload.len() < COMPRESSED_KEY_LEN + AEAD_TAG_LEN {
+        return Err(format!("ciphertext too short: {} bytes", payload.len()));
+    }
+    let (ephemeral_bytes, ciphertext) = payload.split_at(COMPRESSED_KEY_LEN);
+    let ephemeral_pub = PublicKey::from_sec1_bytes(ephemeral_bytes)
+        .map_err(|e| format!("invalid ephemeral public key: {}", e))?;
+    let shared = ecdh::diffie_hellman(secret_key.to_nonzero_scalar(), ephemeral_pub.as_affine());
+    let (cipher, nonce) = ecies_cipher(&shared, ephemeral_bytes)?;
+    cipher
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| "authentication failed: ciphertext was modified or key is wrong".to_string())
+}
+
+/// Encrypts `plaintext` under a 32-byte symmetric key, authenticating `aad`
+/// along with it.
+///
+/// XChaCha20-Poly1305 with a random 24-byte nonce, which is long enough that
+/// random nonces never repeat under one key.
+///
+/// Output layout: `nonce (24) || ciphertext || tag (16)`.
+#[cfg(feature = "crypto")]
+pub fn seal_symmetric(key: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = symmetric_cipher(key)?;
+    let mut nonce = XNonce::default();
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut nonce);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg: plaintext, aad })
+        .map_err(|_| "encryption failed".to_string())?;
+
+    let mut out = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypts a payload produced by `seal_symmetric` with the same key and `aad`.
+#[cfg(feature = "crypto")]
+pub fn open_symmetric(key: &[u8], payload: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    if payload.len() < NONCE_LEN + AEAD_TAG_LEN {
+        return Err(format!("ciphertext too short: {} bytes", payload.len()));
+    }
+    let cipher = symmetric_cipher(key)?;
+    let (nonce_bytes, ciphertext) = payload.split_at(NONCE_LEN);
+    let mut nonce = XNonce::default();
+    nonce.copy_from_slice(nonce_bytes);
+    cipher
+        .decrypt(&nonce, Payload { msg: ciphertext, aad })
+        .map_err(|_| "authentication failed: ciphertext was modified or key is wrong".to_string())
+}
+
+/// Parses a hex-encoded SEC1 public key (with or without "0x" prefix)
+#[cfg(feature = "crypto")]
+pub fn public_key_from_hex(public_key_hex: &str) -> Result<PublicKey, String> {
+    let hex_str = public_key_hex.strip_prefix("0x").unwrap_or(public_key_hex);
+    let bytes = hex::decode(hex_str).map_err(|e| format!("invalid hex: {}", e))?;
+    PublicKey::from_sec1_bytes(&bytes).map_err(|e| format!("invalid public key: {}", e))
+}
+
+/// Encodes a public key as compressed SEC1 hex with "0x" prefix
+#[cfg(feature = "crypto")]
+pub fn public_key_to_hex(public_key: &PublicKey) -> String {
+    format!("0x{}", hex::encode(public_key.to_encoded_point(true).as_bytes()))
+}
+
+/// Computes HMAC-SHA256 of `data` under `key`
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    // HMAC accepts keys of any length, so this cannot fail
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// URL-safe alphabet; decoding accepts input with or without padding
+const BASE64URL: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Standard base64 with padding (RFC 4648)
+pub fn base64_encode(data: &[u8]) -> String {
+    STANDARD.encode(data)
+}
+
+/// URL-safe base64 without padding (RFC 4648 section 5)
+pub fn base64url_encode(data: &[u8]) -> String {
+    BASE64URL.encode(data)
+}
+
+/// Decodes URL-safe base64, with or without padding
+pub fn base64url_decode(encoded: &str) -> Result<Vec<u8>, String> {
+    BASE64URL.decode(encoded).map_err(|e| format!("invalid base64url: {}", e))
+}
+
+/// Computes Keccak-256 (the pre-standard SHA-3 variant used by Ethereum)
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    sha3::Keccak256::digest(data).into()
+}
+
+/// One-time cipher and nonce for an ECIES message
+#[cfg(feature = "crypto")]
+fn ecies_cipher(
+    shared: &SharedSecret,
+    ephemeral_pub: &[u8],
+) -> Result<(ChaCha20Poly1305, chacha20poly1305::Nonce), String> {
+    let mut okm = Zeroizing::new([0u8; 44]);
+    Hkdf::<Sha256>::new(Some(ephemeral_pub), shared.raw_secret_bytes())
+        .expand(b"ecash-sdk/ecies/v2", okm.as_mut_slice())
+        .map_err(|e| format!("key derivation failed: {}", e))?;
+    // `KeyInit` is not imported: its `new_from_slice` would clash with `Mac`'s
+    let cipher = <ChaCha20Poly1305 as chacha20poly1305::KeyInit>::new_from_slice(&okm[..32])
+        .map_err(|e| format!("invalid key: {}", e))?;
+    let mut nonce = chacha20poly1305::Nonce::default();
+    nonce.copy_from_slice(&okm[32..]);
+    Ok((cipher, nonce))
+}
+
+#[cfg(feature = "crypto")]
+fn symmetric_cipher(key: &[u8]) -> Result<XChaCha20Poly1305, String> {
+    <XChaCha20Poly1305 as chacha20poly1305::KeyInit>::new_from_slice(key)
+        .map_err(|_| format!("symmetric key must be 32 bytes, got {}", key.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "crypto")]
+    use k256::SecretKey;
+    #[cfg(feature = "crypto")]
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_base64url_round_trip() {
+        assert_eq!(base64url_encode(b"fo"), "Zm8");
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), "-_8");
+        for len in 0..8 {
+            let data: Vec<u8> = (0..len).map(|i| 0xf0u8.wrapping_add(i * 7)).collect();
+            assert_eq!(base64url_decode(&base64url_encode(&data)).unwrap(), data);
+        }
+        assert_eq!(base64url_decode("Zm8=").unwrap(), b"fo");
+        assert!(base64url_decode("Zm+8").is_err());
+        assert!(base64url_decode("Zm9vY").is_err());
+    }
+
+    #[test]
+    fn test_keccak256_vectors() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"abc")),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_transaction_signer_sign_and_verify() {
+        // Use a deterministic secret key for testing
+        let secret_key_bytes = [1u8; 32];
+        let secret_key = SecretKey::from_bytes(&secret_key_bytes.into()).unwrap();
+        let signer = TransactionSigner::new(secret_key);
+
+        let data = b"test message";
+        let signature = signer.sign_message(data).unwrap();
+
+        // Verify signature using the signer's verifying key
+        let is_valid = verify_signature(&signer.verifying_key(), data, &signature).unwrap();
+        assert!(is_valid);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_signature_deterministic() {
+        let secret_key_bytes = [1u8; 32];
+        let secret_key = SecretKey::from_bytes(&secret_key_bytes.into()).unwrap();
+        let signer = TransactionSigner::new(secret_key);
+
+        let data = b"test message";
+        let sig1 = signer.sign_message(data).unwrap();
+        let sig2 = signer.sign_message(data).unwrap();
+
+        // RFC 6979 nonces make signatures reproducible
+        assert_eq!(sig1, sig2);
+        assert!(verify_signature(&signer.verifying_key(), data, &sig1).unwrap());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_domain_signature_vectors() {
+        // Pinned so signatures stay reproducible across SDK versions
+        let signer = TransactionSigner::new(SecretKey::from_bytes(&[1u8; 32].into()).unwrap());
+        let vectors = [
+            (SigningDomain::Intent, "0xef27344f970759532db863c80cea2d51099830fa7b90ba7d2bafa397434cf185054efdeca70135c62ce21904bef8491be3d2a6239bc49350ff161907c2c8d176"),
+            (SigningDomain::Receipt, "0xba0857a2e81f5a92bdec15c0d346454df747bf8eb0c84c6ec5ab8f161f940dd52eb2a9000629d6df04f2a0fb2fb3213c99b90e3606748e05ce91f6d44778366d"),
+            (SigningDomain::Webhook, "0x1123a9b6164246b4fd403f67b555aa37e7c45ddccc33b55d3c7523431f5c80a725ce652667c1b7c95656c1714a72dba89df5e3f29f02b2fb70497ffff5360baa"),
+            (SigningDomain::Invoice, "0xeeb0b7198e757bc32592451d67bd4c6925b992cb1f28d8093c35a76d11dc7a4828774b61aae3c8f9607ceb56219f5b6e20736939e16ef7cf99ad8ba28d8f568d"),
+            (SigningDomain::ArtifactManifest, "0x2166fcd151c77bf30a505027786c566bf7db3f9937f1418a39424a6e6371c0ba2a6220d27339d0d1ae513022cd54aad26690f066b061488a84eba6c921ab20d0"),
+            (SigningDomain::Quote, "0x70e45c4cbf0f82295263d869685739a37a9875a164ea0cdaf3cdf9a7a7da5b3716eb08d84440622b9bb0a448708e1a58348bed067d61a2a2bb7ac482668d8655"),
+            (SigningDomain::Escrow, "0x04fddd00ad7da4d36eee0555fbeeaaae48afdba5228c3698ea3948ad8dbc3aa030d5dc549c9662b0b4e32efe87721f64824e9291044aac76e72944a87cfcf1a2"),
+            (SigningDomain::Sponsorship, "0xa471a0762d93f1105caa4423f29d5336f3171a37d8902397720740cefd433c7645bc755ae77be48e11c64d0a504071a05c342a153447cb31638ae5d22415e15b"),
+            (SigningDomain::PreparedIntent, "0x86011648e2e3e3bcf4d65421690ab597627933d90b043f4394ce8286b06fc4216a45a506b5f09d66a25157a7b6d961a6fef2fb5ccdf787607c3e3777835ce502"),
+        ];
+        for (domain, expected) in vectors {
+            let signature = signer.sign_in_domain(domain, b"payload").unwrap();
+            assert_eq!(signature, expected, "{:?}", domain);
+            let key = signer.verifying_key();
+            assert!(verify_signature_in_domain(HashFunction::Sha256, domain, &key, b"payload", &signature).unwrap());
+            assert!(!verify_signature(&key, b"payload", &signature).unwrap());
+        }
+        let keccak = signer.with_hash(HashFunction::Keccak256);
+        assert_eq!(keccak.sign_in_domain(SigningDomain::Receipt, b"payload").unwrap(), "0xd95415394d9608be511cd5f8ddbbc1593d9118dbb7a4ff2b383e17b24e64de34551ab9dcde4cfce1bb22c80eb2fb341a8793284f8c128dfe9789bd3a7213e633");
+        assert!(!verify_signature_in_domain(
+            HashFunction::Keccak256,
+            SigningDomain::Invoice,
+            &keccak.verifying_key(),
+            b"payload",
+            &keccak.sign_in_domain(SigningDomain::Receipt, b"payload").unwrap(),
+        )
+        .unwrap());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_signing_with_other_hash() {
+        let secret_key = SecretKey::from_bytes(&[1u8; 32].into()).unwrap();
+        let signer = TransactionSigner::new(secret_key).with_hash(HashFunction::Keccak256);
+        let key = signer.verifying_key();
+        let signature = signer.sign_message(b"test message").unwrap();
+
+        assert!(verify_signature_with(HashFunction::Keccak256, &key, b"test message", &signature).unwrap());
+        assert!(!verify_signature(&key, b"test message", &signature).unwrap());
+        assert!(!verify_signature_with(HashFunction::Blake2b256, &key, b"test message", &signature).unwrap());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_verify_signature_invalid_hex() {
+        let secret_key_bytes = [2u8; 32];
+        let secret_key = SecretKey::from_bytes(&secret_key_bytes.into()).unwrap();
+        let signer = TransactionSigner::new(secret_key);
+
+        let data = b"test message";
+        let invalid_sig = "not_valid_hex";
+
+        let result = verify_signature(&signer.verifying_key(), data, invalid_sig);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_verify_signature_wrong_length() {
+        let secret_key_bytes = [2u8; 32];
+        let secret_key = SecretKey::from_bytes(&secret_key_bytes.into()).unwrap();
+        let signer = TransactionSigner::new(secret_key);
+
+        let data = b"test message";
+        let short_sig = "0x1234567890abcdef";
+
+        let result = verify_signature(&signer.verifying_key(), data, short_sig);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("invalid signature length"));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_verify_signature_wrong_key() {
+        let secret_key_bytes1 = [1u8; 32];
+        let secret_key_bytes2 = [2u8; 32];
+
+        let secret_key1 = SecretKey::from_bytes(&secret_key_bytes1.into()).unwrap();
+        let secret_key2 = SecretKey::from_bytes(&secret_key_bytes2.into()).unwrap();
+
+        let signer1 = TransactionSigner::new(secret_key1);
+        let signer2 = TransactionSigner::new(secret_key2);
+
+        let data = b"test message";
+        let signature = signer1.sign_message(data).unwrap();
+
+        // Verify with wrong key should fail
+        let is_valid = verify_signature(&signer2.verifying_key(), data, &signature).unwrap();
+        assert!(!is_valid);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_ecies_round_trip() {
+        let secret_key = SecretKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let plaintext = b"originator: Alice, beneficiary: Bob";
+
+        let ciphertext = ecies_encrypt(&secret_key.public_key(), plaintext).unwrap();
+        assert_ne!(&ciphertext[COMPRESSED_KEY_LEN..COMPRESSED_KEY_LEN + plaintext.len()], plaintext);
+
+        let decrypted = ecies_decrypt(&secret_key, &ciphertext).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_ecies_rejects_tampering_and_wrong_key() {
+        let secret_key = SecretKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let other_key = SecretKey::from_bytes(&[8u8; 32].into()).unwrap();
+        let mut ciphertext = ecies_encrypt(&secret_key.public_key(), b"payload").unwrap();
+
+        assert!(ecies_decrypt(&other_key, &ciphertext).is_err());
+
+        ciphertext[COMPRESSED_KEY_LEN] ^= 0x01;
+        let err = ecies_decrypt(&secret_key, &ciphertext).unwrap_err();
+        assert!(err.contains("authentication failed"));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_symmetric_seal_round_trip() {
+        let key = [5u8; 32];
+        let sealed = seal_symmetric(&key, b"balance: 1000", b"ref_001").unwrap();
+        assert_eq!(open_symmetric(&key, &sealed, b"ref_001").unwrap(), b"balance: 1000");
+        assert_ne!(sealed, seal_symmetric(&key, b"balance: 1000", b"ref_001").unwrap());
+
+        assert!(open_symmetric(&key, &sealed, b"ref_002").is_err());
+        assert!(open_symmetric(&[6u8; 32], &sealed, b"ref_001").is_err());
+        assert!(seal_symmetric(&key[..16], b"", b"").is_err());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_public_key_hex_round_trip() {
+        let secret_key = SecretKey::from_bytes(&[3u8; 32].into()).unwrap();
+        let encoded = public_key_to_hex(&secret_key.public_key());
+        assert!(encoded.starts_with("0x02") || encoded.starts_with("0x03"));
+        assert_eq!(public_key_from_hex(&encoded).unwrap(), secret_key.public_key());
+    }
+
+    #[cfg(feature = "crypto")]
+    proptest! {
+        #[test]
+        fn test_verify_signature_rejects_malformed_input(
+            message in prop::collection::vec(any::<u8>(), 0..64),
+            bit in any::<prop::sample::Index>(),
+            cut in any::<prop::sample::Index>(),
+            garbage in prop::collection::vec(any::<u8>(), 0..80),
+        ) {
+            let signer = TransactionSigner::new(SecretKey::from_bytes(&[5u8; 32].into()).unwrap());
+            let key = signer.verifying_key();
+            let signature = signer.sign_message(&message).unwrap();
+            prop_assert_eq!(verify_signature(&key, &message, &signature), Ok(true));
+
+            // Flipped bits, other lengths and random bytes never verify (or panic)
+            let mut sig_bytes = hex::decode(&signature[2..]).unwrap();
+            let bit = bit.index(sig_bytes.len() * 8);
+            sig_bytes[bit / 8] ^= 1 << (bit % 8);
+            prop_assert_ne!(verify_signature(&key, &message, &hex::encode(&sig_bytes)), Ok(true));
+            sig_bytes.truncate(cut.index(sig_bytes.len()));
+            prop_assert!(verify_signature(&key, &message, &hex::encode(&sig_bytes)).is_err());
+            prop_assert_ne!(verify_signature(&key, &message, &String::from_utf8_lossy(&garbage)), Ok(true));
+            prop_assert_ne!(verify_signature(&key, &message, &hex::encode(&garbage)), Ok(true));
+            let _ = public_key_from_hex(&hex::encode(&garbage));
+        }
+    }
+}
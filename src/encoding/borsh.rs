@@ -0,0 +1,315 @@
+//! Borsh encoding (<https://borsh.io>) of intents and responses.
+//!
+//! Fields are written in declaration order: integers little-endian, strings
+//! as a u32 length and UTF-8 bytes, `Option` as a 0/1 byte followed by the
+//! value, enums as their variant index (`ChainId::Unknown` and
+//! `IntentType::Unknown` as 255). Borsh has no field names, so the schema is
+//! fixed per protocol version; unknown variant indexes decode as `Unknown`.
+//! Local attribution (`account_id`, `metadata`) is never sent to agents and
+//! has no Borsh encoding.
+
+use crate::travel_rule::{SealedTravelRule, TravelRuleInfo, TravelRuleParty, Vasp};
+use crate::types::{ChainId, IntentType, TransactionRequest, TransactionResponse};
+
+/// Index written for `Unknown` enum variants
+const UNKNOWN_VARIANT: u8 = 255;
+
+/// A type with a Borsh encoding
+pub trait BorshEncode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// A type that can be read from its Borsh encoding
+pub trait BorshDecode: Sized {
+    /// Reads a value from the front of `input`, advancing it
+    fn decode(input: &mut &[u8]) -> Result<Self, String>;
+}
+
+pub fn to_vec<T: BorshEncode + ?Sized>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.encode(&mut out);
+    out
+}
+
+/// Decodes `bytes`, which must hold exactly one value
+pub fn from_slice<T: BorshDecode>(mut bytes: &[u8]) -> Result<T, String> {
+    let value = T::decode(&mut bytes)?;
+    if !bytes.is_empty() {
+        return Err(format!("{} trailing bytes after Borsh value", bytes.len()));
+    }
+    Ok(value)
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if input.len() < len {
+        return Err("unexpected end of Borsh input".to_string());
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+impl BorshEncode for u8 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self);
+    }
+}
+
+impl BorshDecode for u8 {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        Ok(take(input, 1)?[0])
+    }
+}
+
+impl BorshEncode for u32 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl BorshDecode for u32 {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(take(input, 4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+}
+
+impl BorshEncode for u64 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+}
+
+impl BorshDecode for u64 {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(take(input, 8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+impl BorshEncode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(u8::from(*self));
+    }
+}
+
+impl BorshDecode for bool {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(format!("invalid Borsh bool: {}", other)),
+        }
+    }
+}
+
+impl BorshEncode for str {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (self.len() as u32).encode(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl BorshEncode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_str().encode(out);
+    }
+}
+
+impl BorshDecode for String {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        let len = u32::decode(input)? as usize;
+        let bytes = take(input, len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| "invalid UTF-8 in Borsh string".to_string())
+    }
+}
+
+impl<T: BorshEncode> BorshEncode for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Some(value) => {
+                out.push(1);
+                value.encode(out);
+            }
+            None => out.push(0),
+        }
+    }
+}
+
+impl<T: BorshDecode> BorshDecode for Option<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        match u8::decode(input)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(input)?)),
+            other => Err(format!("invalid Borsh option tag: {}", other)),
+        }
+    }
+}
+
+impl BorshEncode for ChainId {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(match self {
+            ChainId::Ethereum => 0,
+            ChainId::Base => 1,
+            ChainId::Solana => 2,
+            ChainId::Unknown => UNKNOWN_VARIANT,
+        });
+    }
+}
+
+impl BorshDecode for ChainId {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        Ok(match u8::decode(input)? {
+            0 => ChainId::Ethereum,
+            1 => ChainId::Base,
+            2 => ChainId::Solana,
+            _ => ChainId::Unknown,
+        })
+    }
+}
+
+impl BorshEncode for IntentType {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(match self {
+            IntentType::Transfer => 0,
+            IntentType::Swap => 1,
+            IntentType::Shield => 2,
+            IntentType::Disburse => 3,
+            IntentType::Escrow => 4,
+            IntentType::Unknown => UNKNOWN_VARIANT,
+        });
+    }
+}
+
+impl BorshDecode for IntentType {
+    fn decode(input: &mut &[u8]) -> Result<Self, String> {
+        Ok(match u8::decode(input)? {
+            0 => IntentType::Transfer,
+            1 => IntentType::Swap,
+            2 => IntentType::Shield,
+            3 => IntentType::Disburse,
+            4 => IntentType::Escrow,
+            _ => IntentType::Unknown,
+        })
+    }
+}
+
+/// Implements both traits for a struct from its field list, in order. Fields
+/// listed under `local` are not encoded and decode as their default.
+macro_rules! borsh_struct {
+    ($ty:ty { $($field:ident),* $(,)? } local { $($local:ident),* $(,)? }) => {
+        impl BorshEncode for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                $(self.$field.encode(out);)*
+            }
+        }
+
+        impl BorshDecode for $ty {
+            fn decode(input: &mut &[u8]) -> Result<Self, String> {
+                Ok(Self {
+                    $($field: BorshDecode::decode(input)?,)*
+                    $($local: Default::default(),)*
+                })
+            }
+        }
+    };
+    ($ty:ty { $($field:ident),* $(,)? }) => {
+        borsh_struct!($ty { $($field),* } local {});
+    };
+}
+
+borsh_struct!(TravelRuleParty {
+    name,
+    account_number,
+    geographic_address,
+    national_identifier,
+    date_of_birth,
+    country_of_residence,
+});
+
+borsh_struct!(Vasp { name, lei, public_key });
+
+borsh_struct!(TravelRuleInfo {
+    originator,
+    beneficiary,
+    originating_vasp,
+    beneficiary_vasp,
+});
+
+borsh_struct!(SealedTravelRule { beneficiary_vasp, ciphertext });
+
+borsh_struct!(TransactionRequest {
+    reference_id,
+    intent_type,
+    amount,
+    asset,
+    recipient,
+    source_chain,
+    target_chain,
+    is_shielded,
+    travel_rule,
+    correlation_id,
+    amount_base_units,
+    sealed_travel_rule,
+    quote_hash,
+} local {
+    disbursements,
+    fee_splits,
+    escrow,
+    fee_payer,
+    account_id,
+    metadata,
+});
+
+borsh_struct!(TransactionResponse {
+    tx_hash,
+    status,
+    block_height,
+    fee_used,
+    correlation_id,
+} local {
+    disbursements,
+    fee_splits,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(to_vec("ab"), [2, 0, 0, 0, b'a', b'b']);
+        assert_eq!(to_vec(&Some(7u64)), [1, 7, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(to_vec(&None::<u64>), [0]);
+        assert_eq!(to_vec(&ChainId::Unknown), [255]);
+        assert_eq!(from_slice::<ChainId>(&[9]).unwrap(), ChainId::Unknown);
+
+        assert!(from_slice::<String>(&[5, 0, 0, 0, b'a']).unwrap_err().contains("end of Borsh input"));
+        assert!(from_slice::<bool>(&[2]).is_err());
+        assert!(from_slice::<u8>(&[1, 2]).unwrap_err().contains("trailing"));
+    }
+
+    #[test]
+    fn test_travel_rule_round_trip() {
+        let info = TravelRuleInfo {
+            originator: TravelRuleParty {
+                name: "Alice".to_string(),
+                country_of_residence: Some("DE".to_string()),
+                ..Default::default()
+            },
+            beneficiary: TravelRuleParty {
+                name: "Bob".to_string(),
+                ..Default::default()
+            },
+            originating_vasp: None,
+            beneficiary_vasp: Some(Vasp {
+                name: "VASP B".to_string(),
+                lei: None,
+                public_key: Some("02ab".to_string()),
+            }),
+        };
+        assert_eq!(from_slice::<TravelRuleInfo>(&to_vec(&info)).unwrap(), info);
+    }
+}
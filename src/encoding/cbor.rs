@@ -0,0 +1,309 @@
+//! CBOR encoding (RFC 8949) of intents and responses.
+//!
+//! Values are encoded from their serde (JSON) form, so field names and enum
+//! spellings match the JSON API. Output follows the core deterministic
+//! encoding rules: shortest-form lengths and map keys sorted by their encoded
+//! bytes, so equal values always encode to the same bytes. Decoding also
+//! accepts indefinite lengths, tags (ignored) and half/single floats.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+/// Nesting depth beyond which decoding gives up
+const MAX_DEPTH: usize = 64;
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+const TAG: u8 = 6;
+const SIMPLE: u8 = 7;
+
+/// Additional-info value marking an indefinite length
+const INDEFINITE: u8 = 31;
+const BREAK: u8 = 0xff;
+
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, String> {
+    let value = serde_json::to_value(value).map_err(|e| format!("failed to encode CBOR: {}", e))?;
+    let mut out = Vec::new();
+    encode_value(&value, &mut out);
+    Ok(out)
+}
+
+/// Decodes `bytes`, which must hold exactly one data item
+pub fn from_slice<T: DeserializeOwned>(mut bytes: &[u8]) -> Result<T, String> {
+    let value = decode_value(&mut bytes, 0)?;
+    if !bytes.is_empty() {
+        return Err(format!("{} trailing bytes after CBOR item", bytes.len()));
+    }
+    serde_json::from_value(value).map_err(|e| format!("failed to decode CBOR: {}", e))
+}
+
+fn encode_head(major: u8, arg: u64, out: &mut Vec<u8>) {
+    let major = major << 5;
+    match arg {
+        0..=23 => out.push(major | arg as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, arg as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(arg as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(arg as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&arg.to_be_bytes());
+        }
+    }
+}
+
+fn encode_text(text: &str, out: &mut Vec<u8>) {
+    encode_head(TEXT, text.len() as u64, out);
+    out.extend_from_slice(text.as_bytes());
+}
+
+fn encode_value(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                encode_head(UNSIGNED, n, out);
+            } else if let Some(n) = n.as_i64() {
+                // -1 - arg == n
+                encode_head(NEGATIVE, !(n as u64), out);
+            } else {
+                out.push(0xfb);
+                out.extend_from_slice(&n.as_f64().unwrap_or(f64::NAN).to_be_bytes());
+            }
+        }
+        Value::String(s) => encode_text(s, out),
+        Value::Array(items) => {
+            encode_head(ARRAY, items.len() as u64, out);
+            for item in items {
+                encode_value(item, out);
+            }
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(Vec<u8>, &Value)> = map
+                .iter()
+                .map(|(key, value)| {
+                    let mut encoded = Vec::with_capacity(key.len() + 1);
+                    encode_text(key, &mut encoded);
+                    (encoded, value)
+                })
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            encode_head(MAP, entries.len() as u64, out);
+            for (key, value) in entries {
+                out.extend_from_slice(&key);
+                encode_value(value, out);
+            }
+        }
+    }
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], String> {
+    if input.len() < len {
+        return Err("unexpected end of CBOR input".to_string());
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+/// Reads the argument of a head with additional info `info`
+fn read_arg(input: &mut &[u8], info: u8) -> Result<u64, String> {
+    Ok(match info {
+        0..=23 => u64::from(info),
+        24 => u64::from(take(input, 1)?[0]),
+        25 => {
+            let mut bytes = [0u8; 2];
+            bytes.copy_from_slice(take(input, 2)?);
+            u64::from(u16::from_be_bytes(bytes))
+        }
+        26 => {
+            let mut bytes = [0u8; 4];
+            bytes.copy_from_slice(take(input, 4)?);
+            u64::from(u32::from_be_bytes(bytes))
+        }
+        27 => {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(take(input, 8)?);
+            u64::from_be_bytes(bytes)
+        }
+        _ => return Err(format!("invalid CBOR additional info {}", info)),
+    })
+}
+
+fn at_break(input: &mut &[u8]) -> bool {
+    if input.first() == Some(&BREAK) {
+        *input = &input[1..];
+        return true;
+    }
+    false
+}
+
+/// Text string, definite or as indefinite-length chunks
+fn decode_text(input: &mut &[u8], info: u8) -> Result<String, String> {
+    let bytes = if info == INDEFINITE {
+        let mut bytes = Vec::new();
+        while !at_break(input) {
+            let head = take(input, 1)?[0];
+            if head >> 5 != TEXT || head & 31 == INDEFINITE {
+                return Err("invalid chunk in indefinite CBOR text string".to_string());
+            }
+            let len = read_arg(input, head & 31)?;
+            bytes.extend_from_slice(take(input, usize::try_from(len).map_err(|_| "CBOR string too long")?)?);
+        }
+        bytes
+    } else {
+        let len = read_arg(input, info)?;
+        take(input, usize::try_from(len).map_err(|_| "CBOR string too long")?)?.to_vec()
+    };
+    String::from_utf8(bytes).map_err(|_| "invalid UTF-8 in CBOR text string".to_string())
+}
+
+fn number(value: f64) -> Result<Value, String> {
+    Number::from_f64(value)
+        .map(Value::Number)
+        .ok_or_else(|| "non-finite CBOR floats are not supported".to_string())
+}
+
+/// IEEE 754 half-precision bits to f64
+fn half_to_f64(bits: u16) -> f64 {
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f64::from(bits & 0x3ff);
+    let magnitude = match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (mantissa + 1024.0) * 2f64.powi(exponent - 25),
+    };
+    if bits & 0x8000 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+fn decode_value(input: &mut &[u8], depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err("CBOR nesting too deep".to_string());
+    }
+    let head = take(input, 1)?[0];
+    let (major, info) = (head >> 5, head & 31);
+    match major {
+        UNSIGNED => Ok(Value::from(read_arg(input, info)?)),
+        NEGATIVE => {
+            let arg = read_arg(input, info)?;
+            i64::try_from(arg)
+                .map(|arg| Value::from(-1 - arg))
+                .map_err(|_| "CBOR negative integer out of range".to_string())
+        }
+        BYTES => Err("CBOR byte strings are not supported".to_string()),
+        TEXT => decode_text(input, info).map(Value::String),
+        ARRAY => {
+            let mut items = Vec::new();
+            if info == INDEFINITE {
+                while !at_break(input) {
+                    items.push(decode_value(input, depth + 1)?);
+                }
+            } else {
+                for _ in 0..read_arg(input, info)? {
+                    items.push(decode_value(input, depth + 1)?);
+                }
+            }
+            Ok(Value::Array(items))
+        }
+        MAP => {
+            let mut map = Map::new();
+            let mut entry = |input: &mut &[u8]| -> Result<(), String> {
+                let key = match decode_value(input, depth + 1)? {
+                    Value::String(key) => key,
+                    other => return Err(format!("unsupported CBOR map key: {}", other)),
+                };
+                map.insert(key, decode_value(input, depth + 1)?);
+                Ok(())
+            };
+            if info == INDEFINITE {
+                while !at_break(input) {
+                    entry(input)?;
+                }
+            } else {
+                for _ in 0..read_arg(input, info)? {
+                    entry(input)?;
+                }
+            }
+            Ok(Value::Object(map))
+        }
+        TAG => {
+            read_arg(input, info)?;
+            decode_value(input, depth + 1)
+        }
+        SIMPLE => match info {
+            20 => Ok(Value::Bool(false)),
+            21 => Ok(Value::Bool(true)),
+            // null and undefined
+            22 | 23 => Ok(Value::Null),
+            25 => number(half_to_f64(read_arg(input, info)? as u16)),
+            26 => number(f64::from(f32::from_bits(read_arg(input, info)? as u32))),
+            27 => number(f64::from_bits(read_arg(input, info)?)),
+            _ => Err(format!("unsupported CBOR simple value {}", info)),
+        },
+        _ => unreachable!("major type is three bits"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_cbor(value: &Value) -> String {
+        hex::encode(to_vec(value).unwrap())
+    }
+
+    #[test]
+    fn test_rfc8949_examples() {
+        // Appendix A of RFC 8949
+        assert_eq!(hex_cbor(&serde_json::json!(0)), "00");
+        assert_eq!(hex_cbor(&serde_json::json!(24)), "1818");
+        assert_eq!(hex_cbor(&serde_json::json!(1000000)), "1a000f4240");
+        assert_eq!(hex_cbor(&serde_json::json!(-1000)), "3903e7");
+        assert_eq!(hex_cbor(&serde_json::json!(1.1)), "fb3ff199999999999a");
+        assert_eq!(hex_cbor(&serde_json::json!("IETF")), "6449455446");
+        assert_eq!(hex_cbor(&serde_json::json!([1, [2, 3]])), "8201820203");
+        assert_eq!(hex_cbor(&serde_json::json!({"a": 1, "b": [2, 3]})), "a26161016162820203");
+
+        let decode = |h: &str| from_slice::<Value>(&hex::decode(h).unwrap());
+        assert_eq!(decode("f93c00").unwrap(), serde_json::json!(1.0));
+        assert_eq!(decode("fa47c35000").unwrap(), serde_json::json!(100000.0));
+        assert_eq!(decode("7f657374726561646d696e67ff").unwrap(), serde_json::json!("streaming"));
+        assert_eq!(decode("9f018202039f0405ffff").unwrap(), serde_json::json!([1, [2, 3], [4, 5]]));
+        assert_eq!(decode("bf6346756ef563416d7421ff").unwrap(), serde_json::json!({"Fun": true, "Amt": -2}));
+        assert_eq!(decode("c074323031332d30332d32315432303a30343a30305a").unwrap(), "2013-03-21T20:04:00Z");
+    }
+
+    #[test]
+    fn test_deterministic_key_order() {
+        // Shorter keys sort first, then bytewise
+        let value = serde_json::json!({"bb": 1, "a": 2, "c": 3});
+        assert_eq!(hex_cbor(&value), "a361610261630362626201");
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let decode = |bytes: &[u8]| from_slice::<Value>(bytes).unwrap_err();
+        assert!(decode(&[0x62, b'a']).contains("end of CBOR input"));
+        assert!(decode(&[0x41, 0x00]).contains("byte strings"));
+        assert!(decode(&[0x01, 0x02]).contains("trailing"));
+        assert!(decode(&[0xa1, 0x01, 0x02]).contains("map key"));
+        assert!(decode(&[0x81; 100]).contains("too deep"));
+    }
+}
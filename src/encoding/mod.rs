@@ -0,0 +1,95 @@
+//! Binary encodings of intents and responses.
+//!
+//! JSON stays the canonical form: `receipt::intent_hash` is computed from the
+//! decoded fields, so a request hashes the same whether it arrived as JSON,
+//! Borsh (`borsh` feature) or CBOR (`cbor` feature).
+
+#[cfg(feature = "borsh")]
+pub mod borsh;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+
+#[cfg(test)]
+mod tests {
+    use crate::receipt::intent_hash;
+    use crate::types::{ChainId, IntentType, TransactionRequest, TransactionResponse};
+
+    /// Request and response whose encodings are pinned below
+    fn golden_request() -> TransactionRequest {
+        TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_target_chain(ChainId::Ethereum)
+            .with_amount_base_units("1000000000")
+    }
+
+    fn golden_response() -> TransactionResponse {
+        TransactionResponse {
+            tx_hash: "0xabc".to_string(),
+            status: "confirmed".to_string(),
+            block_height: 1948201,
+            fee_used: "0.05 USDC".to_string(),
+            correlation_id: "c-1".to_string(),
+            disbursements: Vec::new(),
+            fee_splits: Vec::new(),
+        }
+    }
+
+    const GOLDEN_INTENT_HASH: &str = "0xe1a13da7f6282d743c46c1287c50f9a46bfb25d37d91fd53199778bcad938e6f";
+
+    #[test]
+    fn test_golden_intent_hash() {
+        let json = serde_json::to_vec(&golden_request()).unwrap();
+        let decoded: TransactionRequest = serde_json::from_slice(&json).unwrap();
+        assert_eq!(intent_hash(&decoded), GOLDEN_INTENT_HASH);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh_golden() {
+        use super::borsh;
+
+        let bytes = borsh::to_vec(&golden_request());
+        assert_eq!(
+            hex::encode(&bytes),
+            "070000007265665f3030310007000000313030302e3030040000005553444301\
+             2a000000307837343264333543633636333443303533323932356133623834344263396537353935663062456230\
+             010100000000010a000000313030303030303030300000"
+        );
+        let decoded: TransactionRequest = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(intent_hash(&decoded), GOLDEN_INTENT_HASH);
+        assert_eq!(decoded.amount_base_units, golden_request().amount_base_units);
+
+        let bytes = borsh::to_vec(&golden_response());
+        assert_eq!(
+            hex::encode(&bytes),
+            "05000000307861626309000000636f6e6669726d656429ba1d000000000009000000302e3035205553444303000000632d31"
+        );
+        assert_eq!(borsh::from_slice::<TransactionResponse>(&bytes).unwrap(), golden_response());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_cbor_golden() {
+        use super::cbor;
+
+        let bytes = cbor::to_vec(&golden_request()).unwrap();
+        assert_eq!(
+            hex::encode(&bytes),
+            "a96474797065687472616e73666572656173736574645553444366616d6f756e7467313030302e303069726563697069656e74\
+             782a3078373432643335436336363334433035333239323561336238343442633965373539356630624562306b69735f736869\
+             656c646564f46c7265666572656e63655f6964677265665f3030316c736f757263655f636861696e64626173656c7461726765\
+             745f636861696e68657468657265756d71616d6f756e745f626173655f756e6974736a31303030303030303030"
+        );
+        let decoded: TransactionRequest = cbor::from_slice(&bytes).unwrap();
+        assert_eq!(intent_hash(&decoded), GOLDEN_INTENT_HASH);
+        assert_eq!(decoded.amount_base_units, golden_request().amount_base_units);
+
+        let bytes = cbor::to_vec(&golden_response()).unwrap();
+        assert_eq!(
+            hex::encode(&bytes),
+            "a56673746174757369636f6e6669726d65646774785f68617368653078616263686665655f7573656469302e303520555344\
+             436c626c6f636b5f6865696768741a001dba296e636f7272656c6174696f6e5f696463632d31"
+        );
+        assert_eq!(cbor::from_slice::<TransactionResponse>(&bytes).unwrap(), golden_response());
+    }
+}
@@ -0,0 +1,87 @@
+//! Deployment environments.
+//!
+//! `SdkConfig::environment` selects mainnet or one of the sandboxes. The
+//! environment picks the default API endpoint (`SdkConfig::endpoint`), the
+//! EVM chain IDs transactions are signed for, and whether the sandbox
+//! helpers (`sandbox`) are available. Testnet runs on the public test
+//! networks (Sepolia, Base Sepolia, Solana devnet); devnet is EasyCash's
+//! unstable staging network on the same chains.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "crypto")]
+use crate::types::ChainId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+impl Environment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Environment::Mainnet => "mainnet",
+            Environment::Testnet => "testnet",
+            Environment::Devnet => "devnet",
+        }
+    }
+
+    /// True for the environments settling with test funds
+    pub fn is_sandbox(&self) -> bool {
+        *self != Environment::Mainnet
+    }
+
+    /// EasyCash API of the environment
+    pub fn api_endpoint(&self) -> &'static str {
+        match self {
+            Environment::Mainnet => "https://api.useeasy.cash",
+            Environment::Testnet => "https://api.testnet.useeasy.cash",
+            Environment::Devnet => "https://api.devnet.useeasy.cash",
+        }
+    }
+
+    /// Faucet handing out test funds, in sandboxes only
+    pub fn faucet_endpoint(&self) -> Option<&'static str> {
+        match self {
+            Environment::Mainnet => None,
+            Environment::Testnet => Some("https://faucet.testnet.useeasy.cash/v1/funds"),
+            Environment::Devnet => Some("https://faucet.devnet.useeasy.cash/v1/funds"),
+        }
+    }
+
+    /// EIP-155 chain ID `chain` has in this environment
+    #[cfg(feature = "crypto")]
+    pub fn evm_chain_id(&self, chain: ChainId) -> Option<u64> {
+        match (self, chain) {
+            (Environment::Mainnet, chain) => crate::evm::chain_id(chain),
+            (_, ChainId::Ethereum) => Some(11_155_111),
+            (_, ChainId::Base) => Some(84_532),
+            (_, ChainId::Solana | ChainId::Unknown) => None,
+        }
+    }
+}
+
+impl FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" => Ok(Environment::Mainnet),
+            "testnet" => Ok(Environment::Testnet),
+            "devnet" => Ok(Environment::Devnet),
+            other => Err(format!("invalid environment: {} (must be mainnet, testnet, or devnet)", other)),
+        }
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
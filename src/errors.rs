@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Standardized error codes
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Error, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ErrorCode {
     #[error("INVALID_REQUEST")]
     InvalidRequest,
@@ -10,11 +13,79 @@ pub enum ErrorCode {
     #[error("NETWORK_FAILURE")]
     NetworkFailure,
     #[error("PROOF_GENERATION_FAILED")]
+    #[serde(rename = "PROOF_GENERATION_FAILED")]
     ProofGeneration,
     #[error("AGENT_UNAVAILABLE")]
     AgentUnavailable,
     #[error("TIMEOUT")]
     Timeout,
+    #[error("RATE_LIMITED")]
+    RateLimited,
+    #[error("FEE_TOO_HIGH")]
+    FeeTooHigh,
+    #[error("UNSUPPORTED_CHAIN")]
+    UnsupportedChain,
+    #[error("UNSUPPORTED_ASSET")]
+    UnsupportedAsset,
+    #[error("DUPLICATE_REFERENCE")]
+    DuplicateReference,
+    #[error("COMPLIANCE_REJECTED")]
+    ComplianceRejected,
+    #[error("POLICY_VIOLATION")]
+    PolicyViolation,
+    #[error("BUDGET_EXCEEDED")]
+    BudgetExceeded,
+    #[error("EXPIRED")]
+    Expired,
+    #[error("SIGNER_UNAVAILABLE")]
+    SignerUnavailable,
+    #[error("UNSUPPORTED_PROTOCOL")]
+    UnsupportedProtocol,
+    #[error("CANCELLED")]
+    Cancelled,
+    /// The transaction was included on-chain but reverted
+    #[error("EXECUTION_REVERTED")]
+    ExecutionReverted,
+}
+
+impl ErrorCode {
+    /// Returns true if retrying the same request later may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::NetworkFailure
+                | ErrorCode::AgentUnavailable
+                | ErrorCode::Timeout
+                | ErrorCode::RateLimited
+                | ErrorCode::SignerUnavailable
+        )
+    }
+
+    /// Maps the error code to the HTTP status an API gateway should return
+    pub fn to_http_status(&self) -> u16 {
+        match self {
+            ErrorCode::InvalidRequest => 400,
+            ErrorCode::UnsupportedChain => 400,
+            ErrorCode::UnsupportedAsset => 400,
+            ErrorCode::ComplianceRejected => 403,
+            ErrorCode::PolicyViolation => 403,
+            ErrorCode::BudgetExceeded => 403,
+            ErrorCode::DuplicateReference => 409,
+            ErrorCode::Expired => 410,
+            ErrorCode::InsufficientFunds => 422,
+            ErrorCode::FeeTooHigh => 422,
+            ErrorCode::ExecutionReverted => 422,
+            ErrorCode::RateLimited => 429,
+            // Client closed request
+            ErrorCode::Cancelled => 499,
+            ErrorCode::ProofGeneration => 500,
+            ErrorCode::NetworkFailure => 502,
+            ErrorCode::UnsupportedProtocol => 502,
+            ErrorCode::AgentUnavailable => 503,
+            ErrorCode::SignerUnavailable => 503,
+            ErrorCode::Timeout => 504,
+        }
+    }
 }
 
 /// Structured error type for better error handling
@@ -23,6 +94,10 @@ pub enum ErrorCode {
 pub struct SdkError {
     pub code: ErrorCode,
     pub message: String,
+    /// Machine-readable context (e.g. the violated policy); `Null` when absent
+    pub details: serde_json::Value,
+    /// Correlation ID of the `execute_transaction` call that produced the error
+    pub correlation_id: Option<String>,
     #[source]
     pub cause: Option<anyhow::Error>,
 }
@@ -33,6 +108,8 @@ impl SdkError {
         Self {
             code,
             message: message.into(),
+            details: serde_json::Value::Null,
+            correlation_id: None,
             cause: None,
         }
     }
@@ -42,14 +119,84 @@ impl SdkError {
         Self {
             code,
             message: message.into(),
+            details: serde_json::Value::Null,
+            correlation_id: None,
             cause: Some(cause.into()),
         }
     }
+
+    /// Attaches machine-readable details to the error
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// Tags the error with the correlation ID of the call that produced it
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Returns true if retrying the same request later may succeed
+    pub fn is_retryable(&self) -> bool {
+        self.code.is_retryable()
+    }
+
+    /// Maps the error to the HTTP status an API gateway should return
+    pub fn to_http_status(&self) -> u16 {
+        self.code.to_http_status()
+    }
 }
 
 // Convenience type alias
 pub type Result<T> = std::result::Result<T, SdkError>;
 
+/// Stable JSON representation of an `SdkError` for returning from web services
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SdkErrorResponse {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+    pub retryable: bool,
+    #[serde(rename = "correlation_id", default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl SdkErrorResponse {
+    /// Maps the error to the HTTP status an API gateway should return
+    pub fn http_status(&self) -> u16 {
+        self.code.to_http_status()
+    }
+}
+
+impl From<&SdkError> for SdkErrorResponse {
+    fn from(err: &SdkError) -> Self {
+        Self {
+            code: err.code,
+            message: err.message.clone(),
+            details: err.details.clone(),
+            retryable: err.is_retryable(),
+            correlation_id: err.correlation_id.clone(),
+        }
+    }
+}
+
+impl From<SdkError> for SdkErrorResponse {
+    fn from(err: SdkError) -> Self {
+        Self::from(&err)
+    }
+}
+
+impl From<SdkErrorResponse> for SdkError {
+    fn from(resp: SdkErrorResponse) -> Self {
+        let mut err = SdkError::new(resp.code, resp.message).with_details(resp.details);
+        err.correlation_id = resp.correlation_id;
+        err
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +225,69 @@ mod tests {
         assert!(display.contains("TIMEOUT"));
         assert!(display.contains("request timeout"));
     }
+
+    #[test]
+    fn test_error_retryability() {
+        assert!(SdkError::new(ErrorCode::Timeout, "t").is_retryable());
+        assert!(SdkError::new(ErrorCode::RateLimited, "r").is_retryable());
+        assert!(!SdkError::new(ErrorCode::InvalidRequest, "i").is_retryable());
+        assert!(!SdkError::new(ErrorCode::ComplianceRejected, "c").is_retryable());
+        assert!(!SdkError::new(ErrorCode::ExecutionReverted, "e").is_retryable());
+    }
+
+    #[test]
+    fn test_error_http_status() {
+        assert_eq!(SdkError::new(ErrorCode::InvalidRequest, "").to_http_status(), 400);
+        assert_eq!(SdkError::new(ErrorCode::DuplicateReference, "").to_http_status(), 409);
+        assert_eq!(SdkError::new(ErrorCode::RateLimited, "").to_http_status(), 429);
+        assert_eq!(SdkError::new(ErrorCode::Cancelled, "").to_http_status(), 499);
+        assert_eq!(SdkError::new(ErrorCode::AgentUnavailable, "").to_http_status(), 503);
+    }
+
+    #[test]
+    fn test_error_details() {
+        let err = SdkError::new(ErrorCode::PolicyViolation, "limit exceeded")
+            .with_details(serde_json::json!({"policy": "usdc-daily"}));
+        assert_eq!(err.details["policy"], "usdc-daily");
+        assert!(SdkError::new(ErrorCode::Timeout, "t").details.is_null());
+    }
+
+    #[test]
+    fn test_error_code_serialization() {
+        assert_eq!(serde_json::to_string(&ErrorCode::RateLimited).unwrap(), "\"RATE_LIMITED\"");
+        assert_eq!(
+            serde_json::to_string(&ErrorCode::ProofGeneration).unwrap(),
+            format!("\"{}\"", ErrorCode::ProofGeneration)
+        );
+    }
+
+    #[test]
+    fn test_error_response_round_trip() {
+        let err = SdkError::new(ErrorCode::AgentUnavailable, "no agents")
+            .with_details(serde_json::json!({"agents_tried": 3}));
+        let resp = SdkErrorResponse::from(&err);
+        assert!(resp.retryable);
+        assert_eq!(resp.http_status(), 503);
+
+        let json = serde_json::to_string(&resp).unwrap();
+        assert!(json.contains("\"code\":\"AGENT_UNAVAILABLE\""));
+        assert!(!json.contains("correlation_id"));
+
+        let decoded: SdkErrorResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, resp);
+
+        let restored = SdkError::from(decoded);
+        assert_eq!(restored.code, ErrorCode::AgentUnavailable);
+        assert_eq!(restored.details["agents_tried"], 3);
+    }
+
+    #[test]
+    fn test_error_response_carries_correlation_id() {
+        let err = SdkError::new(ErrorCode::Timeout, "slow").with_correlation_id("corr-123");
+        let json = serde_json::to_value(SdkErrorResponse::from(err)).unwrap();
+        assert_eq!(json["correlation_id"], "corr-123");
+
+        let restored = SdkError::from(serde_json::from_value::<SdkErrorResponse>(json).unwrap());
+        assert_eq!(restored.correlation_id.as_deref(), Some("corr-123"));
+    }
 }
@@ -0,0 +1,610 @@
+//! Escrowed payments.
+//!
+//! An `Escrow` request locks its amount with the executing agent instead of
+//! paying the recipient. The funds stay locked until
+//! `EasyCashClient::release_escrow` pays them to the recipient, which is
+//! allowed once the escrow's `ReleaseCondition` is met, or
+//! `EasyCashClient::refund_escrow` returns them to the payer after the
+//! refund deadline. The `EscrowManager` tracks every escrow and reserves a
+//! release or refund while it executes, so only one of them can happen.
+//!
+//! Counterparties sign an escrow's `release_message`, and oracles its
+//! `attestation_message`, in the `SigningDomain::Escrow` domain.
+//!
+//! Hash-time-locked escrows (`EscrowTerms::htlc`) release to whoever reveals
+//! the secret behind their hashlock (`EasyCashClient::reveal_secret`) and
+//! refund once their timelock expires. The revealed secret is recorded on the
+//! escrow, so a counterparty outside the agent network can use it to claim
+//! the other leg of a cross-chain swap locked under the same hash.
+//! `EscrowRefunds` refunds expired escrows in the background.
+//!
+//! ```
+//! use k256::PublicKey;
+//! use k256::SecretKey;
+//! use ecash_sdk_core::crypto::{self, SigningDomain, TransactionSigner};
+//! use ecash_sdk_core::escrow::{Escrow, EscrowManager, EscrowTerms, InMemoryEscrowStore, ReleaseCondition};
+//! use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+//! use std::sync::Arc;
+//!
+//! let buyer = TransactionSigner::new(SecretKey::from_bytes(&[3u8; 32].into()).unwrap());
+//! let terms = EscrowTerms::new(ReleaseCondition::CounterpartySignature {
+//!     public_key: crypto::public_key_to_hex(&PublicKey::from(&buyer.verifying_key())),
+//! })
+//!.with_refund_after(4_102_444_800);
+//! let mut req = TransactionRequest::new("order_42", IntentType::Escrow, "250", "USDC", ChainId::Base)
+//!     .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+//! terms.attach(&mut req);
+//!
+//! // Once the agent has locked the funds
+//! let escrows = EscrowManager::new(Arc::new(InMemoryEscrowStore::new()));
+//! escrows.record_lock(Escrow::new(&req, terms, "agent-001", "0xlock").unwrap()).unwrap();
+//!
+//! // The buyer confirms delivery by signing the release
+//! let escrow = escrows.escrow("order_42").unwrap().unwrap();
+//! let signature = buyer.sign_in_domain(SigningDomain::Escrow, &escrow.release_message()).unwrap();
+//! assert!(escrows.begin_release("order_42", Some(&signature)).is_ok());
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "client")]
+use std::time::Duration;
+
+use k256::ecdsa::VerifyingKey;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::crypto::hash::HashFunction;
+use crate::crypto::{self, SigningDomain};
+use crate::types::{ChainId, TransactionRequest};
+
+pub use crate::types::{EscrowTerms, ReleaseCondition};
+
+/// Hex hash of `secret`, as used in `ReleaseCondition::Hashlock`
+pub fn hashlock(secret: &[u8], hash_function: HashFunction) -> String {
+    format!("0x{}", hex::encode(hash_function.hash(secret)))
+}
+
+/// Random 32-byte secret for a hashlock
+pub fn generate_secret() -> [u8; 32] {
+    let mut secret = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut secret);
+    secret
+}
+
+impl EscrowTerms {
+    /// Hash-time-locked terms: released by revealing the SHA-256 preimage of
+    /// `hash`, refundable after Unix time `timelock` (seconds)
+    pub fn htlc(hash: impl Into<String>, timelock: u64) -> Self {
+        Self::new(ReleaseCondition::Hashlock {
+            hash: hash.into(),
+            hash_function: HashFunction::Sha256,
+        })
+        .with_refund_after(timelock)
+    }
+
+    /// Terms of an `Escrow` request
+    pub fn of(req: &TransactionRequest) -> Result<Self, String> {
+        req.escrow.clone().ok_or_else(|| "an escrow states its terms in escrow".to_string())
+    }
+
+    /// Sets the terms of `req`
+    pub fn attach(&self, req: &mut TransactionRequest) {
+        req.escrow = Some(self.clone());
+    }
+
+    /// Checks that the terms can still be met. Escrows released by a
+    /// signature or secret need a refund deadline, or the funds would stay
+    /// locked for good if it never comes.
+    pub fn validate(&self) -> Result<(), String> {
+        let now = now_secs();
+        match self.condition {
+            ReleaseCondition::Timeout { release_at } if release_at <= now => {
+                return Err("escrow release time has already passed".to_string());
+            }
+            ReleaseCondition::Timeout { .. } => {}
+            ReleaseCondition::CounterpartySignature { ref public_key }
+            | ReleaseCondition::OracleAttestation { ref public_key, .. } => {
+                crypto::public_key_from_hex(public_key).map_err(|e| format!("invalid release key: {}", e))?;
+                if self.refund_after.is_none() {
+                    return Err("escrows released by a signature need a refund deadline".to_string());
+                }
+            }
+            ReleaseCondition::Hashlock { ref hash, .. } => {
+                let bytes = hex::decode(hash.strip_prefix("0x").unwrap_or(hash))
+                    .map_err(|e| format!("invalid hashlock: {}", e))?;
+                if bytes.len() != 32 {
+                    return Err(format!("invalid hashlock: expected 32 bytes, got {}", bytes.len()));
+                }
+                if self.refund_after.is_none() {
+                    return Err("hash-time-locked escrows need a timelock (refund_after)".to_string());
+                }
+            }
+        }
+        if let ReleaseCondition::OracleAttestation { ref statement, .. } = self.condition {
+            if statement.trim().is_empty() {
+                return Err("oracle attestations need a statement".to_string());
+            }
+        }
+        match self.refund_after {
+            Some(refund_after) if refund_after <= now => Err("escrow refund deadline has already passed".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Where an escrow stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowState {
+    Locked,
+    /// A release is executing
+    Releasing,
+    Released,
+    /// A refund is executing
+    Refunding,
+    Refunded,
+}
+
+/// Funds locked by an `Escrow` request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Escrow {
+    /// Reference ID of the request that locked the funds
+    pub escrow_id: String,
+    /// Agent holding the funds; releases and refunds go through it
+    pub agent_id: String,
+    pub recipient: String,
+    pub amount: String,
+    pub asset: String,
+    pub chain: ChainId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    pub terms: EscrowTerms,
+    pub state: EscrowState,
+    pub lock_tx_hash: String,
+    /// Transaction that released or refunded the funds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settle_tx_hash: Option<String>,
+    /// Hex secret revealed to release a hash-time-locked escrow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub locked_at_ms: u64,
+}
+
+impl Escrow {
+    /// Escrow locked by `req` with `agent_id` in `lock_tx_hash`
+    pub fn new(req: &TransactionRequest, terms: EscrowTerms, agent_id: &str, lock_tx_hash: &str) -> Result<Self, String> {
+        let recipient = req.recipient.clone().ok_or("an escrow needs a recipient")?;
+        req.amount.parse::<Amount>()?;
+        Ok(Self {
+            escrow_id: req.reference_id.clone(),
+            agent_id: agent_id.to_string(),
+            recipient,
+            amount: req.amount.clone(),
+            asset: req.asset.clone(),
+            chain: req.target_chain.unwrap_or(req.source_chain),
+            account_id: req.account_id.clone(),
+            terms,
+            state: EscrowState::Locked,
+            lock_tx_hash: lock_tx_hash.to_string(),
+            settle_tx_hash: None,
+            secret: None,
+            locked_at_ms: now_secs().saturating_mul(1000),
+        })
+    }
+
+    /// Message the counterparty signs to release the funds
+    pub fn release_message(&self) -> Vec<u8> {
+        serde_json::json!({
+            "escrow_id": self.escrow_id,
+            "recipient": self.recipient,
+            "amount": self.amount,
+            "asset": self.asset,
+            "chain": self.chain,
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    /// Message the oracle signs to attest `statement` for this escrow
+    pub fn attestation_message(&self, statement: &str) -> Vec<u8> {
+        serde_json::json!({ "escrow_id": self.escrow_id, "statement": statement })
+            .to_string()
+            .into_bytes()
+    }
+
+    /// Checks that the release condition is met, given the counterparty's or
+    /// oracle's signature where one is needed
+    fn check_release(&self, signature: Option<&str>, now: u64) -> Result<(), String> {
+        let (public_key, message) = match self.terms.condition {
+            ReleaseCondition::Timeout { release_at } if release_at > now => {
+                return Err(format!("the escrow cannot be released before {}", release_at));
+            }
+            ReleaseCondition::Timeout { .. } => return Ok(()),
+            ReleaseCondition::CounterpartySignature { ref public_key } => (public_key, self.release_message()),
+            ReleaseCondition::OracleAttestation {
+                ref public_key,
+                ref statement,
+            } => (public_key, self.attestation_message(statement)),
+            ReleaseCondition::Hashlock { .. } => return Err("the escrow is released by revealing its secret".to_string()),
+        };
+        let signature = signature.ok_or("the release condition needs a signature")?;
+        let key = VerifyingKey::from(&crypto::public_key_from_hex(public_key)?);
+        match crypto::verify_signature_in_domain(HashFunction::Sha256, SigningDomain::Escrow, &key, &message, signature) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("the release signature is invalid".to_string()),
+            Err(e) => Err(format!("the release signature is malformed: {}", e)),
+        }
+    }
+}
+
+impl Escrow {
+    /// Checks `secret` against the escrow's hashlock
+    fn check_secret(&self, secret: &[u8]) -> Result<(), String> {
+        let ReleaseCondition::Hashlock { ref hash, hash_function } = self.terms.condition else {
+            return Err("the escrow has no hashlock".to_string());
+        };
+        match hashlock(secret, hash_function).eq_ignore_ascii_case(&format!("0x{}", hash.trim_start_matches("0x"))) {
+            true => Ok(()),
+            false => Err("the secret does not match the hashlock".to_string()),
+        }
+    }
+}
+
+/// Why a release or refund was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum EscrowRejection {
+    /// No escrow is recorded under the ID
+    NotFound { escrow_id: String },
+    /// The escrow was already released or refunded, or one is executing
+    NotLocked { escrow_id: String, state: EscrowState },
+    /// The release condition is not met
+    ConditionUnmet { escrow_id: String, reason: String },
+    /// The refund deadline has not passed (or the escrow has none)
+    RefundNotDue { escrow_id: String },
+    /// The store failed; the operation is refused to be safe
+    Unavailable(String),
+}
+
+impl std::fmt::Display for EscrowRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EscrowRejection::NotFound { escrow_id } => write!(f, "no escrow recorded for {}", escrow_id),
+            EscrowRejection::NotLocked { escrow_id, state } => {
+                write!(f, "escrow {} is not locked (state {:?})", escrow_id, state)
+            }
+            EscrowRejection::ConditionUnmet { escrow_id, reason } => {
+                write!(f, "escrow {} cannot be released: {}", escrow_id, reason)
+            }
+            EscrowRejection::RefundNotDue { escrow_id } => write!(f, "escrow {} cannot be refunded yet", escrow_id),
+            EscrowRejection::Unavailable(e) => write!(f, "escrow store unavailable: {}", e),
+        }
+    }
+}
+
+impl EscrowRejection {
+    /// Machine-readable reason, used in error details
+    pub fn reason(&self) -> &'static str {
+        match self {
+            EscrowRejection::NotFound { .. } => "escrow_not_found",
+            EscrowRejection::NotLocked { .. } => "escrow_not_locked",
+            EscrowRejection::ConditionUnmet { .. } => "escrow_condition_unmet",
+            EscrowRejection::RefundNotDue { .. } => "escrow_refund_not_due",
+            EscrowRejection::Unavailable(_) => "escrow_store_unavailable",
+        }
+    }
+}
+
+/// Persistent record of escrows, keyed by escrow ID
+pub trait EscrowStore: Send + Sync {
+    fn get(&self, escrow_id: &str) -> Result<Option<Escrow>, String>;
+
+    /// Inserts or replaces an escrow
+    fn put(&self, escrow: &Escrow) -> Result<(), String>;
+
+    /// Every escrow, in no particular order
+    fn all(&self) -> Result<Vec<Escrow>, String>;
+}
+
+/// In-memory escrow store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemoryEscrowStore {
+    escrows: Mutex<HashMap<String, Escrow>>,
+}
+
+impl InMemoryEscrowStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, Escrow>>, String> {
+        self.escrows.lock().map_err(|_| "escrow store poisoned".to_string())
+    }
+}
+
+impl EscrowStore for InMemoryEscrowStore {
+    fn get(&self, escrow_id: &str) -> Result<Option<Escrow>, String> {
+        Ok(self.lock()?.get(escrow_id).cloned())
+    }
+
+    fn put(&self, escrow: &Escrow) -> Result<(), String> {
+        self.lock()?.insert(escrow.escrow_id.clone(), escrow.clone());
+        Ok(())
+    }
+
+    fn all(&self) -> Result<Vec<Escrow>, String> {
+        Ok(self.lock()?.values().cloned().collect())
+    }
+}
+
+/// Tracks escrows and guards their release and refund, used by
+/// `EasyCashClient::with_escrows`.
+///
+/// Reservations are serialized per process; when several processes share a
+/// store, settle an escrow from one of them.
+pub struct EscrowManager {
+    store: Arc<dyn EscrowStore>,
+    lock: Mutex<()>,
+}
+
+impl EscrowManager {
+    pub fn new(store: Arc<dyn EscrowStore>) -> Self {
+        Self {
+            store,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Records funds locked by an `Escrow` request
+    pub fn record_lock(&self, escrow: Escrow) -> Result<(), String> {
+        self.store.put(&escrow)
+    }
+
+    pub fn escrow(&self, escrow_id: &str) -> Result<Option<Escrow>, String> {
+        self.store.get(escrow_id)
+    }
+
+    /// Checks the release condition with `signature` and reserves the
+    /// escrow for release
+    pub fn begin_release(&self, escrow_id: &str, signature: Option<&str>) -> Result<Escrow, EscrowRejection> {
+        self.reserve(escrow_id, EscrowState::Releasing, |escrow, now| {
+            escrow.check_release(signature, now).map_err(|reason| EscrowRejection::ConditionUnmet {
+                escrow_id: escrow_id.to_string(),
+                reason,
+            })
+        })
+    }
+
+    /// Checks `secret` against the hashlock, records it on the escrow and
+    /// reserves the escrow for release
+    pub fn begin_reveal(&self, escrow_id: &str, secret: &[u8]) -> Result<Escrow, EscrowRejection> {
+        self.reserve(escrow_id, EscrowState::Releasing, |escrow, _| {
+            escrow.check_secret(secret).map_err(|reason| EscrowRejection::ConditionUnmet {
+                escrow_id: escrow_id.to_string(),
+                reason,
+            })?;
+            escrow.secret = Some(format!("0x{}", hex::encode(secret)));
+            Ok(())
+        })
+    }
+
+    /// Locked escrows whose refund deadline has passed
+    pub fn expired(&self) -> Result<Vec<Escrow>, String> {
+        let now = now_secs();
+        let mut expired: Vec<Escrow> = self
+            .store
+            .all()?
+            .into_iter()
+            .filter(|e| e.state == EscrowState::Locked && e.terms.refund_after.is_some_and(|at| at <= now))
+            .collect();
+        expired.sort_by(|a, b| a.escrow_id.cmp(&b.escrow_id));
+        Ok(expired)
+    }
+
+    /// Checks that the refund deadline has passed and reserves the escrow
+    /// for refund
+    pub fn begin_refund(&self, escrow_id: &str) -> Result<Escrow, EscrowRejection> {
+        self.reserve(escrow_id, EscrowState::Refunding, |escrow, now| match escrow.terms.refund_after {
+            Some(refund_after) if refund_after <= now => Ok(()),
+            _ => Err(EscrowRejection::RefundNotDue {
+                escrow_id: escrow_id.to_string(),
+            }),
+        })
+    }
+
+    /// Marks a reserved release or refund as executed in `tx_hash`
+    pub fn complete(&self, escrow_id: &str, tx_hash: &str) -> Result<Escrow, String> {
+        self.update(escrow_id, |escrow| {
+            escrow.state = match escrow.state {
+                EscrowState::Releasing => EscrowState::Released,
+                EscrowState::Refunding => EscrowState::Refunded,
+                state => return Err(format!("escrow {} has no release or refund executing (state {:?})", escrow_id, state)),
+            };
+            escrow.settle_tx_hash = Some(tx_hash.to_string());
+            Ok(())
+        })
+    }
+
+    /// Returns an escrow whose release or refund failed to `Locked`
+    pub fn abort(&self, escrow_id: &str) -> Result<(), String> {
+        self.update(escrow_id, |escrow| {
+            if matches!(escrow.state, EscrowState::Releasing | EscrowState::Refunding) {
+                escrow.state = EscrowState::Locked;
+            }
+            Ok(())
+        })
+        .map(|_| ())
+    }
+
+    fn reserve(
+        &self,
+        escrow_id: &str,
+        next: EscrowState,
+        check: impl FnOnce(&mut Escrow, u64) -> Result<(), EscrowRejection>,
+    ) -> Result<Escrow, EscrowRejection> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut escrow = self
+            .store
+            .get(escrow_id)
+            .map_err(EscrowRejection::Unavailable)?
+            .ok_or_else(|| EscrowRejection::NotFound {
+                escrow_id: escrow_id.to_string(),
+            })?;
+        if escrow.state != EscrowState::Locked {
+            return Err(EscrowRejection::NotLocked {
+                escrow_id: escrow_id.to_string(),
+                state: escrow.state,
+            });
+        }
+        check(&mut escrow, now_secs())?;
+        escrow.state = next;
+        self.store.put(&escrow).map_err(EscrowRejection::Unavailable)?;
+        Ok(escrow)
+    }
+
+    fn update(&self, escrow_id: &str, change: impl FnOnce(&mut Escrow) -> Result<(), String>) -> Result<Escrow, String> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut escrow = self.store.get(escrow_id)?.ok_or_else(|| format!("no escrow recorded for {}", escrow_id))?;
+        change(&mut escrow)?;
+        self.store.put(&escrow)?;
+        Ok(escrow)
+    }
+}
+
+/// Background task that refunds expired escrows of a client; stops when
+/// dropped
+#[cfg(feature = "client")]
+pub struct EscrowRefunds {
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "client")]
+impl EscrowRefunds {
+    /// Calls `EasyCashClient::refund_expired_escrows` every `interval` (must
+    /// be called within a Tokio runtime)
+    pub fn spawn(client: Arc<crate::client::EasyCashClient>, interval: Duration) -> Self {
+        let task = tokio::spawn(async move {
+            loop {
+                if let Err(e) = client.refund_expired_escrows().await {
+                    tracing::warn!("[SDK] Failed to refund expired escrows: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+        Self { task }
+    }
+}
+
+#[cfg(feature = "client")]
+impl Drop for EscrowRefunds {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::TransactionSigner;
+    use crate::types::IntentType;
+    use k256::{PublicKey, SecretKey};
+
+    fn signer(byte: u8) -> TransactionSigner {
+        TransactionSigner::new(SecretKey::from_bytes(&[byte; 32].into()).unwrap())
+    }
+
+    fn public_key(signer: &TransactionSigner) -> String {
+        crypto::public_key_to_hex(&PublicKey::from(&signer.verifying_key()))
+    }
+
+    fn lock(escrows: &EscrowManager, escrow_id: &str, terms: EscrowTerms) {
+        let req = TransactionRequest::new(escrow_id, IntentType::Escrow, "100", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        escrows.record_lock(Escrow::new(&req, terms, "agent-001", "0xlock").unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_release_conditions() {
+        let escrows = EscrowManager::new(Arc::new(InMemoryEscrowStore::new()));
+        let now = now_secs();
+        lock(&escrows, "timed_due", EscrowTerms::new(ReleaseCondition::Timeout { release_at: now - 1 }));
+        lock(&escrows, "timed_later", EscrowTerms::new(ReleaseCondition::Timeout { release_at: now + 3600 }));
+        assert!(escrows.begin_release("timed_due", None).is_ok());
+        let err = escrows.begin_release("timed_later", None).unwrap_err();
+        assert_eq!(err.reason(), "escrow_condition_unmet");
+
+        let oracle = signer(5);
+        let condition = ReleaseCondition::OracleAttestation {
+            public_key: public_key(&oracle),
+            statement: "shipment 42 delivered".to_string(),
+        };
+        lock(&escrows, "oracle", EscrowTerms::new(condition));
+        let escrow = escrows.escrow("oracle").unwrap().unwrap();
+        // A signature of the release message is not an attestation
+        let wrong = oracle.sign_in_domain(SigningDomain::Escrow, &escrow.release_message()).unwrap();
+        assert!(escrows.begin_release("oracle", Some(&wrong)).is_err());
+        assert!(escrows.begin_release("oracle", None).is_err());
+        let message = escrow.attestation_message("shipment 42 delivered");
+        let attestation = oracle.sign_in_domain(SigningDomain::Escrow, &message).unwrap();
+        assert_eq!(escrows.begin_release("oracle", Some(&attestation)).unwrap().state, EscrowState::Releasing);
+
+        // Only one release or refund at a time
+        let err = escrows.begin_release("oracle", Some(&attestation)).unwrap_err();
+        assert!(matches!(err, EscrowRejection::NotLocked { state: EscrowState::Releasing, .. }));
+        escrows.abort("oracle").unwrap();
+        escrows.begin_release("oracle", Some(&attestation)).unwrap();
+        let released = escrows.complete("oracle", "0xrelease").unwrap();
+        assert_eq!((released.state, released.settle_tx_hash.as_deref()), (EscrowState::Released, Some("0xrelease")));
+    }
+
+    #[test]
+    fn test_refund_after_deadline() {
+        let escrows = EscrowManager::new(Arc::new(InMemoryEscrowStore::new()));
+        let buyer = public_key(&signer(3));
+        let condition = ReleaseCondition::CounterpartySignature { public_key: buyer };
+        lock(&escrows, "due", EscrowTerms::new(condition.clone()).with_refund_after(now_secs() - 1));
+        lock(&escrows, "later", EscrowTerms::new(condition.clone()).with_refund_after(now_secs() + 3600));
+
+        assert_eq!(escrows.begin_refund("later").unwrap_err().reason(), "escrow_refund_not_due");
+        escrows.begin_refund("due").unwrap();
+        assert_eq!(escrows.complete("due", "0xrefund").unwrap().state, EscrowState::Refunded);
+        assert_eq!(escrows.begin_refund("missing").unwrap_err().reason(), "escrow_not_found");
+
+        // Signature-released escrows must be refundable eventually
+        assert!(EscrowTerms::new(condition.clone()).validate().is_err());
+        assert!(EscrowTerms::new(condition).with_refund_after(now_secs() + 60).validate().is_ok());
+        assert!(EscrowTerms::new(ReleaseCondition::Timeout { release_at: 1 }).validate().is_err());
+    }
+
+    #[test]
+    fn test_hashlock() {
+        let escrows = EscrowManager::new(Arc::new(InMemoryEscrowStore::new()));
+        let secret = generate_secret();
+        let terms = EscrowTerms::htlc(hashlock(&secret, HashFunction::Sha256), now_secs() + 3600);
+        assert!(terms.validate().is_ok());
+        assert!(EscrowTerms::new(terms.condition.clone()).validate().is_err());
+        assert!(EscrowTerms::htlc("0xabcd", now_secs() + 3600).validate().is_err());
+        lock(&escrows, "htlc", terms.clone());
+        lock(&escrows, "htlc_expired", EscrowTerms { refund_after: Some(now_secs() - 1), ..terms });
+
+        assert!(escrows.begin_release("htlc", None).is_err());
+        let err = escrows.begin_reveal("htlc", b"guess").unwrap_err();
+        assert_eq!(err.reason(), "escrow_condition_unmet");
+        assert_eq!(escrows.escrow("htlc").unwrap().unwrap().secret, None);
+        let escrow = escrows.begin_reveal("htlc", &secret).unwrap();
+        assert_eq!(escrow.secret, Some(format!("0x{}", hex::encode(secret))));
+        assert_eq!(escrows.complete("htlc", "0xrelease").unwrap().secret, escrow.secret);
+
+        let expired: Vec<String> = escrows.expired().unwrap().into_iter().map(|e| e.escrow_id).collect();
+        assert_eq!(expired, ["htlc_expired"]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Structured lifecycle events emitted by the SDK.
+//!
+//! Applications subscribe to a broadcast channel of typed events for logging,
+//! UI updates, or alerting without parsing tracing output.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::errors::ErrorCode;
+
+/// Default number of events buffered per subscriber before old events are dropped
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// Lifecycle event for a single transaction.
+///
+/// Every event carries the correlation ID of the `execute_transaction` call
+/// that emitted it. Serialized with a `type` tag equal to `name()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SdkEvent {
+    /// The request was rejected by validation
+    ValidationFailed {
+        reference_id: String,
+        correlation_id: String,
+        reason: String,
+    },
+    /// A privacy proof was generated for a shielded request
+    ProofGenerated {
+        reference_id: String,
+        correlation_id: String,
+        proof: String,
+    },
+    /// An agent returned a route quote
+    QuoteReceived {
+        reference_id: String,
+        correlation_id: String,
+        agent_id: String,
+        estimated_fee: String,
+    },
+    /// A route was chosen for execution
+    RouteSelected {
+        reference_id: String,
+        correlation_id: String,
+        agent_id: String,
+        estimated_fee: String,
+        security_score: f64,
+    },
+    /// Execution was handed to the selected agent
+    ExecutionStarted {
+        reference_id: String,
+        correlation_id: String,
+        agent_id: String,
+    },
+    /// The agent charged more than it quoted (or in another asset)
+    QuoteViolated {
+        reference_id: String,
+        correlation_id: String,
+        agent_id: String,
+        /// `RouteQuote::commitment` of the accepted quote
+        quote_hash: String,
+        quoted_fee: String,
+        fee_used: String,
+    },
+    /// The transaction was confirmed
+    Confirmed {
+        reference_id: String,
+        correlation_id: String,
+        tx_hash: String,
+        fee_used: String,
+        /// `TransactionRequest::account_id` of the transaction
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account_id: Option<String>,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        metadata: HashMap<String, String>,
+    },
+    /// The transaction failed
+    Failed {
+        reference_id: String,
+        correlation_id: String,
+        code: ErrorCode,
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account_id: Option<String>,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        metadata: HashMap<String, String>,
+    },
+}
+
+impl SdkEvent {
+    /// Returns the event name (e.g. "route_selected")
+    pub fn name(&self) -> &'static str {
+        match self {
+            SdkEvent::ValidationFailed { .. } => "validation_failed",
+            SdkEvent::ProofGenerated { .. } => "proof_generated",
+            SdkEvent::QuoteReceived { .. } => "quote_received",
+            SdkEvent::RouteSelected { .. } => "route_selected",
+            SdkEvent::ExecutionStarted { .. } => "execution_started",
+            SdkEvent::QuoteViolated { .. } => "quote_violated",
+            SdkEvent::Confirmed { .. } => "confirmed",
+            SdkEvent::Failed { .. } => "failed",
+        }
+    }
+
+    /// Returns the reference ID of the transaction the event belongs to
+    pub fn reference_id(&self) -> &str {
+        match self {
+            SdkEvent::ValidationFailed { reference_id, .. }
+            | SdkEvent::ProofGenerated { reference_id, .. }
+            | SdkEvent::QuoteReceived { reference_id, .. }
+            | SdkEvent::RouteSelected { reference_id, .. }
+            | SdkEvent::ExecutionStarted { reference_id, .. }
+            | SdkEvent::QuoteViolated { reference_id, .. }
+            | SdkEvent::Confirmed { reference_id, .. }
+            | SdkEvent::Failed { reference_id, .. } => reference_id,
+        }
+    }
+
+    /// Returns the account the transaction is attributed to, on terminal events
+    pub fn account_id(&self) -> Option<&str> {
+        match self {
+            SdkEvent::Confirmed { account_id, .. } | SdkEvent::Failed { account_id, .. } => account_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the correlation ID of the call that emitted the event
+    pub fn correlation_id(&self) -> &str {
+        match self {
+            SdkEvent::ValidationFailed { correlation_id, .. }
+            | SdkEvent::ProofGenerated { correlation_id, .. }
+            | SdkEvent::QuoteReceived { correlation_id, .. }
+            | SdkEvent::RouteSelected { correlation_id, .. }
+            | SdkEvent::ExecutionStarted { correlation_id, .. }
+            | SdkEvent::QuoteViolated { correlation_id, .. }
+            | SdkEvent::Confirmed { correlation_id, .. }
+            | SdkEvent::Failed { correlation_id, .. } => correlation_id,
+        }
+    }
+}
+
+/// Broadcast channel distributing `SdkEvent`s to any number of subscribers.
+///
+/// Publishing never blocks; subscribers that fall behind by more than the
+/// channel capacity miss the oldest events.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::events::{EventBus, SdkEvent};
+///
+/// let bus = EventBus::default();
+/// let mut rx = bus.subscribe();
+/// bus.publish(SdkEvent::ExecutionStarted {
+///     reference_id: "ref_001".to_string(),
+///     correlation_id: "corr-001".to_string(),
+///     agent_id: "agent-001".to_string(),
+/// });
+/// assert_eq!(rx.try_recv().unwrap().name(), "execution_started");
+/// ```
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<SdkEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// Creates a new event bus buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Subscribes to all events published after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<SdkEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes an event to all current subscribers
+    pub fn publish(&self, event: SdkEvent) {
+        // Sending only fails when there are no subscribers, which is fine
+        let _ = self.sender.send(event);
+    }
+
+    /// Builds and publishes an event only if someone is subscribed, so
+    /// callers on the hot path don't pay for unused payloads
+    pub fn publish_with(&self, event: impl FnOnce() -> SdkEvent) {
+        if self.sender.receiver_count() > 0 {
+            self.publish(event());
+        }
+    }
+
+    /// Returns the number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_without_subscribers() {
+        let bus = EventBus::default();
+        bus.publish(SdkEvent::ValidationFailed {
+            reference_id: "ref_001".to_string(),
+            correlation_id: "corr-001".to_string(),
+            reason: "bad amount".to_string(),
+        });
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_multiple_subscribers_receive_events() {
+        let bus = EventBus::new(16);
+        let mut rx1 = bus.subscribe();
+        let mut rx2 = bus.subscribe();
+
+        bus.publish(SdkEvent::Confirmed {
+            reference_id: "ref_001".to_string(),
+            correlation_id: "corr-001".to_string(),
+            tx_hash: "0xabc".to_string(),
+            fee_used: "0.05 USDC".to_string(),
+            account_id: None,
+            metadata: HashMap::new(),
+        });
+
+        assert_eq!(rx1.try_recv().unwrap().reference_id(), "ref_001");
+        assert_eq!(rx2.try_recv().unwrap().name(), "confirmed");
+    }
+
+    #[test]
+    fn test_event_name_and_reference() {
+        let event = SdkEvent::Failed {
+            reference_id: "ref_002".to_string(),
+            correlation_id: "corr-001".to_string(),
+            code: ErrorCode::Timeout,
+            message: "timeout".to_string(),
+            account_id: Some("acct-7".to_string()),
+            metadata: HashMap::new(),
+        };
+        assert_eq!(event.name(), "failed");
+        assert_eq!(event.reference_id(), "ref_002");
+        assert_eq!(event.correlation_id(), "corr-001");
+        assert_eq!(event.account_id(), Some("acct-7"));
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], event.name());
+        assert_eq!(json["code"], "TIMEOUT");
+        assert!(json.get("metadata").is_none());
+        assert_eq!(serde_json::from_value::<SdkEvent>(json).unwrap(), event);
+    }
+}
@@ -0,0 +1,156 @@
+//! EVM primitives that need no RPC connection.
+//!
+//! Addresses, ABI words and chain IDs, plus signed EIP-1559 transactions
+//! (`tx`) and EIP-2612 permits and ERC-20 approvals (`permit`), so integrators
+//! can pull tokens into the settlement contracts without hand-rolling
+//! encodings.
+//!
+//! ```
+//! use k256::SecretKey;
+//! use ecash_sdk_core::crypto::TransactionSigner;
+//! use ecash_sdk_core::evm;
+//!
+//! let signer = TransactionSigner::new(SecretKey::from_bytes(&[1u8; 32].into()).unwrap());
+//! assert_eq!(evm::address(&signer.verifying_key()), "0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1");
+//! assert_eq!(hex::encode(evm::selector("approve(address,uint256)")), "095ea7b3");
+//! ```
+
+pub mod permit;
+pub mod tx;
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+use crate::crypto::keccak256;
+use crate::types::ChainId;
+
+/// EIP-155 chain ID of an EVM chain
+pub fn chain_id(chain: ChainId) -> Option<u64> {
+    match chain {
+        ChainId::Ethereum => Some(1),
+        ChainId::Base => Some(8453),
+        ChainId::Solana | ChainId::Unknown => None,
+    }
+}
+
+/// EIP-55 checksummed address of `key`
+pub fn address(key: &VerifyingKey) -> String {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    to_checksum(&address)
+}
+
+/// `address` with its EIP-55 checksum
+pub fn checksum(address: &str) -> Result<String, String> {
+    Ok(to_checksum(&parse_address(address)?))
+}
+
+/// 20-byte address from hex, with or without "0x"
+pub fn parse_address(address: &str) -> Result<[u8; 20], String> {
+    let bytes = hex::decode(address.strip_prefix("0x").unwrap_or(address))
+        .map_err(|e| format!("invalid address {}: {}", address, e))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("invalid address {}: expected 20 bytes, got {}", address, b.len()))
+}
+
+fn to_checksum(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+/// Big-endian 256-bit word of a decimal or "0x" hex integer
+pub fn uint256(value: &str) -> Result<[u8; 32], String> {
+    let mut word = [0u8; 32];
+    if let Some(hex_digits) = value.strip_prefix("0x") {
+        let digits = if hex_digits.len() % 2 == 1 { format!("0{}", hex_digits) } else { hex_digits.to_string() };
+        let bytes = hex::decode(digits).map_err(|e| format!("invalid integer {}: {}", value, e))?;
+        if bytes.len() > 32 {
+            return Err(format!("{} does not fit in 256 bits", value));
+        }
+        word[32 - bytes.len()..].copy_from_slice(&bytes);
+        return Ok(word);
+    }
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid integer {}", value));
+    }
+    for digit in value.bytes().map(|b| b - b'0') {
+        let mut carry = digit as u16;
+        for byte in word.iter_mut().rev() {
+            let next = *byte as u16 * 10 + carry;
+            *byte = next as u8;
+            carry = next >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("{} does not fit in 256 bits", value));
+        }
+    }
+    Ok(word)
+}
+
+/// ABI word of an address (left-padded to 32 bytes)
+pub fn address_word(address: &str) -> Result<[u8; 32], String> {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&parse_address(address)?);
+    Ok(word)
+}
+
+/// First four bytes of the Keccak-256 hash of a function signature
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Address that produced `signature` (`r || s`) of `digest`
+pub fn recover_address(digest: &[u8; 32], signature: &[u8; 64], recovery_id: u8) -> Result<String, String> {
+    let signature = Signature::from_slice(signature).map_err(|e| format!("invalid signature: {}", e))?;
+    let recovery_id = RecoveryId::from_byte(recovery_id).ok_or("invalid recovery id")?;
+    let key = VerifyingKey::recover_from_prehash(digest, &signature, recovery_id)
+        .map_err(|e| format!("failed to recover signer: {}", e))?;
+    Ok(address(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::TransactionSigner;
+    use k256::SecretKey;
+
+    #[test]
+    fn test_addresses() {
+        let mut key = [0u8; 32];
+        key[31] = 1;
+        let signer = TransactionSigner::new(SecretKey::from_bytes(&key.into()).unwrap());
+        assert_eq!(address(&signer.verifying_key()), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+        assert_eq!(
+            checksum("0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359").unwrap(),
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        );
+        assert!(parse_address("0x1234").is_err());
+
+        let digest = keccak256(b"payload");
+        let (signature, recovery_id) = signer.sign_digest_recoverable(&digest).unwrap();
+        assert_eq!(recover_address(&digest, &signature, recovery_id).unwrap(), address(&signer.verifying_key()));
+    }
+
+    #[test]
+    fn test_uint256() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(uint256(max).unwrap(), [0xff; 32]);
+        assert!(uint256("115792089237316195423570985008687907853269984665640564039457584007913129639936").is_err());
+        assert_eq!(uint256("1000").unwrap()[30..], [0x03, 0xe8]);
+        assert_eq!(uint256("0x3e8").unwrap(), uint256("1000").unwrap());
+        assert!(uint256("-1").is_err());
+        assert!(uint256("").is_err());
+    }
+}
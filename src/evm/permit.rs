@@ -0,0 +1,249 @@
+//! EIP-2612 permits and ERC-20 approvals.
+//!
+//! Before a settlement contract can pull an integrator's tokens it needs an
+//! allowance. Tokens implementing EIP-2612 accept a signed `Permit`, which
+//! the contract redeems itself so the owner never sends a transaction;
+//! other tokens need an `approve` transaction, built by `approval`.
+//!
+//! ```
+//! use k256::SecretKey;
+//! use ecash_sdk_core::crypto::TransactionSigner;
+//! use ecash_sdk_core::evm::{self, permit::{Eip712Domain, Permit}};
+//!
+//! let owner = TransactionSigner::new(SecretKey::from_bytes(&[1u8; 32].into()).unwrap());
+//! let usdc = Eip712Domain::new("USD Coin", "2", 8453, "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+//! let permit = Permit::new(
+//!     evm::address(&owner.verifying_key()),
+//!     "0x5FbDB2315678afecb367f032d93F642f64180aa3", // settlement contract
+//!     "25000000",                                   // 25 USDC
+//!     0,
+//!     4_102_444_800,
+//! );
+//! let signed = permit.sign(&usdc, &owner).unwrap();
+//! assert_eq!(signed.signer(&usdc).unwrap(), permit.owner);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::tx::Eip1559Transaction;
+use super::{address_word, recover_address, selector, uint256};
+use crate::crypto::{keccak256, TransactionSigner};
+use crate::types::TransactionRequest;
+
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const PERMIT_TYPE: &str = "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+/// Allowance of an unlimited approval (2^256 - 1)
+pub const UNLIMITED: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+/// Gas limit of `approval` transactions
+pub const APPROVAL_GAS_LIMIT: u64 = 100_000;
+
+/// EIP-712 domain of a token contract
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip712Domain {
+    /// The token's EIP-712 name (e.g. "USD Coin"), not always its ERC-20 name
+    pub name: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub verifying_contract: String,
+}
+
+impl Eip712Domain {
+    pub fn new(name: impl Into<String>, version: impl Into<String>, chain_id: u64, verifying_contract: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            chain_id,
+            verifying_contract: verifying_contract.into(),
+        }
+    }
+
+    /// `DOMAIN_SEPARATOR` of the contract
+    pub fn separator(&self) -> Result<[u8; 32], String> {
+        Ok(keccak256(
+            &[
+                keccak256(DOMAIN_TYPE.as_bytes()),
+                keccak256(self.name.as_bytes()),
+                keccak256(self.version.as_bytes()),
+                uint256(&self.chain_id.to_string())?,
+                address_word(&self.verifying_contract)?,
+            ]
+            .concat(),
+        ))
+    }
+}
+
+/// EIP-2612 allowance of `value` from `owner` to `spender`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Permit {
+    pub owner: String,
+    pub spender: String,
+    /// Allowance in the token's base units
+    pub value: String,
+    /// The owner's current `nonces(owner)` on the token
+    pub nonce: u64,
+    /// Unix time (seconds) after which the permit can't be used
+    pub deadline: u64,
+}
+
+impl Permit {
+    pub fn new(owner: impl Into<String>, spender: impl Into<String>, value: impl Into<String>, nonce: u64, deadline: u64) -> Self {
+        Self {
+            owner: owner.into(),
+            spender: spender.into(),
+            value: value.into(),
+            nonce,
+            deadline,
+        }
+    }
+
+    /// Permit letting `settlement` pull the amount of `req` from `owner`.
+    /// Needs `req.amount_base_units`, which the client sets when the
+    /// asset's decimals are known.
+    pub fn for_request(req: &TransactionRequest, owner: &str, settlement: &str, nonce: u64, deadline: u64) -> Result<Self, String> {
+        let value = req
+            .amount_base_units
+            .clone()
+            .ok_or_else(|| format!("{} has no amount in base units", req.reference_id))?;
+        Ok(Self::new(owner, settlement, value, nonce, deadline))
+    }
+
+    /// Hash the owner signs for `domain`
+    pub fn digest(&self, domain: &Eip712Domain) -> Result<[u8; 32], String> {
+        let struct_hash = keccak256(
+            &[
+                keccak256(PERMIT_TYPE.as_bytes()),
+                address_word(&self.owner)?,
+                address_word(&self.spender)?,
+                uint256(&self.value)?,
+                uint256(&self.nonce.to_string())?,
+                uint256(&self.deadline.to_string())?,
+            ]
+            .concat(),
+        );
+        Ok(keccak256(&[&[0x19, 0x01][..], &domain.separator()?, &struct_hash].concat()))
+    }
+
+    /// Signs the permit with the owner's key
+    pub fn sign(&self, domain: &Eip712Domain, owner: &TransactionSigner) -> Result<SignedPermit, String> {
+        let (signature, recovery_id) = owner.sign_digest_recoverable(&self.digest(domain)?)?;
+        Ok(SignedPermit {
+            permit: self.clone(),
+            v: 27 + recovery_id,
+            r: format!("0x{}", hex::encode(&signature[..32])),
+            s: format!("0x{}", hex::encode(&signature[32..])),
+        })
+    }
+}
+
+/// Permit with the owner's signature, as passed to `permit`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedPermit {
+    #[serde(flatten)]
+    pub permit: Permit,
+    pub v: u8,
+    pub r: String,
+    pub s: String,
+}
+
+impl SignedPermit {
+    /// Checksummed address that signed the permit; the token rejects it
+    /// unless this is the owner
+    pub fn signer(&self, domain: &Eip712Domain) -> Result<String, String> {
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&hex_word(&self.r)?);
+        signature[32..].copy_from_slice(&hex_word(&self.s)?);
+        let recovery_id = self.v.checked_sub(27).ok_or("invalid v")?;
+        recover_address(&self.permit.digest(domain)?, &signature, recovery_id)
+    }
+
+    /// Calldata of the token's `permit(owner, spender, value, deadline, v, r, s)`
+    pub fn calldata(&self) -> Result<String, String> {
+        let mut v = [0u8; 32];
+        v[31] = self.v;
+        let data = [
+            &selector("permit(address,address,uint256,uint256,uint8,bytes32,bytes32)")[..],
+            &address_word(&self.permit.owner)?,
+            &address_word(&self.permit.spender)?,
+            &uint256(&self.permit.value)?,
+            &uint256(&self.permit.deadline.to_string())?,
+            &v,
+            &hex_word(&self.r)?,
+            &hex_word(&self.s)?,
+        ]
+        .concat();
+        Ok(format!("0x{}", hex::encode(data)))
+    }
+}
+
+/// Calldata of `approve(spender, amount)`
+pub fn approve_calldata(spender: &str, amount: &str) -> Result<String, String> {
+    let data = [&selector("approve(address,uint256)")[..], &address_word(spender)?, &uint256(amount)?].concat();
+    Ok(format!("0x{}", hex::encode(data)))
+}
+
+/// Transaction approving `spender` to pull `amount` of `token` (base units,
+/// or `UNLIMITED`). Set the nonce and fees with `with_nonce` and `with_gas`
+/// before signing.
+pub fn approval(chain_id: u64, token: &str, spender: &str, amount: &str) -> Result<Eip1559Transaction, String> {
+    let mut tx = Eip1559Transaction::call(chain_id, token, approve_calldata(spender, amount)?);
+    tx.gas_limit = APPROVAL_GAS_LIMIT;
+    Ok(tx)
+}
+
+fn hex_word(value: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value)).map_err(|e| format!("invalid word {}: {}", value, e))?;
+    bytes.try_into().map_err(|_| format!("invalid word {}: expected 32 bytes", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evm;
+    use k256::SecretKey;
+
+    #[test]
+    fn test_eip712_hashes() {
+        assert_eq!(
+            hex::encode(keccak256(DOMAIN_TYPE.as_bytes())),
+            "8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f"
+        );
+        assert_eq!(
+            hex::encode(keccak256(PERMIT_TYPE.as_bytes())),
+            "6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9"
+        );
+        // Domain of the EIP-712 specification's example
+        let domain = Eip712Domain::new("Ether Mail", "1", 1, "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC");
+        assert_eq!(
+            hex::encode(domain.separator().unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+    }
+
+    #[test]
+    fn test_permit_and_approval() {
+        let owner = TransactionSigner::new(SecretKey::from_bytes(&[1u8; 32].into()).unwrap());
+        let domain = Eip712Domain::new("USD Coin", "2", 8453, "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+        let spender = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+        let permit = Permit::new(evm::address(&owner.verifying_key()), spender, "1000000", 3, 4_102_444_800);
+        let signed = permit.sign(&domain, &owner).unwrap();
+        assert!(signed.v == 27 || signed.v == 28);
+        assert_eq!(signed.signer(&domain).unwrap(), permit.owner);
+        // Signed for another chain, the permit recovers to someone else
+        let other_chain = Eip712Domain { chain_id: 1, ..domain.clone() };
+        assert_ne!(signed.signer(&other_chain).unwrap(), permit.owner);
+
+        let calldata = signed.calldata().unwrap();
+        assert!(calldata.starts_with("0xd505accf"));
+        assert_eq!(calldata.len(), 2 + 8 + 7 * 64);
+
+        let approve = approve_calldata(spender, UNLIMITED).unwrap();
+        assert_eq!(
+            approve,
+            format!("0x095ea7b3{:0>64}{}", "5fbdb2315678afecb367f032d93f642f64180aa3", "f".repeat(64))
+        );
+        let tx = approval(8453, &domain.verifying_contract, spender, "1000000").unwrap();
+        assert!(tx.clone().with_gas(APPROVAL_GAS_LIMIT, 1_000_000, 1_000).sign(&owner).is_ok());
+    }
+}
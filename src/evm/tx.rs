@@ -0,0 +1,190 @@
+//! Signed EIP-1559 (type 2) transactions.
+//!
+//! `Eip1559Transaction::sign` produces the raw transaction accepted by
+//! `eth_sendRawTransaction`, along with its hash and sender.
+
+use serde::{Deserialize, Serialize};
+
+use super::{address, parse_address, uint256};
+use crate::crypto::{keccak256, TransactionSigner};
+
+/// EIP-2718 type of EIP-1559 transactions
+const TRANSACTION_TYPE: u8 = 0x02;
+
+/// Unsigned EIP-1559 transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    /// Called contract or recipient
+    pub to: String,
+    /// Wei sent along, as a decimal or "0x" hex integer
+    pub value: String,
+    /// "0x" hex calldata
+    pub data: String,
+}
+
+impl Eip1559Transaction {
+    /// Call of `to` with `data`, sending no value. Nonce and gas are zero
+    /// until set.
+    pub fn call(chain_id: u64, to: impl Into<String>, data: impl Into<String>) -> Self {
+        Self {
+            chain_id,
+            nonce: 0,
+            max_priority_fee_per_gas: 0,
+            max_fee_per_gas: 0,
+            gas_limit: 0,
+            to: to.into(),
+            value: "0".to_string(),
+            data: data.into(),
+        }
+    }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sets the gas limit and the fees per gas, in wei
+    pub fn with_gas(mut self, gas_limit: u64, max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Self {
+        self.gas_limit = gas_limit;
+        self.max_fee_per_gas = max_fee_per_gas;
+        self.max_priority_fee_per_gas = max_priority_fee_per_gas;
+        self
+    }
+
+    /// Hash the sender signs
+    pub fn signing_hash(&self) -> Result<[u8; 32], String> {
+        Ok(keccak256(&self.encode(None)?))
+    }
+
+    /// Signs the transaction with `signer`'s key
+    pub fn sign(&self, signer: &TransactionSigner) -> Result<SignedTransaction, String> {
+        if self.gas_limit == 0 {
+            return Err("the transaction has no gas limit".to_string());
+        }
+        if self.max_priority_fee_per_gas > self.max_fee_per_gas {
+            return Err("the priority fee is above the maximum fee per gas".to_string());
+        }
+        let (signature, recovery_id) = signer.sign_digest_recoverable(&self.signing_hash()?)?;
+        let raw = self.encode(Some((recovery_id, &signature)))?;
+        Ok(SignedTransaction {
+            hash: format!("0x{}", hex::encode(keccak256(&raw))),
+            raw: format!("0x{}", hex::encode(raw)),
+            from: address(&signer.verifying_key()),
+            transaction: self.clone(),
+        })
+    }
+
+    /// Type byte followed by the RLP list of the fields, with the signature
+    /// (y parity, r, s) if given
+    fn encode(&self, signature: Option<(u8, &[u8; 64])>) -> Result<Vec<u8>, String> {
+        let data = hex::decode(self.data.strip_prefix("0x").unwrap_or(&self.data))
+            .map_err(|e| format!("invalid calldata: {}", e))?;
+        let mut fields = vec![
+            rlp::uint(&self.chain_id.to_be_bytes()),
+            rlp::uint(&self.nonce.to_be_bytes()),
+            rlp::uint(&self.max_priority_fee_per_gas.to_be_bytes()),
+            rlp::uint(&self.max_fee_per_gas.to_be_bytes()),
+            rlp::uint(&self.gas_limit.to_be_bytes()),
+            rlp::bytes(&parse_address(&self.to)?),
+            rlp::uint(&uint256(&self.value)?),
+            rlp::bytes(&data),
+            // Empty access list
+            rlp::list(&[]),
+        ];
+        if let Some((y_parity, signature)) = signature {
+            fields.push(rlp::uint(&[y_parity]));
+            fields.push(rlp::uint(&signature[..32]));
+            fields.push(rlp::uint(&signature[32..]));
+        }
+        let mut encoded = vec![TRANSACTION_TYPE];
+        encoded.extend(rlp::list(&fields));
+        Ok(encoded)
+    }
+}
+
+/// Transaction ready for `eth_sendRawTransaction`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub transaction: Eip1559Transaction,
+    /// "0x" hex raw transaction
+    pub raw: String,
+    /// Hash the transaction will have on chain
+    pub hash: String,
+    /// Sender address
+    pub from: String,
+}
+
+/// Recursive-length prefix encoding
+mod rlp {
+    /// Byte string
+    pub fn bytes(data: &[u8]) -> Vec<u8> {
+        match data {
+            [byte] if *byte < 0x80 => vec![*byte],
+            _ => [prefix(0x80, data.len()), data.to_vec()].concat(),
+        }
+    }
+
+    /// Big-endian integer, without leading zeros
+    pub fn uint(be: &[u8]) -> Vec<u8> {
+        let start = be.iter().position(|b| *b != 0).unwrap_or(be.len());
+        bytes(&be[start..])
+    }
+
+    /// List of already encoded items
+    pub fn list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        [prefix(0xc0, payload.len()), payload].concat()
+    }
+
+    fn prefix(offset: u8, len: usize) -> Vec<u8> {
+        if len < 56 {
+            return vec![offset + len as u8];
+        }
+        let be = len.to_be_bytes();
+        let len_bytes = &be[be.iter().position(|b| *b != 0).unwrap_or(be.len() - 1)..];
+        [vec![offset + 55 + len_bytes.len() as u8], len_bytes.to_vec()].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::SecretKey;
+
+    #[test]
+    fn test_rlp() {
+        assert_eq!(rlp::bytes(b"dog"), [0x83, b'd', b'o', b'g']);
+        assert_eq!(rlp::list(&[rlp::bytes(b"cat"), rlp::bytes(b"dog")]), hex::decode("c88363617483646f67").unwrap());
+        assert_eq!(rlp::uint(&0u64.to_be_bytes()), [0x80]);
+        assert_eq!(rlp::uint(&15u64.to_be_bytes()), [0x0f]);
+        assert_eq!(rlp::uint(&1024u64.to_be_bytes()), [0x82, 0x04, 0x00]);
+        let long = rlp::bytes(&[b'a'; 56]);
+        assert_eq!(long[..2], [0xb8, 56]);
+    }
+
+    #[test]
+    fn test_sign() {
+        let signer = TransactionSigner::new(SecretKey::from_bytes(&[1u8; 32].into()).unwrap());
+        let tx = Eip1559Transaction::call(8453, "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "0x")
+            .with_nonce(7)
+            .with_gas(60_000, 2_000_000_000, 1_000_000_000);
+        let signed = tx.sign(&signer).unwrap();
+        assert!(signed.raw.starts_with("0x02"));
+        assert_eq!(signed.from, address(&signer.verifying_key()));
+
+        // The signature (y parity, then 32-byte r and s) recovers to the sender
+        let raw = hex::decode(&signed.raw[2..]).unwrap();
+        let n = raw.len();
+        let signature: [u8; 64] = [&raw[n - 65..n - 33], &raw[n - 32..]].concat().try_into().unwrap();
+        let y_parity = if raw[n - 67] == 0x80 { 0 } else { raw[n - 67] };
+        let recovered = super::super::recover_address(&tx.signing_hash().unwrap(), &signature, y_parity).unwrap();
+        assert_eq!(recovered, signed.from);
+
+        assert!(Eip1559Transaction::call(8453, "0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913", "0x").sign(&signer).is_err());
+    }
+}
@@ -0,0 +1,473 @@
+//! Exactly-once execution guard.
+//!
+//! Before a request executes, the guard reserves its reference ID in a
+//! persistent `SubmissionStore`. The reservation is marked as submitted just
+//! before the intent is handed to an agent and completed with the response
+//! afterwards, so after a crash mid-execution the next attempt finds a
+//! submission whose outcome is unknown instead of paying twice. A
+//! `SubmissionLookup` can resolve such submissions by searching the chain for
+//! the intent hash embedded in calldata or memo.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::receipt::intent_hash;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+/// Progress of a reserved reference ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionState {
+    /// Reserved; nothing has been sent to an agent yet
+    Reserved,
+    /// Handed to an agent; the outcome is unknown until completed
+    Submitted,
+    Completed,
+}
+
+/// Stored submission for a reference ID
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionRecord {
+    pub reference_id: String,
+    pub intent_hash: String,
+    pub state: SubmissionState,
+    pub response: Option<TransactionResponse>,
+    pub updated_at_ms: u64,
+    /// Earlier hashes of the transaction, oldest first, when it was replaced
+    /// (e.g. sped up); `response.tx_hash` is the latest
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replaced_tx_hashes: Vec<String>,
+    /// `TransactionRequest::account_id` of the submission
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+/// Persistent record of submissions, keyed by reference ID.
+///
+/// `reserve` (insert-if-absent) and `compare_and_set` must be atomic across
+/// every instance sharing the store.
+pub trait SubmissionStore: Send + Sync {
+    /// Inserts `record` unless one exists for its reference ID, returning the
+    /// existing record in that case
+    fn reserve(&self, record: &SubmissionRecord) -> Result<Option<SubmissionRecord>, String>;
+
+    /// Replaces the record for `record`'s reference ID with `record` only if
+    /// the stored one still equals `expected`; returns whether it did
+    fn compare_and_set(&self, expected: &SubmissionRecord, record: &SubmissionRecord) -> Result<bool, String>;
+
+    /// Inserts or replaces a record
+    fn put(&self, record: &SubmissionRecord) -> Result<(), String>;
+
+    fn get(&self, reference_id: &str) -> Result<Option<SubmissionRecord>, String>;
+
+    fn remove(&self, reference_id: &str) -> Result<(), String>;
+}
+
+/// In-memory submission store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemorySubmissionStore {
+    records: Mutex<HashMap<String, SubmissionRecord>>,
+}
+
+impl InMemorySubmissionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, SubmissionRecord>>, String> {
+        self.records.lock().map_err(|_| "submission store poisoned".to_string())
+    }
+}
+
+impl SubmissionStore for InMemorySubmissionStore {
+    fn reserve(&self, record: &SubmissionRecord) -> Result<Option<SubmissionRecord>, String> {
+        let mut records = self.lock()?;
+        if let Some(existing) = records.get(&record.reference_id) {
+            return Ok(Some(existing.clone()));
+        }
+        records.insert(record.reference_id.clone(), record.clone());
+        Ok(None)
+    }
+
+    fn compare_and_set(&self, expected: &SubmissionRecord, record: &SubmissionRecord) -> Result<bool, String> {
+        let mut records = self.lock()?;
+        if records.get(&record.reference_id) != Some(expected) {
+            return Ok(false);
+        }
+        records.insert(record.reference_id.clone(), record.clone());
+        Ok(true)
+    }
+
+    fn put(&self, record: &SubmissionRecord) -> Result<(), String> {
+        self.lock()?.insert(record.reference_id.clone(), record.clone());
+        Ok(())
+    }
+
+    fn get(&self, reference_id: &str) -> Result<Option<SubmissionRecord>, String> {
+        Ok(self.lock()?.get(reference_id).cloned())
+    }
+
+    fn remove(&self, reference_id: &str) -> Result<(), String> {
+        self.lock()?.remove(reference_id);
+        Ok(())
+    }
+}
+
+/// Searches the chain for a transaction carrying an intent hash
+#[async_trait::async_trait]
+pub trait SubmissionLookup: Send + Sync {
+    /// Returns the settled transaction for the intent, or `None` if the chain
+    /// has no transaction carrying `intent_hash`
+    async fn find_submission(
+        &self,
+        req: &TransactionRequest,
+        intent_hash: &str,
+    ) -> Result<Option<TransactionResponse>, String>;
+}
+
+/// Why the guard refused to execute a request
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardRejection {
+    /// The reference ID was already used for a different intent
+    IntentMismatch { reference_id: String },
+    /// Another execution of the reference ID is in progress
+    InProgress { reference_id: String },
+    /// A previous execution was submitted and its outcome is unknown
+    OutcomeUnknown { reference_id: String },
+    /// The store or chain lookup failed; the request is refused to be safe
+    Unavailable(String),
+}
+
+impl std::fmt::Display for GuardRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuardRejection::IntentMismatch { reference_id } => {
+                write!(f, "reference {} was already used for a different intent", reference_id)
+            }
+            GuardRejection::InProgress { reference_id } => {
+                write!(f, "reference {} is already being executed", reference_id)
+            }
+            GuardRejection::OutcomeUnknown { reference_id } => write!(
+                f,
+                "reference {} was submitted before but its outcome is unknown; resolve it before retrying",
+                reference_id
+            ),
+            GuardRejection::Unavailable(e) => write!(f, "exactly-once guard unavailable: {}", e),
+        }
+    }
+}
+
+/// Exactly-once guard used by `EasyCashClient::with_exactly_once`
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::exactly_once::{ExactlyOnceGuard, InMemorySubmissionStore};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let guard = ExactlyOnceGuard::new(Arc::new(InMemorySubmissionStore::new()))
+///    .with_reservation_timeout(Duration::from_secs(120));
+/// ```
+pub struct ExactlyOnceGuard {
+    store: Arc<dyn SubmissionStore>,
+    lookup: Option<Arc<dyn SubmissionLookup>>,
+    reservation_timeout: Duration,
+}
+
+impl ExactlyOnceGuard {
+    /// Creates a guard; reservations that never reach submission are taken
+    /// over after 5 minutes
+    pub fn new(store: Arc<dyn SubmissionStore>) -> Self {
+        Self {
+            store,
+            lookup: None,
+            reservation_timeout: Duration::from_secs(300),
+        }
+    }
+
+    /// Resolves submissions with unknown outcome by checking the chain
+    pub fn with_lookup(mut self, lookup: Arc<dyn SubmissionLookup>) -> Self {
+        self.lookup = Some(lookup);
+        self
+    }
+
+    /// Age after which an unsubmitted reservation (e.g. left by a crashed
+    /// process) no longer blocks new attempts
+    pub fn with_reservation_timeout(mut self, timeout: Duration) -> Self {
+        self.reservation_timeout = timeout;
+        self
+    }
+
+    /// Reserves the request's reference ID. Returns the stored response when
+    /// the same intent already completed.
+    pub async fn begin(&self, req: &TransactionRequest) -> Result<Option<TransactionResponse>, GuardRejection> {
+        let hash = intent_hash(req);
+        let record = SubmissionRecord {
+            reference_id: req.reference_id.clone(),
+            intent_hash: hash.clone(),
+            state: SubmissionState::Reserved,
+            response: None,
+            updated_at_ms: now_ms(),
+            replaced_tx_hashes: Vec::new(),
+            account_id: req.account_id.clone(),
+            metadata: req.metadata.clone(),
+        };
+        let Some(existing) = self.store.reserve(&record).map_err(GuardRejection::Unavailable)? else {
+            return Ok(None);
+        };
+
+        let reference_id = req.reference_id.clone();
+        if existing.intent_hash != hash {
+            return Err(GuardRejection::IntentMismatch { reference_id });
+        }
+        match existing.state {
+            SubmissionState::Completed => Ok(existing.response),
+            SubmissionState::Reserved => {
+                let age = now_ms().saturating_sub(existing.updated_at_ms);
+                if age < self.reservation_timeout.as_millis() as u64 {
+                    return Err(GuardRejection::InProgress { reference_id });
+                }
+                // Nothing was sent under the stale reservation, so it is safe
+                // to take over; of several attempts doing so, only one wins
+                match self.store.compare_and_set(&existing, &record) {
+                    Ok(true) => Ok(None),
+                    Ok(false) => Err(GuardRejection::InProgress { reference_id }),
+                    Err(e) => Err(GuardRejection::Unavailable(e)),
+                }
+            }
+            SubmissionState::Submitted => {
+                let Some(ref lookup) = self.lookup else {
+                    return Err(GuardRejection::OutcomeUnknown { reference_id });
+                };
+                match lookup.find_submission(req, &hash).await.map_err(GuardRejection::Unavailable)? {
+                    Some(resp) => {
+                        self.complete(req, &resp).map_err(GuardRejection::Unavailable)?;
+                        Ok(Some(resp))
+                    }
+                    None => Err(GuardRejection::OutcomeUnknown { reference_id }),
+                }
+            }
+        }
+    }
+
+    /// Records that the intent is about to be handed to an agent
+    pub fn mark_submitted(&self, req: &TransactionRequest) -> Result<(), String> {
+        self.update(req, SubmissionState::Submitted, None)
+    }
+
+    /// Records the final response
+    pub fn complete(&self, req: &TransactionRequest, resp: &TransactionResponse) -> Result<(), String> {
+        self.update(req, SubmissionState::Completed, Some(resp.clone()))
+    }
+
+    /// Releases the reservation after a failure, unless the intent was
+    /// already submitted (its outcome is then unknown and stays recorded)
+    pub fn abort(&self, req: &TransactionRequest) -> Result<(), String> {
+        match self.store.get(&req.reference_id)? {
+            Some(record) if record.state == SubmissionState::Reserved && record.intent_hash == intent_hash(req) => {
+                self.store.remove(&req.reference_id)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records that the completed transaction of `reference_id` was replaced
+    /// by `new_tx_hash`, so replays return the replacement
+    pub fn record_replacement(&self, reference_id: &str, new_tx_hash: &str) -> Result<(), String> {
+        let Some(mut record) = self.store.get(reference_id)? else {
+            return Ok(());
+        };
+        if let Some(ref mut resp) = record.response {
+            let replaced = std::mem::replace(&mut resp.tx_hash, new_tx_hash.to_string());
+            record.replaced_tx_hashes.push(replaced);
+            record.updated_at_ms = now_ms();
+            self.store.put(&record)?;
+        }
+        Ok(())
+    }
+
+    /// Clears the record for a reference ID after an operator has resolved it
+    pub fn release(&self, reference_id: &str) -> Result<(), String> {
+        self.store.remove(reference_id)
+    }
+
+    fn update(
+        &self,
+        req: &TransactionRequest,
+        state: SubmissionState,
+        response: Option<TransactionResponse>,
+    ) -> Result<(), String> {
+        self.store.put(&SubmissionRecord {
+            reference_id: req.reference_id.clone(),
+            intent_hash: intent_hash(req),
+            state,
+            response,
+            updated_at_ms: now_ms(),
+            replaced_tx_hashes: Vec::new(),
+            account_id: req.account_id.clone(),
+            metadata: req.metadata.clone(),
+        })
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+
+    fn request(amount: &str) -> TransactionRequest {
+        TransactionRequest::new("withdrawal_7", IntentType::Transfer, amount, "USDC", ChainId::Base)
+    }
+
+    fn response() -> TransactionResponse {
+        TransactionResponse {
+            tx_hash: "0xabc".to_string(),
+            status: "confirmed".to_string(),
+            block_height: 7,
+            fee_used: "0.05 USDC".to_string(),
+            correlation_id: "corr".to_string(),
+            disbursements: Vec::new(),
+            fee_splits: Vec::new(),
+        }
+    }
+
+    struct FoundOnChain;
+
+    #[async_trait::async_trait]
+    impl SubmissionLookup for FoundOnChain {
+        async fn find_submission(&self, _: &TransactionRequest, _: &str) -> Result<Option<TransactionResponse>, String> {
+            Ok(Some(response()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reservation_lifecycle() {
+        let guard = ExactlyOnceGuard::new(Arc::new(InMemorySubmissionStore::new()));
+        let req = request("10.00");
+
+        assert_eq!(guard.begin(&req).await, Ok(None));
+        assert!(matches!(guard.begin(&req).await, Err(GuardRejection::InProgress { .. })));
+        assert!(matches!(
+            guard.begin(&request("11.00")).await,
+            Err(GuardRejection::IntentMismatch { .. })
+        ));
+
+        // A failure before submission frees the reference for a retry
+        guard.abort(&req).unwrap();
+        assert_eq!(guard.begin(&req).await, Ok(None));
+
+        guard.complete(&req, &response()).unwrap();
+        assert_eq!(guard.begin(&req).await, Ok(Some(response())));
+
+        // Replays return the replacement of a sped-up transaction
+        guard.record_replacement(&req.reference_id, "0xdef").unwrap();
+        let replayed = guard.begin(&req).await.unwrap().unwrap();
+        assert_eq!(replayed.tx_hash, "0xdef");
+        let record = guard.store.get(&req.reference_id).unwrap().unwrap();
+        assert_eq!(record.replaced_tx_hashes, ["0xabc"]);
+    }
+
+    #[tokio::test]
+    async fn test_submitted_outcome_resolved_on_chain() {
+        let store = Arc::new(InMemorySubmissionStore::new());
+        let req = request("10.00");
+        let crashed = ExactlyOnceGuard::new(store.clone());
+        crashed.begin(&req).await.unwrap();
+        crashed.mark_submitted(&req).unwrap();
+        crashed.abort(&req).unwrap();
+
+        let restarted = ExactlyOnceGuard::new(store.clone());
+        assert!(matches!(restarted.begin(&req).await, Err(GuardRejection::OutcomeUnknown { .. })));
+
+        let restarted = restarted.with_lookup(Arc::new(FoundOnChain));
+        assert_eq!(restarted.begin(&req).await, Ok(Some(response())));
+        assert_eq!(store.get(&req.reference_id).unwrap().unwrap().state, SubmissionState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_stale_reservation_is_taken_over() {
+        let guard = ExactlyOnceGuard::new(Arc::new(InMemorySubmissionStore::new()))
+            .with_reservation_timeout(Duration::from_millis(0));
+        let req = request("10.00");
+        guard.begin(&req).await.unwrap();
+        assert_eq!(guard.begin(&req).await, Ok(None));
+    }
+
+    /// Hands every caller the stored record only once all of them have read
+    /// it, so they contend for the same stale reservation
+    struct Contended {
+        store: InMemorySubmissionStore,
+        readers: std::sync::Barrier,
+    }
+
+    impl SubmissionStore for Contended {
+        fn reserve(&self, record: &SubmissionRecord) -> Result<Option<SubmissionRecord>, String> {
+            let existing = self.store.reserve(record);
+            self.readers.wait();
+            existing
+        }
+
+        fn compare_and_set(&self, expected: &SubmissionRecord, record: &SubmissionRecord) -> Result<bool, String> {
+            self.store.compare_and_set(expected, record)
+        }
+
+        fn put(&self, record: &SubmissionRecord) -> Result<(), String> {
+            self.store.put(record)
+        }
+
+        fn get(&self, reference_id: &str) -> Result<Option<SubmissionRecord>, String> {
+            self.store.get(reference_id)
+        }
+
+        fn remove(&self, reference_id: &str) -> Result<(), String> {
+            self.store.remove(reference_id)
+        }
+    }
+
+    #[test]
+    fn test_stale_reservation_taken_over_once() {
+        let req = request("10.00");
+        let store = Arc::new(Contended {
+            store: InMemorySubmissionStore::new(),
+            readers: std::sync::Barrier::new(2),
+        });
+        store
+            .store
+            .put(&SubmissionRecord {
+                reference_id: req.reference_id.clone(),
+                intent_hash: intent_hash(&req),
+                state: SubmissionState::Reserved,
+                response: None,
+                updated_at_ms: 0,
+                replaced_tx_hashes: Vec::new(),
+                account_id: None,
+                metadata: HashMap::new(),
+            })
+            .unwrap();
+
+        let outcomes: Vec<_> = std::thread::scope(|scope| {
+            let attempts: Vec<_> = (0..2)
+                .map(|_| {
+                    let guard = ExactlyOnceGuard::new(store.clone());
+                    let req = &req;
+                    scope.spawn(move || tokio_test::block_on(guard.begin(req)))
+                })
+                .collect();
+            attempts.into_iter().map(|a| a.join().unwrap()).collect()
+        });
+        assert_eq!(outcomes.iter().filter(|o| **o == Ok(None)).count(), 1);
+        assert!(outcomes.iter().any(|o| matches!(o, Err(GuardRejection::InProgress { .. }))));
+    }
+}
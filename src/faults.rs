@@ -0,0 +1,214 @@
+//! Fault injection for chaos testing (`test-utils` feature only).
+//!
+//! A `FaultInjector` installed with `EasyCashClient::with_fault_injector` is
+//! consulted at fixed points of `execute_transaction` and may fail, delay or
+//! corrupt that step:
+//!
+//! | Point                   | `Error`                    | `Corrupt`                              |
+//! |-------------------------|----------------------------|----------------------------------------|
+//! | `BeforeQuote`           | `AGENT_UNAVAILABLE`        | quotes carry unparseable fees          |
+//! | `BeforeProofGeneration` | `PROOF_GENERATION_FAILED`  | the proof fails self-verification      |
+//! | `BeforeExecution`       | `AGENT_UNAVAILABLE`        | the response carries a malformed hash  |
+//!
+//! `Delay` sleeps before the step, e.g. to trigger timeouts.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::agent::RouteQuote;
+use crate::types::TransactionRequest;
+
+/// Where in the execution pipeline a fault is injected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InjectionPoint {
+    BeforeQuote,
+    BeforeProofGeneration,
+    BeforeExecution,
+}
+
+/// What happens at an injection point
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fault {
+    /// Fail the step with this message
+    Error(String),
+    /// Sleep before running the step
+    Delay(Duration),
+    /// Run the step but corrupt its result
+    Corrupt,
+}
+
+/// Decides which fault, if any, to inject
+pub trait FaultInjector: Send + Sync {
+    fn inject(&self, point: InjectionPoint, req: &TransactionRequest) -> Option<Fault>;
+}
+
+struct FaultRule {
+    point: InjectionPoint,
+    fault: Fault,
+    reference_id: Option<String>,
+    remaining: Option<usize>,
+}
+
+/// Rule-based injector; the first matching rule wins
+#[derive(Default)]
+pub struct FaultPlan {
+    rules: Mutex<Vec<FaultRule>>,
+    hits: Mutex<Vec<(InjectionPoint, String)>>,
+}
+
+impl FaultPlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Injects `fault` at `point` for every request
+    pub fn with_fault(self, point: InjectionPoint, fault: Fault) -> Self {
+        self.push(point, fault, None, None)
+    }
+
+    /// Injects `fault` at `point` for the next `times` requests only
+    pub fn with_fault_times(self, point: InjectionPoint, fault: Fault, times: usize) -> Self {
+        self.push(point, fault, None, Some(times))
+    }
+
+    /// Injects `fault` at `point` for one reference ID only
+    pub fn with_fault_for(self, point: InjectionPoint, reference_id: impl Into<String>, fault: Fault) -> Self {
+        self.push(point, fault, Some(reference_id.into()), None)
+    }
+
+    /// Injected faults as (point, reference ID), in order
+    pub fn hits(&self) -> Vec<(InjectionPoint, String)> {
+        self.hits.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn push(self, point: InjectionPoint, fault: Fault, reference_id: Option<String>, remaining: Option<usize>) -> Self {
+        self.rules.lock().unwrap_or_else(|e| e.into_inner()).push(FaultRule {
+            point,
+            fault,
+            reference_id,
+            remaining,
+        });
+        self
+    }
+}
+
+impl FaultInjector for FaultPlan {
+    fn inject(&self, point: InjectionPoint, req: &TransactionRequest) -> Option<Fault> {
+        let mut rules = self.rules.lock().unwrap_or_else(|e| e.into_inner());
+        let rule = rules.iter_mut().find(|r| {
+            r.point == point
+                && r.remaining != Some(0)
+                && r.reference_id.as_ref().is_none_or(|id| *id == req.reference_id)
+        })?;
+        if let Some(ref mut remaining) = rule.remaining {
+            *remaining -= 1;
+        }
+        self.hits
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((point, req.reference_id.clone()));
+        Some(rule.fault.clone())
+    }
+}
+
+pub(crate) fn corrupt_quotes(quotes: &mut [RouteQuote]) {
+    for quote in quotes {
+        quote.estimated_fee = "NaN \u{fffd}".to_string();
+        quote.estimated_fee_usd = None;
+    }
+}
+
+pub(crate) fn corrupt_proof(proof: &mut String) {
+    proof.truncate(4);
+}
+
+pub(crate) fn corrupt_tx_hash(tx_hash: &mut String) {
+    *tx_hash = format!("0x{}", "zz".repeat(16));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::EasyCashClient;
+    use crate::errors::ErrorCode;
+    use crate::types::{ChainId, IntentType};
+    use std::sync::Arc;
+
+    fn request(reference_id: &str, is_shielded: bool) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_shielded(is_shielded)
+    }
+
+    fn client(plan: Arc<FaultPlan>) -> EasyCashClient {
+        let config = crate::config::SdkConfig {
+            enable_caching: false,
+            ..Default::default()
+        };
+        EasyCashClient::new(Some(config)).unwrap().with_fault_injector(plan)
+    }
+
+    #[test]
+    fn test_plan_matching() {
+        let plan = FaultPlan::new()
+            .with_fault_for(InjectionPoint::BeforeQuote, "ref_x", Fault::Corrupt)
+            .with_fault_times(InjectionPoint::BeforeQuote, Fault::Error("down".to_string()), 1);
+        let req = request("ref_1", false);
+        assert_eq!(plan.inject(InjectionPoint::BeforeExecution, &req), None);
+        assert_eq!(plan.inject(InjectionPoint::BeforeQuote, &req), Some(Fault::Error("down".to_string())));
+        assert_eq!(plan.inject(InjectionPoint::BeforeQuote, &req), None);
+        assert_eq!(plan.inject(InjectionPoint::BeforeQuote, &request("ref_x", false)), Some(Fault::Corrupt));
+        assert_eq!(plan.hits().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_injected_errors_surface_with_step_codes() {
+        let plan = Arc::new(
+            FaultPlan::new()
+                .with_fault_for(InjectionPoint::BeforeQuote, "ref_q", Fault::Error("discovery down".to_string()))
+                .with_fault_for(InjectionPoint::BeforeProofGeneration, "ref_p", Fault::Corrupt)
+                .with_fault_for(InjectionPoint::BeforeExecution, "ref_e", Fault::Error("agent crashed".to_string())),
+        );
+        let client = client(plan.clone());
+
+        let err = client.execute_transaction(&request("ref_q", false)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::AgentUnavailable);
+        assert!(err.message.contains("discovery down"));
+
+        let err = client.execute_transaction(&request("ref_p", true)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ProofGeneration);
+
+        let err = client.execute_transaction(&request("ref_e", false)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::AgentUnavailable);
+        assert!(err.message.contains("agent crashed"));
+
+        assert!(client.execute_transaction(&request("ref_ok", true)).await.is_ok());
+        assert_eq!(plan.hits().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_quotes_and_response() {
+        let plan = Arc::new(
+            FaultPlan::new()
+                .with_fault_for(InjectionPoint::BeforeQuote, "ref_q", Fault::Corrupt)
+                .with_fault_for(InjectionPoint::BeforeExecution, "ref_e", Fault::Corrupt),
+        );
+        let client = client(plan);
+
+        // Malformed quotes are discarded rather than executed
+        let err = client.execute_transaction(&request("ref_q", false)).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::AgentUnavailable);
+
+        let resp = client.execute_transaction(&request("ref_e", false)).await.unwrap();
+        assert!(hex::decode(resp.tx_hash.trim_start_matches("0x")).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_delay() {
+        let plan = Arc::new(FaultPlan::new().with_fault(InjectionPoint::BeforeExecution, Fault::Delay(Duration::from_secs(5))));
+        let client = client(plan);
+        let started = tokio::time::Instant::now();
+        assert!(client.execute_transaction(&request("ref_1", false)).await.is_ok());
+        assert!(started.elapsed() >= Duration::from_secs(5));
+    }
+}
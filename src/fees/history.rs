@@ -0,0 +1,260 @@
+//! Historical fees per route, asset and hour.
+//!
+//! Executed fees are bucketed by route (source and target chain), asset and
+//! UTC hour. Percentiles summarize what a route has cost; forecasts estimate
+//! the fee of a transfer at a given time from the fees paid for comparable
+//! amounts in the same hour of the day, so large batches can be scheduled at
+//! cheap times.
+//!
+//! ```
+//! use ecash_sdk_core::fees::history::{FeeHistory, FeeRoute};
+//! use ecash_sdk_core::types::ChainId;
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let history = FeeHistory::default();
+//! let route = FeeRoute::new(ChainId::Base, ChainId::Ethereum, "USDC");
+//! let three_am = UNIX_EPOCH + Duration::from_secs(3 * 3600);
+//! for (day, fee) in [0.40, 0.50, 0.60].into_iter().enumerate() {
+//!     history.record_at(&route, 1000.0, fee, three_am + Duration::from_secs(day as u64 * 86_400));
+//! }
+//!
+//! let forecast = history.forecast(&route, 1000.0, three_am + Duration::from_secs(3 * 86_400)).unwrap();
+//! assert_eq!(forecast.expected, 0.50);
+//! assert_eq!(history.percentile(&route, 100.0), Some(0.60));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::types::{ChainId, TransactionRequest};
+
+const HOUR_SECS: u64 = 3600;
+
+/// Route and asset fees are tracked for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct FeeRoute {
+    pub source_chain: ChainId,
+    pub target_chain: ChainId,
+    pub asset: String,
+}
+
+impl FeeRoute {
+    pub fn new(source_chain: ChainId, target_chain: ChainId, asset: impl Into<String>) -> Self {
+        Self {
+            source_chain,
+            target_chain,
+            asset: asset.into().to_ascii_uppercase(),
+        }
+    }
+
+    /// Route of `req`; same-chain transfers have the source as target
+    pub fn of(req: &TransactionRequest) -> Self {
+        Self::new(req.source_chain, req.target_chain.unwrap_or(req.source_chain), &req.asset)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FeeSample {
+    amount: f64,
+    fee: f64,
+}
+
+/// Fees of one route in one UTC hour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HourlyFees {
+    /// Hours since the Unix epoch
+    pub hour: u64,
+    pub count: usize,
+    pub median: f64,
+    pub p90: f64,
+}
+
+/// Expected fee of a transfer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeForecast {
+    /// Median fee of the comparable samples
+    pub expected: f64,
+    /// 25th percentile
+    pub low: f64,
+    /// 75th percentile
+    pub high: f64,
+    /// Number of samples the forecast is based on
+    pub samples: usize,
+    /// True if the samples come from the same hour of the day; otherwise the
+    /// route had no history at that hour and all hours were used
+    pub same_hour: bool,
+}
+
+/// Executed fees per route, asset and hour, kept for `retention`
+pub struct FeeHistory {
+    retention: Duration,
+    routes: Mutex<HashMap<FeeRoute, BTreeMap<u64, Vec<FeeSample>>>>,
+}
+
+impl Default for FeeHistory {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(30 * 86_400))
+    }
+}
+
+impl FeeHistory {
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            routes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<FeeRoute, BTreeMap<u64, Vec<FeeSample>>>> {
+        self.routes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Records a fee paid now for transferring `amount` over `route`
+    pub fn record(&self, route: &FeeRoute, amount: f64, fee: f64) {
+        self.record_at(route, amount, fee, SystemTime::now());
+    }
+
+    /// Records a fee paid at `at`. Hours older than the retention period
+    /// are dropped
+    pub fn record_at(&self, route: &FeeRoute, amount: f64, fee: f64, at: SystemTime) {
+        if !(fee.is_finite() && amount.is_finite()) {
+            return;
+        }
+        let hour = hour_of(at);
+        let oldest = hour.saturating_sub(self.retention.as_secs() / HOUR_SECS);
+        let mut routes = self.lock();
+        let hours = routes.entry(route.clone()).or_default();
+        hours.entry(hour).or_default().push(FeeSample { amount, fee });
+        *hours = hours.split_off(&oldest);
+    }
+
+    /// `p`th percentile (0-100) of all retained fees of `route`
+    pub fn percentile(&self, route: &FeeRoute, p: f64) -> Option<f64> {
+        let routes = self.lock();
+        let fees: Vec<f64> = routes.get(route)?.values().flatten().map(|s| s.fee).collect();
+        percentile(fees, p)
+    }
+
+    /// Median and 90th percentile fee of each hour with executions
+    pub fn hourly(&self, route: &FeeRoute) -> Vec<HourlyFees> {
+        let routes = self.lock();
+        let Some(hours) = routes.get(route) else {
+            return Vec::new();
+        };
+        hours
+            .iter()
+            .filter_map(|(hour, samples)| {
+                let fees: Vec<f64> = samples.iter().map(|s| s.fee).collect();
+                Some(HourlyFees {
+                    hour: *hour,
+                    count: fees.len(),
+                    median: percentile(fees.clone(), 50.0)?,
+                    p90: percentile(fees, 90.0)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Expected fee of transferring `amount` over `route` at `at`, from the
+    /// fees paid for amounts within a factor of ten of it. Returns `None`
+    /// without comparable history
+    pub fn forecast(&self, route: &FeeRoute, amount: f64, at: SystemTime) -> Option<FeeForecast> {
+        let hour_of_day = hour_of(at) % 24;
+        let routes = self.lock();
+        let comparable = |same_hour: bool| -> Vec<f64> {
+            routes
+                .get(route)
+                .into_iter()
+                .flatten()
+                .filter(|(hour, _)| !same_hour || *hour % 24 == hour_of_day)
+                .flat_map(|(_, samples)| samples)
+                .filter(|s| s.amount >= amount / 10.0 && s.amount <= amount * 10.0)
+                .map(|s| s.fee)
+                .collect()
+        };
+        let (fees, same_hour) = match comparable(true) {
+            fees if !fees.is_empty() => (fees, true),
+            _ => (comparable(false), false),
+        };
+        Some(FeeForecast {
+            expected: percentile(fees.clone(), 50.0)?,
+            low: percentile(fees.clone(), 25.0)?,
+            high: percentile(fees.clone(), 75.0)?,
+            samples: fees.len(),
+            same_hour,
+        })
+    }
+}
+
+fn hour_of(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / HOUR_SECS
+}
+
+/// Linearly interpolated percentile
+fn percentile(mut values: Vec<f64>, p: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (values.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    Some(values[lower] + (values[upper] - values[lower]) * (rank - lower as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u64, hour: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(day * 86_400 + hour * HOUR_SECS)
+    }
+
+    #[test]
+    fn test_percentiles_and_hourly() {
+        let history = FeeHistory::default();
+        let route = FeeRoute::new(ChainId::Base, ChainId::Base, "usdc");
+        for (i, fee) in [0.01, 0.02, 0.03, 0.04, 0.05].into_iter().enumerate() {
+            history.record_at(&route, 100.0, fee, at(0, i as u64 % 2));
+        }
+        assert_eq!(history.percentile(&route, 50.0), Some(0.03));
+        assert!((history.percentile(&route, 90.0).unwrap() - 0.046).abs() < 1e-9);
+        assert_eq!(history.percentile(&FeeRoute::new(ChainId::Base, ChainId::Ethereum, "USDC"), 50.0), None);
+
+        let hourly = history.hourly(&route);
+        assert_eq!(hourly.iter().map(|h| (h.hour, h.count)).collect::<Vec<_>>(), [(0, 3), (1, 2)]);
+        assert_eq!(hourly[0].median, 0.03);
+    }
+
+    #[test]
+    fn test_forecast_prefers_same_hour_and_comparable_amounts() {
+        let history = FeeHistory::default();
+        let route = FeeRoute::new(ChainId::Base, ChainId::Ethereum, "USDC");
+        for day in 0..5 {
+            history.record_at(&route, 1000.0, 2.0, at(day, 14));
+            history.record_at(&route, 1000.0, 0.5, at(day, 3));
+            // Far larger transfers don't inform a 1000 USDC forecast
+            history.record_at(&route, 1_000_000.0, 40.0, at(day, 3));
+        }
+
+        let night = history.forecast(&route, 1000.0, at(9, 3)).unwrap();
+        assert_eq!((night.expected, night.samples, night.same_hour), (0.5, 5, true));
+        let afternoon = history.forecast(&route, 1000.0, at(9, 14)).unwrap();
+        assert_eq!(afternoon.expected, 2.0);
+
+        let unseen_hour = history.forecast(&route, 1000.0, at(9, 8)).unwrap();
+        assert!(!unseen_hour.same_hour);
+        assert_eq!((unseen_hour.low, unseen_hour.high, unseen_hour.samples), (0.5, 2.0, 10));
+        assert!(history.forecast(&route, 1.0, at(9, 3)).is_none());
+    }
+
+    #[test]
+    fn test_retention() {
+        let history = FeeHistory::new(Duration::from_secs(2 * 86_400));
+        let route = FeeRoute::new(ChainId::Base, ChainId::Base, "USDC");
+        history.record_at(&route, 100.0, 9.0, at(0, 0));
+        history.record_at(&route, 100.0, 1.0, at(5, 0));
+        assert_eq!(history.percentile(&route, 100.0), Some(1.0));
+    }
+}
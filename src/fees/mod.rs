@@ -0,0 +1,8 @@
+//! Fee analytics.
+//!
+//! `history::FeeHistory` records the fees paid for executed transactions and
+//! answers percentile and forecast queries over them; `schedule::plan` uses
+//! the forecasts to move batches into low-fee windows.
+
+pub mod history;
+pub mod schedule;
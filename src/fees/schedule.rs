@@ -0,0 +1,268 @@
+//! Off-peak scheduling of batches.
+//!
+//! `plan` spreads a batch over the hourly windows between now and a
+//! deadline, placing each request in the window with the lowest forecast fee
+//! (see `FeeHistory::forecast`) while keeping every window within the rate
+//! limit. The returned `BatchSchedule` is a proposal: nothing is executed,
+//! so it can be reviewed and approved before its slots are run.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::history::{FeeHistory, FeeRoute};
+use crate::types::TransactionRequest;
+
+const HOUR: Duration = Duration::from_secs(3600);
+
+/// Limits a schedule must respect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleConstraints {
+    /// Earliest time the batch may start
+    pub start: SystemTime,
+    /// Every request must be scheduled before this time
+    pub deadline: SystemTime,
+    /// Most requests executed within one hourly window
+    pub max_per_hour: usize,
+}
+
+/// Requests to execute together at `start`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledSlot {
+    pub start: SystemTime,
+    #[serde(rename = "reference_ids")]
+    pub reference_ids: Vec<String>,
+    /// Sum of the forecast fees of the slot's requests that have a forecast
+    pub expected_fee: f64,
+}
+
+/// Proposed execution times of a batch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchSchedule {
+    /// Non-empty slots in chronological order
+    pub slots: Vec<ScheduledSlot>,
+    /// Forecast fee of the whole batch as scheduled
+    pub expected_fee: f64,
+    /// Forecast fee of executing the whole batch at the start, for comparison
+    pub immediate_fee: f64,
+    /// Requests without fee history, scheduled as early as possible
+    pub unforecast: usize,
+}
+
+impl BatchSchedule {
+    /// Forecast saving over executing the whole batch immediately
+    pub fn expected_savings(&self) -> f64 {
+        self.immediate_fee - self.expected_fee
+    }
+
+    /// Requests of `batch` in the slots due at `now`
+    pub fn due<'a>(&self, batch: &'a [TransactionRequest], now: SystemTime) -> Vec<&'a TransactionRequest> {
+        let due: Vec<&String> = self
+            .slots
+            .iter()
+            .filter(|slot| slot.start <= now)
+            .flat_map(|slot| &slot.reference_ids)
+            .collect();
+        batch.iter().filter(|req| due.contains(&&req.reference_id)).collect()
+    }
+}
+
+/// Plans `batch` into the cheapest forecast windows before the deadline.
+///
+/// Fails if the batch cannot fit before the deadline at `max_per_hour`, or
+/// contains an unparsable amount.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::fees::history::{FeeHistory, FeeRoute};
+/// use ecash_sdk_core::fees::schedule::{plan, ScheduleConstraints};
+/// use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let history = FeeHistory::default();
+/// let route = FeeRoute::new(ChainId::Base, ChainId::Base, "USDC");
+/// let hour = |h: u64| UNIX_EPOCH + Duration::from_secs(h * 3600);
+/// history.record_at(&route, 500.0, 0.90, hour(12));
+/// history.record_at(&route, 500.0, 0.10, hour(15));
+///
+/// let req = TransactionRequest::new("payroll-1", IntentType::Transfer, "500", "USDC", ChainId::Base);
+/// // Next day, from noon with a 6pm deadline: 3pm has been cheapest
+/// let constraints = ScheduleConstraints { start: hour(36), deadline: hour(42), max_per_hour: 10 };
+/// let schedule = plan(&history, &[req], &constraints).unwrap();
+/// assert_eq!(schedule.slots[0].start, hour(39));
+/// assert!((schedule.expected_savings() - 0.80).abs() < 1e-9);
+/// ```
+pub fn plan(
+    history: &FeeHistory,
+    batch: &[TransactionRequest],
+    constraints: &ScheduleConstraints,
+) -> Result<BatchSchedule, String> {
+    let windows = windows(constraints);
+    let capacity = windows.len().saturating_mul(constraints.max_per_hour);
+    if batch.len() > capacity {
+        return Err(format!(
+            "batch of {} does not fit before the deadline at {} per hour ({} windows)",
+            batch.len(),
+            constraints.max_per_hour,
+            windows.len()
+        ));
+    }
+
+    // Forecasts depend only on the route, amount and hour of the day
+    let mut memo: HashMap<(FeeRoute, u64, u64), Option<f64>> = HashMap::new();
+    let mut forecasts = Vec::with_capacity(batch.len());
+    for req in batch {
+        let amount: f64 = req
+            .amount
+            .parse()
+            .map_err(|_| format!("invalid amount {:?} of {}", req.amount, req.reference_id))?;
+        let route = FeeRoute::of(req);
+        let fees: Vec<Option<f64>> = windows
+            .iter()
+            .map(|window| {
+                let key = (route.clone(), hour_of_day(*window), amount.to_bits());
+                *memo
+                    .entry(key)
+                    .or_insert_with(|| history.forecast(&route, amount, *window).map(|f| f.expected))
+            })
+            .collect();
+        forecasts.push(fees);
+    }
+
+    // Requests that can save the most pick their window first
+    let savings = |fees: &[Option<f64>]| {
+        let known = fees.iter().flatten();
+        let max = known.clone().copied().fold(f64::MIN, f64::max);
+        let min = known.copied().fold(f64::MAX, f64::min);
+        if max >= min { max - min } else { -1.0 }
+    };
+    let mut order: Vec<usize> = (0..batch.len()).collect();
+    order.sort_by(|a, b| {
+        savings(&forecasts[*b])
+            .partial_cmp(&savings(&forecasts[*a]))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut load = vec![0usize; windows.len()];
+    let mut assigned: Vec<Vec<usize>> = vec![Vec::new(); windows.len()];
+    let mut expected = vec![0.0; windows.len()];
+    let (mut immediate_fee, mut unforecast) = (0.0, 0);
+    for i in order {
+        let fees = &forecasts[i];
+        let open = (0..windows.len()).filter(|w| load[*w] < constraints.max_per_hour);
+        let window = match fees.iter().any(Option::is_some) {
+            // Unknown windows rank after every forecast one, earliest first
+            true => open.min_by(|a, b| {
+                let fee = |w: usize| fees[w].unwrap_or(f64::MAX);
+                fee(*a).partial_cmp(&fee(*b)).unwrap_or(std::cmp::Ordering::Equal)
+            }),
+            false => {
+                unforecast += 1;
+                open.min()
+            }
+        }
+        .ok_or_else(|| "no window left before the deadline".to_string())?;
+        load[window] += 1;
+        assigned[window].push(i);
+        expected[window] += fees[window].unwrap_or(0.0);
+        immediate_fee += fees[0].or(fees[window]).unwrap_or(0.0);
+    }
+
+    let slots: Vec<ScheduledSlot> = windows
+        .iter()
+        .zip(assigned)
+        .zip(expected)
+        .filter(|((_, requests), _)| !requests.is_empty())
+        .map(|((start, mut requests), expected_fee)| {
+            requests.sort_unstable();
+            ScheduledSlot {
+                start: *start,
+                reference_ids: requests.into_iter().map(|i| batch[i].reference_id.clone()).collect(),
+                expected_fee,
+            }
+        })
+        .collect();
+    Ok(BatchSchedule {
+        expected_fee: slots.iter().map(|s| s.expected_fee).sum(),
+        slots,
+        immediate_fee,
+        unforecast,
+    })
+}
+
+/// Start of each window: the start time, then every following full hour
+/// before the deadline
+fn windows(constraints: &ScheduleConstraints) -> Vec<SystemTime> {
+    let mut windows = Vec::new();
+    let mut window = constraints.start;
+    while window < constraints.deadline {
+        windows.push(window);
+        let since_epoch = window.duration_since(UNIX_EPOCH).unwrap_or_default();
+        window = UNIX_EPOCH + Duration::from_secs((since_epoch.as_secs() / HOUR.as_secs() + 1) * HOUR.as_secs());
+    }
+    windows
+}
+
+fn hour_of_day(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / HOUR.as_secs() % 24
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+
+    fn hour(h: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(h * 3600)
+    }
+
+    fn request(reference_id: &str, amount: &str, target_chain: Option<ChainId>) -> TransactionRequest {
+        TransactionRequest {
+            target_chain,
+            ..TransactionRequest::new(reference_id, IntentType::Transfer, amount, "USDC", ChainId::Base)
+        }
+    }
+
+    #[test]
+    fn test_cheap_windows_fill_up_to_the_rate_limit() {
+        let history = FeeHistory::default();
+        let route = FeeRoute::new(ChainId::Base, ChainId::Base, "USDC");
+        for (h, fee) in [(0, 0.5), (1, 0.1), (2, 0.3)] {
+            history.record_at(&route, 100.0, fee, hour(h));
+        }
+        let batch: Vec<_> = (0..5).map(|i| request(&format!("ref_{}", i), "100", None)).collect();
+        // Start half past midnight on day 2, deadline 3am
+        let constraints = ScheduleConstraints {
+            start: hour(48) + Duration::from_secs(1800),
+            deadline: hour(51),
+            max_per_hour: 2,
+        };
+        let schedule = plan(&history, &batch, &constraints).unwrap();
+        let slots: Vec<(SystemTime, usize)> = schedule.slots.iter().map(|s| (s.start, s.reference_ids.len())).collect();
+        assert_eq!(slots, [(constraints.start, 1), (hour(49), 2), (hour(50), 2)]);
+        assert!((schedule.expected_fee - (0.5 + 0.2 + 0.6)).abs() < 1e-9);
+        assert!((schedule.immediate_fee - 2.5).abs() < 1e-9);
+
+        let due = schedule.due(&batch, hour(49));
+        assert_eq!(due.len(), 3);
+
+        let tight = ScheduleConstraints { deadline: hour(50), ..constraints };
+        assert!(plan(&history, &batch, &tight).unwrap_err().contains("does not fit"));
+    }
+
+    #[test]
+    fn test_unforecast_requests_go_first() {
+        let history = FeeHistory::default();
+        let route = FeeRoute::new(ChainId::Base, ChainId::Base, "USDC");
+        history.record_at(&route, 100.0, 0.5, hour(0));
+        history.record_at(&route, 100.0, 0.1, hour(1));
+        let batch = [request("known", "100", None), request("unknown", "100", Some(ChainId::Ethereum))];
+        let constraints = ScheduleConstraints { start: hour(24), deadline: hour(26), max_per_hour: 1 };
+
+        let schedule = plan(&history, &batch, &constraints).unwrap();
+        assert_eq!(schedule.unforecast, 1);
+        assert_eq!(schedule.slots[0].reference_ids, ["unknown"]);
+        assert_eq!(schedule.slots[1].reference_ids, ["known"]);
+        assert!(plan(&history, &[request("bad", "ten", None)], &constraints).is_err());
+    }
+}
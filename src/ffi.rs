@@ -0,0 +1,261 @@
+//! C ABI for embedding the SDK in non-Rust backends (Go via cgo, Python via
+//! ctypes, ...).
+//!
+//! All data crosses the boundary as NUL-terminated UTF-8 JSON. Every call
+//! returns `ECASH_OK` (0) or a positive error code; when a call produces
+//! output, `*out_json` receives either the result or an `SdkErrorResponse`
+//! JSON object. Strings returned by the SDK must be released with
+//! `ecash_string_free`, clients with `ecash_client_free`.
+//!
+//! The header is `include/ecash_sdk.h` (regenerate with `make header`).
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::blocking::EasyCashClientBlocking;
+use crate::config::SdkConfig;
+use crate::errors::{ErrorCode, SdkError, SdkErrorResponse};
+use crate::types::TransactionRequest;
+
+/// Call succeeded
+pub const ECASH_OK: i32 = 0;
+/// A pointer argument was null or a string was not valid UTF-8
+pub const ECASH_ERR_INVALID_ARGUMENT: i32 = 100;
+/// The SDK panicked; the client should be discarded
+pub const ECASH_ERR_INTERNAL: i32 = 101;
+
+/// Opaque client handle
+pub struct EcashClient {
+    inner: EasyCashClientBlocking,
+}
+
+/// Returns the integer code used for an error code across the C ABI
+pub fn error_code_to_int(code: ErrorCode) -> i32 {
+    match code {
+        ErrorCode::InvalidRequest => 1,
+        ErrorCode::InsufficientFunds => 2,
+        ErrorCode::NetworkFailure => 3,
+        ErrorCode::ProofGeneration => 4,
+        ErrorCode::AgentUnavailable => 5,
+        ErrorCode::Timeout => 6,
+        ErrorCode::RateLimited => 7,
+        ErrorCode::FeeTooHigh => 8,
+        ErrorCode::UnsupportedChain => 9,
+        ErrorCode::UnsupportedAsset => 10,
+        ErrorCode::DuplicateReference => 11,
+        ErrorCode::ComplianceRejected => 12,
+        ErrorCode::PolicyViolation => 13,
+        ErrorCode::Expired => 14,
+        ErrorCode::SignerUnavailable => 15,
+        ErrorCode::BudgetExceeded => 16,
+        ErrorCode::UnsupportedProtocol => 17,
+        ErrorCode::Cancelled => 18,
+        ErrorCode::ExecutionReverted => 19,
+    }
+}
+
+/// Creates a client.
+///
+/// `config_json` may be null for the default configuration, or a JSON object
+/// whose top-level keys override the defaults (e.g. `{"api_key": "..."}`).
+///
+/// # Safety
+/// `config_json` must be null or a valid NUL-terminated string, and `out_client`
+/// must be a valid pointer to writable storage.
+#[no_mangle]
+pub unsafe extern "C" fn ecash_client_new(config_json: *const c_char, out_client: *mut *mut EcashClient) -> i32 {
+    if out_client.is_null() {
+        return ECASH_ERR_INVALID_ARGUMENT;
+    }
+    *out_client = ptr::null_mut();
+    let config = if config_json.is_null() {
+        None
+    } else {
+        match read_str(config_json).map(SdkConfig::from_json) {
+            Some(Ok(config)) => Some(config),
+            Some(Err(_)) => return error_code_to_int(ErrorCode::InvalidRequest),
+            None => return ECASH_ERR_INVALID_ARGUMENT,
+        }
+    };
+
+    guard(|| match EasyCashClientBlocking::new(config) {
+        Ok(inner) => {
+            *out_client = Box::into_raw(Box::new(EcashClient { inner }));
+            ECASH_OK
+        }
+        Err(e) => error_code_to_int(e.code),
+    })
+}
+
+/// Releases a client created by `ecash_client_new`.
+///
+/// # Safety
+/// `client` must be null or a pointer returned by `ecash_client_new` that has
+/// not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ecash_client_free(client: *mut EcashClient) {
+    if !client.is_null() {
+        drop(Box::from_raw(client));
+    }
+}
+
+/// Executes a `TransactionRequest` given as JSON, blocking until it completes.
+///
+/// On success `*out_json` receives the `TransactionResponse`; on SDK errors it
+/// receives an `SdkErrorResponse`.
+///
+/// # Safety
+/// `client` must come from `ecash_client_new`, `request_json` must be a valid
+/// NUL-terminated string and `out_json` a valid pointer to writable storage.
+#[no_mangle]
+pub unsafe extern "C" fn ecash_execute_transaction_json(
+    client: *const EcashClient,
+    request_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if client.is_null() || out_json.is_null() {
+        return ECASH_ERR_INVALID_ARGUMENT;
+    }
+    *out_json = ptr::null_mut();
+    let Some(request) = read_str(request_json) else { return ECASH_ERR_INVALID_ARGUMENT };
+    let client = &*client;
+
+    guard(|| {
+        let result = serde_json::from_str::<TransactionRequest>(request)
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid request JSON: {}", e)))
+            .and_then(|req| client.inner.execute_transaction(&req));
+        match result {
+            Ok(resp) => write_json(out_json, &resp, ECASH_OK),
+            Err(e) => write_json(out_json, &SdkErrorResponse::from(&e), error_code_to_int(e.code)),
+        }
+    })
+}
+
+/// Writes the client's metrics snapshot as JSON to `*out_json`.
+///
+/// # Safety
+/// `client` must come from `ecash_client_new` and `out_json` must be a valid
+/// pointer to writable storage.
+#[no_mangle]
+pub unsafe extern "C" fn ecash_get_metrics_json(client: *const EcashClient, out_json: *mut *mut c_char) -> i32 {
+    if client.is_null() || out_json.is_null() {
+        return ECASH_ERR_INVALID_ARGUMENT;
+    }
+    *out_json = ptr::null_mut();
+    let client = &*client;
+    guard(|| write_json(out_json, &client.inner.metrics_snapshot(), ECASH_OK))
+}
+
+/// Releases a string returned by the SDK.
+///
+/// # Safety
+/// `s` must be null or a string returned by this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ecash_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+unsafe fn read_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+unsafe fn write_json<T: serde::Serialize>(out_json: *mut *mut c_char, value: &T, code: i32) -> i32 {
+    let json = serde_json::to_string(value).unwrap_or_else(|_| "null".to_string());
+    match CString::new(json) {
+        Ok(s) => {
+            *out_json = s.into_raw();
+            code
+        }
+        Err(_) => ECASH_ERR_INTERNAL,
+    }
+}
+
+/// Keeps panics from unwinding across the C boundary
+fn guard(f: impl FnOnce() -> i32) -> i32 {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(ECASH_ERR_INTERNAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn take_string(s: *mut c_char) -> String {
+        let value = CStr::from_ptr(s).to_str().unwrap().to_string();
+        ecash_string_free(s);
+        value
+    }
+
+    #[test]
+    fn test_execute_transaction_json_round_trip() {
+        unsafe {
+            let config = CString::new(r#"{"enable_caching": false}"#).unwrap();
+            let mut client = ptr::null_mut();
+            assert_eq!(ecash_client_new(config.as_ptr(), &mut client), ECASH_OK);
+
+            let request = CString::new(
+                r#"{"reference_id":"ffi_1","type":"transfer","amount":"10.00","asset":"USDC","source_chain":"base","is_shielded":false}"#,
+            )
+            .unwrap();
+            let mut out = ptr::null_mut();
+            assert_eq!(ecash_execute_transaction_json(client, request.as_ptr(), &mut out), ECASH_OK);
+            let resp: serde_json::Value = serde_json::from_str(&take_string(out)).unwrap();
+            assert_eq!(resp["status"], "confirmed");
+
+            let bad = CString::new(r#"{"reference_id":"ffi_2"}"#).unwrap();
+            assert_eq!(ecash_execute_transaction_json(client, bad.as_ptr(), &mut out), 1);
+            let err: serde_json::Value = serde_json::from_str(&take_string(out)).unwrap();
+            assert_eq!(err["code"], "INVALID_REQUEST");
+
+            assert_eq!(ecash_get_metrics_json(client, &mut out), ECASH_OK);
+            let metrics: serde_json::Value = serde_json::from_str(&take_string(out)).unwrap();
+            assert_eq!(metrics["total_transactions"], 1);
+
+            ecash_client_free(client);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        unsafe {
+            assert_eq!(ecash_client_new(ptr::null(), ptr::null_mut()), ECASH_ERR_INVALID_ARGUMENT);
+            let mut client = ptr::null_mut();
+            let config = CString::new("[]").unwrap();
+            assert_eq!(ecash_client_new(config.as_ptr(), &mut client), 1);
+            assert!(client.is_null());
+
+            let mut out = ptr::null_mut();
+            assert_eq!(
+                ecash_execute_transaction_json(ptr::null(), ptr::null(), &mut out),
+                ECASH_ERR_INVALID_ARGUMENT
+            );
+            ecash_client_free(ptr::null_mut());
+            ecash_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_header_declares_exports_and_codes() {
+        let header = include_str!("../include/ecash_sdk.h");
+        for symbol in [
+            "ecash_client_new",
+            "ecash_client_free",
+            "ecash_execute_transaction_json",
+            "ecash_get_metrics_json",
+            "ecash_string_free",
+        ] {
+            assert!(header.contains(&format!("{}(", symbol)), "{} missing from header", symbol);
+        }
+        assert!(header.contains(&format!("#define ECASH_ERR_INVALID_ARGUMENT {}", ECASH_ERR_INVALID_ARGUMENT)));
+        assert!(header.contains(&format!("#define ECASH_ERR_INTERNAL {}", ECASH_ERR_INTERNAL)));
+        assert!(header.contains(&format!(
+            "#define ECASH_ERR_BUDGET_EXCEEDED {}",
+            error_code_to_int(ErrorCode::BudgetExceeded)
+        )));
+    }
+}
@@ -0,0 +1,84 @@
+//! Execution hooks.
+//!
+//! Hooks registered with `EasyCashClient::with_execution_hook` run around
+//! every execution, in registration order, so custom logging, policy,
+//! enrichment or request rewriting plugs in without changing the client:
+//!
+//! 1. `before_validate` may rewrite the request (e.g. fill in metadata) or
+//!    refuse it before any check runs.
+//! 2. `before_execute` sees the validated request and the selected route just
+//!    before the hand-off to the agent, and may still refuse it.
+//! 3. `after_execute` observes the result of every execution that got past
+//!    admission.
+//!
+//! An error from a hook stops the chain and fails the execution with that
+//! error, so hooks choose the error code callers see.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::agent::RouteQuote;
+use crate::errors::Result;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+/// Middleware run around executions. Every method defaults to a no-op.
+#[async_trait::async_trait]
+pub trait ExecutionHook: Send + Sync {
+    /// Runs before validation; may modify the request
+    async fn before_validate(&self, _req: &mut TransactionRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs after all checks, right before the request is handed to the
+    /// agent of `route`
+    async fn before_execute(&self, _req: &TransactionRequest, _route: &RouteQuote) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs after the execution with its result
+    async fn after_execute(&self, _req: &TransactionRequest, _result: &Result<TransactionResponse>) {}
+}
+
+/// Ordered chain of execution hooks
+#[derive(Clone, Default)]
+pub struct ExecutionHooks {
+    hooks: Vec<Arc<dyn ExecutionHook>>,
+}
+
+impl ExecutionHooks {
+    /// Appends a hook to the end of the chain
+    pub fn with_hook(mut self, hook: Arc<dyn ExecutionHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Passes the request through every `before_validate`; borrows it
+    /// unchanged when no hook is registered
+    pub async fn before_validate<'a>(&self, req: &'a TransactionRequest) -> Result<Cow<'a, TransactionRequest>> {
+        if self.hooks.is_empty() {
+            return Ok(Cow::Borrowed(req));
+        }
+        let mut req = req.clone();
+        for hook in &self.hooks {
+            hook.before_validate(&mut req).await?;
+        }
+        Ok(Cow::Owned(req))
+    }
+
+    pub async fn before_execute(&self, req: &TransactionRequest, route: &RouteQuote) -> Result<()> {
+        for hook in &self.hooks {
+            hook.before_execute(req, route).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn after_execute(&self, req: &TransactionRequest, result: &Result<TransactionResponse>) {
+        for hook in &self.hooks {
+            hook.after_execute(req, result).await;
+        }
+    }
+}
@@ -0,0 +1,655 @@
+//! Minimal HTTP/1.1 client used by the SDK's network-backed providers.
+//!
+//! An `HttpClient` carries one configuration's egress proxy, TLS and request
+//! signing settings along with its own keep-alive pool (see
+//! `network::PoolConfig`). `EasyCashClient::new` builds one from its
+//! `SdkConfig` and shares it with the components it creates; providers
+//! built separately take it with their `with_http_client`. Plain `http://`
+//! works out of the box; `https://` needs a `TlsConnector`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+use crate::config::SdkConfig;
+use crate::monitoring::ConnectionPoolStats;
+use crate::network::{Egress, PoolConfig};
+use crate::request_signing::ApiSigner;
+use crate::tls::{BoxedStream, TlsConnector, TlsMaterial};
+
+/// Upper bound on response headers
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+
+type Connection = BufReader<BoxedStream>;
+type PoolKey = (String, u16, bool);
+
+/// Parsed `http[s]://host[:port]/path` URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpUrl {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+    /// True for `https://`
+    pub tls: bool,
+}
+
+impl HttpUrl {
+    /// Parses an HTTP or HTTPS URL
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let (rest, tls) = if let Some(rest) = url.strip_prefix("http://") {
+            (rest, false)
+        } else if let Some(rest) = url.strip_prefix("https://") {
+            (rest, true)
+        } else {
+            return Err(format!("unsupported URL scheme (expected http:// or https://): {}", url));
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/"),
+        };
+        if authority.is_empty() {
+            return Err(format!("missing host in URL: {}", url));
+        }
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>().map_err(|_| format!("invalid port in URL: {}", url))?,
+            ),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+            tls,
+        })
+    }
+}
+
+/// Response returned by the HTTP client
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    /// Body decoded as UTF-8 (invalid sequences replaced)
+    pub body: String,
+    /// Body exactly as received, for binary content
+    pub body_bytes: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Returns true for 2xx status codes
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// HTTP client applying one configuration's network settings.
+///
+/// Clones share the pool and the TLS connector. The default client connects
+/// directly, without TLS connector or request signing.
+#[derive(Clone, Default)]
+pub struct HttpClient(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    egress: Egress,
+    tls: TlsMaterial,
+    connector: RwLock<Option<Arc<dyn TlsConnector>>>,
+    signer: Option<ApiSigner>,
+    pool: Pool,
+}
+
+impl std::fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("tls", &self.0.tls)
+            .field("has_tls_connector", &self.connector().is_some())
+            .field("signs_requests", &self.0.signer.is_some())
+            .finish()
+    }
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Client using the proxy, pool, TLS certificates and request signing
+    /// key `config` sets for its environment
+    pub fn from_config(config: &SdkConfig) -> Result<Self, String> {
+        Ok(Self(Arc::new(Inner {
+            egress: Egress::new(&config.network, &config.environment)?,
+            tls: config.tls.load()?,
+            connector: RwLock::new(None),
+            signer: ApiSigner::new(&config.request_signing, &config.environment, config.endpoint())?,
+            pool: Pool::new(config.network.pool.clone()),
+        })))
+    }
+
+    /// Performs the TLS handshake of `https://` and `wss://` connections
+    /// with `connector`, for this client and its clones
+    pub fn with_tls_connector(self, connector: Arc<dyn TlsConnector>) -> Self {
+        self.set_tls_connector(connector);
+        self
+    }
+
+    pub(crate) fn set_tls_connector(&self, connector: Arc<dyn TlsConnector>) {
+        *self.0.connector.write().unwrap_or_else(|e| e.into_inner()) = Some(connector);
+    }
+
+    fn connector(&self) -> Option<Arc<dyn TlsConnector>> {
+        self.0.connector.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Sends a request and returns the response.
+    ///
+    /// Header names in the response are lower-cased.
+    pub async fn send(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&str>,
+        timeout: Duration,
+    ) -> Result<HttpResponse, String> {
+        tokio::time::timeout(timeout, self.send_inner(method, url, headers, body))
+            .await
+            .map_err(|_| format!("request to {} timed out after {:?}", url, timeout))?
+    }
+
+    /// Sends a JSON `POST` request
+    pub async fn post_json(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<HttpResponse, String> {
+        let mut all_headers = vec![("Content-Type", "application/json")];
+        all_headers.extend_from_slice(headers);
+        self.send("POST", url, &all_headers, Some(&body.to_string()), timeout).await
+    }
+
+    /// Sends a `GET` request
+    pub async fn get(&self, url: &str, headers: &[(&str, &str)], timeout: Duration) -> Result<HttpResponse, String> {
+        self.send("GET", url, headers, None, timeout).await
+    }
+
+    /// Connection reuse counters for this client's pool
+    pub fn pool_stats(&self) -> ConnectionPoolStats {
+        self.0.pool.stats()
+    }
+
+    /// Opens a TCP connection to `host:port` through the egress settings
+    pub(crate) async fn connect_tcp(&self, host: &str, port: u16) -> Result<tokio::net::TcpStream, String> {
+        self.0.egress.connect(host, port).await
+    }
+
+    /// Opens a connection to `url`, wrapped in TLS for `https://`
+    pub(crate) async fn connect(&self, url: &HttpUrl) -> Result<BoxedStream, String> {
+        if !url.tls {
+            return Ok(Box::new(self.connect_tcp(&url.host, url.port).await?));
+        }
+        let connector = self
+            .connector()
+            .ok_or_else(|| "TLS connections need a connector (see HttpClient::with_tls_connector)".to_string())?;
+        let stream = self.connect_tcp(&url.host, url.port).await?;
+        connector.connect(&url.host, stream, &self.0.tls).await
+    }
+
+    /// Signature headers for a request to `host`, if it is the API host
+    pub(crate) fn signature_headers(&self, host: &str, method: &str, path: &str, body: &[u8]) -> Vec<(&'static str, String)> {
+        match &self.0.signer {
+            Some(signer) => signer.headers_for(host, method, path, body),
+            None => Vec::new(),
+        }
+    }
+
+    async fn send_inner(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&str>,
+    ) -> Result<HttpResponse, String> {
+        let url = HttpUrl::parse(url)?;
+        let key = (url.host.clone(), url.port, url.tls);
+        let request = self.build_request(method, &url, headers, body.unwrap_or(""));
+        let pool = &self.0.pool;
+
+        if let Some(mut conn) = pool.checkout(&key) {
+            pool.reused.fetch_add(1, Ordering::Relaxed);
+            match exchange(&mut conn, &request, method).await {
+                Ok((response, reusable)) => {
+                    if reusable {
+                        pool.checkin(key, conn);
+                    }
+                    return Ok(response);
+                }
+                // The server closed the idle connection before answering
+                Err(Exchange::Stale) => {
+                    tracing::debug!("[SDK] Pooled connection to {}:{} was closed; reconnecting", url.host, url.port)
+                }
+                Err(Exchange::Failed(e)) => return Err(e),
+            }
+        }
+
+        let mut conn = BufReader::new(self.connect(&url).await?);
+        pool.opened.fetch_add(1, Ordering::Relaxed);
+        match exchange(&mut conn, &request, method).await {
+            Ok((response, reusable)) => {
+                if reusable {
+                    pool.checkin(key, conn);
+                }
+                Ok(response)
+            }
+            Err(Exchange::Stale) => Err("connection closed before a response was received".to_string()),
+            Err(Exchange::Failed(e)) => Err(e),
+        }
+    }
+
+    fn build_request(&self, method: &str, url: &HttpUrl, headers: &[(&str, &str)], body: &str) -> String {
+        let connection = if self.0.pool.enabled() { "keep-alive" } else { "close" };
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: {}\r\nContent-Length: {}\r\n",
+            method,
+            url.path,
+            url.host,
+            connection,
+            body.len()
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        for (name, value) in self.signature_headers(&url.host, method, &url.path, body.as_bytes()) {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+        request
+    }
+}
+
+enum Exchange {
+    /// No response bytes arrived, so the request can be retried elsewhere
+    Stale,
+    Failed(String),
+}
+
+/// Sends `request` and reads the response; also returns whether the
+/// connection can be reused
+async fn exchange(conn: &mut Connection, request: &str, method: &str) -> Result<(HttpResponse, bool), Exchange> {
+    if conn.get_mut().write_all(request.as_bytes()).await.is_err() {
+        return Err(Exchange::Stale);
+    }
+    match conn.fill_buf().await {
+        Ok([]) | Err(_) => return Err(Exchange::Stale),
+        Ok(_) => {}
+    }
+    read_response(conn, method).await.map_err(Exchange::Failed)
+}
+
+async fn read_response<R: AsyncBufRead + Unpin>(reader: &mut R, method: &str) -> Result<(HttpResponse, bool), String> {
+    let read_err = |e: std::io::Error| format!("failed to read response: {}", e);
+    let mut head = Vec::new();
+    loop {
+        let n = reader.read_until(b'\n', &mut head).await.map_err(read_err)?;
+        if n == 0 || head.ends_with(b"\r\n\r\n") {
+            break;
+        }
+        if head.len() > MAX_HEADER_SIZE {
+            return Err("response headers too large".to_string());
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    if !head.ends_with("\r\n\r\n") {
+        return Err("malformed HTTP response: missing header terminator".to_string());
+    }
+
+    let mut lines = head.trim_end().split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or_else(|| format!("malformed HTTP status line: {}", status_line))?;
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+    let mut reusable = !headers
+        .get("connection")
+        .map(|v| v.to_ascii_lowercase().contains("close"))
+        .unwrap_or(false);
+
+    let mut body = Vec::new();
+    if method.eq_ignore_ascii_case("HEAD") || status == 204 || status == 304 {
+        // No body
+    } else if headers
+        .get("transfer-encoding")
+        .map(|v| v.eq_ignore_ascii_case("chunked"))
+        .unwrap_or(false)
+    {
+        read_chunked(reader, &mut body).await?;
+    } else if let Some(len) = headers.get("content-length").and_then(|v| v.parse::<usize>().ok()) {
+        body.resize(len, 0);
+        reader.read_exact(&mut body).await.map_err(read_err)?;
+    } else {
+        // Delimited by the server closing the connection
+        reader.read_to_end(&mut body).await.map_err(read_err)?;
+        reusable = false;
+    }
+
+    Ok((
+        HttpResponse {
+            status,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+            body_bytes: body,
+        },
+        reusable,
+    ))
+}
+
+async fn read_chunked<R: AsyncBufRead + Unpin>(reader: &mut R, out: &mut Vec<u8>) -> Result<(), String> {
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        reader
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(|e| format!("failed to read chunk: {}", e))?;
+        let size_line = String::from_utf8_lossy(&line);
+        let size = usize::from_str_radix(size_line.split(';').next().unwrap_or("").trim(), 16)
+            .map_err(|_| format!("invalid chunk size: {}", size_line.trim()))?;
+        if size == 0 {
+            // Skip trailers up to the terminating empty line
+            loop {
+                line.clear();
+                let n = reader
+                    .read_until(b'\n', &mut line)
+                    .await
+                    .map_err(|e| format!("failed to read chunk trailer: {}", e))?;
+                if n == 0 || line == b"\r\n" {
+                    return Ok(());
+                }
+            }
+        }
+        let start = out.len();
+        out.resize(start + size + 2, 0);
+        reader.read_exact(&mut out[start..]).await.map_err(|_| "truncated chunk".to_string())?;
+        if !out.ends_with(b"\r\n") {
+            return Err("malformed chunked body".to_string());
+        }
+        out.truncate(start + size);
+    }
+}
+
+/// Keep-alive connections by (host, port, tls)
+#[derive(Default)]
+struct Pool {
+    config: PoolConfig,
+    idle: Mutex<HashMap<PoolKey, Vec<(Connection, Instant)>>>,
+    opened: AtomicU64,
+    reused: AtomicU64,
+}
+
+impl Pool {
+    fn new(config: PoolConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.config.max_idle_per_host > 0
+    }
+
+    fn checkout(&self, key: &PoolKey) -> Option<Connection> {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        let conns = idle.get_mut(key)?;
+        // Most recently used first; older ones are more likely to be closed
+        while let Some((conn, since)) = conns.pop() {
+            if since.elapsed() < self.config.idle_timeout {
+                return Some(conn);
+            }
+        }
+        None
+    }
+
+    fn checkin(&self, key: PoolKey, conn: Connection) {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        let conns = idle.entry(key).or_default();
+        conns.retain(|(_, since)| since.elapsed() < self.config.idle_timeout);
+        if conns.len() < self.config.max_idle_per_host {
+            conns.push((conn, Instant::now()));
+        }
+    }
+
+    fn stats(&self) -> ConnectionPoolStats {
+        let idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        ConnectionPoolStats {
+            idle_connections: idle
+                .values()
+                .flatten()
+                .filter(|(_, since)| since.elapsed() < self.config.idle_timeout)
+                .count() as u64,
+            connections_opened: self.opened.load(Ordering::Relaxed),
+            connections_reused: self.reused.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::TlsVersion;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[test]
+    fn test_parse_url() {
+        let url = HttpUrl::parse("http://localhost:8080/v1/screen").unwrap();
+        assert_eq!(url.host, "localhost");
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.path, "/v1/screen");
+
+        let url = HttpUrl::parse("http://example.com").unwrap();
+        assert_eq!(url.port, 80);
+        assert_eq!(url.path, "/");
+    }
+
+    #[test]
+    fn test_parse_url_schemes() {
+        let url = HttpUrl::parse("https://api.easycash.example/v1").unwrap();
+        assert!(url.tls);
+        assert_eq!(url.port, 443);
+        assert!(!HttpUrl::parse("http://example.com").unwrap().tls);
+        assert!(HttpUrl::parse("ftp://example.com").is_err());
+        assert!(HttpUrl::parse("http://").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parse_chunked_response() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n5\r\npedia\r\n0\r\n\r\n";
+        let (resp, reusable) = read_response(&mut &raw[..], "GET").await.unwrap();
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.body, "Wikipedia");
+        assert!(reusable);
+
+        let raw = b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\nuntil eof";
+        let (resp, reusable) = read_response(&mut &raw[..], "GET").await.unwrap();
+        assert_eq!(resp.body, "until eof");
+        assert!(!reusable);
+    }
+
+    #[tokio::test]
+    async fn test_post_json_round_trip() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            assert!(request.starts_with("POST /echo HTTP/1.1"));
+            let body = "{\"ok\":true}";
+            let resp = format!(
+                "HTTP/1.1 201 Created\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(resp.as_bytes()).await.unwrap();
+        });
+
+        let url = format!("http://{}/echo", addr);
+        let resp = HttpClient::new()
+            .post_json(&url, &[], &serde_json::json!({"a": 1}), Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(resp.status, 201);
+        assert!(resp.is_success());
+        assert_eq!(resp.body, "{\"ok\":true}");
+    }
+
+    /// Passes bytes through unchanged, recording the server name
+    struct PlaintextConnector(std::sync::Mutex<Vec<String>>);
+
+    #[async_trait::async_trait]
+    impl TlsConnector for PlaintextConnector {
+        async fn connect(
+            &self,
+            server_name: &str,
+            stream: TcpStream,
+            material: &TlsMaterial,
+        ) -> Result<BoxedStream, String> {
+            assert_eq!(material.min_version, TlsVersion::Tls13);
+            self.0.lock().unwrap().push(server_name.to_string());
+            Ok(Box::new(stream))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_https_uses_client_connector() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            assert!(String::from_utf8_lossy(&buf[..n]).starts_with("GET /health"));
+            socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok").await.unwrap();
+        });
+
+        let url = format!("https://127.0.0.1:{}/health", addr.port());
+        // Connectors are per client
+        let err = HttpClient::new().get(&url, &[], Duration::from_secs(2)).await.unwrap_err();
+        assert!(err.contains("need a connector"));
+
+        let connector = Arc::new(PlaintextConnector(Default::default()));
+        let mut config = SdkConfig::default_config();
+        config.tls.min_version = TlsVersion::Tls13;
+        let http = HttpClient::from_config(&config).unwrap().with_tls_connector(connector.clone());
+        let resp = http.get(&url, &[], Duration::from_secs(2)).await.unwrap();
+        assert_eq!(resp.body, "ok");
+        assert_eq!(*connector.0.lock().unwrap(), vec!["127.0.0.1".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_requests_to_api_host_are_signed() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase();
+            assert!(request.contains("x-ecash-key-id: key_1\r\n"));
+            assert!(request.contains("x-ecash-request-signature: hmac-sha256="));
+            socket.write_all(b"HTTP/1.1 204 No Content\r\n\r\n").await.unwrap();
+        });
+
+        let mut config = SdkConfig::default_config();
+        config.api_endpoint = "https://localhost".to_string();
+        config.request_signing = serde_json::from_value(serde_json::json!({
+            "key": {"algorithm": "hmac_sha256", "key_id": "key_1", "secret": "s3cret"}
+        }))
+        .unwrap();
+        let http = HttpClient::from_config(&config).unwrap();
+        // Only the API host is signed
+        assert!(http.signature_headers("127.0.0.1", "GET", "/", b"").is_empty());
+        let resp = http
+            .post_json(&format!("http://localhost:{}/v1/intents", port), &[], &serde_json::json!({}), Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(resp.status, 204);
+    }
+
+    /// Reads one request (headers plus Content-Length body) from `socket`
+    async fn read_request(socket: &mut BufReader<TcpStream>) -> Option<String> {
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            if socket.read_until(b'\n', &mut head).await.ok()? == 0 {
+                return None;
+            }
+        }
+        let head = String::from_utf8(head).unwrap();
+        let len: usize = head
+            .lines()
+            .find_map(|l| l.strip_prefix("Content-Length: "))
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0u8; len];
+        socket.read_exact(&mut body).await.ok()?;
+        Some(head)
+    }
+
+    #[tokio::test]
+    async fn test_keep_alive_connections_are_reused() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            // A single accepted connection serves every request
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut socket = BufReader::new(socket);
+            while let Some(head) = read_request(&mut socket).await {
+                assert!(head.contains("Connection: keep-alive"));
+                socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok").await.unwrap();
+            }
+        });
+
+        let http = HttpClient::new();
+        let url = format!("http://{}/v1/quote", addr);
+        for _ in 0..3 {
+            let resp = http.post_json(&url, &[], &serde_json::json!({}), Duration::from_secs(2)).await.unwrap();
+            assert_eq!(resp.body, "ok");
+        }
+        let stats = http.pool_stats();
+        assert_eq!((stats.connections_opened, stats.connections_reused), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_closed_pooled_connection_is_replaced() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            // Each connection answers once and is then closed by the server
+            for _ in 0..2 {
+                let (socket, _) = listener.accept().await.unwrap();
+                let mut socket = BufReader::new(socket);
+                read_request(&mut socket).await.unwrap();
+                socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok").await.unwrap();
+            }
+        });
+
+        let http = HttpClient::new();
+        let url = format!("http://{}/health", addr);
+        assert_eq!(http.get(&url, &[], Duration::from_secs(2)).await.unwrap().body, "ok");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(http.get(&url, &[], Duration::from_secs(2)).await.unwrap().body, "ok");
+    }
+}
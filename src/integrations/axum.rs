@@ -0,0 +1,286 @@
+//! axum extractors, a tower layer and `SdkError` responses.
+//!
+//! `SdkLayer` gives every request a correlation ID (the caller's
+//! `x-correlation-id` header, or a generated one), echoes it on the response
+//! and applies an optional rate limiter. `TransactionRequest` is an extractor
+//! that parses the JSON body and picks up that correlation ID, and `SdkError`
+//! converts to its HTTP status and `SdkErrorResponse` body, so a withdrawal
+//! endpoint is:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use axum::{extract::State, routing::post, Json, Router};
+//! use ecash_sdk_core::errors::SdkError;
+//! use ecash_sdk_core::integrations::axum::SdkLayer;
+//! use ecash_sdk_core::{EasyCashClient, TransactionRequest, TransactionResponse};
+//!
+//! async fn withdraw(
+//!     State(sdk): State<Arc<EasyCashClient>>,
+//!     req: TransactionRequest,
+//! ) -> Result<Json<TransactionResponse>, SdkError> {
+//!     sdk.execute_transaction(&req).await.map(Json)
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let app: Router = Router::new()
+//!     .route("/withdrawals", post(withdraw))
+//!     .layer(SdkLayer::new())
+//!     .with_state(Arc::new(EasyCashClient::new(None).unwrap()));
+//! # });
+//! ```
+
+use ::axum::body::Bytes;
+use ::axum::extract::{FromRequest, FromRequestParts, Request};
+use ::axum::http::request::Parts;
+use ::axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+use futures_core::future::BoxFuture;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+use super::gateway::{parse_transaction_request, GatewayResponse, CORRELATION_ID_HEADER};
+use crate::errors::{ErrorCode, SdkError};
+use crate::rate_limiter::RateLimiter;
+use crate::types::TransactionRequest;
+
+/// Correlation ID of a request, assigned by `SdkLayer`. As an extractor
+/// without the layer, it is the `x-correlation-id` header or a new ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(pub String);
+
+impl CorrelationId {
+    fn of(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(CORRELATION_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .filter(|id| !id.is_empty())
+            .map(|id| Self(id.to_string()))
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for CorrelationId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<CorrelationId>()
+            .cloned()
+            .or_else(|| CorrelationId::of(&parts.headers))
+            .unwrap_or_else(|| CorrelationId(Uuid::new_v4().to_string())))
+    }
+}
+
+/// Parses the JSON body; the request's correlation ID applies when the body
+/// does not carry one
+impl<S: Send + Sync> FromRequest<S> for TransactionRequest {
+    type Rejection = SdkError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let correlation_id = req
+            .extensions()
+            .get::<CorrelationId>()
+            .cloned()
+            .or_else(|| CorrelationId::of(req.headers()));
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("failed to read request body: {}", e)))?;
+        let id = correlation_id.as_ref().map(|id| id.0.as_str());
+        parse_transaction_request(&body, id).map_err(|err| match id {
+            Some(id) => err.with_correlation_id(id),
+            None => err,
+        })
+    }
+}
+
+impl IntoResponse for GatewayResponse {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut resp = (status, self.body).into_response();
+        for (name, value) in self.headers {
+            if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+                resp.headers_mut().insert(name, value);
+            }
+        }
+        resp
+    }
+}
+
+/// Responds with the error's HTTP status and `SdkErrorResponse` body
+impl IntoResponse for SdkError {
+    fn into_response(self) -> Response {
+        GatewayResponse::from_error(&self).into_response()
+    }
+}
+
+/// tower layer assigning correlation IDs and applying a rate limiter
+#[derive(Clone, Default)]
+pub struct SdkLayer {
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl SdkLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects requests over the limiter's rate with 429 before they reach
+    /// the inner service
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(Arc::new(limiter));
+        self
+    }
+}
+
+impl<S> Layer<S> for SdkLayer {
+    type Service = SdkService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SdkService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Service produced by `SdkLayer`
+#[derive(Clone)]
+pub struct SdkService<S> {
+    inner: S,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<S> Service<Request> for SdkService<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request) -> Self::Future {
+        let correlation_id =
+            CorrelationId::of(req.headers()).unwrap_or_else(|| CorrelationId(Uuid::new_v4().to_string()));
+        req.extensions_mut().insert(correlation_id.clone());
+        // Call the instance that was polled ready
+        let ready = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, ready);
+        let limiter = self.limiter.clone();
+        Box::pin(async move {
+            let limited = match limiter {
+                Some(limiter) => limiter.check().await.err(),
+                None => None,
+            };
+            let mut resp = match limited {
+                Some(err) => SdkError::new(ErrorCode::RateLimited, err).into_response(),
+                None => inner.call(req).await?,
+            };
+            if let Ok(value) = HeaderValue::try_from(correlation_id.0) {
+                resp.headers_mut().insert(CORRELATION_ID_HEADER, value);
+            }
+            Ok(resp)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::EasyCashClient;
+    use crate::rate_limiter::RateLimiterConfig;
+    use crate::types::TransactionResponse;
+    use ::axum::extract::State;
+    use ::axum::routing::post;
+    use ::axum::{Json, Router};
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    const BODY: &str =
+        r#"{"reference_id":"ax_1","type":"transfer","amount":"10.00","asset":"USDC","source_chain":"base","is_shielded":false}"#;
+
+    async fn withdraw(
+        State(sdk): State<Arc<EasyCashClient>>,
+        req: TransactionRequest,
+    ) -> Result<Json<TransactionResponse>, SdkError> {
+        sdk.execute_transaction(&req).await.map(Json)
+    }
+
+    fn app(layer: SdkLayer) -> Router {
+        Router::new()
+            .route("/withdrawals", post(withdraw))
+            .route("/id", post(|id: CorrelationId| async move { id.0 }))
+            .layer(layer)
+            .with_state(Arc::new(EasyCashClient::new(None).unwrap()))
+    }
+
+    fn request(uri: &str, correlation_id: Option<&str>, body: &str) -> Request {
+        let mut req = Request::post(uri).header("content-type", "application/json");
+        if let Some(id) = correlation_id {
+            req = req.header(CORRELATION_ID_HEADER, id);
+        }
+        req.body(body.to_string().into()).unwrap()
+    }
+
+    async fn json(resp: Response) -> serde_json::Value {
+        let body = ::axum::body::to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_executes_with_callers_correlation_id() {
+        let resp = app(SdkLayer::new())
+            .oneshot(request("/withdrawals", Some("corr-ax"), BODY))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[CORRELATION_ID_HEADER], "corr-ax");
+        let body = json(resp).await;
+        assert_eq!((body["status"].as_str(), body["correlation_id"].as_str()), (Some("confirmed"), Some("corr-ax")));
+    }
+
+    #[tokio::test]
+    async fn test_generated_correlation_id_reaches_handler_and_response() {
+        let resp = app(SdkLayer::new()).oneshot(request("/id", None, "")).await.unwrap();
+        let header = resp.headers()[CORRELATION_ID_HEADER].to_str().unwrap().to_string();
+        let body = ::axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+        assert_eq!(body, header.as_bytes());
+        assert!(Uuid::parse_str(&header).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_errors_map_to_http() {
+        let resp = app(SdkLayer::new())
+            .oneshot(request("/withdrawals", Some("corr-bad"), "{not json"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(resp.headers()[CORRELATION_ID_HEADER], "corr-bad");
+        let body = json(resp).await;
+        assert_eq!(body["code"], "INVALID_REQUEST");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited() {
+        let limiter = RateLimiter::new(RateLimiterConfig {
+            max_requests: 1,
+            window: Duration::from_secs(60),
+            enabled: true,
+        });
+        let app = app(SdkLayer::new().with_rate_limiter(limiter));
+
+        let first = app.clone().oneshot(request("/withdrawals", None, BODY)).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let second = app.oneshot(request("/withdrawals", Some("corr-limited"), BODY)).await.unwrap();
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(second.headers()[CORRELATION_ID_HEADER], "corr-limited");
+        assert_eq!(json(second).await["code"], "RATE_LIMITED");
+    }
+}
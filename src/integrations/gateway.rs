@@ -0,0 +1,154 @@
+//! Framework-neutral HTTP gateway over `EasyCashClient`.
+
+use std::sync::Arc;
+
+use crate::client::EasyCashClient;
+use crate::errors::{ErrorCode, SdkError, SdkErrorResponse};
+use crate::rate_limiter::RateLimiter;
+use crate::types::TransactionRequest;
+
+/// Header carrying the caller's correlation ID (echoed on every response)
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// HTTP response produced by the gateway
+#[derive(Debug, Clone, PartialEq)]
+pub struct GatewayResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    /// JSON body
+    pub body: String,
+}
+
+impl GatewayResponse {
+    /// Serializes `value` as a JSON response with the given status
+    pub fn json<T: serde::Serialize>(status: u16, value: &T) -> Self {
+        Self {
+            status,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: serde_json::to_string(value).unwrap_or_else(|_| "null".to_string()),
+        }
+    }
+
+    /// Maps an SDK error to its HTTP status and `SdkErrorResponse` body
+    pub fn from_error(err: &SdkError) -> Self {
+        let mut resp = Self::json(err.to_http_status(), &SdkErrorResponse::from(err));
+        if let Some(ref correlation_id) = err.correlation_id {
+            resp.headers.push((CORRELATION_ID_HEADER.to_string(), correlation_id.clone()));
+        }
+        resp
+    }
+}
+
+/// Parses a JSON `TransactionRequest`, taking the correlation ID from the
+/// header when the body does not carry one
+pub fn parse_transaction_request(body: &[u8], correlation_id: Option<&str>) -> Result<TransactionRequest, SdkError> {
+    let mut req: TransactionRequest = serde_json::from_slice(body)
+        .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid request body: {}", e)))?;
+    if req.correlation_id.is_none() {
+        req.correlation_id = correlation_id.filter(|id| !id.is_empty()).map(str::to_string);
+    }
+    Ok(req)
+}
+
+/// Executes JSON transaction requests on behalf of an HTTP handler, applying
+/// an optional rate limiter first.
+pub struct TransactionGateway {
+    client: Arc<EasyCashClient>,
+    limiter: Option<RateLimiter>,
+}
+
+impl TransactionGateway {
+    pub fn new(client: Arc<EasyCashClient>) -> Self {
+        Self { client, limiter: None }
+    }
+
+    /// Rejects requests over the limiter's rate with 429 before they reach the client
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Handles a request body and optional correlation ID header value
+    pub async fn execute(&self, correlation_id: Option<&str>, body: &[u8]) -> GatewayResponse {
+        let result = self.execute_inner(correlation_id, body).await;
+        match result {
+            Ok(resp) => {
+                let mut out = GatewayResponse::json(200, &resp);
+                out.headers
+                    .push((CORRELATION_ID_HEADER.to_string(), resp.correlation_id.clone()));
+                out
+            }
+            Err(e) => GatewayResponse::from_error(&e),
+        }
+    }
+
+    async fn execute_inner(
+        &self,
+        correlation_id: Option<&str>,
+        body: &[u8],
+    ) -> crate::errors::Result<crate::types::TransactionResponse> {
+        if let Some(ref limiter) = self.limiter {
+            limiter.check().await.map_err(|e| {
+                let err = SdkError::new(ErrorCode::RateLimited, e);
+                match correlation_id {
+                    Some(id) => err.with_correlation_id(id),
+                    None => err,
+                }
+            })?;
+        }
+        let req = parse_transaction_request(body, correlation_id)?;
+        self.client.execute_transaction(&req).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limiter::RateLimiterConfig;
+    use std::time::Duration;
+
+    const BODY: &[u8] =
+        br#"{"reference_id":"gw_1","type":"transfer","amount":"10.00","asset":"USDC","source_chain":"base","is_shielded":false}"#;
+
+    fn header<'a>(resp: &'a GatewayResponse, name: &str) -> Option<&'a str> {
+        resp.headers.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    #[tokio::test]
+    async fn test_gateway_executes_and_echoes_correlation_id() {
+        let gateway = TransactionGateway::new(Arc::new(EasyCashClient::new(None).unwrap()));
+        let resp = gateway.execute(Some("corr-gw"), BODY).await;
+        assert_eq!(resp.status, 200);
+        assert_eq!(header(&resp, CORRELATION_ID_HEADER), Some("corr-gw"));
+        let body: serde_json::Value = serde_json::from_str(&resp.body).unwrap();
+        assert_eq!(body["status"], "confirmed");
+    }
+
+    #[tokio::test]
+    async fn test_gateway_maps_errors() {
+        let limiter = RateLimiter::new(RateLimiterConfig {
+            max_requests: 1,
+            window: Duration::from_secs(60),
+            enabled: true,
+        });
+        let gateway = TransactionGateway::new(Arc::new(EasyCashClient::new(None).unwrap())).with_rate_limiter(limiter);
+
+        let resp = gateway.execute(None, b"{not json").await;
+        assert_eq!(resp.status, 400);
+        let body: serde_json::Value = serde_json::from_str(&resp.body).unwrap();
+        assert_eq!(body["code"], "INVALID_REQUEST");
+
+        let resp = gateway.execute(Some("corr-limited"), BODY).await;
+        assert_eq!(resp.status, 429);
+        assert_eq!(header(&resp, CORRELATION_ID_HEADER), Some("corr-limited"));
+    }
+
+    #[test]
+    fn test_body_correlation_id_wins_over_header() {
+        let body = br#"{"reference_id":"gw_2","type":"transfer","amount":"1","asset":"USDC","source_chain":"base","is_shielded":false,"correlation_id":"from-body"}"#;
+        let req = parse_transaction_request(body, Some("from-header")).unwrap();
+        assert_eq!(req.correlation_id.as_deref(), Some("from-body"));
+        let req = parse_transaction_request(BODY, Some("")).unwrap();
+        assert!(req.correlation_id.is_none());
+    }
+}
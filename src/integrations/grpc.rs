@@ -0,0 +1,473 @@
+//! tonic server for the `ecash.v1.TransactionService` gRPC API defined in
+//! `proto/ecash/v1/transaction.proto`.
+//!
+//! `TransactionService` implements the generated service on top of
+//! `EasyCashClient`; errors become statuses whose message is the JSON
+//! `SdkErrorResponse`. A sidecar serving it is:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use ecash_sdk_core::integrations::grpc::TransactionService;
+//! use ecash_sdk_core::EasyCashClient;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let service = TransactionService::new(Arc::new(EasyCashClient::new(None)?));
+//! tonic::transport::Server::builder()
+//!     .add_service(service.into_server())
+//!     .serve("0.0.0.0:50051".parse()?)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use serde::de::DeserializeOwned;
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tonic::{Code, Request, Response, Status};
+
+use crate::agent::RouteQuote;
+use crate::chain::TxReceipt;
+use crate::client::EasyCashClient;
+use crate::errors::{ErrorCode, SdkError, SdkErrorResponse};
+use crate::events::SdkEvent;
+use crate::types::{Disbursement, FeeSplit, TransactionRequest, TransactionResponse};
+
+/// Messages and service traits generated from the proto
+#[allow(clippy::all)]
+pub mod pb {
+    tonic::include_proto!("ecash.v1");
+}
+
+use pb::transaction_service_server::TransactionServiceServer;
+
+/// Events buffered per `StreamEvents` call before the forwarder waits
+const EVENT_BUFFER: usize = 64;
+
+/// gRPC status code for an SDK error code
+pub fn grpc_code(code: ErrorCode) -> Code {
+    match code {
+        ErrorCode::Cancelled => Code::Cancelled,
+        ErrorCode::InvalidRequest | ErrorCode::UnsupportedChain | ErrorCode::UnsupportedAsset => {
+            Code::InvalidArgument
+        }
+        ErrorCode::Timeout => Code::DeadlineExceeded,
+        ErrorCode::DuplicateReference => Code::AlreadyExists,
+        ErrorCode::ComplianceRejected | ErrorCode::PolicyViolation => Code::PermissionDenied,
+        ErrorCode::RateLimited | ErrorCode::BudgetExceeded => Code::ResourceExhausted,
+        ErrorCode::InsufficientFunds
+        | ErrorCode::FeeTooHigh
+        | ErrorCode::Expired
+        | ErrorCode::UnsupportedProtocol
+        | ErrorCode::ExecutionReverted => Code::FailedPrecondition,
+        ErrorCode::ProofGeneration => Code::Internal,
+        ErrorCode::NetworkFailure | ErrorCode::AgentUnavailable | ErrorCode::SignerUnavailable => {
+            Code::Unavailable
+        }
+    }
+}
+
+/// Status for an SDK error; the message is the JSON `SdkErrorResponse`
+impl From<&SdkError> for Status {
+    fn from(err: &SdkError) -> Self {
+        let message = serde_json::to_string(&SdkErrorResponse::from(err)).unwrap_or_else(|_| err.to_string());
+        Status::new(grpc_code(err.code), message)
+    }
+}
+
+impl From<SdkError> for Status {
+    fn from(err: SdkError) -> Self {
+        Status::from(&err)
+    }
+}
+
+fn invalid_request(message: String) -> Status {
+    Status::from(SdkError::new(ErrorCode::InvalidRequest, message))
+}
+
+/// Parses a field the same way as its JSON form (so `set_strict_enums` applies)
+fn parse_field<T: DeserializeOwned>(name: &str, value: serde_json::Value) -> Result<T, Status> {
+    serde_json::from_value(value).map_err(|e| invalid_request(format!("invalid {}: {}", name, e)))
+}
+
+fn parse_json_field<T: DeserializeOwned>(name: &str, value: Option<String>) -> Result<Option<T>, Status> {
+    value
+        .map(|json| serde_json::from_str(&json).map_err(|e| invalid_request(format!("invalid {}: {}", name, e))))
+        .transpose()
+}
+
+impl TryFrom<pb::TransactionRequest> for TransactionRequest {
+    type Error = Status;
+
+    fn try_from(msg: pb::TransactionRequest) -> Result<Self, Status> {
+        let mut req = TransactionRequest::new(
+            msg.reference_id,
+            parse_field("type", msg.r#type.into())?,
+            msg.amount,
+            msg.asset,
+            parse_field("source_chain", msg.source_chain.into())?,
+        )
+        .with_shielded(msg.is_shielded);
+        req.recipient = msg.recipient;
+        req.target_chain = msg.target_chain.map(|c| parse_field("target_chain", c.into())).transpose()?;
+        req.travel_rule = parse_json_field("travel_rule_json", msg.travel_rule_json)?;
+        req.correlation_id = msg.correlation_id;
+        req.account_id = msg.account_id;
+        req.metadata = msg.metadata;
+        req.disbursements = msg
+            .disbursements
+            .into_iter()
+            .map(|d| Disbursement {
+                recipient: d.recipient,
+                amount: d.amount,
+            })
+            .collect();
+        req.fee_splits = msg
+            .fee_splits
+            .into_iter()
+            .map(|s| FeeSplit {
+                recipient: s.recipient,
+                bps: s.bps,
+            })
+            .collect();
+        req.escrow = parse_json_field("escrow_json", msg.escrow_json)?;
+        req.fee_payer = parse_json_field("fee_payer_json", msg.fee_payer_json)?;
+        Ok(req)
+    }
+}
+
+impl From<TransactionResponse> for pb::TransactionResponse {
+    fn from(resp: TransactionResponse) -> Self {
+        Self {
+            tx_hash: resp.tx_hash,
+            status: resp.status,
+            block_height: resp.block_height,
+            fee_used: resp.fee_used,
+            correlation_id: resp.correlation_id,
+            disbursements: resp
+                .disbursements
+                .into_iter()
+                .map(|d| pb::DisbursementResult {
+                    recipient: d.recipient,
+                    amount: d.amount,
+                    status: d.status,
+                    error: d.error,
+                })
+                .collect(),
+            fee_splits: resp
+                .fee_splits
+                .into_iter()
+                .map(|s| pb::FeeSplitAmount {
+                    recipient: s.recipient,
+                    bps: s.bps,
+                    amount: s.amount,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<RouteQuote> for pb::RouteQuote {
+    fn from(quote: RouteQuote) -> Self {
+        Self {
+            agent_id: quote.agent_id,
+            estimated_fee: quote.estimated_fee,
+            estimated_time_ms: quote.estimated_time.as_millis() as u64,
+            route: quote.route,
+            security_score: quote.security_score,
+            estimated_fee_usd: quote.estimated_fee_usd,
+        }
+    }
+}
+
+impl From<TxReceipt> for pb::TxReceipt {
+    fn from(receipt: TxReceipt) -> Self {
+        Self {
+            tx_hash: receipt.tx_hash,
+            block_height: receipt.block_height,
+            success: receipt.success,
+            fee_paid: receipt.fee_paid,
+        }
+    }
+}
+
+impl From<&SdkEvent> for pb::Event {
+    fn from(event: &SdkEvent) -> Self {
+        let attributes: Vec<(&str, String)> = match event {
+            SdkEvent::ValidationFailed { reason, .. } => vec![("reason", reason.clone())],
+            SdkEvent::ProofGenerated { proof, .. } => vec![("proof", proof.clone())],
+            SdkEvent::QuoteReceived {
+                agent_id,
+                estimated_fee,
+                ..
+            } => {
+                vec![
+                    ("agent_id", agent_id.clone()),
+                    ("estimated_fee", estimated_fee.clone()),
+                ]
+            }
+            SdkEvent::RouteSelected {
+                agent_id,
+                estimated_fee,
+                security_score,
+                ..
+            } => vec![
+                ("agent_id", agent_id.clone()),
+                ("estimated_fee", estimated_fee.clone()),
+                ("security_score", security_score.to_string()),
+            ],
+            SdkEvent::ExecutionStarted { agent_id, .. } => vec![("agent_id", agent_id.clone())],
+            SdkEvent::QuoteViolated {
+                agent_id,
+                quote_hash,
+                quoted_fee,
+                fee_used,
+                ..
+            } => vec![
+                ("agent_id", agent_id.clone()),
+                ("quote_hash", quote_hash.clone()),
+                ("quoted_fee", quoted_fee.clone()),
+                ("fee_used", fee_used.clone()),
+            ],
+            SdkEvent::Confirmed {
+                tx_hash, fee_used, ..
+            } => {
+                vec![("tx_hash", tx_hash.clone()), ("fee_used", fee_used.clone())]
+            }
+            SdkEvent::Failed { code, message, .. } => {
+                vec![("code", code.to_string()), ("message", message.clone())]
+            }
+        };
+        let mut attributes: std::collections::HashMap<String, String> =
+            attributes.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        if let SdkEvent::Confirmed { account_id, metadata, .. } | SdkEvent::Failed { account_id, metadata, .. } = event {
+            if let Some(id) = account_id {
+                attributes.insert("account_id".to_string(), id.clone());
+            }
+            attributes.extend(metadata.iter().map(|(k, v)| (format!("metadata.{}", k), v.clone())));
+        }
+        Self {
+            name: event.name().to_string(),
+            reference_id: event.reference_id().to_string(),
+            correlation_id: event.correlation_id().to_string(),
+            attributes,
+        }
+    }
+}
+
+/// True if `event` passes a `StreamEvents` filter; unset fields match every event
+fn matches(filter: &pb::StreamEventsRequest, event: &SdkEvent) -> bool {
+    filter
+        .reference_id
+        .as_deref()
+        .is_none_or(|id| id == event.reference_id())
+        && filter
+            .correlation_id
+            .as_deref()
+            .is_none_or(|id| id == event.correlation_id())
+}
+
+/// Server side of a `StreamEvents` call; ends when the client is dropped.
+/// Events missed by a slow consumer are skipped.
+pub struct EventStream {
+    rx: mpsc::Receiver<pb::Event>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl futures_core::Stream for EventStream {
+    type Item = Result<pb::Event, Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx).map(|event| event.map(Ok))
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Implements the `TransactionService` RPCs on top of `EasyCashClient`
+#[derive(Clone)]
+pub struct TransactionService {
+    client: Arc<EasyCashClient>,
+}
+
+impl TransactionService {
+    pub fn new(client: Arc<EasyCashClient>) -> Self {
+        Self { client }
+    }
+
+    /// tonic service to add to a `tonic::transport::Server`
+    pub fn into_server(self) -> TransactionServiceServer<Self> {
+        TransactionServiceServer::new(self)
+    }
+}
+
+#[tonic::async_trait]
+impl pb::transaction_service_server::TransactionService for TransactionService {
+    async fn execute(
+        &self,
+        request: Request<pb::TransactionRequest>,
+    ) -> Result<Response<pb::TransactionResponse>, Status> {
+        let req = TransactionRequest::try_from(request.into_inner())?;
+        let resp = self.client.execute_transaction(&req).await?;
+        Ok(Response::new(resp.into()))
+    }
+
+    async fn get_quote(&self, request: Request<pb::TransactionRequest>) -> Result<Response<pb::RouteQuote>, Status> {
+        let req = TransactionRequest::try_from(request.into_inner())?;
+        let quote = self.client.get_quote(&req).await?;
+        Ok(Response::new(quote.into()))
+    }
+
+    async fn get_status(
+        &self,
+        request: Request<pb::GetStatusRequest>,
+    ) -> Result<Response<pb::GetStatusResponse>, Status> {
+        let req = request.into_inner();
+        let chain = req
+            .chain
+            .parse()
+            .map_err(|e: String| SdkError::new(ErrorCode::UnsupportedChain, e))?;
+        let receipt = self.client.get_transaction_status(chain, &req.tx_hash).await?;
+        Ok(Response::new(pb::GetStatusResponse {
+            found: receipt.is_some(),
+            receipt: receipt.map(Into::into),
+        }))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    /// Only events published after the call are streamed
+    async fn stream_events(
+        &self,
+        request: Request<pb::StreamEventsRequest>,
+    ) -> Result<Response<EventStream>, Status> {
+        let filter = request.into_inner();
+        let mut events = self.client.subscribe_events();
+        let (tx, rx) = mpsc::channel(EVENT_BUFFER);
+        let task = tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) if matches(&filter, &event) => {
+                        if tx.send(pb::Event::from(&event)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("[SDK] Event stream lagged, skipped {} events", skipped);
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+        Ok(Response::new(EventStream { rx, task }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pb::transaction_service_client::TransactionServiceClient;
+    use tonic::codegen::tokio_stream::wrappers::TcpListenerStream;
+    use tonic::transport::Channel;
+
+    /// Serves a fresh client's service on a local port
+    async fn connect() -> TransactionServiceClient<Channel> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = TransactionService::new(Arc::new(EasyCashClient::new(None).unwrap()));
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        TransactionServiceClient::connect(format!("http://{}", addr)).await.unwrap()
+    }
+
+    fn request(reference_id: &str) -> pb::TransactionRequest {
+        pb::TransactionRequest {
+            reference_id: reference_id.to_string(),
+            r#type: "transfer".to_string(),
+            amount: "10.00".to_string(),
+            asset: "USDC".to_string(),
+            source_chain: "base".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_streams_filtered_events() {
+        let mut client = connect().await;
+        let mut events = client
+            .stream_events(pb::StreamEventsRequest {
+                reference_id: Some("grpc_2".to_string()),
+                correlation_id: None,
+            })
+            .await
+            .unwrap()
+            .into_inner();
+
+        client.execute(request("grpc_1")).await.unwrap();
+        let resp = client.execute(request("grpc_2")).await.unwrap().into_inner();
+        assert_eq!(resp.status, "confirmed");
+
+        let first = events.message().await.unwrap().unwrap();
+        assert_eq!(first.reference_id, "grpc_2");
+        assert_eq!(first.correlation_id, resp.correlation_id);
+        let mut last = first;
+        while last.name != "confirmed" {
+            last = events.message().await.unwrap().unwrap();
+        }
+        assert_eq!(last.attributes["tx_hash"], resp.tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_errors_map_to_grpc_status() {
+        let mut client = connect().await;
+
+        let mut bad = request("grpc_3");
+        bad.amount = "-1".to_string();
+        let status = client.get_quote(bad).await.unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        let body: SdkErrorResponse = serde_json::from_str(status.message()).unwrap();
+        assert_eq!(body.code, ErrorCode::InvalidRequest);
+
+        let mut bad = request("grpc_4");
+        bad.escrow_json = Some("{".to_string());
+        let status = client.execute(bad).await.unwrap_err();
+        assert!(status.message().contains("invalid escrow_json"));
+
+        let status = client
+            .get_status(pb::GetStatusRequest {
+                chain: "dogecoin".to_string(),
+                tx_hash: "0xabc".to_string(),
+            })
+            .await
+            .unwrap_err();
+        let body: SdkErrorResponse = serde_json::from_str(status.message()).unwrap();
+        assert_eq!(body.code, ErrorCode::UnsupportedChain);
+    }
+
+    #[test]
+    fn test_request_conversion() {
+        let mut msg = request("grpc_5");
+        msg.r#type = "bridge".to_string();
+        msg.target_chain = Some("ethereum".to_string());
+        msg.fee_payer_json = Some(r#"{"type":"recipient"}"#.to_string());
+        msg.fee_splits.push(pb::FeeSplit {
+            recipient: "0xplatform".to_string(),
+            bps: 50,
+        });
+        let req = TransactionRequest::try_from(msg).unwrap();
+        assert_eq!(req.intent_type, crate::types::IntentType::Unknown);
+        assert_eq!(req.target_chain, Some(crate::types::ChainId::Ethereum));
+        assert_eq!(req.fee_payer, Some(crate::types::FeePayer::Recipient));
+        assert_eq!(req.fee_splits[0].bps, 50);
+    }
+}
@@ -0,0 +1,14 @@
+//! Building blocks for exposing the SDK through web frameworks.
+//!
+//! `axum` (feature `axum`) provides a `TransactionRequest` extractor, a tower
+//! layer applying correlation IDs and a rate limiter, and `SdkError`
+//! responses. `gateway` is the framework-neutral core it builds on: it works
+//! on header values and raw bodies and returns a status, headers and a JSON
+//! body, for frameworks without a dedicated module. `grpc` (feature `grpc`)
+//! is a tonic server for the gRPC API in `proto/ecash/v1`.
+
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod gateway;
+#[cfg(feature = "grpc")]
+pub mod grpc;
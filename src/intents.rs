@@ -0,0 +1,631 @@
+//! Per-intent execution handling.
+//!
+//! Every request runs the same common pipeline (validation, screening,
+//! spending policies, quoting, routing). The `IntentHandler` registered for
+//! its `IntentType` then adds what is specific to that intent:
+//!
+//! - `TransferHandler` checks the recipient against the address book.
+//! - `SwapHandler` also checks the recipient and caps the caller's slippage
+//!   tolerance.
+//! - `ShieldHandler` also checks the recipient and creates the shielded note
+//!   the deposit mints.
+//! - `DisburseHandler` checks every recipient of a split transfer and that
+//!   their amounts add up to the debit.
+//! - `EscrowHandler` checks the recipient and the escrow's terms.
+//!
+//! Transfers may carry fee splits (see `fee_splits`): shares of the amount
+//! paid to platforms rather than the recipient, for marketplaces taking a cut.
+//!
+//! Register a handler with `EasyCashClient::with_intent_handler` to replace
+//! the built-in handling of an intent.
+
+use async_trait::async_trait;
+
+use crate::address_book::{AddressBook, AddressRejection};
+use crate::agent::RouteQuote;
+use crate::amount::Amount;
+use crate::crypto::hash::HashFunction;
+use crate::errors::{ErrorCode, Result, SdkError};
+use crate::escrow::EscrowTerms;
+use crate::types::{Disbursement, FeeSplit, FeeSplitAmount, IntentType, TransactionRequest};
+use crate::validator;
+use crate::zk::notes;
+
+/// Client state available to intent handlers
+pub struct IntentContext<'a> {
+    pub correlation_id: &'a str,
+    pub address_book: Option<&'a AddressBook>,
+    /// See `SdkConfig::require_allowlisted_recipients`
+    pub require_allowlisted_recipients: bool,
+    /// Hash function of note commitments (`SdkConfig::hashes`)
+    pub commitment_hash: HashFunction,
+}
+
+/// Handling specific to one intent type
+#[async_trait]
+pub trait IntentHandler: Send + Sync {
+    /// Intent type the handler is registered for
+    fn intent(&self) -> IntentType;
+
+    /// Runs after the common checks (validation, screening, policies) and
+    /// before quoting
+    async fn check(&self, _ctx: &IntentContext<'_>, _req: &TransactionRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs once a route is selected, before the hand-off to its agent.
+    /// Returned details are recorded in the audit trail (`Null` for none).
+    async fn prepare(
+        &self,
+        _ctx: &IntentContext<'_>,
+        _req: &TransactionRequest,
+        _route: &RouteQuote,
+    ) -> Result<serde_json::Value> {
+        Ok(serde_json::Value::Null)
+    }
+}
+
+/// Holds the recipient to its address book entry; with
+/// `require_allowlisted_recipients`, refuses unregistered recipients
+pub fn check_recipient(ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+    match ctx.address_book {
+        Some(book) => {
+            if let Err(rejection) = book.check(req, ctx.require_allowlisted_recipients) {
+                let code = match rejection {
+                    AddressRejection::Unavailable(_) => ErrorCode::NetworkFailure,
+                    _ => ErrorCode::PolicyViolation,
+                };
+                return Err(SdkError::new(code, rejection.to_string())
+                    .with_details(serde_json::json!({ "reason": rejection.reason() })));
+            }
+            Ok(())
+        }
+        None if ctx.require_allowlisted_recipients && req.recipient.is_some() => Err(SdkError::new(
+            ErrorCode::PolicyViolation,
+            "require_allowlisted_recipients is set but no address book is configured",
+        )
+        .with_details(serde_json::json!({ "reason": "recipient_not_allowlisted" }))),
+        None => Ok(()),
+    }
+}
+
+/// Plain transfers
+pub struct TransferHandler;
+
+#[async_trait]
+impl IntentHandler for TransferHandler {
+    fn intent(&self) -> IntentType {
+        IntentType::Transfer
+    }
+
+    async fn check(&self, ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+        check_recipient(ctx, req)
+    }
+}
+
+/// Swaps. Callers state their slippage tolerance in basis points in the
+/// request's `max_slippage_bps` metadata; the handler caps it at the
+/// operator's limit and records the tolerance the route was taken with.
+pub struct SwapHandler {
+    default_slippage_bps: u32,
+    max_slippage_bps: u32,
+}
+
+/// Metadata key of a swap's slippage tolerance, in basis points
+pub const MAX_SLIPPAGE_METADATA: &str = "max_slippage_bps";
+
+impl Default for SwapHandler {
+    fn default() -> Self {
+        Self {
+            default_slippage_bps: 50,
+            max_slippage_bps: 300,
+        }
+    }
+}
+
+impl SwapHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tolerance of swaps that don't state one (default 50, i.e. 0.5%)
+    pub fn with_default_slippage_bps(mut self, bps: u32) -> Self {
+        self.default_slippage_bps = bps;
+        self
+    }
+
+    /// Largest tolerance a swap may state (default 300, i.e. 3%)
+    pub fn with_max_slippage_bps(mut self, bps: u32) -> Self {
+        self.max_slippage_bps = bps;
+        self
+    }
+
+    fn slippage_bps(&self, req: &TransactionRequest) -> Result<u32> {
+        let Some(value) = req.metadata.get(MAX_SLIPPAGE_METADATA) else {
+            return Ok(self.default_slippage_bps.min(self.max_slippage_bps));
+        };
+        let bps: u32 = value.trim().parse().map_err(|_| {
+            SdkError::new(
+                ErrorCode::InvalidRequest,
+                format!("{} must be a whole number of basis points, got {:?}", MAX_SLIPPAGE_METADATA, value),
+            )
+        })?;
+        if bps > self.max_slippage_bps {
+            return Err(SdkError::new(
+                ErrorCode::PolicyViolation,
+                format!("slippage tolerance of {} bps exceeds the maximum of {} bps", bps, self.max_slippage_bps),
+            )
+            .with_details(serde_json::json!({
+                "reason": "slippage_exceeded",
+                "max_slippage_bps": self.max_slippage_bps,
+            })));
+        }
+        Ok(bps)
+    }
+}
+
+#[async_trait]
+impl IntentHandler for SwapHandler {
+    fn intent(&self) -> IntentType {
+        IntentType::Swap
+    }
+
+    async fn check(&self, ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+        self.slippage_bps(req)?;
+        check_recipient(ctx, req)
+    }
+
+    async fn prepare(
+        &self,
+        _ctx: &IntentContext<'_>,
+        req: &TransactionRequest,
+        route: &RouteQuote,
+    ) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({
+            "max_slippage_bps": self.slippage_bps(req)?,
+            "agent_id": route.agent_id,
+        }))
+    }
+}
+
+/// Deposits into the shielded pool
+pub struct ShieldHandler;
+
+#[async_trait]
+impl IntentHandler for ShieldHandler {
+    fn intent(&self) -> IntentType {
+        IntentType::Shield
+    }
+
+    async fn check(&self, ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+        check_recipient(ctx, req)
+    }
+
+    /// Creates the note minted for the recipient (the payer when unset)
+    async fn prepare(
+        &self,
+        ctx: &IntentContext<'_>,
+        req: &TransactionRequest,
+        _route: &RouteQuote,
+    ) -> Result<serde_json::Value> {
+        let amount = req
+            .amount_base_units
+            .as_deref()
+            .ok_or_else(|| {
+                SdkError::new(
+                    ErrorCode::InvalidRequest,
+                    format!("shielding needs the decimals of {}; register it in the asset registry", req.asset),
+                )
+            })?
+            .parse::<u128>()
+            .map_err(|e| SdkError::new(ErrorCode::InvalidRequest, format!("invalid base-unit amount: {}", e)))?;
+        let note = notes::create_note_with(
+            ctx.commitment_hash,
+            &req.asset,
+            amount,
+            req.recipient.as_deref().unwrap_or_default(),
+        );
+        Ok(serde_json::json!({ "note_commitment": note.commitment, "note_amount": note.amount.to_string() }))
+    }
+}
+
+/// Recipients of `req`, which only `Disburse` requests may list
+pub fn disbursements(req: &TransactionRequest) -> Result<&[Disbursement]> {
+    if req.intent_type != IntentType::Disburse && !req.disbursements.is_empty() {
+        return Err(SdkError::new(
+            ErrorCode::InvalidRequest,
+            format!("only disburse intents list disbursements, not {} intents", req.intent_type),
+        ));
+    }
+    Ok(&req.disbursements)
+}
+
+/// Sets the recipients of a `Disburse` request and its amount to their sum
+pub fn set_disbursements(req: &mut TransactionRequest, disbursements: &[Disbursement]) -> Result<()> {
+    req.amount = sum(disbursements)?.to_string();
+    req.disbursements = disbursements.to_vec();
+    Ok(())
+}
+
+fn sum(disbursements: &[Disbursement]) -> Result<Amount> {
+    disbursements.iter().try_fold(Amount::from_base_units(0, 0), |total, d| {
+        let amount: Amount = d.amount.parse().map_err(|e| {
+            SdkError::new(ErrorCode::InvalidRequest, format!("invalid amount for {}: {}", d.recipient, e))
+        })?;
+        total
+            .checked_add(&amount)
+            .ok_or_else(|| SdkError::new(ErrorCode::InvalidRequest, "disbursement total overflows"))
+    })
+}
+
+/// Basis points in a whole transfer
+const TOTAL_BPS: u32 = 10_000;
+
+/// Fee splits of `req`, validated; empty when it has none.
+///
+/// Only transparent transfers to a recipient may carry fee splits, and they
+/// may take at most the whole amount (10000 bps in total).
+pub fn fee_splits(req: &TransactionRequest) -> Result<&[FeeSplit]> {
+    let splits = &req.fee_splits;
+    if splits.is_empty() {
+        return Ok(splits);
+    }
+    let invalid = |msg: String| SdkError::new(ErrorCode::InvalidRequest, msg);
+    if req.intent_type != IntentType::Transfer || req.is_shielded || req.recipient.is_none() {
+        return Err(invalid("fee splits are only supported on transparent transfers to a recipient".to_string()));
+    }
+    let chain = req.target_chain.unwrap_or(req.source_chain);
+    let mut total: u32 = 0;
+    for split in splits {
+        validator::validate_address_for_chain(&split.recipient, chain)
+            .map_err(|e| invalid(format!("invalid fee split recipient {}: {}", split.recipient, e)))?;
+        if split.bps == 0 {
+            return Err(invalid(format!("fee split to {} must be greater than 0 bps", split.recipient)));
+        }
+        total = total.saturating_add(split.bps);
+    }
+    if total > TOTAL_BPS {
+        return Err(invalid(format!("fee splits total {} bps, more than {}", total, TOTAL_BPS))
+            .with_details(serde_json::json!({ "reason": "fee_splits_exceed_amount" })));
+    }
+    Ok(splits)
+}
+
+/// Amount of each split of `req`'s amount, exact to the basis point, and
+/// what remains for the recipient
+pub fn apply_fee_splits(req: &TransactionRequest, splits: &[FeeSplit]) -> Result<(Amount, Vec<FeeSplitAmount>)> {
+    let invalid = |msg: String| SdkError::new(ErrorCode::InvalidRequest, msg);
+    let amount: Amount = req.amount.parse().map_err(|e| invalid(format!("invalid amount: {}", e)))?;
+    // Four more decimals make every basis point of the amount a whole unit
+    let scale = amount.scale() + 4;
+    let units = amount
+        .to_base_units_exact(scale)
+        .map_err(|e| invalid(format!("cannot split {}: {}", amount, e)))?;
+    let mut remaining = amount;
+    let mut applied = Vec::with_capacity(splits.len());
+    for split in splits {
+        let cut = units
+            .checked_mul(u128::from(split.bps))
+            .map(|cut| Amount::from_base_units(cut / u128::from(TOTAL_BPS), scale))
+            .ok_or_else(|| invalid(format!("cannot split {}: amount too large", amount)))?;
+        remaining = remaining
+            .checked_sub(&cut)
+            .ok_or_else(|| invalid("fee splits exceed the amount".to_string()))?;
+        applied.push(FeeSplitAmount {
+            recipient: split.recipient.clone(),
+            bps: split.bps,
+            amount: cut.to_string(),
+        });
+    }
+    Ok((remaining, applied))
+}
+
+/// Split transfers: one debit funding several recipients, listed in the
+/// request's `disbursements`. The request's `amount` must equal the
+/// sum of the recipients' amounts.
+pub struct DisburseHandler {
+    max_recipients: usize,
+    max_per_recipient: Option<Amount>,
+}
+
+impl Default for DisburseHandler {
+    fn default() -> Self {
+        Self {
+            max_recipients: 100,
+            max_per_recipient: None,
+        }
+    }
+}
+
+impl DisburseHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Most recipients of one disbursement (default 100)
+    pub fn with_max_recipients(mut self, max: usize) -> Self {
+        self.max_recipients = max;
+        self
+    }
+
+    /// Largest amount a single recipient may receive (default unlimited)
+    pub fn with_max_per_recipient(mut self, max: Amount) -> Self {
+        self.max_per_recipient = Some(max);
+        self
+    }
+
+    fn check_limits(&self, req: &TransactionRequest, disbursements: &[Disbursement]) -> Result<()> {
+        let invalid = |msg: String| SdkError::new(ErrorCode::InvalidRequest, msg);
+        if req.recipient.is_some() {
+            return Err(invalid("a disbursement's recipients are listed in disbursements, not in recipient".to_string()));
+        }
+        if req.is_shielded {
+            return Err(invalid("disbursements cannot be shielded".to_string()));
+        }
+        if disbursements.is_empty() {
+            return Err(invalid("a disbursement needs at least one recipient".to_string()));
+        }
+        if disbursements.len() > self.max_recipients {
+            return Err(SdkError::new(
+                ErrorCode::PolicyViolation,
+                format!("{} recipients exceed the maximum of {}", disbursements.len(), self.max_recipients),
+            )
+            .with_details(serde_json::json!({
+                "reason": "too_many_recipients",
+                "max_recipients": self.max_recipients,
+            })));
+        }
+        let chain = req.target_chain.unwrap_or(req.source_chain);
+        for d in disbursements {
+            validator::validate_address_for_chain(&d.recipient, chain)
+                .map_err(|e| invalid(format!("invalid recipient {}: {}", d.recipient, e)))?;
+            let amount: Amount = d
+                .amount
+                .parse()
+                .map_err(|e| invalid(format!("invalid amount for {}: {}", d.recipient, e)))?;
+            if amount.is_zero() {
+                return Err(invalid(format!("amount for {} must be greater than 0", d.recipient)));
+            }
+            if let Some(max) = self.max_per_recipient.filter(|max| amount > *max) {
+                return Err(SdkError::new(
+                    ErrorCode::PolicyViolation,
+                    format!("{} {} to {} exceeds the per-recipient maximum of {}", amount, req.asset, d.recipient, max),
+                )
+                .with_details(serde_json::json!({
+                    "reason": "recipient_limit_exceeded",
+                    "recipient": d.recipient,
+                    "max_per_recipient": max.to_string(),
+                })));
+            }
+        }
+        let total = sum(disbursements)?;
+        let requested: Amount = req.amount.parse().map_err(|e| invalid(format!("invalid amount: {}", e)))?;
+        if total != requested {
+            return Err(invalid(format!(
+                "disbursements add up to {} but the request is for {}",
+                total, requested
+            ))
+            .with_details(serde_json::json!({ "reason": "disbursement_sum_mismatch" })));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IntentHandler for DisburseHandler {
+    fn intent(&self) -> IntentType {
+        IntentType::Disburse
+    }
+
+    async fn check(&self, ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+        let disbursements = disbursements(req)?;
+        self.check_limits(req, disbursements)?;
+        for d in disbursements {
+            let leg = TransactionRequest {
+                recipient: Some(d.recipient.clone()),
+                amount: d.amount.clone(),
+                ..req.clone()
+            };
+            check_recipient(ctx, &leg)?;
+        }
+        Ok(())
+    }
+
+    async fn prepare(
+        &self,
+        _ctx: &IntentContext<'_>,
+        req: &TransactionRequest,
+        route: &RouteQuote,
+    ) -> Result<serde_json::Value> {
+        Ok(serde_json::json!({
+            "recipients": disbursements(req)?.len(),
+            "agent_id": route.agent_id,
+        }))
+    }
+}
+
+/// Escrowed payments (see `escrow`): the recipient is checked now, but only
+/// paid once the escrow is released
+pub struct EscrowHandler;
+
+#[async_trait]
+impl IntentHandler for EscrowHandler {
+    fn intent(&self) -> IntentType {
+        IntentType::Escrow
+    }
+
+    async fn check(&self, ctx: &IntentContext<'_>, req: &TransactionRequest) -> Result<()> {
+        let invalid = |msg: String| SdkError::new(ErrorCode::InvalidRequest, msg);
+        if req.recipient.is_none() {
+            return Err(invalid("an escrow needs a recipient".to_string()));
+        }
+        if req.is_shielded {
+            return Err(invalid("escrows cannot be shielded".to_string()));
+        }
+        EscrowTerms::of(req)
+            .and_then(|terms| terms.validate())
+            .map_err(|e| invalid(e).with_details(serde_json::json!({ "reason": "invalid_escrow_terms" })))?;
+        check_recipient(ctx, req)
+    }
+
+    async fn prepare(
+        &self,
+        _ctx: &IntentContext<'_>,
+        req: &TransactionRequest,
+        route: &RouteQuote,
+    ) -> Result<serde_json::Value> {
+        let terms = EscrowTerms::of(req).map_err(|e| SdkError::new(ErrorCode::InvalidRequest, e))?;
+        Ok(serde_json::json!({ "terms": terms, "agent_id": route.agent_id }))
+    }
+}
+
+/// Built-in handlers for every known intent type
+pub fn default_handlers() -> Vec<std::sync::Arc<dyn IntentHandler>> {
+    vec![
+        std::sync::Arc::new(TransferHandler),
+        std::sync::Arc::new(SwapHandler::default()),
+        std::sync::Arc::new(ShieldHandler),
+        std::sync::Arc::new(DisburseHandler::default()),
+        std::sync::Arc::new(EscrowHandler),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ChainId;
+    use std::time::Duration;
+
+    fn request(intent_type: IntentType) -> TransactionRequest {
+        TransactionRequest::new("ref_intent", intent_type, "2.5", "USDC", ChainId::Base)
+            .with_amount_base_units("2500000")
+    }
+
+    fn route() -> RouteQuote {
+        RouteQuote {
+            agent_id: "agent-1".to_string(),
+            estimated_fee: "0.05 USDC".to_string(),
+            estimated_time: Duration::from_secs(30),
+            route: vec!["base".to_string()],
+            security_score: 0.9,
+            estimated_fee_usd: None,
+            signature: None,
+            agent_pubkey: None,
+            region: None,
+        }
+    }
+
+    fn ctx() -> IntentContext<'static> {
+        IntentContext {
+            correlation_id: "corr-1",
+            address_book: None,
+            require_allowlisted_recipients: false,
+            commitment_hash: HashFunction::Sha256,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_swap_caps_slippage_tolerance() {
+        let handler = SwapHandler::new().with_max_slippage_bps(100);
+        let mut req = request(IntentType::Swap);
+        assert_eq!(handler.prepare(&ctx(), &req, &route()).await.unwrap()["max_slippage_bps"], 50);
+
+        req.metadata.insert(MAX_SLIPPAGE_METADATA.to_string(), "150".to_string());
+        let err = handler.check(&ctx(), &req).await.unwrap_err();
+        assert_eq!((err.code, err.details["reason"].as_str()), (ErrorCode::PolicyViolation, Some("slippage_exceeded")));
+        req.metadata.insert(MAX_SLIPPAGE_METADATA.to_string(), "1%".to_string());
+        assert_eq!(handler.check(&ctx(), &req).await.unwrap_err().code, ErrorCode::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn test_shield_creates_note() {
+        let mut req = request(IntentType::Shield);
+        let prepared = ShieldHandler.prepare(&ctx(), &req, &route()).await.unwrap();
+        assert_eq!(prepared["note_amount"], "2500000");
+        assert!(prepared["note_commitment"].as_str().unwrap().starts_with("0x"));
+
+        req.amount_base_units = None;
+        assert!(ShieldHandler.prepare(&ctx(), &req, &route()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_disburse_checks_recipients_and_sum() {
+        let handler = DisburseHandler::new()
+            .with_max_recipients(2)
+            .with_max_per_recipient("100".parse().unwrap());
+        let split = |amounts: &[&str]| -> Vec<Disbursement> {
+            amounts
+                .iter()
+                .enumerate()
+                .map(|(i, amount)| Disbursement {
+                    recipient: format!("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb{}", i),
+                    amount: amount.to_string(),
+                })
+                .collect()
+        };
+        let mut req = request(IntentType::Disburse);
+        assert_eq!(handler.check(&ctx(), &req).await.unwrap_err().code, ErrorCode::InvalidRequest);
+
+        set_disbursements(&mut req, &split(&["1.5", "1.00"])).unwrap();
+        assert_eq!(req.amount, "2.5");
+        assert!(handler.check(&ctx(), &req).await.is_ok());
+        assert_eq!(disbursements(&req).unwrap().len(), 2);
+        assert_eq!(handler.prepare(&ctx(), &req, &route()).await.unwrap()["recipients"], 2);
+
+        req.amount = "3".to_string();
+        let err = handler.check(&ctx(), &req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "disbursement_sum_mismatch");
+
+        set_disbursements(&mut req, &split(&["1", "1", "1"])).unwrap();
+        let err = handler.check(&ctx(), &req).await.unwrap_err();
+        assert_eq!((err.code, err.details["reason"].as_str()), (ErrorCode::PolicyViolation, Some("too_many_recipients")));
+        set_disbursements(&mut req, &split(&["150"])).unwrap();
+        let err = handler.check(&ctx(), &req).await.unwrap_err();
+        assert_eq!(err.details["reason"], "recipient_limit_exceeded");
+        set_disbursements(&mut req, &split(&["0", "1"])).unwrap();
+        assert!(handler.check(&ctx(), &req).await.is_err());
+        req.recipient = Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string());
+        set_disbursements(&mut req, &split(&["1"])).unwrap();
+        assert!(handler.check(&ctx(), &req).await.is_err());
+
+        req.intent_type = IntentType::Transfer;
+        assert!(disbursements(&req).is_err());
+    }
+
+    #[test]
+    fn test_fee_splits() {
+        let split = |bps: u32| FeeSplit {
+            recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1".to_string(),
+            bps,
+        };
+        let mut req = request(IntentType::Transfer);
+        req.recipient = Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string());
+        assert!(fee_splits(&req).unwrap().is_empty());
+
+        req.fee_splits = vec![split(250), split(1)];
+        let (net, applied) = apply_fee_splits(&req, fee_splits(&req).unwrap()).unwrap();
+        assert_eq!(net.to_string(), "2.43725");
+        let amounts: Vec<&str> = applied.iter().map(|a| a.amount.as_str()).collect();
+        assert_eq!(amounts, ["0.0625", "0.00025"]);
+
+        req.fee_splits = vec![split(9_000), split(1_001)];
+        assert_eq!(fee_splits(&req).unwrap_err().details["reason"], "fee_splits_exceed_amount");
+        req.fee_splits = vec![split(0)];
+        assert!(fee_splits(&req).is_err());
+        req.fee_splits = vec![split(100)];
+        req.intent_type = IntentType::Swap;
+        assert!(fee_splits(&req).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recipient_required_without_book() {
+        let ctx = IntentContext {
+            require_allowlisted_recipients: true,
+            ..ctx()
+        };
+        let mut req = request(IntentType::Transfer);
+        assert!(TransferHandler.check(&ctx, &req).await.is_ok());
+        req.recipient = Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string());
+        assert_eq!(TransferHandler.check(&ctx, &req).await.unwrap_err().code, ErrorCode::PolicyViolation);
+    }
+}
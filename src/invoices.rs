@@ -0,0 +1,431 @@
+//! Payment requests (invoices).
+//!
+//! A merchant creates an `Invoice` for what it expects to be paid, signs it,
+//! and hands the payer the encoded invoice as a URL parameter or QR code.
+//! The payer verifies the signature against the merchant's public key before
+//! paying. When a payment arrives, `InvoiceBook::settle` matches it against
+//! the open invoice and marks the invoice paid.
+//!
+//! ```
+//! use k256::SecretKey;
+//! use ecash_sdk_core::crypto::TransactionSigner;
+//! use ecash_sdk_core::invoices::{Invoice, SignedInvoice};
+//! use ecash_sdk_core::ChainId;
+//! use std::time::Duration;
+//!
+//! let merchant = TransactionSigner::new(SecretKey::from_bytes(&[7u8; 32].into()).unwrap());
+//! let invoice = Invoice::new("25.00", "USDC", ChainId::Base, "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", Duration::from_secs(900))
+//!     .with_memo("Order #42");
+//! let uri = invoice.sign(&merchant).unwrap().to_uri().unwrap();
+//!
+//! let received = SignedInvoice::decode(&uri).unwrap();
+//! assert!(received.verify(&merchant.verifying_key()).unwrap());
+//! assert_eq!(received.invoice.memo.as_deref(), Some("Order #42"));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use k256::ecdsa::VerifyingKey;
+use k256::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::crypto::hash::HashFunction;
+use crate::crypto::{self, SigningDomain, TransactionSigner};
+use crate::types::ChainId;
+
+/// URI scheme of encoded invoices
+pub const URI_SCHEME: &str = "ecash:";
+
+/// Request to pay `amount` of `asset` to `recipient` on `chain`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invoice {
+    /// Unique ID; payers reference it so the payment can be matched
+    pub id: String,
+    pub amount: String,
+    pub asset: String,
+    pub chain: ChainId,
+    pub recipient: String,
+    /// Unix time (seconds) after which the invoice can no longer be paid
+    pub expiry: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+}
+
+impl Invoice {
+    /// Invoice with a fresh ID that expires `ttl` from now
+    pub fn new(
+        amount: impl Into<String>,
+        asset: impl Into<String>,
+        chain: ChainId,
+        recipient: impl Into<String>,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            id: format!("inv_{}", uuid::Uuid::new_v4().simple()),
+            amount: amount.into(),
+            asset: asset.into(),
+            chain,
+            recipient: recipient.into(),
+            expiry: now_secs().saturating_add(ttl.as_secs()),
+            memo: None,
+        }
+    }
+
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    pub fn is_expired(&self) -> bool {
+        now_secs() >= self.expiry
+    }
+
+    /// Checks the fields a payer relies on
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() {
+            return Err("invoice id is required".to_string());
+        }
+        let amount: Amount = self.amount.parse()?;
+        if amount.is_zero() {
+            return Err("invoice amount must be positive".to_string());
+        }
+        if self.asset.is_empty() || self.recipient.is_empty() {
+            return Err("invoice asset and recipient are required".to_string());
+        }
+        Ok(())
+    }
+
+    /// Signs the invoice as the merchant
+    pub fn sign(self, signer: &TransactionSigner) -> Result<SignedInvoice, String> {
+        self.validate()?;
+        let mut signed = SignedInvoice {
+            invoice: self,
+            signer: crypto::public_key_to_hex(&PublicKey::from(&signer.verifying_key())),
+            signature: String::new(),
+        };
+        signed.signature = signer.sign_in_domain(SigningDomain::Invoice, &signed.signing_payload()?)?;
+        Ok(signed)
+    }
+}
+
+/// Invoice signed by the merchant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedInvoice {
+    pub invoice: Invoice,
+    /// Compressed hex public key of the merchant
+    pub signer: String,
+    /// Hex-encoded ECDSA signature over the invoice and signer
+    pub signature: String,
+}
+
+impl SignedInvoice {
+    /// URL-safe base64 payload, usable as a query parameter as is
+    pub fn encode(&self) -> Result<String, String> {
+        let json = serde_json::to_vec(self).map_err(|e| format!("failed to encode invoice: {}", e))?;
+        Ok(crypto::base64url_encode(&json))
+    }
+
+    /// `ecash:` URI for QR codes and payment links
+    pub fn to_uri(&self) -> Result<String, String> {
+        Ok(format!("{}{}", URI_SCHEME, self.encode()?))
+    }
+
+    /// Parses a payload from `encode` or a URI from `to_uri`. Does not verify
+    /// the signature; call `verify` before paying.
+    pub fn decode(encoded: &str) -> Result<Self, String> {
+        let encoded = encoded.trim();
+        // QR scanners may upper-case the scheme
+        let payload = match encoded.get(..URI_SCHEME.len()) {
+            Some(scheme) if scheme.eq_ignore_ascii_case(URI_SCHEME) => &encoded[URI_SCHEME.len()..],
+            _ => encoded,
+        };
+        let json = crypto::base64url_decode(payload)?;
+        serde_json::from_slice(&json).map_err(|e| format!("invalid invoice: {}", e))
+    }
+
+    /// Verifies the invoice was signed by `merchant` and not modified.
+    ///
+    /// Returns `Ok(false)` for a different signer or a tampered invoice.
+    pub fn verify(&self, merchant: &VerifyingKey) -> Result<bool, String> {
+        let expected = crypto::public_key_to_hex(&PublicKey::from(merchant));
+        if !self.signer.eq_ignore_ascii_case(&expected) {
+            return Ok(false);
+        }
+        crypto::verify_signature_in_domain(
+            HashFunction::Sha256,
+            SigningDomain::Invoice,
+            merchant,
+            &self.signing_payload()?,
+            &self.signature,
+        )
+    }
+
+    fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let payload = serde_json::json!({
+            "invoice": self.invoice,
+            "signer": self.signer,
+        });
+        serde_json::to_vec(&payload).map_err(|e| format!("failed to encode invoice: {}", e))
+    }
+}
+
+/// Payment received by the merchant, as reported by a chain watcher
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InboundPayment {
+    /// Invoice ID the payer referenced
+    pub invoice_id: String,
+    pub recipient: String,
+    pub asset: String,
+    pub amount: String,
+    pub chain: ChainId,
+    pub tx_hash: String,
+}
+
+/// Whether an invoice has been paid
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum InvoiceState {
+    Open,
+    Paid { tx_hash: String },
+}
+
+/// Invoice and its state, as kept by an `InvoiceStore`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvoiceRecord {
+    pub invoice: Invoice,
+    #[serde(flatten)]
+    pub state: InvoiceState,
+}
+
+/// Persistent record of invoices, keyed by invoice ID
+pub trait InvoiceStore: Send + Sync {
+    fn get(&self, invoice_id: &str) -> Result<Option<InvoiceRecord>, String>;
+
+    /// Inserts or replaces an invoice record
+    fn put(&self, record: &InvoiceRecord) -> Result<(), String>;
+}
+
+/// In-memory invoice store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemoryInvoiceStore {
+    records: Mutex<HashMap<String, InvoiceRecord>>,
+}
+
+impl InMemoryInvoiceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, InvoiceRecord>>, String> {
+        self.records.lock().map_err(|_| "invoice store poisoned".to_string())
+    }
+}
+
+impl InvoiceStore for InMemoryInvoiceStore {
+    fn get(&self, invoice_id: &str) -> Result<Option<InvoiceRecord>, String> {
+        Ok(self.lock()?.get(invoice_id).cloned())
+    }
+
+    fn put(&self, record: &InvoiceRecord) -> Result<(), String> {
+        self.lock()?.insert(record.invoice.id.clone(), record.clone());
+        Ok(())
+    }
+}
+
+/// Why an inbound payment did not settle an invoice
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvoiceRejection {
+    UnknownInvoice { invoice_id: String },
+    AlreadyPaid { invoice_id: String, tx_hash: String },
+    Expired { invoice_id: String },
+    /// The payment's recipient, asset, chain or amount differs from the invoice
+    Mismatch { field: &'static str, expected: String, actual: String },
+    /// The store failed; the payment is left unmatched to be safe
+    Unavailable(String),
+}
+
+impl std::fmt::Display for InvoiceRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvoiceRejection::UnknownInvoice { invoice_id } => write!(f, "no open invoice {}", invoice_id),
+            InvoiceRejection::AlreadyPaid { invoice_id, tx_hash } => {
+                write!(f, "invoice {} was already paid in {}", invoice_id, tx_hash)
+            }
+            InvoiceRejection::Expired { invoice_id } => write!(f, "invoice {} has expired", invoice_id),
+            InvoiceRejection::Mismatch { field, expected, actual } => {
+                write!(f, "payment {} {} does not match invoice {}", field, actual, expected)
+            }
+            InvoiceRejection::Unavailable(e) => write!(f, "invoice store unavailable: {}", e),
+        }
+    }
+}
+
+/// Open invoices of a merchant, matched against inbound payments
+pub struct InvoiceBook {
+    store: std::sync::Arc<dyn InvoiceStore>,
+    lock: Mutex<()>,
+}
+
+impl InvoiceBook {
+    pub fn new(store: std::sync::Arc<dyn InvoiceStore>) -> Self {
+        Self { store, lock: Mutex::new(()) }
+    }
+
+    /// Records an issued invoice as open
+    pub fn open(&self, invoice: &Invoice) -> Result<(), String> {
+        invoice.validate()?;
+        self.store.put(&InvoiceRecord {
+            invoice: invoice.clone(),
+            state: InvoiceState::Open,
+        })
+    }
+
+    pub fn get(&self, invoice_id: &str) -> Result<Option<InvoiceRecord>, String> {
+        self.store.get(invoice_id)
+    }
+
+    /// Matches a payment against its open invoice and marks the invoice paid.
+    ///
+    /// The payment must reach the invoice's recipient in the invoiced asset,
+    /// chain and amount before the invoice expires. A payment that does not
+    /// match leaves the invoice open.
+    pub fn settle(&self, payment: &InboundPayment) -> Result<Invoice, InvoiceRejection> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut record = self
+            .store
+            .get(&payment.invoice_id)
+            .map_err(InvoiceRejection::Unavailable)?
+            .ok_or_else(|| InvoiceRejection::UnknownInvoice {
+                invoice_id: payment.invoice_id.clone(),
+            })?;
+        let invoice = &record.invoice;
+        if let InvoiceState::Paid { ref tx_hash } = record.state {
+            return Err(InvoiceRejection::AlreadyPaid {
+                invoice_id: invoice.id.clone(),
+                tx_hash: tx_hash.clone(),
+            });
+        }
+        if invoice.is_expired() {
+            return Err(InvoiceRejection::Expired {
+                invoice_id: invoice.id.clone(),
+            });
+        }
+        let mismatch = |field, expected: &str, actual: &str| InvoiceRejection::Mismatch {
+            field,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        };
+        if !payment.recipient.eq_ignore_ascii_case(&invoice.recipient) {
+            return Err(mismatch("recipient", &invoice.recipient, &payment.recipient));
+        }
+        if !payment.asset.eq_ignore_ascii_case(&invoice.asset) {
+            return Err(mismatch("asset", &invoice.asset, &payment.asset));
+        }
+        if payment.chain != invoice.chain {
+            return Err(mismatch("chain", invoice.chain.as_str(), payment.chain.as_str()));
+        }
+        let expected: Amount = invoice.amount.parse().map_err(InvoiceRejection::Unavailable)?;
+        if payment.amount.parse::<Amount>().ok() != Some(expected) {
+            return Err(mismatch("amount", &invoice.amount, &payment.amount));
+        }
+
+        record.state = InvoiceState::Paid {
+            tx_hash: payment.tx_hash.clone(),
+        };
+        self.store.put(&record).map_err(InvoiceRejection::Unavailable)?;
+        Ok(record.invoice)
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::SecretKey;
+    use std::sync::Arc;
+
+    const MERCHANT: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+
+    fn signer(seed: u8) -> TransactionSigner {
+        TransactionSigner::new(SecretKey::from_bytes(&[seed; 32].into()).unwrap())
+    }
+
+    fn invoice() -> Invoice {
+        Invoice::new("25.00", "USDC", ChainId::Base, MERCHANT, Duration::from_secs(900)).with_memo("Order #42")
+    }
+
+    fn payment(invoice: &Invoice) -> InboundPayment {
+        InboundPayment {
+            invoice_id: invoice.id.clone(),
+            recipient: MERCHANT.to_lowercase(),
+            asset: "USDC".to_string(),
+            amount: "25".to_string(),
+            chain: ChainId::Base,
+            tx_hash: "0xpaid".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_and_verify() {
+        let merchant = signer(7);
+        let signed = invoice().sign(&merchant).unwrap();
+        let payload = signed.encode().unwrap();
+        assert!(payload.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+
+        let uri = signed.to_uri().unwrap();
+        assert!(uri.starts_with("ecash:"));
+        let decoded = SignedInvoice::decode(&uri.replacen("ecash:", "ECASH:", 1)).unwrap();
+        assert_eq!(decoded, signed);
+        assert!(decoded.verify(&merchant.verifying_key()).unwrap());
+        assert!(!decoded.verify(&signer(8).verifying_key()).unwrap());
+
+        let mut tampered = decoded;
+        tampered.invoice.recipient = "0x0000000000000000000000000000000000000001".to_string();
+        assert!(!tampered.verify(&merchant.verifying_key()).unwrap());
+
+        assert!(SignedInvoice::decode("ecash:not*base64").is_err());
+        assert!(Invoice::new("0", "USDC", ChainId::Base, MERCHANT, Duration::ZERO).sign(&merchant).is_err());
+    }
+
+    #[test]
+    fn test_settle_matches_open_invoice() {
+        let book = InvoiceBook::new(Arc::new(InMemoryInvoiceStore::new()));
+        let invoice = invoice();
+        book.open(&invoice).unwrap();
+
+        let wrong_amount = InboundPayment {
+            amount: "24.99".to_string(),
+            ..payment(&invoice)
+        };
+        assert!(matches!(book.settle(&wrong_amount), Err(InvoiceRejection::Mismatch { field: "amount", .. })));
+        let wrong_chain = InboundPayment {
+            chain: ChainId::Ethereum,
+            ..payment(&invoice)
+        };
+        assert!(matches!(book.settle(&wrong_chain), Err(InvoiceRejection::Mismatch { field: "chain", .. })));
+
+        assert_eq!(book.settle(&payment(&invoice)).unwrap().id, invoice.id);
+        let record = book.get(&invoice.id).unwrap().unwrap();
+        assert_eq!(record.state, InvoiceState::Paid { tx_hash: "0xpaid".to_string() });
+        assert!(matches!(book.settle(&payment(&invoice)), Err(InvoiceRejection::AlreadyPaid { .. })));
+
+        let unknown = InboundPayment {
+            invoice_id: "inv_unknown".to_string(),
+            ..payment(&invoice)
+        };
+        assert!(matches!(book.settle(&unknown), Err(InvoiceRejection::UnknownInvoice { .. })));
+
+        let expired = Invoice::new("25.00", "USDC", ChainId::Base, MERCHANT, Duration::ZERO);
+        book.open(&expired).unwrap();
+        assert!(matches!(book.settle(&payment(&expired)), Err(InvoiceRejection::Expired { .. })));
+    }
+}
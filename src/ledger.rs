@@ -0,0 +1,482 @@
+//! Double-entry internal ledger.
+//!
+//! Attach a `Ledger` with `EasyCashClient::with_ledger` and every transaction
+//! the client executes is booked as balanced journal entries. Each entry
+//! debits one account and credits another by the same amount:
+//!
+//! | Event | Debit | Credit |
+//! |-------|-------|--------|
+//! | Handed to an agent | `account:<account_id>` | `pending` |
+//! | Confirmed | `pending` | `settled` |
+//! | Fee charged | `account:<account_id>` | `fees` |
+//! | Fee split paid to a platform | `settled` | `platform:<recipient>` |
+//! | Failed | `pending` | `account:<account_id>` |
+//! | Reverted on-chain (`reverse`) | `settled` | `account:<account_id>` |
+//!
+//! The account ID is the request's `account_id` (`default` when unset).
+//! Transactions that were not confirmed when the call returned stay in
+//! `pending`. Settle them with `settle` once reconciliation against the
+//! chain confirms them. Other movements, such as customer deposits, are
+//! booked with `adjust`.
+//!
+//! ```
+//! use ecash_sdk_core::ledger::{customer_account, InMemoryLedgerStore, Ledger};
+//! use std::sync::Arc;
+//!
+//! let ledger = Ledger::new(Arc::new(InMemoryLedgerStore::new()));
+//! ledger.adjust("deposit_1", "external", &customer_account(Some("acct-7")), "USDC", "100").unwrap();
+//! assert_eq!(ledger.balance(&customer_account(Some("acct-7")), "USDC").unwrap().net(), "100");
+//! assert_eq!(ledger.balance("external", "USDC").unwrap().net(), "-100");
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::reconciliation::csv_escape;
+use crate::types::{FeeSplitAmount, TransactionRequest};
+
+/// Funds handed to an agent and not yet settled
+pub const PENDING: &str = "pending";
+/// Funds that left on-chain
+pub const SETTLED: &str = "settled";
+/// Fees paid to agents and networks
+pub const FEES: &str = "fees";
+
+/// Ledger account of an internal (customer) account
+pub fn customer_account(account_id: Option<&str>) -> String {
+    format!("account:{}", account_id.unwrap_or("default"))
+}
+
+/// Ledger account of a platform paid fee splits
+pub fn platform_account(recipient: &str) -> String {
+    format!("platform:{}", recipient)
+}
+
+/// What a journal entry books
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Submitted,
+    Settled,
+    Fee,
+    PlatformFee,
+    Released,
+    Reversed,
+    Adjustment,
+}
+
+impl EntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryKind::Submitted => "submitted",
+            EntryKind::Settled => "settled",
+            EntryKind::Fee => "fee",
+            EntryKind::PlatformFee => "platform_fee",
+            EntryKind::Released => "released",
+            EntryKind::Reversed => "reversed",
+            EntryKind::Adjustment => "adjustment",
+        }
+    }
+}
+
+/// Movement of `amount` of `asset` from `debit_account` to `credit_account`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position in the journal, from 1
+    pub sequence: u64,
+    pub reference_id: String,
+    pub kind: EntryKind,
+    pub debit_account: String,
+    pub credit_account: String,
+    pub asset: String,
+    pub amount: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub created_at_ms: u64,
+}
+
+/// Append-only storage of journal entries
+pub trait LedgerStore: Send + Sync {
+    /// Appends an entry, assigning its `sequence`
+    fn append(&self, entry: JournalEntry) -> Result<JournalEntry, String>;
+
+    /// Entries of one transaction, in journal order
+    fn entries_for(&self, reference_id: &str) -> Result<Vec<JournalEntry>, String>;
+
+    /// Every entry, in journal order
+    fn entries(&self) -> Result<Vec<JournalEntry>, String>;
+}
+
+/// In-memory ledger store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemoryLedgerStore {
+    journal: Mutex<Journal>,
+}
+
+#[derive(Default)]
+struct Journal {
+    entries: Vec<JournalEntry>,
+    by_reference: HashMap<String, Vec<usize>>,
+}
+
+impl InMemoryLedgerStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Journal>, String> {
+        self.journal.lock().map_err(|_| "ledger store poisoned".to_string())
+    }
+}
+
+impl LedgerStore for InMemoryLedgerStore {
+    fn append(&self, mut entry: JournalEntry) -> Result<JournalEntry, String> {
+        let mut journal = self.lock()?;
+        entry.sequence = journal.entries.len() as u64 + 1;
+        let index = journal.entries.len();
+        journal.by_reference.entry(entry.reference_id.clone()).or_default().push(index);
+        journal.entries.push(entry.clone());
+        Ok(entry)
+    }
+
+    fn entries_for(&self, reference_id: &str) -> Result<Vec<JournalEntry>, String> {
+        let journal = self.lock()?;
+        let indexes = journal.by_reference.get(reference_id).map(Vec::as_slice).unwrap_or_default();
+        Ok(indexes.iter().map(|&i| journal.entries[i].clone()).collect())
+    }
+
+    fn entries(&self) -> Result<Vec<JournalEntry>, String> {
+        Ok(self.lock()?.entries.clone())
+    }
+}
+
+/// Debit and credit totals of an account in one asset
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccountBalance {
+    pub debits: String,
+    pub credits: String,
+}
+
+impl AccountBalance {
+    /// Credits minus debits, negative when the account is overdrawn
+    pub fn net(&self) -> String {
+        let (Ok(debits), Ok(credits)) = (self.debits.parse::<Amount>(), self.credits.parse::<Amount>()) else {
+            return "0".to_string();
+        };
+        match credits.checked_sub(&debits) {
+            Some(net) => net.to_string(),
+            None => format!("-{}", debits.checked_sub(&credits).unwrap_or(debits)),
+        }
+    }
+}
+
+/// Books executions as balanced journal entries and answers balance queries
+pub struct Ledger {
+    store: Arc<dyn LedgerStore>,
+    /// Serializes the check-then-append of settling and releasing
+    lock: Mutex<()>,
+}
+
+impl Ledger {
+    pub fn new(store: Arc<dyn LedgerStore>) -> Self {
+        Self {
+            store,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Books a request handed to an agent: its account to `pending`
+    pub fn record_submission(&self, req: &TransactionRequest) -> Result<(), String> {
+        let amount: Amount = req.amount.parse()?;
+        self.append(
+            &req.reference_id,
+            EntryKind::Submitted,
+            &customer_account(req.account_id.as_deref()),
+            PENDING,
+            &req.asset,
+            amount,
+            None,
+        )
+    }
+
+    /// Settles a pending transaction confirmed in `tx_hash`, charging
+    /// `fee_used` ("0.05 USDC") to its account. Does nothing when the
+    /// transaction is not pending.
+    pub fn settle(&self, reference_id: &str, tx_hash: &str, fee_used: Option<&str>) -> Result<(), String> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(submitted) = self.open_submission(reference_id)? else {
+            return Ok(());
+        };
+        let amount: Amount = submitted.amount.parse()?;
+        let tx_hash = Some(tx_hash);
+        self.append(reference_id, EntryKind::Settled, PENDING, SETTLED, &submitted.asset, amount, tx_hash)?;
+        if let Some((fee, asset)) = fee_used.and_then(parse_fee) {
+            self.append(reference_id, EntryKind::Fee, &submitted.debit_account, FEES, &asset, fee, tx_hash)?;
+        }
+        Ok(())
+    }
+
+    /// Itemizes the share of a settled transfer paid to each platform of its
+    /// fee splits
+    pub fn record_fee_splits(
+        &self,
+        reference_id: &str,
+        asset: &str,
+        splits: &[FeeSplitAmount],
+        tx_hash: &str,
+    ) -> Result<(), String> {
+        for split in splits {
+            let amount: Amount = split.amount.parse()?;
+            if amount.is_zero() {
+                continue;
+            }
+            let platform = platform_account(&split.recipient);
+            self.append(reference_id, EntryKind::PlatformFee, SETTLED, &platform, asset, amount, Some(tx_hash))?;
+        }
+        Ok(())
+    }
+
+    /// Returns a failed transaction's pending amount to its account. Does
+    /// nothing when the transaction is not pending.
+    pub fn release(&self, reference_id: &str) -> Result<(), String> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(submitted) = self.open_submission(reference_id)? else {
+            return Ok(());
+        };
+        let amount: Amount = submitted.amount.parse()?;
+        self.append(
+            reference_id,
+            EntryKind::Released,
+            PENDING,
+            &submitted.debit_account,
+            &submitted.asset,
+            amount,
+            None,
+        )
+    }
+
+    /// Returns a settled transaction that reverted on-chain to its account
+    /// (the fee stays paid)
+    pub fn reverse(&self, reference_id: &str) -> Result<(), String> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let entries = self.store.entries_for(reference_id)?;
+        let settled = entries.iter().rposition(|e| e.kind == EntryKind::Settled);
+        let Some(settled) = settled.filter(|&i| !entries[i..].iter().any(|e| e.kind == EntryKind::Reversed)) else {
+            return Err(format!("{} has no settlement to reverse", reference_id));
+        };
+        let submitted = entries[..settled]
+            .iter()
+            .rev()
+            .find(|e| e.kind == EntryKind::Submitted)
+            .ok_or_else(|| format!("{} has no submission", reference_id))?;
+        let settlement = &entries[settled];
+        self.append(
+            reference_id,
+            EntryKind::Reversed,
+            SETTLED,
+            &submitted.debit_account,
+            &settlement.asset,
+            settlement.amount.parse()?,
+            settlement.tx_hash.as_deref(),
+        )
+    }
+
+    /// Books a movement the SDK did not execute, such as a customer deposit
+    pub fn adjust(
+        &self,
+        reference_id: &str,
+        debit_account: &str,
+        credit_account: &str,
+        asset: &str,
+        amount: &str,
+    ) -> Result<(), String> {
+        let amount: Amount = amount.parse()?;
+        self.append(reference_id, EntryKind::Adjustment, debit_account, credit_account, asset, amount, None)
+    }
+
+    pub fn balance(&self, account: &str, asset: &str) -> Result<AccountBalance, String> {
+        Ok(self
+            .balances(account)?
+            .remove(&asset.to_uppercase())
+            .unwrap_or(AccountBalance {
+                debits: "0".to_string(),
+                credits: "0".to_string(),
+            }))
+    }
+
+    /// Balances of an account, by upper-case asset symbol
+    pub fn balances(&self, account: &str) -> Result<BTreeMap<String, AccountBalance>, String> {
+        let zero = Amount::from_base_units(0, 0);
+        let mut totals: BTreeMap<String, (Amount, Amount)> = BTreeMap::new();
+        for entry in self.store.entries()? {
+            let is_debit = entry.debit_account == account;
+            if !is_debit && entry.credit_account != account {
+                continue;
+            }
+            let amount: Amount = entry.amount.parse()?;
+            let (debits, credits) = totals.entry(entry.asset.to_uppercase()).or_insert((zero, zero));
+            let total = if is_debit { debits } else { credits };
+            *total = total
+                .checked_add(&amount)
+                .ok_or_else(|| format!("balance of {} overflowed", account))?;
+        }
+        Ok(totals
+            .into_iter()
+            .map(|(asset, (debits, credits))| {
+                let balance = AccountBalance {
+                    debits: debits.to_string(),
+                    credits: credits.to_string(),
+                };
+                (asset, balance)
+            })
+            .collect())
+    }
+
+    /// Every journal entry, in order
+    pub fn entries(&self) -> Result<Vec<JournalEntry>, String> {
+        self.store.entries()
+    }
+
+    /// Exports the journal as CSV with a header row
+    pub fn export_csv(&self) -> Result<String, String> {
+        let mut csv =
+            String::from("sequence,reference_id,kind,debit_account,credit_account,asset,amount,tx_hash,created_at_ms\n");
+        for entry in self.store.entries()? {
+            let cells = [
+                entry.sequence.to_string(),
+                entry.reference_id,
+                entry.kind.as_str().to_string(),
+                entry.debit_account,
+                entry.credit_account,
+                entry.asset,
+                entry.amount,
+                entry.tx_hash.unwrap_or_default(),
+                entry.created_at_ms.to_string(),
+            ];
+            let row: Vec<String> = cells.iter().map(|c| csv_escape(c)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        Ok(csv)
+    }
+
+    /// The latest submission of the transaction, unless it was already
+    /// settled or released
+    fn open_submission(&self, reference_id: &str) -> Result<Option<JournalEntry>, String> {
+        let entries = self.store.entries_for(reference_id)?;
+        let Some(index) = entries.iter().rposition(|e| e.kind == EntryKind::Submitted) else {
+            return Ok(None);
+        };
+        let closed = entries[index..]
+            .iter()
+            .any(|e| matches!(e.kind, EntryKind::Settled | EntryKind::Released));
+        Ok((!closed).then(|| entries[index].clone()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn append(
+        &self,
+        reference_id: &str,
+        kind: EntryKind,
+        debit_account: &str,
+        credit_account: &str,
+        asset: &str,
+        amount: Amount,
+        tx_hash: Option<&str>,
+    ) -> Result<(), String> {
+        self.store.append(JournalEntry {
+            sequence: 0,
+            reference_id: reference_id.to_string(),
+            kind,
+            debit_account: debit_account.to_string(),
+            credit_account: credit_account.to_string(),
+            asset: asset.to_uppercase(),
+            amount: amount.to_string(),
+            tx_hash: tx_hash.map(str::to_string),
+            created_at_ms: now_ms(),
+        })?;
+        Ok(())
+    }
+}
+
+/// Parses a fee such as "0.05 USDC"; `None` when unparseable or zero
+fn parse_fee(fee: &str) -> Option<(Amount, String)> {
+    let mut parts = fee.split_whitespace();
+    let amount: Amount = parts.next()?.parse().ok()?;
+    let asset = parts.next()?;
+    (!amount.is_zero()).then(|| (amount, asset.to_string()))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+
+    fn request(reference_id: &str, amount: &str) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, amount, "USDC", ChainId::Base)
+            .with_account_id("acct-7")
+    }
+
+    fn net(ledger: &Ledger, account: &str) -> String {
+        ledger.balance(account, "usdc").unwrap().net()
+    }
+
+    #[test]
+    fn test_settle_release_and_reverse() {
+        let ledger = Ledger::new(Arc::new(InMemoryLedgerStore::new()));
+        let customer = customer_account(Some("acct-7"));
+        ledger.adjust("deposit_1", "external", &customer, "USDC", "100").unwrap();
+
+        ledger.record_submission(&request("ref_1", "40.00")).unwrap();
+        assert_eq!((net(&ledger, &customer), net(&ledger, PENDING)), ("60".to_string(), "40".to_string()));
+        ledger.settle("ref_1", "0xabc", Some("0.05 USDC")).unwrap();
+        // Settling twice books nothing
+        ledger.settle("ref_1", "0xabc", Some("0.05 USDC")).unwrap();
+        assert_eq!(net(&ledger, &customer), "59.95");
+        assert_eq!((net(&ledger, PENDING), net(&ledger, SETTLED), net(&ledger, FEES)), ("0".into(), "40".into(), "0.05".into()));
+
+        ledger.record_submission(&request("ref_2", "10")).unwrap();
+        ledger.release("ref_2").unwrap();
+        ledger.release("ref_2").unwrap();
+        assert_eq!(net(&ledger, &customer), "59.95");
+
+        ledger.reverse("ref_1").unwrap();
+        assert!(ledger.reverse("ref_1").is_err());
+        assert_eq!((net(&ledger, &customer), net(&ledger, SETTLED)), ("99.95".into(), "0".into()));
+
+        // Every entry is balanced, so all accounts sum to zero
+        let accounts = ["external", customer.as_str(), PENDING, SETTLED, FEES];
+        let mut positive = Amount::from_base_units(0, 0);
+        let mut negative = positive;
+        for account in accounts {
+            let balance = net(&ledger, account);
+            match balance.strip_prefix('-') {
+                Some(n) => negative = negative.checked_add(&n.parse().unwrap()).unwrap(),
+                None => positive = positive.checked_add(&balance.parse().unwrap()).unwrap(),
+            }
+        }
+        assert_eq!(positive, negative);
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let ledger = Ledger::new(Arc::new(InMemoryLedgerStore::new()));
+        ledger.record_submission(&request("ref,1", "5")).unwrap();
+        let csv = ledger.export_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("1,\"ref,1\",submitted,account:acct-7,pending,USDC,5,,"));
+        assert!(ledger.record_submission(&request("ref_2", "five")).is_err());
+    }
+}
@@ -19,10 +19,33 @@
 //! * **Type Safety**: Strict typing for Assets, Chains, and Intent structures to prevent financial errors.
 //! * **Comprehensive Validation**: Input validation for addresses, amounts, and chain compatibility.
 //! * **Rate Limiting**: Built-in rate limiting to prevent abuse.
+//! * **Circuit Breaking**: Fails fast while the agent network is unhealthy.
+//! * **Admission Control**: Caps in-flight transactions and queues the rest with backpressure.
+//! * **Lifecycle Events**: Subscribe to typed events for every stage of a transaction.
+//! * **Live Subscriptions**: Stream status changes, agent announcements and fee updates over WebSocket.
+//! * **Log Redaction**: Recipients, amounts, memos, API keys and proofs are hashed, truncated or dropped in logs.
+//! * **Audit Trail**: Tamper-evident, hash-chained log of requests, decisions, and outcomes.
+//! * **Compliance Screening**: Pluggable sanctions/denylist checks before every execution.
+//! * **Travel Rule**: IVMS101-style originator/beneficiary data, sealed to the beneficiary VASP.
+//! * **Spending Policies**: Rolling per-asset limits and route restrictions enforced before execution.
+//! * **Signed Receipts**: Verifiable proof of settlement bound to the original intent.
+//! * **Exactly-once Execution**: Persistent guard against paying the same reference twice, even after a crash.
+//! * **Inclusion Verification**: Check EVM Merkle proofs or Solana confirmations against your own headers.
+//!
+//! ## Runtime-free core
+//!
+//! Building with `default-features = false` drops the `client` feature and
+//! with it Tokio and every networked component. What remains is types,
+//! validation, canonical intent hashing, policies and inclusion verification;
+//! the `crypto` (signing, receipts, sealing), `zk` (solvency proofs), `cache`
+//! and `metrics` features add the rest back one at a time. The core compiles
+//! for `wasm32-unknown-unknown`; such builds must enable getrandom's `js`
+//! feature for key generation and UUIDs.
 //!
 //! ## Quick Start
 //!
-//! ```no_run
+#![cfg_attr(feature = "client", doc = "```no_run")]
+#![cfg_attr(not(feature = "client"), doc = "```ignore")]
 //! use ecash_sdk_core::{EasyCashClient, SdkConfig, TransactionRequest, ChainId, IntentType};
 //!
 //! #[tokio::main]
@@ -31,16 +54,9 @@
 //!     let client = EasyCashClient::new(None)?;
 //!
 //!     // Create a transaction request
-//!     let req = TransactionRequest {
-//!         reference_id: "ref_001".to_string(),
-//!         intent_type: IntentType::Transfer,
-//!         amount: "1000.00".to_string(),
-//!         asset: "USDC".to_string(),
-//!         recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-//!         source_chain: ChainId::Base,
-//!         target_chain: None,
-//!         is_shielded: true,
-//!     };
+//!     let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+//!         .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+//!         .with_shielded(true);
 //!
 //!     // Execute the transaction
 //!     let response = client.execute_transaction(&req).await?;
@@ -50,24 +66,124 @@
 //! }
 //! ```
 
+pub mod address_book;
+#[cfg(feature = "client")]
 pub mod agent;
+pub mod amount;
+pub mod assets;
+pub mod audit;
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "crypto")]
+pub mod broadcast;
+#[cfg(feature = "client")]
+pub mod budget;
+#[cfg(feature = "cache")]
 pub mod cache;
+#[cfg(feature = "client")]
+pub mod cancellation;
+#[cfg(feature = "client")]
+pub mod chain;
+pub mod circuit_breaker;
+pub mod compliance;
+#[cfg(feature = "client")]
+pub mod concurrency;
+#[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "client")]
 pub mod config;
+#[cfg(feature = "crypto")]
+pub mod contracts;
+pub mod conversion;
+pub mod credentials;
 pub mod crypto;
+#[cfg(any(feature = "borsh", feature = "cbor"))]
+pub mod encoding;
+pub mod environment;
 pub mod errors;
+#[cfg(feature = "crypto")]
+pub mod escrow;
+#[cfg(feature = "client")]
+pub mod events;
+#[cfg(feature = "crypto")]
+pub mod evm;
+pub mod exactly_once;
+#[cfg(feature = "test-utils")]
+pub mod faults;
+pub mod fees;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "client")]
+pub mod hooks;
+#[cfg(feature = "client")]
+pub mod http;
+#[cfg(feature = "client")]
+pub mod integrations;
+#[cfg(feature = "client")]
+pub mod intents;
+#[cfg(feature = "crypto")]
+pub mod invoices;
+pub mod ledger;
+#[cfg(feature = "metrics")]
 pub mod monitoring;
+pub mod network;
+#[cfg(feature = "client")]
+pub mod offline;
+pub mod policy;
+pub mod pricing;
+pub mod protocol;
+#[cfg(feature = "client")]
+pub mod queue;
+#[cfg(feature = "client")]
 pub mod rate_limiter;
+pub mod receipt;
+pub mod reconciliation;
+pub mod redaction;
+pub mod refunds;
+#[cfg(feature = "client")]
+pub mod replay;
+#[cfg(feature = "crypto")]
+pub mod request_signing;
+pub mod residency;
+#[cfg(feature = "client")]
+pub mod sandbox;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod secrets;
+#[cfg(feature = "test-utils")]
+pub mod simulation;
+#[cfg(feature = "client")]
+pub mod solvency;
+#[cfg(feature = "client")]
+pub mod sponsorship;
+#[cfg(feature = "client")]
+pub mod streaming;
+#[cfg(feature = "client")]
+pub mod subscriptions;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod tls;
+#[cfg(feature = "crypto")]
+pub mod transport;
+pub mod travel_rule;
 pub mod types;
 pub mod validator;
+pub mod verification;
+pub mod webhooks;
+#[cfg(feature = "zk")]
 pub mod zk;
 
 // Re-export main types for convenience
-pub use client::EasyCashClient;
+#[cfg(feature = "client")]
+pub use client::{EasyCashClient, ExecuteOptions};
+#[cfg(feature = "client")]
 pub use config::SdkConfig;
-pub use errors::{ErrorCode, Result, SdkError};
+pub use errors::{ErrorCode, Result, SdkError, SdkErrorResponse};
 pub use types::{ChainId, IntentType, TransactionRequest, TransactionResponse};
 
 // Re-export commonly used traits
+#[cfg(feature = "client")]
 pub use agent::AgentNegotiatorTrait;
+#[cfg(feature = "zk")]
 pub use zk::ZkProofGenerator;
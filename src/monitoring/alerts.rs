@@ -0,0 +1,339 @@
+//! Alert rules evaluated against the sliding-window metrics.
+//!
+//! An `AlertMonitor` checks its rules after each transaction and hands every
+//! breach to an `AlertHandler`. Each rule has a cooldown so a sustained
+//! breach fires once per cooldown period instead of once per transaction.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::{Metrics, MetricsWindow};
+
+/// Threshold checked by an alert rule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Share of failed transactions (0.0-1.0) exceeds `threshold`, once the
+    /// window holds at least `min_transactions`
+    FailureRateAbove {
+        threshold: f64,
+        window: MetricsWindow,
+        min_transactions: u64,
+    },
+    /// Average transaction latency exceeds `threshold_ms`
+    AverageLatencyAbove { threshold_ms: f64, window: MetricsWindow },
+    /// Total fees paid within the window exceed `threshold`
+    FeeSpendAbove { threshold: f64, window: MetricsWindow },
+}
+
+impl AlertCondition {
+    /// Returns the observed value if the condition is breached
+    fn check(&self, metrics: &Metrics) -> Option<f64> {
+        match self {
+            AlertCondition::FailureRateAbove {
+                threshold,
+                window,
+                min_transactions,
+            } => {
+                let stats = metrics.window_stats(*window);
+                let failure_rate = 1.0 - stats.success_rate;
+                (stats.transactions > 0 && stats.transactions >= *min_transactions && failure_rate > *threshold)
+                    .then_some(failure_rate)
+            }
+            AlertCondition::AverageLatencyAbove { threshold_ms, window } => {
+                let stats = metrics.window_stats(*window);
+                (stats.transactions > 0 && stats.average_latency_ms > *threshold_ms).then_some(stats.average_latency_ms)
+            }
+            AlertCondition::FeeSpendAbove { threshold, window } => {
+                let stats = metrics.window_stats(*window);
+                (stats.fee_paid > *threshold).then_some(stats.fee_paid)
+            }
+        }
+    }
+}
+
+/// Named alert condition with a cooldown between firings
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub name: String,
+    pub condition: AlertCondition,
+    /// Minimum time between two firings of this rule
+    pub cooldown: Duration,
+}
+
+impl AlertRule {
+    /// Creates a rule with a 5 minute cooldown
+    pub fn new(name: impl Into<String>, condition: AlertCondition) -> Self {
+        Self {
+            name: name.into(),
+            condition,
+            cooldown: Duration::from_secs(300),
+        }
+    }
+
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+/// Breach of an alert rule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub rule: String,
+    pub condition: AlertCondition,
+    /// Observed value that breached the threshold
+    pub value: f64,
+    pub timestamp_ms: u64,
+}
+
+/// Receives fired alerts
+#[async_trait::async_trait]
+pub trait AlertHandler: Send + Sync {
+    async fn on_alert(&self, alert: &Alert);
+}
+
+/// Any `async fn(Alert)` closure can be used as a handler
+#[async_trait::async_trait]
+impl<F, Fut> AlertHandler for F
+where
+    F: Fn(Alert) -> Fut + Send + Sync,
+    Fut: Future<Output = ()> + Send,
+{
+    async fn on_alert(&self, alert: &Alert) {
+        self(alert.clone()).await
+    }
+}
+
+/// Posts each alert as JSON to a webhook URL
+#[cfg(feature = "alert-webhook")]
+pub struct WebhookAlertHandler {
+    url: String,
+    timeout: Duration,
+    http: crate::http::HttpClient,
+}
+
+#[cfg(feature = "alert-webhook")]
+impl WebhookAlertHandler {
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            url: url.into(),
+            timeout,
+            http: crate::http::HttpClient::default(),
+        }
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: crate::http::HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[cfg(feature = "alert-webhook")]
+#[async_trait::async_trait]
+impl AlertHandler for WebhookAlertHandler {
+    async fn on_alert(&self, alert: &Alert) {
+        let body = serde_json::json!(alert);
+        match self.http.post_json(&self.url, &[], &body, self.timeout).await {
+            Ok(resp) if resp.is_success() => {}
+            Ok(resp) => tracing::warn!("[SDK] Alert webhook returned status {}", resp.status),
+            Err(e) => tracing::warn!("[SDK] Failed to deliver alert {}: {}", alert.rule, e),
+        }
+    }
+}
+
+/// Evaluates alert rules against a client's metrics
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use ecash_sdk_core::monitoring::{Metrics, MetricsWindow};
+/// use ecash_sdk_core::monitoring::alerts::{Alert, AlertCondition, AlertMonitor, AlertRule};
+///
+/// # tokio_test::block_on(async {
+/// let monitor = AlertMonitor::new(Arc::new(|alert: Alert| async move {
+///     eprintln!("alert {} fired: {}", alert.rule, alert.value);
+/// }))
+/// .with_rule(AlertRule::new(
+///     "failure-rate",
+///     AlertCondition::FailureRateAbove { threshold: 0.05, window: MetricsWindow::FiveMinutes, min_transactions: 20 },
+/// ));
+/// // `EasyCashClient::with_alert_monitor` runs this after every transaction
+/// assert!(monitor.evaluate(&Metrics::new()).await.is_empty());
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct AlertMonitor {
+    rules: Vec<AlertRule>,
+    handler: Arc<dyn AlertHandler>,
+    last_fired: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl AlertMonitor {
+    pub fn new(handler: Arc<dyn AlertHandler>) -> Self {
+        Self {
+            rules: Vec::new(),
+            handler,
+            last_fired: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Adds a rule (builder style)
+    pub fn with_rule(mut self, rule: AlertRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Checks every rule, fires the handler for each breach outside its
+    /// cooldown and returns the fired alerts
+    pub async fn evaluate(&self, metrics: &Metrics) -> Vec<Alert> {
+        let fired = self.breaches(metrics);
+        for alert in &fired {
+            tracing::warn!("[SDK] Alert {} fired (value: {})", alert.rule, alert.value);
+            self.handler.on_alert(alert).await;
+        }
+        fired
+    }
+
+    fn breaches(&self, metrics: &Metrics) -> Vec<Alert> {
+        let now = Instant::now();
+        let mut last_fired = self.last_fired.lock().unwrap_or_else(|e| e.into_inner());
+        let mut fired = Vec::new();
+        for rule in &self.rules {
+            let Some(value) = rule.condition.check(metrics) else { continue };
+            if last_fired
+                .get(&rule.name)
+                .is_some_and(|at| now.duration_since(*at) < rule.cooldown)
+            {
+                continue;
+            }
+            last_fired.insert(rule.name.clone(), now);
+            fired.push(Alert {
+                rule: rule.name.clone(),
+                condition: rule.condition.clone(),
+                value,
+                timestamp_ms: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0),
+            });
+        }
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collecting_monitor() -> (AlertMonitor, Arc<Mutex<Vec<Alert>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let monitor = AlertMonitor::new(Arc::new(move |alert: Alert| {
+            let sink = sink.clone();
+            async move { sink.lock().unwrap().push(alert) }
+        }));
+        (monitor, received)
+    }
+
+    #[tokio::test]
+    async fn test_failure_rate_alert_respects_minimum_and_cooldown() {
+        let (monitor, received) = collecting_monitor();
+        let monitor = monitor.with_rule(AlertRule::new(
+            "failure-rate",
+            AlertCondition::FailureRateAbove {
+                threshold: 0.05,
+                window: MetricsWindow::FiveMinutes,
+                min_transactions: 3,
+            },
+        ));
+        let metrics = Metrics::new();
+
+        metrics.record_transaction(false, 0.0, Duration::from_millis(10));
+        metrics.record_transaction(true, 0.01, Duration::from_millis(10));
+        assert!(monitor.evaluate(&metrics).await.is_empty());
+
+        metrics.record_transaction(true, 0.01, Duration::from_millis(10));
+        let fired = monitor.evaluate(&metrics).await;
+        assert_eq!(fired.len(), 1);
+        assert!((fired[0].value - 1.0 / 3.0).abs() < 1e-9);
+
+        // Still breached, but within the cooldown
+        assert!(monitor.evaluate(&metrics).await.is_empty());
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_latency_and_fee_alerts() {
+        let (monitor, received) = collecting_monitor();
+        let monitor = monitor
+            .with_rule(
+                AlertRule::new(
+                    "latency",
+                    AlertCondition::AverageLatencyAbove {
+                        threshold_ms: 500.0,
+                        window: MetricsWindow::OneMinute,
+                    },
+                )
+                .with_cooldown(Duration::ZERO),
+            )
+            .with_rule(AlertRule::new(
+                "fee-spend",
+                AlertCondition::FeeSpendAbove {
+                    threshold: 1.0,
+                    window: MetricsWindow::OneHour,
+                },
+            ));
+        let metrics = Metrics::new();
+
+        metrics.record_transaction(true, 0.5, Duration::from_millis(100));
+        assert!(monitor.evaluate(&metrics).await.is_empty());
+
+        metrics.record_transaction(true, 0.7, Duration::from_millis(1500));
+        let fired: Vec<String> = monitor.evaluate(&metrics).await.into_iter().map(|a| a.rule).collect();
+        assert_eq!(fired, vec!["latency", "fee-spend"]);
+
+        // Zero cooldown fires again; the fee rule is still cooling down
+        let fired: Vec<String> = monitor.evaluate(&metrics).await.into_iter().map(|a| a.rule).collect();
+        assert_eq!(fired, vec!["latency"]);
+        assert_eq!(received.lock().unwrap().len(), 3);
+    }
+
+    #[cfg(feature = "alert-webhook")]
+    #[tokio::test]
+    async fn test_webhook_handler_posts_alert() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket.write_all(b"HTTP/1.1 204 No Content\r\n\r\n").await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let handler = WebhookAlertHandler::new(format!("http://{}/alerts", addr), Duration::from_secs(1));
+        handler
+            .on_alert(&Alert {
+                rule: "fee-spend".to_string(),
+                condition: AlertCondition::FeeSpendAbove {
+                    threshold: 1.0,
+                    window: MetricsWindow::OneHour,
+                },
+                value: 1.2,
+                timestamp_ms: 1,
+            })
+            .await;
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /alerts "));
+        assert!(request.contains(r#""rule":"fee-spend""#));
+        assert!(request.contains(r#""type":"fee_spend_above""#));
+    }
+}
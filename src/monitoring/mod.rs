@@ -0,0 +1,923 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::circuit_breaker::CircuitState;
+
+pub mod alerts;
+
+/// Health report for the SDK client
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthStatus {
+    /// Whether the client is currently able to process transactions
+    pub healthy: bool,
+    /// State of the circuit breaker guarding agent calls
+    pub circuit_breaker: CircuitState,
+}
+
+/// Keep-alive connection reuse by the SDK's HTTP client
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConnectionPoolStats {
+    /// Connections currently idle in the pool
+    pub idle_connections: u64,
+    /// New TCP (and TLS) connections established
+    pub connections_opened: u64,
+    /// Requests served on a pooled connection
+    pub connections_reused: u64,
+}
+
+/// Load and timings of the proof worker pool
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ProofPoolStats {
+    /// Jobs waiting for a worker
+    pub queued: u64,
+    pub in_progress: u64,
+    pub completed: u64,
+    pub failed: u64,
+    /// Jobs refused because the queue was full
+    pub rejected: u64,
+    pub average_queue_time_ms: f64,
+    pub average_prove_time_ms: f64,
+}
+
+/// Performance of a single agent, used to spot degrading agents
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgentStats {
+    pub agent_id: String,
+    /// Number of quotes the agent returned
+    pub quotes_received: u64,
+    /// Average latency of the quote requests the agent answered
+    pub average_quote_latency_ms: f64,
+    /// Number of times the agent's quote was selected
+    pub selections_won: u64,
+    /// Executions handed to the agent
+    pub executions: u64,
+    /// Share of executions that succeeded (0.0 when none were attempted)
+    pub execution_success_rate: f64,
+    /// Average amount by which the agent's selected fee exceeded the best quoted fee
+    pub average_fee_delta: f64,
+    /// Executions for which the agent charged more than it quoted
+    pub quote_violations: u64,
+}
+
+/// Receives every recorded transaction as it happens, for forwarding to an
+/// external aggregator
+pub trait MetricsSink: Send + Sync {
+    fn record_transaction(&self, success: bool, fee: f64, latency: Duration);
+
+    /// Records a transaction attributed to `account_id`
+    /// (`TransactionRequest::account_id`). Sinks that break metrics down by
+    /// account override this; by default the account is ignored.
+    fn record_account_transaction(&self, account_id: Option<&str>, success: bool, fee: f64, latency: Duration) {
+        let _ = account_id;
+        self.record_transaction(success, fee, latency);
+    }
+}
+
+/// Sends transaction metrics to a StatsD (or Datadog DogStatsD) agent over UDP.
+///
+/// Each transaction emits, in a single datagram:
+/// - `<prefix>.transactions:1|c` tagged with `status:success` or `status:failure`
+/// - `<prefix>.latency_ms:<ms>|ms`
+/// - `<prefix>.fee_paid:<fee>|h` for successful transactions
+///
+/// Transactions with an `account_id` are additionally tagged `account:<id>`.
+///
+/// Sends are fire-and-forget; a missing agent never affects transactions.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "client")] {
+/// use std::sync::Arc;
+/// use ecash_sdk_core::client::EasyCashClient;
+/// use ecash_sdk_core::monitoring::StatsdSink;
+///
+/// let sink = StatsdSink::new("127.0.0.1:8125", "ecash").unwrap().with_tag("env", "prod");
+/// let client = EasyCashClient::new(None).unwrap().with_metrics_sink(Arc::new(sink));
+/// # }
+/// ```
+#[cfg(feature = "statsd")]
+pub struct StatsdSink {
+    socket: std::net::UdpSocket,
+    prefix: String,
+    tags: Vec<String>,
+}
+
+#[cfg(feature = "statsd")]
+impl StatsdSink {
+    /// Creates a sink sending to the agent at `addr` (e.g. "127.0.0.1:8125")
+    pub fn new(addr: impl std::net::ToSocketAddrs, prefix: impl Into<String>) -> Result<Self, String> {
+        let socket = std::net::UdpSocket::bind("0.0.0.0:0").map_err(|e| format!("failed to bind statsd socket: {}", e))?;
+        socket
+            .connect(addr)
+            .map_err(|e| format!("failed to resolve statsd agent: {}", e))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| format!("failed to configure statsd socket: {}", e))?;
+        Ok(Self {
+            socket,
+            prefix: prefix.into(),
+            tags: Vec::new(),
+        })
+    }
+
+    /// Adds a DogStatsD tag sent with every metric
+    pub fn with_tag(mut self, key: &str, value: &str) -> Self {
+        self.tags.push(format!("{}:{}", key, value));
+        self
+    }
+
+    fn line(&self, name: &str, value: &str, kind: &str, extra_tags: &[Option<&str>]) -> String {
+        let mut line = format!("{}.{}:{}|{}", self.prefix, name, value, kind);
+        let tags: Vec<&str> = self.tags.iter().map(String::as_str).chain(extra_tags.iter().flatten().copied()).collect();
+        if !tags.is_empty() {
+            line.push_str("|#");
+            line.push_str(&tags.join(","));
+        }
+        line
+    }
+}
+
+#[cfg(feature = "statsd")]
+impl MetricsSink for StatsdSink {
+    fn record_transaction(&self, success: bool, fee: f64, latency: Duration) {
+        self.record_account_transaction(None, success, fee, latency);
+    }
+
+    fn record_account_transaction(&self, account_id: Option<&str>, success: bool, fee: f64, latency: Duration) {
+        let status = if success { "status:success" } else { "status:failure" };
+        let account = account_id.map(|id| format!("account:{}", id));
+        let account = account.as_deref();
+        let mut lines = vec![
+            self.line("transactions", "1", "c", &[Some(status), account]),
+            self.line("latency_ms", &latency.as_millis().to_string(), "ms", &[account]),
+        ];
+        if success {
+            lines.push(self.line("fee_paid", &fee.to_string(), "h", &[account]));
+        }
+        if let Err(e) = self.socket.send(lines.join("\n").as_bytes()) {
+            tracing::debug!("[SDK] Failed to send statsd metrics: {}", e);
+        }
+    }
+}
+
+/// Recent time window over which metrics are aggregated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum MetricsWindow {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+}
+
+impl MetricsWindow {
+    /// All windows, shortest first
+    pub const ALL: [MetricsWindow; 3] = [MetricsWindow::OneMinute, MetricsWindow::FiveMinutes, MetricsWindow::OneHour];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricsWindow::OneMinute => "1m",
+            MetricsWindow::FiveMinutes => "5m",
+            MetricsWindow::OneHour => "1h",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            MetricsWindow::OneMinute => Duration::from_secs(60),
+            MetricsWindow::FiveMinutes => Duration::from_secs(300),
+            MetricsWindow::OneHour => Duration::from_secs(3600),
+        }
+    }
+}
+
+/// Transaction metrics aggregated over a recent window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WindowStats {
+    pub window: MetricsWindow,
+    pub transactions: u64,
+    pub successful_transactions: u64,
+    /// Share of transactions in the window that succeeded (0.0 when there were none)
+    pub success_rate: f64,
+    pub average_latency_ms: f64,
+    pub fee_paid: f64,
+}
+
+/// Fees are accumulated as integer micro-units so they can be added atomically
+const FEE_SCALE: f64 = 1_000_000.0;
+
+fn fee_to_micros(fee: f64) -> i64 {
+    (fee * FEE_SCALE).round() as i64
+}
+
+fn micros_to_fee(micros: i64) -> f64 {
+    micros as f64 / FEE_SCALE
+}
+
+/// Running totals for the threads assigned to one shard, padded to its own
+/// cache line so shards don't contend
+#[repr(align(128))]
+#[derive(Default)]
+struct TotalsShard {
+    transactions: AtomicU64,
+    successful: AtomicU64,
+    failed: AtomicU64,
+    fee_micros: AtomicI64,
+    latency_ms: AtomicU64,
+}
+
+/// Sum of all shards at the time of reading
+#[derive(Default)]
+struct Totals {
+    transactions: u64,
+    successful: u64,
+    failed: u64,
+    fee_paid: f64,
+    latency_ms: u64,
+}
+
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // Threads are spread round-robin over the shards
+    static SHARD: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Lifetime totals split over roughly one shard per core; recording adds to
+/// the calling thread's shard without locking, reading sums them all
+struct ShardedTotals {
+    shards: Box<[TotalsShard]>,
+}
+
+impl ShardedTotals {
+    fn new() -> Self {
+        let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+        Self {
+            shards: (0..cores.next_power_of_two().min(64)).map(|_| TotalsShard::default()).collect(),
+        }
+    }
+
+    fn shard(&self) -> &TotalsShard {
+        &self.shards[SHARD.with(|s| *s) % self.shards.len()]
+    }
+
+    fn sum(&self) -> Totals {
+        let (mut totals, mut fee_micros) = (Totals::default(), 0i64);
+        for shard in self.shards.iter() {
+            totals.transactions += shard.transactions.load(Ordering::Relaxed);
+            totals.successful += shard.successful.load(Ordering::Relaxed);
+            totals.failed += shard.failed.load(Ordering::Relaxed);
+            fee_micros += shard.fee_micros.load(Ordering::Relaxed);
+            totals.latency_ms += shard.latency_ms.load(Ordering::Relaxed);
+        }
+        totals.fee_paid = micros_to_fee(fee_micros);
+        totals
+    }
+
+    fn reset(&self) {
+        for shard in self.shards.iter() {
+            shard.transactions.store(0, Ordering::Relaxed);
+            shard.successful.store(0, Ordering::Relaxed);
+            shard.failed.store(0, Ordering::Relaxed);
+            shard.fee_micros.store(0, Ordering::Relaxed);
+            shard.latency_ms.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// One slot per second of the longest window
+const RECENT_SLOTS: u64 = 3600;
+/// Marks a slot that is being cleared for a new second
+const CLEARING: u64 = u64::MAX;
+
+/// Transactions recorded during the second in `second`
+#[derive(Default)]
+struct SecondBucket {
+    second: AtomicU64,
+    transactions: AtomicU64,
+    successful: AtomicU64,
+    latency_ms: AtomicU64,
+    fee_micros: AtomicI64,
+}
+
+/// Point-in-time copy of all client metrics
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::monitoring::Metrics;
+///
+/// let snapshot = Metrics::new().snapshot();
+/// assert_eq!(snapshot.total_transactions, 0);
+/// let json = serde_json::to_string(&snapshot).unwrap();
+/// assert!(json.contains("\"timestamp_ms\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    /// Unix time at which the snapshot was taken
+    pub timestamp_ms: u64,
+    /// False when metrics collection is disabled in the config
+    pub metrics_enabled: bool,
+    pub total_transactions: u64,
+    pub successful_transactions: u64,
+    pub failed_transactions: u64,
+    pub success_rate: f64,
+    pub total_fee_paid: f64,
+    pub average_latency_ms: f64,
+    pub circuit_breaker: CircuitState,
+    pub circuit_breaker_failure_rate: f64,
+    pub in_flight_transactions: u64,
+    pub queue_depth: u64,
+    pub average_queue_wait_ms: f64,
+    pub rejected_admissions: u64,
+    /// Shielded spends refused locally because their note was already spent
+    pub near_miss_double_spends: u64,
+    /// Executions for which an agent charged more than it quoted
+    pub quote_violations: u64,
+    /// Aggregates over the last minute, five minutes and hour
+    pub windows: Vec<WindowStats>,
+    /// Per-agent statistics, sorted by agent ID
+    pub agents: Vec<AgentStats>,
+    pub connection_pool: ConnectionPoolStats,
+    pub proof_pool: ProofPoolStats,
+}
+
+impl MetricsSnapshot {
+    /// Flattens the snapshot into the map returned by `EasyCashClient::get_metrics`
+    pub fn to_map(&self) -> HashMap<String, f64> {
+        if !self.metrics_enabled {
+            return HashMap::from([("metrics_disabled".to_string(), 1.0)]);
+        }
+        let mut map = HashMap::from([
+            ("total_transactions".to_string(), self.total_transactions as f64),
+            ("successful_transactions".to_string(), self.successful_transactions as f64),
+            ("failed_transactions".to_string(), self.failed_transactions as f64),
+            ("success_rate".to_string(), self.success_rate),
+            ("total_fee_paid".to_string(), self.total_fee_paid),
+            ("average_latency_ms".to_string(), self.average_latency_ms),
+            ("circuit_breaker_state".to_string(), self.circuit_breaker.as_metric()),
+            ("circuit_breaker_failure_rate".to_string(), self.circuit_breaker_failure_rate),
+            ("in_flight_transactions".to_string(), self.in_flight_transactions as f64),
+            ("queue_depth".to_string(), self.queue_depth as f64),
+            ("average_queue_wait_ms".to_string(), self.average_queue_wait_ms),
+            ("rejected_admissions".to_string(), self.rejected_admissions as f64),
+            ("near_miss_double_spends".to_string(), self.near_miss_double_spends as f64),
+            ("quote_violations".to_string(), self.quote_violations as f64),
+            ("pool_idle_connections".to_string(), self.connection_pool.idle_connections as f64),
+            ("pool_connections_opened".to_string(), self.connection_pool.connections_opened as f64),
+            ("pool_connections_reused".to_string(), self.connection_pool.connections_reused as f64),
+            ("proof_queue_depth".to_string(), self.proof_pool.queued as f64),
+            ("proofs_rejected".to_string(), self.proof_pool.rejected as f64),
+            ("average_proof_queue_time_ms".to_string(), self.proof_pool.average_queue_time_ms),
+            ("average_prove_time_ms".to_string(), self.proof_pool.average_prove_time_ms),
+        ]);
+        for w in &self.windows {
+            insert_window(&mut map, w);
+        }
+        map
+    }
+}
+
+fn insert_window(map: &mut HashMap<String, f64>, w: &WindowStats) {
+    let label = w.window.as_str();
+    map.insert(format!("transactions_{}", label), w.transactions as f64);
+    map.insert(format!("success_rate_{}", label), w.success_rate);
+    map.insert(format!("average_latency_ms_{}", label), w.average_latency_ms);
+    map.insert(format!("fee_paid_{}", label), w.fee_paid);
+}
+
+#[derive(Default)]
+struct AgentCounters {
+    quotes_received: u64,
+    quote_latency_ms: u64,
+    selections_won: u64,
+    fee_delta_total: f64,
+    fee_delta_samples: u64,
+    executions: u64,
+    successful_executions: u64,
+    quote_violations: u64,
+}
+
+impl AgentCounters {
+    fn to_stats(&self, agent_id: &str) -> AgentStats {
+        let ratio = |n: f64, d: u64| if d > 0 { n / d as f64 } else { 0.0 };
+        AgentStats {
+            agent_id: agent_id.to_string(),
+            quotes_received: self.quotes_received,
+            average_quote_latency_ms: ratio(self.quote_latency_ms as f64, self.quotes_received),
+            selections_won: self.selections_won,
+            executions: self.executions,
+            execution_success_rate: ratio(self.successful_executions as f64, self.executions),
+            average_fee_delta: ratio(self.fee_delta_total, self.fee_delta_samples),
+            quote_violations: self.quote_violations,
+        }
+    }
+}
+
+// Note: Global metrics removed - each client instance has its own metrics
+// This prevents cross-client metric pollution
+
+/// Metrics tracks SDK performance and usage statistics
+#[derive(Clone)]
+pub struct Metrics {
+    totals: Arc<ShardedTotals>,
+    agents: Arc<Mutex<HashMap<String, AgentCounters>>>,
+    // Ring of per-second buckets covering the longest window, indexed by
+    // second modulo RECENT_SLOTS
+    recent: Arc<[SecondBucket]>,
+    sinks: Vec<Arc<dyn MetricsSink>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    /// Returns the global metrics instance
+    pub fn new() -> Self {
+        Self {
+            totals: Arc::new(ShardedTotals::new()),
+            agents: Arc::new(Mutex::new(HashMap::new())),
+            recent: (0..RECENT_SLOTS).map(|_| SecondBucket::default()).collect(),
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Forwards every recorded transaction to the sink
+    pub fn with_sink(mut self, sink: Arc<dyn MetricsSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Records a quote returned by an agent and the latency of the quote request
+    pub fn record_quote(&self, agent_id: &str, latency: Duration) {
+        if let Ok(mut agents) = self.agents.lock() {
+            let counters = agents.entry(agent_id.to_string()).or_default();
+            counters.quotes_received += 1;
+            counters.quote_latency_ms += latency.as_millis() as u64;
+        }
+    }
+
+    /// Records that an agent's quote was selected.
+    ///
+    /// `fee_delta` is the selected fee minus the best quoted fee, when the fees
+    /// were comparable.
+    pub fn record_selection(&self, agent_id: &str, fee_delta: Option<f64>) {
+        if let Ok(mut agents) = self.agents.lock() {
+            let counters = agents.entry(agent_id.to_string()).or_default();
+            counters.selections_won += 1;
+            if let Some(delta) = fee_delta {
+                counters.fee_delta_total += delta;
+                counters.fee_delta_samples += 1;
+            }
+        }
+    }
+
+    /// Records the outcome of an execution handed to an agent
+    pub fn record_execution(&self, agent_id: &str, success: bool) {
+        if let Ok(mut agents) = self.agents.lock() {
+            let counters = agents.entry(agent_id.to_string()).or_default();
+            counters.executions += 1;
+            if success {
+                counters.successful_executions += 1;
+            }
+        }
+    }
+
+    /// Records that an agent charged more than it quoted
+    pub fn record_quote_violation(&self, agent_id: &str) {
+        if let Ok(mut agents) = self.agents.lock() {
+            agents.entry(agent_id.to_string()).or_default().quote_violations += 1;
+        }
+    }
+
+    /// Returns per-agent statistics keyed by agent ID
+    pub fn get_agent_stats(&self) -> HashMap<String, AgentStats> {
+        self.agents
+            .lock()
+            .map(|agents| {
+                agents
+                    .iter()
+                    .map(|(id, counters)| (id.clone(), counters.to_stats(id)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Records a transaction attempt without taking any lock
+    pub fn record_transaction(&self, success: bool, fee: f64, latency: Duration) {
+        self.record_transaction_for(None, success, fee, latency);
+    }
+
+    /// Records a transaction attempt attributed to `account_id`. Totals are
+    /// not broken down by account; sinks receive the account as a dimension.
+    pub fn record_transaction_for(&self, account_id: Option<&str>, success: bool, fee: f64, latency: Duration) {
+        let shard = self.totals.shard();
+        shard.transactions.fetch_add(1, Ordering::Relaxed);
+
+        if success {
+            shard.successful.fetch_add(1, Ordering::Relaxed);
+            shard.fee_micros.fetch_add(fee_to_micros(fee), Ordering::Relaxed);
+        } else {
+            shard.failed.fetch_add(1, Ordering::Relaxed);
+        }
+
+        // Accumulate total latency (average calculated in get_stats)
+        let latency_ms = latency.as_millis() as u64;
+        shard.latency_ms.fetch_add(latency_ms, Ordering::Relaxed);
+
+        self.record_recent(now_secs(), success, fee, latency_ms);
+
+        for sink in &self.sinks {
+            sink.record_account_transaction(account_id, success, fee, latency);
+        }
+    }
+
+    fn record_recent(&self, second: u64, success: bool, fee: f64, latency_ms: u64) {
+        let bucket = &self.recent[(second % RECENT_SLOTS) as usize];
+        loop {
+            let current = bucket.second.load(Ordering::Acquire);
+            if current == second {
+                break;
+            }
+            if current == CLEARING {
+                // Another thread is clearing the slot for this second
+                std::hint::spin_loop();
+                continue;
+            }
+            if current > second {
+                // The slot already holds a later second; this one has left every window
+                return;
+            }
+            if bucket
+                .second
+                .compare_exchange(current, CLEARING, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                bucket.transactions.store(0, Ordering::Relaxed);
+                bucket.successful.store(0, Ordering::Relaxed);
+                bucket.latency_ms.store(0, Ordering::Relaxed);
+                bucket.fee_micros.store(0, Ordering::Relaxed);
+                bucket.second.store(second, Ordering::Release);
+                break;
+            }
+        }
+        bucket.transactions.fetch_add(1, Ordering::Relaxed);
+        bucket.latency_ms.fetch_add(latency_ms, Ordering::Relaxed);
+        if success {
+            bucket.successful.fetch_add(1, Ordering::Relaxed);
+            bucket.fee_micros.fetch_add(fee_to_micros(fee), Ordering::Relaxed);
+        }
+    }
+
+    /// Returns transaction metrics aggregated over a recent window
+    pub fn window_stats(&self, window: MetricsWindow) -> WindowStats {
+        self.window_stats_at(window, now_secs())
+    }
+
+    fn window_stats_at(&self, window: MetricsWindow, now: u64) -> WindowStats {
+        let cutoff = now.saturating_sub(window.duration().as_secs());
+        let (mut transactions, mut successful, mut latency_ms, mut fee_micros) = (0u64, 0u64, 0u64, 0i64);
+        for second in cutoff + 1..=now {
+            let bucket = &self.recent[(second % RECENT_SLOTS) as usize];
+            if bucket.second.load(Ordering::Acquire) != second {
+                continue;
+            }
+            transactions += bucket.transactions.load(Ordering::Relaxed);
+            successful += bucket.successful.load(Ordering::Relaxed);
+            latency_ms += bucket.latency_ms.load(Ordering::Relaxed);
+            fee_micros += bucket.fee_micros.load(Ordering::Relaxed);
+        }
+        let ratio = |n: f64| if transactions > 0 { n / transactions as f64 } else { 0.0 };
+        WindowStats {
+            window,
+            transactions,
+            successful_transactions: successful,
+            success_rate: ratio(successful as f64),
+            average_latency_ms: ratio(latency_ms as f64),
+            fee_paid: micros_to_fee(fee_micros),
+        }
+    }
+
+    /// Returns current statistics
+    pub fn get_stats(&self) -> std::collections::HashMap<String, f64> {
+        let totals = self.totals.sum();
+        let total = totals.transactions as f64;
+        let successful = totals.successful as f64;
+        let failed = totals.failed as f64;
+        let total_fee = totals.fee_paid;
+        let total_latency = totals.latency_ms as f64;
+
+        let mut stats = std::collections::HashMap::new();
+        stats.insert("total_transactions".to_string(), total);
+        stats.insert("successful_transactions".to_string(), successful);
+        stats.insert("failed_transactions".to_string(), failed);
+        stats.insert("total_fee_paid".to_string(), total_fee);
+        
+        if total > 0.0 {
+            stats.insert("average_latency_ms".to_string(), total_latency / total);
+            stats.insert("success_rate".to_string(), successful / total);
+        } else {
+            stats.insert("average_latency_ms".to_string(), 0.0);
+            stats.insert("success_rate".to_string(), 0.0);
+        }
+
+        for window in MetricsWindow::ALL {
+            insert_window(&mut stats, &self.window_stats(window));
+        }
+
+        stats
+    }
+
+    /// Captures the transaction and agent metrics.
+    ///
+    /// Circuit breaker, admission, connection and proof pool fields are left at
+    /// their idle values; the client fills them in from its own components.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let totals = self.totals.sum();
+        let (total, successful, total_latency) = (totals.transactions, totals.successful, totals.latency_ms);
+        let mut agents: Vec<AgentStats> = self.get_agent_stats().into_values().collect();
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+
+        MetricsSnapshot {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            metrics_enabled: true,
+            total_transactions: total,
+            successful_transactions: successful,
+            failed_transactions: totals.failed,
+            success_rate: if total > 0 { successful as f64 / total as f64 } else { 0.0 },
+            total_fee_paid: totals.fee_paid,
+            average_latency_ms: if total > 0 { total_latency as f64 / total as f64 } else { 0.0 },
+            circuit_breaker: CircuitState::Closed,
+            circuit_breaker_failure_rate: 0.0,
+            in_flight_transactions: 0,
+            queue_depth: 0,
+            average_queue_wait_ms: 0.0,
+            rejected_admissions: 0,
+            near_miss_double_spends: 0,
+            quote_violations: agents.iter().map(|a| a.quote_violations).sum(),
+            windows: MetricsWindow::ALL.iter().map(|w| self.window_stats(*w)).collect(),
+            agents,
+            connection_pool: ConnectionPoolStats::default(),
+            proof_pool: ProofPoolStats::default(),
+        }
+    }
+
+    /// Clears all metrics (useful for testing)
+    pub fn reset(&self) {
+        self.totals.reset();
+        if let Ok(mut agents) = self.agents.lock() {
+            agents.clear();
+        }
+        for bucket in self.recent.iter() {
+            bucket.second.store(0, Ordering::Release);
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Note: Global metrics removed - each client instance has its own metrics
+// This prevents cross-client metric pollution
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_metrics_new() {
+        let metrics = Metrics::new();
+        let stats = metrics.get_stats();
+        assert_eq!(stats["total_transactions"], 0.0);
+        assert_eq!(stats["successful_transactions"], 0.0);
+        assert_eq!(stats["failed_transactions"], 0.0);
+    }
+
+    #[test]
+    fn test_metrics_record_success() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(true, 0.05, Duration::from_millis(100));
+        let stats = metrics.get_stats();
+        assert_eq!(stats["total_transactions"], 1.0);
+        assert_eq!(stats["successful_transactions"], 1.0);
+        assert_eq!(stats["failed_transactions"], 0.0);
+        assert_eq!(stats["total_fee_paid"], 0.05);
+        assert_eq!(stats["success_rate"], 1.0);
+    }
+
+    #[test]
+    fn test_metrics_record_failure() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(false, 0.0, Duration::from_millis(50));
+        let stats = metrics.get_stats();
+        assert_eq!(stats["total_transactions"], 1.0);
+        assert_eq!(stats["successful_transactions"], 0.0);
+        assert_eq!(stats["failed_transactions"], 1.0);
+        assert_eq!(stats["success_rate"], 0.0);
+    }
+
+    #[test]
+    fn test_metrics_multiple_transactions() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(true, 0.05, Duration::from_millis(100));
+        metrics.record_transaction(true, 0.03, Duration::from_millis(80));
+        metrics.record_transaction(false, 0.0, Duration::from_millis(50));
+        
+        let stats = metrics.get_stats();
+        assert_eq!(stats["total_transactions"], 3.0);
+        assert_eq!(stats["successful_transactions"], 2.0);
+        assert_eq!(stats["failed_transactions"], 1.0);
+        assert_eq!(stats["total_fee_paid"], 0.08);
+        assert!((stats["success_rate"] - 2.0 / 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_metrics_reset() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(true, 0.05, Duration::from_millis(100));
+        metrics.reset();
+        let stats = metrics.get_stats();
+        assert_eq!(stats["total_transactions"], 0.0);
+        assert_eq!(stats["successful_transactions"], 0.0);
+    }
+
+    #[test]
+    fn test_metrics_average_latency() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(true, 0.05, Duration::from_millis(100));
+        metrics.record_transaction(true, 0.03, Duration::from_millis(200));
+        let stats = metrics.get_stats();
+        assert_eq!(stats["average_latency_ms"], 150.0);
+    }
+
+    #[test]
+    fn test_agent_stats() {
+        let metrics = Metrics::new();
+        metrics.record_quote("agent-001", Duration::from_millis(40));
+        metrics.record_quote("agent-001", Duration::from_millis(60));
+        metrics.record_quote("agent-002", Duration::from_millis(50));
+        metrics.record_selection("agent-001", Some(0.02));
+        metrics.record_selection("agent-001", Some(0.0));
+        metrics.record_execution("agent-001", true);
+        metrics.record_execution("agent-001", false);
+
+        let stats = metrics.get_agent_stats();
+        let agent = &stats["agent-001"];
+        assert_eq!(agent.quotes_received, 2);
+        assert_eq!(agent.average_quote_latency_ms, 50.0);
+        assert_eq!(agent.selections_won, 2);
+        assert_eq!(agent.execution_success_rate, 0.5);
+        assert!((agent.average_fee_delta - 0.01).abs() < 1e-9);
+
+        let idle = &stats["agent-002"];
+        assert_eq!(idle.selections_won, 0);
+        assert_eq!(idle.execution_success_rate, 0.0);
+
+        metrics.reset();
+        assert!(metrics.get_agent_stats().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_matches_stats_map() {
+        let metrics = Metrics::new();
+        metrics.record_transaction(true, 0.05, Duration::from_millis(100));
+        metrics.record_transaction(false, 0.0, Duration::from_millis(50));
+        metrics.record_quote("agent-002", Duration::from_millis(10));
+        metrics.record_quote("agent-001", Duration::from_millis(10));
+
+        let snapshot = metrics.snapshot();
+        assert!(snapshot.timestamp_ms > 0);
+        assert_eq!(snapshot.total_transactions, 2);
+        assert_eq!(snapshot.agents[0].agent_id, "agent-001");
+
+        let map = snapshot.to_map();
+        for (key, value) in metrics.get_stats() {
+            assert_eq!(map[&key], value, "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_sinks_receive_transactions() {
+        struct CountingSink(Mutex<Vec<(bool, f64)>>);
+        impl MetricsSink for CountingSink {
+            fn record_transaction(&self, success: bool, fee: f64, _latency: Duration) {
+                self.0.lock().unwrap().push((success, fee));
+            }
+        }
+
+        let sink = Arc::new(CountingSink(Mutex::new(Vec::new())));
+        let metrics = Metrics::new().with_sink(sink.clone());
+        metrics.record_transaction(true, 0.05, Duration::from_millis(100));
+        // Sinks without account breakdown still see attributed transactions
+        metrics.record_transaction_for(Some("acct-7"), false, 0.0, Duration::from_millis(50));
+        assert_eq!(*sink.0.lock().unwrap(), vec![(true, 0.05), (false, 0.0)]);
+    }
+
+    #[cfg(feature = "statsd")]
+    #[test]
+    fn test_statsd_sink_sends_datagram() {
+        let agent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        agent.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        let sink = StatsdSink::new(agent.local_addr().unwrap(), "ecash")
+            .unwrap()
+            .with_tag("env", "test");
+
+        sink.record_transaction(true, 0.05, Duration::from_millis(120));
+
+        let mut buf = [0u8; 1024];
+        let n = agent.recv(&mut buf).unwrap();
+        let packet = String::from_utf8_lossy(&buf[..n]);
+        let lines: Vec<&str> = packet.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "ecash.transactions:1|c|#env:test,status:success",
+                "ecash.latency_ms:120|ms|#env:test",
+                "ecash.fee_paid:0.05|h|#env:test",
+            ]
+        );
+
+        sink.record_account_transaction(Some("acct-7"), false, 0.0, Duration::from_millis(80));
+        let n = agent.recv(&mut buf).unwrap();
+        let packet = String::from_utf8_lossy(&buf[..n]);
+        assert_eq!(
+            packet.lines().collect::<Vec<_>>(),
+            vec![
+                "ecash.transactions:1|c|#env:test,status:failure,account:acct-7",
+                "ecash.latency_ms:80|ms|#env:test,account:acct-7",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concurrent_recording() {
+        let metrics = Metrics::new();
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let metrics = metrics.clone();
+                std::thread::spawn(move || {
+                    for i in 0..1000 {
+                        metrics.record_transaction(i % 4 != 0, 0.01, Duration::from_millis(2));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.total_transactions, 8000);
+        assert_eq!(snapshot.failed_transactions, 2000);
+        // Fixed-point accumulation doesn't drift like repeated f64 addition
+        assert_eq!(snapshot.total_fee_paid, 60.0);
+        assert_eq!(snapshot.average_latency_ms, 2.0);
+        assert_eq!(snapshot.windows[0].transactions, 8000);
+    }
+
+    #[test]
+    fn test_window_stats() {
+        let metrics = Metrics::new();
+        let now = 1_700_000_000;
+        // Two hours ago: outside every window; its slot is reused by `now`
+        metrics.record_recent(now - 7200, false, 0.0, 900);
+        // Ten minutes ago: only in the 1h window
+        metrics.record_recent(now - 600, false, 0.0, 400);
+        // Two minutes ago: in the 5m and 1h windows
+        metrics.record_recent(now - 120, true, 0.02, 200);
+        // Now: in every window
+        metrics.record_recent(now, true, 0.05, 100);
+        metrics.record_recent(now, true, 0.03, 100);
+
+        let one_minute = metrics.window_stats_at(MetricsWindow::OneMinute, now);
+        assert_eq!(one_minute.transactions, 2);
+        assert_eq!(one_minute.success_rate, 1.0);
+        assert_eq!(one_minute.average_latency_ms, 100.0);
+        assert!((one_minute.fee_paid - 0.08).abs() < 1e-9);
+
+        let five_minutes = metrics.window_stats_at(MetricsWindow::FiveMinutes, now);
+        assert_eq!(five_minutes.transactions, 3);
+
+        let one_hour = metrics.window_stats_at(MetricsWindow::OneHour, now);
+        assert_eq!(one_hour.transactions, 4);
+        assert_eq!(one_hour.success_rate, 0.75);
+        assert_eq!(metrics.recent.iter().filter(|b| b.second.load(Ordering::Relaxed) != 0).count(), 3);
+
+        // Windows slide forward as time passes
+        assert_eq!(metrics.window_stats_at(MetricsWindow::OneMinute, now + 61).transactions, 0);
+    }
+}
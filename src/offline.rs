@@ -0,0 +1,171 @@
+//! Offline intent preparation.
+//!
+//! The execution pipeline can be split across two machines. Online,
+//! `EasyCashClient::fetch_quote_bundle` collects the agent quotes for a
+//! request into a `QuoteBundle`. In an air-gapped environment,
+//! `EasyCashClient::prepare` validates the request against the bundle,
+//! selects its route, generates its solvency proof and signs the result as a
+//! `PreparedIntent`. Back online, `EasyCashClient::submit` checks the
+//! preparer's signature and executes the intent on the prepared route,
+//! without requesting quotes again. Both types serialize to JSON to cross
+//! the gap.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use k256::ecdsa::VerifyingKey;
+use k256::PublicKey;
+use serde::{Deserialize, Serialize};
+
+use crate::agent::RouteQuote;
+use crate::crypto::hash::HashFunction;
+use crate::crypto::{self, SigningDomain, TransactionSigner};
+use crate::receipt;
+use crate::types::TransactionRequest;
+use crate::zk::SolvencyInput;
+
+/// Agent quotes for one request, fetched ahead of preparation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteBundle {
+    /// `receipt::intent_hash` of the quoted request
+    pub intent_hash: String,
+    pub quotes: Vec<RouteQuote>,
+    pub fetched_at_ms: u64,
+    /// Past this, the quotes are stale and the bundle can't be prepared
+    pub expires_at_ms: u64,
+}
+
+impl QuoteBundle {
+    /// True if the bundle was fetched for `req`
+    pub fn covers(&self, req: &TransactionRequest) -> bool {
+        self.intent_hash == receipt::intent_hash(req)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        now_ms() >= self.expires_at_ms
+    }
+}
+
+/// Solvency proof generated at preparation, with its statement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreparedProof {
+    pub input: SolvencyInput,
+    pub proof: String,
+}
+
+/// Request validated, routed and proven offline, ready for `submit`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparedIntent {
+    pub request: TransactionRequest,
+    /// Route selected from the quote bundle
+    pub route: RouteQuote,
+    /// Present for shielded requests when ZK proofs are enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<PreparedProof>,
+    pub prepared_at_ms: u64,
+    /// Expiry of the quote bundle the route was selected from
+    pub expires_at_ms: u64,
+    /// Compressed hex public key of the preparer
+    #[serde(default)]
+    pub public_key: String,
+    /// Hex-encoded ECDSA signature of the preparer (see `PreparedIntent::sign`)
+    #[serde(default)]
+    pub signature: String,
+}
+
+impl PreparedIntent {
+    /// Signs the intent as `signer`, setting `public_key` and `signature`
+    pub fn sign(mut self, signer: &TransactionSigner) -> Result<Self, String> {
+        self.public_key = crypto::public_key_to_hex(&PublicKey::from(&signer.verifying_key()));
+        self.signature = signer.sign_in_domain(SigningDomain::PreparedIntent, &self.signing_payload()?)?;
+        Ok(self)
+    }
+
+    /// Verifies the signature, returning the preparer's public key
+    pub fn verify(&self) -> Result<PublicKey, String> {
+        let key = crypto::public_key_from_hex(&self.public_key)?;
+        let verifying_key = VerifyingKey::from(&key);
+        let payload = self.signing_payload()?;
+        let valid = crypto::verify_signature_in_domain(
+            HashFunction::Sha256,
+            SigningDomain::PreparedIntent,
+            &verifying_key,
+            &payload,
+            &self.signature,
+        )?;
+        if !valid {
+            return Err(format!("prepared intent {} has an invalid signature", self.request.reference_id));
+        }
+        Ok(key)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        now_ms() >= self.expires_at_ms
+    }
+
+    /// Everything but the signature. Building a `serde_json::Value` sorts
+    /// the request's metadata, so the payload survives a round trip.
+    fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let payload = serde_json::json!({
+            "request": self.request,
+            "route": self.route,
+            "proof": self.proof,
+            "prepared_at_ms": self.prepared_at_ms,
+            "expires_at_ms": self.expires_at_ms,
+            "public_key": self.public_key,
+        });
+        serde_json::to_vec(&payload).map_err(|e| format!("failed to encode prepared intent: {}", e))
+    }
+}
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+    use k256::SecretKey;
+    use std::time::Duration;
+
+    #[test]
+    fn test_prepared_intent_round_trip() {
+        let signer = TransactionSigner::new(SecretKey::from_bytes(&[6u8; 32].into()).unwrap());
+        let mut request = TransactionRequest::new("offline_1", IntentType::Transfer, "25.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+        request.metadata.insert("invoice".to_string(), "inv_1".to_string());
+        request.metadata.insert("customer".to_string(), "cus_1".to_string());
+        let intent = PreparedIntent {
+            request,
+            route: RouteQuote {
+                agent_id: "agent_a".to_string(),
+                estimated_fee: "0.05 USDC".to_string(),
+                estimated_time: Duration::from_secs(30),
+                route: vec!["base".to_string()],
+                security_score: 0.9,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
+            },
+            proof: None,
+            prepared_at_ms: 1_700_000_000_000,
+            expires_at_ms: 1_700_000_060_000,
+            public_key: String::new(),
+            signature: String::new(),
+        }
+        .sign(&signer)
+        .unwrap();
+
+        let decoded: PreparedIntent = serde_json::from_str(&serde_json::to_string(&intent).unwrap()).unwrap();
+        assert_eq!(decoded.verify().unwrap(), PublicKey::from(&signer.verifying_key()));
+        assert!(decoded.is_expired());
+
+        let mut tampered = decoded;
+        tampered.route.agent_id = "agent_b".to_string();
+        assert!(tampered.verify().is_err());
+    }
+}
@@ -0,0 +1,450 @@
+//! Spending policy engine.
+//!
+//! Operators declare rules such as "max 50,000 USDC per 24h" or "no cross-chain
+//! transfers to Solana"; the client evaluates them before execution and records
+//! rolling usage in a pluggable store after each successful transaction.
+//! Amounts are compared as exact decimals, and a request whose amount can't be
+//! read is rejected by any policy that limits amounts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::amount::Amount;
+use crate::types::{ChainId, IntentType, TransactionRequest};
+
+/// A single policy rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// Caps the total amount moved within a rolling window (tracked per asset)
+    RollingLimit {
+        /// Asset the limit applies to (all assets if `None`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        asset: Option<String>,
+        /// Source chain the limit applies to (all chains if `None`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        chain: Option<ChainId>,
+        #[serde(rename = "max_amount")]
+        max_amount: Amount,
+        window: Duration,
+    },
+    /// Caps the amount of a single transaction
+    MaxPerTransaction {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        asset: Option<String>,
+        #[serde(rename = "max_amount")]
+        max_amount: Amount,
+    },
+    /// Forbids cross-chain transfers matching the given chains
+    DenyCrossChain {
+        #[serde(rename = "source_chain", default, skip_serializing_if = "Option::is_none")]
+        source_chain: Option<ChainId>,
+        #[serde(rename = "target_chain", default, skip_serializing_if = "Option::is_none")]
+        target_chain: Option<ChainId>,
+    },
+    /// Forbids an intent type entirely
+    DenyIntent {
+        #[serde(rename = "intent_type")]
+        intent_type: IntentType,
+    },
+}
+
+/// Named policy rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Policy {
+    pub name: String,
+    pub rule: PolicyRule,
+}
+
+impl Policy {
+    pub fn new(name: impl Into<String>, rule: PolicyRule) -> Self {
+        Self {
+            name: name.into(),
+            rule,
+        }
+    }
+}
+
+/// Details of a violated policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    /// Name of the violated policy
+    pub policy: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "policy '{}' violated: {}", self.policy, self.reason)
+    }
+}
+
+/// Storage for rolling usage used by `RollingLimit` policies.
+///
+/// Timestamps are milliseconds since the Unix epoch so implementations can
+/// persist them (e.g. in Redis) and share usage across instances.
+pub trait UsageStore: Send + Sync {
+    /// Records `amount` of usage for `key` at `timestamp_ms`
+    fn record(&self, key: &str, amount: Amount, timestamp_ms: u64) -> Result<(), String>;
+
+    /// Returns total usage for `key` recorded at or after `since_ms`
+    fn usage_since(&self, key: &str, since_ms: u64) -> Result<Amount, String>;
+}
+
+/// In-memory usage store (per process)
+#[derive(Default)]
+pub struct InMemoryUsageStore {
+    entries: Mutex<HashMap<String, VecDeque<(u64, Amount)>>>,
+    retention: Option<Duration>,
+}
+
+impl InMemoryUsageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops usage older than `retention` on every write
+    pub fn with_retention(retention: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            retention: Some(retention),
+        }
+    }
+}
+
+impl UsageStore for InMemoryUsageStore {
+    fn record(&self, key: &str, amount: Amount, timestamp_ms: u64) -> Result<(), String> {
+        let mut entries = self.entries.lock().map_err(|_| "usage store poisoned".to_string())?;
+        let series = entries.entry(key.to_string()).or_default();
+        series.push_back((timestamp_ms, amount));
+        if let Some(retention) = self.retention {
+            let cutoff = timestamp_ms.saturating_sub(retention.as_millis() as u64);
+            while series.front().map(|(t, _)| *t < cutoff).unwrap_or(false) {
+                series.pop_front();
+            }
+        }
+        Ok(())
+    }
+
+    fn usage_since(&self, key: &str, since_ms: u64) -> Result<Amount, String> {
+        let entries = self.entries.lock().map_err(|_| "usage store poisoned".to_string())?;
+        entries
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|(t, _)| *t >= since_ms)
+            .try_fold(Amount::default(), |total, (_, amount)| total.checked_add(amount))
+            .ok_or_else(|| format!("usage for {} overflows", key))
+    }
+}
+
+/// Evaluates policies against requests and tracks rolling usage.
+///
+/// Usage is recorded after a successful execution, so concurrent requests can
+/// briefly overshoot a rolling limit by up to the in-flight amount.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use ecash_sdk_core::policy::{Policy, PolicyEngine, PolicyRule};
+///
+/// let engine = PolicyEngine::new().with_policy(Policy::new(
+///     "usdc-daily",
+///     PolicyRule::RollingLimit {
+///         asset: Some("USDC".to_string()),
+///         chain: None,
+///         max_amount: "50000".parse().unwrap(),
+///         window: Duration::from_secs(24 * 3600),
+///     },
+/// ));
+/// assert_eq!(engine.policies().len(), 1);
+/// ```
+pub struct PolicyEngine {
+    policies: Vec<Policy>,
+    store: Arc<dyn UsageStore>,
+}
+
+impl Default for PolicyEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PolicyEngine {
+    /// Creates an engine with no policies and an in-memory usage store
+    pub fn new() -> Self {
+        Self {
+            policies: Vec::new(),
+            store: Arc::new(InMemoryUsageStore::new()),
+        }
+    }
+
+    /// Uses a custom usage store (e.g. shared across instances)
+    pub fn with_store(mut self, store: Arc<dyn UsageStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Adds a policy
+    pub fn with_policy(mut self, policy: Policy) -> Self {
+        self.policies.push(policy);
+        self
+    }
+
+    /// Returns the configured policies
+    pub fn policies(&self) -> &[Policy] {
+        &self.policies
+    }
+
+    /// Checks a request against every policy, returning the first violation
+    pub fn evaluate(&self, req: &TransactionRequest) -> Result<(), PolicyViolation> {
+        // Only an error once a policy needs the amount
+        let amount = parse_amount(req);
+        let now = now_ms();
+
+        for policy in &self.policies {
+            let violation = |reason: String| PolicyViolation {
+                policy: policy.name.clone(),
+                reason,
+            };
+
+            match &policy.rule {
+                PolicyRule::RollingLimit {
+                    asset,
+                    chain,
+                    max_amount,
+                    window,
+                } => {
+                    if !matches_asset(asset, req) || !matches_chain(chain, req.source_chain) {
+                        continue;
+                    }
+                    let amount = amount.clone().map_err(violation)?;
+                    let since = now.saturating_sub(window.as_millis() as u64);
+                    let used = self
+                        .store
+                        .usage_since(&usage_key(&policy.name, req), since)
+                        .map_err(|e| violation(format!("usage store unavailable: {}", e)))?;
+                    if used.checked_add(&amount).is_none_or(|total| total > *max_amount) {
+                        return Err(violation(format!(
+                            "amount {} would exceed limit of {} per {:?} ({} already used)",
+                            req.amount, max_amount, window, used
+                        )));
+                    }
+                }
+                PolicyRule::MaxPerTransaction { asset, max_amount } => {
+                    if !matches_asset(asset, req) {
+                        continue;
+                    }
+                    if amount.clone().map_err(violation)? > *max_amount {
+                        return Err(violation(format!(
+                            "amount {} exceeds per-transaction maximum of {}",
+                            req.amount, max_amount
+                        )));
+                    }
+                }
+                PolicyRule::DenyCrossChain {
+                    source_chain,
+                    target_chain,
+                } => {
+                    let Some(target) = req.target_chain.filter(|t| *t != req.source_chain) else {
+                        continue;
+                    };
+                    if matches_chain(source_chain, req.source_chain) && matches_chain(target_chain, target) {
+                        return Err(violation(format!(
+                            "cross-chain transfers from {} to {} are not allowed",
+                            req.source_chain, target
+                        )));
+                    }
+                }
+                PolicyRule::DenyIntent { intent_type } => {
+                    if req.intent_type == *intent_type {
+                        return Err(violation(format!("{} intents are not allowed", intent_type)));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records usage of an executed request against all matching rolling limits
+    pub fn record_usage(&self, req: &TransactionRequest) -> Result<(), String> {
+        let now = now_ms();
+        for policy in &self.policies {
+            if let PolicyRule::RollingLimit { asset, chain, .. } = &policy.rule {
+                if matches_asset(asset, req) && matches_chain(chain, req.source_chain) {
+                    self.store.record(&usage_key(&policy.name, req), parse_amount(req)?, now)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_amount(req: &TransactionRequest) -> Result<Amount, String> {
+    req.amount
+        .parse()
+        .map_err(|e| format!("amount can't be checked against the limit: {}", e))
+}
+
+fn matches_asset(asset: &Option<String>, req: &TransactionRequest) -> bool {
+    asset.as_ref().map(|a| a.eq_ignore_ascii_case(&req.asset)).unwrap_or(true)
+}
+
+fn matches_chain(expected: &Option<ChainId>, chain: ChainId) -> bool {
+    expected.map(|c| c == chain).unwrap_or(true)
+}
+
+fn usage_key(policy: &str, req: &TransactionRequest) -> String {
+    format!("{}:{}", policy, req.asset.to_uppercase())
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(amount: &str, asset: &str, target_chain: Option<ChainId>) -> TransactionRequest {
+        TransactionRequest {
+            target_chain,
+            ..TransactionRequest::new("ref_001", IntentType::Transfer, amount, asset, ChainId::Base)
+        }
+    }
+
+    fn daily_limit(max_amount: &str) -> Policy {
+        Policy::new(
+            "usdc-daily",
+            PolicyRule::RollingLimit {
+                asset: Some("USDC".to_string()),
+                chain: None,
+                max_amount: max_amount.parse().unwrap(),
+                window: Duration::from_secs(24 * 3600),
+            },
+        )
+    }
+
+    #[test]
+    fn test_rolling_limit_accumulates_usage() {
+        let engine = PolicyEngine::new().with_policy(daily_limit("1000"));
+        let req = request("600", "USDC", None);
+
+        assert!(engine.evaluate(&req).is_ok());
+        engine.record_usage(&req).unwrap();
+
+        let violation = engine.evaluate(&req).unwrap_err();
+        assert_eq!(violation.policy, "usdc-daily");
+        assert!(violation.reason.contains("600 already used"));
+    }
+
+    #[test]
+    fn test_rolling_limit_ignores_other_assets() {
+        let engine = PolicyEngine::new().with_policy(daily_limit("100"));
+        assert!(engine.evaluate(&request("5000", "USDT", None)).is_ok());
+    }
+
+    #[test]
+    fn test_max_per_transaction() {
+        let engine = PolicyEngine::new().with_policy(Policy::new(
+            "single-cap",
+            PolicyRule::MaxPerTransaction {
+                asset: None,
+                max_amount: "100".parse().unwrap(),
+            },
+        ));
+        assert!(engine.evaluate(&request("100", "USDC", None)).is_ok());
+        assert!(engine.evaluate(&request("100.01", "USDC", None)).is_err());
+        // Beyond f64 precision
+        assert!(engine.evaluate(&request("100.0000000000000001", "USDC", None)).is_err());
+    }
+
+    #[test]
+    fn test_unreadable_amount_fails_closed() {
+        let engine = PolicyEngine::new().with_policy(daily_limit("1000"));
+        for amount in ["", "abc", "1e9", "-5"] {
+            let violation = engine.evaluate(&request(amount, "USDC", None)).unwrap_err();
+            assert_eq!(violation.policy, "usdc-daily");
+            assert!(violation.reason.contains("can't be checked"));
+        }
+        assert!(engine.record_usage(&request("abc", "USDC", None)).is_err());
+
+        // Policies that don't limit amounts don't need one
+        let engine = PolicyEngine::new().with_policy(Policy::new(
+            "no-swaps",
+            PolicyRule::DenyIntent {
+                intent_type: IntentType::Swap,
+            },
+        ));
+        assert!(engine.evaluate(&request("abc", "USDC", None)).is_ok());
+    }
+
+    #[test]
+    fn test_rolling_limit_is_exact() {
+        // 0.1 + 0.2 > 0.3 in f64
+        let engine = PolicyEngine::new().with_policy(daily_limit("0.3"));
+        engine.record_usage(&request("0.1", "USDC", None)).unwrap();
+        assert!(engine.evaluate(&request("0.2", "USDC", None)).is_ok());
+        engine.record_usage(&request("0.2", "USDC", None)).unwrap();
+        assert!(engine.evaluate(&request("0.000001", "USDC", None)).is_err());
+    }
+
+    #[test]
+    fn test_deny_cross_chain_to_target() {
+        let engine = PolicyEngine::new().with_policy(Policy::new(
+            "no-solana-bridging",
+            PolicyRule::DenyCrossChain {
+                source_chain: None,
+                target_chain: Some(ChainId::Solana),
+            },
+        ));
+        assert!(engine.evaluate(&request("10", "USDC", Some(ChainId::Solana))).is_err());
+        assert!(engine.evaluate(&request("10", "USDC", Some(ChainId::Ethereum))).is_ok());
+        assert!(engine.evaluate(&request("10", "USDC", None)).is_ok());
+    }
+
+    #[test]
+    fn test_deny_intent() {
+        let engine = PolicyEngine::new().with_policy(Policy::new(
+            "no-swaps",
+            PolicyRule::DenyIntent {
+                intent_type: IntentType::Swap,
+            },
+        ));
+        let mut req = request("10", "USDC", None);
+        assert!(engine.evaluate(&req).is_ok());
+        req.intent_type = IntentType::Swap;
+        assert!(engine.evaluate(&req).is_err());
+    }
+
+    #[test]
+    fn test_in_memory_store_window() {
+        let amount = |s: &str| s.parse::<Amount>().unwrap();
+        let store = InMemoryUsageStore::with_retention(Duration::from_secs(60));
+        store.record("k", amount("10"), 1_000).unwrap();
+        store.record("k", amount("5"), 50_000).unwrap();
+        assert_eq!(store.usage_since("k", 0).unwrap(), amount("15"));
+        assert_eq!(store.usage_since("k", 2_000).unwrap(), amount("5"));
+        assert!(store.usage_since("other", 0).unwrap().is_zero());
+
+        // Entry at t=1s falls outside the retention window of the t=70s write
+        store.record("k", amount("1"), 70_000).unwrap();
+        assert_eq!(store.usage_since("k", 0).unwrap(), amount("6"));
+    }
+
+    #[test]
+    fn test_policy_serialization() {
+        let policy = daily_limit("50000");
+        let json = serde_json::to_string(&policy).unwrap();
+        assert!(json.contains("\"type\":\"rolling_limit\""));
+        assert!(json.contains("\"max_amount\":\"50000\""));
+        let decoded: Policy = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, policy);
+    }
+}
@@ -0,0 +1,169 @@
+//! Asset prices used to compare fees quoted in different assets.
+//!
+//! Fees are normalized to USD so that "0.03 USDC" and "0.0004 ETH" can be
+//! ranked against each other and summed in metrics.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Source of USD prices for assets
+#[async_trait::async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Returns the USD price of one unit of `asset`
+    async fn usd_price(&self, asset: &str) -> Result<f64, String>;
+}
+
+/// Converts a fee string such as "0.0004 ETH" to USD
+pub async fn normalize_fee(oracle: &dyn PriceOracle, fee: &str) -> Result<f64, String> {
+    let mut parts = fee.split_whitespace();
+    let amount = parts
+        .next()
+        .and_then(|s| s.parse::<f64>().ok())
+        .ok_or_else(|| format!("invalid fee amount: {}", fee))?;
+    let asset = parts.next().ok_or_else(|| format!("fee has no asset: {}", fee))?;
+    Ok(amount * oracle.usd_price(asset).await?)
+}
+
+/// Fixed prices, useful for tests and for pegged assets
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::pricing::{normalize_fee, StaticPriceOracle};
+///
+/// # tokio_test::block_on(async {
+/// let oracle = StaticPriceOracle::new().with_price("ETH", 3000.0);
+/// assert_eq!(normalize_fee(&oracle, "0.001 ETH").await.unwrap(), 3.0);
+/// # });
+/// ```
+#[derive(Default)]
+pub struct StaticPriceOracle {
+    prices: RwLock<HashMap<String, f64>>,
+}
+
+impl StaticPriceOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a price (builder style)
+    pub fn with_price(self, asset: &str, usd_price: f64) -> Self {
+        self.set_price(asset, usd_price);
+        self
+    }
+
+    /// Sets or updates the USD price of an asset
+    pub fn set_price(&self, asset: &str, usd_price: f64) {
+        if let Ok(mut prices) = self.prices.write() {
+            prices.insert(asset.to_uppercase(), usd_price);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceOracle for StaticPriceOracle {
+    async fn usd_price(&self, asset: &str) -> Result<f64, String> {
+        self.prices
+            .read()
+            .map_err(|_| "price oracle poisoned".to_string())?
+            .get(&asset.to_uppercase())
+            .copied()
+            .ok_or_else(|| format!("no price for {}", asset))
+    }
+}
+
+/// Price oracle backed by an HTTP price service.
+///
+/// Sends `GET {endpoint}?asset=ETH` and expects `{"usd": 3000.12}`. Prices
+/// are cached for `cache_ttl` to keep route selection off the network.
+#[cfg(feature = "price-http")]
+pub struct HttpPriceOracle {
+    endpoint: String,
+    timeout: std::time::Duration,
+    cache: crate::cache::Cache<f64>,
+    http: crate::http::HttpClient,
+}
+
+#[cfg(feature = "price-http")]
+impl HttpPriceOracle {
+    /// Creates an oracle querying `endpoint`
+    pub fn new(endpoint: impl Into<String>, timeout: std::time::Duration, cache_ttl: std::time::Duration) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            timeout,
+            cache: crate::cache::Cache::new(cache_ttl),
+            http: crate::http::HttpClient::default(),
+        }
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: crate::http::HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[cfg(feature = "price-http")]
+#[async_trait::async_trait]
+impl PriceOracle for HttpPriceOracle {
+    async fn usd_price(&self, asset: &str) -> Result<f64, String> {
+        let asset = asset.to_uppercase();
+        if let Some(price) = self.cache.get(&asset) {
+            return Ok(price);
+        }
+
+        let url = format!("{}?asset={}", self.endpoint, asset);
+        let resp = self.http.get(&url, &[], self.timeout).await?;
+        if !resp.is_success() {
+            return Err(format!("price service returned status {}", resp.status));
+        }
+        let body: serde_json::Value =
+            serde_json::from_str(&resp.body).map_err(|e| format!("invalid price response: {}", e))?;
+        let price = body["usd"]
+            .as_f64()
+            .ok_or_else(|| format!("price response has no usd price for {}", asset))?;
+        self.cache.set(asset, price);
+        Ok(price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_normalize_fee() {
+        let oracle = StaticPriceOracle::new().with_price("ETH", 2500.0).with_price("usdc", 1.0);
+        assert_eq!(normalize_fee(&oracle, "0.0004 ETH").await.unwrap(), 1.0);
+        assert_eq!(normalize_fee(&oracle, "0.03 USDC").await.unwrap(), 0.03);
+        assert!(normalize_fee(&oracle, "0.1 DOGE").await.is_err());
+        assert!(normalize_fee(&oracle, "0.1").await.is_err());
+    }
+
+    #[cfg(feature = "price-http")]
+    #[tokio::test]
+    async fn test_http_oracle_caches_prices() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            // Serves exactly one request; a second request would hang and time out
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            assert!(String::from_utf8_lossy(&buf[..n]).starts_with("GET /price?asset=ETH "));
+            let body = r#"{"usd":3100.5}"#;
+            let resp = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}", body.len(), body);
+            socket.write_all(resp.as_bytes()).await.unwrap();
+        });
+
+        let oracle = HttpPriceOracle::new(
+            format!("http://{}/price", addr),
+            std::time::Duration::from_secs(1),
+            std::time::Duration::from_secs(60),
+        );
+        assert_eq!(oracle.usd_price("eth").await.unwrap(), 3100.5);
+        assert_eq!(oracle.usd_price("ETH").await.unwrap(), 3100.5);
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,184 @@
+//! Intent protocol versions.
+//!
+//! Intents and responses exchanged with agents carry a `protocol_version`.
+//! The client asks the agent network which versions it speaks, uses the
+//! highest version both sides support, and downconverts requests to it.
+//! When the network only speaks versions newer than this SDK, requests fail
+//! with `UNSUPPORTED_PROTOCOL` instead of being misread.
+//!
+//! | Version | Change |
+//! |---------|--------|
+//! | 1 | Initial intent format |
+//! | 2 | `amount_base_units` |
+//! | 3 | `disbursements`, `fee_splits`, `escrow`, `fee_payer` |
+//!
+//! ```
+//! use ecash_sdk_core::protocol::{self, Versioned};
+//! use ecash_sdk_core::TransactionResponse;
+//!
+//! assert_eq!(protocol::negotiate(&[3, 4]), Ok(3));
+//! assert!(protocol::negotiate(&[4]).is_err());
+//!
+//! let json = r#"{"protocol_version": 2, "tx_hash": "0xabc", "status": "confirmed",
+//!     "block_height": 1, "fee_used": "0.05 USDC"}"#;
+//! let resp: Versioned<TransactionResponse> = protocol::decode(json.as_bytes()).unwrap();
+//! assert_eq!(resp.body.tx_hash, "0xabc");
+//! ```
+
+use std::borrow::Cow;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::types::TransactionRequest;
+
+/// Version this SDK writes by default
+pub const CURRENT_VERSION: u32 = 3;
+
+const SUPPORTED_VERSIONS: [u32; 3] = [1, 2, 3];
+
+/// Protocol versions this SDK can read and write, oldest first
+pub fn supported_versions() -> &'static [u32] {
+    &SUPPORTED_VERSIONS
+}
+
+/// Highest version supported by both this SDK and the remote side
+pub fn negotiate(remote: &[u32]) -> Result<u32, String> {
+    remote
+        .iter()
+        .copied()
+        .filter(|v| SUPPORTED_VERSIONS.contains(v))
+        .max()
+        .ok_or_else(|| {
+            format!(
+                "agents speak protocol versions {:?}, this SDK supports {:?}",
+                remote, SUPPORTED_VERSIONS
+            )
+        })
+}
+
+/// `req` in the format of protocol `version` (fields newer than it dropped).
+/// Attribution (`account_id`, `metadata`) is local to the caller and dropped
+/// in every version.
+pub fn downconvert(req: &TransactionRequest, version: u32) -> Cow<'_, TransactionRequest> {
+    let drop_base_units = version < 2 && req.amount_base_units.is_some();
+    let drop_payees = version < 3
+        && (!req.disbursements.is_empty()
+            || !req.fee_splits.is_empty()
+            || req.escrow.is_some()
+            || req.fee_payer.is_some());
+    let has_attribution = req.account_id.is_some() || !req.metadata.is_empty();
+    if !drop_base_units && !drop_payees && !has_attribution {
+        return Cow::Borrowed(req);
+    }
+    let mut req = req.clone();
+    if drop_base_units {
+        req.amount_base_units = None;
+    }
+    if drop_payees {
+        req.disbursements.clear();
+        req.fee_splits.clear();
+        req.escrow = None;
+        req.fee_payer = None;
+    }
+    req.account_id = None;
+    req.metadata.clear();
+    Cow::Owned(req)
+}
+
+/// A payload tagged with the protocol version it is written in. Payloads
+/// without a version are version 1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    #[serde(default = "initial_version")]
+    pub protocol_version: u32,
+    #[serde(flatten)]
+    pub body: T,
+}
+
+fn initial_version() -> u32 {
+    1
+}
+
+impl<T> Versioned<T> {
+    /// `body` tagged with `CURRENT_VERSION`
+    pub fn new(body: T) -> Self {
+        Self {
+            protocol_version: CURRENT_VERSION,
+            body,
+        }
+    }
+}
+
+/// Wraps `req` for sending in protocol `version`
+pub fn encode_request(req: &TransactionRequest, version: u32) -> Versioned<Cow<'_, TransactionRequest>> {
+    Versioned {
+        protocol_version: version,
+        body: downconvert(req, version),
+    }
+}
+
+/// Parses a versioned JSON payload, refusing versions this SDK doesn't support
+pub fn decode<T: DeserializeOwned>(json: &[u8]) -> Result<Versioned<T>, String> {
+    #[derive(Deserialize)]
+    struct Version {
+        #[serde(default = "initial_version")]
+        protocol_version: u32,
+    }
+    let version: Version = serde_json::from_slice(json).map_err(|e| format!("invalid payload: {}", e))?;
+    if !SUPPORTED_VERSIONS.contains(&version.protocol_version) {
+        return Err(format!(
+            "payload uses protocol version {}, this SDK supports {:?}",
+            version.protocol_version, SUPPORTED_VERSIONS
+        ));
+    }
+    serde_json::from_slice(json).map_err(|e| format!("invalid payload: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+
+    fn request() -> TransactionRequest {
+        TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_amount_base_units("1000000000")
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(&[1, 2, 3, 4]), Ok(3));
+        assert_eq!(negotiate(&[1]), Ok(1));
+        assert!(negotiate(&[4, 5]).unwrap_err().contains("[4, 5]"));
+        assert!(negotiate(&[]).is_err());
+    }
+
+    #[test]
+    fn test_encode_and_decode() {
+        let v1 = serde_json::to_value(encode_request(&request(), 1)).unwrap();
+        assert_eq!(v1["protocol_version"], 1);
+        assert!(v1.get("amount_base_units").is_none());
+        let v2 = serde_json::to_vec(&encode_request(&request(), 2)).unwrap();
+        let decoded: Versioned<TransactionRequest> = decode(&v2).unwrap();
+        assert_eq!(decoded.protocol_version, 2);
+        assert_eq!(decoded.body.amount_base_units.as_deref(), Some("1000000000"));
+
+        let unversioned = serde_json::to_vec(&request()).unwrap();
+        assert_eq!(decode::<TransactionRequest>(&unversioned).unwrap().protocol_version, 1);
+        let newer = br#"{"protocol_version": 4, "reference_id": "ref_001"}"#;
+        assert!(decode::<TransactionRequest>(newer).unwrap_err().contains("protocol version 4"));
+    }
+
+    #[test]
+    fn test_attribution_is_not_sent() {
+        let mut req = request();
+        assert!(matches!(downconvert(&req, 2), Cow::Borrowed(_)));
+        req.account_id = Some("acct-7".to_string());
+        req.metadata.insert("desk".to_string(), "otc".to_string());
+        for version in [1, 2, 3] {
+            let wire = serde_json::to_value(encode_request(&req, version)).unwrap();
+            assert!(wire.get("account_id").is_none() && wire.get("metadata").is_none());
+        }
+        assert_eq!(downconvert(&req, 2).amount_base_units, req.amount_base_units);
+    }
+}
@@ -0,0 +1,475 @@
+//! Scheduled transaction queue.
+//!
+//! Transactions are enqueued with a priority and an optional earliest
+//! execution time and drained by a `QueueWorkerPool`. The pool runs at most
+//! one execution per worker, so the worker count is the queue's concurrency
+//! cap, and an optional `RateLimiter` paces executions. Queued items are
+//! written to a `QueueStore`; with `FileQueueStore` they survive restarts.
+//!
+//! Items are removed from the store only after execution finishes, so an item
+//! interrupted by a crash is executed again on restart.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch, Notify};
+use tokio::task::JoinHandle;
+
+use crate::client::EasyCashClient;
+use crate::errors::Result as SdkResult;
+use crate::rate_limiter::RateLimiter;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+/// A transaction waiting in the queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedTransaction {
+    pub id: String,
+    pub request: TransactionRequest,
+    /// Higher priorities run first
+    pub priority: i32,
+    /// Earliest execution time (ms since the Unix epoch); 0 for immediately
+    pub not_before_ms: u64,
+    /// Insertion order, used to keep equal priorities FIFO
+    pub sequence: u64,
+}
+
+impl QueuedTransaction {
+    /// Queues `request` at priority 0 for immediate execution
+    pub fn new(request: TransactionRequest) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            request,
+            priority: 0,
+            not_before_ms: 0,
+            sequence: 0,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Delays execution until `time`
+    pub fn not_before(mut self, time: SystemTime) -> Self {
+        self.not_before_ms = time.duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+        self
+    }
+
+    fn runs_before(&self, other: &Self) -> bool {
+        (other.priority, self.sequence) < (self.priority, other.sequence)
+    }
+}
+
+/// Persistence for queued transactions
+pub trait QueueStore: Send + Sync {
+    /// Stores or replaces an item
+    fn save(&self, item: &QueuedTransaction) -> Result<(), String>;
+
+    /// Removes an item once it has been executed
+    fn remove(&self, id: &str) -> Result<(), String>;
+
+    /// Returns every stored item
+    fn load(&self) -> Result<Vec<QueuedTransaction>, String>;
+}
+
+/// In-memory queue store (per process)
+#[derive(Default)]
+pub struct InMemoryQueueStore {
+    items: Mutex<BTreeMap<String, QueuedTransaction>>,
+}
+
+impl InMemoryQueueStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl QueueStore for InMemoryQueueStore {
+    fn save(&self, item: &QueuedTransaction) -> Result<(), String> {
+        let mut items = self.items.lock().map_err(|_| "queue store poisoned".to_string())?;
+        items.insert(item.id.clone(), item.clone());
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) -> Result<(), String> {
+        let mut items = self.items.lock().map_err(|_| "queue store poisoned".to_string())?;
+        items.remove(id);
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<QueuedTransaction>, String> {
+        let items = self.items.lock().map_err(|_| "queue store poisoned".to_string())?;
+        Ok(items.values().cloned().collect())
+    }
+}
+
+/// Queue store kept in a JSON file, rewritten (via a temporary file and
+/// rename) on every change
+pub struct FileQueueStore {
+    path: PathBuf,
+    items: Mutex<BTreeMap<String, QueuedTransaction>>,
+}
+
+impl FileQueueStore {
+    /// Opens the store, loading any items already in `path`
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let items = match std::fs::read_to_string(&path) {
+            Ok(json) => {
+                let items: Vec<QueuedTransaction> = serde_json::from_str(&json)
+                    .map_err(|e| format!("corrupt queue file {}: {}", path.display(), e))?;
+                items.into_iter().map(|i| (i.id.clone(), i)).collect()
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("failed to read {}: {}", path.display(), e)),
+        };
+        Ok(Self {
+            path,
+            items: Mutex::new(items),
+        })
+    }
+
+    fn update(&self, f: impl FnOnce(&mut BTreeMap<String, QueuedTransaction>)) -> Result<(), String> {
+        let mut items = self.items.lock().map_err(|_| "queue store poisoned".to_string())?;
+        f(&mut items);
+        let json = serde_json::to_string(&items.values().collect::<Vec<_>>()).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| format!("failed to replace {}: {}", self.path.display(), e))
+    }
+}
+
+impl QueueStore for FileQueueStore {
+    fn save(&self, item: &QueuedTransaction) -> Result<(), String> {
+        self.update(|items| {
+            items.insert(item.id.clone(), item.clone());
+        })
+    }
+
+    fn remove(&self, id: &str) -> Result<(), String> {
+        self.update(|items| {
+            items.remove(id);
+        })
+    }
+
+    fn load(&self) -> Result<Vec<QueuedTransaction>, String> {
+        let items = self.items.lock().map_err(|_| "queue store poisoned".to_string())?;
+        Ok(items.values().cloned().collect())
+    }
+}
+
+/// Priority queue of scheduled transactions
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::queue::{QueuedTransaction, TransactionQueue};
+/// use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+///
+/// let queue = TransactionQueue::new();
+/// let req = TransactionRequest::new("withdrawal_001", IntentType::Transfer, "100.00", "USDC", ChainId::Base);
+/// queue.enqueue(QueuedTransaction::new(req).with_priority(10)).unwrap();
+/// assert_eq!(queue.len(), 1);
+/// ```
+pub struct TransactionQueue {
+    /// Items not yet handed to a worker
+    ready: Mutex<Vec<QueuedTransaction>>,
+    store: Arc<dyn QueueStore>,
+    sequence: AtomicU64,
+    notify: Notify,
+}
+
+impl Default for TransactionQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionQueue {
+    /// Creates an empty queue backed by an in-memory store
+    pub fn new() -> Self {
+        Self {
+            ready: Mutex::new(Vec::new()),
+            store: Arc::new(InMemoryQueueStore::new()),
+            sequence: AtomicU64::new(0),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Creates a queue backed by `store`, restoring the items it holds
+    pub fn with_store(store: Arc<dyn QueueStore>) -> Result<Self, String> {
+        let items = store.load()?;
+        let next = items.iter().map(|i| i.sequence + 1).max().unwrap_or(0);
+        Ok(Self {
+            ready: Mutex::new(items),
+            store,
+            sequence: AtomicU64::new(next),
+            notify: Notify::new(),
+        })
+    }
+
+    /// Adds a transaction and returns its queue ID
+    pub fn enqueue(&self, mut item: QueuedTransaction) -> Result<String, String> {
+        item.sequence = self.sequence.fetch_add(1, Ordering::SeqCst);
+        self.store.save(&item)?;
+        let id = item.id.clone();
+        self.lock()?.push(item);
+        self.notify.notify_one();
+        Ok(id)
+    }
+
+    /// Removes a transaction that has not started executing
+    pub fn cancel(&self, id: &str) -> Result<bool, String> {
+        let mut ready = self.lock()?;
+        let Some(pos) = ready.iter().position(|i| i.id == id) else { return Ok(false) };
+        self.store.remove(id)?;
+        ready.remove(pos);
+        Ok(true)
+    }
+
+    /// Number of transactions waiting to start
+    pub fn len(&self) -> usize {
+        self.lock().map(|r| r.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Takes the highest-priority transaction that is due at `now_ms`
+    pub fn pop_ready(&self, now_ms: u64) -> Option<QueuedTransaction> {
+        let mut ready = self.lock().ok()?;
+        let mut best: Option<usize> = None;
+        for (i, item) in ready.iter().enumerate() {
+            if item.not_before_ms <= now_ms && best.is_none_or(|b| item.runs_before(&ready[b])) {
+                best = Some(i);
+            }
+        }
+        best.map(|i| ready.remove(i))
+    }
+
+    /// Earliest `not_before_ms` of the waiting transactions
+    fn next_due_ms(&self) -> Option<u64> {
+        self.lock().ok()?.iter().map(|i| i.not_before_ms).min()
+    }
+
+    /// Marks a popped transaction as executed
+    fn complete(&self, id: &str) {
+        if let Err(e) = self.store.remove(id) {
+            tracing::warn!("[SDK] Failed to remove {} from queue store: {}", id, e);
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Vec<QueuedTransaction>>, String> {
+        self.ready.lock().map_err(|_| "queue poisoned".to_string())
+    }
+}
+
+/// Result of executing a queued transaction
+#[derive(Debug)]
+pub struct QueueOutcome {
+    pub item: QueuedTransaction,
+    pub result: SdkResult<TransactionResponse>,
+}
+
+/// Workers draining a `TransactionQueue` through an `EasyCashClient`
+pub struct QueueWorkerPool {
+    queue: Arc<TransactionQueue>,
+    client: Arc<EasyCashClient>,
+    workers: usize,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    poll_interval: Duration,
+}
+
+impl QueueWorkerPool {
+    /// Creates a pool with 4 workers and no rate limit
+    pub fn new(queue: Arc<TransactionQueue>, client: Arc<EasyCashClient>) -> Self {
+        Self {
+            queue,
+            client,
+            workers: 4,
+            rate_limiter: None,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Sets the number of workers, i.e. the maximum concurrent executions
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers.max(1);
+        self
+    }
+
+    /// Waits for the limiter before each execution
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// How long idle workers and rate-limited workers wait before re-checking
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Starts the workers; outcomes are delivered on the returned receiver
+    pub fn start(self) -> (QueueWorkerHandle, mpsc::UnboundedReceiver<QueueOutcome>) {
+        let (outcomes, rx) = mpsc::unbounded_channel();
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let pool = Arc::new(self);
+        let tasks = (0..pool.workers)
+            .map(|_| {
+                let pool = pool.clone();
+                let outcomes = outcomes.clone();
+                let shutdown_rx = shutdown_rx.clone();
+                tokio::spawn(async move { pool.run_worker(outcomes, shutdown_rx).await })
+            })
+            .collect();
+        (QueueWorkerHandle { shutdown, tasks }, rx)
+    }
+
+    async fn run_worker(&self, outcomes: mpsc::UnboundedSender<QueueOutcome>, mut shutdown: watch::Receiver<bool>) {
+        while !*shutdown.borrow() {
+            let Some(item) = self.queue.pop_ready(now_ms()) else {
+                let wait = self
+                    .queue
+                    .next_due_ms()
+                    .map(|due| Duration::from_millis(due.saturating_sub(now_ms())))
+                    .map_or(self.poll_interval, |d| d.min(self.poll_interval));
+                tokio::select! {
+                    _ = self.queue.notify.notified() => {}
+                    _ = tokio::time::sleep(wait) => {}
+                    _ = shutdown.changed() => {}
+                }
+                continue;
+            };
+
+            if let Some(ref limiter) = self.rate_limiter {
+                while limiter.check().await.is_err() {
+                    tokio::select! {
+                        _ = tokio::time::sleep(self.poll_interval) => {}
+                        _ = shutdown.changed() => {}
+                    }
+                    if *shutdown.borrow() {
+                        // Hand the item back; it is still in the store
+                        if let Ok(mut ready) = self.queue.lock() {
+                            ready.push(item);
+                        }
+                        return;
+                    }
+                }
+            }
+
+            let result = self.client.execute_transaction(&item.request).await;
+            self.queue.complete(&item.id);
+            let _ = outcomes.send(QueueOutcome { item, result });
+        }
+    }
+}
+
+/// Handle to running queue workers
+pub struct QueueWorkerHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl QueueWorkerHandle {
+    /// Stops the workers after their current execution finishes
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limiter::RateLimiterConfig;
+    use crate::types::{ChainId, IntentType};
+
+    fn request(reference_id: &str) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "10.00", "USDC", ChainId::Base)
+    }
+
+    #[test]
+    fn test_pop_ready_orders_by_priority_then_fifo() {
+        let queue = TransactionQueue::new();
+        queue.enqueue(QueuedTransaction::new(request("low"))).unwrap();
+        queue.enqueue(QueuedTransaction::new(request("high_1")).with_priority(5)).unwrap();
+        queue.enqueue(QueuedTransaction::new(request("high_2")).with_priority(5)).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        queue.enqueue(QueuedTransaction::new(request("urgent_later")).with_priority(9).not_before(later)).unwrap();
+
+        let now = now_ms();
+        let order: Vec<String> = std::iter::from_fn(|| queue.pop_ready(now)).map(|i| i.request.reference_id).collect();
+        assert_eq!(order, vec!["high_1", "high_2", "low"]);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.pop_ready(now + 3_600_001).unwrap().request.reference_id, "urgent_later");
+    }
+
+    #[test]
+    fn test_file_store_survives_restart() {
+        let path = std::env::temp_dir().join(format!("ecash-queue-{}.json", uuid::Uuid::new_v4()));
+        {
+            let queue = TransactionQueue::with_store(Arc::new(FileQueueStore::open(&path).unwrap())).unwrap();
+            queue.enqueue(QueuedTransaction::new(request("a"))).unwrap();
+            let id = queue.enqueue(QueuedTransaction::new(request("b")).with_priority(1)).unwrap();
+            queue.enqueue(QueuedTransaction::new(request("c"))).unwrap();
+            assert!(queue.cancel(&id).unwrap());
+            // Popped but never completed, e.g. crashed mid-execution
+            assert_eq!(queue.pop_ready(now_ms()).unwrap().request.reference_id, "a");
+        }
+
+        let queue = TransactionQueue::with_store(Arc::new(FileQueueStore::open(&path).unwrap())).unwrap();
+        assert_eq!(queue.len(), 2);
+        let order: Vec<String> =
+            std::iter::from_fn(|| queue.pop_ready(now_ms())).map(|i| i.request.reference_id).collect();
+        assert_eq!(order, vec!["a", "c"]);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_workers_drain_queue_with_rate_limit() {
+        let queue = Arc::new(TransactionQueue::new());
+        let client = Arc::new(EasyCashClient::new(None).unwrap());
+        let limiter = Arc::new(RateLimiter::new(RateLimiterConfig {
+            max_requests: 2,
+            window: Duration::from_millis(200),
+            enabled: true,
+        }));
+        let (workers, mut outcomes) = QueueWorkerPool::new(queue.clone(), client)
+            .with_workers(2)
+            .with_rate_limiter(limiter)
+            .with_poll_interval(Duration::from_millis(20))
+            .start();
+
+        for i in 0..3 {
+            queue.enqueue(QueuedTransaction::new(request(&format!("q_{}", i)))).unwrap();
+        }
+        let started = std::time::Instant::now();
+        let mut done = Vec::new();
+        for _ in 0..3 {
+            let outcome = outcomes.recv().await.unwrap();
+            assert_eq!(outcome.result.unwrap().status, "confirmed");
+            done.push(outcome.item.request.reference_id);
+        }
+        // The third execution had to wait for the next rate limit window
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        done.sort();
+        assert_eq!(done, vec!["q_0", "q_1", "q_2"]);
+        assert!(queue.is_empty());
+        workers.shutdown().await;
+    }
+}
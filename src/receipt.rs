@@ -0,0 +1,463 @@
+//! Signed settlement receipts.
+//!
+//! A receipt binds the final `TransactionResponse` to a hash of the original
+//! intent and is signed by the SDK operator (or the executing agent), so a
+//! merchant can prove settlement to a third party holding only the signer's
+//! public key.
+
+#[cfg(feature = "crypto")]
+use k256::{ecdsa::VerifyingKey, PublicKey};
+#[cfg(feature = "crypto")]
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "crypto")]
+use crate::crypto::{self, hash::HashFunction, SigningDomain, TransactionSigner};
+#[cfg(feature = "crypto")]
+use crate::transport::{IntentSecrets, SealedIntent};
+use crate::types::{ChainId, IntentType, TransactionRequest};
+#[cfg(feature = "crypto")]
+use crate::types::TransactionResponse;
+
+/// Computes the hex-encoded SHA-256 hash of the intent fields of a request.
+///
+/// Travel-rule data and the correlation ID are excluded: the hash identifies
+/// what was paid, not who asked for it. Who gets paid is included in full,
+/// down to each recipient of a disbursement and each fee split, as are the
+/// terms of an escrow and who pays the network fee.
+pub fn intent_hash(req: &TransactionRequest) -> String {
+    let intent = IntentView {
+        amount: &req.amount,
+        asset: &req.asset,
+        disbursements: nested(&req.disbursements),
+        escrow: req.escrow.as_ref().and_then(|terms| serde_json::to_value(terms).ok()),
+        fee_payer: req.fee_payer.as_ref().and_then(|payer| serde_json::to_value(payer).ok()),
+        fee_splits: nested(&req.fee_splits),
+        is_shielded: req.is_shielded,
+        recipient: req.recipient.as_deref(),
+        reference_id: &req.reference_id,
+        source_chain: req.source_chain,
+        target_chain: req.target_chain,
+        intent_type: req.intent_type,
+    };
+    let mut hasher = HashWriter(Sha256::new());
+    // Writing to a hasher cannot fail
+    let _ = serde_json::to_writer(&mut hasher, &intent);
+    format!("0x{}", hex::encode(hasher.0.finalize()))
+}
+
+/// Borrowed intent fields, declared in sorted key order so the JSON matches
+/// a `serde_json::Value` object of the same fields without building one.
+/// Fields added after the first version are left out when empty, so the
+/// hashes of requests without them are unchanged.
+#[derive(Serialize)]
+struct IntentView<'a> {
+    amount: &'a str,
+    asset: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disbursements: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    escrow: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee_payer: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee_splits: Option<serde_json::Value>,
+    is_shielded: bool,
+    recipient: Option<&'a str>,
+    reference_id: &'a str,
+    source_chain: ChainId,
+    target_chain: Option<ChainId>,
+    #[serde(rename = "type")]
+    intent_type: IntentType,
+}
+
+/// `value` as a `serde_json::Value`, whose objects have sorted keys; `None`
+/// when empty
+fn nested<T: Serialize>(value: &[T]) -> Option<serde_json::Value> {
+    if value.is_empty() {
+        return None;
+    }
+    serde_json::to_value(value).ok()
+}
+
+struct HashWriter(Sha256);
+
+impl std::io::Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Transaction response and intent hash signed by the SDK or agent
+#[cfg(feature = "crypto")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedReceipt {
+    pub response: TransactionResponse,
+    #[serde(rename = "intent_hash")]
+    pub intent_hash: String,
+    /// Compressed hex public key of the signer
+    pub signer: String,
+    /// Hex-encoded ECDSA signature over the receipt payload
+    pub signature: String,
+    /// Hash function the payload was digested with before signing
+    #[serde(default, skip_serializing_if = "is_sha256")]
+    pub hash: HashFunction,
+    /// Intent fields as sealed to the executing agent, when the intent was
+    /// sent encrypted (see `transport`)
+    #[serde(rename = "sealed_intent", default, skip_serializing_if = "Option::is_none")]
+    pub sealed_intent: Option<SealedIntent>,
+    /// Commitment to the agent quote the execution accepted
+    /// (`agent::RouteQuote::commitment`)
+    #[serde(rename = "quote_hash", default, skip_serializing_if = "Option::is_none")]
+    pub quote_hash: Option<String>,
+}
+
+#[cfg(feature = "crypto")]
+fn is_sha256(hash: &HashFunction) -> bool {
+    *hash == HashFunction::Sha256
+}
+
+#[cfg(feature = "crypto")]
+impl SignedReceipt {
+    /// Signs the response for the given request
+    ///
+    /// # Example
+    /// ```
+    /// use k256::SecretKey;
+    /// use ecash_sdk_core::crypto::TransactionSigner;
+    /// use ecash_sdk_core::receipt::{verify_receipt, SignedReceipt};
+    /// use ecash_sdk_core::types::*;
+    ///
+    /// let signer = TransactionSigner::new(SecretKey::from_bytes(&[7u8; 32].into()).unwrap());
+    /// let req = TransactionRequest::new("ref_001", IntentType::Transfer, "10.00", "USDC", ChainId::Base);
+    /// let resp = TransactionResponse {
+    ///     tx_hash: "0xabc".to_string(),
+    ///     status: "confirmed".to_string(),
+    ///     block_height: 1,
+    ///     fee_used: "0.05 USDC".to_string(),
+    ///     correlation_id: String::new(),
+    ///     disbursements: Vec::new(),
+    ///     fee_splits: Vec::new(),
+    /// };
+    ///
+    /// let receipt = SignedReceipt::sign(&req, resp, &signer).unwrap();
+    /// assert!(verify_receipt(&receipt, &signer.verifying_key()).unwrap());
+    /// ```
+    pub fn sign(
+        req: &TransactionRequest,
+        response: TransactionResponse,
+        signer: &TransactionSigner,
+    ) -> Result<Self, String> {
+        Self::sign_settlement(req, response, None, None, signer)
+    }
+
+    /// Signs the response, binding the commitment of the quote the execution
+    /// accepted and the sealed intent fields, when there are any
+    pub fn sign_settlement(
+        req: &TransactionRequest,
+        response: TransactionResponse,
+        quote_hash: Option<String>,
+        sealed_intent: Option<SealedIntent>,
+        signer: &TransactionSigner,
+    ) -> Result<Self, String> {
+        let mut receipt = Self {
+            response,
+            intent_hash: intent_hash(req),
+            signer: verifying_key_to_hex(&signer.verifying_key()),
+            signature: String::new(),
+            hash: signer.hash_function(),
+            sealed_intent,
+            quote_hash,
+        };
+        receipt.signature = signer.sign_in_domain(SigningDomain::Receipt, &receipt.signing_payload()?)?;
+        Ok(receipt)
+    }
+
+    /// Decrypts the sealed intent fields with the agent's transport secret
+    /// key; `None` if the intent was not sent encrypted
+    pub fn open_intent(&self, secret_key: &k256::SecretKey) -> Result<Option<IntentSecrets>, String> {
+        self.sealed_intent.as_ref().map(|sealed| sealed.open(secret_key)).transpose()
+    }
+
+    /// Returns true if this receipt was issued for the given request
+    pub fn matches_request(&self, req: &TransactionRequest) -> bool {
+        self.intent_hash == intent_hash(req)
+    }
+
+    fn signing_payload(&self) -> Result<Vec<u8>, String> {
+        let mut payload = serde_json::json!({
+            "response": self.response,
+            "intent_hash": self.intent_hash,
+            "signer": self.signer,
+        });
+        // Only bound when set, so SHA-256 receipts keep their original payload
+        if !is_sha256(&self.hash) {
+            payload["hash"] = serde_json::json!(self.hash);
+        }
+        if let Some(ref sealed) = self.sealed_intent {
+            payload["sealed_intent"] = serde_json::json!(sealed);
+        }
+        if let Some(ref quote_hash) = self.quote_hash {
+            payload["quote_hash"] = serde_json::json!(quote_hash);
+        }
+        serde_json::to_vec(&payload).map_err(|e| format!("failed to encode receipt: {}", e))
+    }
+}
+
+/// Verifies a receipt against the expected signer's public key.
+///
+/// Returns `Ok(false)` if the receipt was signed by a different key or any
+/// signed field was modified.
+#[cfg(feature = "crypto")]
+pub fn verify_receipt(receipt: &SignedReceipt, agent_pubkey: &VerifyingKey) -> Result<bool, String> {
+    if !receipt.signer.eq_ignore_ascii_case(&verifying_key_to_hex(agent_pubkey)) {
+        return Ok(false);
+    }
+    crypto::verify_signature_in_domain(
+        receipt.hash,
+        SigningDomain::Receipt,
+        agent_pubkey,
+        &receipt.signing_payload()?,
+        &receipt.signature,
+    )
+}
+
+#[cfg(feature = "crypto")]
+fn verifying_key_to_hex(key: &VerifyingKey) -> String {
+    crypto::public_key_to_hex(&PublicKey::from(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+    use crate::types::{Disbursement, EscrowTerms, FeePayer, FeeSplit, ReleaseCondition};
+    #[cfg(feature = "crypto")]
+    use k256::SecretKey;
+    use proptest::prelude::*;
+
+    #[cfg(feature = "crypto")]
+    fn signer(seed: u8) -> TransactionSigner {
+        TransactionSigner::new(SecretKey::from_bytes(&[seed; 32].into()).unwrap())
+    }
+
+    fn request() -> TransactionRequest {
+        TransactionRequest::new("ref_001", IntentType::Transfer, "250.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_correlation_id("corr-001")
+    }
+
+    #[cfg(feature = "crypto")]
+    fn response() -> TransactionResponse {
+        TransactionResponse {
+            tx_hash: "0xdeadbeef".to_string(),
+            status: "confirmed".to_string(),
+            block_height: 1948201,
+            fee_used: "0.05 USDC".to_string(),
+            correlation_id: "corr-001".to_string(),
+            disbursements: Vec::new(),
+            fee_splits: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_sign_and_verify() {
+        let agent = signer(3);
+        let receipt = SignedReceipt::sign(&request(), response(), &agent).unwrap();
+        assert!(verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+        assert!(receipt.matches_request(&request()));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_tampered_receipt_fails() {
+        let agent = signer(3);
+        let mut receipt = SignedReceipt::sign(&request(), response(), &agent).unwrap();
+        receipt.response.fee_used = "0.00 USDC".to_string();
+        assert!(!verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_receipt_records_signing_hash() {
+        let agent = signer(3).with_hash(HashFunction::Blake2b256);
+        let mut receipt = SignedReceipt::sign(&request(), response(), &agent).unwrap();
+        assert!(serde_json::to_string(&receipt).unwrap().contains("\"hash\":\"blake2b256\""));
+        assert!(verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+
+        receipt.hash = HashFunction::Sha256;
+        assert!(!verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_sealed_intent_is_signed_and_opens() {
+        let agent_key = SecretKey::from_bytes(&[9u8; 32].into()).unwrap();
+        let req = request();
+        let (mut wire, sealed) = crate::transport::seal(&req, "agent-001", &agent_key.public_key()).unwrap();
+        let agent = signer(3);
+        let mut receipt = SignedReceipt::sign_settlement(&req, response(), None, Some(sealed), &agent).unwrap();
+        assert!(verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+
+        receipt.open_intent(&agent_key).unwrap().unwrap().restore(&mut wire).unwrap();
+        assert!(receipt.matches_request(&wire));
+        assert!(receipt.open_intent(&SecretKey::from_bytes(&[8u8; 32].into()).unwrap()).is_err());
+
+        receipt.sealed_intent = None;
+        assert!(!verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+        assert_eq!(receipt.open_intent(&agent_key), Ok(None));
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_quote_hash_is_signed() {
+        let agent = signer(3);
+        let quote_hash = "0x5f0c".to_string();
+        let mut receipt = SignedReceipt::sign_settlement(&request(), response(), Some(quote_hash), None, &agent).unwrap();
+        assert!(verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+        receipt.quote_hash = Some("0x0000".to_string());
+        assert!(!verify_receipt(&receipt, &agent.verifying_key()).unwrap());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_wrong_key_fails() {
+        let receipt = SignedReceipt::sign(&request(), response(), &signer(3)).unwrap();
+        assert!(!verify_receipt(&receipt, &signer(4).verifying_key()).unwrap());
+    }
+
+    #[test]
+    fn test_intent_hash_ignores_correlation_id() {
+        let mut other = request();
+        other.correlation_id = None;
+        assert_eq!(intent_hash(&request()), intent_hash(&other));
+
+        other.amount = "251.00".to_string();
+        assert_ne!(intent_hash(&request()), intent_hash(&other));
+    }
+
+    #[test]
+    fn test_intent_hash_matches_value_encoding() {
+        // Receipts signed before the borrowed encoding must still verify
+        let mut req = request();
+        for recipient in [None, Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string())] {
+            req.recipient = recipient;
+            req.target_chain = req.target_chain.xor(Some(ChainId::Solana));
+            let intent = serde_json::json!({
+                "reference_id": req.reference_id,
+                "type": req.intent_type,
+                "amount": req.amount,
+                "asset": req.asset,
+                "recipient": req.recipient,
+                "source_chain": req.source_chain,
+                "target_chain": req.target_chain,
+                "is_shielded": req.is_shielded,
+            });
+            let expected = format!("0x{}", hex::encode(Sha256::digest(intent.to_string().as_bytes())));
+            assert_eq!(intent_hash(&req), expected);
+        }
+    }
+
+    #[test]
+    fn test_intent_hash_binds_payees() {
+        let leg = |recipient: &str, amount: &str| Disbursement {
+            recipient: recipient.to_string(),
+            amount: amount.to_string(),
+        };
+        let mut req = request();
+        req.intent_type = IntentType::Disburse;
+        req.recipient = None;
+        req.disbursements = vec![leg("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1", "250.00")];
+        let intent = serde_json::json!({
+            "reference_id": req.reference_id,
+            "type": req.intent_type,
+            "amount": req.amount,
+            "asset": req.asset,
+            "recipient": req.recipient,
+            "source_chain": req.source_chain,
+            "target_chain": req.target_chain,
+            "is_shielded": req.is_shielded,
+            "disbursements": req.disbursements,
+        });
+        let hash = intent_hash(&req);
+        assert_eq!(hash, format!("0x{}", hex::encode(Sha256::digest(intent.to_string().as_bytes()))));
+
+        let mut redirected = req.clone();
+        redirected.disbursements = vec![leg("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb2", "250.00")];
+        assert_ne!(intent_hash(&redirected), hash);
+
+        let mut split = request();
+        let hash = intent_hash(&split);
+        split.fee_splits = vec![FeeSplit {
+            recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb2".to_string(),
+            bps: 250,
+        }];
+        assert_ne!(intent_hash(&split), hash);
+
+        let mut escrow = request();
+        escrow.escrow = Some(EscrowTerms::new(ReleaseCondition::Timeout { release_at: 4_102_444_800 }));
+        let hash = intent_hash(&escrow);
+        escrow.escrow = Some(EscrowTerms::new(ReleaseCondition::Timeout { release_at: 4_102_444_801 }));
+        assert_ne!(intent_hash(&escrow), hash);
+
+        let mut sponsored = request();
+        let hash = intent_hash(&sponsored);
+        sponsored.fee_payer = Some(FeePayer::Recipient);
+        assert_ne!(intent_hash(&sponsored), hash);
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_receipt_json_round_trip() {
+        let agent = signer(5);
+        let receipt = SignedReceipt::sign(&request(), response(), &agent).unwrap();
+        let json = serde_json::to_string(&receipt).unwrap();
+        let decoded: SignedReceipt = serde_json::from_str(&json).unwrap();
+        assert!(verify_receipt(&decoded, &agent.verifying_key()).unwrap());
+    }
+
+    fn chain() -> impl Strategy<Value = ChainId> {
+        prop::sample::select(vec![ChainId::Ethereum, ChainId::Base, ChainId::Solana])
+    }
+
+    proptest! {
+        #[test]
+        fn test_intent_hash_is_canonical(
+            nonce in any::<u32>(),
+            units in 1..1_000_000u32,
+            cents in 0..100u32,
+            source_chain in chain(),
+            target_chain in prop::option::of(chain()),
+            is_shielded in any::<bool>(),
+        ) {
+            let mut req = request();
+            req.reference_id = format!("ref_{}", nonce);
+            req.amount = format!("{}.{:02}", units, cents);
+            req.source_chain = source_chain;
+            req.target_chain = target_chain;
+            req.is_shielded = is_shielded;
+            let hash = intent_hash(&req);
+
+            // Stable across a serialization round trip and independent of the correlation ID
+            let decoded: TransactionRequest = serde_json::from_str(&serde_json::to_string(&req).unwrap()).unwrap();
+            prop_assert_eq!(&intent_hash(&decoded), &hash);
+            req.correlation_id = Some(format!("corr-{}", nonce));
+            prop_assert_eq!(&intent_hash(&req), &hash);
+
+            // Sensitive to every intent field
+            let mut changed = req.clone();
+            changed.amount.push('1');
+            prop_assert_ne!(&intent_hash(&changed), &hash);
+            let mut changed = req.clone();
+            changed.is_shielded = !changed.is_shielded;
+            prop_assert_ne!(&intent_hash(&changed), &hash);
+        }
+    }
+}
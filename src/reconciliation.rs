@@ -0,0 +1,370 @@
+//! Batch result reports and reconciliation against the chain.
+//!
+//! A `ReconciliationReport` records the outcome of every item of a batch run
+//! and can be exported for finance teams. Items still pending after the run
+//! are kept in a `ReconciliationStore` and re-checked with `reconcile`.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "client")]
+use crate::chain::ChainAdapter;
+use crate::errors::{ErrorCode, SdkError};
+use crate::types::{ChainId, TransactionRequest, TransactionResponse};
+
+/// Outcome of a batch item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    Confirmed,
+    Pending,
+    Failed,
+}
+
+impl ItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemStatus::Confirmed => "confirmed",
+            ItemStatus::Pending => "pending",
+            ItemStatus::Failed => "failed",
+        }
+    }
+}
+
+/// One line of a reconciliation report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconciliationItem {
+    pub reference_id: String,
+    pub chain: ChainId,
+    pub tx_hash: Option<String>,
+    pub status: ItemStatus,
+    /// Fee reported by the agent (e.g. "0.05 USDC"), or the on-chain fee in
+    /// the chain's smallest native unit once reconciled
+    pub fee: Option<String>,
+    pub error_code: Option<ErrorCode>,
+    pub failure_reason: Option<String>,
+}
+
+impl ReconciliationItem {
+    /// Builds the item for a request from its execution result
+    pub fn from_result(req: &TransactionRequest, result: &Result<TransactionResponse, SdkError>) -> Self {
+        match result {
+            Ok(resp) => Self {
+                reference_id: req.reference_id.clone(),
+                chain: req.source_chain,
+                tx_hash: Some(resp.tx_hash.clone()).filter(|h| !h.is_empty()),
+                status: match resp.status.as_str() {
+                    "confirmed" => ItemStatus::Confirmed,
+                    "failed" => ItemStatus::Failed,
+                    _ => ItemStatus::Pending,
+                },
+                fee: Some(resp.fee_used.clone()).filter(|f| !f.is_empty()),
+                error_code: None,
+                failure_reason: None,
+            },
+            Err(e) => Self {
+                reference_id: req.reference_id.clone(),
+                chain: req.source_chain,
+                tx_hash: None,
+                status: ItemStatus::Failed,
+                fee: None,
+                error_code: Some(e.code),
+                failure_reason: Some(e.message.clone()),
+            },
+        }
+    }
+}
+
+/// Item counts by status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReportSummary {
+    pub total: usize,
+    pub confirmed: usize,
+    pub pending: usize,
+    pub failed: usize,
+}
+
+/// Per-item outcome of a batch run
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::reconciliation::{ItemStatus, ReconciliationReport};
+/// use ecash_sdk_core::errors::{ErrorCode, SdkError};
+/// use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+///
+/// let req = TransactionRequest::new("salary_001", IntentType::Transfer, "2500.00", "USDC", ChainId::Base);
+/// let results = vec![Err(SdkError::new(ErrorCode::FeeTooHigh, "fee exceeds cap"))];
+/// let report = ReconciliationReport::from_batch(&[req], &results);
+/// assert_eq!(report.items[0].status, ItemStatus::Failed);
+/// assert!(report.to_csv().contains("salary_001,base,,failed,,FEE_TOO_HIGH,fee exceeds cap"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub generated_at_ms: u64,
+    pub items: Vec<ReconciliationItem>,
+}
+
+impl ReconciliationReport {
+    pub fn new(items: Vec<ReconciliationItem>) -> Self {
+        Self {
+            generated_at_ms: now_ms(),
+            items,
+        }
+    }
+
+    /// Builds a report from requests and their `execute_batch` results (same order)
+    pub fn from_batch(reqs: &[TransactionRequest], results: &[Result<TransactionResponse, SdkError>]) -> Self {
+        Self::new(
+            reqs.iter()
+                .zip(results)
+                .map(|(req, result)| ReconciliationItem::from_result(req, result))
+                .collect(),
+        )
+    }
+
+    pub fn summary(&self) -> ReportSummary {
+        let count = |status| self.items.iter().filter(|i| i.status == status).count();
+        ReportSummary {
+            total: self.items.len(),
+            confirmed: count(ItemStatus::Confirmed),
+            pending: count(ItemStatus::Pending),
+            failed: count(ItemStatus::Failed),
+        }
+    }
+
+    /// Items that are still pending
+    pub fn pending(&self) -> impl Iterator<Item = &ReconciliationItem> {
+        self.items.iter().filter(|i| i.status == ItemStatus::Pending)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Exports the items as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("reference_id,chain,tx_hash,status,fee,error_code,failure_reason\n");
+        for item in &self.items {
+            let cells = [
+                item.reference_id.clone(),
+                item.chain.to_string(),
+                item.tx_hash.clone().unwrap_or_default(),
+                item.status.as_str().to_string(),
+                item.fee.clone().unwrap_or_default(),
+                item.error_code.map(|c| c.to_string()).unwrap_or_default(),
+                item.failure_reason.clone().unwrap_or_default(),
+            ];
+            let row: Vec<String> = cells.iter().map(|c| csv_escape(c)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// Storage for report items, keyed by reference ID
+pub trait ReconciliationStore: Send + Sync {
+    /// Inserts or replaces the item with the same reference ID
+    fn upsert(&self, item: ReconciliationItem) -> Result<(), String>;
+
+    /// Returns every pending item
+    fn pending(&self) -> Result<Vec<ReconciliationItem>, String>;
+
+    /// Returns every stored item, ordered by reference ID
+    fn items(&self) -> Result<Vec<ReconciliationItem>, String>;
+
+    /// Stores every item of a report
+    fn save_report(&self, report: &ReconciliationReport) -> Result<(), String> {
+        report.items.iter().try_for_each(|item| self.upsert(item.clone()))
+    }
+}
+
+/// In-memory reconciliation store (per process)
+#[derive(Default)]
+pub struct InMemoryReconciliationStore {
+    items: Mutex<BTreeMap<String, ReconciliationItem>>,
+}
+
+impl InMemoryReconciliationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReconciliationStore for InMemoryReconciliationStore {
+    fn upsert(&self, item: ReconciliationItem) -> Result<(), String> {
+        let mut items = self.items.lock().map_err(|_| "reconciliation store poisoned".to_string())?;
+        items.insert(item.reference_id.clone(), item);
+        Ok(())
+    }
+
+    fn pending(&self) -> Result<Vec<ReconciliationItem>, String> {
+        Ok(self.items()?.into_iter().filter(|i| i.status == ItemStatus::Pending).collect())
+    }
+
+    fn items(&self) -> Result<Vec<ReconciliationItem>, String> {
+        let items = self.items.lock().map_err(|_| "reconciliation store poisoned".to_string())?;
+        Ok(items.values().cloned().collect())
+    }
+}
+
+/// Re-checks the pending items on the adapter's chain and stores any that
+/// settled. Returns a report of the re-checked items.
+///
+/// Items whose receipt cannot be fetched stay pending and are retried on the
+/// next run.
+#[cfg(feature = "client")]
+pub async fn reconcile(
+    store: &dyn ReconciliationStore,
+    chain_adapter: &dyn ChainAdapter,
+) -> Result<ReconciliationReport, String> {
+    let mut checked = Vec::new();
+    for mut item in store.pending()? {
+        if item.chain != chain_adapter.chain() {
+            continue;
+        }
+        let Some(ref tx_hash) = item.tx_hash else { continue };
+        match chain_adapter.get_receipt(tx_hash).await {
+            Ok(Some(receipt)) => {
+                item.fee = Some(receipt.fee_paid);
+                if receipt.success {
+                    item.status = ItemStatus::Confirmed;
+                } else {
+                    item.status = ItemStatus::Failed;
+                    item.failure_reason = Some("transaction reverted on-chain".to_string());
+                }
+                store.upsert(item.clone())?;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("[SDK] Failed to reconcile {}: {}", item.reference_id, e);
+            }
+        }
+        checked.push(item);
+    }
+    Ok(ReconciliationReport::new(checked))
+}
+
+pub(crate) fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::IntentType;
+
+    fn request(reference_id: &str, chain: ChainId) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "10.00", "USDC", chain)
+    }
+
+    fn response(tx_hash: &str, status: &str) -> TransactionResponse {
+        TransactionResponse {
+            tx_hash: tx_hash.to_string(),
+            status: status.to_string(),
+            block_height: 1,
+            fee_used: "0.05 USDC".to_string(),
+            correlation_id: "corr".to_string(),
+            disbursements: Vec::new(),
+            fee_splits: Vec::new(),
+        }
+    }
+
+    fn report() -> ReconciliationReport {
+        let reqs = vec![
+            request("pay_1", ChainId::Base),
+            request("pay_2", ChainId::Base),
+            request("pay_3", ChainId::Solana),
+            request("pay_4", ChainId::Base),
+        ];
+        let results = vec![
+            Ok(response("0xaaa", "confirmed")),
+            Ok(response("0xbbb", "pending")),
+            Ok(response("sig_ccc", "pending")),
+            Err(SdkError::new(ErrorCode::PolicyViolation, "over limit, \"daily\"")),
+        ];
+        ReconciliationReport::from_batch(&reqs, &results)
+    }
+
+    #[test]
+    fn test_report_summary_and_export() {
+        let report = report();
+        assert_eq!(
+            report.summary(),
+            ReportSummary {
+                total: 4,
+                confirmed: 1,
+                pending: 2,
+                failed: 1
+            }
+        );
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[1], "pay_1,base,0xaaa,confirmed,0.05 USDC,,");
+        assert_eq!(lines[4], "pay_4,base,,failed,,POLICY_VIOLATION,\"over limit, \"\"daily\"\"\"");
+
+        let json: ReconciliationReport = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json, report);
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn test_reconcile_updates_pending_items() {
+        use crate::chain::TxReceipt;
+
+        struct Receipts;
+
+        #[async_trait::async_trait]
+        impl ChainAdapter for Receipts {
+            fn chain(&self) -> ChainId {
+                ChainId::Base
+            }
+            async fn get_balance(&self, _: &str, _: &str) -> Result<String, String> {
+                Ok("0".to_string())
+            }
+            async fn broadcast_raw_transaction(&self, _: &str) -> Result<String, String> {
+                Err("read-only".to_string())
+            }
+            async fn get_receipt(&self, tx_hash: &str) -> Result<Option<TxReceipt>, String> {
+                Ok(Some(TxReceipt {
+                    tx_hash: tx_hash.to_string(),
+                    block_height: 42,
+                    success: true,
+                    fee_paid: "21000".to_string(),
+                }))
+            }
+            async fn get_block_height(&self) -> Result<u64, String> {
+                Ok(42)
+            }
+        }
+
+        let store = InMemoryReconciliationStore::new();
+        store.save_report(&report()).unwrap();
+
+        let checked = reconcile(&store, &Receipts).await.unwrap();
+        // The Solana item is left for a Solana adapter
+        assert_eq!(checked.items.len(), 1);
+        assert_eq!(checked.items[0].reference_id, "pay_2");
+        assert_eq!(checked.items[0].status, ItemStatus::Confirmed);
+
+        let pending = store.pending().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].reference_id, "pay_3");
+        assert_eq!(store.items().unwrap()[1].fee.as_deref(), Some("21000"));
+    }
+}
@@ -0,0 +1,264 @@
+//! Redaction of sensitive values in logs and audit payloads.
+//!
+//! Values that identify payers or payees (recipients, amounts, memos) and
+//! credentials never reach `tracing` output or the audit trail as-is. Each kind of field has a
+//! `RedactionPolicy`; the defaults keep logs privacy- and compliance-safe
+//! while still letting operators correlate entries for the same recipient.
+//!
+//! ```
+//! use ecash_sdk_core::redaction::{RedactionConfig, SensitiveField};
+//!
+//! let redaction = RedactionConfig::default();
+//! let recipient = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+//! tracing::info!(recipient = %redaction.redact(SensitiveField::Recipient, recipient), "[SDK] Paying");
+//! assert!(!redaction.redact(SensitiveField::Recipient, recipient).to_string().contains("742d"));
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Kinds of sensitive values the SDK logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensitiveField {
+    Recipient,
+    Amount,
+    Memo,
+    ApiKey,
+    Proof,
+}
+
+impl SensitiveField {
+    /// Field held by a JSON object key of that name, if it is sensitive
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "recipient" => Some(SensitiveField::Recipient),
+            "amount" | "amount_base_units" => Some(SensitiveField::Amount),
+            "memo" => Some(SensitiveField::Memo),
+            "api_key" => Some(SensitiveField::ApiKey),
+            "proof" => Some(SensitiveField::Proof),
+            _ => None,
+        }
+    }
+}
+
+/// How a sensitive value is rendered in logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum RedactionPolicy {
+    /// Log the value unchanged
+    Plain,
+    /// Log a short, stable hash (`sha256:<16 hex>`) so entries can be correlated
+    Hash,
+    /// Keep the first `keep` characters
+    Truncate { keep: usize },
+    /// Log `[redacted]`
+    Drop,
+}
+
+impl RedactionPolicy {
+    /// Renders `value` under this policy; `salt` is mixed into hashes
+    pub fn apply(&self, value: &str, salt: &str) -> String {
+        match self {
+            RedactionPolicy::Plain => value.to_string(),
+            RedactionPolicy::Hash => {
+                let digest = Sha256::new().chain_update(salt.as_bytes()).chain_update(value.as_bytes()).finalize();
+                format!("sha256:{}", hex::encode(&digest[..8]))
+            }
+            RedactionPolicy::Truncate { keep } => match value.char_indices().nth(*keep) {
+                Some((end, _)) => format!("{}...", &value[..end]),
+                None => value.to_string(),
+            },
+            RedactionPolicy::Drop => "[redacted]".to_string(),
+        }
+    }
+}
+
+/// Redaction policy per sensitive field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionConfig {
+    pub recipient: RedactionPolicy,
+    pub amount: RedactionPolicy,
+    pub memo: RedactionPolicy,
+    pub api_key: RedactionPolicy,
+    pub proof: RedactionPolicy,
+    /// Mixed into hashes so they can't be matched against hashes from other
+    /// deployments (or brute-forced from a list of known addresses)
+    pub hash_salt: String,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            recipient: RedactionPolicy::Hash,
+            // Amounts are low-entropy, so a hash would be trivially reversible
+            amount: RedactionPolicy::Drop,
+            memo: RedactionPolicy::Drop,
+            api_key: RedactionPolicy::Drop,
+            proof: RedactionPolicy::Truncate { keep: 10 },
+            hash_salt: String::new(),
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Logs everything unchanged (local debugging only)
+    pub fn disabled() -> Self {
+        Self {
+            recipient: RedactionPolicy::Plain,
+            amount: RedactionPolicy::Plain,
+            memo: RedactionPolicy::Plain,
+            api_key: RedactionPolicy::Plain,
+            proof: RedactionPolicy::Plain,
+            hash_salt: String::new(),
+        }
+    }
+
+    pub fn policy(&self, field: SensitiveField) -> RedactionPolicy {
+        match field {
+            SensitiveField::Recipient => self.recipient,
+            SensitiveField::Amount => self.amount,
+            SensitiveField::Memo => self.memo,
+            SensitiveField::ApiKey => self.api_key,
+            SensitiveField::Proof => self.proof,
+        }
+    }
+
+    /// Wraps `value` for use as a `tracing` field (`field = %redacted`)
+    pub fn redact<'a>(&'a self, field: SensitiveField, value: &'a str) -> Redacted<'a> {
+        Redacted {
+            value,
+            policy: self.policy(field),
+            salt: &self.hash_salt,
+        }
+    }
+
+    /// Applies the policies to the values of sensitive keys (see
+    /// `SensitiveField::from_key`) anywhere in a JSON document
+    pub fn redact_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    match SensitiveField::from_key(key) {
+                        Some(field) => self.redact_json_value(field, value),
+                        None => self.redact_json(value),
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            _ => {}
+        }
+    }
+
+    fn redact_json_value(&self, field: SensitiveField, value: &mut serde_json::Value) {
+        let policy = self.policy(field);
+        if policy == RedactionPolicy::Plain {
+            return;
+        }
+        let plain = match &*value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::Null => return,
+            // Structured values (e.g. a proof object) are rendered whole
+            other => other.to_string(),
+        };
+        *value = serde_json::Value::String(policy.apply(&plain, &self.hash_salt));
+    }
+
+    /// Like `redact`, rendering `None` as `-`
+    pub fn redact_opt<'a>(&'a self, field: SensitiveField, value: Option<&'a str>) -> Redacted<'a> {
+        match value {
+            Some(value) => self.redact(field, value),
+            None => Redacted {
+                value: "-",
+                policy: RedactionPolicy::Plain,
+                salt: "",
+            },
+        }
+    }
+}
+
+/// A value rendered through its redaction policy when displayed
+#[derive(Clone, Copy)]
+pub struct Redacted<'a> {
+    value: &'a str,
+    policy: RedactionPolicy,
+    salt: &'a str,
+}
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.policy.apply(self.value, self.salt))
+    }
+}
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.policy.apply(self.value, self.salt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECIPIENT: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+
+    #[test]
+    fn test_policies() {
+        assert_eq!(RedactionPolicy::Plain.apply("100.00", ""), "100.00");
+        assert_eq!(RedactionPolicy::Drop.apply("100.00", ""), "[redacted]");
+        assert_eq!(RedactionPolicy::Truncate { keep: 4 }.apply("0xabcdef", ""), "0xab...");
+        assert_eq!(RedactionPolicy::Truncate { keep: 4 }.apply("0xab", ""), "0xab");
+        // Multi-byte characters are not split
+        assert_eq!(RedactionPolicy::Truncate { keep: 1 }.apply("\u{e9}t\u{e9}", ""), "\u{e9}...");
+
+        let hash = RedactionPolicy::Hash.apply(RECIPIENT, "");
+        assert!(hash.starts_with("sha256:") && hash.len() == 23);
+        assert_eq!(hash, RedactionPolicy::Hash.apply(RECIPIENT, ""));
+        assert_ne!(hash, RedactionPolicy::Hash.apply(RECIPIENT, "deployment-a"));
+    }
+
+    #[test]
+    fn test_defaults_hide_sensitive_values() {
+        let config = RedactionConfig::default();
+        assert!(!config.redact(SensitiveField::Recipient, RECIPIENT).to_string().contains("742d35"));
+        assert_eq!(config.redact(SensitiveField::Amount, "1000.00").to_string(), "[redacted]");
+        assert_eq!(config.redact(SensitiveField::ApiKey, "sk_live_123").to_string(), "[redacted]");
+        assert_eq!(format!("{:?}", config.redact(SensitiveField::Memo, "rent")), "\"[redacted]\"");
+        assert_eq!(config.redact_opt(SensitiveField::Recipient, None).to_string(), "-");
+
+        let config: RedactionConfig = serde_json::from_str(r#"{"amount":{"policy":"truncate","keep":2}}"#).unwrap();
+        assert_eq!(config.redact(SensitiveField::Amount, "1000.00").to_string(), "10...");
+        assert_eq!(config.recipient, RedactionPolicy::Hash);
+    }
+
+    #[test]
+    fn test_redact_json() {
+        let mut doc = serde_json::json!({
+            "reference_id": "ref_001",
+            "amount": "1000.00",
+            "amount_base_units": 1000000000,
+            "recipient": RECIPIENT,
+            "disbursements": [{ "recipient": RECIPIENT, "amount": "5" }],
+            "proof": { "a": "0x01" },
+            "memo": null,
+        });
+        RedactionConfig::default().redact_json(&mut doc);
+
+        assert_eq!(doc["reference_id"], "ref_001");
+        assert_eq!(doc["amount"], "[redacted]");
+        assert_eq!(doc["amount_base_units"], "[redacted]");
+        assert_eq!(doc["recipient"], RedactionPolicy::Hash.apply(RECIPIENT, ""));
+        assert_eq!(doc["disbursements"][0]["recipient"], doc["recipient"]);
+        assert_eq!(doc["disbursements"][0]["amount"], "[redacted]");
+        assert_eq!(doc["proof"], r#"{"a":"0x01..."#);
+        assert!(doc["memo"].is_null());
+
+        let mut plain = serde_json::json!({ "amount": 10 });
+        RedactionConfig::disabled().redact_json(&mut plain);
+        assert_eq!(plain, serde_json::json!({ "amount": 10 }));
+    }
+}
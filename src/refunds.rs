@@ -0,0 +1,403 @@
+//! Refunds of settled payments.
+//!
+//! Merchants record incoming payments as `Settlement`s once they settle.
+//! `EasyCashClient::refund` then sends all or part of a payment back to its
+//! payer. The `RefundManager` checks the refund against the settlement
+//! (window, partial refunds, remaining amount), reserves it so concurrent
+//! refunds cannot exceed the original, and links it to the original: the
+//! refund's reference ID is `<original>-refund-<n>` and the settlement lists
+//! every refund issued against it.
+//!
+//! ```
+//! use ecash_sdk_core::refunds::{InMemorySettlementStore, RefundManager, RefundPolicy, Settlement};
+//! use ecash_sdk_core::ChainId;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let refunds = RefundManager::new(Arc::new(InMemorySettlementStore::new())).with_policy(RefundPolicy {
+//!     window: Duration::from_secs(14 * 24 * 3600),
+//!     ..RefundPolicy::default()
+//! });
+//! refunds
+//!     .record_settlement(Settlement::new("order_42", "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0", "USDC", "25.00", ChainId::Base, "0xabc"))
+//!     .unwrap();
+//! assert_eq!(refunds.refundable("order_42").unwrap(), "25");
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::types::{ChainId, IntentType, TransactionRequest};
+
+/// Progress of a refund
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefundState {
+    /// Reserved against the settlement; the transfer is executing
+    Pending,
+    Completed,
+}
+
+/// Refund issued against a settlement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefundRecord {
+    pub reference_id: String,
+    pub amount: String,
+    pub state: RefundState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub created_at_ms: u64,
+}
+
+/// Settled incoming payment that may be refunded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settlement {
+    pub reference_id: String,
+    /// Address the payment came from; refunds are sent here
+    pub payer: String,
+    pub asset: String,
+    pub amount: String,
+    pub chain: ChainId,
+    pub tx_hash: String,
+    pub settled_at_ms: u64,
+    #[serde(default)]
+    pub refunds: Vec<RefundRecord>,
+}
+
+impl Settlement {
+    /// Settlement of a payment that settled now
+    pub fn new(
+        reference_id: impl Into<String>,
+        payer: impl Into<String>,
+        asset: impl Into<String>,
+        amount: impl Into<String>,
+        chain: ChainId,
+        tx_hash: impl Into<String>,
+    ) -> Self {
+        Self {
+            reference_id: reference_id.into(),
+            payer: payer.into(),
+            asset: asset.into(),
+            amount: amount.into(),
+            chain,
+            tx_hash: tx_hash.into(),
+            settled_at_ms: now_ms(),
+            refunds: Vec::new(),
+        }
+    }
+
+    /// Amount not yet refunded (pending refunds count as refunded)
+    fn refundable(&self) -> Result<Amount, String> {
+        let mut remaining: Amount = self.amount.parse()?;
+        for refund in &self.refunds {
+            let amount: Amount = refund.amount.parse()?;
+            remaining = remaining.checked_sub(&amount).unwrap_or(Amount::from_base_units(0, 0));
+        }
+        Ok(remaining)
+    }
+}
+
+/// Persistent record of settlements, keyed by reference ID
+pub trait SettlementStore: Send + Sync {
+    fn get(&self, reference_id: &str) -> Result<Option<Settlement>, String>;
+
+    /// Inserts or replaces a settlement
+    fn put(&self, settlement: &Settlement) -> Result<(), String>;
+}
+
+/// In-memory settlement store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemorySettlementStore {
+    settlements: Mutex<HashMap<String, Settlement>>,
+}
+
+impl InMemorySettlementStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, Settlement>>, String> {
+        self.settlements.lock().map_err(|_| "settlement store poisoned".to_string())
+    }
+}
+
+impl SettlementStore for InMemorySettlementStore {
+    fn get(&self, reference_id: &str) -> Result<Option<Settlement>, String> {
+        Ok(self.lock()?.get(reference_id).cloned())
+    }
+
+    fn put(&self, settlement: &Settlement) -> Result<(), String> {
+        self.lock()?.insert(settlement.reference_id.clone(), settlement.clone());
+        Ok(())
+    }
+}
+
+/// Limits on refunds
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefundPolicy {
+    /// How long after settlement refunds are accepted
+    pub window: Duration,
+    /// Whether a settlement may be refunded in parts
+    pub allow_partial: bool,
+    /// Most refunds per settlement
+    pub max_refunds: usize,
+}
+
+impl Default for RefundPolicy {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(30 * 24 * 3600),
+            allow_partial: true,
+            max_refunds: 10,
+        }
+    }
+}
+
+/// Why a refund was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefundRejection {
+    /// No settlement is recorded for the reference ID
+    NotFound { reference_id: String },
+    /// The refund window has passed
+    WindowExpired { reference_id: String, window: Duration },
+    /// The amount is not the full settlement and partial refunds are off
+    PartialNotAllowed { reference_id: String },
+    /// The amount exceeds what has not been refunded yet
+    ExceedsRefundable { reference_id: String, refundable: String },
+    /// The settlement already has the maximum number of refunds
+    TooManyRefunds { reference_id: String, max_refunds: usize },
+    InvalidAmount(String),
+    /// The store failed; the refund is refused to be safe
+    Unavailable(String),
+}
+
+impl std::fmt::Display for RefundRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RefundRejection::NotFound { reference_id } => write!(f, "no settlement recorded for {}", reference_id),
+            RefundRejection::WindowExpired { reference_id, window } => {
+                write!(f, "refund window of {:?} for {} has passed", window, reference_id)
+            }
+            RefundRejection::PartialNotAllowed { reference_id } => {
+                write!(f, "partial refunds are not allowed; refund {} in full", reference_id)
+            }
+            RefundRejection::ExceedsRefundable { reference_id, refundable } => {
+                write!(f, "refund exceeds the {} still refundable for {}", refundable, reference_id)
+            }
+            RefundRejection::TooManyRefunds { reference_id, max_refunds } => {
+                write!(f, "{} already has the maximum of {} refunds", reference_id, max_refunds)
+            }
+            RefundRejection::InvalidAmount(e) => write!(f, "invalid refund amount: {}", e),
+            RefundRejection::Unavailable(e) => write!(f, "settlement store unavailable: {}", e),
+        }
+    }
+}
+
+/// Validates and tracks refunds, used by `EasyCashClient::with_refunds`.
+///
+/// Reservations are serialized per process; when several processes share a
+/// store, issue refunds for a settlement from one of them.
+pub struct RefundManager {
+    store: std::sync::Arc<dyn SettlementStore>,
+    policy: RefundPolicy,
+    lock: Mutex<()>,
+}
+
+impl RefundManager {
+    pub fn new(store: std::sync::Arc<dyn SettlementStore>) -> Self {
+        Self {
+            store,
+            policy: RefundPolicy::default(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: RefundPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Records a settled payment so it can be refunded
+    pub fn record_settlement(&self, settlement: Settlement) -> Result<(), String> {
+        settlement.amount.parse::<Amount>()?;
+        self.store.put(&settlement)
+    }
+
+    pub fn settlement(&self, reference_id: &str) -> Result<Option<Settlement>, String> {
+        self.store.get(reference_id)
+    }
+
+    /// Amount of the settlement that can still be refunded
+    pub fn refundable(&self, reference_id: &str) -> Result<String, RefundRejection> {
+        Ok(self.load(reference_id)?.refundable().map_err(RefundRejection::Unavailable)?.to_string())
+    }
+
+    /// Checks a refund of `amount` against the settlement of
+    /// `original_reference_id`, reserves it, and returns the reverse transfer
+    pub fn begin(&self, original_reference_id: &str, amount: &str) -> Result<TransactionRequest, RefundRejection> {
+        let amount: Amount = amount.parse().map_err(RefundRejection::InvalidAmount)?;
+        if amount.is_zero() {
+            return Err(RefundRejection::InvalidAmount("amount must be positive".to_string()));
+        }
+
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut settlement = self.load(original_reference_id)?;
+        let reference_id = settlement.reference_id.clone();
+        let age = now_ms().saturating_sub(settlement.settled_at_ms);
+        if age > self.policy.window.as_millis() as u64 {
+            return Err(RefundRejection::WindowExpired {
+                reference_id,
+                window: self.policy.window,
+            });
+        }
+        if settlement.refunds.len() >= self.policy.max_refunds {
+            return Err(RefundRejection::TooManyRefunds {
+                reference_id,
+                max_refunds: self.policy.max_refunds,
+            });
+        }
+        let original: Amount = settlement.amount.parse().map_err(RefundRejection::Unavailable)?;
+        if !self.policy.allow_partial && amount != original {
+            return Err(RefundRejection::PartialNotAllowed { reference_id });
+        }
+        let refundable = settlement.refundable().map_err(RefundRejection::Unavailable)?;
+        if amount > refundable {
+            return Err(RefundRejection::ExceedsRefundable {
+                reference_id,
+                refundable: refundable.to_string(),
+            });
+        }
+
+        let refund_reference = format!("{}-refund-{}", reference_id, settlement.refunds.len() + 1);
+        settlement.refunds.push(RefundRecord {
+            reference_id: refund_reference.clone(),
+            amount: amount.to_string(),
+            state: RefundState::Pending,
+            tx_hash: None,
+            created_at_ms: now_ms(),
+        });
+        self.store.put(&settlement).map_err(RefundRejection::Unavailable)?;
+
+        Ok(
+            TransactionRequest::new(refund_reference, IntentType::Transfer, amount.to_string(), settlement.asset, settlement.chain)
+                .with_recipient(settlement.payer)
+                .with_metadata("refund_of", reference_id),
+        )
+    }
+
+    /// Marks the refund `refund_reference_id` as sent in `tx_hash`
+    pub fn complete(&self, original_reference_id: &str, refund_reference_id: &str, tx_hash: &str) -> Result<(), String> {
+        self.update(original_reference_id, |refunds| {
+            if let Some(refund) = refunds.iter_mut().find(|r| r.reference_id == refund_reference_id) {
+                refund.state = RefundState::Completed;
+                refund.tx_hash = Some(tx_hash.to_string());
+            }
+        })
+    }
+
+    /// Releases a refund that failed, so its amount can be refunded again
+    pub fn abort(&self, original_reference_id: &str, refund_reference_id: &str) -> Result<(), String> {
+        self.update(original_reference_id, |refunds| {
+            refunds.retain(|r| r.reference_id != refund_reference_id || r.state != RefundState::Pending)
+        })
+    }
+
+    fn load(&self, reference_id: &str) -> Result<Settlement, RefundRejection> {
+        self.store
+            .get(reference_id)
+            .map_err(RefundRejection::Unavailable)?
+            .ok_or_else(|| RefundRejection::NotFound {
+                reference_id: reference_id.to_string(),
+            })
+    }
+
+    fn update(&self, reference_id: &str, change: impl FnOnce(&mut Vec<RefundRecord>)) -> Result<(), String> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(mut settlement) = self.store.get(reference_id)? else {
+            return Ok(());
+        };
+        change(&mut settlement.refunds);
+        self.store.put(&settlement)
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn manager(policy: RefundPolicy) -> RefundManager {
+        let manager = RefundManager::new(Arc::new(InMemorySettlementStore::new())).with_policy(policy);
+        manager
+            .record_settlement(Settlement::new("order_1", "0xpayer", "USDC", "100.00", ChainId::Base, "0xabc"))
+            .unwrap();
+        manager
+    }
+
+    #[test]
+    fn test_partial_refunds_up_to_original() {
+        let refunds = manager(RefundPolicy::default());
+        let req = refunds.begin("order_1", "40").unwrap();
+        assert_eq!(req.reference_id, "order_1-refund-1");
+        assert_eq!(req.recipient.as_deref(), Some("0xpayer"));
+        assert_eq!((req.amount.as_str(), req.asset.as_str(), req.source_chain), ("40", "USDC", ChainId::Base));
+        assert_eq!(req.metadata["refund_of"], "order_1");
+        // Pending refunds count against the remaining amount
+        assert!(matches!(
+            refunds.begin("order_1", "60.01"),
+            Err(RefundRejection::ExceedsRefundable { ref refundable, .. }) if refundable == "60"
+        ));
+        refunds.complete("order_1", &req.reference_id, "0xrefund").unwrap();
+
+        // A failed refund frees its amount
+        let failed = refunds.begin("order_1", "60").unwrap();
+        assert_eq!(failed.reference_id, "order_1-refund-2");
+        refunds.abort("order_1", &failed.reference_id).unwrap();
+        assert_eq!(refunds.refundable("order_1").unwrap(), "60");
+
+        refunds.begin("order_1", "60.00").unwrap();
+        assert_eq!(refunds.refundable("order_1").unwrap(), "0");
+        let settlement = refunds.settlement("order_1").unwrap().unwrap();
+        assert_eq!(settlement.refunds[0].tx_hash.as_deref(), Some("0xrefund"));
+        assert_eq!(settlement.refunds[1].state, RefundState::Pending);
+    }
+
+    #[test]
+    fn test_policy_limits() {
+        let refunds = manager(RefundPolicy {
+            allow_partial: false,
+            ..RefundPolicy::default()
+        });
+        assert!(matches!(refunds.begin("order_1", "50"), Err(RefundRejection::PartialNotAllowed { .. })));
+        refunds.begin("order_1", "100").unwrap();
+
+        let refunds = manager(RefundPolicy {
+            max_refunds: 1,
+            ..RefundPolicy::default()
+        });
+        refunds.begin("order_1", "1").unwrap();
+        assert!(matches!(refunds.begin("order_1", "1"), Err(RefundRejection::TooManyRefunds { .. })));
+
+        let refunds = manager(RefundPolicy {
+            window: Duration::ZERO,
+            ..RefundPolicy::default()
+        });
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(matches!(refunds.begin("order_1", "1"), Err(RefundRejection::WindowExpired { .. })));
+
+        assert!(matches!(refunds.begin("order_2", "1"), Err(RefundRejection::NotFound { .. })));
+        assert!(matches!(refunds.begin("order_1", "0"), Err(RefundRejection::InvalidAmount(_))));
+        assert!(matches!(refunds.begin("order_1", "-5"), Err(RefundRejection::InvalidAmount(_))));
+    }
+}
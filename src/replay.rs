@@ -0,0 +1,752 @@
+//! Deterministic replay of recorded executions.
+//!
+//! A `TraceRecorder` wraps the client's external dependencies (agent
+//! negotiator, chain adapters, balance provider, price oracle and screening
+//! provider) and captures every call they answer, with its result and
+//! latency. `TraceRecorder::finish` bundles the calls made for one execution
+//! with the request, configuration and outcome into a `ReplayTrace` that can
+//! be written to a file. `replay` re-runs the client against the recorded
+//! answers, so an incident can be reproduced under a debugger.
+//!
+//! Calls are recorded in the order they are made, so record executions one
+//! at a time (e.g. on a client dedicated to the affected account). Replies
+//! are matched by kind in recorded order; a replay that makes a call the
+//! recording didn't fails that call. Recorded latencies are slept out with
+//! `tokio::time::sleep`, so replays under a paused clock don't wait.
+//!
+//! Traces contain the full request, including recipient and travel-rule
+//! data, and should be stored like any other personal data.
+//!
+//! ```no_run
+//! use ecash_sdk_core::client::EasyCashClient;
+//! use ecash_sdk_core::replay::{self, ReplayTrace, TraceRecorder};
+//! # use ecash_sdk_core::agent::{AgentNegotiatorTrait, MockAgentNegotiator};
+//! # use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+//! # use std::sync::Arc;
+//! # use std::time::Duration;
+//!
+//! # async fn run(req: TransactionRequest) -> ecash_sdk_core::errors::Result<()> {
+//! # let negotiator: Arc<dyn AgentNegotiatorTrait> = Arc::new(MockAgentNegotiator::new(Duration::from_secs(5)));
+//! let recorder = TraceRecorder::new();
+//! let client = EasyCashClient::new(None)?.with_negotiator(recorder.negotiator(negotiator));
+//! let result = client.execute_transaction(&req).await;
+//! recorder.finish(&client, &req, &result).write_file("incident.json").unwrap();
+//!
+//! // Later, elsewhere
+//! let trace = ReplayTrace::from_file("incident.json").unwrap();
+//! let report = replay::replay(&trace).await?;
+//! assert!(report.reproduced());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::{self, AgentNegotiatorTrait, Replacement, RouteQuote, SpeedUpRequest};
+use crate::chain::{ChainAdapter, NativeAsset, TxReceipt};
+use crate::client::EasyCashClient;
+use crate::compliance::{ScreeningDecision, ScreeningProvider, ScreeningRequest};
+use crate::config::SdkConfig;
+use crate::errors::{ErrorCode, Result, SdkError, SdkErrorResponse};
+use crate::escrow::{Escrow, EscrowTerms};
+use crate::pricing::PriceOracle;
+use crate::protocol;
+use crate::solvency::BalanceProvider;
+use crate::sponsorship::Sponsorship;
+use crate::streaming::ProgressReporter;
+use crate::transport::SealedIntent;
+use crate::types::{ChainId, Disbursement, TransactionRequest, TransactionResponse};
+
+/// Format version of `ReplayTrace`
+pub const TRACE_VERSION: u32 = 2;
+
+/// External call answered during a recorded execution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum Call {
+    RequestQuotes { result: std::result::Result<Vec<RouteQuote>, String> },
+    Execute { agent_id: String, result: std::result::Result<TransactionResponse, String> },
+    ExecuteSealed { agent_id: String, result: std::result::Result<TransactionResponse, String> },
+    ExecuteDisbursement { agent_id: String, result: std::result::Result<TransactionResponse, String> },
+    LockEscrow { agent_id: String, result: std::result::Result<TransactionResponse, String> },
+    SponsorFee { agent_id: String, result: std::result::Result<(), String> },
+    SupportedVersions { result: std::result::Result<Vec<u32>, String> },
+    GetBalance { chain: ChainId, address: String, asset: String, result: std::result::Result<String, String> },
+    BroadcastRawTransaction { chain: ChainId, result: std::result::Result<String, String> },
+    GetReceipt { chain: ChainId, tx_hash: String, result: std::result::Result<Option<TxReceipt>, String> },
+    GetBlockHeight { chain: ChainId, result: std::result::Result<u64, String> },
+    AvailableBalance { result: std::result::Result<f64, String> },
+    UsdPrice { asset: String, result: std::result::Result<f64, String> },
+    Screen { result: std::result::Result<ScreeningDecision, String> },
+}
+
+/// Recorded call and how long it took to answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub elapsed_ms: u64,
+    #[serde(flatten)]
+    pub call: Call,
+}
+
+/// Everything needed to re-run one execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayTrace {
+    pub version: u32,
+    /// Configuration of the recording client (without its API key)
+    pub config: SdkConfig,
+    /// The request, with the correlation ID of the recorded execution
+    pub request: TransactionRequest,
+    pub interactions: Vec<Interaction>,
+    pub outcome: std::result::Result<TransactionResponse, SdkErrorResponse>,
+}
+
+impl ReplayTrace {
+    /// Reads a trace written by `write_file`
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> std::result::Result<Self, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| format!("invalid trace {}: {}", path.display(), e))
+    }
+
+    /// Writes the trace as JSON
+    pub fn write_file(&self, path: impl AsRef<std::path::Path>) -> std::result::Result<(), String> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("failed to encode trace: {}", e))?;
+        std::fs::write(path, json).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Records the calls answered by the dependencies it wraps
+#[derive(Debug, Clone, Default)]
+pub struct TraceRecorder {
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn negotiator(&self, inner: Arc<dyn AgentNegotiatorTrait>) -> Arc<dyn AgentNegotiatorTrait> {
+        Arc::new(RecordingNegotiator { inner, recorder: self.clone() })
+    }
+
+    pub fn chain_adapter(&self, inner: Arc<dyn ChainAdapter>) -> Arc<dyn ChainAdapter> {
+        Arc::new(RecordingChainAdapter { inner, recorder: self.clone() })
+    }
+
+    pub fn balance_provider(&self, inner: Arc<dyn BalanceProvider>) -> Arc<dyn BalanceProvider> {
+        Arc::new(RecordingBalanceProvider { inner, recorder: self.clone() })
+    }
+
+    pub fn price_oracle(&self, inner: Arc<dyn PriceOracle>) -> Arc<dyn PriceOracle> {
+        Arc::new(RecordingPriceOracle { inner, recorder: self.clone() })
+    }
+
+    pub fn screening_provider(&self, inner: Arc<dyn ScreeningProvider>) -> Arc<dyn ScreeningProvider> {
+        Arc::new(RecordingScreeningProvider { inner, recorder: self.clone() })
+    }
+
+    /// Trace of the execution of `req` by `client`, taking the calls
+    /// recorded since the last `finish`
+    pub fn finish(
+        &self,
+        client: &EasyCashClient,
+        req: &TransactionRequest,
+        result: &Result<TransactionResponse>,
+    ) -> ReplayTrace {
+        let interactions = std::mem::take(&mut *self.interactions.lock().unwrap_or_else(|e| e.into_inner()));
+        let outcome = match result {
+            Ok(resp) => Ok(resp.clone()),
+            Err(err) => Err(SdkErrorResponse::from(err)),
+        };
+        let mut request = req.clone();
+        if request.correlation_id.is_none() {
+            request.correlation_id = match outcome {
+                Ok(ref resp) => Some(resp.correlation_id.clone()),
+                Err(ref err) => err.correlation_id.clone(),
+            };
+        }
+        ReplayTrace {
+            version: TRACE_VERSION,
+            config: client.config().clone(),
+            request,
+            interactions,
+            outcome,
+        }
+    }
+
+    async fn record<T: Clone>(
+        &self,
+        call: impl Future<Output = std::result::Result<T, String>>,
+        recorded: impl FnOnce(std::result::Result<T, String>) -> Call,
+    ) -> std::result::Result<T, String> {
+        let started = Instant::now();
+        let result = call.await;
+        let interaction = Interaction {
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            call: recorded(result.clone()),
+        };
+        self.interactions.lock().unwrap_or_else(|e| e.into_inner()).push(interaction);
+        result
+    }
+}
+
+/// Result of re-running a trace
+#[derive(Debug)]
+pub struct ReplayReport {
+    pub recorded: std::result::Result<TransactionResponse, SdkErrorResponse>,
+    pub replayed: std::result::Result<TransactionResponse, SdkErrorResponse>,
+    /// Recorded calls the replay didn't make
+    pub unconsumed: usize,
+}
+
+impl ReplayReport {
+    /// True if the replay ended like the recording. Transaction hashes are
+    /// generated per execution and not compared.
+    pub fn reproduced(&self) -> bool {
+        match (&self.recorded, &self.replayed) {
+            (Ok(recorded), Ok(replayed)) => {
+                TransactionResponse { tx_hash: String::new(), ..recorded.clone() }
+                    == TransactionResponse { tx_hash: String::new(), ..replayed.clone() }
+            }
+            (Err(recorded), Err(replayed)) => (recorded.code, &recorded.message) == (replayed.code, &replayed.message),
+            _ => false,
+        }
+    }
+}
+
+/// Re-runs the execution of `trace` on a client built from its
+/// configuration, answering external calls from the recording
+pub async fn replay(trace: &ReplayTrace) -> Result<ReplayReport> {
+    replay_with(trace, |client| client).await
+}
+
+/// Like `replay`, letting `customize` add the policies, ledgers and other
+/// local state the recording client was built with
+pub async fn replay_with(
+    trace: &ReplayTrace,
+    customize: impl FnOnce(EasyCashClient) -> EasyCashClient,
+) -> Result<ReplayReport> {
+    if trace.version != TRACE_VERSION {
+        return Err(SdkError::new(
+            ErrorCode::InvalidRequest,
+            format!("trace version {} is not supported (expected {})", trace.version, TRACE_VERSION),
+        ));
+    }
+    let replayer = Arc::new(Replayer {
+        interactions: Mutex::new(trace.interactions.iter().cloned().map(Some).collect()),
+    });
+    let mut client = EasyCashClient::new(Some(trace.config.clone()))?
+        .with_negotiator(Arc::new(ReplayNegotiator(replayer.clone())));
+    let chains: HashSet<ChainId> = trace
+        .interactions
+        .iter()
+        .filter_map(|i| match i.call {
+            Call::GetBalance { chain, .. }
+            | Call::BroadcastRawTransaction { chain, .. }
+            | Call::GetReceipt { chain, .. }
+            | Call::GetBlockHeight { chain, .. } => Some(chain),
+            _ => None,
+        })
+        .collect();
+    for chain in chains {
+        client = client.with_chain_adapter(Arc::new(ReplayChainAdapter { chain, replayer: replayer.clone() }));
+    }
+    let recorded = |matches: fn(&Call) -> bool| trace.interactions.iter().any(|i| matches(&i.call));
+    if recorded(|call| matches!(call, Call::AvailableBalance { .. })) {
+        client = client.with_balance_provider(Arc::new(ReplayBalanceProvider(replayer.clone())));
+    }
+    if recorded(|call| matches!(call, Call::UsdPrice { .. })) {
+        client = client.with_price_oracle(Arc::new(ReplayPriceOracle(replayer.clone())));
+    }
+    if recorded(|call| matches!(call, Call::Screen { .. })) {
+        client = client.with_screening_provider(Arc::new(ReplayScreeningProvider(replayer.clone())));
+    }
+    let client = customize(client);
+
+    let replayed = client.execute_transaction(&trace.request).await.map_err(SdkErrorResponse::from);
+    Ok(ReplayReport {
+        recorded: trace.outcome.clone(),
+        replayed,
+        unconsumed: replayer.unconsumed(),
+    })
+}
+
+/// Recorded calls not yet replayed
+struct Replayer {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl Replayer {
+    /// Answers with the first unreplayed call `answer` accepts, after its
+    /// recorded latency
+    async fn answer<T>(
+        &self,
+        what: &str,
+        answer: impl Fn(&Call) -> Option<std::result::Result<T, String>>,
+    ) -> std::result::Result<T, String> {
+        let found = {
+            let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+            interactions.iter_mut().find_map(|slot| {
+                let (result, elapsed_ms) = slot.as_ref().and_then(|i| Some((answer(&i.call)?, i.elapsed_ms)))?;
+                *slot = None;
+                Some((result, elapsed_ms))
+            })
+        };
+        match found {
+            Some((result, elapsed_ms)) => {
+                tokio::time::sleep(Duration::from_millis(elapsed_ms)).await;
+                result
+            }
+            None => Err(format!("the trace records no further {}", what)),
+        }
+    }
+
+    fn unconsumed(&self) -> usize {
+        self.interactions.lock().unwrap_or_else(|e| e.into_inner()).iter().flatten().count()
+    }
+}
+
+struct RecordingNegotiator {
+    inner: Arc<dyn AgentNegotiatorTrait>,
+    recorder: TraceRecorder,
+}
+
+#[async_trait::async_trait]
+impl AgentNegotiatorTrait for RecordingNegotiator {
+    async fn request_quotes(&self, req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+        self.recorder
+            .record(self.inner.request_quotes(req), |result| Call::RequestQuotes { result })
+            .await
+    }
+
+    async fn execute(&self, req: &TransactionRequest, route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+        self.recorder
+            .record(self.inner.execute(req, route), |result| Call::Execute { agent_id: route.agent_id.clone(), result })
+            .await
+    }
+
+    async fn execute_with_progress(
+        &self,
+        req: &TransactionRequest,
+        route: &RouteQuote,
+        progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.recorder
+            .record(self.inner.execute_with_progress(req, route, progress), |result| Call::Execute {
+                agent_id: route.agent_id.clone(),
+                result,
+            })
+            .await
+    }
+
+    async fn execute_sealed(
+        &self,
+        req: &TransactionRequest,
+        sealed: &SealedIntent,
+        route: &RouteQuote,
+        progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.recorder
+            .record(self.inner.execute_sealed(req, sealed, route, progress), |result| Call::ExecuteSealed {
+                agent_id: route.agent_id.clone(),
+                result,
+            })
+            .await
+    }
+
+    async fn execute_disbursement(
+        &self,
+        req: &TransactionRequest,
+        disbursements: &[Disbursement],
+        route: &RouteQuote,
+        progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.recorder
+            .record(self.inner.execute_disbursement(req, disbursements, route, progress), |result| {
+                Call::ExecuteDisbursement { agent_id: route.agent_id.clone(), result }
+            })
+            .await
+    }
+
+    async fn lock_escrow(
+        &self,
+        req: &TransactionRequest,
+        terms: &EscrowTerms,
+        route: &RouteQuote,
+        progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.recorder
+            .record(self.inner.lock_escrow(req, terms, route, progress), |result| Call::LockEscrow {
+                agent_id: route.agent_id.clone(),
+                result,
+            })
+            .await
+    }
+
+    async fn release_escrow(&self, escrow: &Escrow) -> std::result::Result<(), String> {
+        self.inner.release_escrow(escrow).await
+    }
+
+    async fn refund_escrow(&self, escrow: &Escrow) -> std::result::Result<(), String> {
+        self.inner.refund_escrow(escrow).await
+    }
+
+    async fn sponsor_fee(&self, sponsorship: &Sponsorship, route: &RouteQuote) -> std::result::Result<(), String> {
+        self.recorder
+            .record(self.inner.sponsor_fee(sponsorship, route), |result| Call::SponsorFee {
+                agent_id: route.agent_id.clone(),
+                result,
+            })
+            .await
+    }
+
+    async fn speed_up(&self, req: &SpeedUpRequest) -> std::result::Result<Replacement, String> {
+        self.inner.speed_up(req).await
+    }
+
+    async fn supported_versions(&self) -> std::result::Result<Vec<u32>, String> {
+        self.recorder
+            .record(self.inner.supported_versions(), |result| Call::SupportedVersions { result })
+            .await
+    }
+
+    fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+        self.inner.select_best_route(quotes, preference)
+    }
+}
+
+struct RecordingChainAdapter {
+    inner: Arc<dyn ChainAdapter>,
+    recorder: TraceRecorder,
+}
+
+#[async_trait::async_trait]
+impl ChainAdapter for RecordingChainAdapter {
+    fn chain(&self) -> ChainId {
+        self.inner.chain()
+    }
+
+    fn native_asset(&self) -> Option<NativeAsset> {
+        self.inner.native_asset()
+    }
+
+    async fn get_balance(&self, address: &str, asset: &str) -> std::result::Result<String, String> {
+        self.recorder
+            .record(self.inner.get_balance(address, asset), |result| Call::GetBalance {
+                chain: self.chain(),
+                address: address.to_string(),
+                asset: asset.to_string(),
+                result,
+            })
+            .await
+    }
+
+    async fn broadcast_raw_transaction(&self, raw_tx: &str) -> std::result::Result<String, String> {
+        self.recorder
+            .record(self.inner.broadcast_raw_transaction(raw_tx), |result| Call::BroadcastRawTransaction {
+                chain: self.chain(),
+                result,
+            })
+            .await
+    }
+
+    async fn get_receipt(&self, tx_hash: &str) -> std::result::Result<Option<TxReceipt>, String> {
+        self.recorder
+            .record(self.inner.get_receipt(tx_hash), |result| Call::GetReceipt {
+                chain: self.chain(),
+                tx_hash: tx_hash.to_string(),
+                result,
+            })
+            .await
+    }
+
+    async fn get_block_height(&self) -> std::result::Result<u64, String> {
+        self.recorder
+            .record(self.inner.get_block_height(), |result| Call::GetBlockHeight { chain: self.chain(), result })
+            .await
+    }
+}
+
+struct RecordingBalanceProvider {
+    inner: Arc<dyn BalanceProvider>,
+    recorder: TraceRecorder,
+}
+
+#[async_trait::async_trait]
+impl BalanceProvider for RecordingBalanceProvider {
+    async fn available_balance(&self, req: &TransactionRequest) -> std::result::Result<f64, String> {
+        self.recorder
+            .record(self.inner.available_balance(req), |result| Call::AvailableBalance { result })
+            .await
+    }
+}
+
+struct RecordingPriceOracle {
+    inner: Arc<dyn PriceOracle>,
+    recorder: TraceRecorder,
+}
+
+#[async_trait::async_trait]
+impl PriceOracle for RecordingPriceOracle {
+    async fn usd_price(&self, asset: &str) -> std::result::Result<f64, String> {
+        self.recorder
+            .record(self.inner.usd_price(asset), |result| Call::UsdPrice { asset: asset.to_string(), result })
+            .await
+    }
+}
+
+struct RecordingScreeningProvider {
+    inner: Arc<dyn ScreeningProvider>,
+    recorder: TraceRecorder,
+}
+
+#[async_trait::async_trait]
+impl ScreeningProvider for RecordingScreeningProvider {
+    async fn screen(&self, req: &ScreeningRequest) -> std::result::Result<ScreeningDecision, String> {
+        self.recorder.record(self.inner.screen(req), |result| Call::Screen { result }).await
+    }
+}
+
+struct ReplayNegotiator(Arc<Replayer>);
+
+#[async_trait::async_trait]
+impl AgentNegotiatorTrait for ReplayNegotiator {
+    async fn request_quotes(&self, _req: &TransactionRequest) -> std::result::Result<Vec<RouteQuote>, String> {
+        self.0
+            .answer("quote request", |call| match call {
+                Call::RequestQuotes { result } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn execute(&self, _req: &TransactionRequest, _route: &RouteQuote) -> std::result::Result<TransactionResponse, String> {
+        self.0
+            .answer("execution", |call| match call {
+                Call::Execute { result, .. } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn execute_sealed(
+        &self,
+        _req: &TransactionRequest,
+        _sealed: &SealedIntent,
+        _route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.0
+            .answer("sealed execution", |call| match call {
+                Call::ExecuteSealed { result, .. } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn execute_disbursement(
+        &self,
+        _req: &TransactionRequest,
+        _disbursements: &[Disbursement],
+        _route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.0
+            .answer("disbursement", |call| match call {
+                Call::ExecuteDisbursement { result, .. } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn lock_escrow(
+        &self,
+        _req: &TransactionRequest,
+        _terms: &EscrowTerms,
+        _route: &RouteQuote,
+        _progress: &ProgressReporter,
+    ) -> std::result::Result<TransactionResponse, String> {
+        self.0
+            .answer("escrow lock", |call| match call {
+                Call::LockEscrow { result, .. } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn sponsor_fee(&self, _sponsorship: &Sponsorship, _route: &RouteQuote) -> std::result::Result<(), String> {
+        self.0
+            .answer("fee sponsorship", |call| match call {
+                Call::SponsorFee { result, .. } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn supported_versions(&self) -> std::result::Result<Vec<u32>, String> {
+        // Negotiated once per client, so usually recorded by an earlier execution
+        let versions = self
+            .0
+            .answer("protocol negotiation", |call| match call {
+                Call::SupportedVersions { result } => Some(result.clone()),
+                _ => None,
+            })
+            .await;
+        versions.or_else(|_| Ok(protocol::supported_versions().to_vec()))
+    }
+
+    fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> std::result::Result<RouteQuote, String> {
+        agent::select_best_route(quotes, preference)
+    }
+}
+
+struct ReplayChainAdapter {
+    chain: ChainId,
+    replayer: Arc<Replayer>,
+}
+
+#[async_trait::async_trait]
+impl ChainAdapter for ReplayChainAdapter {
+    fn chain(&self) -> ChainId {
+        self.chain
+    }
+
+    async fn get_balance(&self, _address: &str, _asset: &str) -> std::result::Result<String, String> {
+        self.replayer
+            .answer("balance read", |call| match call {
+                Call::GetBalance { chain, result, .. } if *chain == self.chain => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn broadcast_raw_transaction(&self, _raw_tx: &str) -> std::result::Result<String, String> {
+        self.replayer
+            .answer("broadcast", |call| match call {
+                Call::BroadcastRawTransaction { chain, result } if *chain == self.chain => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn get_receipt(&self, _tx_hash: &str) -> std::result::Result<Option<TxReceipt>, String> {
+        self.replayer
+            .answer("receipt read", |call| match call {
+                Call::GetReceipt { chain, result, .. } if *chain == self.chain => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+
+    async fn get_block_height(&self) -> std::result::Result<u64, String> {
+        self.replayer
+            .answer("block height read", |call| match call {
+                Call::GetBlockHeight { chain, result } if *chain == self.chain => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+}
+
+struct ReplayBalanceProvider(Arc<Replayer>);
+
+#[async_trait::async_trait]
+impl BalanceProvider for ReplayBalanceProvider {
+    async fn available_balance(&self, _req: &TransactionRequest) -> std::result::Result<f64, String> {
+        self.0
+            .answer("balance lookup", |call| match call {
+                Call::AvailableBalance { result } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+}
+
+struct ReplayPriceOracle(Arc<Replayer>);
+
+#[async_trait::async_trait]
+impl PriceOracle for ReplayPriceOracle {
+    async fn usd_price(&self, asset: &str) -> std::result::Result<f64, String> {
+        self.0
+            .answer("price lookup", |call| match call {
+                Call::UsdPrice { asset: recorded, result } if recorded == asset => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+}
+
+struct ReplayScreeningProvider(Arc<Replayer>);
+
+#[async_trait::async_trait]
+impl ScreeningProvider for ReplayScreeningProvider {
+    async fn screen(&self, _req: &ScreeningRequest) -> std::result::Result<ScreeningDecision, String> {
+        self.0
+            .answer("screening", |call| match call {
+                Call::Screen { result } => Some(result.clone()),
+                _ => None,
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::MockAgentNegotiator;
+    use crate::chain::MockChainAdapter;
+    use crate::solvency::StaticBalanceProvider;
+    use crate::types::IntentType;
+
+    fn request(reference_id: &str) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "10", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_record_and_replay() {
+        let recorder = TraceRecorder::new();
+        let balances = Arc::new(StaticBalanceProvider::new());
+        balances.set_balance("USDC", 5.0);
+        let mut config = SdkConfig::default_config();
+        config.enable_caching = false;
+        let client = EasyCashClient::new(Some(config))
+            .unwrap()
+            .with_negotiator(recorder.negotiator(Arc::new(MockAgentNegotiator::new(Duration::from_secs(5)))))
+            .with_chain_adapter(recorder.chain_adapter(Arc::new(MockChainAdapter::new(ChainId::Base))))
+            .with_balance_provider(recorder.balance_provider(balances.clone()));
+
+        let req = request("ref_replay");
+        let result = client.execute_transaction(&req).await;
+        assert_eq!(result.as_ref().unwrap_err().code, ErrorCode::InsufficientFunds);
+        let trace = recorder.finish(&client, &req, &result);
+        assert_eq!(trace.interactions.len(), 1);
+        let trace: ReplayTrace = serde_json::from_str(&serde_json::to_string(&trace).unwrap()).unwrap();
+        let report = replay(&trace).await.unwrap();
+        assert!(report.reproduced());
+
+        balances.set_balance("USDC", 100.0);
+        let req = request("ref_replay_ok");
+        let result = client.execute_transaction(&req).await;
+        let trace = recorder.finish(&client, &req, &result);
+        assert!(trace.interactions.iter().any(|i| matches!(i.call, Call::GetBlockHeight { .. })));
+        let report = replay(&trace).await.unwrap();
+        assert!(report.reproduced(), "{:?}", report);
+        assert_eq!(report.unconsumed, 0);
+        assert_eq!(report.replayed.unwrap().correlation_id, result.unwrap().correlation_id);
+
+        // A trace missing the agent's answer fails where the recording didn't
+        let mut diverged = trace.clone();
+        diverged.interactions.retain(|i| !matches!(i.call, Call::Execute { .. }));
+        let report = replay(&diverged).await.unwrap();
+        assert!(!report.reproduced());
+        assert!(report.replayed.unwrap_err().message.contains("no further execution"));
+    }
+}
@@ -0,0 +1,270 @@
+//! Signing of outbound EasyCash API requests.
+//!
+//! In addition to the bearer `api_key`, requests to the API host can carry a
+//! signature over the method, path, a timestamp, a one-time nonce and the
+//! SHA-256 digest of the body, either an HMAC-SHA256 with a shared secret or
+//! an ECDSA (secp256k1) signature, both over the intent domain tag
+//! (`SigningDomain::Intent`) followed by the canonical request. Servers reject
+//! stale timestamps and replayed nonces.
+//!
+//! Keys are configured per environment in `SdkConfig::request_signing`.
+//! `EasyCashClient::new` applies them to the client's `HttpClient`, which
+//! then signs every request sent to the `api_endpoint` host.
+//!
+//! ```
+//! use ecash_sdk_core::request_signing::{RequestSigner, SigningKey, SIGNATURE_HEADER};
+//!
+//! let key: SigningKey = serde_json::from_str(
+//!     r#"{"algorithm": "hmac_sha256", "key_id": "key_1", "secret": "s3cret"}"#,
+//! ).unwrap();
+//! let signer = RequestSigner::new(&key).unwrap();
+//! let headers = signer.sign_at("POST", "/v1/intents", b"{}", 1_700_000_000, "0011");
+//! assert!(headers.iter().any(|(name, value)| *name == SIGNATURE_HEADER && value.starts_with("hmac-sha256=")));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::crypto::{self, SigningDomain, TransactionSigner};
+use crate::secrets::SecretString;
+
+pub const TIMESTAMP_HEADER: &str = "x-ecash-timestamp";
+pub const NONCE_HEADER: &str = "x-ecash-nonce";
+pub const CONTENT_DIGEST_HEADER: &str = "x-ecash-content-sha256";
+pub const KEY_ID_HEADER: &str = "x-ecash-key-id";
+pub const SIGNATURE_HEADER: &str = "x-ecash-request-signature";
+
+/// Request signing key
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "snake_case")]
+pub enum SigningKey {
+    /// Shared secret known to the API
+    HmacSha256 { key_id: String, secret: SecretString },
+    /// Hex-encoded secp256k1 private key whose public key is registered with the API
+    EcdsaSecp256k1 { key_id: String, private_key: SecretString },
+}
+
+/// Signing keys per environment
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RequestSigningConfig {
+    /// Key used for every environment without its own entry
+    pub key: Option<SigningKey>,
+    pub environments: HashMap<String, SigningKey>,
+}
+
+impl RequestSigningConfig {
+    /// Signer for `environment`, if signing is configured for it
+    pub fn signer_for(&self, environment: &str) -> Result<Option<RequestSigner>, String> {
+        self.environments
+            .get(environment)
+            .or(self.key.as_ref())
+            .map(RequestSigner::new)
+            .transpose()
+    }
+
+    /// Checks that every key is usable
+    pub fn validate(&self) -> Result<(), String> {
+        for key in self.key.iter().chain(self.environments.values()) {
+            RequestSigner::new(key)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+enum Algorithm {
+    Hmac(SecretString),
+    Ecdsa(Arc<TransactionSigner>),
+}
+
+/// Produces the signature headers for a request
+#[derive(Clone)]
+pub struct RequestSigner {
+    key_id: String,
+    algorithm: Algorithm,
+}
+
+impl RequestSigner {
+    pub fn new(key: &SigningKey) -> Result<Self, String> {
+        let (key_id, algorithm) = match key {
+            SigningKey::HmacSha256 { key_id, secret } => {
+                if secret.is_empty() {
+                    return Err(format!("request signing key {} has an empty secret", key_id));
+                }
+                (key_id, Algorithm::Hmac(secret.clone()))
+            }
+            SigningKey::EcdsaSecp256k1 { key_id, private_key } => {
+                let bytes = zeroize::Zeroizing::new(
+                    hex::decode(private_key.expose().trim_start_matches("0x"))
+                        .map_err(|_| format!("request signing key {} is not valid hex", key_id))?,
+                );
+                let secret = k256::SecretKey::from_slice(&bytes)
+                    .map_err(|_| format!("request signing key {} is not a valid secp256k1 key", key_id))?;
+                (key_id, Algorithm::Ecdsa(Arc::new(TransactionSigner::new(secret))))
+            }
+        };
+        Ok(Self {
+            key_id: key_id.clone(),
+            algorithm,
+        })
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Signs a request with the current time and a random nonce
+    pub fn sign(&self, method: &str, path: &str, body: &[u8]) -> Vec<(&'static str, String)> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let mut nonce = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        self.sign_at(method, path, body, timestamp, &hex::encode(nonce))
+    }
+
+    /// Signs a request with a fixed timestamp and nonce
+    pub fn sign_at(&self, method: &str, path: &str, body: &[u8], timestamp: i64, nonce: &str) -> Vec<(&'static str, String)> {
+        let digest = hex::encode(Sha256::digest(body));
+        let canonical = canonical_request(method, path, timestamp, nonce, &digest);
+        let signature = match &self.algorithm {
+            Algorithm::Hmac(secret) => {
+                let message = SigningDomain::Intent.separate(canonical.as_bytes());
+                format!("hmac-sha256={}", hex::encode(crypto::hmac_sha256(secret.expose().as_bytes(), &message)))
+            }
+            Algorithm::Ecdsa(signer) => {
+                // Signing with a valid key cannot fail
+                let signature = signer
+                    .sign_in_domain(SigningDomain::Intent, canonical.as_bytes())
+                    .unwrap_or_default();
+                format!("ecdsa-secp256k1={}", signature.trim_start_matches("0x"))
+            }
+        };
+        vec![
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (NONCE_HEADER, nonce.to_string()),
+            (CONTENT_DIGEST_HEADER, digest),
+            (KEY_ID_HEADER, self.key_id.clone()),
+            (SIGNATURE_HEADER, signature),
+        ]
+    }
+}
+
+/// String covered by the signature: method, path (with query), timestamp,
+/// nonce and hex body digest, separated by newlines
+pub fn canonical_request(method: &str, path: &str, timestamp: i64, nonce: &str, body_sha256_hex: &str) -> String {
+    format!("{}\n{}\n{}\n{}\n{}", method.to_ascii_uppercase(), path, timestamp, nonce, body_sha256_hex)
+}
+
+/// Signs the requests sent to the API host
+#[cfg(feature = "client")]
+#[derive(Clone)]
+pub(crate) struct ApiSigner {
+    api_host: String,
+    signer: RequestSigner,
+}
+
+#[cfg(feature = "client")]
+impl ApiSigner {
+    /// Signer for the host of `api_endpoint` with the key configured for
+    /// `environment`, if there is one
+    pub(crate) fn new(config: &RequestSigningConfig, environment: &str, api_endpoint: &str) -> Result<Option<Self>, String> {
+        let api_host = crate::http::HttpUrl::parse(api_endpoint)?.host;
+        Ok(config
+            .signer_for(environment)?
+            .map(|signer| Self { api_host, signer }))
+    }
+
+    /// Signature headers for a request to `host`, if it is the API host
+    pub(crate) fn headers_for(&self, host: &str, method: &str, path: &str, body: &[u8]) -> Vec<(&'static str, String)> {
+        if self.api_host.eq_ignore_ascii_case(host) {
+            self.signer.sign(method, path, body)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header<'a>(headers: &'a [(&'static str, String)], name: &str) -> &'a str {
+        &headers.iter().find(|(n, _)| *n == name).unwrap().1
+    }
+
+    #[test]
+    fn test_hmac_signature() {
+        let key = SigningKey::HmacSha256 {
+            key_id: "key_1".to_string(),
+            secret: SecretString::from("s3cret"),
+        };
+        let signer = RequestSigner::new(&key).unwrap();
+        let headers = signer.sign_at("post", "/v1/intents?dry_run=1", b"{\"a\":1}", 1_700_000_000, "abcd");
+
+        let digest = hex::encode(Sha256::digest(b"{\"a\":1}"));
+        assert_eq!(header(&headers, CONTENT_DIGEST_HEADER), digest);
+        assert_eq!(header(&headers, KEY_ID_HEADER), "key_1");
+        let canonical = canonical_request("POST", "/v1/intents?dry_run=1", 1_700_000_000, "abcd", &digest);
+        let message = SigningDomain::Intent.separate(canonical.as_bytes());
+        let expected = format!("hmac-sha256={}", hex::encode(crypto::hmac_sha256(b"s3cret", &message)));
+        assert_eq!(header(&headers, SIGNATURE_HEADER), expected);
+
+        // Any change to the covered fields changes the signature
+        let other = signer.sign_at("POST", "/v1/intents?dry_run=1", b"{\"a\":2}", 1_700_000_000, "abcd");
+        assert_ne!(header(&other, SIGNATURE_HEADER), expected);
+        let fresh = signer.sign("POST", "/v1/intents", b"");
+        assert_ne!(header(&fresh, NONCE_HEADER), header(&signer.sign("POST", "/v1/intents", b""), NONCE_HEADER));
+    }
+
+    #[test]
+    fn test_ecdsa_signature_verifies() {
+        let private_key = hex::encode([7u8; 32]);
+        let key: SigningKey = serde_json::from_value(serde_json::json!({
+            "algorithm": "ecdsa_secp256k1",
+            "key_id": "key_2",
+            "private_key": private_key,
+        }))
+        .unwrap();
+        let signer = RequestSigner::new(&key).unwrap();
+        let headers = signer.sign_at("GET", "/v1/stream", b"", 1_700_000_000, "ffee");
+
+        let signature = header(&headers, SIGNATURE_HEADER).strip_prefix("ecdsa-secp256k1=").unwrap();
+        let canonical = canonical_request("GET", "/v1/stream", 1_700_000_000, "ffee", header(&headers, CONTENT_DIGEST_HEADER));
+        let verifying_key = TransactionSigner::new(k256::SecretKey::from_slice(&[7u8; 32]).unwrap()).verifying_key();
+        let domain = SigningDomain::Intent;
+        let hash = crate::crypto::hash::HashFunction::Sha256;
+        assert!(crypto::verify_signature_in_domain(hash, domain, &verifying_key, canonical.as_bytes(), signature).unwrap());
+        assert!(!crypto::verify_signature(&verifying_key, canonical.as_bytes(), signature).unwrap());
+    }
+
+    #[test]
+    fn test_config_per_environment() {
+        let config: RequestSigningConfig = serde_json::from_str(
+            r#"{
+                "key": {"algorithm": "hmac_sha256", "key_id": "prod", "secret": "a"},
+                "environments": {"testnet": {"algorithm": "hmac_sha256", "key_id": "test", "secret": "b"}}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.signer_for("mainnet").unwrap().unwrap().key_id(), "prod");
+        assert_eq!(config.signer_for("testnet").unwrap().unwrap().key_id(), "test");
+        assert!(RequestSigningConfig::default().signer_for("mainnet").unwrap().is_none());
+
+        let invalid = RequestSigningConfig {
+            key: Some(SigningKey::EcdsaSecp256k1 {
+                key_id: "bad".to_string(),
+                private_key: SecretString::from("zz"),
+            }),
+            ..Default::default()
+        };
+        assert!(invalid.validate().unwrap_err().contains("not valid hex"));
+    }
+}
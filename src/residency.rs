@@ -0,0 +1,128 @@
+//! Data-residency constraints on agent routing.
+//!
+//! Regulated custodians may only route through agents operating in certain
+//! jurisdictions. `ResidencyConfig` lists the permitted regions (ISO 3166-1
+//! alpha-2 country codes or the `EU` / `EEA` groups) and countries to
+//! exclude; quotes from agents outside them are discarded before selection.
+//! Once any constraint is set, agents that report no region are excluded too.
+//!
+//! ```
+//! use ecash_sdk_core::residency::ResidencyConfig;
+//!
+//! let eu_only = ResidencyConfig::default().allow("EU").exclude("HU");
+//! assert!(eu_only.permits(Some("de")));
+//! assert!(!eu_only.permits(Some("HU")));
+//! assert!(!eu_only.permits(Some("US")));
+//! assert!(!eu_only.permits(None));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// EU member states
+const EU: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT", "LT", "LU",
+    "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+/// EEA members outside the EU
+const EEA_ONLY: &[&str] = &["IS", "LI", "NO"];
+
+/// Jurisdictions agents may execute from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResidencyConfig {
+    /// Country codes or groups (`EU`, `EEA`) agents must be in; empty allows any
+    #[serde(default)]
+    pub allowed_regions: Vec<String>,
+    /// Country codes agents must not be in, even if in an allowed group
+    #[serde(default)]
+    pub excluded_countries: Vec<String>,
+}
+
+impl ResidencyConfig {
+    pub fn allow(mut self, region: impl Into<String>) -> Self {
+        self.allowed_regions.push(region.into());
+        self
+    }
+
+    pub fn exclude(mut self, country: impl Into<String>) -> Self {
+        self.excluded_countries.push(country.into());
+        self
+    }
+
+    /// True if any constraint is configured
+    pub fn is_restricted(&self) -> bool {
+        !self.allowed_regions.is_empty() || !self.excluded_countries.is_empty()
+    }
+
+    /// True if an agent in `region` may be routed through
+    pub fn permits(&self, region: Option<&str>) -> bool {
+        if !self.is_restricted() {
+            return true;
+        }
+        let Some(country) = region.map(|r| r.trim().to_ascii_uppercase()) else {
+            return false;
+        };
+        if self.excluded_countries.iter().any(|c| c.eq_ignore_ascii_case(&country)) {
+            return false;
+        }
+        self.allowed_regions.is_empty() || self.allowed_regions.iter().any(|r| region_contains(r, &country))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for region in &self.allowed_regions {
+            if !is_group(region) && !is_country_code(region) {
+                return Err(format!("residency.allowed_regions: unknown region {:?}", region));
+            }
+        }
+        for country in &self.excluded_countries {
+            if is_group(country) || !is_country_code(country) {
+                return Err(format!("residency.excluded_countries: invalid country code {:?}", country));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_group(region: &str) -> bool {
+    region.eq_ignore_ascii_case("EU") || region.eq_ignore_ascii_case("EEA")
+}
+
+fn is_country_code(code: &str) -> bool {
+    code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// True if `country` (upper-case) is `region` or one of its members
+fn region_contains(region: &str, country: &str) -> bool {
+    if region.eq_ignore_ascii_case("EU") {
+        EU.contains(&country)
+    } else if region.eq_ignore_ascii_case("EEA") {
+        EU.contains(&country) || EEA_ONLY.contains(&country)
+    } else {
+        region.eq_ignore_ascii_case(country)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_and_exclusions() {
+        let unrestricted = ResidencyConfig::default();
+        assert!(unrestricted.permits(None) && unrestricted.permits(Some("US")));
+
+        let eea = ResidencyConfig::default().allow("eea");
+        assert!(eea.permits(Some("NO")) && eea.permits(Some("FR")));
+        assert!(!eea.permits(Some("CH")));
+        assert!(!ResidencyConfig::default().allow("EU").permits(Some("NO")));
+
+        let not_us = ResidencyConfig::default().exclude("US");
+        assert!(not_us.permits(Some("SG")));
+        assert!(!not_us.permits(Some("us")));
+        assert!(!not_us.permits(None));
+
+        let config: ResidencyConfig = serde_json::from_str(r#"{"allowed_regions": ["EU", "CH"]}"#).unwrap();
+        assert!(config.permits(Some("CH")) && config.validate().is_ok());
+        assert!(ResidencyConfig::default().allow("Europe").validate().is_err());
+        assert!(ResidencyConfig::default().exclude("EU").validate().is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Test funds and throwaway accounts for the sandbox environments.
+//!
+//! With `SdkConfig::environment` set to "testnet" or "devnet", the client
+//! talks to the sandbox's faucet: `EasyCashClient::request_test_funds` sends
+//! test tokens to an address, `create_test_account` generates a fresh EVM
+//! account and `fund_test_account` funds it. On mainnet these helpers fail
+//! with `INVALID_REQUEST`.
+
+use std::time::Duration;
+
+use k256::SecretKey;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::TransactionSigner;
+use crate::evm;
+use crate::http::HttpClient;
+use crate::secrets::SecretString;
+use crate::types::ChainId;
+
+/// Test funds asked of a faucet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaucetRequest {
+    pub chain: ChainId,
+    pub address: String,
+    pub asset: String,
+}
+
+/// Test funds sent by a faucet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaucetGrant {
+    pub chain: ChainId,
+    pub address: String,
+    pub asset: String,
+    /// Amount sent, in units of `asset`
+    pub amount: String,
+    pub tx_hash: String,
+}
+
+/// Source of sandbox test funds
+#[async_trait::async_trait]
+pub trait Faucet: Send + Sync {
+    async fn request_funds(&self, req: &FaucetRequest) -> Result<FaucetGrant, String>;
+}
+
+/// Faucet behind the sandbox's HTTP API (`Environment::faucet_endpoint`).
+///
+/// POSTs the `FaucetRequest` as JSON and expects a `FaucetGrant`.
+pub struct HttpFaucet {
+    endpoint: String,
+    api_key: Option<SecretString>,
+    timeout: Duration,
+    http: HttpClient,
+}
+
+impl HttpFaucet {
+    pub fn new(endpoint: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            api_key: None,
+            timeout,
+            http: HttpClient::default(),
+        }
+    }
+
+    /// Sends the key as a bearer token with every request
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into().into());
+        self
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Faucet for HttpFaucet {
+    async fn request_funds(&self, req: &FaucetRequest) -> Result<FaucetGrant, String> {
+        let body = serde_json::to_value(req).map_err(|e| format!("failed to encode request: {}", e))?;
+        let auth = self.api_key.as_ref().map(|k| format!("Bearer {}", k.expose()));
+        let headers: Vec<(&str, &str)> = auth.iter().map(|a| ("Authorization", a.as_str())).collect();
+
+        let resp = self.http.post_json(&self.endpoint, &headers, &body, self.timeout).await?;
+        match resp.status {
+            429 => Err("faucet limit reached for this address; try again later".to_string()),
+            _ if !resp.is_success() => Err(format!("faucet returned status {}", resp.status)),
+            _ => serde_json::from_str(&resp.body).map_err(|e| format!("invalid faucet response: {}", e)),
+        }
+    }
+}
+
+/// Throwaway EVM account for sandbox testing
+pub struct TestAccount {
+    pub chain: ChainId,
+    /// Checksummed address
+    pub address: String,
+    signer: TransactionSigner,
+}
+
+impl TestAccount {
+    /// Account with a fresh random key on `chain`
+    pub fn generate(chain: ChainId) -> Result<Self, String> {
+        if evm::chain_id(chain).is_none() {
+            return Err(format!("test accounts can only be generated on EVM chains, not {}", chain));
+        }
+        let signer = TransactionSigner::new(SecretKey::random(&mut rand::rngs::OsRng));
+        Ok(Self {
+            chain,
+            address: evm::address(&signer.verifying_key()),
+            signer,
+        })
+    }
+
+    /// Signer holding the account's key
+    pub fn signer(&self) -> &TransactionSigner {
+        &self.signer
+    }
+}
+
+/// Chain test funds for `address` are sent on: Base for EVM addresses,
+/// Solana otherwise
+pub fn chain_of(address: &str) -> ChainId {
+    match evm::parse_address(address) {
+        Ok(_) => ChainId::Base,
+        Err(_) => ChainId::Solana,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_account() {
+        let account = TestAccount::generate(ChainId::Base).unwrap();
+        assert_eq!(account.address, evm::address(&account.signer().verifying_key()));
+        assert_eq!(chain_of(&account.address), ChainId::Base);
+        assert_eq!(chain_of("7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV"), ChainId::Solana);
+        assert!(TestAccount::generate(ChainId::Solana).is_err());
+    }
+}
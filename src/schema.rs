@@ -0,0 +1,336 @@
+//! JSON Schema (draft 2020-12) and OpenAPI 3.1 descriptions of the wire types.
+//!
+//! Gateway teams generate client models in other languages from these
+//! instead of transcribing the Rust structs. The wire types derive
+//! `schemars::JsonSchema` under this feature, so the schemas follow their
+//! serde attributes: optional fields are the ones the SDK omits when unset
+//! or fills in when missing.
+//!
+//! ```
+//! use ecash_sdk_core::schema;
+//! use ecash_sdk_core::TransactionRequest;
+//!
+//! let schema = schema::schema_for::<TransactionRequest>();
+//! assert_eq!(schema["properties"]["source_chain"]["$ref"], "#/$defs/ChainId");
+//!
+//! let doc = schema::openapi();
+//! assert!(doc["components"]["schemas"]["SdkErrorResponse"].is_object());
+//! ```
+
+use schemars::generate::SchemaSettings;
+use serde_json::{json, Value};
+
+use crate::errors::SdkErrorResponse;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+pub use schemars::JsonSchema;
+
+/// Standalone JSON Schema of `T`, with the types it uses under `$defs`
+pub fn schema_for<T: JsonSchema>() -> Value {
+    schemars::schema_for!(T).to_value()
+}
+
+/// OpenAPI 3.1 document whose `components/schemas` hold every public wire
+/// type: requests, responses, error bodies and webhook payloads
+pub fn openapi() -> Value {
+    let settings = SchemaSettings::draft2020_12().with(|s| s.definitions_path = "/components/schemas".into());
+    let dialect = settings.meta_schema.clone();
+    let mut gen = settings.into_generator();
+    gen.subschema_for::<TransactionRequest>();
+    gen.subschema_for::<TransactionResponse>();
+    gen.subschema_for::<SdkErrorResponse>();
+    #[cfg(feature = "client")]
+    gen.subschema_for::<crate::webhooks::WebhookDelivery>();
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "EasyCash SDK types",
+            "version": env!("CARGO_PKG_VERSION"),
+            "x-protocol-version": crate::protocol::CURRENT_VERSION,
+        },
+        "jsonSchemaDialect": dialect,
+        "paths": {},
+        "components": { "schemas": gen.take_definitions(true) },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use crate::travel_rule::{TravelRuleInfo, TravelRuleParty, Vasp};
+    use crate::types::{ChainId, DisbursementResult, FeeSplitAmount, IntentType};
+
+    /// Checks `value` against `schema`, treating keys the schema doesn't
+    /// declare as errors so that new fields must be added to the schema
+    fn check(schema: &Value, value: &Value, defs: &Value) -> Result<(), String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.rsplit('/').next().unwrap();
+            let target = defs.get(name).ok_or_else(|| format!("dangling {}", reference))?;
+            return check(target, value, defs);
+        }
+        if let Some(variants) = schema.get("oneOf").and_then(Value::as_array) {
+            let matches = variants.iter().filter(|v| check(v, value, defs).is_ok()).count();
+            if matches != 1 {
+                return Err(format!("{} matches {} variants", value, matches));
+            }
+        }
+        if let Some(variants) = schema.get("anyOf").and_then(Value::as_array) {
+            if !variants.iter().any(|v| check(v, value, defs).is_ok()) {
+                return Err(format!("{} matches no variant", value));
+            }
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+            if !allowed.contains(value) {
+                return Err(format!("{} not in enum", value));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                return Err(format!("{} != {}", value, expected));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            let text = value.as_str().unwrap_or_default();
+            if !regex::Regex::new(pattern).unwrap().is_match(text) {
+                return Err(format!("{:?} doesn't match {}", text, pattern));
+            }
+        }
+        let is_type = |name: &str| match name {
+            "string" => value.is_string(),
+            "boolean" => value.is_boolean(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        let ok = match schema.get("type") {
+            Some(Value::String(name)) => is_type(name),
+            Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).any(is_type),
+            _ => true,
+        };
+        if !ok {
+            return Err(format!("{} is not of type {}", value, schema["type"]));
+        }
+        if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+            for (i, item) in array.iter().enumerate() {
+                check(items, item, defs).map_err(|e| format!("[{}]: {}", i, e))?;
+            }
+        }
+        if let (Some(properties), Some(object)) = (schema.get("properties"), value.as_object()) {
+            for (key, field) in object {
+                let field_schema = properties.get(key).ok_or_else(|| format!("undeclared field {}", key))?;
+                check(field_schema, field, defs).map_err(|e| format!("{}: {}", key, e))?;
+            }
+            for key in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                if !object.contains_key(key.as_str().unwrap()) {
+                    return Err(format!("missing required field {}", key));
+                }
+            }
+        }
+        if let (Some(values), Some(object)) = (schema.get("additionalProperties"), value.as_object()) {
+            for (key, field) in object {
+                check(values, field, defs).map_err(|e| format!("{}: {}", key, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn assert_matches<T: JsonSchema + serde::Serialize>(value: &T) {
+        let schema = schema_for::<T>();
+        let defs = schema.get("$defs").cloned().unwrap_or_default();
+        let json = serde_json::to_value(value).unwrap();
+        if let Err(e) = check(&schema, &json, &defs) {
+            panic!("{} doesn't match its schema: {}", json, e);
+        }
+    }
+
+    fn request() -> TransactionRequest {
+        TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+    }
+
+    #[test]
+    fn test_request_and_response_match_schema() {
+        assert_matches(&request());
+        let party = TravelRuleParty {
+            name: "Alice".to_string(),
+            account_number: Some("0xabc".to_string()),
+            geographic_address: Some("1 Main St".to_string()),
+            national_identifier: Some("X1".to_string()),
+            date_of_birth: Some("1990-01-01".to_string()),
+            country_of_residence: Some("DE".to_string()),
+        };
+        let vasp = Vasp {
+            name: "VASP A".to_string(),
+            lei: Some("5493001KJTIIGC8Y1R12".to_string()),
+            public_key: Some("02ab".to_string()),
+        };
+        assert_matches(&TransactionRequest {
+            intent_type: IntentType::Unknown,
+            recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
+            target_chain: Some(ChainId::Unknown),
+            is_shielded: true,
+            travel_rule: Some(TravelRuleInfo {
+                originator: party.clone(),
+                beneficiary: party,
+                originating_vasp: Some(vasp.clone()),
+                beneficiary_vasp: Some(vasp),
+            }),
+            correlation_id: Some("c-1".to_string()),
+            amount_base_units: Some("1000000000".to_string()),
+            account_id: Some("acct-7".to_string()),
+            metadata: [("desk".to_string(), "otc".to_string())].into(),
+            ..request()
+        });
+        assert_matches(&TransactionResponse {
+            tx_hash: "0xabc".to_string(),
+            status: "confirmed".to_string(),
+            block_height: 1948201,
+            fee_used: "0.05 USDC".to_string(),
+            correlation_id: "c-1".to_string(),
+            disbursements: vec![DisbursementResult {
+                recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string(),
+                amount: "10".to_string(),
+                status: "failed".to_string(),
+                error: Some("recipient rejected".to_string()),
+            }],
+            fee_splits: vec![FeeSplitAmount {
+                recipient: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb1".to_string(),
+                bps: 250,
+                amount: "0.25".to_string(),
+            }],
+        });
+
+        let schema = schema_for::<TransactionRequest>();
+        let bad = json!({ "reference_id": "r", "type": "transfer", "amount": "1", "asset": "USDC",
+            "source_chain": "base", "is_shielded": false, "amount_base_units": "1.5" });
+        assert!(check(&schema, &bad, &schema["$defs"]).unwrap_err().contains("amount_base_units"));
+    }
+
+    #[test]
+    fn test_error_codes_match_schema() {
+        let schema = schema_for::<ErrorCode>();
+        let codes: Vec<Value> = schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|variant| match variant.get("const") {
+                Some(code) => vec![code.clone()],
+                None => variant["enum"].as_array().unwrap().clone(),
+            })
+            .collect();
+        // Adding a code breaks this match; the derived schema must list it too
+        let listed = |code: ErrorCode| match code {
+            ErrorCode::InvalidRequest
+            | ErrorCode::InsufficientFunds
+            | ErrorCode::NetworkFailure
+            | ErrorCode::ProofGeneration
+            | ErrorCode::AgentUnavailable
+            | ErrorCode::Timeout
+            | ErrorCode::RateLimited
+            | ErrorCode::FeeTooHigh
+            | ErrorCode::UnsupportedChain
+            | ErrorCode::UnsupportedAsset
+            | ErrorCode::DuplicateReference
+            | ErrorCode::ComplianceRejected
+            | ErrorCode::PolicyViolation
+            | ErrorCode::BudgetExceeded
+            | ErrorCode::Expired
+            | ErrorCode::SignerUnavailable
+            | ErrorCode::UnsupportedProtocol
+            | ErrorCode::Cancelled
+            | ErrorCode::ExecutionReverted => 19,
+        };
+        assert_eq!(codes.len(), listed(ErrorCode::Timeout));
+        for code in &codes {
+            let code: ErrorCode = serde_json::from_value(code.clone()).unwrap();
+            assert_matches(&SdkErrorResponse {
+                code,
+                message: "boom".to_string(),
+                details: json!({ "field": "amount" }),
+                retryable: code.is_retryable(),
+                correlation_id: Some("c-1".to_string()),
+            });
+        }
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_webhook_events_match_schema() {
+        use crate::events::SdkEvent;
+        use crate::webhooks::WebhookDelivery;
+
+        let (reference_id, correlation_id) = ("ref_001".to_string(), "c-1".to_string());
+        let events = [
+            SdkEvent::ValidationFailed { reference_id: reference_id.clone(), correlation_id: correlation_id.clone(), reason: "bad".to_string() },
+            SdkEvent::ProofGenerated { reference_id: reference_id.clone(), correlation_id: correlation_id.clone(), proof: "0x01".to_string() },
+            SdkEvent::QuoteReceived {
+                reference_id: reference_id.clone(),
+                correlation_id: correlation_id.clone(),
+                agent_id: "a".to_string(),
+                estimated_fee: "0.1".to_string(),
+            },
+            SdkEvent::RouteSelected {
+                reference_id: reference_id.clone(),
+                correlation_id: correlation_id.clone(),
+                agent_id: "a".to_string(),
+                estimated_fee: "0.1".to_string(),
+                security_score: 0.9,
+            },
+            SdkEvent::ExecutionStarted { reference_id: reference_id.clone(), correlation_id: correlation_id.clone(), agent_id: "a".to_string() },
+            SdkEvent::QuoteViolated {
+                reference_id: reference_id.clone(),
+                correlation_id: correlation_id.clone(),
+                agent_id: "a".to_string(),
+                quote_hash: "0x01".to_string(),
+                quoted_fee: "0.1".to_string(),
+                fee_used: "0.2".to_string(),
+            },
+            SdkEvent::Confirmed {
+                reference_id: reference_id.clone(),
+                correlation_id: correlation_id.clone(),
+                tx_hash: "0xabc".to_string(),
+                fee_used: "0.1".to_string(),
+                account_id: Some("acct-7".to_string()),
+                metadata: [("desk".to_string(), "otc".to_string())].into(),
+            },
+            SdkEvent::Failed {
+                reference_id,
+                correlation_id,
+                code: ErrorCode::Timeout,
+                message: "slow".to_string(),
+                account_id: None,
+                metadata: Default::default(),
+            },
+        ];
+        for event in events {
+            assert_matches(&WebhookDelivery { id: "evt_1".to_string(), created_at: 1_700_000_000, event });
+        }
+    }
+
+    #[test]
+    fn test_openapi_references_resolve() {
+        let doc = openapi();
+        let schemas = &doc["components"]["schemas"];
+        fn walk(value: &Value, schemas: &Value) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(reference)) = map.get("$ref") {
+                        let name = reference.strip_prefix("#/components/schemas/").unwrap();
+                        assert!(schemas.get(name).is_some(), "dangling {}", reference);
+                    }
+                    map.values().for_each(|v| walk(v, schemas));
+                }
+                Value::Array(items) => items.iter().for_each(|v| walk(v, schemas)),
+                _ => {}
+            }
+        }
+        walk(schemas, schemas);
+        for name in ["TransactionRequest", "TransactionResponse", "SdkErrorResponse", "TravelRuleInfo", "ErrorCode"] {
+            assert!(schemas.get(name).is_some(), "{} missing", name);
+        }
+        assert_eq!(doc["info"]["x-protocol-version"], crate::protocol::CURRENT_VERSION);
+    }
+}
@@ -0,0 +1,95 @@
+//! Wrapper for credentials that must not leak into logs or config dumps.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer};
+use zeroize::Zeroize;
+
+/// String secret (e.g. an API key).
+///
+/// `Debug` and `Display` print `***`, it deliberately doesn't implement
+/// `Serialize` (mark fields holding it `#[serde(skip_serializing)]`), and
+/// its memory is zeroed when dropped. Use `expose` where the raw value is
+/// actually needed, such as an `Authorization` header.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Returns the secret itself
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(***)")
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+impl Zeroize for SecretString {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_formatting() {
+        let secret = SecretString::new("sk_live_123");
+        assert_eq!(format!("{:?}", secret), "SecretString(***)");
+        assert_eq!(secret.to_string(), "***");
+        assert_eq!(secret.expose(), "sk_live_123");
+
+        let parsed: SecretString = serde_json::from_str("\"sk_live_123\"").unwrap();
+        assert_eq!(parsed, secret);
+    }
+
+    #[test]
+    fn test_zeroize_clears_value() {
+        let mut secret = SecretString::new("sk_live_123");
+        secret.zeroize();
+        assert!(secret.is_empty());
+    }
+}
@@ -0,0 +1,423 @@
+//! Deterministic simulation of the agent network.
+//!
+//! `SimulationEnvironment` replaces the agent negotiator with simulated agents
+//! whose latencies, fees and failures are drawn from a seeded RNG. Combined
+//! with Tokio's paused clock, a run with the same seed and the same requests
+//! produces the same quotes, failures, routing decisions and circuit-breaker
+//! transitions every time, without waiting in real time. Use it for
+//! reproducible load and chaos tests.
+//!
+//! Runs are reproducible as long as requests are issued in a deterministic
+//! order, e.g. sequentially or from a single-threaded runtime.
+//!
+//! # Example
+//! ```
+//! use ecash_sdk_core::simulation::{SimulatedAgent, SimulationEnvironment};
+//! use std::time::Duration;
+//!
+//! let env = SimulationEnvironment::new(42)
+//!    .with_agent(SimulatedAgent::new("fast").latency(Duration::from_millis(200), Duration::from_millis(50)))
+//!    .with_agent(SimulatedAgent::new("flaky").failure_rate(0.3));
+//! let client = env.client();
+//! env.runtime().block_on(async {
+//!     let requests = Vec::new(); // your TransactionRequests
+//!     let report = env.run(&client, &requests).await;
+//!     println!("{} ok, {} failed in {:?} of virtual time", report.succeeded, report.failed, report.elapsed);
+//! });
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tokio::time::Instant;
+
+use crate::agent::{self, AgentNegotiatorTrait, RouteQuote};
+use crate::client::EasyCashClient;
+use crate::config::SdkConfig;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+/// Behaviour of one simulated agent
+#[derive(Debug, Clone)]
+pub struct SimulatedAgent {
+    pub agent_id: String,
+    /// Mean fee in units of the requested asset
+    pub fee: f64,
+    /// Fees vary uniformly by up to this much either way
+    pub fee_jitter: f64,
+    /// Mean response latency, for both quoting and execution
+    pub latency: Duration,
+    pub latency_jitter: Duration,
+    /// Estimated settlement time advertised in quotes
+    pub settlement_time: Duration,
+    pub security_score: f64,
+    /// Probability (0.0 - 1.0) that the agent doesn't answer a quote request
+    pub quote_failure_rate: f64,
+    /// Probability (0.0 - 1.0) that an execution fails
+    pub failure_rate: f64,
+}
+
+impl SimulatedAgent {
+    /// A reliable agent charging 0.05 with 100ms latency
+    pub fn new(agent_id: impl Into<String>) -> Self {
+        Self {
+            agent_id: agent_id.into(),
+            fee: 0.05,
+            fee_jitter: 0.0,
+            latency: Duration::from_millis(100),
+            latency_jitter: Duration::ZERO,
+            settlement_time: Duration::from_secs(15),
+            security_score: 0.9,
+            quote_failure_rate: 0.0,
+            failure_rate: 0.0,
+        }
+    }
+
+    pub fn fee(mut self, mean: f64, jitter: f64) -> Self {
+        self.fee = mean;
+        self.fee_jitter = jitter;
+        self
+    }
+
+    pub fn latency(mut self, mean: Duration, jitter: Duration) -> Self {
+        self.latency = mean;
+        self.latency_jitter = jitter;
+        self
+    }
+
+    pub fn settlement_time(mut self, time: Duration) -> Self {
+        self.settlement_time = time;
+        self
+    }
+
+    pub fn security(mut self, score: f64) -> Self {
+        self.security_score = score;
+        self
+    }
+
+    pub fn quote_failure_rate(mut self, rate: f64) -> Self {
+        self.quote_failure_rate = rate;
+        self
+    }
+
+    pub fn failure_rate(mut self, rate: f64) -> Self {
+        self.failure_rate = rate;
+        self
+    }
+}
+
+/// What happened during a simulation step
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationEventKind {
+    Quoted { fee: String },
+    QuoteDropped,
+    Executed,
+    ExecutionFailed,
+}
+
+/// Entry in the simulation trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationEvent {
+    /// Virtual time since the first simulated call
+    pub at: Duration,
+    pub reference_id: String,
+    pub agent_id: String,
+    pub kind: SimulationEventKind,
+}
+
+/// Outcome of `SimulationEnvironment::run`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationReport {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Failure count per error code
+    pub errors: BTreeMap<String, usize>,
+    /// Successful executions per agent
+    pub executions_by_agent: BTreeMap<String, usize>,
+    /// Virtual time taken by the run
+    pub elapsed: Duration,
+}
+
+struct SimulationState {
+    rng: StdRng,
+    started: Option<Instant>,
+    trace: Vec<SimulationEvent>,
+}
+
+/// Seeded, simulated agent network.
+///
+/// Clones share the RNG and trace.
+#[derive(Clone)]
+pub struct SimulationEnvironment {
+    agents: Vec<SimulatedAgent>,
+    state: Arc<Mutex<SimulationState>>,
+}
+
+impl SimulationEnvironment {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            agents: Vec::new(),
+            state: Arc::new(Mutex::new(SimulationState {
+                rng: StdRng::seed_from_u64(seed),
+                started: None,
+                trace: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn with_agent(mut self, agent: SimulatedAgent) -> Self {
+        self.agents.push(agent);
+        self
+    }
+
+    /// Single-threaded runtime with a paused clock: sleeps complete
+    /// instantly, in a deterministic order
+    pub fn runtime(&self) -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .start_paused(true)
+            .build()
+            .expect("failed to build simulation runtime")
+    }
+
+    /// Client with default configuration (caching off) wired to the simulation
+    pub fn client(&self) -> EasyCashClient {
+        let config = SdkConfig {
+            enable_caching: false,
+            ..SdkConfig::default_config()
+        };
+        self.client_with_config(config)
+    }
+
+    /// Client with the given configuration wired to the simulation
+    pub fn client_with_config(&self, config: SdkConfig) -> EasyCashClient {
+        EasyCashClient::new(Some(config))
+            .expect("invalid simulation configuration")
+            .with_negotiator(Arc::new(self.clone()))
+    }
+
+    /// Executes `requests` one after another and summarizes the outcomes
+    pub async fn run(&self, client: &EasyCashClient, requests: &[TransactionRequest]) -> SimulationReport {
+        let started = Instant::now();
+        let seen = self.lock().trace.len();
+        let mut report = SimulationReport::default();
+        for req in requests {
+            match client.execute_transaction(req).await {
+                Ok(_) => report.succeeded += 1,
+                Err(e) => {
+                    report.failed += 1;
+                    *report.errors.entry(e.code.to_string()).or_default() += 1;
+                }
+            }
+        }
+        for event in self.trace().into_iter().skip(seen) {
+            if event.kind == SimulationEventKind::Executed {
+                *report.executions_by_agent.entry(event.agent_id).or_default() += 1;
+            }
+        }
+        report.elapsed = started.elapsed();
+        report
+    }
+
+    /// Everything the simulated agents did so far, in order
+    pub fn trace(&self) -> Vec<SimulationEvent> {
+        self.lock().trace.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SimulationState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record(state: &mut SimulationState, reference_id: &str, agent_id: &str, kind: SimulationEventKind) {
+        let started = *state.started.get_or_insert_with(Instant::now);
+        state.trace.push(SimulationEvent {
+            at: started.elapsed(),
+            reference_id: reference_id.to_string(),
+            agent_id: agent_id.to_string(),
+            kind,
+        });
+    }
+}
+
+fn sample_latency(rng: &mut StdRng, agent: &SimulatedAgent) -> Duration {
+    if agent.latency_jitter.is_zero() {
+        return agent.latency;
+    }
+    let low = agent.latency.saturating_sub(agent.latency_jitter);
+    let high = agent.latency + agent.latency_jitter;
+    rng.gen_range(low..=high)
+}
+
+#[async_trait::async_trait]
+impl AgentNegotiatorTrait for SimulationEnvironment {
+    async fn request_quotes(&self, req: &TransactionRequest) -> Result<Vec<RouteQuote>, String> {
+        // Agents are asked in parallel, so the round-trip is the slowest answer
+        let mut slowest = Duration::ZERO;
+        let mut answers = Vec::new();
+        {
+            let mut state = self.lock();
+            for agent in &self.agents {
+                let latency = sample_latency(&mut state.rng, agent);
+                if state.rng.gen_bool(agent.quote_failure_rate.clamp(0.0, 1.0)) {
+                    answers.push((agent, latency, None));
+                    continue;
+                }
+                slowest = slowest.max(latency);
+                let jitter = if agent.fee_jitter > 0.0 {
+                    state.rng.gen_range(-agent.fee_jitter..=agent.fee_jitter)
+                } else {
+                    0.0
+                };
+                answers.push((agent, latency, Some((agent.fee + jitter).max(0.0))));
+            }
+        }
+        tokio::time::sleep(slowest).await;
+
+        let mut state = self.lock();
+        let mut quotes = Vec::new();
+        for (agent, _, fee) in answers {
+            let Some(fee) = fee else {
+                Self::record(&mut state, &req.reference_id, &agent.agent_id, SimulationEventKind::QuoteDropped);
+                continue;
+            };
+            let fee = format!("{:.4} {}", fee, req.asset);
+            Self::record(
+                &mut state,
+                &req.reference_id,
+                &agent.agent_id,
+                SimulationEventKind::Quoted { fee: fee.clone() },
+            );
+            let mut route = vec![req.source_chain.as_str().to_string()];
+            if let Some(target) = req.target_chain {
+                route.push(target.as_str().to_string());
+            }
+            quotes.push(RouteQuote {
+                agent_id: agent.agent_id.clone(),
+                estimated_fee: fee,
+                estimated_time: agent.settlement_time,
+                route,
+                security_score: agent.security_score,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
+            });
+        }
+        if quotes.is_empty() {
+            return Err("no simulated agent answered".to_string());
+        }
+        Ok(quotes)
+    }
+
+    async fn execute(&self, req: &TransactionRequest, route: &RouteQuote) -> Result<TransactionResponse, String> {
+        let agent = self
+            .agents
+            .iter()
+            .find(|a| a.agent_id == route.agent_id)
+            .ok_or_else(|| format!("unknown simulated agent {}", route.agent_id))?;
+        let (latency, fails) = {
+            let mut state = self.lock();
+            let latency = sample_latency(&mut state.rng, agent);
+            (latency, state.rng.gen_bool(agent.failure_rate.clamp(0.0, 1.0)))
+        };
+        tokio::time::sleep(latency).await;
+
+        let kind = if fails {
+            SimulationEventKind::ExecutionFailed
+        } else {
+            SimulationEventKind::Executed
+        };
+        Self::record(&mut self.lock(), &req.reference_id, &agent.agent_id, kind);
+        if fails {
+            return Err(format!("simulated failure in {}", agent.agent_id));
+        }
+        // Simulated agents charge what they quoted
+        Ok(TransactionResponse::executed(route.estimated_fee.clone()))
+    }
+
+    fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> Result<RouteQuote, String> {
+        agent::select_best_route(quotes, preference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::{CircuitBreakerConfig, CircuitState};
+    use crate::types::{ChainId, IntentType};
+
+    fn requests(n: usize) -> Vec<TransactionRequest> {
+        (0..n)
+            .map(|i| TransactionRequest::new(format!("sim_{}", i), IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+                .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0"))
+            .collect()
+    }
+
+    fn environment(seed: u64) -> SimulationEnvironment {
+        SimulationEnvironment::new(seed)
+            .with_agent(
+                SimulatedAgent::new("steady")
+                    .fee(0.05, 0.02)
+                    .latency(Duration::from_millis(300), Duration::from_millis(200))
+                    .security(0.95),
+            )
+            .with_agent(
+                SimulatedAgent::new("flaky")
+                    .fee(0.01, 0.005)
+                    .quote_failure_rate(0.2)
+                    .failure_rate(0.5)
+                    .security(0.97),
+            )
+    }
+
+    fn simulate(seed: u64) -> (SimulationReport, Vec<SimulationEvent>) {
+        let env = environment(seed);
+        let client = env.client();
+        let report = env.runtime().block_on(env.run(&client, &requests(30)));
+        (report, env.trace())
+    }
+
+    #[test]
+    fn test_same_seed_is_reproducible() {
+        let (report, trace) = simulate(7);
+        assert_eq!(report.succeeded + report.failed, 30);
+        assert!(report.failed > 0);
+        assert!(report.elapsed >= Duration::from_secs(3));
+        assert_eq!(simulate(7), (report, trace.clone()));
+        assert_ne!(simulate(8).1, trace);
+    }
+
+    #[test]
+    fn test_circuit_breaker_under_virtual_time() {
+        let env = SimulationEnvironment::new(1).with_agent(SimulatedAgent::new("down").failure_rate(1.0));
+        let config = SdkConfig {
+            enable_caching: false,
+            circuit_breaker: CircuitBreakerConfig {
+                minimum_calls: 4,
+                window_size: 4,
+                open_duration: Duration::from_secs(60),
+                ..CircuitBreakerConfig::default()
+            },
+            ..SdkConfig::default_config()
+        };
+        let client = env.client_with_config(config);
+        env.runtime().block_on(async {
+            // Each request records a quote (success) and an execution (failure)
+            let report = env.run(&client, &requests(4)).await;
+            assert_eq!(report.failed, 4);
+            assert_eq!(client.metrics_snapshot().circuit_breaker, CircuitState::Open);
+
+            // Rejected without reaching the agents while open
+            let calls = env.trace().len();
+            assert!(client.execute_transaction(&requests(5)[4]).await.is_err());
+            assert_eq!(env.trace().len(), calls);
+
+            // Trial calls go through once the open period has passed in virtual time
+            tokio::time::advance(Duration::from_secs(61)).await;
+            assert!(client.execute_transaction(&requests(6)[5]).await.is_err());
+            assert!(env.trace().len() > calls);
+        });
+    }
+}
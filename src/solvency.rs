@@ -0,0 +1,159 @@
+//! Solvency pre-checks run before a proof is generated.
+//!
+//! A `BalanceProvider` reports what the payer can spend; the client rejects
+//! requests that cannot cover `amount + fee` before generating a proof or
+//! handing the intent to an agent.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::chain::ChainAdapter;
+use crate::types::TransactionRequest;
+
+/// Source of the payer's spendable balance
+#[async_trait::async_trait]
+pub trait BalanceProvider: Send + Sync {
+    /// Returns the balance of `req.asset` available to the payer, in the same
+    /// (decimal) units as `req.amount`
+    async fn available_balance(&self, req: &TransactionRequest) -> Result<f64, String>;
+}
+
+/// Fixed per-asset balances, useful for tests and custodial ledgers kept elsewhere
+#[derive(Default)]
+pub struct StaticBalanceProvider {
+    balances: RwLock<HashMap<String, f64>>,
+}
+
+impl StaticBalanceProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the available balance of an asset
+    pub fn set_balance(&self, asset: &str, amount: f64) {
+        if let Ok(mut balances) = self.balances.write() {
+            balances.insert(asset.to_uppercase(), amount);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BalanceProvider for StaticBalanceProvider {
+    async fn available_balance(&self, req: &TransactionRequest) -> Result<f64, String> {
+        Ok(self
+            .balances
+            .read()
+            .map_err(|_| "balance provider poisoned".to_string())?
+            .get(&req.asset.to_uppercase())
+            .copied()
+            .unwrap_or(0.0))
+    }
+}
+
+/// Reads the payer's on-chain balance through a chain adapter.
+///
+/// Adapters report balances in smallest units, so each asset's decimals must
+/// be registered with `with_decimals`.
+pub struct ChainBalanceProvider {
+    adapter: Arc<dyn ChainAdapter>,
+    payer: String,
+    decimals: HashMap<String, u32>,
+}
+
+impl ChainBalanceProvider {
+    /// Creates a provider reading the balance of `payer` on the adapter's chain
+    pub fn new(adapter: Arc<dyn ChainAdapter>, payer: impl Into<String>) -> Self {
+        Self {
+            adapter,
+            payer: payer.into(),
+            decimals: HashMap::new(),
+        }
+    }
+
+    /// Registers the number of decimals of an asset (e.g. 6 for USDC)
+    pub fn with_decimals(mut self, asset: &str, decimals: u32) -> Self {
+        self.decimals.insert(asset.to_uppercase(), decimals);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl BalanceProvider for ChainBalanceProvider {
+    async fn available_balance(&self, req: &TransactionRequest) -> Result<f64, String> {
+        if req.source_chain != self.adapter.chain() {
+            return Err(format!(
+                "balance provider is connected to {}, not {}",
+                self.adapter.chain(),
+                req.source_chain
+            ));
+        }
+        let decimals = *self
+            .decimals
+            .get(&req.asset.to_uppercase())
+            .ok_or_else(|| format!("no decimals registered for {}", req.asset))?;
+        let raw = self.adapter.get_balance(&self.payer, &req.asset).await?;
+        let units = raw
+            .parse::<u128>()
+            .map_err(|e| format!("invalid balance {}: {}", raw, e))?;
+        Ok(units as f64 / 10f64.powi(decimals as i32))
+    }
+}
+
+/// Splits a fee string such as "0.05 USDC" into amount and asset
+pub fn parse_fee(fee: &str) -> Option<(f64, &str)> {
+    let mut parts = fee.split_whitespace();
+    let amount = parts.next()?.parse::<f64>().ok()?;
+    Some((amount, parts.next().unwrap_or("")))
+}
+
+/// Amount the payer must hold: the transfer amount plus the fee when the fee
+/// is charged in the same asset
+pub fn required_amount(req: &TransactionRequest, fee: &str) -> Result<f64, String> {
+    let amount = req
+        .amount
+        .parse::<f64>()
+        .map_err(|e| format!("invalid amount {}: {}", req.amount, e))?;
+    match parse_fee(fee) {
+        Some((fee_amount, fee_asset)) if fee_asset.eq_ignore_ascii_case(&req.asset) => Ok(amount + fee_amount),
+        _ => Ok(amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::MockChainAdapter;
+    use crate::types::{ChainId, IntentType};
+
+    fn request(amount: &str) -> TransactionRequest {
+        TransactionRequest::new("ref_001", IntentType::Transfer, amount, "USDC", ChainId::Base)
+            .with_shielded(true)
+    }
+
+    #[test]
+    fn test_required_amount_includes_same_asset_fee() {
+        assert_eq!(required_amount(&request("100"), "0.05 USDC").unwrap(), 100.05);
+        assert_eq!(required_amount(&request("100"), "0.0004 ETH").unwrap(), 100.0);
+        assert!(required_amount(&request("abc"), "0.05 USDC").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_chain_balance_provider_converts_units() {
+        let adapter = Arc::new(MockChainAdapter::new(ChainId::Base));
+        adapter.set_balance("0xpayer", "USDC", "2500000");
+        let provider = ChainBalanceProvider::new(adapter, "0xpayer").with_decimals("usdc", 6);
+
+        assert_eq!(provider.available_balance(&request("1")).await.unwrap(), 2.5);
+
+        let mut other_chain = request("1");
+        other_chain.source_chain = ChainId::Ethereum;
+        assert!(provider.available_balance(&other_chain).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_static_balance_provider() {
+        let provider = StaticBalanceProvider::new();
+        provider.set_balance("usdc", 42.0);
+        assert_eq!(provider.available_balance(&request("1")).await.unwrap(), 42.0);
+    }
+}
@@ -0,0 +1,491 @@
+//! Sponsored network fees.
+//!
+//! By default the payer covers a transaction's network fee. A request's
+//! `FeePayer` can instead charge it to the recipient, deducted from the
+//! amount they receive, or to a registered `Sponsor`: a platform account, or
+//! the paymaster contract paying on its behalf. Sponsors authorize each
+//! request by signing its `authorization_message` in the
+//! `SigningDomain::Sponsorship` domain.
+//!
+//! `EasyCashClient::with_sponsors` checks the authorization and the
+//! sponsor's policy, fee balance and daily limit against the selected
+//! route's fee, and hands the resulting `Sponsorship` to the agent with the
+//! intent. Spend is recorded after execution, so concurrent requests can
+//! briefly overshoot a sponsor's balance by up to the in-flight fees.
+//!
+//! ```
+//! use k256::{PublicKey, SecretKey};
+//! use ecash_sdk_core::crypto::{self, TransactionSigner};
+//! use ecash_sdk_core::sponsorship::{FeePayer, Sponsor, SponsorRegistry};
+//! use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+//!
+//! let platform = TransactionSigner::new(SecretKey::from_bytes(&[4u8; 32].into()).unwrap());
+//! let public_key = crypto::public_key_to_hex(&PublicKey::from(&platform.verifying_key()));
+//! let sponsors = SponsorRegistry::new().with_sponsor(
+//!     Sponsor::new("platform", "0x8ba1f109551bD432803012645Ac136ddd64DBA72", public_key)
+//!        .with_balance("USDC", 100.0)
+//!        .with_max_fee(0.10),
+//! );
+//!
+//! let mut req = TransactionRequest::new("gasless_1", IntentType::Transfer, "20", "USDC", ChainId::Base)
+//!     .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0");
+//! FeePayer::sponsor(&req, "platform", &platform).unwrap().attach(&mut req);
+//!
+//! let payer = FeePayer::of(&req).unwrap();
+//! let sponsorship = sponsors.authorize(&req, &payer, "0.05 USDC").unwrap();
+//! assert_eq!(sponsorship.fee_account, "0x8ba1f109551bD432803012645Ac136ddd64DBA72");
+//! assert!(sponsors.authorize(&req, &payer, "0.50 USDC").is_err());
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use k256::ecdsa::VerifyingKey;
+use serde::{Deserialize, Serialize};
+
+use crate::amount;
+use crate::crypto::hash::HashFunction;
+use crate::crypto::{self, SigningDomain, TransactionSigner};
+use crate::policy::{InMemoryUsageStore, UsageStore};
+use crate::solvency::parse_fee;
+use crate::types::{ChainId, TransactionRequest};
+
+const DAY_MS: u64 = 86_400_000;
+
+pub use crate::types::FeePayer;
+
+impl FeePayer {
+    /// Fee payer of `req`, if it names one
+    pub fn of(req: &TransactionRequest) -> Option<Self> {
+        req.fee_payer.clone()
+    }
+
+    /// Sets the fee payer of `req`
+    pub fn attach(&self, req: &mut TransactionRequest) {
+        req.fee_payer = Some(self.clone());
+    }
+
+    /// `sponsor_id` paying the fee of `req`, authorized with `signer`
+    pub fn sponsor(req: &TransactionRequest, sponsor_id: &str, signer: &TransactionSigner) -> Result<Self, String> {
+        Ok(FeePayer::Sponsor {
+            sponsor_id: sponsor_id.to_string(),
+            signature: signer.sign_in_domain(SigningDomain::Sponsorship, &authorization_message(req, sponsor_id))?,
+        })
+    }
+}
+
+/// Message a sponsor signs to pay the fee of `req`. It covers the intent,
+/// so the authorization can't be reused for another transfer.
+pub fn authorization_message(req: &TransactionRequest, sponsor_id: &str) -> Vec<u8> {
+    serde_json::json!({
+        "reference_id": req.reference_id,
+        "type": req.intent_type,
+        "amount": req.amount,
+        "asset": req.asset,
+        "recipient": req.recipient,
+        "source_chain": req.source_chain,
+        "target_chain": req.target_chain,
+        "sponsor_id": sponsor_id,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+/// Account that pays the fees of the requests it authorizes, and its limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sponsor {
+    pub sponsor_id: String,
+    /// Account the fees are charged to
+    pub account: String,
+    /// Hex public key the sponsor signs authorizations with
+    pub public_key: String,
+    /// Paymaster contract paying the fees on the account's behalf
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<String>,
+    /// Fees the sponsor has deposited, per fee asset
+    #[serde(default)]
+    pub balances: HashMap<String, f64>,
+    /// Highest fee of one request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<f64>,
+    /// Fees per UTC day, in units of the fee's asset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_limit: Option<f64>,
+    /// Assets the sponsor pays fees for; empty for all
+    #[serde(default)]
+    pub assets: Vec<String>,
+    /// Source chains the sponsor pays fees on; empty for all
+    #[serde(default)]
+    pub chains: Vec<ChainId>,
+}
+
+impl Sponsor {
+    pub fn new(sponsor_id: impl Into<String>, account: impl Into<String>, public_key: impl Into<String>) -> Self {
+        Self {
+            sponsor_id: sponsor_id.into(),
+            account: account.into(),
+            public_key: public_key.into(),
+            paymaster: None,
+            balances: HashMap::new(),
+            max_fee: None,
+            daily_limit: None,
+            assets: Vec::new(),
+            chains: Vec::new(),
+        }
+    }
+
+    pub fn with_paymaster(mut self, paymaster: impl Into<String>) -> Self {
+        self.paymaster = Some(paymaster.into());
+        self
+    }
+
+    /// Deposits `amount` of fees in `asset`
+    pub fn with_balance(mut self, asset: &str, amount: f64) -> Self {
+        *self.balances.entry(asset.to_ascii_uppercase()).or_default() += amount;
+        self
+    }
+
+    pub fn with_max_fee(mut self, max_fee: f64) -> Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    pub fn with_daily_limit(mut self, daily_limit: f64) -> Self {
+        self.daily_limit = Some(daily_limit);
+        self
+    }
+
+    pub fn with_assets(mut self, assets: Vec<String>) -> Self {
+        self.assets = assets;
+        self
+    }
+
+    pub fn with_chains(mut self, chains: Vec<ChainId>) -> Self {
+        self.chains = chains;
+        self
+    }
+
+    fn balance_key(&self, asset: &str) -> String {
+        format!("sponsor:{}:{}", self.sponsor_id, asset.to_ascii_uppercase())
+    }
+}
+
+/// Fee payment arranged for a request, passed to the executing agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sponsorship {
+    #[serde(rename = "reference_id")]
+    pub reference_id: String,
+    pub fee_payer: FeePayer,
+    /// Account or paymaster contract the fee is charged to; empty when the
+    /// sender pays
+    pub fee_account: String,
+    /// Fee the payer agreed to, from the selected quote
+    pub max_fee: String,
+}
+
+/// Why a fee payer was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum SponsorRejection {
+    NotFound { sponsor_id: String },
+    InvalidAuthorization { sponsor_id: String, reason: String },
+    /// The sponsor doesn't pay fees for this asset, chain or amount
+    NotCovered { sponsor_id: String, reason: String },
+    InsufficientBalance { sponsor_id: String, asset: String, remaining: f64, fee: f64 },
+    DailyLimitExceeded { sponsor_id: String, asset: String, limit: f64, spent: f64, fee: f64 },
+    /// The fee isn't in the transferred asset, or would take all of the amount
+    RecipientCannotPay { fee: String, amount: String },
+    Unavailable(String),
+}
+
+impl std::fmt::Display for SponsorRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SponsorRejection::NotFound { sponsor_id } => write!(f, "no sponsor registered as {}", sponsor_id),
+            SponsorRejection::InvalidAuthorization { sponsor_id, reason } => {
+                write!(f, "authorization of sponsor {} is invalid: {}", sponsor_id, reason)
+            }
+            SponsorRejection::NotCovered { sponsor_id, reason } => {
+                write!(f, "sponsor {} does not cover this fee: {}", sponsor_id, reason)
+            }
+            SponsorRejection::InsufficientBalance { sponsor_id, asset, remaining, fee } => {
+                write!(f, "sponsor {} has {} {} left, the fee is {}", sponsor_id, remaining, asset, fee)
+            }
+            SponsorRejection::DailyLimitExceeded { sponsor_id, asset, limit, spent, fee } => write!(
+                f,
+                "daily {} fee limit of sponsor {} is {} ({} spent, fee {})",
+                asset, sponsor_id, limit, spent, fee
+            ),
+            SponsorRejection::RecipientCannotPay { fee, amount } => {
+                write!(f, "the recipient of {} cannot pay the fee of {}", amount, fee)
+            }
+            SponsorRejection::Unavailable(e) => write!(f, "sponsor store unavailable: {}", e),
+        }
+    }
+}
+
+impl SponsorRejection {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            SponsorRejection::NotFound { .. } => "sponsor_not_found",
+            SponsorRejection::InvalidAuthorization { .. } => "invalid_sponsor_authorization",
+            SponsorRejection::NotCovered { .. } => "sponsor_policy_violation",
+            SponsorRejection::InsufficientBalance { .. } => "sponsor_balance_insufficient",
+            SponsorRejection::DailyLimitExceeded { .. } => "sponsor_daily_limit_exceeded",
+            SponsorRejection::RecipientCannotPay { .. } => "recipient_cannot_pay_fee",
+            SponsorRejection::Unavailable(_) => "sponsor_store_unavailable",
+        }
+    }
+}
+
+/// Registered sponsors and the fees they have paid, used by
+/// `EasyCashClient::with_sponsors`
+pub struct SponsorRegistry {
+    sponsors: HashMap<String, Sponsor>,
+    store: Arc<dyn UsageStore>,
+}
+
+impl Default for SponsorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SponsorRegistry {
+    /// Creates a registry with no sponsors and an in-memory usage store
+    pub fn new() -> Self {
+        Self {
+            sponsors: HashMap::new(),
+            store: Arc::new(InMemoryUsageStore::new()),
+        }
+    }
+
+    /// Uses a custom usage store (e.g. shared across instances)
+    pub fn with_store(mut self, store: Arc<dyn UsageStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    pub fn with_sponsor(mut self, sponsor: Sponsor) -> Self {
+        self.sponsors.insert(sponsor.sponsor_id.clone(), sponsor);
+        self
+    }
+
+    pub fn sponsor(&self, sponsor_id: &str) -> Option<&Sponsor> {
+        self.sponsors.get(sponsor_id)
+    }
+
+    /// Fees `sponsor_id` can still pay in `asset`
+    pub fn remaining(&self, sponsor_id: &str, asset: &str) -> Result<f64, String> {
+        let sponsor = self.sponsors.get(sponsor_id).ok_or_else(|| format!("no sponsor registered as {}", sponsor_id))?;
+        let deposited = sponsor.balances.get(&asset.to_ascii_uppercase()).copied().unwrap_or(0.0);
+        Ok(deposited - self.store.usage_since(&sponsor.balance_key(asset), 0)?.to_f64())
+    }
+
+    /// Arranges for `payer` to pay `fee` (e.g. "0.05 USDC") for `req`.
+    /// Sponsors must have signed the request and cover the fee; recipients
+    /// must receive more than the fee.
+    pub fn authorize(&self, req: &TransactionRequest, payer: &FeePayer, fee: &str) -> Result<Sponsorship, SponsorRejection> {
+        let fee_account = match payer {
+            FeePayer::Sender => String::new(),
+            FeePayer::Recipient => return recipient_pays(req, fee),
+            FeePayer::Sponsor { sponsor_id, signature } => self.check_sponsor(req, sponsor_id, signature, fee)?,
+        };
+        Ok(Sponsorship {
+            reference_id: req.reference_id.clone(),
+            fee_payer: payer.clone(),
+            fee_account,
+            max_fee: fee.to_string(),
+        })
+    }
+
+    /// Records `fee_used` against the sponsor of `sponsorship`, if any
+    pub fn record(&self, sponsorship: &Sponsorship, fee_used: &str) -> Result<(), String> {
+        let FeePayer::Sponsor { ref sponsor_id, .. } = sponsorship.fee_payer else {
+            return Ok(());
+        };
+        let sponsor = self.sponsors.get(sponsor_id).ok_or_else(|| format!("no sponsor registered as {}", sponsor_id))?;
+        let (fee, asset) = amount::parse_fee(fee_used).ok_or_else(|| format!("unreadable fee {}", fee_used))?;
+        self.store.record(&sponsor.balance_key(asset), fee, now_ms())
+    }
+
+    fn check_sponsor(&self, req: &TransactionRequest, sponsor_id: &str, signature: &str, fee: &str) -> Result<String, SponsorRejection> {
+        let sponsor = self.sponsors.get(sponsor_id).ok_or_else(|| SponsorRejection::NotFound {
+            sponsor_id: sponsor_id.to_string(),
+        })?;
+        let invalid = |reason: String| SponsorRejection::InvalidAuthorization {
+            sponsor_id: sponsor_id.to_string(),
+            reason,
+        };
+        let key = VerifyingKey::from(&crypto::public_key_from_hex(&sponsor.public_key).map_err(invalid)?);
+        let message = authorization_message(req, sponsor_id);
+        match crypto::verify_signature_in_domain(HashFunction::Sha256, SigningDomain::Sponsorship, &key, &message, signature) {
+            Ok(true) => {}
+            Ok(false) => return Err(invalid("the signature does not match the request".to_string())),
+            Err(e) => return Err(invalid(format!("malformed signature: {}", e))),
+        }
+
+        let not_covered = |reason: String| SponsorRejection::NotCovered {
+            sponsor_id: sponsor_id.to_string(),
+            reason,
+        };
+        if !sponsor.assets.is_empty() && !sponsor.assets.iter().any(|a| a.eq_ignore_ascii_case(&req.asset)) {
+            return Err(not_covered(format!("{} transfers are not sponsored", req.asset)));
+        }
+        if !sponsor.chains.is_empty() && !sponsor.chains.contains(&req.source_chain) {
+            return Err(not_covered(format!("transfers on {:?} are not sponsored", req.source_chain)));
+        }
+        let (amount, asset) = parse_fee(fee).ok_or_else(|| not_covered(format!("unreadable fee {}", fee)))?;
+        let asset = if asset.is_empty() { req.asset.as_str() } else { asset };
+        if let Some(max_fee) = sponsor.max_fee.filter(|max| amount > *max) {
+            return Err(not_covered(format!("fee {} is above the maximum of {}", fee, max_fee)));
+        }
+
+        let remaining = self.remaining(sponsor_id, asset).map_err(SponsorRejection::Unavailable)?;
+        if amount > remaining {
+            return Err(SponsorRejection::InsufficientBalance {
+                sponsor_id: sponsor_id.to_string(),
+                asset: asset.to_string(),
+                remaining,
+                fee: amount,
+            });
+        }
+        if let Some(limit) = sponsor.daily_limit {
+            let now = now_ms();
+            let spent = self
+                .store
+                .usage_since(&sponsor.balance_key(asset), now - now % DAY_MS)
+                .map_err(SponsorRejection::Unavailable)?
+                .to_f64();
+            if spent + amount > limit {
+                return Err(SponsorRejection::DailyLimitExceeded {
+                    sponsor_id: sponsor_id.to_string(),
+                    asset: asset.to_string(),
+                    limit,
+                    spent,
+                    fee: amount,
+                });
+            }
+        }
+        Ok(sponsor.paymaster.clone().unwrap_or_else(|| sponsor.account.clone()))
+    }
+}
+
+/// Charges `fee` for `req` to its recipient, which needs no sponsor. The
+/// fee must be in the transferred asset and below the amount.
+pub fn recipient_pays(req: &TransactionRequest, fee: &str) -> Result<Sponsorship, SponsorRejection> {
+    let cannot_pay = || SponsorRejection::RecipientCannotPay {
+        fee: fee.to_string(),
+        amount: req.amount.clone(),
+    };
+    let recipient = req.recipient.clone().ok_or_else(cannot_pay)?;
+    let amount = req.amount.parse::<f64>().map_err(|_| cannot_pay())?;
+    match parse_fee(fee) {
+        Some((value, asset)) if (asset.is_empty() || asset.eq_ignore_ascii_case(&req.asset)) && value < amount => {
+            Ok(Sponsorship {
+                reference_id: req.reference_id.clone(),
+                fee_payer: FeePayer::Recipient,
+                fee_account: recipient,
+                max_fee: fee.to_string(),
+            })
+        }
+        _ => Err(cannot_pay()),
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::IntentType;
+    use k256::{PublicKey, SecretKey};
+
+    fn signer(byte: u8) -> TransactionSigner {
+        TransactionSigner::new(SecretKey::from_bytes(&[byte; 32].into()).unwrap())
+    }
+
+    fn request(reference_id: &str) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "20", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+    }
+
+    fn registry(sponsor: Sponsor) -> SponsorRegistry {
+        SponsorRegistry::new().with_sponsor(sponsor)
+    }
+
+    fn platform(signer: &TransactionSigner) -> Sponsor {
+        let public_key = crypto::public_key_to_hex(&PublicKey::from(&signer.verifying_key()));
+        Sponsor::new("platform", "0xplatform", public_key)
+    }
+
+    #[test]
+    fn test_sponsor_authorization_and_balance() {
+        let key = signer(4);
+        let sponsors = registry(platform(&key).with_balance("usdc", 0.1).with_paymaster("0xpaymaster"));
+        let req = request("ref_1");
+        let payer = FeePayer::sponsor(&req, "platform", &key).unwrap();
+
+        let sponsorship = sponsors.authorize(&req, &payer, "0.06 USDC").unwrap();
+        assert_eq!(sponsorship.fee_account, "0xpaymaster");
+        sponsors.record(&sponsorship, "0.06 USDC").unwrap();
+        assert!((sponsors.remaining("platform", "USDC").unwrap() - 0.04).abs() < 1e-9);
+        let err = sponsors.authorize(&req, &payer, "0.06 USDC").unwrap_err();
+        assert_eq!(err.reason(), "sponsor_balance_insufficient");
+
+        // Authorizations are bound to their request and sponsor
+        let other = request("ref_2");
+        assert_eq!(sponsors.authorize(&other, &payer, "0.01 USDC").unwrap_err().reason(), "invalid_sponsor_authorization");
+        let forged = FeePayer::sponsor(&req, "platform", &signer(5)).unwrap();
+        assert_eq!(sponsors.authorize(&req, &forged, "0.01 USDC").unwrap_err().reason(), "invalid_sponsor_authorization");
+        let unknown = FeePayer::sponsor(&req, "unknown", &key).unwrap();
+        assert_eq!(sponsors.authorize(&req, &unknown, "0.01 USDC").unwrap_err().reason(), "sponsor_not_found");
+    }
+
+    #[test]
+    fn test_sponsor_policy() {
+        let key = signer(4);
+        let sponsor = platform(&key)
+            .with_balance("USDC", 10.0)
+            .with_max_fee(0.5)
+            .with_daily_limit(1.0)
+            .with_chains(vec![ChainId::Base]);
+        let sponsors = registry(sponsor);
+        let req = request("ref_1");
+        let payer = FeePayer::sponsor(&req, "platform", &key).unwrap();
+
+        assert_eq!(sponsors.authorize(&req, &payer, "0.6 USDC").unwrap_err().reason(), "sponsor_policy_violation");
+        let mut on_ethereum = req.clone();
+        on_ethereum.source_chain = ChainId::Ethereum;
+        let payer_on_ethereum = FeePayer::sponsor(&on_ethereum, "platform", &key).unwrap();
+        let err = sponsors.authorize(&on_ethereum, &payer_on_ethereum, "0.1 USDC").unwrap_err();
+        assert_eq!(err.reason(), "sponsor_policy_violation");
+
+        for _ in 0..2 {
+            let sponsorship = sponsors.authorize(&req, &payer, "0.45 USDC").unwrap();
+            sponsors.record(&sponsorship, "0.45 USDC").unwrap();
+        }
+        let err = sponsors.authorize(&req, &payer, "0.45 USDC").unwrap_err();
+        assert_eq!(err.reason(), "sponsor_daily_limit_exceeded");
+    }
+
+    #[test]
+    fn test_recipient_pays() {
+        let sponsors = SponsorRegistry::new();
+        let mut req = request("ref_1");
+        FeePayer::Recipient.attach(&mut req);
+        let payer = FeePayer::of(&req).unwrap();
+        assert_eq!(payer, FeePayer::Recipient);
+
+        let sponsorship = sponsors.authorize(&req, &payer, "0.05 USDC").unwrap();
+        assert_eq!(sponsorship.fee_account, req.recipient.clone().unwrap());
+        assert_eq!(sponsors.authorize(&req, &payer, "25 USDC").unwrap_err().reason(), "recipient_cannot_pay_fee");
+        assert_eq!(sponsors.authorize(&req, &payer, "0.001 ETH").unwrap_err().reason(), "recipient_cannot_pay_fee");
+        assert_eq!(FeePayer::of(&request("ref_2")), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! Progress updates for long-running executions.
+//!
+//! `EasyCashClient::execute_transaction_streaming` returns an
+//! `ExecutionStream` instead of a single awaited response. Multi-hop
+//! cross-chain transfers can take minutes. During that time the stream yields
+//! the selected route, per-hop progress and bridge confirmations reported by
+//! the agent, and finally the result.
+//!
+//! Agents report progress from `AgentNegotiatorTrait::execute_with_progress`
+//! through the `ProgressReporter` they are handed.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::errors::Result;
+use crate::types::TransactionResponse;
+
+/// State of one hop of a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HopStatus {
+    /// The hop's transaction was broadcast
+    Submitted,
+    /// Waiting for bridge or block confirmations
+    Confirming,
+    /// The hop is final
+    Completed,
+}
+
+/// Progress of one hop, as reported by the executing agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HopProgress {
+    /// Position of the hop in `RouteQuote::route`, from 0
+    pub hop: usize,
+    /// Chain the hop executes on
+    pub chain: String,
+    pub status: HopStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// Confirmations observed so far
+    #[serde(default)]
+    pub confirmations: u32,
+    /// Confirmations the bridge waits for (0 when not applicable)
+    #[serde(default)]
+    pub required_confirmations: u32,
+}
+
+/// Item of an `ExecutionStream`
+#[derive(Debug)]
+pub enum ExecutionUpdate {
+    /// A route was chosen; `hops` lists the chains it crosses
+    RouteSelected { agent_id: String, hops: Vec<String> },
+    /// The agent reported progress on a hop
+    Hop(HopProgress),
+    /// Outcome of the execution; always the last update
+    Finished(Result<TransactionResponse>),
+}
+
+/// Handle agents use to report progress while executing. Reports are
+/// dropped when nobody is streaming the execution.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressReporter {
+    tx: Option<mpsc::UnboundedSender<ExecutionUpdate>>,
+}
+
+impl ProgressReporter {
+    pub(crate) fn channel() -> (Self, mpsc::UnboundedReceiver<ExecutionUpdate>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx: Some(tx) }, rx)
+    }
+
+    /// True when the execution is being streamed
+    pub fn is_enabled(&self) -> bool {
+        self.tx.as_ref().is_some_and(|tx| !tx.is_closed())
+    }
+
+    pub fn report(&self, progress: HopProgress) {
+        self.send(ExecutionUpdate::Hop(progress));
+    }
+
+    pub(crate) fn send(&self, update: ExecutionUpdate) {
+        if let Some(ref tx) = self.tx {
+            // The consumer may have dropped the stream; execution carries on
+            let _ = tx.send(update);
+        }
+    }
+}
+
+type Execution<'a> = Pin<Box<dyn Future<Output = Result<TransactionResponse>> + Send + 'a>>;
+
+/// Updates of a running execution, ending with `ExecutionUpdate::Finished`.
+///
+/// The execution only makes progress while the stream is polled; dropping
+/// the stream cancels it like dropping the `execute_transaction` future.
+pub struct ExecutionStream<'a> {
+    rx: mpsc::UnboundedReceiver<ExecutionUpdate>,
+    execution: Option<Execution<'a>>,
+    result: Option<Result<TransactionResponse>>,
+}
+
+impl<'a> ExecutionStream<'a> {
+    pub(crate) fn new(rx: mpsc::UnboundedReceiver<ExecutionUpdate>, execution: Execution<'a>) -> Self {
+        Self {
+            rx,
+            execution: Some(execution),
+            result: None,
+        }
+    }
+
+    /// Waits for the next update (`None` after `Finished`)
+    pub async fn next(&mut self) -> Option<ExecutionUpdate> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    /// Drives the execution to completion, discarding progress updates
+    pub async fn finish(mut self) -> Result<TransactionResponse> {
+        while let Some(update) = self.next().await {
+            if let ExecutionUpdate::Finished(result) = update {
+                return result;
+            }
+        }
+        unreachable!("execution streams end with Finished")
+    }
+}
+
+impl Stream for ExecutionStream<'_> {
+    type Item = ExecutionUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            // Progress reported before completion is delivered before the result
+            if let Poll::Ready(Some(update)) = self.rx.poll_recv(cx) {
+                return Poll::Ready(Some(update));
+            }
+            match self.execution.as_mut() {
+                Some(execution) => match execution.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        self.execution = None;
+                        self.result = Some(result);
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+                None => return Poll::Ready(self.result.take().map(ExecutionUpdate::Finished)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hop(hop: usize, status: HopStatus) -> HopProgress {
+        HopProgress {
+            hop,
+            chain: "base".to_string(),
+            status,
+            tx_hash: None,
+            confirmations: 0,
+            required_confirmations: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_updates_precede_result() {
+        let (progress, rx) = ProgressReporter::channel();
+        let mut stream = ExecutionStream::new(
+            rx,
+            Box::pin(async move {
+                progress.report(hop(0, HopStatus::Submitted));
+                tokio::task::yield_now().await;
+                progress.report(hop(0, HopStatus::Completed));
+                Ok(TransactionResponse {
+                    tx_hash: "0xabc".to_string(),
+                    status: "confirmed".to_string(),
+                    block_height: 1,
+                    fee_used: "0.05 USDC".to_string(),
+                    correlation_id: String::new(),
+                    disbursements: Vec::new(),
+                    fee_splits: Vec::new(),
+                })
+            }),
+        );
+
+        let mut statuses = Vec::new();
+        while let Some(update) = stream.next().await {
+            match update {
+                ExecutionUpdate::Hop(h) => statuses.push(h.status),
+                ExecutionUpdate::Finished(result) => assert_eq!(result.unwrap().tx_hash, "0xabc"),
+                other => panic!("unexpected update {:?}", other),
+            }
+        }
+        assert_eq!(statuses, [HopStatus::Submitted, HopStatus::Completed]);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    fn test_disabled_reporter() {
+        let progress = ProgressReporter::default();
+        assert!(!progress.is_enabled());
+        progress.report(hop(0, HopStatus::Submitted));
+
+        let (progress, rx) = ProgressReporter::channel();
+        assert!(progress.is_enabled());
+        drop(rx);
+        assert!(!progress.is_enabled());
+        progress.report(hop(0, HopStatus::Submitted));
+    }
+}
@@ -0,0 +1,605 @@
+//! Live event subscriptions over WebSocket.
+//!
+//! `Subscription::connect` opens a WebSocket to the EasyCash API and yields a
+//! stream of typed events. Dropped connections are re-established with
+//! exponential backoff, and every (re)subscribe carries the last sequence
+//! number seen so the server can replay what was missed. Events at or below
+//! that sequence are discarded, so each event is delivered at most once.
+//!
+//! Supports plain `ws://` endpoints only (e.g. behind a TLS-terminating
+//! proxy), like the `http` module.
+//!
+//! # Example
+//! ```no_run
+//! use ecash_sdk_core::subscriptions::{Subscription, SubscriptionConfig, SubscriptionEvent, Topic};
+//!
+//! # async fn run() {
+//! let config = SubscriptionConfig::new("ws://localhost:8080/v1/stream", "api-key")
+//!     .with_topics(vec![Topic::Status, Topic::Fees]);
+//! let mut subscription = Subscription::connect(config);
+//! while let Some(event) = subscription.next().await {
+//!     if let SubscriptionEvent::StatusChanged { reference_id, status, .. } = event.event {
+//!         println!("#{} {} is now {}", event.sequence, reference_id, status);
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::credentials::ApiKeyRing;
+use crate::crypto::base64_encode;
+use crate::http::{HttpClient, HttpUrl};
+use crate::tls::BoxedStream;
+use crate::types::ChainId;
+
+/// Events buffered before the connection task waits for the consumer
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Prefix of handshake errors caused by a rejected API key
+const UNAUTHORIZED: &str = "unauthorized";
+
+/// Largest message accepted from the server
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// GUID appended to the handshake key (RFC 6455 section 1.3)
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// Event categories a subscription can ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    /// Transaction status changes
+    Status,
+    /// Agents joining or leaving the network
+    Agents,
+    /// Network fee updates
+    Fees,
+}
+
+/// Event pushed by the EasyCash API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SubscriptionEvent {
+    /// A transaction moved to a new status
+    StatusChanged {
+        reference_id: String,
+        #[serde(default)]
+        tx_hash: Option<String>,
+        status: String,
+    },
+    /// An agent joined, updated or left the network
+    AgentAnnouncement {
+        agent_id: String,
+        #[serde(default)]
+        chains: Vec<ChainId>,
+        online: bool,
+    },
+    /// The network fee for an asset on a chain changed
+    FeeUpdate { chain: ChainId, asset: String, fee: String },
+}
+
+/// Event together with its position in the server's stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequencedEvent {
+    /// Strictly increasing across reconnects
+    pub sequence: u64,
+    #[serde(flatten)]
+    pub event: SubscriptionEvent,
+}
+
+/// Subscription settings
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    /// `ws://host[:port]/path` of the stream endpoint
+    pub url: String,
+    /// Read on every (re)connect, so rotations apply to the next handshake
+    pub credentials: Arc<ApiKeyRing>,
+    pub topics: Vec<Topic>,
+    /// Replay events after this sequence on the first connect (e.g. persisted
+    /// from a previous process)
+    pub resume_from: Option<u64>,
+    /// Delay before the first reconnect attempt; doubled after each failure
+    pub reconnect_delay: Duration,
+    pub max_reconnect_delay: Duration,
+    pub connect_timeout: Duration,
+    /// Proxy, TLS and request signing settings used to connect
+    pub http: HttpClient,
+}
+
+impl SubscriptionConfig {
+    /// Subscribes to all topics
+    pub fn new(url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            credentials: Arc::new(ApiKeyRing::new(api_key.into())),
+            topics: vec![Topic::Status, Topic::Agents, Topic::Fees],
+            resume_from: None,
+            reconnect_delay: Duration::from_millis(500),
+            max_reconnect_delay: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            http: HttpClient::default(),
+        }
+    }
+
+    /// Authenticates with a shared, rotatable key (e.g. `EasyCashClient::credentials`)
+    pub fn with_credentials(mut self, credentials: Arc<ApiKeyRing>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Connects through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+
+    pub fn with_topics(mut self, topics: Vec<Topic>) -> Self {
+        self.topics = topics;
+        self
+    }
+
+    pub fn with_resume_from(mut self, sequence: u64) -> Self {
+        self.resume_from = Some(sequence);
+        self
+    }
+
+    pub fn with_reconnect_delay(mut self, initial: Duration, max: Duration) -> Self {
+        self.reconnect_delay = initial;
+        self.max_reconnect_delay = max;
+        self
+    }
+}
+
+/// Stream of events from a live subscription.
+///
+/// The connection runs in a background task that stops when the
+/// subscription is dropped.
+pub struct Subscription {
+    rx: mpsc::Receiver<SequencedEvent>,
+    last_sequence: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+}
+
+impl Subscription {
+    /// Starts connecting in the background and returns immediately
+    pub fn connect(config: SubscriptionConfig) -> Self {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let last_sequence = Arc::new(AtomicU64::new(config.resume_from.unwrap_or(0)));
+        let task = tokio::spawn(run(config, tx, last_sequence.clone()));
+        Self {
+            rx,
+            last_sequence,
+            task,
+        }
+    }
+
+    /// Waits for the next event (`None` once the subscription is closed)
+    pub async fn next(&mut self) -> Option<SequencedEvent> {
+        self.rx.recv().await
+    }
+
+    /// Highest sequence received so far; persist it to resume after a restart
+    pub fn last_sequence(&self) -> Option<u64> {
+        match self.last_sequence.load(Ordering::SeqCst) {
+            0 => None,
+            seq => Some(seq),
+        }
+    }
+}
+
+impl futures_core::Stream for Subscription {
+    type Item = SequencedEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Parses a `ws[s]://host[:port]/path` URL
+fn parse_ws_url(url: &str) -> Result<HttpUrl, String> {
+    let http_url = if let Some(rest) = url.strip_prefix("ws://") {
+        format!("http://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("wss://") {
+        format!("https://{}", rest)
+    } else {
+        return Err(format!("unsupported URL scheme (expected ws:// or wss://): {}", url));
+    };
+    HttpUrl::parse(&http_url).map_err(|_| format!("invalid URL: {}", url))
+}
+
+/// Connection loop: reconnects until the subscription is dropped
+async fn run(config: SubscriptionConfig, tx: mpsc::Sender<SequencedEvent>, last_sequence: Arc<AtomicU64>) {
+    let mut delay = config.reconnect_delay;
+    loop {
+        match session(&config, &tx, &last_sequence, &mut delay).await {
+            // The consumer went away
+            Ok(()) => return,
+            Err(e) => {
+                if tx.is_closed() {
+                    return;
+                }
+                tracing::warn!("[SDK] Subscription to {} dropped: {}; reconnecting in {:?}", config.url, e, delay);
+            }
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(config.max_reconnect_delay);
+    }
+}
+
+/// Runs one connection; returns `Ok` only when the receiver was dropped
+async fn session(
+    config: &SubscriptionConfig,
+    tx: &mpsc::Sender<SequencedEvent>,
+    last_sequence: &AtomicU64,
+    delay: &mut Duration,
+) -> Result<(), String> {
+    let url = parse_ws_url(&config.url)?;
+    let mut stream = connect_with_credentials(config, &url).await?;
+    *delay = config.reconnect_delay;
+
+    let resume = match last_sequence.load(Ordering::SeqCst) {
+        0 => None,
+        seq => Some(seq),
+    };
+    let subscribe = serde_json::json!({
+        "action": "subscribe",
+        "topics": config.topics,
+        "last_sequence": resume,
+    });
+    write_frame(&mut stream, OP_TEXT, subscribe.to_string().as_bytes(), true).await?;
+
+    loop {
+        let message = read_message(&mut stream).await?;
+        let event: SequencedEvent = match serde_json::from_slice(&message) {
+            Ok(event) => event,
+            // Acknowledgements and event types this SDK version doesn't know
+            Err(e) => {
+                tracing::debug!("[SDK] Ignoring subscription message: {}", e);
+                continue;
+            }
+        };
+        if event.sequence <= last_sequence.load(Ordering::SeqCst) {
+            continue;
+        }
+        last_sequence.store(event.sequence, Ordering::SeqCst);
+        if tx.send(event).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Performs the handshake with the current key, falling back to the
+/// previous key during its grace period if the server rejects the current one
+async fn connect_with_credentials(config: &SubscriptionConfig, url: &HttpUrl) -> Result<BoxedStream, String> {
+    let mut last_error = String::new();
+    for api_key in config.credentials.candidates() {
+        match tokio::time::timeout(config.connect_timeout, handshake(&config.http, url, api_key.expose()))
+            .await
+            .map_err(|_| format!("handshake timed out after {:?}", config.connect_timeout))?
+        {
+            Ok(stream) => return Ok(stream),
+            Err(e) if e.starts_with(UNAUTHORIZED) => last_error = e,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_error)
+}
+
+/// Opens the connection and performs the HTTP upgrade
+async fn handshake(http: &HttpClient, url: &HttpUrl, api_key: &str) -> Result<BoxedStream, String> {
+    let mut stream = http.connect(url).await?;
+
+    let mut nonce = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let key = base64_encode(&nonce);
+    let mut request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}:{}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\nAuthorization: Bearer {}\r\n",
+        url.path, url.host, url.port, key, api_key
+    );
+    for (name, value) in http.signature_headers(&url.host, "GET", &url.path, b"") {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("failed to send handshake: {}", e))?;
+
+    // Read byte by byte so no frame data after the headers is consumed
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() > 8192 {
+            return Err("handshake response headers too large".to_string());
+        }
+        let byte = stream
+            .read_u8()
+            .await
+            .map_err(|e| format!("failed to read handshake: {}", e))?;
+        head.push(byte);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some("101") => {}
+        Some("401") | Some("403") => return Err(format!("{}: {}", UNAUTHORIZED, status_line)),
+        _ => return Err(format!("upgrade rejected: {}", status_line)),
+    }
+    let accept = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("sec-websocket-accept"))
+        .map(|(_, value)| value.trim().to_string());
+    if accept.as_deref() != Some(accept_key(&key).as_str()) {
+        return Err("invalid Sec-WebSocket-Accept".to_string());
+    }
+    Ok(stream)
+}
+
+/// Expected `Sec-WebSocket-Accept` for a handshake key
+fn accept_key(key: &str) -> String {
+    // SHA-1 is mandated by the handshake; it protects nothing here
+    base64_encode(&Sha1::digest(format!("{}{}", key, WEBSOCKET_GUID).as_bytes()))
+}
+
+/// Reads the next complete data message, answering pings along the way
+async fn read_message<S>(stream: &mut S) -> Result<Vec<u8>, String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut message = Vec::new();
+    loop {
+        let (fin, opcode, payload) = read_frame(stream).await?;
+        match opcode {
+            OP_PING => write_frame(stream, OP_PONG, &payload, true).await?,
+            OP_PONG => {}
+            OP_CLOSE => {
+                // Best effort: echo the close before dropping the connection
+                let _ = write_frame(stream, OP_CLOSE, &payload, true).await;
+                return Err("connection closed by server".to_string());
+            }
+            OP_TEXT | OP_BINARY | OP_CONTINUATION => {
+                if message.len() + payload.len() > MAX_MESSAGE_SIZE {
+                    return Err("message too large".to_string());
+                }
+                message.extend_from_slice(&payload);
+                if fin {
+                    return Ok(message);
+                }
+            }
+            other => return Err(format!("unsupported opcode {:#x}", other)),
+        }
+    }
+}
+
+/// Reads one frame, unmasking it if needed; returns (fin, opcode, payload)
+async fn read_frame<S: AsyncRead + Unpin>(stream: &mut S) -> Result<(bool, u8, Vec<u8>), String> {
+    let mut header = [0u8; 2];
+    stream
+        .read_exact(&mut header)
+        .await
+        .map_err(|e| format!("failed to read frame: {}", e))?;
+    let fin = header[0] & 0x80 != 0;
+    let opcode = header[0] & 0x0F;
+    let masked = header[1] & 0x80 != 0;
+    let len = match header[1] & 0x7F {
+        126 => stream.read_u16().await.map_err(|e| e.to_string())? as u64,
+        127 => stream.read_u64().await.map_err(|e| e.to_string())?,
+        len => len as u64,
+    };
+    if len > MAX_MESSAGE_SIZE as u64 {
+        return Err("frame too large".to_string());
+    }
+    let mut mask = [0u8; 4];
+    if masked {
+        stream.read_exact(&mut mask).await.map_err(|e| e.to_string())?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    stream
+        .read_exact(&mut payload)
+        .await
+        .map_err(|e| format!("failed to read frame: {}", e))?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+    Ok((fin, opcode, payload))
+}
+
+/// Writes one final frame; clients must mask, servers must not
+async fn write_frame<S: AsyncWrite + Unpin>(stream: &mut S, opcode: u8, payload: &[u8], mask: bool) -> Result<(), String> {
+    let mut frame = vec![0x80 | opcode];
+    let mask_bit = if mask { 0x80 } else { 0 };
+    match payload.len() {
+        len if len < 126 => frame.push(mask_bit | len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(mask_bit | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(mask_bit | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    if mask {
+        let mut key = [0u8; 4];
+        rand::thread_rng().fill_bytes(&mut key);
+        frame.extend_from_slice(&key);
+        frame.extend(payload.iter().enumerate().map(|(i, byte)| byte ^ key[i % 4]));
+    } else {
+        frame.extend_from_slice(payload);
+    }
+    stream
+        .write_all(&frame)
+        .await
+        .map_err(|e| format!("failed to write frame: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Accepts one connection, completes the handshake and returns the
+    /// client's subscribe message
+    async fn accept(listener: &TcpListener) -> (TcpStream, serde_json::Value) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.contains("Authorization: Bearer test-key"));
+        let key = head
+            .lines()
+            .find_map(|line| line.strip_prefix("Sec-WebSocket-Key: "))
+            .unwrap();
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+
+        let (_, opcode, payload) = read_frame(&mut stream).await.unwrap();
+        assert_eq!(opcode, OP_TEXT);
+        (stream, serde_json::from_slice(&payload).unwrap())
+    }
+
+    async fn send_event(stream: &mut TcpStream, sequence: u64, reference_id: &str) {
+        let event = serde_json::json!({
+            "sequence": sequence,
+            "type": "status_changed",
+            "reference_id": reference_id,
+            "status": "confirmed",
+        });
+        write_frame(stream, OP_TEXT, event.to_string().as_bytes(), false).await.unwrap();
+    }
+
+    #[test]
+    fn test_accept_key_rfc_example() {
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[test]
+    fn test_event_deserialization() {
+        let event: SequencedEvent =
+            serde_json::from_str(r#"{"sequence":7,"type":"fee_update","chain":"base","asset":"USDC","fee":"0.02"}"#).unwrap();
+        assert_eq!(event.sequence, 7);
+        assert_eq!(
+            event.event,
+            SubscriptionEvent::FeeUpdate {
+                chain: ChainId::Base,
+                asset: "USDC".to_string(),
+                fee: "0.02".to_string()
+            }
+        );
+        assert!(parse_ws_url("wss://api.useeasy.cash/v1/stream").unwrap().tls);
+        assert!(parse_ws_url("https://api.useeasy.cash/v1/stream").is_err());
+        assert_eq!(parse_ws_url("ws://localhost:9000").unwrap().port, 9000);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_previous_key_during_grace_period() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/v1/stream", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            // The server hasn't picked up the rotated key yet
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            while !head.ends_with(b"\r\n\r\n") {
+                head.push(stream.read_u8().await.unwrap());
+            }
+            assert!(String::from_utf8(head).unwrap().contains("Authorization: Bearer new-key"));
+            stream.write_all(b"HTTP/1.1 401 Unauthorized\r\n\r\n").await.unwrap();
+            drop(stream);
+
+            let (mut stream, _) = accept(&listener).await;
+            send_event(&mut stream, 1, "ref_1").await;
+            stream
+        });
+
+        let credentials = Arc::new(ApiKeyRing::new("test-key"));
+        credentials.rotate("new-key");
+        let config = SubscriptionConfig::new(url, "").with_credentials(credentials);
+        let mut subscription = Subscription::connect(config);
+        let event = tokio::time::timeout(Duration::from_secs(5), subscription.next()).await.unwrap().unwrap();
+        assert_eq!(event.sequence, 1);
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_and_resumes_from_last_sequence() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/v1/stream", listener.local_addr().unwrap());
+        let config = SubscriptionConfig::new(url, "test-key")
+            .with_topics(vec![Topic::Status])
+            .with_reconnect_delay(Duration::from_millis(10), Duration::from_millis(50));
+        let mut subscription = Subscription::connect(config);
+
+        let server = tokio::spawn(async move {
+            let (mut stream, subscribe) = accept(&listener).await;
+            assert_eq!(subscribe["topics"], serde_json::json!(["status"]));
+            assert!(subscribe["last_sequence"].is_null());
+            // Fragmented message, with a ping in between
+            let event = br#"{"sequence":1,"type":"status_changed","reference_id":"ref_1","status":"pending"}"#;
+            stream.write_all(&[OP_TEXT, 10]).await.unwrap();
+            stream.write_all(&event[..10]).await.unwrap();
+            write_frame(&mut stream, OP_PING, b"hi", false).await.unwrap();
+            let rest = &event[10..];
+            stream.write_all(&[0x80 | OP_CONTINUATION, rest.len() as u8]).await.unwrap();
+            stream.write_all(rest).await.unwrap();
+            let (_, opcode, payload) = read_frame(&mut stream).await.unwrap();
+            assert_eq!((opcode, payload.as_slice()), (OP_PONG, b"hi".as_slice()));
+            send_event(&mut stream, 2, "ref_2").await;
+            drop(stream);
+
+            let (mut stream, subscribe) = accept(&listener).await;
+            assert_eq!(subscribe["last_sequence"], 2);
+            // Replayed duplicate is skipped
+            send_event(&mut stream, 2, "ref_2").await;
+            send_event(&mut stream, 3, "ref_3").await;
+            stream
+        });
+
+        let mut references = Vec::new();
+        for _ in 0..3 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next())
+                .await
+                .unwrap()
+                .unwrap();
+            match event.event {
+                SubscriptionEvent::StatusChanged { reference_id, .. } => references.push(reference_id),
+                other => panic!("unexpected event {:?}", other),
+            }
+        }
+        assert_eq!(references, ["ref_1", "ref_2", "ref_3"]);
+        assert_eq!(subscription.last_sequence(), Some(3));
+        let _stream = server.await.unwrap();
+    }
+}
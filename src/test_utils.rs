@@ -0,0 +1,469 @@
+//! Test doubles for integration tests of code built on the SDK.
+//!
+//! `MockEasyCashServer` is a wiremock server standing in for the agent
+//! network's HTTP API: it returns canned quotes, fails or slows down on
+//! demand, and records every request, so withdrawal flows can be tested
+//! deterministically without real infrastructure. Clients from
+//! `MockEasyCashServer::client` reach it over HTTP through the SDK's own
+//! `HttpClient`; other code can use `uri` or mount extra mocks on `server`.
+//!
+//! | Route                 | Body                             | Response       |
+//! |-----------------------|----------------------------------|----------------|
+//! | `POST /v1/quotes`     | `TransactionRequest`             | `[RouteQuote]` |
+//! | `POST /v1/executions` | `{"request": ..., "route": ...}` | `{}`           |
+//! | `GET /v1/versions`    |                                  | `[u32]`        |
+//!
+//! Failures are answered with a non-2xx status and `{"error": message}`.
+//! Latencies are real delays of the HTTP response.
+//!
+//! # Example
+//! ```
+//! use ecash_sdk_core::test_utils::{MockEasyCashServer, MockQuote};
+//!
+//! # async fn run() {
+//! let server = MockEasyCashServer::builder()
+//!     .with_quote(MockQuote::new("agent-a").fee("0.02 USDC"))
+//!     .fail_executions(1, "agent timed out")
+//!     .start()
+//!     .await;
+//! let client = server.client();
+//! // First execution fails, later ones succeed; inspect server.executions()
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+use crate::agent::{self, AgentNegotiatorTrait, RouteQuote};
+use crate::client::EasyCashClient;
+use crate::config::SdkConfig;
+use crate::http::HttpClient;
+use crate::protocol;
+use crate::types::{TransactionRequest, TransactionResponse};
+
+const QUOTES_PATH: &str = "/v1/quotes";
+const EXECUTIONS_PATH: &str = "/v1/executions";
+const VERSIONS_PATH: &str = "/v1/versions";
+
+/// Builder for a canned `RouteQuote`
+#[derive(Debug, Clone)]
+pub struct MockQuote {
+    quote: RouteQuote,
+}
+
+impl MockQuote {
+    /// A secure, direct quote costing 0.05 USDC and taking 15s
+    pub fn new(agent_id: impl Into<String>) -> Self {
+        Self {
+            quote: RouteQuote {
+                agent_id: agent_id.into(),
+                estimated_fee: "0.05 USDC".to_string(),
+                estimated_time: Duration::from_secs(15),
+                route: Vec::new(),
+                security_score: 0.95,
+                estimated_fee_usd: None,
+                signature: None,
+                agent_pubkey: None,
+                region: None,
+            },
+        }
+    }
+
+    pub fn fee(mut self, fee: impl Into<String>) -> Self {
+        self.quote.estimated_fee = fee.into();
+        self
+    }
+
+    pub fn time(mut self, estimated_time: Duration) -> Self {
+        self.quote.estimated_time = estimated_time;
+        self
+    }
+
+    pub fn security(mut self, score: f64) -> Self {
+        self.quote.security_score = score;
+        self
+    }
+
+    /// Chain hops; defaults to source (and target) chain of the request
+    pub fn route(mut self, hops: &[&str]) -> Self {
+        self.quote.route = hops.iter().map(|h| h.to_string()).collect();
+        self
+    }
+
+    fn build(&self, req: &TransactionRequest) -> RouteQuote {
+        let mut quote = self.quote.clone();
+        if quote.route.is_empty() {
+            quote.route.push(req.source_chain.as_str().to_string());
+            if let Some(target) = req.target_chain {
+                quote.route.push(target.as_str().to_string());
+            }
+        }
+        quote
+    }
+}
+
+/// Execution request received by the mock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedExecution {
+    pub reference_id: String,
+    pub agent_id: String,
+}
+
+/// Body of `POST /v1/executions`
+#[derive(Serialize, Deserialize)]
+struct ExecutionBody {
+    request: TransactionRequest,
+    route: RouteQuote,
+}
+
+/// Answers `POST /v1/quotes` with the canned quotes for the request
+struct QuoteResponder {
+    quotes: Vec<MockQuote>,
+    latency: Duration,
+}
+
+impl Respond for QuoteResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let req: TransactionRequest = match request.body_json() {
+            Ok(req) => req,
+            Err(e) => return error_response(400, &format!("invalid request: {}", e), Duration::ZERO),
+        };
+        let quotes: Vec<RouteQuote> = self.quotes.iter().map(|q| q.build(&req)).collect();
+        ResponseTemplate::new(200).set_body_json(quotes).set_delay(self.latency)
+    }
+}
+
+/// Answers `POST /v1/executions` as an agent charging its quoted fee
+struct ExecutionResponder {
+    latency: Duration,
+}
+
+impl Respond for ExecutionResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let body: ExecutionBody = match request.body_json() {
+            Ok(body) => body,
+            Err(e) => return error_response(400, &format!("invalid execution: {}", e), Duration::ZERO),
+        };
+        ResponseTemplate::new(200)
+            .set_body_json(TransactionResponse::executed(body.route.estimated_fee))
+            .set_delay(self.latency)
+    }
+}
+
+fn error_response(status: u16, message: &str, latency: Duration) -> ResponseTemplate {
+    ResponseTemplate::new(status)
+        .set_body_json(json!({ "error": message }))
+        .set_delay(latency)
+}
+
+/// Scripted stand-in for the EasyCash agent network, configured before it
+/// starts (see `MockEasyCashServer::builder`).
+pub struct MockEasyCashServerBuilder {
+    quotes: Vec<MockQuote>,
+    quote_latency: Duration,
+    execution_latency: Duration,
+    quote_failures: Vec<(usize, String)>,
+    execution_failures: Vec<(usize, String)>,
+    failing_agents: HashSet<String>,
+    protocol_versions: Vec<u32>,
+}
+
+impl MockEasyCashServerBuilder {
+    /// Adds a quote returned for every request (replaces the default quote
+    /// from `mock-agent`)
+    pub fn with_quote(mut self, quote: MockQuote) -> Self {
+        self.quotes.push(quote);
+        self
+    }
+
+    /// Protocol versions the mock agents report (default: the SDK's own)
+    pub fn with_protocol_versions(mut self, versions: &[u32]) -> Self {
+        self.protocol_versions = versions.to_vec();
+        self
+    }
+
+    pub fn with_quote_latency(mut self, latency: Duration) -> Self {
+        self.quote_latency = latency;
+        self
+    }
+
+    pub fn with_execution_latency(mut self, latency: Duration) -> Self {
+        self.execution_latency = latency;
+        self
+    }
+
+    /// Fails the next `times` quote requests with `message`
+    pub fn fail_quotes(mut self, times: usize, message: impl Into<String>) -> Self {
+        self.quote_failures.push((times, message.into()));
+        self
+    }
+
+    /// Fails the next `times` executions with `message`
+    pub fn fail_executions(mut self, times: usize, message: impl Into<String>) -> Self {
+        self.execution_failures.push((times, message.into()));
+        self
+    }
+
+    /// Fails every execution routed to `agent_id`
+    pub fn with_failing_agent(mut self, agent_id: impl Into<String>) -> Self {
+        self.failing_agents.insert(agent_id.into());
+        self
+    }
+
+    /// Starts the wiremock server on a random local port and mounts the
+    /// scripted responses
+    pub async fn start(self) -> MockEasyCashServer {
+        let server = MockServer::start().await;
+
+        // Lower priority values win; among equals, the first mounted mock
+        // that still has uses left answers
+        for agent_id in &self.failing_agents {
+            Mock::given(method("POST"))
+                .and(path(EXECUTIONS_PATH))
+                .and(body_partial_json(json!({ "route": { "agent_id": agent_id } })))
+                .respond_with(error_response(
+                    502,
+                    &format!("agent {} rejected the transaction", agent_id),
+                    self.execution_latency,
+                ))
+                .with_priority(1)
+                .mount(&server)
+                .await;
+        }
+        for (times, message) in &self.execution_failures {
+            Mock::given(method("POST"))
+                .and(path(EXECUTIONS_PATH))
+                .respond_with(error_response(503, message, self.execution_latency))
+                .up_to_n_times(*times as u64)
+                .with_priority(2)
+                .mount(&server)
+                .await;
+        }
+        for (times, message) in &self.quote_failures {
+            Mock::given(method("POST"))
+                .and(path(QUOTES_PATH))
+                .respond_with(error_response(503, message, self.quote_latency))
+                .up_to_n_times(*times as u64)
+                .with_priority(2)
+                .mount(&server)
+                .await;
+        }
+
+        let quotes = match self.quotes.is_empty() {
+            true => vec![MockQuote::new("mock-agent")],
+            false => self.quotes,
+        };
+        Mock::given(method("POST"))
+            .and(path(QUOTES_PATH))
+            .respond_with(QuoteResponder {
+                quotes,
+                latency: self.quote_latency,
+            })
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(EXECUTIONS_PATH))
+            .respond_with(ExecutionResponder { latency: self.execution_latency })
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(VERSIONS_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&self.protocol_versions))
+            .mount(&server)
+            .await;
+
+        MockEasyCashServer { server }
+    }
+}
+
+/// Running wiremock server standing in for the EasyCash agent network
+pub struct MockEasyCashServer {
+    server: MockServer,
+}
+
+impl MockEasyCashServer {
+    /// Quotes from a single agent (`mock-agent`) and succeeds instantly
+    /// unless configured otherwise
+    pub fn builder() -> MockEasyCashServerBuilder {
+        MockEasyCashServerBuilder {
+            quotes: Vec::new(),
+            quote_latency: Duration::ZERO,
+            execution_latency: Duration::ZERO,
+            quote_failures: Vec::new(),
+            execution_failures: Vec::new(),
+            failing_agents: HashSet::new(),
+            protocol_versions: protocol::supported_versions().to_vec(),
+        }
+    }
+
+    /// Base URL of the server, e.g. `http://127.0.0.1:41234`
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// The underlying wiremock server, for mounting additional mocks
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+
+    /// Client with default configuration (caching off) wired to this mock
+    pub fn client(&self) -> EasyCashClient {
+        let config = SdkConfig {
+            enable_caching: false,
+            ..SdkConfig::default_config()
+        };
+        self.client_with_config(config)
+    }
+
+    /// Client with the given configuration wired to this mock
+    pub fn client_with_config(&self, config: SdkConfig) -> EasyCashClient {
+        let timeout = config.timeout;
+        let client = EasyCashClient::new(Some(config)).expect("invalid test configuration");
+        let negotiator = HttpNegotiator {
+            http: client.http_client().clone(),
+            url: self.uri(),
+            timeout,
+        };
+        client.with_negotiator(Arc::new(negotiator))
+    }
+
+    /// Reference IDs of all quote requests, in order
+    pub async fn quote_requests(&self) -> Vec<String> {
+        self.requests(QUOTES_PATH)
+            .await
+            .iter()
+            .filter_map(|r| r.body_json::<TransactionRequest>().ok())
+            .map(|req| req.reference_id)
+            .collect()
+    }
+
+    /// All execution attempts, in order
+    pub async fn executions(&self) -> Vec<RecordedExecution> {
+        self.requests(EXECUTIONS_PATH)
+            .await
+            .iter()
+            .filter_map(|r| r.body_json::<ExecutionBody>().ok())
+            .map(|body| RecordedExecution {
+                reference_id: body.request.reference_id,
+                agent_id: body.route.agent_id,
+            })
+            .collect()
+    }
+
+    async fn requests(&self, route: &str) -> Vec<Request> {
+        let mut requests = self.server.received_requests().await.unwrap_or_default();
+        requests.retain(|r| r.url.path() == route);
+        requests
+    }
+}
+
+/// Agent negotiator speaking the mock's HTTP API
+struct HttpNegotiator {
+    http: HttpClient,
+    url: String,
+    timeout: Duration,
+}
+
+impl HttpNegotiator {
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        route: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T, String> {
+        let url = format!("{}{}", self.url, route);
+        let resp = match body {
+            Some(body) => self.http.post_json(&url, &[], &body, self.timeout).await?,
+            None => self.http.get(&url, &[], self.timeout).await?,
+        };
+        if !resp.is_success() {
+            let error = serde_json::from_str::<serde_json::Value>(&resp.body)
+                .ok()
+                .and_then(|v| v["error"].as_str().map(str::to_string))
+                .unwrap_or_else(|| format!("HTTP status {}", resp.status));
+            return Err(error);
+        }
+        serde_json::from_str(&resp.body).map_err(|e| format!("invalid response from {}: {}", route, e))
+    }
+}
+
+#[async_trait::async_trait]
+impl AgentNegotiatorTrait for HttpNegotiator {
+    async fn request_quotes(&self, req: &TransactionRequest) -> Result<Vec<RouteQuote>, String> {
+        let body = serde_json::to_value(req).map_err(|e| e.to_string())?;
+        self.call(QUOTES_PATH, Some(body)).await
+    }
+
+    async fn execute(&self, req: &TransactionRequest, route: &RouteQuote) -> Result<TransactionResponse, String> {
+        let body = json!({ "request": req, "route": route });
+        self.call(EXECUTIONS_PATH, Some(body)).await
+    }
+
+    fn select_best_route(&self, quotes: &[RouteQuote], preference: &str) -> Result<RouteQuote, String> {
+        agent::select_best_route(quotes, preference)
+    }
+
+    async fn supported_versions(&self) -> Result<Vec<u32>, String> {
+        self.call(VERSIONS_PATH, None).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use crate::types::{ChainId, IntentType};
+
+    fn request(reference_id: &str) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+    }
+
+    #[tokio::test]
+    async fn test_canned_quotes_and_failures() {
+        let server = MockEasyCashServer::builder()
+            .with_quote(MockQuote::new("agent-cheap").fee("0.01 USDC").security(0.5))
+            .with_quote(MockQuote::new("agent-safe").fee("0.02 USDC").security(0.99))
+            .fail_quotes(1, "discovery down")
+            .fail_executions(1, "agent timed out")
+            .start()
+            .await;
+        let client = server.client();
+
+        let err = client.execute_transaction(&request("ref_1")).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::AgentUnavailable);
+        assert!(err.message.contains("discovery down"));
+
+        let err = client.execute_transaction(&request("ref_2")).await.unwrap_err();
+        assert!(err.message.contains("agent timed out"));
+
+        let resp = client.execute_transaction(&request("ref_3")).await.unwrap();
+        assert_eq!(resp.fee_used, "0.02 USDC");
+
+        assert_eq!(server.quote_requests().await, ["ref_1", "ref_2", "ref_3"]);
+        let executions = server.executions().await;
+        assert_eq!(executions.len(), 2);
+        assert_eq!(executions[0].reference_id, "ref_2");
+        assert_eq!(executions[1].agent_id, "agent-safe");
+    }
+
+    #[tokio::test]
+    async fn test_latency_and_failing_agent() {
+        let server = MockEasyCashServer::builder()
+            .with_quote_latency(Duration::from_millis(200))
+            .with_failing_agent("mock-agent")
+            .start()
+            .await;
+        let client = server.client();
+
+        let started = std::time::Instant::now();
+        let err = client.execute_transaction(&request("ref_1")).await.unwrap_err();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+        assert!(err.message.contains("agent mock-agent rejected the transaction"));
+        assert_eq!(server.executions().await[0].agent_id, "mock-agent");
+    }
+}
@@ -0,0 +1,162 @@
+//! Encrypted transport of shielded intents to agents.
+//!
+//! Shielded intents are not sent to agents in plaintext JSON. Their amount,
+//! recipient and travel-rule data are sealed (ECIES) to the transport key
+//! the executing agent publishes in the agent directory, and cleared from
+//! the request sent on the wire. The agent restores them with its secret key.
+//!
+//! ```
+//! use ecash_sdk_core::transport;
+//! use ecash_sdk_core::types::{ChainId, IntentType, TransactionRequest};
+//! use k256::SecretKey;
+//!
+//! let agent_key = SecretKey::from_bytes(&[9u8; 32].into()).unwrap();
+//! let req = TransactionRequest::new("ref_001", IntentType::Transfer, "250.00", "USDC", ChainId::Base)
+//!     .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+//!     .with_shielded(true);
+//!
+//! let (mut wire, sealed) = transport::seal(&req, "agent-001", &agent_key.public_key()).unwrap();
+//! assert!(wire.amount.is_empty() && wire.recipient.is_none());
+//!
+//! sealed.open(&agent_key).unwrap().restore(&mut wire).unwrap();
+//! assert_eq!((wire.amount, wire.recipient), (req.amount, req.recipient));
+//! ```
+
+use k256::{PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
+
+use crate::crypto;
+use crate::travel_rule::TravelRuleInfo;
+use crate::types::TransactionRequest;
+
+/// Intent fields sealed to the executing agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentSecrets {
+    /// Reference ID of the intent the fields belong to
+    #[serde(rename = "reference_id")]
+    pub reference_id: String,
+    pub amount: String,
+    #[serde(rename = "amount_base_units", default, skip_serializing_if = "Option::is_none")]
+    pub amount_base_units: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    #[serde(rename = "travel_rule", default, skip_serializing_if = "Option::is_none")]
+    pub travel_rule: Option<TravelRuleInfo>,
+    /// Quote the intent is executed under, repeated from the request so the
+    /// seal covers it
+    #[serde(rename = "quote_hash", default, skip_serializing_if = "Option::is_none")]
+    pub quote_hash: Option<String>,
+}
+
+impl IntentSecrets {
+    /// Puts the fields back into the request they were sealed from
+    pub fn restore(self, req: &mut TransactionRequest) -> Result<(), String> {
+        if self.reference_id != req.reference_id {
+            return Err(format!(
+                "sealed fields belong to {}, not {}",
+                self.reference_id, req.reference_id
+            ));
+        }
+        if req.quote_hash.is_some() && req.quote_hash != self.quote_hash {
+            return Err(format!("sealed fields of {} are bound to another quote", req.reference_id));
+        }
+        req.amount = self.amount;
+        req.amount_base_units = self.amount_base_units;
+        req.recipient = self.recipient;
+        req.travel_rule = self.travel_rule;
+        req.quote_hash = self.quote_hash;
+        Ok(())
+    }
+}
+
+/// Intent fields encrypted to an agent's transport key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SealedIntent {
+    /// Agent able to decrypt the payload
+    #[serde(rename = "agent_id")]
+    pub agent_id: String,
+    /// Hex-encoded ECIES ciphertext of the JSON-encoded `IntentSecrets`
+    pub ciphertext: String,
+}
+
+impl SealedIntent {
+    /// Decrypts the fields with the agent's transport secret key
+    pub fn open(&self, secret_key: &SecretKey) -> Result<IntentSecrets, String> {
+        let hex_str = self.ciphertext.strip_prefix("0x").unwrap_or(&self.ciphertext);
+        let bytes = hex::decode(hex_str).map_err(|e| format!("invalid hex: {}", e))?;
+        let plaintext = zeroize::Zeroizing::new(crypto::ecies_decrypt(secret_key, &bytes)?);
+        serde_json::from_slice(&plaintext).map_err(|e| format!("invalid sealed intent: {}", e))
+    }
+}
+
+/// Seals the sensitive fields of `req` to `agent_key`, returning the request
+/// with those fields cleared alongside the sealed payload
+pub fn seal(
+    req: &TransactionRequest,
+    agent_id: &str,
+    agent_key: &PublicKey,
+) -> Result<(TransactionRequest, SealedIntent), String> {
+    let mut wire = req.clone();
+    let secrets = IntentSecrets {
+        reference_id: req.reference_id.clone(),
+        amount: std::mem::take(&mut wire.amount),
+        amount_base_units: wire.amount_base_units.take(),
+        recipient: wire.recipient.take(),
+        travel_rule: wire.travel_rule.take(),
+        quote_hash: req.quote_hash.clone(),
+    };
+    let plaintext = zeroize::Zeroizing::new(
+        serde_json::to_vec(&secrets).map_err(|e| format!("failed to encode sealed intent: {}", e))?,
+    );
+    let ciphertext = crypto::ecies_encrypt(agent_key, &plaintext)?;
+    Ok((
+        wire,
+        SealedIntent {
+            agent_id: agent_id.to_string(),
+            ciphertext: format!("0x{}", hex::encode(ciphertext)),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+
+    fn key(seed: u8) -> SecretKey {
+        SecretKey::from_bytes(&[seed; 32].into()).unwrap()
+    }
+
+    fn request(reference_id: &str) -> TransactionRequest {
+        TransactionRequest::new(reference_id, IntentType::Transfer, "250.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_shielded(true)
+            .with_amount_base_units("250000000")
+    }
+
+    #[test]
+    fn test_sealed_fields_are_not_on_the_wire() {
+        let (wire, sealed) = seal(&request("ref_001"), "agent-001", &key(9).public_key()).unwrap();
+        let json = serde_json::to_string(&wire).unwrap();
+        assert!(!json.contains("250") && !json.contains("0x742d35"));
+
+        assert!(sealed.open(&key(8)).is_err());
+        let secrets = sealed.open(&key(9)).unwrap();
+        assert_eq!(secrets.amount_base_units.as_deref(), Some("250000000"));
+
+        // Sealed fields cannot be moved onto another intent
+        let mut other = request("ref_002");
+        assert!(secrets.restore(&mut other).unwrap_err().contains("ref_001"));
+
+        // Nor onto another quote
+        let mut req = request("ref_003");
+        req.quote_hash = Some("0xq1".to_string());
+        let (mut wire, sealed) = seal(&req, "agent-001", &key(9).public_key()).unwrap();
+        wire.quote_hash = Some("0xq2".to_string());
+        let err = sealed.open(&key(9)).unwrap().restore(&mut wire).unwrap_err();
+        assert!(err.contains("another quote"));
+        wire.quote_hash = None;
+        sealed.open(&key(9)).unwrap().restore(&mut wire).unwrap();
+        assert_eq!((wire.amount.as_str(), wire.quote_hash), ("250.00", req.quote_hash));
+    }
+}
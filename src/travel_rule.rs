@@ -0,0 +1,297 @@
+//! Travel-rule (FATF Recommendation 16) metadata for VASP-to-VASP transfers.
+//!
+//! Originator and beneficiary data loosely follow the IVMS101 data model. Above
+//! a configurable threshold the data is mandatory, and it is sealed (ECIES) to
+//! the beneficiary VASP's public key before being transmitted with the intent.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "crypto")]
+use crate::crypto;
+use crate::types::TransactionRequest;
+
+/// Configuration for travel-rule enforcement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TravelRuleConfig {
+    /// Amount (in asset units) at or above which travel-rule data is required
+    pub threshold: f64,
+    /// Whether to require a public key for the beneficiary VASP so data can be sealed
+    #[serde(rename = "require_encryption")]
+    pub require_encryption: bool,
+    /// Whether to enforce travel-rule requirements (opt-in)
+    pub enabled: bool,
+}
+
+impl Default for TravelRuleConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 1000.0,
+            require_encryption: true,
+            enabled: false,
+        }
+    }
+}
+
+/// Natural or legal person taking part in a transfer (IVMS101 subset)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TravelRuleParty {
+    /// Full name of the natural person or legal entity
+    pub name: String,
+    /// Wallet address or account number used for the transfer
+    #[serde(rename = "account_number", skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<String>,
+    #[serde(rename = "geographic_address", skip_serializing_if = "Option::is_none")]
+    pub geographic_address: Option<String>,
+    #[serde(rename = "national_identifier", skip_serializing_if = "Option::is_none")]
+    pub national_identifier: Option<String>,
+    /// Date of birth in ISO 8601 format (YYYY-MM-DD)
+    #[serde(rename = "date_of_birth", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(extend("format" = "date")))]
+    pub date_of_birth: Option<String>,
+    /// ISO 3166-1 alpha-2 country code
+    #[serde(rename = "country_of_residence", skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(pattern(r"^[A-Z]{2}$")))]
+    pub country_of_residence: Option<String>,
+}
+
+/// Virtual asset service provider on either side of the transfer
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Vasp {
+    pub name: String,
+    /// Legal Entity Identifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lei: Option<String>,
+    /// Hex-encoded secp256k1 public key used to seal travel-rule data
+    #[serde(rename = "public_key", skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+/// Originator/beneficiary information attached to a transaction request
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TravelRuleInfo {
+    pub originator: TravelRuleParty,
+    pub beneficiary: TravelRuleParty,
+    #[serde(rename = "originating_vasp", skip_serializing_if = "Option::is_none")]
+    pub originating_vasp: Option<Vasp>,
+    #[serde(rename = "beneficiary_vasp", skip_serializing_if = "Option::is_none")]
+    pub beneficiary_vasp: Option<Vasp>,
+}
+
+/// Travel-rule payload encrypted to the beneficiary VASP
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SealedTravelRule {
+    /// Name of the VASP able to decrypt the payload
+    #[serde(rename = "beneficiary_vasp")]
+    pub beneficiary_vasp: String,
+    /// Hex-encoded ECIES ciphertext of the JSON-encoded `TravelRuleInfo`
+    pub ciphertext: String,
+}
+
+impl TravelRuleInfo {
+    /// Checks that the fields required by FATF R.16 are present
+    pub fn validate(&self) -> Result<(), String> {
+        let originator = &self.originator;
+        if originator.name.trim().is_empty() {
+            return Err("originator name is required".to_string());
+        }
+        if originator.account_number.is_none() {
+            return Err("originator account number is required".to_string());
+        }
+        if originator.geographic_address.is_none()
+            && originator.national_identifier.is_none()
+            && originator.date_of_birth.is_none()
+        {
+            return Err(
+                "originator geographic address, national identifier, or date of birth is required"
+                    .to_string(),
+            );
+        }
+        if self.beneficiary.name.trim().is_empty() {
+            return Err("beneficiary name is required".to_string());
+        }
+        if self.beneficiary.account_number.is_none() {
+            return Err("beneficiary account number is required".to_string());
+        }
+        Ok(())
+    }
+
+    /// Encrypts this payload to the beneficiary VASP's public key
+    #[cfg(feature = "crypto")]
+    pub fn seal(&self) -> Result<SealedTravelRule, String> {
+        let vasp = self
+            .beneficiary_vasp
+            .as_ref()
+            .ok_or_else(|| "beneficiary VASP is required to seal travel-rule data".to_string())?;
+        let key_hex = vasp
+            .public_key
+            .as_ref()
+            .ok_or_else(|| format!("beneficiary VASP {} has no public key", vasp.name))?;
+        let public_key = crypto::public_key_from_hex(key_hex)?;
+
+        let plaintext = serde_json::to_vec(self).map_err(|e| format!("failed to encode travel-rule data: {}", e))?;
+        let ciphertext = crypto::ecies_encrypt(&public_key, &plaintext)?;
+        Ok(SealedTravelRule {
+            beneficiary_vasp: vasp.name.clone(),
+            ciphertext: format!("0x{}", hex::encode(ciphertext)),
+        })
+    }
+}
+
+#[cfg(feature = "crypto")]
+impl SealedTravelRule {
+    /// Decrypts the payload with the beneficiary VASP's secret key
+    pub fn open(&self, secret_key: &k256::SecretKey) -> Result<TravelRuleInfo, String> {
+        let hex_str = self.ciphertext.strip_prefix("0x").unwrap_or(&self.ciphertext);
+        let bytes = hex::decode(hex_str).map_err(|e| format!("invalid hex: {}", e))?;
+        let plaintext = crypto::ecies_decrypt(secret_key, &bytes)?;
+        serde_json::from_slice(&plaintext).map_err(|e| format!("invalid travel-rule payload: {}", e))
+    }
+}
+
+/// Returns true if the request amount requires travel-rule data
+pub fn is_required(req: &TransactionRequest, config: &TravelRuleConfig) -> bool {
+    if !config.enabled {
+        return false;
+    }
+    // Amounts that can't be read are held to the requirement
+    match req.amount.parse::<f64>() {
+        Ok(amount) => amount >= config.threshold,
+        Err(_) => true,
+    }
+}
+
+/// Validates travel-rule data on a request against the configuration
+pub fn validate_request(req: &TransactionRequest, config: &TravelRuleConfig) -> Result<(), String> {
+    match &req.travel_rule {
+        Some(info) => {
+            info.validate()?;
+            if config.enabled && config.require_encryption {
+                let has_key = info
+                    .beneficiary_vasp
+                    .as_ref()
+                    .map(|v| v.public_key.is_some())
+                    .unwrap_or(false);
+                if !has_key {
+                    return Err("beneficiary VASP public key is required to encrypt travel-rule data".to_string());
+                }
+            }
+            Ok(())
+        }
+        None if is_required(req, config) => Err(format!(
+            "travel-rule data is required for transfers of {} or more",
+            config.threshold
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChainId, IntentType};
+    #[cfg(feature = "crypto")]
+    use k256::SecretKey;
+
+    /// Compressed public key of `vasp_key`
+    const VASP_PUBLIC_KEY: &str = "0x0256b328b30c8bf5839e24058747879408bdb36241dc9c2e7c619faa12b2920967";
+
+    #[cfg(feature = "crypto")]
+    fn vasp_key() -> SecretKey {
+        SecretKey::from_bytes(&[9u8; 32].into()).unwrap()
+    }
+
+    fn travel_rule_info() -> TravelRuleInfo {
+        TravelRuleInfo {
+            originator: TravelRuleParty {
+                name: "Alice Example".to_string(),
+                account_number: Some("0x1111111111111111111111111111111111111111".to_string()),
+                date_of_birth: Some("1990-01-01".to_string()),
+                ..Default::default()
+            },
+            beneficiary: TravelRuleParty {
+                name: "Bob Example".to_string(),
+                account_number: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
+                ..Default::default()
+            },
+            originating_vasp: None,
+            beneficiary_vasp: Some(Vasp {
+                name: "Beneficiary Exchange".to_string(),
+                lei: None,
+                public_key: Some(VASP_PUBLIC_KEY.to_string()),
+            }),
+        }
+    }
+
+    fn request(amount: &str, travel_rule: Option<TravelRuleInfo>) -> TransactionRequest {
+        TransactionRequest {
+            travel_rule,
+            ..TransactionRequest::new("ref_001", IntentType::Transfer, amount, "USDC", ChainId::Base)
+        }
+    }
+
+    fn enabled_config() -> TravelRuleConfig {
+        TravelRuleConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_required_above_threshold() {
+        let config = enabled_config();
+        assert!(validate_request(&request("999.99", None), &config).is_ok());
+        assert!(validate_request(&request("1000", None), &config).is_err());
+        assert!(validate_request(&request("5000", Some(travel_rule_info())), &config).is_ok());
+    }
+
+    #[test]
+    fn test_unreadable_amount_requires_travel_rule() {
+        let config = enabled_config();
+        assert!(is_required(&request("1,000,000", None), &config));
+        assert!(validate_request(&request("lots", None), &config).is_err());
+    }
+
+    #[test]
+    fn test_validate_missing_originator_identifier() {
+        let mut info = travel_rule_info();
+        info.originator.date_of_birth = None;
+        assert!(info.validate().unwrap_err().contains("originator geographic address"));
+    }
+
+    #[test]
+    fn test_validate_requires_vasp_key_when_encrypting() {
+        let mut info = travel_rule_info();
+        info.beneficiary_vasp = None;
+        let config = enabled_config();
+        assert!(validate_request(&request("5000", Some(info.clone())), &config).is_err());
+
+        let config = TravelRuleConfig {
+            require_encryption: false,
+            ..enabled_config()
+        };
+        assert!(validate_request(&request("5000", Some(info)), &config).is_ok());
+    }
+
+    #[cfg(feature = "crypto")]
+    #[test]
+    fn test_seal_and_open() {
+        assert_eq!(crypto::public_key_to_hex(&vasp_key().public_key()), VASP_PUBLIC_KEY);
+        let info = travel_rule_info();
+        let sealed = info.seal().unwrap();
+        assert_eq!(sealed.beneficiary_vasp, "Beneficiary Exchange");
+        assert!(!sealed.ciphertext.contains("Alice"));
+
+        let opened = sealed.open(&vasp_key()).unwrap();
+        assert_eq!(opened, info);
+    }
+
+    #[test]
+    fn test_disabled_config_skips_threshold() {
+        let config = TravelRuleConfig::default();
+        assert!(validate_request(&request("1000000", None), &config).is_ok());
+    }
+}
@@ -1,13 +1,54 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::crypto::hash::HashFunction;
+use crate::travel_rule::{SealedTravelRule, TravelRuleInfo};
+
+static STRICT_ENUMS: AtomicBool = AtomicBool::new(false);
+
+/// Makes deserializing an unknown chain or intent type an error. By default
+/// unknown values (e.g. a chain added to the API after this SDK version)
+/// deserialize as `Unknown`, which validation then rejects. Process-wide.
+pub fn set_strict_enums(strict: bool) {
+    STRICT_ENUMS.store(strict, Ordering::Relaxed);
+}
+
+/// Parses a serialized enum value, falling back to `unknown` unless strict
+fn deserialize_tolerant<'de, D, T>(deserializer: D, unknown: T) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = String>,
+{
+    let value = Cow::<str>::deserialize(deserializer)?;
+    match value.parse() {
+        Ok(parsed) => Ok(parsed),
+        Err(e) if STRICT_ENUMS.load(Ordering::Relaxed) => Err(serde::de::Error::custom(e)),
+        Err(_) => {
+            tracing::debug!("[SDK] Treating unrecognized value {:?} as unknown", value);
+            Ok(unknown)
+        }
+    }
+}
 
 /// Supported blockchain networks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum ChainId {
     Ethereum,
     Base,
     Solana,
+    /// A chain this SDK version doesn't know (see `set_strict_enums`)
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for ChainId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_tolerant(deserializer, ChainId::Unknown)
+    }
 }
 
 impl ChainId {
@@ -16,6 +57,7 @@ impl ChainId {
             ChainId::Ethereum => "ethereum",
             ChainId::Base => "base",
             ChainId::Solana => "solana",
+            ChainId::Unknown => "unknown",
         }
     }
 }
@@ -40,12 +82,25 @@ impl FromStr for ChainId {
 }
 
 /// Classification of the operation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum IntentType {
     Transfer,
     Swap,
     Shield,
+    /// One debit funding several recipients (see `intents::DisburseHandler`)
+    Disburse,
+    /// Funds locked until a release condition is met (see `escrow`)
+    Escrow,
+    /// An intent type this SDK version doesn't know (see `set_strict_enums`)
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for IntentType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_tolerant(deserializer, IntentType::Unknown)
+    }
 }
 
 impl IntentType {
@@ -54,6 +109,9 @@ impl IntentType {
             IntentType::Transfer => "transfer",
             IntentType::Swap => "swap",
             IntentType::Shield => "shield",
+            IntentType::Disburse => "disburse",
+            IntentType::Escrow => "escrow",
+            IntentType::Unknown => "unknown",
         }
     }
 }
@@ -72,6 +130,8 @@ impl FromStr for IntentType {
             "transfer" => Ok(IntentType::Transfer),
             "swap" => Ok(IntentType::Swap),
             "shield" => Ok(IntentType::Shield),
+            "disburse" => Ok(IntentType::Disburse),
+            "escrow" => Ok(IntentType::Escrow),
             _ => Err(format!("unknown intent type: {}", s)),
         }
     }
@@ -79,13 +139,16 @@ impl FromStr for IntentType {
 
 /// Standard payload for initiating an operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransactionRequest {
     #[serde(rename = "reference_id")]
     pub reference_id: String,
     #[serde(rename = "type")]
     pub intent_type: IntentType,
-    pub amount: String, // String to support big integers
-    pub asset: String,  // e.g., "USDC"
+    /// Decimal amount in whole units of the asset, e.g. "1000.00"
+    pub amount: String,
+    /// Asset symbol, e.g. "USDC"
+    pub asset: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recipient: Option<String>,
     #[serde(rename = "source_chain")]
@@ -95,9 +158,155 @@ pub struct TransactionRequest {
     /// Privacy options
     #[serde(rename = "is_shielded")]
     pub is_shielded: bool,
+    /// Originator/beneficiary data for VASP-to-VASP transfers
+    #[serde(rename = "travel_rule", default, skip_serializing_if = "Option::is_none")]
+    pub travel_rule: Option<TravelRuleInfo>,
+    /// `travel_rule` sealed to the beneficiary VASP. Set by the client in
+    /// place of `travel_rule` before the request reaches agents
+    #[serde(rename = "sealed_travel_rule", default, skip_serializing_if = "Option::is_none")]
+    pub sealed_travel_rule: Option<SealedTravelRule>,
+    /// Caller-supplied ID used to correlate SDK logs, events, and errors; generated if absent
+    #[serde(rename = "correlation_id", default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// `amount` in the asset's smallest unit, as an integer string. Set by the
+    /// client before the request reaches agents when the asset's decimals are known
+    #[serde(rename = "amount_base_units", default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(pattern(r"^[0-9]+$")))]
+    pub amount_base_units: Option<String>,
+    /// Recipients of a `Disburse` request, whose amounts add up to `amount`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disbursements: Vec<Disbursement>,
+    /// Shares of a transfer's `amount` paid to platforms rather than the recipient
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fee_splits: Vec<FeeSplit>,
+    /// Release condition and refund deadline of an `Escrow` request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub escrow: Option<EscrowTerms>,
+    /// Who pays the network fee, when not the payer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<FeePayer>,
+    /// Commitment to the quote the intent is executed under (see
+    /// `RouteQuote::commitment`). Set by the client before the request
+    /// reaches the executing agent, binding it to the quoted fee
+    #[serde(rename = "quote_hash", default, skip_serializing_if = "Option::is_none")]
+    pub quote_hash: Option<String>,
+    /// Internal account the transaction is attributed to (e.g. an exchange
+    /// sub-account). Recorded in submissions, events and metrics; never sent
+    /// to agents
+    #[serde(rename = "account_id", default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    /// Caller-defined attributes, carried along with `account_id`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
 }
 
 impl TransactionRequest {
+    /// Request with the required fields set and every optional field empty;
+    /// the `with_*` builders set the rest.
+    ///
+    /// ```
+    /// use ecash_sdk_core::{ChainId, IntentType, TransactionRequest};
+    ///
+    /// let req = TransactionRequest::new("ref_001", IntentType::Transfer, "100.00", "USDC", ChainId::Base)
+    ///    .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+    ///    .with_shielded(true);
+    /// assert!(req.validate().is_ok());
+    /// ```
+    pub fn new(
+        reference_id: impl Into<String>,
+        intent_type: IntentType,
+        amount: impl Into<String>,
+        asset: impl Into<String>,
+        source_chain: ChainId,
+    ) -> Self {
+        Self {
+            reference_id: reference_id.into(),
+            intent_type,
+            amount: amount.into(),
+            asset: asset.into(),
+            recipient: None,
+            source_chain,
+            target_chain: None,
+            is_shielded: false,
+            travel_rule: None,
+            sealed_travel_rule: None,
+            correlation_id: None,
+            amount_base_units: None,
+            disbursements: Vec::new(),
+            fee_splits: Vec::new(),
+            escrow: None,
+            fee_payer: None,
+            quote_hash: None,
+            account_id: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_recipient(mut self, recipient: impl Into<String>) -> Self {
+        self.recipient = Some(recipient.into());
+        self
+    }
+
+    pub fn with_target_chain(mut self, target_chain: ChainId) -> Self {
+        self.target_chain = Some(target_chain);
+        self
+    }
+
+    pub fn with_shielded(mut self, is_shielded: bool) -> Self {
+        self.is_shielded = is_shielded;
+        self
+    }
+
+    pub fn with_travel_rule(mut self, travel_rule: TravelRuleInfo) -> Self {
+        self.travel_rule = Some(travel_rule);
+        self
+    }
+
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    pub fn with_amount_base_units(mut self, amount_base_units: impl Into<String>) -> Self {
+        self.amount_base_units = Some(amount_base_units.into());
+        self
+    }
+
+    pub fn with_account_id(mut self, account_id: impl Into<String>) -> Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// Adds one caller-defined attribute
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// New reference ID: `prefix` followed by a UUIDv7 in ULID encoding (26
+    /// Crockford base32 characters). IDs from one process sort in creation
+    /// order, and IDs from different processes sort by millisecond.
+    ///
+    /// ```
+    /// use ecash_sdk_core::TransactionRequest;
+    ///
+    /// let first = TransactionRequest::generate_reference("pay_");
+    /// let second = TransactionRequest::generate_reference("pay_");
+    /// assert_eq!(first.len(), 30);
+    /// assert!(first.starts_with("pay_") && first < second);
+    /// ```
+    pub fn generate_reference(prefix: &str) -> String {
+        const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+        let value = uuid::Uuid::now_v7().as_u128();
+        let mut reference = String::with_capacity(prefix.len() + 26);
+        reference.push_str(prefix);
+        // 26 characters of 5 bits; the first holds the top 3 bits
+        for i in (0..26).rev() {
+            reference.push(CROCKFORD[((value >> (i * 5)) & 31) as usize] as char);
+        }
+        reference
+    }
+
     /// Validates the transaction request
     pub fn validate(&self) -> Result<(), String> {
         if self.amount.is_empty() {
@@ -112,19 +321,171 @@ impl TransactionRequest {
 
 /// Result of an intent execution
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TransactionResponse {
     #[serde(rename = "tx_hash")]
     pub tx_hash: String,
     pub status: String,
     #[serde(rename = "block_height")]
     pub block_height: u64,
+    /// Fee paid, with its asset, e.g. "0.05 USDC"
     #[serde(rename = "fee_used")]
     pub fee_used: String,
+    /// Correlation ID of the `execute_transaction` call
+    #[serde(rename = "correlation_id", default)]
+    pub correlation_id: String,
+    /// Outcome per recipient of a disbursement, in request order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disbursements: Vec<DisbursementResult>,
+    /// Platform fees taken out of a transfer with fee splits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fee_splits: Vec<FeeSplitAmount>,
+}
+
+impl TransactionResponse {
+    /// An agent's report of an execution it settled for `fee_used`. The
+    /// client checks the fee against the quote and fills in the rest
+    pub fn executed(fee_used: impl Into<String>) -> Self {
+        Self {
+            tx_hash: String::new(),
+            status: "confirmed".to_string(),
+            block_height: 0,
+            fee_used: fee_used.into(),
+            correlation_id: String::new(),
+            disbursements: Vec::new(),
+            fee_splits: Vec::new(),
+        }
+    }
+}
+
+/// What allows the locked funds to be paid to the recipient
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReleaseCondition {
+    /// Releasable once Unix time `release_at` (seconds) has passed
+    Timeout { release_at: u64 },
+    /// Releasable with the counterparty's signature of the release message
+    CounterpartySignature { public_key: String },
+    /// Releasable with the oracle's signed attestation of `statement`
+    OracleAttestation { public_key: String, statement: String },
+    /// Releasable by revealing the secret whose `hash_function` hash is
+    /// `hash` (hex)
+    Hashlock {
+        hash: String,
+        #[serde(default)]
+        hash_function: HashFunction,
+    },
+}
+
+/// Release condition and refund deadline of an escrow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EscrowTerms {
+    pub condition: ReleaseCondition,
+    /// Unix time (seconds) after which the payer may take the funds back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_after: Option<u64>,
+}
+
+impl EscrowTerms {
+    pub fn new(condition: ReleaseCondition) -> Self {
+        Self {
+            condition,
+            refund_after: None,
+        }
+    }
+
+    pub fn with_refund_after(mut self, refund_after: u64) -> Self {
+        self.refund_after = Some(refund_after);
+        self
+    }
+}
+
+/// Who pays a request's network fee
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeePayer {
+    /// The payer, as without a fee payer
+    Sender,
+    /// The recipient, receiving the amount less the fee
+    Recipient,
+    /// A registered sponsor, with its signed `authorization_message`
+    Sponsor { sponsor_id: String, signature: String },
+}
+
+/// One recipient of a disbursement and the amount it receives
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Disbursement {
+    pub recipient: String,
+    pub amount: String,
+}
+
+/// Outcome of one disbursement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DisbursementResult {
+    pub recipient: String,
+    pub amount: String,
+    /// "confirmed" or "failed"
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DisbursementResult {
+    /// Result of `disbursement`, failed if `error` is set
+    pub fn new(disbursement: &Disbursement, error: Option<String>) -> Self {
+        Self {
+            recipient: disbursement.recipient.clone(),
+            amount: disbursement.amount.clone(),
+            status: if error.is_some() { "failed" } else { "confirmed" }.to_string(),
+            error,
+        }
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Share of a transfer paid to a platform, in basis points of its amount
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FeeSplit {
+    pub recipient: String,
+    pub bps: u32,
+}
+
+/// A fee split applied to a transfer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FeeSplitAmount {
+    pub recipient: String,
+    pub bps: u32,
+    /// Amount paid to `recipient`, in whole units of the transfer's asset
+    pub amount: String,
+}
+
+/// Balance of an asset held by an address or viewing key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Balance {
+    pub asset: String,
+    pub chain: ChainId,
+    /// On-chain balance of an address, in the asset's smallest unit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transparent: Option<String>,
+    /// Sum of unspent shielded notes visible to a viewing key, in the asset's smallest unit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shielded: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_chain_id_display() {
@@ -145,50 +506,58 @@ mod tests {
         assert_eq!(IntentType::Transfer.to_string(), "transfer");
         assert_eq!(IntentType::Swap.to_string(), "swap");
         assert_eq!(IntentType::Shield.to_string(), "shield");
+        assert_eq!(IntentType::Disburse.to_string(), "disburse");
+        assert_eq!(IntentType::Escrow.to_string(), "escrow");
+    }
+
+    #[test]
+    fn test_generate_reference() {
+        let references: Vec<String> = (0..1000).map(|_| TransactionRequest::generate_reference("ref_")).collect();
+        assert!(references.windows(2).all(|w| w[0] < w[1]));
+        let ulid = &references[0]["ref_".len()..];
+        assert_eq!(ulid.len(), 26);
+        assert!(ulid.bytes().all(|b| b.is_ascii_digit() || b.is_ascii_uppercase()));
+        assert!(crate::validator::ReferenceIdPolicy::default().check(&references[0]).is_ok());
     }
 
     #[test]
     fn test_transaction_request_validate() {
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: None,
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base);
         assert!(req.validate().is_ok());
     }
 
     #[test]
     fn test_transaction_request_validate_empty_amount() {
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "".to_string(),
-            asset: "USDC".to_string(),
-            recipient: None,
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "", "USDC", ChainId::Base);
         assert!(req.validate().is_err());
     }
 
+    #[test]
+    fn test_unknown_variants() {
+        let json = r#"{"reference_id": "ref_001", "type": "bridge", "amount": "1", "asset": "USDC",
+            "recipient": null, "source_chain": "polygon", "target_chain": "base", "is_shielded": false}"#;
+        let req: TransactionRequest = serde_json::from_str(json).unwrap();
+        assert_eq!((req.intent_type, req.source_chain), (IntentType::Unknown, ChainId::Unknown));
+        assert_eq!(req.target_chain, Some(ChainId::Base));
+        assert!(crate::validator::validate_transaction_request(&req).is_err());
+        assert_eq!(serde_json::to_value(req.source_chain).unwrap(), "unknown");
+
+        let balances: HashMap<ChainId, u32> = serde_json::from_str(r#"{"base": 1, "sui": 2}"#).unwrap();
+        assert_eq!(balances[&ChainId::Unknown], 2);
+
+        set_strict_enums(true);
+        let strict = serde_json::from_str::<TransactionRequest>(json);
+        set_strict_enums(false);
+        assert!(strict.unwrap_err().to_string().contains("unknown"));
+        assert!(ChainId::from_str("polygon").is_err());
+    }
+
     #[test]
     fn test_transaction_request_serialize() {
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-            source_chain: ChainId::Base,
-            target_chain: Some(ChainId::Ethereum),
-            is_shielded: true,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_target_chain(ChainId::Ethereum)
+            .with_shielded(true);
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("transfer"));
         assert!(json.contains("base"));
@@ -208,6 +577,8 @@ mod tests {
         assert_eq!(IntentType::from_str("transfer").unwrap(), IntentType::Transfer);
         assert_eq!(IntentType::from_str("swap").unwrap(), IntentType::Swap);
         assert_eq!(IntentType::from_str("shield").unwrap(), IntentType::Shield);
+        assert_eq!(IntentType::from_str("disburse").unwrap(), IntentType::Disburse);
+        assert_eq!(IntentType::from_str("escrow").unwrap(), IntentType::Escrow);
         assert!(IntentType::from_str("invalid").is_err());
     }
 }
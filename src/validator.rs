@@ -1,27 +1,191 @@
-use regex::Regex;
-use crate::types::{ChainId, TransactionRequest};
+//! Request validation.
+//!
+//! Every request passes through a `ValidationPipeline` before it executes.
+//! The pipeline starts with the built-in validators (`amount`, `chain`,
+//! `recipient`, `reference` and, when configured, `asset` and `minimum`); integrators can
+//! disable built-ins by name and append their own `Validator`s, e.g. an
+//! internal recipient allowlist.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use ecash_sdk_core::validator::{ValidationPipeline, Validator};
+//! use ecash_sdk_core::TransactionRequest;
+//!
+//! struct RecipientRequired;
+//!
+//! impl Validator for RecipientRequired {
+//!     fn name(&self) -> &str {
+//!         "recipient_required"
+//!     }
+//!
+//!     fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+//!         if req.recipient.is_none() {
+//!             return Err("a recipient is required".to_string());
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let pipeline = ValidationPipeline::default().with_validator(Arc::new(RecipientRequired));
+//! assert_eq!(pipeline.names(), ["amount", "chain", "recipient", "reference", "recipient_required"]);
+//! ```
 
-lazy_static::lazy_static! {
-    static ref ADDRESS_REGEX: Regex = Regex::new(r"^0x[a-fA-F0-9]{40}$").unwrap();
-    static ref AMOUNT_REGEX: Regex = Regex::new(r"^\d+(\.\d+)?$").unwrap();
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+use crate::assets::AssetRegistry;
+use crate::types::{ChainId, IntentType, TransactionRequest};
+
+// Both checks run on every request, so they scan bytes instead of running a
+// regex. ASCII only: other scripts' digits don't parse as f64.
+
+/// `0x` followed by 40 hex digits
+fn is_address(address: &str) -> bool {
+    address.len() == 42 && address.starts_with("0x") && address.as_bytes()[2..].iter().all(u8::is_ascii_hexdigit)
+}
+
+/// Digits, optionally followed by `.` and more digits
+fn is_decimal(amount: &str) -> bool {
+    let (int, frac) = match amount.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (amount, None),
+    };
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    digits(int) && frac.is_none_or(digits)
+}
+
+/// Thousands separators other than `.` and `,`: spaces (including no-break
+/// and narrow no-break spaces) and the apostrophe
+fn is_group_separator(c: char) -> bool {
+    matches!(c, ' ' | '\'' | '\u{a0}' | '\u{202f}')
+}
+
+/// Byte index of the decimal separator in a localized amount: the last of `.`
+/// and `,` when both appear, otherwise one that appears exactly once. A lone
+/// `,` followed by three digits could be either and is an error.
+fn decimal_separator(amount: &str) -> Result<Option<usize>, String> {
+    let once = |c: char| amount.matches(c).count() == 1;
+    Ok(match (amount.rfind('.'), amount.rfind(',')) {
+        (Some(dot), Some(comma)) => Some(dot.max(comma)),
+        (Some(dot), None) => once('.').then_some(dot),
+        (None, Some(comma)) if once(',') => {
+            if amount.len() - comma == 4 && !amount.trim().contains(is_group_separator) {
+                return Err(format!(
+                    "',' at position {} could separate thousands or decimals; remove it or use '.' for decimals",
+                    amount[..comma].chars().count() + 1
+                ));
+            }
+            Some(comma)
+        }
+        _ => None,
+    })
+}
+
+/// Rewrites a localized amount ("1,000.50", "1.000,50", "1 000,50",
+/// "1'000.50") as a plain decimal ("1000.50"). Thousands groups must have
+/// three digits. Plain decimals are returned unchanged.
+pub fn normalize_amount(amount: &str) -> Result<String, String> {
+    let amount = amount.trim();
+    if is_decimal(amount) {
+        return Ok(amount.to_string());
+    }
+    let unrecognized = || format!("unrecognized amount format: {}", amount);
+    let (int, frac) = match decimal_separator(amount)? {
+        Some(i) => (&amount[..i], Some(&amount[i + 1..])),
+        None => (amount, None),
+    };
+    let mut normalized = String::with_capacity(amount.len());
+    if let Some(separator) = int.chars().find(|c| !c.is_ascii_digit()) {
+        if !matches!(separator, '.' | ',') && !is_group_separator(separator) {
+            return Err(unrecognized());
+        }
+        for (n, group) in int.split(separator).enumerate() {
+            let valid_len = if n == 0 { (1..=3).contains(&group.len()) } else { group.len() == 3 };
+            if !valid_len || !group.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(unrecognized());
+            }
+            normalized.push_str(group);
+        }
+    } else {
+        normalized.push_str(int);
+    }
+    if let Some(frac) = frac {
+        normalized.push('.');
+        normalized.push_str(frac);
+    }
+    if !is_decimal(&normalized) {
+        return Err(unrecognized());
+    }
+    Ok(normalized)
+}
+
+/// Explains why `amount` isn't a plain decimal: the first offending
+/// character (1-based position) and, for locale formats, the plain form
+fn describe_invalid_amount(amount: &str) -> String {
+    let separator = decimal_separator(amount);
+    let normalized = normalize_amount(amount).ok();
+    let at_edge = |i: usize| amount[..i].trim().is_empty() || amount[i..].trim().is_empty();
+    let problem = amount.char_indices().zip(1..).find_map(|((i, c), position)| {
+        let problem = match c {
+            '0'..='9' => return None,
+            '.' if separator == Ok(Some(i)) => return None,
+            ',' if separator.is_err() => return separator.clone().err(),
+            ',' if separator == Ok(Some(i)) && normalized.is_some() => "use '.' as the decimal separator".to_string(),
+            c if c.is_whitespace() && at_edge(i) => "remove the surrounding whitespace".to_string(),
+            c if (matches!(c, '.' | ',') || is_group_separator(c)) && normalized.is_some() => {
+                format!("remove the thousands separator {:?}", c)
+            }
+            '-' => "amounts must be positive; remove the '-'".to_string(),
+            c if c.is_numeric() => format!("use ASCII digits 0-9 instead of {:?}", c),
+            c => format!("unexpected {:?}", c),
+        };
+        Some(format!("{} at position {}", problem, position))
+    });
+    match (problem, normalized) {
+        (Some(problem), Some(normalized)) => {
+            format!("invalid amount format: {} ({}; did you mean {}?)", amount, problem, normalized)
+        }
+        (Some(problem), None) => format!("invalid amount format: {} ({})", amount, problem),
+        (None, _) => format!("invalid amount format: {} (expected positive number)", amount),
+    }
+}
+
+/// 32 to 44 base58 characters (an encoded 32-byte public key)
+fn is_solana_address(address: &str) -> bool {
+    (32..=44).contains(&address.len())
+        && address
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() && !matches!(b, b'0' | b'O' | b'I' | b'l'))
 }
 
 /// Validates an Ethereum address format
 pub fn validate_address(address: &str) -> Result<(), String> {
-    if !ADDRESS_REGEX.is_match(address) {
+    if !is_address(address) {
         return Err(format!("invalid address format: {}", address));
     }
     Ok(())
 }
 
+/// Validates an address in the format used on `chain`
+pub fn validate_address_for_chain(address: &str, chain: ChainId) -> Result<(), String> {
+    match chain {
+        ChainId::Ethereum | ChainId::Base => validate_address(address),
+        ChainId::Solana if is_solana_address(address) => Ok(()),
+        ChainId::Solana => Err(format!("invalid Solana address: {}", address)),
+        ChainId::Unknown => Err("unsupported chain".to_string()),
+    }
+}
+
 /// Validates an amount string
 pub fn validate_amount(amount: &str) -> Result<(), String> {
     if amount.is_empty() {
         return Err("amount cannot be empty".to_string());
     }
     
-    if !AMOUNT_REGEX.is_match(amount) {
-        return Err(format!("invalid amount format: {} (expected positive number)", amount));
+    if !is_decimal(amount) {
+        return Err(describe_invalid_amount(amount));
     }
 
     // Check if amount is positive
@@ -40,37 +204,347 @@ pub fn validate_amount(amount: &str) -> Result<(), String> {
 
 /// Validates if a chain ID is supported
 pub fn validate_chain(chain: ChainId) -> Result<(), String> {
-    // All defined ChainId variants are valid
+    // Every chain this SDK version knows is supported
     match chain {
         ChainId::Ethereum | ChainId::Base | ChainId::Solana => Ok(()),
+        ChainId::Unknown => Err("unsupported chain (this SDK version doesn't know it)".to_string()),
     }
 }
 
-/// Performs comprehensive validation on a transaction request
+/// Performs comprehensive validation on a transaction request (the
+/// built-in validators of `ValidationPipeline::default`)
 pub fn validate_transaction_request(req: &TransactionRequest) -> Result<(), String> {
-    validate_amount(&req.amount)
-        .map_err(|e| format!("amount validation failed: {}", e))?;
+    DEFAULT_PIPELINE.validate(req)
+}
+
+lazy_static! {
+    static ref DEFAULT_PIPELINE: ValidationPipeline = ValidationPipeline::default();
+}
+
+/// A check run on every request before it executes
+pub trait Validator: Send + Sync {
+    /// Name used in error messages and to disable the validator
+    fn name(&self) -> &str;
+
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String>;
+}
 
-    validate_chain(req.source_chain)
-        .map_err(|e| format!("source chain validation failed: {}", e))?;
+/// Amount is a positive decimal within bounds
+pub struct AmountValidator;
 
-    if let Some(target_chain) = req.target_chain {
-        validate_chain(target_chain)
-            .map_err(|e| format!("target chain validation failed: {}", e))?;
+impl Validator for AmountValidator {
+    fn name(&self) -> &str {
+        "amount"
     }
 
-    if let Some(ref recipient) = req.recipient {
-        validate_address(recipient)
-            .map_err(|e| format!("recipient validation failed: {}", e))?;
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        validate_amount(&req.amount)
     }
+}
 
-    Ok(())
+/// Chains and intent type are supported and the intent can run across the chains
+pub struct ChainCompatibilityValidator;
+
+impl Validator for ChainCompatibilityValidator {
+    fn name(&self) -> &str {
+        "chain"
+    }
+
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        if req.intent_type == IntentType::Unknown {
+            return Err("unsupported intent type (this SDK version doesn't know it)".to_string());
+        }
+        validate_chain(req.source_chain).map_err(|e| format!("source chain: {}", e))?;
+        let Some(target_chain) = req.target_chain else {
+            return Ok(());
+        };
+        validate_chain(target_chain).map_err(|e| format!("target chain: {}", e))?;
+        if req.intent_type == IntentType::Shield && target_chain != req.source_chain {
+            return Err(format!(
+                "shield intents stay on the source chain ({} -> {})",
+                req.source_chain, target_chain
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Recipient is an address in the destination chain's format
+pub struct RecipientValidator;
+
+impl Validator for RecipientValidator {
+    fn name(&self) -> &str {
+        "recipient"
+    }
+
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        match req.recipient {
+            Some(ref recipient) => validate_address_for_chain(recipient, req.target_chain.unwrap_or(req.source_chain)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Format rules for reference IDs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReferenceIdPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    /// Characters allowed besides ASCII letters and digits
+    pub extra_chars: String,
+    /// Reference IDs must start with one of these; empty allows any
+    pub prefixes: Vec<String>,
+}
+
+impl Default for ReferenceIdPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 1,
+            max_length: 128,
+            extra_chars: "-_.:".to_string(),
+            prefixes: Vec::new(),
+        }
+    }
+}
+
+impl ReferenceIdPolicy {
+    /// Checks `reference_id` against the policy
+    pub fn check(&self, reference_id: &str) -> Result<(), String> {
+        let len = reference_id.chars().count();
+        if len < self.min_length || len > self.max_length {
+            return Err(format!(
+                "reference ID must be {} to {} characters long (got {})",
+                self.min_length, self.max_length, len
+            ));
+        }
+        let allowed = |c: char| c.is_ascii_alphanumeric() || self.extra_chars.contains(c);
+        if let Some((position, c)) = reference_id.chars().zip(1..).find(|(c, _)| !allowed(*c)).map(|(c, p)| (p, c)) {
+            return Err(format!("reference ID contains {:?} at position {}", c, position));
+        }
+        if !self.prefixes.is_empty() && !self.prefixes.iter().any(|p| reference_id.starts_with(p.as_str())) {
+            return Err(format!(
+                "reference ID {} must start with one of: {}",
+                reference_id,
+                self.prefixes.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_length > self.max_length {
+            return Err("min_length must not exceed max_length".to_string());
+        }
+        if self.extra_chars.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err("extra_chars must not contain whitespace or control characters".to_string());
+        }
+        // Generated references are a prefix plus 26 ULID characters
+        match self.prefixes.iter().find(|p| self.check(&format!("{}{}", p, "0".repeat(26))).is_err()) {
+            Some(prefix) => Err(format!("prefix {:?} can't produce valid reference IDs", prefix)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reference ID follows the configured `ReferenceIdPolicy`
+pub struct ReferenceIdValidator {
+    policy: ReferenceIdPolicy,
+}
+
+impl ReferenceIdValidator {
+    pub fn new(policy: ReferenceIdPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl Validator for ReferenceIdValidator {
+    fn name(&self) -> &str {
+        "reference"
+    }
+
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        self.policy.check(&req.reference_id)
+    }
+}
+
+/// Asset is one of a fixed set (case-insensitive)
+pub struct AssetSupportValidator {
+    assets: Vec<String>,
+}
+
+impl AssetSupportValidator {
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(assets: I) -> Self {
+        Self {
+            assets: assets.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Validator for AssetSupportValidator {
+    fn name(&self) -> &str {
+        "asset"
+    }
+
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        if self.assets.iter().any(|a| a.eq_ignore_ascii_case(&req.asset)) {
+            return Ok(());
+        }
+        Err(format!("unsupported asset: {}", req.asset))
+    }
+}
+
+/// Amount meets the asset's minimum on the source chain; shielded amounts
+/// must also reach the dust threshold
+pub struct MinimumAmountValidator {
+    assets: AssetRegistry,
+}
+
+impl MinimumAmountValidator {
+    pub fn new(assets: AssetRegistry) -> Self {
+        Self { assets }
+    }
+}
+
+impl Validator for MinimumAmountValidator {
+    fn name(&self) -> &str {
+        "minimum"
+    }
+
+    fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        let chain = req.source_chain;
+        let min_amount = self.assets.min_amount(&req.asset, chain);
+        let dust = req.is_shielded.then(|| self.assets.dust_threshold(&req.asset, chain)).flatten();
+        if min_amount.is_none() && dust.is_none() {
+            return Ok(());
+        }
+        let amount: f64 = req.amount.parse().map_err(|e| format!("failed to parse amount: {}", e))?;
+        if let Some(min) = min_amount.filter(|min| amount < *min) {
+            return Err(format!(
+                "{} {} is below the minimum of {} on {}",
+                req.amount, req.asset, min, chain
+            ));
+        }
+        if let Some(dust) = dust.filter(|dust| amount < *dust) {
+            return Err(format!(
+                "shielded {} {} is below the dust threshold of {} on {}",
+                req.amount, req.asset, dust, chain
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Names of the built-in validators, in the order they run
+pub const BUILTIN_VALIDATORS: [&str; 6] = ["amount", "chain", "recipient", "reference", "asset", "minimum"];
+
+/// Built-in validator by name, with default settings (`asset` and `minimum` need configuration, see
+/// `AssetSupportValidator::new` and `MinimumAmountValidator::new`)
+pub fn builtin(name: &str) -> Option<Arc<dyn Validator>> {
+    match name {
+        "amount" => Some(Arc::new(AmountValidator)),
+        "chain" => Some(Arc::new(ChainCompatibilityValidator)),
+        "recipient" => Some(Arc::new(RecipientValidator)),
+        "reference" => Some(Arc::new(ReferenceIdValidator::new(ReferenceIdPolicy::default()))),
+        _ => None,
+    }
+}
+
+/// Validation settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// Built-in validators to skip, by name
+    pub disabled: Vec<String>,
+    /// Assets accepted by the `asset` validator; empty accepts any asset
+    pub supported_assets: Vec<String>,
+    /// Rewrite localized amounts ("1,000.50", "1 000,50") as plain decimals
+    /// before validation instead of rejecting them (see `normalize_amount`)
+    pub normalize_locale_amounts: bool,
+    /// Format rules checked by the `reference` validator
+    pub reference: ReferenceIdPolicy,
+}
+
+impl ValidationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = self.disabled.iter().find(|name| !BUILTIN_VALIDATORS.contains(&name.as_str())) {
+            return Err(format!("validation.disabled: unknown validator {}", name));
+        }
+        self.reference.validate().map_err(|e| format!("validation.reference: {}", e))
+    }
+}
+
+/// Ordered validators; the first failure stops the pipeline
+#[derive(Clone)]
+pub struct ValidationPipeline {
+    validators: Vec<Arc<dyn Validator>>,
+}
+
+impl Default for ValidationPipeline {
+    /// The built-in validators that need no configuration
+    fn default() -> Self {
+        Self::from_config(&ValidationConfig::default(), &AssetRegistry::default())
+    }
+}
+
+impl ValidationPipeline {
+    /// A pipeline without any validators
+    pub fn empty() -> Self {
+        Self { validators: Vec::new() }
+    }
+
+    /// The built-in validators enabled by `config`, with minimums from `assets`
+    pub fn from_config(config: &ValidationConfig, assets: &AssetRegistry) -> Self {
+        let enabled = |name: &str| !config.disabled.iter().any(|d| d == name);
+        let mut validators: Vec<Arc<dyn Validator>> = BUILTIN_VALIDATORS
+            .iter()
+            .filter(|name| enabled(name))
+            .filter_map(|&name| match name {
+                "reference" => Some(Arc::new(ReferenceIdValidator::new(config.reference.clone())) as Arc<dyn Validator>),
+                name => builtin(name),
+            })
+            .collect();
+        if enabled("asset") && !config.supported_assets.is_empty() {
+            validators.push(Arc::new(AssetSupportValidator::new(config.supported_assets.iter().cloned())));
+        }
+        if enabled("minimum") && assets.has_limits() {
+            validators.push(Arc::new(MinimumAmountValidator::new(assets.clone())));
+        }
+        Self { validators }
+    }
+
+    /// Appends a validator that runs after the existing ones
+    pub fn with_validator(mut self, validator: Arc<dyn Validator>) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Removes the validators called `name`
+    pub fn without(mut self, name: &str) -> Self {
+        self.validators.retain(|v| v.name() != name);
+        self
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.validators.iter().map(|v| v.name()).collect()
+    }
+
+    /// Runs every validator in order
+    pub fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+        for validator in &self.validators {
+            validator
+                .validate(req)
+                .map_err(|e| format!("{} validation failed: {}", validator.name(), e))?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::{ChainId, IntentType, TransactionRequest};
+    use proptest::prelude::*;
 
     #[test]
     fn test_validate_address_valid() {
@@ -116,6 +590,45 @@ mod tests {
         assert!(validate_amount("2000000000000000").is_err()); // > 1e15 (2e15)
     }
 
+    #[test]
+    fn test_validate_amount_locale_hints() {
+        let err = |amount: &str| validate_amount(amount).unwrap_err();
+        assert_eq!(
+            err("1,000.00"),
+            "invalid amount format: 1,000.00 (remove the thousands separator ',' at position 2; did you mean 1000.00?)"
+        );
+        assert!(err("1 000,50").contains("remove the thousands separator ' ' at position 2; did you mean 1000.50?"));
+        assert!(err("1.000.000,5").contains("separator '.' at position 2; did you mean 1000000.5?"));
+        assert!(err("12,5").contains("use '.' as the decimal separator at position 3; did you mean 12.5?"));
+        assert!(err("1\u{a0}000").contains("separator '\\u{a0}' at position 2"));
+        assert!(err("1,000").contains("',' at position 2 could separate thousands or decimals"));
+        assert!(err(" 100").contains("remove the surrounding whitespace at position 1; did you mean 100?"));
+        assert!(err("-100").contains("amounts must be positive; remove the '-' at position 1"));
+        assert!(err("١٠٠").contains("use ASCII digits 0-9 instead of '١' at position 1"));
+        assert!(err("100.50.25").contains("unexpected '.' at position 4"));
+        assert!(err("10 USDC").contains("unexpected ' ' at position 3"));
+        assert!(err("5.").contains("(expected positive number)"));
+    }
+
+    #[test]
+    fn test_normalize_amount() {
+        for (localized, plain) in [
+            ("1000.50", "1000.50"),
+            ("1,000.50", "1000.50"),
+            ("1.000,50", "1000.50"),
+            ("1 234 567,5", "1234567.5"),
+            ("1'000'000", "1000000"),
+            ("1\u{202f}000,25", "1000.25"),
+            ("12,5", "12.5"),
+            (" 42 ", "42"),
+        ] {
+            assert_eq!(normalize_amount(localized).unwrap(), plain, "{}", localized);
+        }
+        for invalid in ["1,000", "10,00,000.5", "1,0000.5", "1.000.5,2", "abc", "1 000 USDC", ""] {
+            assert!(normalize_amount(invalid).is_err(), "{}", invalid);
+        }
+    }
+
     #[test]
     fn test_validate_chain() {
         assert!(validate_chain(ChainId::Ethereum).is_ok());
@@ -125,46 +638,216 @@ mod tests {
 
     #[test]
     fn test_validate_transaction_request_valid() {
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0".to_string()),
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: true,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0")
+            .with_shielded(true);
         assert!(validate_transaction_request(&req).is_ok());
     }
 
     #[test]
     fn test_validate_transaction_request_invalid_amount() {
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "".to_string(),
-            asset: "USDC".to_string(),
-            recipient: None,
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "", "USDC", ChainId::Base);
         assert!(validate_transaction_request(&req).is_err());
     }
 
     #[test]
     fn test_validate_transaction_request_invalid_recipient() {
-        let req = TransactionRequest {
-            reference_id: "ref_001".to_string(),
-            intent_type: IntentType::Transfer,
-            amount: "1000.00".to_string(),
-            asset: "USDC".to_string(),
-            recipient: Some("invalid_address".to_string()),
-            source_chain: ChainId::Base,
-            target_chain: None,
-            is_shielded: false,
-        };
+        let req = TransactionRequest::new("ref_001", IntentType::Transfer, "1000.00", "USDC", ChainId::Base)
+            .with_recipient("invalid_address");
         assert!(validate_transaction_request(&req).is_err());
     }
+
+    fn request(recipient: Option<&str>, source_chain: ChainId, target_chain: Option<ChainId>) -> TransactionRequest {
+        TransactionRequest {
+            recipient: recipient.map(str::to_string),
+            target_chain,
+            ..TransactionRequest::new("ref_001", IntentType::Transfer, "10", "USDC", source_chain)
+        }
+    }
+
+    #[test]
+    fn test_recipient_format_follows_destination_chain() {
+        let evm = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0";
+        let solana = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+        assert!(validate_transaction_request(&request(Some(evm), ChainId::Base, None)).is_ok());
+        assert!(validate_transaction_request(&request(Some(solana), ChainId::Solana, None)).is_ok());
+        assert!(validate_transaction_request(&request(Some(solana), ChainId::Base, Some(ChainId::Solana))).is_ok());
+
+        let err = validate_transaction_request(&request(Some(evm), ChainId::Base, Some(ChainId::Solana))).unwrap_err();
+        assert!(err.starts_with("recipient validation failed: invalid Solana address"), "{}", err);
+        assert!(validate_transaction_request(&request(Some(solana), ChainId::Ethereum, None)).is_err());
+        // base58 excludes 0, O, I and l
+        assert!(!is_solana_address("0WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"));
+    }
+
+    #[test]
+    fn test_shield_cannot_cross_chains() {
+        let mut req = request(None, ChainId::Base, Some(ChainId::Ethereum));
+        assert!(validate_transaction_request(&req).is_ok());
+        req.intent_type = IntentType::Shield;
+        let err = validate_transaction_request(&req).unwrap_err();
+        assert!(err.starts_with("chain validation failed: shield intents"), "{}", err);
+        req.target_chain = Some(ChainId::Base);
+        assert!(validate_transaction_request(&req).is_ok());
+    }
+
+    #[test]
+    fn test_pipeline_configuration() {
+        struct Allowlist;
+        impl Validator for Allowlist {
+            fn name(&self) -> &str {
+                "allowlist"
+            }
+            fn validate(&self, req: &TransactionRequest) -> Result<(), String> {
+                match req.recipient.as_deref() {
+                    Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0") | None => Ok(()),
+                    Some(other) => Err(format!("{} is not on the allowlist", other)),
+                }
+            }
+        }
+
+        let config: ValidationConfig =
+            serde_json::from_str(r#"{"disabled": ["recipient"], "supported_assets": ["usdc", "USDT"]}"#).unwrap();
+        assert!(config.validate().is_ok());
+        let pipeline = ValidationPipeline::from_config(&config, &AssetRegistry::default()).with_validator(Arc::new(Allowlist));
+        assert_eq!(pipeline.names(), ["amount", "chain", "reference", "asset", "allowlist"]);
+
+        let mut req = request(Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb0"), ChainId::Base, None);
+        assert!(pipeline.validate(&req).is_ok());
+        req.asset = "DAI".to_string();
+        assert_eq!(pipeline.validate(&req).unwrap_err(), "asset validation failed: unsupported asset: DAI");
+        req.asset = "usdt".to_string();
+        req.recipient = Some("not-an-address".to_string());
+        assert_eq!(
+            pipeline.validate(&req).unwrap_err(),
+            "allowlist validation failed: not-an-address is not on the allowlist"
+        );
+        assert!(pipeline.clone().without("allowlist").validate(&req).is_ok());
+        assert!(ValidationPipeline::empty().validate(&request(None, ChainId::Base, None)).is_ok());
+
+        let unknown = ValidationConfig {
+            disabled: vec!["amounts".to_string()],
+            ..Default::default()
+        };
+        assert!(unknown.validate().unwrap_err().contains("unknown validator amounts"));
+    }
+
+    #[test]
+    fn test_reference_id_policy() {
+        let config: ValidationConfig = serde_json::from_str(
+            r#"{"reference": {"max_length": 40, "extra_chars": "_", "prefixes": ["pay_", "refund_"]}}"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        let pipeline = ValidationPipeline::from_config(&config, &AssetRegistry::default());
+        let mut req = request(None, ChainId::Base, None);
+
+        req.reference_id = TransactionRequest::generate_reference("pay_");
+        assert!(pipeline.validate(&req).is_ok());
+        req.reference_id = "ref_001".to_string();
+        assert_eq!(
+            pipeline.validate(&req).unwrap_err(),
+            "reference validation failed: reference ID ref_001 must start with one of: pay_, refund_"
+        );
+        req.reference_id = "pay-001".to_string();
+        assert!(pipeline.validate(&req).unwrap_err().contains("'-' at position 4"));
+        req.reference_id = format!("pay_{}", "0".repeat(40));
+        assert!(pipeline.validate(&req).unwrap_err().contains("40 characters long (got 44)"));
+        req.reference_id = String::new();
+        assert!(validate_transaction_request(&req).unwrap_err().contains("1 to 128 characters"));
+
+        let invalid: ValidationConfig = serde_json::from_str(r#"{"reference": {"prefixes": ["pay "]}}"#).unwrap();
+        assert!(invalid.validate().unwrap_err().contains("prefix \"pay \""));
+    }
+
+    #[test]
+    fn test_minimum_and_dust() {
+        let assets: AssetRegistry = serde_json::from_str(
+            r#"{"USDC": {"min_amount": 1.0, "dust_threshold": 5.0, "chains": {"ethereum": {"min_amount": 25.0}}}}"#,
+        )
+        .unwrap();
+        let pipeline = ValidationPipeline::from_config(&ValidationConfig::default(), &assets);
+        assert_eq!(pipeline.names(), ["amount", "chain", "recipient", "reference", "minimum"]);
+
+        let mut req = request(None, ChainId::Base, None);
+        req.amount = "0.10".to_string();
+        assert_eq!(
+            pipeline.validate(&req).unwrap_err(),
+            "minimum validation failed: 0.10 USDC is below the minimum of 1 on base"
+        );
+        req.amount = "2".to_string();
+        assert!(pipeline.validate(&req).is_ok());
+        req.is_shielded = true;
+        assert!(pipeline.validate(&req).unwrap_err().contains("dust threshold of 5"));
+        req.source_chain = ChainId::Ethereum;
+        req.amount = "10".to_string();
+        assert!(pipeline.validate(&req).unwrap_err().contains("minimum of 25 on ethereum"));
+
+        // Assets without limits and disabled validators pass
+        req.asset = "DAI".to_string();
+        assert!(pipeline.validate(&req).is_ok());
+        let disabled = ValidationConfig {
+            disabled: vec!["minimum".to_string()],
+            ..Default::default()
+        };
+        assert!(!ValidationPipeline::from_config(&disabled, &assets).names().contains(&"minimum"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_format_checks_match_patterns(candidate in "[09aFgx. \\x{663}-]{0,8}", body in "[09aFg]{0,41}") {
+            let address = regex::Regex::new(r"^0x[a-fA-F0-9]{40}$").unwrap();
+            let amount = regex::Regex::new(r"^[0-9]+(\.[0-9]+)?$").unwrap();
+            prop_assert_eq!(is_decimal(&candidate), amount.is_match(&candidate), "{:?}", candidate);
+            let candidate = format!("0x{}", body);
+            prop_assert_eq!(is_address(&candidate), address.is_match(&candidate), "{:?}", candidate);
+        }
+
+        #[test]
+        fn test_validate_amount_properties(amount in "[019.+e _\\x{663}\\x{ff11}\\x00-]{0,24}") {
+            if validate_amount(&amount).is_ok() {
+                prop_assert!(amount.bytes().all(|b| b.is_ascii_digit() || b == b'.'), "accepted {:?}", amount);
+                let value: f64 = amount.parse().unwrap();
+                prop_assert!(value > 0.0 && value <= 1e15, "accepted {:?}", amount);
+            }
+        }
+
+        #[test]
+        fn test_validate_amount_accepts_decimals(units in 1..1_000_000_000u64, cents in 0..100u32) {
+            let amount = format!("{}.{:02}", units, cents);
+            prop_assert!(validate_amount(&amount).is_ok(), "rejected {:?}", amount);
+        }
+    }
+
+    #[test]
+    fn test_validate_amount_rejects_non_ascii_digits() {
+        assert!(validate_amount("\u{661}\u{660}\u{660}").is_err());
+        assert!(validate_amount("\u{ff11}\u{ff10}").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_validate_address_properties(
+            body in "[0-9a-fA-F]{40}",
+            index in 2..42usize,
+            bad in prop::sample::select(vec!['g', 'x', ' ', '\u{e9}']),
+            cut in 0..42usize,
+        ) {
+            let address = format!("0x{}", body);
+            prop_assert!(validate_address(&address).is_ok());
+
+            // Any single non-hex character, a truncation or an extension is rejected
+            let mut chars: Vec<char> = address.chars().collect();
+            chars[index] = bad;
+            prop_assert!(validate_address(&chars.iter().collect::<String>()).is_err());
+            prop_assert!(validate_address(&address[..cut]).is_err());
+            let extended = format!("{}0", address);
+            prop_assert!(validate_address(&extended).is_err());
+        }
+
+        #[test]
+        fn test_validate_address_never_panics(input in "[0xaFg \\x{e9}]{0,48}") {
+            let _ = validate_address(&input);
+        }
+    }
 }
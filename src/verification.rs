@@ -0,0 +1,643 @@
+//! Independent verification of on-chain settlement.
+//!
+//! Rather than trusting the `TransactionResponse` returned by an agent,
+//! integrators can check an inclusion proof against a block header fetched
+//! from a source they trust (their own node, a light client, ...).
+//!
+//! * **EVM**: Merkle-Patricia proofs against the block's transactions and
+//!   receipts tries; the transaction must hash to the claimed `tx_hash` and its
+//!   receipt must report success.
+//! * **Solana**: there is no receipts trie, so the signature must appear in
+//!   the block reported by the header source and the slot must be finalized.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::crypto::keccak256;
+use crate::types::{ChainId, TransactionResponse};
+
+/// Block header fields needed for inclusion verification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeader {
+    #[serde(rename = "block_height")]
+    pub block_height: u64,
+    #[serde(rename = "block_hash")]
+    pub block_hash: String,
+    /// Hex-encoded root of the transactions trie (EVM only)
+    #[serde(rename = "transactions_root", skip_serializing_if = "Option::is_none")]
+    pub transactions_root: Option<String>,
+    /// Hex-encoded root of the receipts trie (EVM only)
+    #[serde(rename = "receipts_root", skip_serializing_if = "Option::is_none")]
+    pub receipts_root: Option<String>,
+    /// Whether the block can no longer be reorganized out of the chain
+    pub finalized: bool,
+}
+
+/// Trusted source of block headers (e.g. the integrator's own node)
+#[async_trait::async_trait]
+pub trait BlockHeaderSource: Send + Sync {
+    /// Returns the header of the block at `block_height`
+    async fn block_header(&self, chain: ChainId, block_height: u64) -> Result<BlockHeader, String>;
+
+    /// Returns the transaction signatures included in a block (Solana)
+    async fn block_signatures(&self, chain: ChainId, block_height: u64) -> Result<Vec<String>, String> {
+        let _ = block_height;
+        Err(format!("block signatures are not available for {}", chain))
+    }
+}
+
+/// In-memory header source, useful for tests and pinned checkpoints
+#[derive(Default)]
+pub struct StaticHeaderSource {
+    headers: RwLock<HashMap<(ChainId, u64), BlockHeader>>,
+    signatures: RwLock<HashMap<(ChainId, u64), Vec<String>>>,
+}
+
+impl StaticHeaderSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) a block header
+    pub fn insert_header(&self, chain: ChainId, header: BlockHeader) {
+        if let Ok(mut headers) = self.headers.write() {
+            headers.insert((chain, header.block_height), header);
+        }
+    }
+
+    /// Sets the transaction signatures included in a block
+    pub fn insert_signatures(&self, chain: ChainId, block_height: u64, signatures: Vec<String>) {
+        if let Ok(mut map) = self.signatures.write() {
+            map.insert((chain, block_height), signatures);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockHeaderSource for StaticHeaderSource {
+    async fn block_header(&self, chain: ChainId, block_height: u64) -> Result<BlockHeader, String> {
+        self.headers
+            .read()
+            .map_err(|_| "header source poisoned".to_string())?
+            .get(&(chain, block_height))
+            .cloned()
+            .ok_or_else(|| format!("no header for {} block {}", chain, block_height))
+    }
+
+    async fn block_signatures(&self, chain: ChainId, block_height: u64) -> Result<Vec<String>, String> {
+        self.signatures
+            .read()
+            .map_err(|_| "header source poisoned".to_string())?
+            .get(&(chain, block_height))
+            .cloned()
+            .ok_or_else(|| format!("no signatures for {} block {}", chain, block_height))
+    }
+}
+
+/// Proof that a transaction was included in a block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InclusionProof {
+    /// Merkle-Patricia proofs (hex-encoded RLP nodes, root first) for the
+    /// transaction and its receipt at `tx_index`
+    EvmReceipt {
+        #[serde(rename = "tx_index")]
+        tx_index: u64,
+        #[serde(rename = "transaction_proof")]
+        transaction_proof: Vec<String>,
+        #[serde(rename = "receipt_proof")]
+        receipt_proof: Vec<String>,
+    },
+    /// Confirmation that the signature is part of a finalized Solana block
+    SolanaConfirmation,
+}
+
+/// Verifies transaction inclusion against a trusted header source.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+/// use ecash_sdk_core::verification::{InclusionVerifier, StaticHeaderSource};
+///
+/// let verifier = InclusionVerifier::new(Arc::new(StaticHeaderSource::new()));
+/// ```
+pub struct InclusionVerifier {
+    source: Arc<dyn BlockHeaderSource>,
+    require_finalized: bool,
+}
+
+impl InclusionVerifier {
+    /// Creates a verifier that only accepts finalized blocks
+    pub fn new(source: Arc<dyn BlockHeaderSource>) -> Self {
+        Self {
+            source,
+            require_finalized: true,
+        }
+    }
+
+    /// Controls whether blocks must be finalized to be accepted
+    pub fn with_require_finalized(mut self, require_finalized: bool) -> Self {
+        self.require_finalized = require_finalized;
+        self
+    }
+
+    /// Verifies that `tx_hash` was included (and succeeded) in block `block_height`
+    pub async fn verify(
+        &self,
+        chain: ChainId,
+        tx_hash: &str,
+        block_height: u64,
+        proof: &InclusionProof,
+    ) -> Result<(), String> {
+        let header = self.source.block_header(chain, block_height).await?;
+        if header.block_height != block_height {
+            return Err(format!(
+                "header source returned block {} instead of {}",
+                header.block_height, block_height
+            ));
+        }
+        if self.require_finalized && !header.finalized {
+            return Err(format!("block {} is not finalized", block_height));
+        }
+
+        match (chain, proof) {
+            (ChainId::Solana, InclusionProof::SolanaConfirmation) => {
+                let signatures = self.source.block_signatures(chain, block_height).await?;
+                if signatures.iter().any(|s| s == tx_hash) {
+                    Ok(())
+                } else {
+                    Err(format!("signature {} not found in slot {}", tx_hash, block_height))
+                }
+            }
+            (ChainId::Ethereum | ChainId::Base, InclusionProof::EvmReceipt {
+                tx_index,
+                transaction_proof,
+                receipt_proof,
+            }) => verify_evm_inclusion(&header, tx_hash, *tx_index, transaction_proof, receipt_proof),
+            (chain, _) => Err(format!("proof type does not match chain {}", chain)),
+        }
+    }
+
+    /// Verifies the transaction hash and block height reported in a response
+    pub async fn verify_response(
+        &self,
+        chain: ChainId,
+        resp: &TransactionResponse,
+        proof: &InclusionProof,
+    ) -> Result<(), String> {
+        self.verify(chain, &resp.tx_hash, resp.block_height, proof).await
+    }
+}
+
+/// Verifies EVM transaction and receipt proofs against a block header
+pub fn verify_evm_inclusion(
+    header: &BlockHeader,
+    tx_hash: &str,
+    tx_index: u64,
+    transaction_proof: &[String],
+    receipt_proof: &[String],
+) -> Result<(), String> {
+    let key = rlp_encode_uint(tx_index);
+
+    let tx_root = parse_hash(header.transactions_root.as_deref().ok_or("header has no transactions root")?)?;
+    let tx_nodes = decode_proof(transaction_proof)?;
+    let raw_tx = verify_proof(&tx_root, &key, &tx_nodes)?
+        .ok_or_else(|| format!("no transaction at index {}", tx_index))?;
+    let expected_hash = parse_hash(tx_hash)?;
+    if keccak256(&raw_tx) != expected_hash {
+        return Err(format!("transaction at index {} does not hash to {}", tx_index, tx_hash));
+    }
+
+    let receipts_root = parse_hash(header.receipts_root.as_deref().ok_or("header has no receipts root")?)?;
+    let receipt_nodes = decode_proof(receipt_proof)?;
+    let receipt = verify_proof(&receipts_root, &key, &receipt_nodes)?
+        .ok_or_else(|| format!("no receipt at index {}", tx_index))?;
+    if !receipt_succeeded(&receipt)? {
+        return Err(format!("transaction {} reverted", tx_hash));
+    }
+    Ok(())
+}
+
+/// Verifies a Merkle-Patricia trie proof and returns the value stored at `key`.
+///
+/// `proof` holds the RLP-encoded nodes on the path from the root. Returns
+/// `Ok(None)` for a valid proof of absence.
+pub fn verify_proof(root: &[u8; 32], key: &[u8], proof: &[Vec<u8>]) -> Result<Option<Vec<u8>>, String> {
+    let nibbles: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+    let mut position = 0;
+    let mut expected = NodeRef::Hash(*root);
+
+    for (depth, node) in proof.iter().enumerate() {
+        let matches = match &expected {
+            NodeRef::Hash(hash) => keccak256(node) == *hash,
+            NodeRef::Inline(raw) => raw == node,
+        };
+        if !matches {
+            return Err(format!("proof node {} does not match its parent reference", depth));
+        }
+
+        let items = rlp_decode_list(node)?;
+        match items.len() {
+            17 => {
+                if position == nibbles.len() {
+                    let value = items[16].bytes()?;
+                    return Ok((!value.is_empty()).then(|| value.to_vec()));
+                }
+                match NodeRef::from_item(&items[nibbles[position] as usize])? {
+                    Some(child) => expected = child,
+                    None => return Ok(None),
+                }
+                position += 1;
+            }
+            2 => {
+                let (path, is_leaf) = decode_hex_prefix(items[0].bytes()?)?;
+                let remaining = &nibbles[position..];
+                if is_leaf {
+                    if remaining != path.as_slice() {
+                        return Ok(None);
+                    }
+                    return Ok(Some(items[1].bytes()?.to_vec()));
+                }
+                if !remaining.starts_with(&path) {
+                    return Ok(None);
+                }
+                position += path.len();
+                expected = NodeRef::from_item(&items[1])?.ok_or("extension node has an empty child")?;
+            }
+            n => return Err(format!("invalid trie node with {} items", n)),
+        }
+    }
+    Err("proof ended before reaching a leaf".to_string())
+}
+
+/// Returns true if an (optionally typed) EVM receipt reports success
+fn receipt_succeeded(receipt: &[u8]) -> Result<bool, String> {
+    // EIP-2718 typed receipts are prefixed with the transaction type
+    let body = match receipt.first() {
+        Some(&ty) if ty < 0x80 => &receipt[1..],
+        _ => receipt,
+    };
+    let fields = rlp_decode_list(body)?;
+    let status = fields.first().ok_or("empty receipt")?.bytes()?;
+    match status {
+        [] => Ok(false),
+        [1] => Ok(true),
+        s if s.len() == 32 => Err("pre-Byzantium receipt has no status field".to_string()),
+        _ => Err("invalid receipt status".to_string()),
+    }
+}
+
+enum NodeRef {
+    Hash([u8; 32]),
+    Inline(Vec<u8>),
+}
+
+impl NodeRef {
+    fn from_item(item: &RlpItem) -> Result<Option<Self>, String> {
+        match item {
+            RlpItem::Bytes([]) => Ok(None),
+            RlpItem::Bytes(b) if b.len() == 32 => {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(b);
+                Ok(Some(NodeRef::Hash(hash)))
+            }
+            RlpItem::Bytes(_) => Err("invalid child reference length".to_string()),
+            // Nodes shorter than 32 bytes are embedded in their parent
+            RlpItem::List(raw) => Ok(Some(NodeRef::Inline(raw.to_vec()))),
+        }
+    }
+}
+
+fn decode_hex_prefix(encoded: &[u8]) -> Result<(Vec<u8>, bool), String> {
+    let first = *encoded.first().ok_or("empty trie path")?;
+    let flag = first >> 4;
+    let is_leaf = flag >= 2;
+    let mut nibbles = Vec::with_capacity(encoded.len() * 2);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for b in &encoded[1..] {
+        nibbles.push(b >> 4);
+        nibbles.push(b & 0x0f);
+    }
+    if flag > 3 {
+        return Err(format!("invalid hex-prefix flag {}", flag));
+    }
+    Ok((nibbles, is_leaf))
+}
+
+fn decode_proof(nodes: &[String]) -> Result<Vec<Vec<u8>>, String> {
+    nodes
+        .iter()
+        .map(|n| hex::decode(n.strip_prefix("0x").unwrap_or(n)).map_err(|e| format!("invalid proof node hex: {}", e)))
+        .collect()
+}
+
+fn parse_hash(value: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value)).map_err(|e| format!("invalid hash hex: {}", e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("hash must be 32 bytes: {}", value))
+}
+
+/// Decoded RLP item; lists keep their full encoding so they can be compared
+/// against embedded trie nodes
+enum RlpItem<'a> {
+    Bytes(&'a [u8]),
+    List(&'a [u8]),
+}
+
+impl<'a> RlpItem<'a> {
+    fn bytes(&self) -> Result<&'a [u8], String> {
+        match self {
+            RlpItem::Bytes(b) => Ok(b),
+            RlpItem::List(_) => Err("expected RLP string, found list".to_string()),
+        }
+    }
+}
+
+/// Decodes one item from the front of `data`, returning it and its encoded length
+fn rlp_decode_item(data: &[u8]) -> Result<(RlpItem<'_>, usize), String> {
+    let prefix = *data.first().ok_or("unexpected end of RLP data")?;
+    let read_len = |len_of_len: usize| -> Result<usize, String> {
+        let bytes = data.get(1..1 + len_of_len).ok_or("truncated RLP length")?;
+        Ok(bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize))
+    };
+    let (header, len, is_list) = match prefix {
+        0x00..=0x7f => return Ok((RlpItem::Bytes(&data[..1]), 1)),
+        0x80..=0xb7 => (1, (prefix - 0x80) as usize, false),
+        0xb8..=0xbf => {
+            let len_of_len = (prefix - 0xb7) as usize;
+            (1 + len_of_len, read_len(len_of_len)?, false)
+        }
+        0xc0..=0xf7 => (1, (prefix - 0xc0) as usize, true),
+        0xf8..=0xff => {
+            let len_of_len = (prefix - 0xf7) as usize;
+            (1 + len_of_len, read_len(len_of_len)?, true)
+        }
+    };
+    let end = header + len;
+    if data.len() < end {
+        return Err("truncated RLP item".to_string());
+    }
+    let item = if is_list {
+        RlpItem::List(&data[..end])
+    } else {
+        RlpItem::Bytes(&data[header..end])
+    };
+    Ok((item, end))
+}
+
+/// Decodes an RLP list that spans all of `data` into its items
+fn rlp_decode_list(data: &[u8]) -> Result<Vec<RlpItem<'_>>, String> {
+    let (item, consumed) = rlp_decode_item(data)?;
+    let raw = match item {
+        RlpItem::List(raw) if consumed == data.len() => raw,
+        RlpItem::List(_) => return Err("trailing bytes after RLP list".to_string()),
+        RlpItem::Bytes(_) => return Err("expected RLP list".to_string()),
+    };
+    let (_, mut payload) = rlp_split_list_header(raw)?;
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let (item, used) = rlp_decode_item(payload)?;
+        items.push(item);
+        payload = &payload[used..];
+    }
+    Ok(items)
+}
+
+fn rlp_split_list_header(raw: &[u8]) -> Result<(usize, &[u8]), String> {
+    let header = match raw[0] {
+        0xc0..=0xf7 => 1,
+        p => 1 + (p - 0xf7) as usize,
+    };
+    Ok((header, raw.get(header..).ok_or("truncated RLP list")?))
+}
+
+fn rlp_encode_length(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let bytes: Vec<u8> = len.to_be_bytes().iter().copied().skip_while(|b| *b == 0).collect();
+    let mut out = vec![offset + 55 + bytes.len() as u8];
+    out.extend(bytes);
+    out
+}
+
+fn rlp_encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_encode_length(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn rlp_encode_uint(value: u64) -> Vec<u8> {
+    let bytes: Vec<u8> = value.to_be_bytes().iter().copied().skip_while(|b| *b == 0).collect();
+    rlp_encode_bytes(&bytes)
+}
+
+#[cfg(test)]
+fn rlp_encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload: Vec<u8> = items.concat();
+    let mut out = rlp_encode_length(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(path: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut encoded_path = if path.len() % 2 == 1 {
+            vec![0x30 | path[0]]
+        } else {
+            vec![0x20]
+        };
+        let rest = if path.len() % 2 == 1 { &path[1..] } else { path };
+        encoded_path.extend(rest.chunks(2).map(|c| (c[0] << 4) | c[1]));
+        rlp_encode_list(&[rlp_encode_bytes(&encoded_path), rlp_encode_bytes(value)])
+    }
+
+    fn child_ref(node: &[u8]) -> Vec<u8> {
+        if node.len() < 32 {
+            node.to_vec()
+        } else {
+            rlp_encode_bytes(&keccak256(node))
+        }
+    }
+
+    fn branch(children: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut items = vec![rlp_encode_bytes(&[]); 17];
+        for (nibble, node) in children {
+            items[*nibble as usize] = child_ref(node);
+        }
+        rlp_encode_list(&items)
+    }
+
+    fn receipt(success: bool) -> Vec<u8> {
+        let status = if success { vec![1u8] } else { vec![] };
+        let mut out = vec![0x02];
+        out.extend(rlp_encode_list(&[
+            rlp_encode_bytes(&status),
+            rlp_encode_uint(21_000),
+            rlp_encode_bytes(&[0u8; 256]),
+            rlp_encode_list(&[]),
+        ]));
+        out
+    }
+
+    fn hex_nodes(nodes: &[&Vec<u8>]) -> Vec<String> {
+        nodes.iter().map(|n| format!("0x{}", hex::encode(n))).collect()
+    }
+
+    /// Two-transaction block: keys rlp(0) = 0x80 and rlp(1) = 0x01
+    struct Block {
+        header: BlockHeader,
+        tx1: Vec<u8>,
+        tx_root_node: Vec<u8>,
+        tx1_leaf: Vec<u8>,
+        receipt_root_node: Vec<u8>,
+        receipt1_leaf: Vec<u8>,
+    }
+
+    fn block(tx1_success: bool) -> Block {
+        let tx0 = vec![0xaa; 40];
+        let tx1 = vec![0xbb; 40];
+        let tx0_leaf = leaf(&[0], &tx0);
+        let tx1_leaf = leaf(&[1], &tx1);
+        let tx_root_node = branch(&[(8, &tx0_leaf), (0, &tx1_leaf)]);
+
+        let receipt0_leaf = leaf(&[0], &receipt(true));
+        let receipt1_leaf = leaf(&[1], &receipt(tx1_success));
+        let receipt_root_node = branch(&[(8, &receipt0_leaf), (0, &receipt1_leaf)]);
+
+        Block {
+            header: BlockHeader {
+                block_height: 100,
+                block_hash: "0x01".to_string(),
+                transactions_root: Some(format!("0x{}", hex::encode(keccak256(&tx_root_node)))),
+                receipts_root: Some(format!("0x{}", hex::encode(keccak256(&receipt_root_node)))),
+                finalized: true,
+            },
+            tx1,
+            tx_root_node,
+            tx1_leaf,
+            receipt_root_node,
+            receipt1_leaf,
+        }
+    }
+
+    impl Block {
+        fn proof(&self) -> InclusionProof {
+            InclusionProof::EvmReceipt {
+                tx_index: 1,
+                transaction_proof: hex_nodes(&[&self.tx_root_node, &self.tx1_leaf]),
+                receipt_proof: hex_nodes(&[&self.receipt_root_node, &self.receipt1_leaf]),
+            }
+        }
+
+        fn tx_hash(&self) -> String {
+            format!("0x{}", hex::encode(keccak256(&self.tx1)))
+        }
+    }
+
+    #[test]
+    fn test_rlp_round_trip() {
+        let encoded = rlp_encode_list(&[rlp_encode_bytes(b"dog"), rlp_encode_bytes(&[0x7f]), rlp_encode_bytes(&[0u8; 60])]);
+        let items = rlp_decode_list(&encoded).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].bytes().unwrap(), b"dog");
+        assert_eq!(items[1].bytes().unwrap(), &[0x7f]);
+        assert_eq!(items[2].bytes().unwrap().len(), 60);
+        assert_eq!(rlp_encode_uint(0), vec![0x80]);
+        assert_eq!(rlp_encode_uint(1024), vec![0x82, 0x04, 0x00]);
+    }
+
+    #[test]
+    fn test_verify_proof_with_inline_node() {
+        // Short leaves (< 32 bytes) are embedded directly in the branch
+        let leaf_a = leaf(&[5], b"a");
+        let leaf_b = leaf(&[6], b"b");
+        assert!(leaf_a.len() < 32);
+        let root_node = branch(&[(1, &leaf_a), (2, &leaf_b)]);
+        let root = keccak256(&root_node);
+
+        let value = verify_proof(&root, &[0x15], &[root_node.clone(), leaf_a]).unwrap();
+        assert_eq!(value, Some(b"a".to_vec()));
+        assert_eq!(verify_proof(&root, &[0x35], std::slice::from_ref(&root_node)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_verify_proof_rejects_wrong_root() {
+        let b = block(true);
+        let nodes = vec![b.tx_root_node.clone(), b.tx1_leaf.clone()];
+        assert!(verify_proof(&[0u8; 32], &rlp_encode_uint(1), &nodes).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_evm_inclusion() {
+        let b = block(true);
+        let source = Arc::new(StaticHeaderSource::new());
+        source.insert_header(ChainId::Ethereum, b.header.clone());
+        let verifier = InclusionVerifier::new(source);
+
+        verifier.verify(ChainId::Ethereum, &b.tx_hash(), 100, &b.proof()).await.unwrap();
+
+        let wrong_hash = format!("0x{}", hex::encode([0x11u8; 32]));
+        let err = verifier.verify(ChainId::Ethereum, &wrong_hash, 100, &b.proof()).await.unwrap_err();
+        assert!(err.contains("does not hash to"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_evm_reverted_receipt() {
+        let b = block(false);
+        let source = Arc::new(StaticHeaderSource::new());
+        source.insert_header(ChainId::Base, b.header.clone());
+        let verifier = InclusionVerifier::new(source);
+
+        let err = verifier.verify(ChainId::Base, &b.tx_hash(), 100, &b.proof()).await.unwrap_err();
+        assert!(err.contains("reverted"));
+    }
+
+    #[tokio::test]
+    async fn test_requires_finalized_block() {
+        let mut b = block(true);
+        b.header.finalized = false;
+        let source = Arc::new(StaticHeaderSource::new());
+        source.insert_header(ChainId::Ethereum, b.header.clone());
+
+        let verifier = InclusionVerifier::new(source.clone());
+        assert!(verifier.verify(ChainId::Ethereum, &b.tx_hash(), 100, &b.proof()).await.is_err());
+
+        let verifier = InclusionVerifier::new(source).with_require_finalized(false);
+        assert!(verifier.verify(ChainId::Ethereum, &b.tx_hash(), 100, &b.proof()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verify_solana_confirmation() {
+        let source = Arc::new(StaticHeaderSource::new());
+        source.insert_header(
+            ChainId::Solana,
+            BlockHeader {
+                block_height: 250_000_000,
+                block_hash: "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d".to_string(),
+                transactions_root: None,
+                receipts_root: None,
+                finalized: true,
+            },
+        );
+        source.insert_signatures(ChainId::Solana, 250_000_000, vec!["sig_a".to_string()]);
+        let verifier = InclusionVerifier::new(source);
+
+        let proof = InclusionProof::SolanaConfirmation;
+        assert!(verifier.verify(ChainId::Solana, "sig_a", 250_000_000, &proof).await.is_ok());
+        assert!(verifier.verify(ChainId::Solana, "sig_b", 250_000_000, &proof).await.is_err());
+
+        let b = block(true);
+        assert!(verifier.verify(ChainId::Solana, "sig_a", 250_000_000, &b.proof()).await.is_err());
+    }
+}
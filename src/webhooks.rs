@@ -0,0 +1,374 @@
+//! Verification of webhook callbacks pushed by EasyCash servers.
+//!
+//! Every delivery carries three headers:
+//!
+//! * `x-ecash-timestamp`: Unix time (seconds) the delivery was signed
+//! * `x-ecash-signature`: `v2=<hex HMAC-SHA256 of "{timestamp}.{body}">`, with
+//!   the webhook domain tag prepended (see `crypto::SigningDomain`); during
+//!   secret rotation several comma-separated signatures may be present.
+//!   Untagged `v1=` signatures from older servers are still accepted.
+//! * `x-ecash-delivery-id`: unique ID of the delivery. It is not signed, so replay
+//!   protection keys on the signed timestamp and body instead
+//!
+//! Verify the raw body before parsing it; re-serialized JSON will not match.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::crypto::SigningDomain;
+
+pub const TIMESTAMP_HEADER: &str = "x-ecash-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-ecash-signature";
+pub const DELIVERY_ID_HEADER: &str = "x-ecash-delivery-id";
+
+/// Maximum age (and clock skew) of a delivery accepted by default
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+const SIGNATURE_VERSION: &str = "v2";
+/// Signatures without the domain tag
+const LEGACY_SIGNATURE_VERSION: &str = "v1";
+
+/// Why a delivery was rejected
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WebhookError {
+    #[error("missing header {0}")]
+    MissingHeader(&'static str),
+    #[error("invalid timestamp header")]
+    InvalidTimestamp,
+    #[error("timestamp outside tolerance ({age_secs}s old)")]
+    Expired { age_secs: i64 },
+    #[error("signature mismatch")]
+    InvalidSignature,
+    #[error("delivery {0} was already processed")]
+    Replayed(String),
+    #[error("replay store unavailable: {0}")]
+    ReplayStore(String),
+    #[error("invalid payload: {0}")]
+    InvalidPayload(String),
+}
+
+/// Records deliveries that have been accepted, by a hash of their signed content
+pub trait ReplayStore: Send + Sync {
+    /// Remembers `key` for `ttl`; returns false if it was already recorded
+    fn insert(&self, key: &str, ttl: Duration) -> Result<bool, String>;
+}
+
+/// In-memory replay store (per process)
+#[derive(Default)]
+pub struct InMemoryReplayStore {
+    seen: Mutex<HashMap<String, Instant>>,
+}
+
+impl InMemoryReplayStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ReplayStore for InMemoryReplayStore {
+    fn insert(&self, key: &str, ttl: Duration) -> Result<bool, String> {
+        let mut seen = self.seen.lock().map_err(|_| "replay store poisoned".to_string())?;
+        let now = Instant::now();
+        seen.retain(|_, expires| *expires > now);
+        if seen.contains_key(key) {
+            return Ok(false);
+        }
+        seen.insert(key.to_string(), now + ttl);
+        Ok(true)
+    }
+}
+
+/// Computes the `x-ecash-signature` header value for a body
+pub fn sign(body: &[u8], secret: &[u8], timestamp: i64) -> String {
+    let mac = mac(secret, SIGNATURE_VERSION, timestamp, body);
+    format!("{}={}", SIGNATURE_VERSION, hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verifies a delivery's signature and timestamp with the default tolerance.
+///
+/// Header names are matched case-insensitively. Use `WebhookVerifier` for
+/// replay protection and secret rotation.
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::webhooks::{self, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+///
+/// let body = br#"{"id":"evt_1"}"#;
+/// let now = 1_700_000_000;
+/// let headers = [
+///     (TIMESTAMP_HEADER.to_string(), now.to_string()),
+///     (SIGNATURE_HEADER.to_string(), webhooks::sign(body, b"whsec", now)),
+/// ];
+/// assert!(webhooks::verify_signature_at(headers.clone(), body, b"whsec", now).is_ok());
+/// assert!(webhooks::verify_signature_at(headers, body, b"other", now).is_err());
+/// ```
+pub fn verify_signature<K, V>(
+    headers: impl IntoIterator<Item = (K, V)>,
+    body: &[u8],
+    secret: &[u8],
+) -> Result<(), WebhookError>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    verify_signature_at(headers, body, secret, now_secs())
+}
+
+/// `verify_signature` against a given current time (Unix seconds)
+pub fn verify_signature_at<K, V>(
+    headers: impl IntoIterator<Item = (K, V)>,
+    body: &[u8],
+    secret: &[u8],
+    now: i64,
+) -> Result<(), WebhookError>
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    WebhookVerifier::new(secret).verify_at(headers, body, now).map(|_| ())
+}
+
+/// Webhook verifier with secret rotation and replay protection
+pub struct WebhookVerifier {
+    secrets: Vec<Vec<u8>>,
+    tolerance: Duration,
+    replay: Option<Arc<dyn ReplayStore>>,
+}
+
+impl WebhookVerifier {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secrets: vec![secret.as_ref().to_vec()],
+            tolerance: DEFAULT_TOLERANCE,
+            replay: None,
+        }
+    }
+
+    /// Also accepts signatures made with `secret` (e.g. during rotation)
+    pub fn with_secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.secrets.push(secret.as_ref().to_vec());
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Rejects deliveries whose signed timestamp and body were already accepted
+    /// within the tolerance window
+    pub fn with_replay_store(mut self, store: Arc<dyn ReplayStore>) -> Self {
+        self.replay = Some(store);
+        self
+    }
+
+    /// Verifies a delivery and returns its delivery ID (the signature when the
+    /// delivery-ID header is absent)
+    pub fn verify<K, V>(&self, headers: impl IntoIterator<Item = (K, V)>, body: &[u8]) -> Result<String, WebhookError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.verify_at(headers, body, now_secs())
+    }
+
+    /// `verify` against a given current time (Unix seconds)
+    pub fn verify_at<K, V>(
+        &self,
+        headers: impl IntoIterator<Item = (K, V)>,
+        body: &[u8],
+        now: i64,
+    ) -> Result<String, WebhookError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let headers: HashMap<String, String> = headers
+            .into_iter()
+            .map(|(k, v)| (k.as_ref().to_ascii_lowercase(), v.as_ref().trim().to_string()))
+            .collect();
+        let header = |name: &'static str| headers.get(name).ok_or(WebhookError::MissingHeader(name));
+
+        let timestamp: i64 = header(TIMESTAMP_HEADER)?.parse().map_err(|_| WebhookError::InvalidTimestamp)?;
+        let age_secs = now - timestamp;
+        if age_secs.unsigned_abs() > self.tolerance.as_secs() {
+            return Err(WebhookError::Expired { age_secs });
+        }
+
+        let signatures = header(SIGNATURE_HEADER)?;
+        let valid = signatures
+            .split(',')
+            .filter_map(|s| s.trim().split_once('='))
+            .filter(|(version, _)| [SIGNATURE_VERSION, LEGACY_SIGNATURE_VERSION].contains(version))
+            .filter_map(|(version, s)| Some((version, hex::decode(s).ok()?)))
+            .any(|(version, sig)| {
+                self.secrets
+                    .iter()
+                    .any(|secret| mac(secret, version, timestamp, body).verify_slice(&sig).is_ok())
+            });
+        if !valid {
+            return Err(WebhookError::InvalidSignature);
+        }
+
+        let delivery_id = headers.get(DELIVERY_ID_HEADER).unwrap_or(signatures).clone();
+        if let Some(ref replay) = self.replay {
+            // Keyed on what the MAC covers; the delivery-ID header can be rewritten freely
+            let key = hex::encode(Sha256::new().chain_update(format!("{}.", timestamp)).chain_update(body).finalize());
+            // A delivery is acceptable for up to `tolerance` on either side of its timestamp
+            if !replay.insert(&key, self.tolerance * 2).map_err(WebhookError::ReplayStore)? {
+                return Err(WebhookError::Replayed(delivery_id));
+            }
+        }
+        Ok(delivery_id)
+    }
+
+    /// Verifies a delivery and parses its body
+    #[cfg(feature = "client")]
+    pub fn verify_event<K, V>(
+        &self,
+        headers: impl IntoIterator<Item = (K, V)>,
+        body: &[u8],
+    ) -> Result<WebhookDelivery, WebhookError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.verify(headers, body)?;
+        parse_delivery(body)
+    }
+}
+
+/// Callback payload: an SDK lifecycle event as seen by EasyCash servers
+#[cfg(feature = "client")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WebhookDelivery {
+    pub id: String,
+    /// Unix time (seconds) the event occurred
+    pub created_at: i64,
+    pub event: crate::events::SdkEvent,
+}
+
+/// Parses a callback body (verify it first)
+#[cfg(feature = "client")]
+pub fn parse_delivery(body: &[u8]) -> Result<WebhookDelivery, WebhookError> {
+    serde_json::from_slice(body).map_err(|e| WebhookError::InvalidPayload(e.to_string()))
+}
+
+fn mac(secret: &[u8], version: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    // HMAC accepts keys of any length, so this cannot fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    if version != LEGACY_SIGNATURE_VERSION {
+        mac.update(SigningDomain::Webhook.tag().as_bytes());
+        mac.update(&[0]);
+    }
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+fn now_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+    const BODY: &[u8] = br#"{"id":"evt_1","created_at":1700000000,"event":{"type":"confirmed","reference_id":"ref_001","correlation_id":"corr-001","tx_hash":"0xabc","fee_used":"0.05 USDC"}}"#;
+
+    fn headers(signature: String, timestamp: i64) -> Vec<(&'static str, String)> {
+        vec![
+            ("X-Ecash-Timestamp", timestamp.to_string()),
+            ("X-Ecash-Signature", signature),
+            ("X-Ecash-Delivery-Id", "dlv_1".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_rejects_tampering_and_stale_deliveries() {
+        let verifier = WebhookVerifier::new("whsec_new");
+        let signed = headers(sign(BODY, b"whsec_new", NOW), NOW);
+        assert_eq!(verifier.verify_at(signed.clone(), BODY, NOW + 10), Ok("dlv_1".to_string()));
+
+        assert_eq!(verifier.verify_at(signed.clone(), b"{}", NOW), Err(WebhookError::InvalidSignature));
+        assert_eq!(
+            verifier.verify_at(signed, BODY, NOW + 301),
+            Err(WebhookError::Expired { age_secs: 301 })
+        );
+        assert_eq!(
+            verifier.verify_at(vec![(TIMESTAMP_HEADER, NOW.to_string())], BODY, NOW),
+            Err(WebhookError::MissingHeader(SIGNATURE_HEADER))
+        );
+    }
+
+    #[test]
+    fn test_secret_rotation_and_replay() {
+        let verifier = WebhookVerifier::new("whsec_new")
+            .with_secret("whsec_old")
+            .with_replay_store(Arc::new(InMemoryReplayStore::new()));
+        let both = format!("{}, {}", sign(BODY, b"whsec_unknown", NOW), sign(BODY, b"whsec_old", NOW));
+        assert!(verifier.verify_at(headers(both.clone(), NOW), BODY, NOW).is_ok());
+        assert_eq!(
+            verifier.verify_at(headers(both, NOW), BODY, NOW),
+            Err(WebhookError::Replayed("dlv_1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_replay_with_rewritten_delivery_id() {
+        let verifier = WebhookVerifier::new("whsec_new").with_replay_store(Arc::new(InMemoryReplayStore::new()));
+        let signed = headers(sign(BODY, b"whsec_new", NOW), NOW);
+        assert!(verifier.verify_at(signed.clone(), BODY, NOW).is_ok());
+
+        let mut rewritten = signed;
+        rewritten[2].1 = "dlv_2".to_string();
+        assert_eq!(
+            verifier.verify_at(rewritten.clone(), BODY, NOW),
+            Err(WebhookError::Replayed("dlv_2".to_string()))
+        );
+        rewritten.truncate(2);
+        assert!(matches!(verifier.verify_at(rewritten, BODY, NOW), Err(WebhookError::Replayed(_))));
+
+        // A fresh signature over the same body is a new delivery
+        let resent = headers(sign(BODY, b"whsec_new", NOW + 1), NOW + 1);
+        assert!(verifier.verify_at(resent, BODY, NOW + 1).is_ok());
+    }
+
+    #[test]
+    fn test_domain_tagged_and_legacy_signatures() {
+        let verifier = WebhookVerifier::new("whsec_new");
+        let signed = sign(BODY, b"whsec_new", NOW);
+        let tagged = hex::decode(signed.strip_prefix("v2=").unwrap()).unwrap();
+        let message = SigningDomain::Webhook.separate(&[format!("{}.", NOW).as_bytes(), BODY].concat());
+        assert_eq!(tagged, crate::crypto::hmac_sha256(b"whsec_new", &message));
+
+        // Untagged v1 signatures still verify; a tagged MAC labelled v1 does not
+        let legacy = format!("v1={}", hex::encode(mac(b"whsec_new", "v1", NOW, BODY).finalize().into_bytes()));
+        assert!(verifier.verify_at(headers(legacy, NOW), BODY, NOW).is_ok());
+        let mislabelled = signed.replacen("v2=", "v1=", 1);
+        assert_eq!(
+            verifier.verify_at(headers(mislabelled, NOW), BODY, NOW),
+            Err(WebhookError::InvalidSignature)
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_parse_delivery_into_sdk_event() {
+        let delivery = parse_delivery(BODY).unwrap();
+        assert_eq!(delivery.id, "evt_1");
+        assert_eq!(delivery.event.name(), "confirmed");
+        assert_eq!(delivery.event.correlation_id(), "corr-001");
+        assert!(matches!(parse_delivery(b"{}"), Err(WebhookError::InvalidPayload(_))));
+    }
+}
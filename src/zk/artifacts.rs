@@ -0,0 +1,506 @@
+//! Proving keys, verifying keys and circuit WASM.
+//!
+//! Artifacts are published to a registry under
+//! `{circuit}/{version}/manifest.json`, or `{circuit}/latest/manifest.json`
+//! for the newest version. The manifest lists each file's path (relative to
+//! its version directory) and SHA-256 hash. It may be signed by the
+//! publisher. `ArtifactManager` downloads a version, checks the signature
+//! and every hash, and caches the files under
+//! `{cache_dir}/{circuit}/{version}/`. Later loads of a pinned version are
+//! served from the cache once the hashes match. Activating another version
+//! swaps the loaded set without restarting; proofs already running finish
+//! with the set they started with. With `trusted_transcripts` configured, a
+//! set is only activated once its proving key passes
+//! `zk::verify_parameters` against its ceremony transcript.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use k256::ecdsa::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::crypto::hash::HashFunction;
+use crate::crypto::{self, SigningDomain, TransactionSigner};
+use crate::http::HttpClient;
+use crate::zk::setup;
+
+/// Configuration of circuit artifacts (`SdkConfig::zk_artifacts`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtifactConfig {
+    /// Base URL of the artifact registry; without one, artifacts are only
+    /// read from the cache
+    pub registry_url: Option<String>,
+    pub circuit: String,
+    /// Version to load; the registry's latest when unset
+    pub version: Option<String>,
+    pub cache_dir: PathBuf,
+    /// Hex public key that must have signed manifests; unsigned manifests
+    /// are accepted when unset
+    pub publisher_key: Option<String>,
+    /// Hex SHA-256 hashes of the published trusted-setup transcripts;
+    /// proving keys are not checked when empty
+    pub trusted_transcripts: Vec<String>,
+    pub timeout: Duration,
+}
+
+impl Default for ArtifactConfig {
+    fn default() -> Self {
+        Self {
+            registry_url: None,
+            circuit: "spend".to_string(),
+            version: None,
+            cache_dir: PathBuf::from("./circuits"),
+            publisher_key: None,
+            trusted_transcripts: Vec::new(),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ArtifactConfig {
+    /// Whether artifacts are managed (a registry or a pinned version is
+    /// configured) rather than read from the fixed `{cache_dir}/{circuit}.wasm`
+    pub fn is_managed(&self) -> bool {
+        self.registry_url.is_some() || self.version.is_some()
+    }
+
+    /// Circuit WASM used when artifacts are not managed
+    pub fn default_circuit_path(&self) -> PathBuf {
+        self.cache_dir.join(format!("{}.wasm", self.circuit))
+    }
+
+    /// Proving key used when artifacts are not managed
+    pub fn default_proving_key_path(&self) -> PathBuf {
+        self.cache_dir.join(format!("{}.zkey", self.circuit))
+    }
+
+    /// Ceremony transcript used when artifacts are not managed
+    pub fn default_transcript_path(&self) -> PathBuf {
+        self.cache_dir.join(format!("{}.transcript.json", self.circuit))
+    }
+}
+
+/// Kind of circuit artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    ProvingKey,
+    VerifyingKey,
+    CircuitWasm,
+    /// Trusted-setup transcript the proving key came from
+    CeremonyTranscript,
+}
+
+impl ArtifactKind {
+    /// Name of the cached file
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ArtifactKind::ProvingKey => "proving.key",
+            ArtifactKind::VerifyingKey => "verifying.key",
+            ArtifactKind::CircuitWasm => "circuit.wasm",
+            ArtifactKind::CeremonyTranscript => "transcript.json",
+        }
+    }
+}
+
+/// File listed in a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactEntry {
+    /// Path relative to the version directory in the registry
+    pub path: String,
+    /// Hex-encoded SHA-256 of the file
+    pub sha256: String,
+}
+
+/// Published description of one circuit version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactManifest {
+    pub circuit: String,
+    pub version: String,
+    pub artifacts: BTreeMap<ArtifactKind, ArtifactEntry>,
+    /// Publisher's signature over the manifest without this field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl ArtifactManifest {
+    fn signing_bytes(&self) -> Result<Vec<u8>, String> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsigned).map_err(|e| e.to_string())
+    }
+
+    /// Signs the manifest as its publisher
+    pub fn sign(mut self, signer: &TransactionSigner) -> Result<Self, String> {
+        self.signature = Some(signer.sign_in_domain(SigningDomain::ArtifactManifest, &self.signing_bytes()?)?);
+        Ok(self)
+    }
+
+    pub fn verify(&self, publisher: &VerifyingKey) -> Result<(), String> {
+        let signature = self.signature.as_deref().ok_or("manifest is not signed")?;
+        let bytes = self.signing_bytes()?;
+        let domain = SigningDomain::ArtifactManifest;
+        if !crypto::verify_signature_in_domain(HashFunction::Sha256, domain, publisher, &bytes, signature)? {
+            return Err(format!("invalid signature on {} {} manifest", self.circuit, self.version));
+        }
+        Ok(())
+    }
+}
+
+/// Hex-encoded SHA-256 of `data`
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Registry the artifacts are downloaded from
+#[async_trait::async_trait]
+pub trait ArtifactSource: Send + Sync {
+    /// Returns the file at `path`, relative to the registry root
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, String>;
+}
+
+/// Registry served over HTTP(S)
+pub struct HttpArtifactSource {
+    base_url: String,
+    timeout: Duration,
+    http: HttpClient,
+}
+
+impl HttpArtifactSource {
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            timeout,
+            http: HttpClient::default(),
+        }
+    }
+
+    /// Sends requests through `http` (e.g. `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl ArtifactSource for HttpArtifactSource {
+    async fn fetch(&self, path: &str) -> Result<Vec<u8>, String> {
+        let url = format!("{}/{}", self.base_url, path);
+        let resp = self.http.get(&url, &[], self.timeout).await?;
+        if !resp.is_success() {
+            return Err(format!("artifact registry returned status {} for {}", resp.status, path));
+        }
+        Ok(resp.body_bytes)
+    }
+}
+
+/// Verified artifacts of one circuit version, on disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitArtifacts {
+    pub manifest: ArtifactManifest,
+    pub files: BTreeMap<ArtifactKind, PathBuf>,
+}
+
+impl CircuitArtifacts {
+    pub fn version(&self) -> &str {
+        &self.manifest.version
+    }
+
+    pub fn path(&self, kind: ArtifactKind) -> Option<&Path> {
+        self.files.get(&kind).map(PathBuf::as_path)
+    }
+}
+
+/// Downloads, verifies, caches and hot-swaps circuit artifacts
+pub struct ArtifactManager {
+    config: ArtifactConfig,
+    source: Option<Arc<dyn ArtifactSource>>,
+    current: RwLock<Option<Arc<CircuitArtifacts>>>,
+}
+
+impl ArtifactManager {
+    /// Downloads from the configured registry, if any
+    pub fn new(config: ArtifactConfig) -> Self {
+        let source = config
+            .registry_url
+            .as_ref()
+            .map(|url| Arc::new(HttpArtifactSource::new(url.clone(), config.timeout)) as Arc<dyn ArtifactSource>);
+        Self {
+            config,
+            source,
+            current: RwLock::new(None),
+        }
+    }
+
+    /// Downloads from the configured registry through `http` (e.g.
+    /// `EasyCashClient::http_client`)
+    pub fn with_http_client(mut self, http: HttpClient) -> Self {
+        if let Some(url) = &self.config.registry_url {
+            self.source = Some(Arc::new(HttpArtifactSource::new(url.clone(), self.config.timeout).with_http_client(http)));
+        }
+        self
+    }
+
+    /// Downloads from `source` instead of the configured registry
+    pub fn with_source(mut self, source: Arc<dyn ArtifactSource>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Artifacts currently loaded, if any
+    pub fn current(&self) -> Option<Arc<CircuitArtifacts>> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Loads the configured version (or the latest) and makes it current
+    pub async fn load(&self) -> Result<Arc<CircuitArtifacts>, String> {
+        let version = self.config.version.clone();
+        self.activate_version(version.as_deref()).await
+    }
+
+    /// Loads `version` and makes it current, replacing the loaded set
+    pub async fn activate(&self, version: &str) -> Result<Arc<CircuitArtifacts>, String> {
+        self.activate_version(Some(version)).await
+    }
+
+    async fn activate_version(&self, version: Option<&str>) -> Result<Arc<CircuitArtifacts>, String> {
+        let artifacts = Arc::new(self.fetch_version(version).await?);
+        if !self.config.trusted_transcripts.is_empty() {
+            let (Some(key), Some(transcript)) = (
+                artifacts.path(ArtifactKind::ProvingKey),
+                artifacts.path(ArtifactKind::CeremonyTranscript),
+            ) else {
+                return Err(format!(
+                    "{} {} lists no proving key and ceremony transcript to verify",
+                    self.config.circuit,
+                    artifacts.version()
+                ));
+            };
+            setup::verify_parameter_files(key, transcript, &self.config.trusted_transcripts)
+                .map_err(|e| format!("{} {}: {}", self.config.circuit, artifacts.version(), e))?;
+        }
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Some(artifacts.clone());
+        tracing::info!("[SDK] Loaded {} circuit artifacts {}", self.config.circuit, artifacts.version());
+        Ok(artifacts)
+    }
+
+    async fn fetch_version(&self, version: Option<&str>) -> Result<CircuitArtifacts, String> {
+        let circuit = &self.config.circuit;
+        // A pinned version that is fully cached needs no registry
+        if let Some(version) = version {
+            match self.cached(version) {
+                Ok(Some(artifacts)) => return Ok(artifacts),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("[SDK] Ignoring cached {} artifacts {}: {}", circuit, version, e),
+            }
+        }
+        let source = self
+            .source
+            .as_ref()
+            .ok_or_else(|| format!("{} artifacts {} are not cached and no registry is configured", circuit, version.unwrap_or("latest")))?;
+
+        let manifest_bytes = source
+            .fetch(&format!("{}/{}/manifest.json", circuit, version.unwrap_or("latest")))
+            .await?;
+        let manifest: ArtifactManifest =
+            serde_json::from_slice(&manifest_bytes).map_err(|e| format!("invalid artifact manifest: {}", e))?;
+        self.check_manifest(&manifest, version)?;
+
+        let dir = self.version_dir(&manifest.version);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+        let mut files = BTreeMap::new();
+        for (kind, entry) in &manifest.artifacts {
+            let path = dir.join(kind.file_name());
+            if file_hash(&path).as_deref() != Some(entry.sha256.as_str()) {
+                let data = source.fetch(&format!("{}/{}/{}", circuit, manifest.version, entry.path)).await?;
+                if !sha256_hex(&data).eq_ignore_ascii_case(&entry.sha256) {
+                    return Err(format!("{} of {} {} does not match its hash", entry.path, circuit, manifest.version));
+                }
+                write_atomic(&path, &data)?;
+            }
+            files.insert(*kind, path);
+        }
+        write_atomic(&dir.join("manifest.json"), &manifest_bytes)?;
+        Ok(CircuitArtifacts { manifest, files })
+    }
+
+    /// The cached artifacts of `version`, if the manifest and every file
+    /// are present and intact
+    fn cached(&self, version: &str) -> Result<Option<CircuitArtifacts>, String> {
+        let dir = self.version_dir(version);
+        let Ok(manifest_bytes) = std::fs::read(dir.join("manifest.json")) else {
+            return Ok(None);
+        };
+        let manifest: ArtifactManifest =
+            serde_json::from_slice(&manifest_bytes).map_err(|e| format!("invalid cached manifest: {}", e))?;
+        self.check_manifest(&manifest, Some(version))?;
+        let mut files = BTreeMap::new();
+        for (kind, entry) in &manifest.artifacts {
+            let path = dir.join(kind.file_name());
+            if file_hash(&path).as_deref() != Some(entry.sha256.as_str()) {
+                return Ok(None);
+            }
+            files.insert(*kind, path);
+        }
+        Ok(Some(CircuitArtifacts { manifest, files }))
+    }
+
+    fn check_manifest(&self, manifest: &ArtifactManifest, version: Option<&str>) -> Result<(), String> {
+        if manifest.circuit != self.config.circuit {
+            return Err(format!("manifest is for circuit {}, expected {}", manifest.circuit, self.config.circuit));
+        }
+        if version.is_some_and(|v| v != manifest.version) {
+            return Err(format!("manifest is for version {}, expected {}", manifest.version, version.unwrap_or_default()));
+        }
+        if !manifest.artifacts.contains_key(&ArtifactKind::CircuitWasm) {
+            return Err("manifest lists no circuit WASM".to_string());
+        }
+        if let Some(ref publisher) = self.config.publisher_key {
+            let key = crypto::public_key_from_hex(publisher)?;
+            manifest.verify(&VerifyingKey::from(&key))?;
+        }
+        Ok(())
+    }
+
+    fn version_dir(&self, version: &str) -> PathBuf {
+        self.config.cache_dir.join(&self.config.circuit).join(version)
+    }
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|data| sha256_hex(&data))
+}
+
+/// Writes through a temporary file so readers never see a partial file
+fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, data).map_err(|e| format!("failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::SecretKey;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// In-memory registry counting fetches
+    #[derive(Default)]
+    struct MemoryRegistry {
+        files: Mutex<HashMap<String, Vec<u8>>>,
+        fetches: Mutex<u32>,
+    }
+
+    impl MemoryRegistry {
+        fn publish(&self, version: &str, wasm: &[u8], signer: &TransactionSigner, latest: bool) {
+            let manifest = ArtifactManifest {
+                circuit: "spend".to_string(),
+                version: version.to_string(),
+                artifacts: BTreeMap::from([(
+                    ArtifactKind::CircuitWasm,
+                    ArtifactEntry {
+                        path: "spend.wasm".to_string(),
+                        sha256: sha256_hex(wasm),
+                    },
+                )]),
+                signature: None,
+            }
+            .sign(signer)
+            .unwrap();
+            let manifest = serde_json::to_vec(&manifest).unwrap();
+            let mut files = self.files.lock().unwrap();
+            files.insert(format!("spend/{}/spend.wasm", version), wasm.to_vec());
+            files.insert(format!("spend/{}/manifest.json", version), manifest.clone());
+            if latest {
+                files.insert("spend/latest/manifest.json".to_string(), manifest);
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ArtifactSource for MemoryRegistry {
+        async fn fetch(&self, path: &str) -> Result<Vec<u8>, String> {
+            *self.fetches.lock().unwrap() += 1;
+            self.files.lock().unwrap().get(path).cloned().ok_or_else(|| format!("{} not found", path))
+        }
+    }
+
+    fn signer(seed: u8) -> TransactionSigner {
+        TransactionSigner::new(SecretKey::from_bytes(&[seed; 32].into()).unwrap())
+    }
+
+    fn config(cache_dir: &Path, publisher: &TransactionSigner) -> ArtifactConfig {
+        let key = k256::PublicKey::from(publisher.verifying_key());
+        ArtifactConfig {
+            cache_dir: cache_dir.to_path_buf(),
+            publisher_key: Some(crypto::public_key_to_hex(&key)),
+            ..ArtifactConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_cache_and_activate() {
+        let cache_dir = std::env::temp_dir().join(format!("ecash-artifacts-{}", uuid::Uuid::new_v4()));
+        let publisher = signer(7);
+        let registry = Arc::new(MemoryRegistry::default());
+        registry.publish("1.0.0", b"wasm v1", &publisher, false);
+        registry.publish("1.1.0", b"wasm v2", &publisher, true);
+
+        let manager = ArtifactManager::new(config(&cache_dir, &publisher)).with_source(registry.clone());
+        let latest = manager.load().await.unwrap();
+        assert_eq!(latest.version(), "1.1.0");
+        assert_eq!(std::fs::read(latest.path(ArtifactKind::CircuitWasm).unwrap()).unwrap(), b"wasm v2");
+
+        let pinned = manager.activate("1.0.0").await.unwrap();
+        assert_eq!(manager.current().unwrap().version(), "1.0.0");
+
+        // A pinned, cached version loads without the registry
+        let fetches = *registry.fetches.lock().unwrap();
+        let offline = ArtifactManager::new(ArtifactConfig {
+            version: Some("1.0.0".to_string()),
+            ..config(&cache_dir, &publisher)
+        });
+        assert_eq!(*offline.load().await.unwrap(), *pinned);
+        assert_eq!(*registry.fetches.lock().unwrap(), fetches);
+        assert!(offline.activate("2.0.0").await.unwrap_err().contains("no registry"));
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_tampered_artifacts() {
+        let cache_dir = std::env::temp_dir().join(format!("ecash-artifacts-{}", uuid::Uuid::new_v4()));
+        let publisher = signer(7);
+        let registry = Arc::new(MemoryRegistry::default());
+        registry.publish("1.0.0", b"wasm v1", &publisher, true);
+        registry
+            .files
+            .lock()
+            .unwrap()
+            .insert("spend/1.0.0/spend.wasm".to_string(), b"tampered".to_vec());
+        let manager = ArtifactManager::new(config(&cache_dir, &publisher)).with_source(registry.clone());
+        assert!(manager.load().await.unwrap_err().contains("does not match its hash"));
+        assert!(manager.current().is_none());
+
+        // Manifests signed by anyone else are refused
+        registry.publish("1.0.0", b"wasm v1", &signer(8), true);
+        assert!(manager.load().await.unwrap_err().contains("invalid signature"));
+
+        // With trusted transcripts, versions without a verifiable proving key are refused
+        let verifying = ArtifactManager::new(ArtifactConfig {
+            publisher_key: None,
+            trusted_transcripts: vec!["00".repeat(32)],
+            ..config(&cache_dir, &publisher)
+        })
+        .with_source(registry.clone());
+        assert!(verifying.load().await.unwrap_err().contains("no proving key and ceremony transcript"));
+        assert!(verifying.current().is_none());
+        let _ = std::fs::remove_dir_all(&cache_dir);
+    }
+}
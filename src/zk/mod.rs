@@ -1,10 +1,53 @@
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use hex;
 
+#[cfg(feature = "client")]
+pub mod artifacts;
+pub mod notes;
+pub mod nullifiers;
+#[cfg(feature = "client")]
+pub mod pool;
+pub mod proof_cache;
+pub mod setup;
+pub mod sync;
+pub mod witness;
+
+pub use setup::{verify_parameter_files, verify_parameters};
+
+/// Statement of a solvency proof: `balance` covers `required`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolvencyInput {
+    pub balance: String,
+    pub required: String,
+}
+
+impl SolvencyInput {
+    pub fn new(balance: impl Into<String>, required: impl Into<String>) -> Self {
+        Self {
+            balance: balance.into(),
+            required: required.into(),
+        }
+    }
+}
+
 /// Trait for ZK proof generation (allows for future real implementation)
 pub trait ZkProofGenerator: Send + Sync {
     /// Generates a solvency proof without revealing the actual balance
     fn generate_solvency_proof(&self, balance: &str, required: &str) -> Result<String, String>;
+
+    /// Generates one proof of all `inputs` at once. Generators without
+    /// native aggregation prove each input and commit to the proofs in order.
+    fn generate_batch_proof(&self, inputs: &[SolvencyInput]) -> Result<String, String> {
+        if inputs.is_empty() {
+            return Err("batch proof needs at least one input".to_string());
+        }
+        let mut hasher = Sha256::new();
+        for input in inputs {
+            hasher.update(self.generate_solvency_proof(&input.balance, &input.required)?.as_bytes());
+        }
+        Ok(format!("0x{}", hex::encode(hasher.finalize())))
+    }
     
     /// Verifies a ZK proof
     fn verify_proof(&self, proof: &str) -> bool;
@@ -47,6 +90,21 @@ impl ZkProofGenerator for MockProofGenerator {
         Ok(format!("0x{}", hex::encode(hash)))
     }
 
+    /// Simulates an aggregated proof over all inputs in a single pass.
+    ///
+    /// **MOCK IMPLEMENTATION**: hashes the inputs as one statement.
+    fn generate_batch_proof(&self, inputs: &[SolvencyInput]) -> Result<String, String> {
+        if inputs.is_empty() {
+            return Err("batch proof needs at least one input".to_string());
+        }
+        let mut hasher = Sha256::new();
+        for input in inputs {
+            hasher.update(format!("{}-{};", input.balance, input.required).as_bytes());
+        }
+        hasher.update(self.circuit_path.as_bytes());
+        Ok(format!("0x{}", hex::encode(hasher.finalize())))
+    }
+
     /// Verifies a ZK proof off-chain.
     /// 
     /// **MOCK IMPLEMENTATION**: Always returns true if proof length > 10.
@@ -85,6 +143,16 @@ mod tests {
         assert_eq!(proof1, proof2);
     }
 
+    #[test]
+    fn test_generate_batch_proof() {
+        let generator = MockProofGenerator::new("./circuits/spend.wasm");
+        let inputs = vec![SolvencyInput::new("1000", "500"), SolvencyInput::new("80", "75")];
+        let proof = generator.generate_batch_proof(&inputs).unwrap();
+        assert!(generator.verify_proof(&proof));
+        assert_ne!(proof, generator.generate_batch_proof(&inputs[..1]).unwrap());
+        assert!(generator.generate_batch_proof(&[]).is_err());
+    }
+
     #[test]
     fn test_verify_proof_valid() {
         let generator = MockProofGenerator::new("./circuits/spend.wasm");
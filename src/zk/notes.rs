@@ -0,0 +1,257 @@
+//! Shielded notes and the viewing keys used to discover them.
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::crypto::hash::HashFunction;
+use crate::types::ChainId;
+
+/// Prefix identifying an encoded viewing key
+pub const VIEWING_KEY_PREFIX: &str = "evk_";
+
+/// Read-only key that can discover (but not spend) an owner's shielded notes.
+///
+/// Encoded as `evk_` followed by 64 hex characters.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ViewingKey(String);
+
+impl ViewingKey {
+    /// Parses an encoded viewing key
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let body = value
+            .strip_prefix(VIEWING_KEY_PREFIX)
+            .ok_or_else(|| format!("viewing key must start with {}", VIEWING_KEY_PREFIX))?;
+        if body.len() != 64 || !body.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("viewing key must contain 64 hex characters".to_string());
+        }
+        Ok(Self(value.to_lowercase()))
+    }
+
+    /// Returns true if `value` looks like an encoded viewing key
+    pub fn is_viewing_key(value: &str) -> bool {
+        value.starts_with(VIEWING_KEY_PREFIX)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// Viewing keys reveal transaction history, so keep them out of logs
+impl std::fmt::Debug for ViewingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ViewingKey({}…)", &self.0[..VIEWING_KEY_PREFIX.len() + 8])
+    }
+}
+
+/// Shielded note owned by a viewing key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShieldedNote {
+    /// Hex-encoded note commitment
+    pub commitment: String,
+    pub asset: String,
+    /// Value in the asset's smallest unit
+    pub amount: u128,
+    pub spent: bool,
+}
+
+/// Creates the note a shield deposit mints for `owner`. The commitment
+/// hides the owner and value behind a random blinding factor.
+pub fn create_note(asset: &str, amount: u128, owner: &str) -> ShieldedNote {
+    create_note_with(HashFunction::Sha256, asset, amount, owner)
+}
+
+/// Creates a note like `create_note`, committing with `hash` (see
+/// `HashConfig::commitments`)
+pub fn create_note_with(hash: HashFunction, asset: &str, amount: u128, owner: &str) -> ShieldedNote {
+    let mut blinding = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut blinding);
+    let preimage = [
+        owner.as_bytes(),
+        asset.to_uppercase().as_bytes(),
+        &amount.to_be_bytes(),
+        &blinding,
+    ]
+    .concat();
+    ShieldedNote {
+        commitment: format!("0x{}", hex::encode(hash.hash(&preimage))),
+        asset: asset.to_uppercase(),
+        amount,
+        spent: false,
+    }
+}
+
+/// Source of the shielded notes visible to a viewing key
+#[async_trait::async_trait]
+pub trait NoteScanner: Send + Sync {
+    /// Returns all notes (spent and unspent) visible to the viewing key on `chain`
+    async fn notes(&self, viewing_key: &ViewingKey, chain: ChainId) -> Result<Vec<ShieldedNote>, String>;
+}
+
+/// Sums the unspent notes of `asset`
+pub fn unspent_balance(notes: &[ShieldedNote], asset: &str) -> u128 {
+    notes
+        .iter()
+        .filter(|n| !n.spent && n.asset.eq_ignore_ascii_case(asset))
+        .fold(0u128, |acc, n| acc.saturating_add(n.amount))
+}
+
+/// Notes chosen to fund a shielded spend
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteSelection {
+    pub notes: Vec<ShieldedNote>,
+    /// Value returned to the owner in a new change note (0 for none)
+    pub change: u128,
+    /// Leftover below the dust threshold, spent with the transfer instead of
+    /// becoming a note that would cost more to spend than it holds
+    pub dust: u128,
+}
+
+/// Picks unspent notes of `asset` worth at least `amount`: a note matching
+/// exactly if there is one, otherwise the largest notes first. Change below
+/// `dust_threshold` (see `AssetRegistry::dust_threshold_units`) gets no note.
+pub fn select_notes(notes: &[ShieldedNote], asset: &str, amount: u128, dust_threshold: u128) -> Result<NoteSelection, String> {
+    let mut candidates: Vec<&ShieldedNote> = notes
+        .iter()
+        .filter(|n| !n.spent && n.asset.eq_ignore_ascii_case(asset))
+        .collect();
+    if let Some(exact) = candidates.iter().find(|n| n.amount == amount) {
+        return Ok(NoteSelection {
+            notes: vec![(*exact).clone()],
+            change: 0,
+            dust: 0,
+        });
+    }
+
+    candidates.sort_by_key(|n| std::cmp::Reverse(n.amount));
+    let mut selected = Vec::new();
+    let mut total = 0u128;
+    for note in candidates {
+        if total >= amount {
+            break;
+        }
+        total = total.saturating_add(note.amount);
+        selected.push(note.clone());
+    }
+    if total < amount {
+        return Err(format!("shielded {} notes hold {}, need {}", asset, total, amount));
+    }
+    let leftover = total - amount;
+    let (change, dust) = if leftover < dust_threshold { (0, leftover) } else { (leftover, 0) };
+    Ok(NoteSelection {
+        notes: selected,
+        change,
+        dust,
+    })
+}
+
+/// In-memory note scanner for development/testing
+#[derive(Default)]
+pub struct InMemoryNoteScanner {
+    notes: RwLock<HashMap<(ViewingKey, ChainId), Vec<ShieldedNote>>>,
+}
+
+impl InMemoryNoteScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a note visible to the viewing key
+    pub fn add_note(&self, viewing_key: &ViewingKey, chain: ChainId, note: ShieldedNote) {
+        if let Ok(mut notes) = self.notes.write() {
+            notes.entry((viewing_key.clone(), chain)).or_default().push(note);
+        }
+    }
+
+    /// Marks the note with the given commitment as spent
+    pub fn mark_spent(&self, commitment: &str) {
+        if let Ok(mut notes) = self.notes.write() {
+            for note in notes.values_mut().flatten() {
+                if note.commitment == commitment {
+                    note.spent = true;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl NoteScanner for InMemoryNoteScanner {
+    async fn notes(&self, viewing_key: &ViewingKey, chain: ChainId) -> Result<Vec<ShieldedNote>, String> {
+        Ok(self
+            .notes
+            .read()
+            .map_err(|_| "note scanner poisoned".to_string())?
+            .get(&(viewing_key.clone(), chain))
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewing_key() -> ViewingKey {
+        ViewingKey::parse(&format!("evk_{}", "ab".repeat(32))).unwrap()
+    }
+
+    fn note(commitment: &str, asset: &str, amount: u128) -> ShieldedNote {
+        ShieldedNote {
+            commitment: commitment.to_string(),
+            asset: asset.to_string(),
+            amount,
+            spent: false,
+        }
+    }
+
+    #[test]
+    fn test_create_note_blinds_commitment() {
+        let first = create_note("usdc", 5_000_000, "0xowner");
+        let second = create_note("USDC", 5_000_000, "0xowner");
+        assert_eq!((first.asset.as_str(), first.amount, first.spent), ("USDC", 5_000_000, false));
+        assert_eq!(first.commitment.len(), 66);
+        assert_ne!(first.commitment, second.commitment);
+    }
+
+    #[test]
+    fn test_parse_viewing_key() {
+        assert!(ViewingKey::parse("evk_1234").is_err());
+        assert!(ViewingKey::parse(&"ab".repeat(34)).is_err());
+        assert!(!format!("{:?}", viewing_key()).contains(&"ab".repeat(32)));
+    }
+
+    #[test]
+    fn test_select_notes_avoids_dust_change() {
+        let mut notes = vec![note("0x01", "USDC", 500), note("0x02", "USDC", 300), note("0x03", "USDC", 1_000)];
+        notes[2].spent = true;
+
+        let exact = select_notes(&notes, "usdc", 300, 50).unwrap();
+        assert_eq!((exact.notes[0].commitment.as_str(), exact.change, exact.dust), ("0x02", 0, 0));
+
+        let with_change = select_notes(&notes, "USDC", 400, 50).unwrap();
+        assert_eq!((with_change.notes.len(), with_change.change, with_change.dust), (1, 100, 0));
+
+        // 20 left over is below the threshold: no change note
+        let dusty = select_notes(&notes, "USDC", 780, 50).unwrap();
+        assert_eq!((dusty.notes.len(), dusty.change, dusty.dust), (2, 0, 20));
+
+        assert!(select_notes(&notes, "USDC", 900, 50).unwrap_err().contains("hold 800"));
+    }
+
+    #[tokio::test]
+    async fn test_unspent_balance() {
+        let scanner = InMemoryNoteScanner::new();
+        let vk = viewing_key();
+        scanner.add_note(&vk, ChainId::Base, note("0x01", "USDC", 500));
+        scanner.add_note(&vk, ChainId::Base, note("0x02", "usdc", 250));
+        scanner.add_note(&vk, ChainId::Base, note("0x03", "DAI", 999));
+        scanner.mark_spent("0x01");
+
+        let notes = scanner.notes(&vk, ChainId::Base).await.unwrap();
+        assert_eq!(unspent_balance(&notes, "USDC"), 250);
+        assert!(scanner.notes(&vk, ChainId::Ethereum).await.unwrap().is_empty());
+    }
+}
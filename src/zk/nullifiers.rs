@@ -0,0 +1,296 @@
+//! Local double-spend detection for shielded notes.
+//!
+//! Spending a note publishes its nullifier; the pool rejects a nullifier it
+//! has seen before. The `NullifierTracker` records the nullifiers of every
+//! spend this SDK hands to an agent, so a second spend of the same note is
+//! refused before a proof is generated for it instead of failing on-chain.
+//! Rejections are counted as near-miss double spends.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::zk::notes::ShieldedNote;
+
+/// Nullifier published when `note` is spent.
+///
+/// **MOCK IMPLEMENTATION**: derived from the commitment alone. A real
+/// circuit derives it from the commitment and the owner's spending key.
+pub fn nullifier(note: &ShieldedNote) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"ecash-sdk/nullifier/v1");
+    hasher.update(note.commitment.as_bytes());
+    format!("0x{}", hex::encode(hasher.finalize()))
+}
+
+/// Recorded spend of a nullifier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpentNullifier {
+    pub nullifier: String,
+    /// Reference ID of the transaction that spent it
+    pub reference_id: String,
+    pub spent_at_ms: u64,
+}
+
+/// Persistent record of spent nullifiers.
+///
+/// `insert` must be atomic (insert-if-absent) across every instance sharing
+/// the store.
+pub trait NullifierStore: Send + Sync {
+    /// Inserts `record` unless its nullifier is already recorded, returning
+    /// the existing record in that case
+    fn insert(&self, record: &SpentNullifier) -> Result<Option<SpentNullifier>, String>;
+
+    fn get(&self, nullifier: &str) -> Result<Option<SpentNullifier>, String>;
+}
+
+/// In-memory nullifier store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemoryNullifierStore {
+    records: Mutex<BTreeMap<String, SpentNullifier>>,
+}
+
+impl InMemoryNullifierStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, BTreeMap<String, SpentNullifier>>, String> {
+        self.records.lock().map_err(|_| "nullifier store poisoned".to_string())
+    }
+}
+
+impl NullifierStore for InMemoryNullifierStore {
+    fn insert(&self, record: &SpentNullifier) -> Result<Option<SpentNullifier>, String> {
+        let mut records = self.lock()?;
+        if let Some(existing) = records.get(&record.nullifier) {
+            return Ok(Some(existing.clone()));
+        }
+        records.insert(record.nullifier.clone(), record.clone());
+        Ok(None)
+    }
+
+    fn get(&self, nullifier: &str) -> Result<Option<SpentNullifier>, String> {
+        Ok(self.lock()?.get(nullifier).cloned())
+    }
+}
+
+/// Nullifier store kept in a JSON file, rewritten (via a temporary file and
+/// rename) on every insert. Atomic only within one process.
+pub struct FileNullifierStore {
+    path: PathBuf,
+    records: Mutex<BTreeMap<String, SpentNullifier>>,
+}
+
+impl FileNullifierStore {
+    /// Opens the store, loading any nullifiers already in `path`
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let records = match std::fs::read_to_string(&path) {
+            Ok(json) => {
+                let records: Vec<SpentNullifier> = serde_json::from_str(&json)
+                    .map_err(|e| format!("corrupt nullifier file {}: {}", path.display(), e))?;
+                records.into_iter().map(|r| (r.nullifier.clone(), r)).collect()
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("failed to read {}: {}", path.display(), e)),
+        };
+        Ok(Self {
+            path,
+            records: Mutex::new(records),
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, BTreeMap<String, SpentNullifier>>, String> {
+        self.records.lock().map_err(|_| "nullifier store poisoned".to_string())
+    }
+}
+
+impl NullifierStore for FileNullifierStore {
+    fn insert(&self, record: &SpentNullifier) -> Result<Option<SpentNullifier>, String> {
+        let mut records = self.lock()?;
+        if let Some(existing) = records.get(&record.nullifier) {
+            return Ok(Some(existing.clone()));
+        }
+        let mut updated = records.clone();
+        updated.insert(record.nullifier.clone(), record.clone());
+        let json = serde_json::to_string(&updated.values().collect::<Vec<_>>()).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &self.path)
+            .map_err(|e| format!("failed to replace {}: {}", self.path.display(), e))?;
+        *records = updated;
+        Ok(None)
+    }
+
+    fn get(&self, nullifier: &str) -> Result<Option<SpentNullifier>, String> {
+        Ok(self.lock()?.get(nullifier).cloned())
+    }
+}
+
+/// Why a spend was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpendRejection {
+    /// The note's nullifier was already spent by another transaction
+    DoubleSpend {
+        commitment: String,
+        nullifier: String,
+        spent_by: String,
+    },
+    /// The note scanner already reports the note as spent
+    AlreadySpent { commitment: String },
+    /// The nullifier store could not be read or written
+    Unavailable(String),
+}
+
+impl SpendRejection {
+    /// Machine-readable reason, as used in error details
+    pub fn reason(&self) -> &'static str {
+        match self {
+            SpendRejection::DoubleSpend { .. } | SpendRejection::AlreadySpent { .. } => "double_spend",
+            SpendRejection::Unavailable(_) => "nullifier_store_unavailable",
+        }
+    }
+}
+
+impl std::fmt::Display for SpendRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpendRejection::DoubleSpend { commitment, spent_by, .. } => {
+                write!(f, "note {} was already spent by {}", commitment, spent_by)
+            }
+            SpendRejection::AlreadySpent { commitment } => write!(f, "note {} is already spent", commitment),
+            SpendRejection::Unavailable(e) => write!(f, "nullifier store unavailable: {}", e),
+        }
+    }
+}
+
+/// Records spent nullifiers and refuses second spends of the same note
+pub struct NullifierTracker {
+    store: Arc<dyn NullifierStore>,
+    near_misses: AtomicU64,
+}
+
+impl Default for NullifierTracker {
+    fn default() -> Self {
+        Self::new(Arc::new(InMemoryNullifierStore::new()))
+    }
+}
+
+impl NullifierTracker {
+    pub fn new(store: Arc<dyn NullifierStore>) -> Self {
+        Self {
+            store,
+            near_misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Checks that none of `notes` has been spent by a transaction other than
+    /// `reference_id` (a retry of the same reference may spend them again)
+    pub fn check(&self, reference_id: &str, notes: &[ShieldedNote]) -> Result<(), SpendRejection> {
+        for note in notes {
+            if note.spent {
+                return Err(self.near_miss(SpendRejection::AlreadySpent {
+                    commitment: note.commitment.clone(),
+                }));
+            }
+            let nullifier = nullifier(note);
+            let spent = self.store.get(&nullifier).map_err(SpendRejection::Unavailable)?;
+            if let Some(spent) = spent.filter(|s| s.reference_id != reference_id) {
+                return Err(self.near_miss(SpendRejection::DoubleSpend {
+                    commitment: note.commitment.clone(),
+                    nullifier,
+                    spent_by: spent.reference_id,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records `notes` as spent by `reference_id`. Fails if another
+    /// transaction recorded one of them since `check`.
+    pub fn record(&self, reference_id: &str, notes: &[ShieldedNote]) -> Result<(), SpendRejection> {
+        let spent_at_ms = now_ms();
+        for note in notes {
+            let record = SpentNullifier {
+                nullifier: nullifier(note),
+                reference_id: reference_id.to_string(),
+                spent_at_ms,
+            };
+            let existing = self.store.insert(&record).map_err(SpendRejection::Unavailable)?;
+            if let Some(existing) = existing.filter(|s| s.reference_id != reference_id) {
+                return Err(self.near_miss(SpendRejection::DoubleSpend {
+                    commitment: note.commitment.clone(),
+                    nullifier: record.nullifier,
+                    spent_by: existing.reference_id,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of spends refused because their note was already spent
+    pub fn near_misses(&self) -> u64 {
+        self.near_misses.load(Ordering::Relaxed)
+    }
+
+    fn near_miss(&self, rejection: SpendRejection) -> SpendRejection {
+        self.near_misses.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!("[SDK] Refused double spend: {}", rejection);
+        rejection
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zk::notes::create_note;
+
+    #[test]
+    fn test_tracker_refuses_second_spend() {
+        let tracker = NullifierTracker::default();
+        let note = create_note("USDC", 500, "0xowner");
+        let other = create_note("USDC", 500, "0xowner");
+        assert_ne!(nullifier(&note), nullifier(&other));
+
+        tracker.check("ref_001", std::slice::from_ref(&note)).unwrap();
+        tracker.record("ref_001", std::slice::from_ref(&note)).unwrap();
+        // A retry of the same transaction may spend it again
+        tracker.check("ref_001", std::slice::from_ref(&note)).unwrap();
+
+        let err = tracker.check("ref_002", &[other.clone(), note.clone()]).unwrap_err();
+        assert!(matches!(err, SpendRejection::DoubleSpend { ref spent_by, .. } if spent_by == "ref_001"));
+        assert_eq!(err.reason(), "double_spend");
+        assert!(tracker.record("ref_002", std::slice::from_ref(&note)).is_err());
+
+        let mut spent = other;
+        spent.spent = true;
+        assert!(matches!(tracker.check("ref_003", &[spent]), Err(SpendRejection::AlreadySpent { .. })));
+        assert_eq!(tracker.near_misses(), 3);
+    }
+
+    #[test]
+    fn test_file_store_persists_nullifiers() {
+        let path = std::env::temp_dir().join(format!("ecash-nullifiers-{}.json", uuid::Uuid::new_v4()));
+        let note = create_note("USDC", 500, "0xowner");
+        NullifierTracker::new(Arc::new(FileNullifierStore::open(&path).unwrap()))
+            .record("ref_001", std::slice::from_ref(&note))
+            .unwrap();
+
+        let reopened = NullifierTracker::new(Arc::new(FileNullifierStore::open(&path).unwrap()));
+        assert!(reopened.check("ref_002", &[note]).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,356 @@
+//! Worker pool for proof generation.
+//!
+//! Proving is CPU heavy, so generating proofs inline on the async runtime
+//! both blocks it and serializes throughput. The pool runs proofs on
+//! dedicated threads. Its queue is bounded and ordered by job size, so small
+//! proofs are not stuck behind large ones. Threads start on the first job.
+
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, RwLock};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::monitoring::ProofPoolStats;
+use crate::zk::{SolvencyInput, ZkProofGenerator};
+
+/// Configuration of the proof worker pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofPoolConfig {
+    /// Worker threads (0 uses the number of available CPUs)
+    pub threads: usize,
+    /// Maximum number of jobs waiting for a worker
+    pub queue_depth: usize,
+}
+
+impl Default for ProofPoolConfig {
+    fn default() -> Self {
+        Self {
+            threads: 0,
+            queue_depth: 256,
+        }
+    }
+}
+
+/// Solvency proof to generate, of one statement or aggregated over several
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofJob {
+    pub inputs: Vec<SolvencyInput>,
+    /// Relative cost of the proof (e.g. number of notes spent); smaller jobs
+    /// run first
+    pub size: u32,
+}
+
+impl ProofJob {
+    pub fn solvency(balance: impl Into<String>, required: impl Into<String>) -> Self {
+        Self {
+            inputs: vec![SolvencyInput::new(balance, required)],
+            size: 1,
+        }
+    }
+
+    /// One aggregated proof of all `inputs` (see
+    /// `ZkProofGenerator::generate_batch_proof`), sized by their number
+    pub fn batch(inputs: Vec<SolvencyInput>) -> Self {
+        Self {
+            size: inputs.len() as u32,
+            inputs,
+        }
+    }
+
+    pub fn with_size(mut self, size: u32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+struct Queued {
+    job: ProofJob,
+    sequence: u64,
+    enqueued_at: Instant,
+    reply: oneshot::Sender<Result<String, String>>,
+}
+
+// Max-heap order: the smallest job first, then the oldest
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        other
+            .job
+            .size
+            .cmp(&self.job.size)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.sequence == other.sequence
+    }
+}
+
+impl Eq for Queued {}
+
+#[derive(Default)]
+struct Queue {
+    jobs: BinaryHeap<Queued>,
+    next_sequence: u64,
+    shutdown: bool,
+}
+
+#[derive(Default)]
+struct Counters {
+    in_progress: AtomicU64,
+    completed: AtomicU64,
+    failed: AtomicU64,
+    rejected: AtomicU64,
+    queue_time_us: AtomicU64,
+    prove_time_us: AtomicU64,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    available: Condvar,
+    counters: Counters,
+    generator: RwLock<Arc<dyn ZkProofGenerator>>,
+}
+
+/// Bounded, size-prioritized pool of proving threads
+///
+/// # Example
+/// ```
+/// use ecash_sdk_core::zk::pool::{ProofJob, ProofPool, ProofPoolConfig};
+/// use ecash_sdk_core::zk::ProofGenerator;
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// let pool = ProofPool::new(Arc::new(ProofGenerator::new("./circuits/spend.wasm")), ProofPoolConfig::default());
+/// let proof = pool.prove(ProofJob::solvency("100", "25")).await.unwrap();
+/// assert!(proof.starts_with("0x"));
+/// assert_eq!(pool.stats().completed, 1);
+/// # });
+/// ```
+pub struct ProofPool {
+    config: ProofPoolConfig,
+    shared: Arc<Shared>,
+    workers: OnceLock<Vec<std::thread::JoinHandle<()>>>,
+}
+
+impl ProofPool {
+    pub fn new(generator: Arc<dyn ZkProofGenerator>, config: ProofPoolConfig) -> Self {
+        Self {
+            config,
+            shared: Arc::new(Shared {
+                queue: Mutex::new(Queue::default()),
+                available: Condvar::new(),
+                counters: Counters::default(),
+                generator: RwLock::new(generator),
+            }),
+            workers: OnceLock::new(),
+        }
+    }
+
+    /// Queues the job and waits for its proof. Fails immediately when the
+    /// queue is full.
+    pub async fn prove(&self, job: ProofJob) -> Result<String, String> {
+        self.workers.get_or_init(|| self.spawn_workers());
+        let (reply, proof) = oneshot::channel();
+        {
+            let mut queue = self.shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+            if queue.jobs.len() >= self.config.queue_depth.max(1) {
+                self.shared.counters.rejected.fetch_add(1, Ordering::Relaxed);
+                return Err(format!("proof queue is full ({} jobs waiting)", queue.jobs.len()));
+            }
+            let sequence = queue.next_sequence;
+            queue.next_sequence += 1;
+            queue.jobs.push(Queued {
+                job,
+                sequence,
+                enqueued_at: Instant::now(),
+                reply,
+            });
+        }
+        self.shared.available.notify_one();
+        proof.await.map_err(|_| "proof worker stopped".to_string())?
+    }
+
+    /// Replaces the generator (e.g. with one for new circuit artifacts);
+    /// jobs already running finish with the previous one
+    pub fn set_generator(&self, generator: Arc<dyn ZkProofGenerator>) {
+        *self.shared.generator.write().unwrap_or_else(|e| e.into_inner()) = generator;
+    }
+
+    /// Verifies a proof with the current generator
+    pub fn verify(&self, proof: &str) -> bool {
+        self.shared.generator.read().unwrap_or_else(|e| e.into_inner()).verify_proof(proof)
+    }
+
+    pub fn stats(&self) -> ProofPoolStats {
+        let counters = &self.shared.counters;
+        let queued = self.shared.queue.lock().map(|q| q.jobs.len() as u64).unwrap_or(0);
+        let completed = counters.completed.load(Ordering::Relaxed);
+        let failed = counters.failed.load(Ordering::Relaxed);
+        let average_ms = |total_us: &AtomicU64| {
+            let finished = completed + failed;
+            if finished > 0 {
+                total_us.load(Ordering::Relaxed) as f64 / finished as f64 / 1000.0
+            } else {
+                0.0
+            }
+        };
+        ProofPoolStats {
+            queued,
+            in_progress: counters.in_progress.load(Ordering::Relaxed),
+            completed,
+            failed,
+            rejected: counters.rejected.load(Ordering::Relaxed),
+            average_queue_time_ms: average_ms(&counters.queue_time_us),
+            average_prove_time_ms: average_ms(&counters.prove_time_us),
+        }
+    }
+
+    fn spawn_workers(&self) -> Vec<std::thread::JoinHandle<()>> {
+        let threads = match self.config.threads {
+            0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(2),
+            n => n,
+        };
+        (0..threads)
+            .filter_map(|i| {
+                let shared = self.shared.clone();
+                std::thread::Builder::new()
+                    .name(format!("ecash-prover-{}", i))
+                    .spawn(move || work(&shared))
+                    .map_err(|e| tracing::warn!("[SDK] Failed to start proof worker: {}", e))
+                    .ok()
+            })
+            .collect()
+    }
+}
+
+impl Drop for ProofPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap_or_else(|e| e.into_inner()).shutdown = true;
+        self.shared.available.notify_all();
+        for worker in self.workers.take().unwrap_or_default() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn work(shared: &Shared) {
+    loop {
+        let queued = {
+            let mut queue = shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                if queue.shutdown {
+                    return;
+                }
+                if let Some(queued) = queue.jobs.pop() {
+                    break queued;
+                }
+                queue = shared.available.wait(queue).unwrap_or_else(|e| e.into_inner());
+            }
+        };
+
+        let counters = &shared.counters;
+        counters
+            .queue_time_us
+            .fetch_add(queued.enqueued_at.elapsed().as_micros() as u64, Ordering::Relaxed);
+        counters.in_progress.fetch_add(1, Ordering::Relaxed);
+        let started = Instant::now();
+        let generator = shared.generator.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let result = match queued.job.inputs.as_slice() {
+            [input] => generator.generate_solvency_proof(&input.balance, &input.required),
+            inputs => generator.generate_batch_proof(inputs),
+        };
+        counters
+            .prove_time_us
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+        counters.in_progress.fetch_sub(1, Ordering::Relaxed);
+        let finished = if result.is_ok() { &counters.completed } else { &counters.failed };
+        finished.fetch_add(1, Ordering::Relaxed);
+        // The caller may have given up waiting
+        let _ = queued.reply.send(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Records the order jobs run in, blocking the worker until released
+    struct Gate {
+        order: Mutex<Vec<String>>,
+        release: Mutex<bool>,
+        released: Condvar,
+    }
+
+    impl ZkProofGenerator for Gate {
+        fn generate_solvency_proof(&self, balance: &str, _required: &str) -> Result<String, String> {
+            let mut released = self.release.lock().unwrap();
+            while !*released {
+                released = self.released.wait(released).unwrap();
+            }
+            self.order.lock().unwrap().push(balance.to_string());
+            if balance == "bad" {
+                return Err("unsatisfiable".to_string());
+            }
+            Ok(format!("0xproof-{}", balance))
+        }
+
+        fn verify_proof(&self, _proof: &str) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_small_jobs_run_first_and_queue_is_bounded() {
+        let gate = Arc::new(Gate {
+            order: Mutex::new(Vec::new()),
+            release: Mutex::new(false),
+            released: Condvar::new(),
+        });
+        let pool = Arc::new(ProofPool::new(gate.clone(), ProofPoolConfig { threads: 1, queue_depth: 3 }));
+
+        // The first job occupies the only worker until the gate opens
+        let first = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.prove(ProofJob::solvency("first", "1")).await }
+        });
+        while pool.stats().in_progress == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let mut waiting = Vec::new();
+        for (balance, size) in [("large", 10), ("bad", 5), ("small", 1)] {
+            let pool = pool.clone();
+            waiting.push(tokio::spawn(async move { pool.prove(ProofJob::solvency(balance, "1").with_size(size)).await }));
+        }
+        while pool.stats().queued < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(pool.prove(ProofJob::solvency("overflow", "1")).await.unwrap_err().contains("full"));
+
+        *gate.release.lock().unwrap() = true;
+        gate.released.notify_all();
+        assert_eq!(first.await.unwrap().unwrap(), "0xproof-first");
+        let mut results = Vec::new();
+        for job in waiting {
+            results.push(job.await.unwrap());
+        }
+        assert!(results[1].is_err());
+        assert_eq!(*gate.order.lock().unwrap(), vec!["first", "small", "bad", "large"]);
+
+        let stats = pool.stats();
+        assert_eq!((stats.completed, stats.failed, stats.rejected, stats.queued), (3, 1, 1, 0));
+        assert!(stats.average_queue_time_ms > 0.0);
+    }
+}
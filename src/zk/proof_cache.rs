@@ -0,0 +1,168 @@
+//! Cache of generated proofs, so a retried execution does not prove again.
+//!
+//! Entries are keyed by reference ID and keep the proof's witness sealed by a
+//! `WitnessVault`. A cached proof is reused only while it is younger than the
+//! TTL and its witness matches the statement being proven.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::zk::witness::{SealedWitness, WitnessVault};
+use crate::zk::SolvencyInput;
+
+/// Cached proof with its encrypted witness
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedProof {
+    pub proof: String,
+    pub witness: SealedWitness,
+    pub created_at_ms: u64,
+}
+
+/// Storage backend for cached proofs
+pub trait ProofCacheStore: Send + Sync {
+    fn get(&self, reference_id: &str) -> Result<Option<CachedProof>, String>;
+
+    fn put(&self, reference_id: &str, entry: &CachedProof) -> Result<(), String>;
+
+    fn remove(&self, reference_id: &str) -> Result<(), String>;
+
+    /// Reference IDs of all entries
+    fn keys(&self) -> Result<Vec<String>, String>;
+}
+
+/// In-memory proof cache store (per process; use a durable store in production)
+#[derive(Default)]
+pub struct InMemoryProofCacheStore {
+    entries: Mutex<HashMap<String, CachedProof>>,
+}
+
+impl InMemoryProofCacheStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, HashMap<String, CachedProof>>, String> {
+        self.entries.lock().map_err(|_| "proof cache store poisoned".to_string())
+    }
+}
+
+impl ProofCacheStore for InMemoryProofCacheStore {
+    fn get(&self, reference_id: &str) -> Result<Option<CachedProof>, String> {
+        Ok(self.lock()?.get(reference_id).cloned())
+    }
+
+    fn put(&self, reference_id: &str, entry: &CachedProof) -> Result<(), String> {
+        self.lock()?.insert(reference_id.to_string(), entry.clone());
+        Ok(())
+    }
+
+    fn remove(&self, reference_id: &str) -> Result<(), String> {
+        self.lock()?.remove(reference_id);
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<Vec<String>, String> {
+        Ok(self.lock()?.keys().cloned().collect())
+    }
+}
+
+/// Proof cache with witnesses encrypted at rest
+#[derive(Clone)]
+pub struct ProofCache {
+    store: std::sync::Arc<dyn ProofCacheStore>,
+    vault: WitnessVault,
+    ttl: Duration,
+}
+
+impl ProofCache {
+    pub fn new(store: std::sync::Arc<dyn ProofCacheStore>, vault: WitnessVault, ttl: Duration) -> Self {
+        Self { store, vault, ttl }
+    }
+
+    /// The proof cached for `reference_id`, if it is fresh and proves `input`
+    pub fn get(&self, reference_id: &str, input: &SolvencyInput) -> Result<Option<String>, String> {
+        let Some(entry) = self.store.get(reference_id)? else {
+            return Ok(None);
+        };
+        if now_ms().saturating_sub(entry.created_at_ms) >= self.ttl.as_millis() as u64 {
+            self.store.remove(reference_id)?;
+            return Ok(None);
+        }
+        let witness = self.vault.open(&entry.witness, reference_id)?;
+        Ok((witness == *input).then_some(entry.proof))
+    }
+
+    pub fn put(&self, reference_id: &str, input: &SolvencyInput, proof: &str) -> Result<(), String> {
+        let entry = CachedProof {
+            proof: proof.to_string(),
+            witness: self.vault.seal(input, reference_id)?,
+            created_at_ms: now_ms(),
+        };
+        self.store.put(reference_id, &entry)
+    }
+
+    /// Re-wraps every witness with the current key-encryption key (run after
+    /// rotating it, before retiring the old key), returning how many changed
+    pub fn rotate_keys(&self) -> Result<usize, String> {
+        let mut rotated = 0;
+        for reference_id in self.store.keys()? {
+            let Some(mut entry) = self.store.get(&reference_id)? else {
+                continue;
+            };
+            if let Some(witness) = self.vault.rewrap(&entry.witness)? {
+                entry.witness = witness;
+                self.store.put(&reference_id, &entry)?;
+                rotated += 1;
+            }
+        }
+        Ok(rotated)
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zk::witness::LocalKeyStore;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_cache_reuses_matching_proof_and_rotates_keys() {
+        let keys = Arc::new(LocalKeyStore::new("kek-1", [1u8; 32]));
+        let store = Arc::new(InMemoryProofCacheStore::new());
+        let cache = ProofCache::new(store.clone(), WitnessVault::new(keys.clone()), Duration::from_secs(60));
+        let input = SolvencyInput::new("100", "25");
+
+        cache.put("ref_001", &input, "0xproof").unwrap();
+        let stored = serde_json::to_string(&store.get("ref_001").unwrap().unwrap()).unwrap();
+        assert!(!stored.contains("\"100\""));
+        assert_eq!(cache.get("ref_001", &input).unwrap().as_deref(), Some("0xproof"));
+        assert_eq!(cache.get("ref_001", &SolvencyInput::new("100", "30")).unwrap(), None);
+        assert_eq!(cache.get("ref_002", &input).unwrap(), None);
+
+        keys.rotate("kek-2", [2u8; 32]);
+        assert_eq!(cache.rotate_keys().unwrap(), 1);
+        keys.retire("kek-1");
+        assert_eq!(cache.get("ref_001", &input).unwrap().as_deref(), Some("0xproof"));
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped() {
+        let vault = WitnessVault::new(Arc::new(LocalKeyStore::new("kek-1", [1u8; 32])));
+        let store = Arc::new(InMemoryProofCacheStore::new());
+        let cache = ProofCache::new(store.clone(), vault, Duration::ZERO);
+        let input = SolvencyInput::new("100", "25");
+        cache.put("ref_001", &input, "0xproof").unwrap();
+        assert_eq!(cache.get("ref_001", &input).unwrap(), None);
+        assert!(store.keys().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+//! Trusted-setup provenance of circuit parameters.
+//!
+//! A proving key is only as trustworthy as the ceremony that produced it.
+//! The ceremony publishes a transcript listing each contribution's input and
+//! output parameter hashes, and the hash of that transcript is announced
+//! out of band. `verify_parameters` accepts a proving key only when three
+//! things hold: the transcript is one of the announced ones, its
+//! contributions form an unbroken chain, and the key is the chain's final
+//! output.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// One participant's contribution to a ceremony
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contribution {
+    pub participant: String,
+    /// Hex SHA-256 of the parameters the participant started from
+    pub input_hash: String,
+    /// Hex SHA-256 of the parameters the participant produced
+    pub output_hash: String,
+}
+
+/// Published record of a trusted-setup ceremony
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CeremonyTranscript {
+    pub circuit: String,
+    pub contributions: Vec<Contribution>,
+}
+
+/// Why circuit parameters were rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterError {
+    /// The transcript's hash is not among the trusted ones
+    UntrustedTranscript { hash: String },
+    /// The transcript cannot be parsed or lists no contributions
+    InvalidTranscript(String),
+    /// A contribution did not start from its predecessor's output
+    BrokenChain { participant: String },
+    /// The proving key is not the ceremony's final output
+    KeyMismatch { expected: String, actual: String },
+    /// A parameter file could not be read
+    Unreadable(String),
+}
+
+impl std::fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterError::UntrustedTranscript { hash } => {
+                write!(f, "ceremony transcript {} is not among the trusted transcripts", hash)
+            }
+            ParameterError::InvalidTranscript(e) => write!(f, "invalid ceremony transcript: {}", e),
+            ParameterError::BrokenChain { participant } => {
+                write!(f, "contribution of {} does not build on the previous contribution", participant)
+            }
+            ParameterError::KeyMismatch { expected, actual } => write!(
+                f,
+                "proving key hash {} does not match the ceremony output {}",
+                actual, expected
+            ),
+            ParameterError::Unreadable(e) => write!(f, "cannot read circuit parameters: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// Checks `proving_key` against the ceremony `transcript` (raw JSON), whose
+/// hex SHA-256 must be one of `trusted_transcripts`
+pub fn verify_parameters(
+    proving_key: &[u8],
+    transcript: &[u8],
+    trusted_transcripts: &[String],
+) -> Result<CeremonyTranscript, ParameterError> {
+    let hash = sha256_hex(transcript);
+    if !trusted_transcripts.iter().any(|t| t.trim_start_matches("0x").eq_ignore_ascii_case(&hash)) {
+        return Err(ParameterError::UntrustedTranscript { hash });
+    }
+    let transcript: CeremonyTranscript =
+        serde_json::from_slice(transcript).map_err(|e| ParameterError::InvalidTranscript(e.to_string()))?;
+    let last = transcript
+        .contributions
+        .last()
+        .ok_or_else(|| ParameterError::InvalidTranscript("no contributions".to_string()))?;
+    for pair in transcript.contributions.windows(2) {
+        if !pair[1].input_hash.eq_ignore_ascii_case(&pair[0].output_hash) {
+            return Err(ParameterError::BrokenChain {
+                participant: pair[1].participant.clone(),
+            });
+        }
+    }
+    let actual = sha256_hex(proving_key);
+    if !last.output_hash.eq_ignore_ascii_case(&actual) {
+        return Err(ParameterError::KeyMismatch {
+            expected: last.output_hash.to_lowercase(),
+            actual,
+        });
+    }
+    Ok(transcript)
+}
+
+/// `verify_parameters` on files
+pub fn verify_parameter_files(
+    proving_key: &Path,
+    transcript: &Path,
+    trusted_transcripts: &[String],
+) -> Result<CeremonyTranscript, ParameterError> {
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|e| ParameterError::Unreadable(format!("{}: {}", path.display(), e)))
+    };
+    verify_parameters(&read(proving_key)?, &read(transcript)?, trusted_transcripts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(key: &[u8]) -> Vec<u8> {
+        let transcript = CeremonyTranscript {
+            circuit: "spend".to_string(),
+            contributions: vec![
+                Contribution {
+                    participant: "alice".to_string(),
+                    input_hash: sha256_hex(b"powers of tau"),
+                    output_hash: sha256_hex(b"round 1"),
+                },
+                Contribution {
+                    participant: "bob".to_string(),
+                    input_hash: sha256_hex(b"round 1"),
+                    output_hash: sha256_hex(key),
+                },
+            ],
+        };
+        serde_json::to_vec(&transcript).unwrap()
+    }
+
+    #[test]
+    fn test_verify_parameters() {
+        let published = transcript(b"proving key");
+        let trusted = vec![format!("0x{}", sha256_hex(&published).to_uppercase())];
+        assert_eq!(verify_parameters(b"proving key", &published, &trusted).unwrap().contributions.len(), 2);
+
+        let err = verify_parameters(b"other key", &published, &trusted).unwrap_err();
+        assert!(matches!(err, ParameterError::KeyMismatch { .. }));
+        assert!(err.to_string().contains("does not match the ceremony output"));
+
+        let forged = transcript(b"other key");
+        assert!(matches!(
+            verify_parameters(b"other key", &forged, &trusted),
+            Err(ParameterError::UntrustedTranscript { .. })
+        ));
+    }
+
+    #[test]
+    fn test_broken_chain_is_rejected() {
+        let mut transcript: CeremonyTranscript = serde_json::from_slice(&transcript(b"key")).unwrap();
+        transcript.contributions[1].input_hash = sha256_hex(b"skipped alice");
+        let published = serde_json::to_vec(&transcript).unwrap();
+        let trusted = vec![sha256_hex(&published)];
+        assert_eq!(
+            verify_parameters(b"key", &published, &trusted).unwrap_err(),
+            ParameterError::BrokenChain {
+                participant: "bob".to_string()
+            }
+        );
+    }
+}
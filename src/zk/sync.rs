@@ -0,0 +1,653 @@
+//! Light sync of the shielded pool's note-commitment tree.
+//!
+//! Spending a shielded note needs a Merkle path from its commitment to a
+//! recent root of the pool's append-only commitment tree. `ShieldedSync`
+//! pulls commitments from a `CommitmentSource` in batches and keeps only
+//! what a wallet needs: the tree's frontier (one node per level) and an
+//! incrementally updated witness for each tracked (owned) note. After every
+//! batch it writes a checkpoint to a `SyncCheckpointStore`, so an
+//! interrupted sync resumes where it stopped.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use crate::crypto::hash::HashFunction;
+use crate::types::ChainId;
+
+/// Depth of the pool's commitment tree
+pub const TREE_DEPTH: usize = 32;
+
+type Hash = [u8; 32];
+
+/// Roots of empty subtrees, by height
+fn empty_roots(hash: HashFunction) -> Vec<Hash> {
+    let mut roots = vec![[0u8; 32]];
+    for height in 0..TREE_DEPTH {
+        let next = hash.hash_pair(&roots[height], &roots[height]);
+        roots.push(next);
+    }
+    roots
+}
+
+fn parse_commitment(commitment: &str) -> Result<Hash, String> {
+    let bytes = hex::decode(commitment.strip_prefix("0x").unwrap_or(commitment))
+        .map_err(|e| format!("invalid commitment {}: {}", commitment, e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("commitment {} must be 32 bytes", commitment))
+}
+
+fn encode(hash: &Hash) -> String {
+    format!("0x{}", hex::encode(hash))
+}
+
+/// Rightmost path of an append-only tree of `depth` levels: the left
+/// sibling of every level of the next leaf
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Frontier {
+    size: u64,
+    branch: Vec<Hash>,
+}
+
+impl Frontier {
+    fn new(depth: usize) -> Self {
+        Self {
+            size: 0,
+            branch: vec![[0u8; 32]; depth],
+        }
+    }
+
+    /// Appends a leaf, returning the root once it completes the tree
+    fn append(&mut self, leaf: Hash, hash: HashFunction) -> Option<Hash> {
+        let mut node = leaf;
+        let mut size = self.size;
+        self.size += 1;
+        for height in 0..self.branch.len() {
+            if size & 1 == 0 {
+                self.branch[height] = node;
+                return None;
+            }
+            node = hash.hash_pair(&self.branch[height], &node);
+            size >>= 1;
+        }
+        Some(node)
+    }
+
+    fn is_full(&self) -> bool {
+        self.size == 1u64 << self.branch.len()
+    }
+
+    fn root(&self, empty: &[Hash], hash: HashFunction) -> Hash {
+        let mut node = empty[0];
+        let mut size = self.size;
+        for (height, left) in self.branch.iter().enumerate() {
+            node = if size & 1 == 1 {
+                hash.hash_pair(left, &node)
+            } else {
+                hash.hash_pair(&node, &empty[height])
+            };
+            size >>= 1;
+        }
+        node
+    }
+}
+
+/// Merkle path of a tracked note, kept up to date as the tree grows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IncrementalWitness {
+    position: u64,
+    /// Sibling per level; `None` for right siblings still being filled
+    siblings: Vec<Option<Hash>>,
+    /// Partially filled subtree of the lowest missing right sibling
+    cursor: Option<Frontier>,
+}
+
+impl IncrementalWitness {
+    /// Witness of the leaf about to be appended at `frontier.size`
+    fn new(frontier: &Frontier) -> Self {
+        let position = frontier.size;
+        let siblings = (0..TREE_DEPTH)
+            .map(|height| (position >> height & 1 == 1).then_some(frontier.branch[height]))
+            .collect();
+        Self {
+            position,
+            siblings,
+            cursor: None,
+        }
+    }
+
+    fn next_missing(&self) -> Option<usize> {
+        self.siblings.iter().position(Option::is_none)
+    }
+
+    /// Adds a leaf appended after this witness's leaf
+    fn append(&mut self, leaf: Hash, hash: HashFunction) {
+        let Some(height) = self.next_missing() else {
+            return;
+        };
+        let filled = if height == 0 {
+            Some(leaf)
+        } else {
+            self.cursor.get_or_insert_with(|| Frontier::new(height)).append(leaf, hash)
+        };
+        if let Some(root) = filled {
+            self.siblings[height] = Some(root);
+            self.cursor = None;
+        }
+    }
+
+    fn path(&self, empty: &[Hash], hash: HashFunction) -> Vec<Hash> {
+        let current = self.next_missing();
+        self.siblings
+            .iter()
+            .enumerate()
+            .map(|(height, sibling)| match sibling {
+                Some(sibling) => *sibling,
+                None if Some(height) == current => self.cursor.as_ref().map_or(empty[height], |c| c.root(empty, hash)),
+                None => empty[height],
+            })
+            .collect()
+    }
+}
+
+/// Merkle path from a note commitment to the tree root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteWitness {
+    pub commitment: String,
+    /// Leaf index of the commitment
+    pub position: u64,
+    /// Hex-encoded siblings, leaf level first
+    pub path: Vec<String>,
+    /// Root the path leads to
+    pub root: String,
+    /// Hash function of the tree
+    #[serde(default)]
+    pub hash: HashFunction,
+}
+
+impl NoteWitness {
+    /// Recomputes the root from the commitment and path
+    pub fn verify(&self) -> Result<bool, String> {
+        let mut node = parse_commitment(&self.commitment)?;
+        for (height, sibling) in self.path.iter().enumerate() {
+            let sibling = parse_commitment(sibling)?;
+            node = if self.position >> height & 1 == 1 {
+                self.hash.hash_pair(&sibling, &node)
+            } else {
+                self.hash.hash_pair(&node, &sibling)
+            };
+        }
+        Ok(encode(&node) == self.root)
+    }
+}
+
+/// Commitments appended to the tree, in tree order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitmentBatch {
+    /// Leaf index of the first commitment
+    pub start: u64,
+    pub commitments: Vec<String>,
+    /// Number of commitments in the tree at the source's tip
+    pub tip: u64,
+    /// Block height the batch was read at
+    pub block_height: u64,
+    /// Tree root after the batch, checked when given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+}
+
+/// API or chain endpoint serving the pool's commitment tree updates
+#[async_trait::async_trait]
+pub trait CommitmentSource: Send + Sync {
+    /// Returns up to `limit` commitments starting at leaf index `start`
+    async fn commitments(&self, chain: ChainId, start: u64, limit: usize) -> Result<CommitmentBatch, String>;
+}
+
+/// In-memory commitment source for development/testing
+#[derive(Default)]
+pub struct InMemoryCommitmentSource {
+    commitments: Mutex<HashMap<ChainId, Vec<String>>>,
+}
+
+impl InMemoryCommitmentSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a commitment to the tree of `chain`
+    pub fn push(&self, chain: ChainId, commitment: impl Into<String>) {
+        if let Ok(mut commitments) = self.commitments.lock() {
+            commitments.entry(chain).or_default().push(commitment.into());
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CommitmentSource for InMemoryCommitmentSource {
+    async fn commitments(&self, chain: ChainId, start: u64, limit: usize) -> Result<CommitmentBatch, String> {
+        let commitments = self.commitments.lock().map_err(|_| "commitment source poisoned".to_string())?;
+        let all = commitments.get(&chain).map(Vec::as_slice).unwrap_or_default();
+        let from = (start as usize).min(all.len());
+        let to = from.saturating_add(limit).min(all.len());
+        Ok(CommitmentBatch {
+            start,
+            commitments: all[from..to].to_vec(),
+            tip: all.len() as u64,
+            block_height: all.len() as u64,
+            root: None,
+        })
+    }
+}
+
+/// Saved sync state, from which a sync resumes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncCheckpoint {
+    pub chain: ChainId,
+    pub block_height: u64,
+    /// Hash function the tree was built with
+    #[serde(default)]
+    pub hash: HashFunction,
+    frontier: Frontier,
+    witnesses: BTreeMap<String, IncrementalWitness>,
+    /// Tracked commitments not yet seen in the tree
+    pending: BTreeSet<String>,
+}
+
+impl SyncCheckpoint {
+    fn new(chain: ChainId) -> Self {
+        Self {
+            chain,
+            block_height: 0,
+            hash: HashFunction::Sha256,
+            frontier: Frontier::new(TREE_DEPTH),
+            witnesses: BTreeMap::new(),
+            pending: BTreeSet::new(),
+        }
+    }
+
+    /// Number of commitments synced
+    pub fn synced(&self) -> u64 {
+        self.frontier.size
+    }
+}
+
+/// Persistence for sync checkpoints, one per chain
+pub trait SyncCheckpointStore: Send + Sync {
+    fn load(&self, chain: ChainId) -> Result<Option<SyncCheckpoint>, String>;
+
+    fn save(&self, checkpoint: &SyncCheckpoint) -> Result<(), String>;
+}
+
+/// In-memory checkpoint store (per process)
+#[derive(Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<ChainId, SyncCheckpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SyncCheckpointStore for InMemoryCheckpointStore {
+    fn load(&self, chain: ChainId) -> Result<Option<SyncCheckpoint>, String> {
+        let checkpoints = self.checkpoints.lock().map_err(|_| "checkpoint store poisoned".to_string())?;
+        Ok(checkpoints.get(&chain).cloned())
+    }
+
+    fn save(&self, checkpoint: &SyncCheckpoint) -> Result<(), String> {
+        let mut checkpoints = self.checkpoints.lock().map_err(|_| "checkpoint store poisoned".to_string())?;
+        checkpoints.insert(checkpoint.chain, checkpoint.clone());
+        Ok(())
+    }
+}
+
+/// Checkpoint store keeping one JSON file per chain in a directory,
+/// replaced (via a temporary file and rename) on every save
+pub struct FileCheckpointStore {
+    dir: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, chain: ChainId) -> PathBuf {
+        self.dir.join(format!("shielded-sync-{}.json", chain))
+    }
+}
+
+impl SyncCheckpointStore for FileCheckpointStore {
+    fn load(&self, chain: ChainId) -> Result<Option<SyncCheckpoint>, String> {
+        let path = self.path(chain);
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| format!("corrupt checkpoint {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save(&self, checkpoint: &SyncCheckpoint) -> Result<(), String> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("failed to create {}: {}", self.dir.display(), e))?;
+        let path = self.path(checkpoint.chain);
+        let json = serde_json::to_string(checkpoint).map_err(|e| e.to_string())?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(|e| format!("failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &path).map_err(|e| format!("failed to replace {}: {}", path.display(), e))
+    }
+}
+
+/// How far a sync has come
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SyncProgress {
+    /// Commitments synced
+    pub synced: u64,
+    /// Commitments in the tree at the source's tip when last asked
+    pub tip: u64,
+    pub block_height: u64,
+}
+
+impl SyncProgress {
+    /// Share of the tree synced, from 0.0 to 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.tip == 0 {
+            1.0
+        } else {
+            (self.synced as f64 / self.tip as f64).min(1.0)
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.synced >= self.tip
+    }
+}
+
+struct SyncState {
+    checkpoint: SyncCheckpoint,
+    tip: u64,
+}
+
+/// Incremental, resumable sync of one chain's commitment tree
+///
+/// # Example
+/// ```
+/// # tokio_test::block_on(async {
+/// use std::sync::Arc;
+/// use ecash_sdk_core::types::ChainId;
+/// use ecash_sdk_core::zk::notes::create_note;
+/// use ecash_sdk_core::zk::sync::{InMemoryCheckpointStore, InMemoryCommitmentSource, ShieldedSync};
+///
+/// let source = Arc::new(InMemoryCommitmentSource::new());
+/// let sync = ShieldedSync::open(ChainId::Base, source.clone(), Arc::new(InMemoryCheckpointStore::new())).unwrap();
+/// let note = create_note("USDC", 5_000_000, "0xowner");
+/// sync.track(&note.commitment).unwrap();
+/// source.push(ChainId::Base, note.commitment.clone());
+///
+/// assert!(sync.sync().await.unwrap().is_complete());
+/// assert!(sync.witness(&note.commitment).unwrap().verify().unwrap());
+/// # });
+/// ```
+pub struct ShieldedSync {
+    chain: ChainId,
+    source: Arc<dyn CommitmentSource>,
+    store: Arc<dyn SyncCheckpointStore>,
+    batch_size: usize,
+    empty: Vec<Hash>,
+    state: Mutex<SyncState>,
+}
+
+impl ShieldedSync {
+    /// Opens the sync of `chain`, resuming from the stored checkpoint if any
+    pub fn open(
+        chain: ChainId,
+        source: Arc<dyn CommitmentSource>,
+        store: Arc<dyn SyncCheckpointStore>,
+    ) -> Result<Self, String> {
+        let checkpoint = store.load(chain)?.unwrap_or_else(|| SyncCheckpoint::new(chain));
+        let tip = checkpoint.synced();
+        Ok(Self {
+            chain,
+            source,
+            store,
+            batch_size: 1_000,
+            empty: empty_roots(checkpoint.hash),
+            state: Mutex::new(SyncState { checkpoint, tip }),
+        })
+    }
+
+    /// Builds the tree with `hash` (default SHA-256; see
+    /// `HashConfig::commitments`). Fails if the stored checkpoint was synced
+    /// with a different hash function.
+    pub fn with_hash(mut self, hash: HashFunction) -> Result<Self, String> {
+        let state = self.state.get_mut().map_err(|_| "shielded sync poisoned".to_string())?;
+        if state.checkpoint.hash != hash {
+            if state.checkpoint.synced() > 0 {
+                return Err(format!(
+                    "checkpoint of {} was synced with {}, not {}",
+                    self.chain, state.checkpoint.hash, hash
+                ));
+            }
+            state.checkpoint.hash = hash;
+            self.empty = empty_roots(hash);
+        }
+        Ok(self)
+    }
+
+    /// Commitments fetched per request (default 1000)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, SyncState>, String> {
+        self.state.lock().map_err(|_| "shielded sync poisoned".to_string())
+    }
+
+    /// Maintains a witness for the note with `commitment` once the sync
+    /// reaches it. Track owned notes before syncing past them.
+    pub fn track(&self, commitment: &str) -> Result<(), String> {
+        parse_commitment(commitment)?;
+        let mut state = self.lock()?;
+        if !state.checkpoint.witnesses.contains_key(commitment) {
+            state.checkpoint.pending.insert(commitment.to_string());
+        }
+        Ok(())
+    }
+
+    /// Stops maintaining the witness of a (spent) note
+    pub fn untrack(&self, commitment: &str) -> Result<(), String> {
+        let mut state = self.lock()?;
+        state.checkpoint.witnesses.remove(commitment);
+        state.checkpoint.pending.remove(commitment);
+        self.store.save(&state.checkpoint)
+    }
+
+    /// Fetches and applies one batch, then checkpoints
+    pub async fn sync_batch(&self) -> Result<SyncProgress, String> {
+        let start = self.lock()?.checkpoint.synced();
+        let batch = self.source.commitments(self.chain, start, self.batch_size).await?;
+        if batch.start != start {
+            return Err(format!("source returned commitments from {}, expected {}", batch.start, start));
+        }
+        let leaves = batch
+            .commitments
+            .iter()
+            .map(|c| parse_commitment(c))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut state = self.lock()?;
+        if state.checkpoint.synced() != start {
+            return Err("sync advanced concurrently; retry".to_string());
+        }
+        let mut checkpoint = state.checkpoint.clone();
+        let hash = checkpoint.hash;
+        for (commitment, leaf) in batch.commitments.iter().zip(leaves) {
+            for witness in checkpoint.witnesses.values_mut() {
+                witness.append(leaf, hash);
+            }
+            if checkpoint.pending.remove(commitment) {
+                checkpoint
+                    .witnesses
+                    .insert(commitment.clone(), IncrementalWitness::new(&checkpoint.frontier));
+            }
+            if checkpoint.frontier.is_full() {
+                return Err("commitment tree is full".to_string());
+            }
+            checkpoint.frontier.append(leaf, hash);
+        }
+        if let Some(ref expected) = batch.root {
+            let root = encode(&checkpoint.frontier.root(&self.empty, hash));
+            if !root.eq_ignore_ascii_case(expected) {
+                return Err(format!("tree root mismatch after {} commitments: {} != {}", checkpoint.synced(), root, expected));
+            }
+        }
+        checkpoint.block_height = checkpoint.block_height.max(batch.block_height);
+        self.store.save(&checkpoint)?;
+        state.checkpoint = checkpoint;
+        state.tip = batch.tip.max(state.checkpoint.synced());
+        Ok(progress_of(&state))
+    }
+
+    /// Syncs batches until the source's tip is reached
+    pub async fn sync(&self) -> Result<SyncProgress, String> {
+        loop {
+            let before = self.lock()?.checkpoint.synced();
+            let progress = self.sync_batch().await?;
+            if progress.is_complete() || progress.synced == before {
+                return Ok(progress);
+            }
+        }
+    }
+
+    pub fn progress(&self) -> SyncProgress {
+        self.lock().map(|state| progress_of(&state)).unwrap_or(SyncProgress {
+            synced: 0,
+            tip: 0,
+            block_height: 0,
+        })
+    }
+
+    /// Current root of the synced tree
+    pub fn root(&self) -> Result<String, String> {
+        let state = self.lock()?;
+        Ok(encode(&state.checkpoint.frontier.root(&self.empty, state.checkpoint.hash)))
+    }
+
+    /// Witness of a tracked note against the current root; `None` until the
+    /// sync has reached the note
+    pub fn witness(&self, commitment: &str) -> Option<NoteWitness> {
+        let state = self.lock().ok()?;
+        let witness = state.checkpoint.witnesses.get(commitment)?;
+        Some(NoteWitness {
+            commitment: commitment.to_string(),
+            position: witness.position,
+            path: witness.path(&self.empty, state.checkpoint.hash).iter().map(encode).collect(),
+            root: encode(&state.checkpoint.frontier.root(&self.empty, state.checkpoint.hash)),
+            hash: state.checkpoint.hash,
+        })
+    }
+}
+
+fn progress_of(state: &SyncState) -> SyncProgress {
+    SyncProgress {
+        synced: state.checkpoint.synced(),
+        tip: state.tip,
+        block_height: state.checkpoint.block_height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zk::notes::create_note;
+
+    fn commitment(i: u64) -> String {
+        encode(&HashFunction::Sha256.hash(&i.to_be_bytes()))
+    }
+
+    #[tokio::test]
+    async fn test_witnesses_track_root_as_tree_grows() {
+        let source = Arc::new(InMemoryCommitmentSource::new());
+        let sync = ShieldedSync::open(ChainId::Base, source.clone(), Arc::new(InMemoryCheckpointStore::new()))
+            .unwrap()
+            .with_hash(HashFunction::Blake2b256)
+            .unwrap()
+            .with_batch_size(3);
+        let owned: Vec<String> = [0, 5, 6, 13].iter().map(|i| commitment(*i)).collect();
+        for c in &owned {
+            sync.track(c).unwrap();
+        }
+
+        for i in 0..20 {
+            source.push(ChainId::Base, commitment(i));
+            let progress = sync.sync().await.unwrap();
+            assert_eq!((progress.synced, progress.fraction()), (i + 1, 1.0));
+            let root = sync.root().unwrap();
+            for c in owned.iter().filter(|c| sync.witness(c).is_some()) {
+                let witness = sync.witness(c).unwrap();
+                assert_eq!(witness.root, root);
+                assert!(witness.verify().unwrap(), "witness of {} at size {}", witness.position, i + 1);
+            }
+        }
+        assert_eq!(sync.witness(&owned[2]).unwrap().position, 6);
+        assert!(sync.witness(&commitment(1)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sync_resumes_from_checkpoint() {
+        let dir = std::env::temp_dir().join(format!("ecash-sync-{}", uuid::Uuid::new_v4()));
+        let store = Arc::new(FileCheckpointStore::new(&dir));
+        let source = Arc::new(InMemoryCommitmentSource::new());
+        let note = create_note("USDC", 100, "0xowner");
+        for i in 0..4 {
+            source.push(ChainId::Base, commitment(i));
+        }
+        source.push(ChainId::Base, note.commitment.clone());
+        for i in 5..10 {
+            source.push(ChainId::Base, commitment(i));
+        }
+
+        let first = ShieldedSync::open(ChainId::Base, source.clone(), store.clone()).unwrap().with_batch_size(4);
+        first.track(&note.commitment).unwrap();
+        let progress = first.sync_batch().await.unwrap();
+        assert_eq!((progress.synced, progress.tip, progress.is_complete()), (4, 10, false));
+
+        let rehashed = ShieldedSync::open(ChainId::Base, source.clone(), store.clone()).unwrap();
+        assert!(rehashed.with_hash(HashFunction::Keccak256).is_err());
+        let resumed = ShieldedSync::open(ChainId::Base, source.clone(), store).unwrap();
+        assert_eq!(resumed.progress().synced, 4);
+        assert!(resumed.sync().await.unwrap().is_complete());
+        let witness = resumed.witness(&note.commitment).unwrap();
+        assert_eq!(witness.position, 4);
+        assert!(witness.verify().unwrap());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_root_mismatch_is_not_checkpointed() {
+        struct Lying;
+
+        #[async_trait::async_trait]
+        impl CommitmentSource for Lying {
+            async fn commitments(&self, _chain: ChainId, start: u64, _limit: usize) -> Result<CommitmentBatch, String> {
+                Ok(CommitmentBatch {
+                    start,
+                    commitments: vec![commitment(start)],
+                    tip: 1,
+                    block_height: 1,
+                    root: Some(commitment(99)),
+                })
+            }
+        }
+
+        let sync = ShieldedSync::open(ChainId::Base, Arc::new(Lying), Arc::new(InMemoryCheckpointStore::new())).unwrap();
+        assert!(sync.sync_batch().await.unwrap_err().contains("root mismatch"));
+        assert_eq!(sync.progress().synced, 0);
+    }
+}
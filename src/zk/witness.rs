@@ -0,0 +1,198 @@
+//! Envelope encryption of proof witnesses at rest.
+//!
+//! A witness (the balance a solvency proof is generated from) reveals exactly
+//! what the proof hides, so it is never stored in the clear. Each witness is
+//! encrypted under a fresh data key. That data key is in turn wrapped by a
+//! key-encryption key held in a keystore or KMS (`KeyEncryptionKeys`), and
+//! only the wrapped form is stored next to the ciphertext. Rotating the
+//! key-encryption key re-wraps data keys without decrypting any witness.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::crypto;
+use crate::zk::SolvencyInput;
+
+/// Keystore or KMS holding the key-encryption keys. Implementations backed by
+/// a KMS forward `wrap` and `unwrap` to it, so the keys never leave it.
+pub trait KeyEncryptionKeys: Send + Sync {
+    /// ID of the key new data keys are wrapped with
+    fn current_key_id(&self) -> Result<String, String>;
+
+    fn wrap(&self, key_id: &str, data_key: &[u8]) -> Result<Vec<u8>, String>;
+
+    fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, String>;
+}
+
+/// Key-encryption keys held in process memory. Retired keys stay available
+/// for unwrapping until every witness has been re-wrapped.
+#[derive(Default)]
+pub struct LocalKeyStore {
+    keys: Mutex<LocalKeys>,
+}
+
+#[derive(Default)]
+struct LocalKeys {
+    current: Option<String>,
+    keys: HashMap<String, Zeroizing<[u8; 32]>>,
+}
+
+impl LocalKeyStore {
+    /// Keystore whose current key is `key`
+    pub fn new(key_id: impl Into<String>, key: [u8; 32]) -> Self {
+        let store = Self::default();
+        store.rotate(key_id, key);
+        store
+    }
+
+    /// Makes `key` the current key; previous keys remain usable for unwrapping
+    pub fn rotate(&self, key_id: impl Into<String>, key: [u8; 32]) {
+        let key_id = key_id.into();
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        keys.keys.insert(key_id.clone(), Zeroizing::new(key));
+        keys.current = Some(key_id);
+    }
+
+    /// Removes a retired key; witnesses still wrapped with it become unreadable
+    pub fn retire(&self, key_id: &str) {
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        if keys.current.as_deref() != Some(key_id) {
+            keys.keys.remove(key_id);
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, LocalKeys>, String> {
+        self.keys.lock().map_err(|_| "key store poisoned".to_string())
+    }
+
+    fn key(&self, key_id: &str) -> Result<Zeroizing<[u8; 32]>, String> {
+        self.lock()?
+            .keys
+            .get(key_id)
+            .cloned()
+            .ok_or_else(|| format!("unknown key-encryption key {}", key_id))
+    }
+}
+
+impl KeyEncryptionKeys for LocalKeyStore {
+    fn current_key_id(&self) -> Result<String, String> {
+        self.lock()?.current.clone().ok_or_else(|| "key store has no keys".to_string())
+    }
+
+    fn wrap(&self, key_id: &str, data_key: &[u8]) -> Result<Vec<u8>, String> {
+        crypto::seal_symmetric(self.key(key_id)?.as_slice(), data_key, key_id.as_bytes())
+    }
+
+    fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, String> {
+        crypto::open_symmetric(self.key(key_id)?.as_slice(), wrapped, key_id.as_bytes()).map(Zeroizing::new)
+    }
+}
+
+/// Encrypted witness, safe to persist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SealedWitness {
+    /// Key-encryption key the data key is wrapped with
+    pub key_id: String,
+    /// Hex-encoded wrapped data key
+    pub wrapped_key: String,
+    /// Hex-encoded witness ciphertext
+    pub ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WitnessPlaintext {
+    balance: String,
+    required: String,
+}
+
+/// Seals and opens witnesses with envelope encryption
+#[derive(Clone)]
+pub struct WitnessVault {
+    keys: std::sync::Arc<dyn KeyEncryptionKeys>,
+}
+
+impl WitnessVault {
+    pub fn new(keys: std::sync::Arc<dyn KeyEncryptionKeys>) -> Self {
+        Self { keys }
+    }
+
+    /// Encrypts `input` for storage under `context` (e.g. the reference ID it
+    /// is stored for), which must be given again to open it
+    pub fn seal(&self, input: &SolvencyInput, context: &str) -> Result<SealedWitness, String> {
+        let mut data_key = Zeroizing::new([0u8; 32]);
+        rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, data_key.as_mut_slice());
+        let plaintext = Zeroizing::new(
+            serde_json::to_vec(&WitnessPlaintext {
+                balance: input.balance.clone(),
+                required: input.required.clone(),
+            })
+            .map_err(|e| format!("failed to encode witness: {}", e))?,
+        );
+        let ciphertext = crypto::seal_symmetric(data_key.as_slice(), &plaintext, context.as_bytes())?;
+        let key_id = self.keys.current_key_id()?;
+        let wrapped = self.keys.wrap(&key_id, data_key.as_slice())?;
+        Ok(SealedWitness {
+            key_id,
+            wrapped_key: hex::encode(wrapped),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    pub fn open(&self, sealed: &SealedWitness, context: &str) -> Result<SolvencyInput, String> {
+        let data_key = self.unwrap_data_key(sealed)?;
+        let ciphertext = hex::decode(&sealed.ciphertext).map_err(|e| format!("invalid witness ciphertext: {}", e))?;
+        let plaintext = Zeroizing::new(crypto::open_symmetric(&data_key, &ciphertext, context.as_bytes())?);
+        let witness: WitnessPlaintext =
+            serde_json::from_slice(&plaintext).map_err(|e| format!("invalid witness: {}", e))?;
+        Ok(SolvencyInput::new(witness.balance, witness.required))
+    }
+
+    /// Re-wraps the data key with the current key-encryption key, leaving the
+    /// ciphertext untouched. `None` when it is already current.
+    pub fn rewrap(&self, sealed: &SealedWitness) -> Result<Option<SealedWitness>, String> {
+        let key_id = self.keys.current_key_id()?;
+        if sealed.key_id == key_id {
+            return Ok(None);
+        }
+        let data_key = self.unwrap_data_key(sealed)?;
+        Ok(Some(SealedWitness {
+            wrapped_key: hex::encode(self.keys.wrap(&key_id, &data_key)?),
+            key_id,
+            ciphertext: sealed.ciphertext.clone(),
+        }))
+    }
+
+    fn unwrap_data_key(&self, sealed: &SealedWitness) -> Result<Zeroizing<Vec<u8>>, String> {
+        let wrapped = hex::decode(&sealed.wrapped_key).map_err(|e| format!("invalid wrapped key: {}", e))?;
+        self.keys.unwrap(&sealed.key_id, &wrapped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_witness_round_trip_and_rotation() {
+        let keys = Arc::new(LocalKeyStore::new("kek-1", [1u8; 32]));
+        let vault = WitnessVault::new(keys.clone());
+        let input = SolvencyInput::new("1000", "250.5");
+
+        let sealed = vault.seal(&input, "ref_001").unwrap();
+        assert!(!sealed.ciphertext.contains(&hex::encode("1000")));
+        assert_eq!(vault.open(&sealed, "ref_001").unwrap(), input);
+        assert!(vault.open(&sealed, "ref_002").is_err());
+        assert_eq!(vault.rewrap(&sealed).unwrap(), None);
+
+        keys.rotate("kek-2", [2u8; 32]);
+        let rewrapped = vault.rewrap(&sealed).unwrap().unwrap();
+        assert_eq!((rewrapped.key_id.as_str(), &rewrapped.ciphertext), ("kek-2", &sealed.ciphertext));
+        keys.retire("kek-1");
+        assert!(vault.open(&sealed, "ref_001").unwrap_err().contains("unknown key-encryption key"));
+        assert_eq!(vault.open(&rewrapped, "ref_001").unwrap(), input);
+    }
+}